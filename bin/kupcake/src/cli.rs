@@ -3,13 +3,15 @@ use clap_complete::engine::ArgValueCandidates;
 
 use crate::completions::{AllDevnetCompleter, RunningDevnetCompleter};
 use kupcake_deploy::{
-    ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG, GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG,
-    KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG, OP_BATCHER_DEFAULT_IMAGE,
-    OP_BATCHER_DEFAULT_TAG, OP_CHALLENGER_DEFAULT_IMAGE, OP_CHALLENGER_DEFAULT_TAG,
-    OP_CONDUCTOR_DEFAULT_IMAGE, OP_CONDUCTOR_DEFAULT_TAG, OP_DEPLOYER_DEFAULT_IMAGE,
-    OP_DEPLOYER_DEFAULT_TAG, OP_PROPOSER_DEFAULT_IMAGE, OP_PROPOSER_DEFAULT_TAG,
+    ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG, BLOCKSCOUT_DEFAULT_IMAGE, BLOCKSCOUT_DEFAULT_TAG,
+    GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG, KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG,
+    LOKI_DEFAULT_IMAGE, LOKI_DEFAULT_TAG, OP_BATCHER_DEFAULT_IMAGE, OP_BATCHER_DEFAULT_TAG,
+    OP_CHALLENGER_DEFAULT_IMAGE, OP_CHALLENGER_DEFAULT_TAG, OP_CONDUCTOR_DEFAULT_IMAGE,
+    OP_CONDUCTOR_DEFAULT_TAG, OP_DEPLOYER_DEFAULT_IMAGE, OP_DEPLOYER_DEFAULT_TAG,
+    OP_NODE_DEFAULT_IMAGE, OP_NODE_DEFAULT_TAG, OP_PROPOSER_DEFAULT_IMAGE, OP_PROPOSER_DEFAULT_TAG,
     OP_RBUILDER_DEFAULT_IMAGE, OP_RBUILDER_DEFAULT_TAG, OP_RETH_DEFAULT_IMAGE, OP_RETH_DEFAULT_TAG,
-    PROMETHEUS_DEFAULT_IMAGE, PROMETHEUS_DEFAULT_TAG,
+    PROMETHEUS_DEFAULT_IMAGE, PROMETHEUS_DEFAULT_TAG, PROMTAIL_DEFAULT_IMAGE, PROMTAIL_DEFAULT_TAG,
+    faucet::EthAmount,
 };
 use tracing::level_filters::LevelFilter;
 
@@ -49,6 +51,15 @@ impl L1Source {
             L1Source::Custom(url) => url.clone(),
         }
     }
+
+    /// Returns the chain ID of a known chain, None for a custom RPC URL.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            L1Source::Sepolia => Some(11155111),
+            L1Source::Mainnet => Some(1),
+            L1Source::Custom(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
@@ -92,6 +103,108 @@ impl From<DeploymentTargetArg> for kupcake_deploy::DeploymentTarget {
     }
 }
 
+/// CLI-facing network name collision policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum NameCollisionArg {
+    /// Refuse to deploy (default).
+    Error,
+    /// Warn and deploy under the name anyway.
+    Warn,
+    /// Deploy under the first free `<network>-<n>` name.
+    Suffix,
+}
+
+impl From<NameCollisionArg> for kupcake_deploy::NameCollision {
+    fn from(arg: NameCollisionArg) -> Self {
+        match arg {
+            NameCollisionArg::Error => kupcake_deploy::NameCollision::Error,
+            NameCollisionArg::Warn => kupcake_deploy::NameCollision::Warn,
+            NameCollisionArg::Suffix => kupcake_deploy::NameCollision::Suffix,
+        }
+    }
+}
+
+/// CLI-facing network topology preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum TopologyArg {
+    /// 1 sequencer, no validators.
+    Solo,
+    /// 3 sequencers with op-conductor and 2 validators.
+    HaSequencer,
+    /// 1 sequencer and 10 validators.
+    LargeFleet,
+}
+
+impl From<TopologyArg> for kupcake_deploy::Topology {
+    fn from(arg: TopologyArg) -> Self {
+        match arg {
+            TopologyArg::Solo => kupcake_deploy::Topology::Solo,
+            TopologyArg::HaSequencer => kupcake_deploy::Topology::HaSequencer,
+            TopologyArg::LargeFleet => kupcake_deploy::Topology::LargeFleet,
+        }
+    }
+}
+
+/// CLI-facing consensus client argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum ConsensusClientArg {
+    /// kona-node (default).
+    Kona,
+    /// Go op-node.
+    OpNode,
+}
+
+impl From<ConsensusClientArg> for kupcake_deploy::ConsensusClient {
+    fn from(arg: ConsensusClientArg) -> Self {
+        match arg {
+            ConsensusClientArg::Kona => kupcake_deploy::ConsensusClient::Kona,
+            ConsensusClientArg::OpNode => kupcake_deploy::ConsensusClient::OpNode,
+        }
+    }
+}
+
+/// CLI-facing op-batcher data-availability type argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum DaTypeArg {
+    /// EIP-4844 blobs (default).
+    Blobs,
+    /// Calldata, for an L1 without blob support.
+    Calldata,
+    /// Whichever is cheaper on L1.
+    Auto,
+}
+
+impl From<DaTypeArg> for kupcake_deploy::DataAvailabilityType {
+    fn from(arg: DaTypeArg) -> Self {
+        match arg {
+            DaTypeArg::Blobs => kupcake_deploy::DataAvailabilityType::Blobs,
+            DaTypeArg::Calldata => kupcake_deploy::DataAvailabilityType::Calldata,
+            DaTypeArg::Auto => kupcake_deploy::DataAvailabilityType::Auto,
+        }
+    }
+}
+
+/// CLI-facing op-reth pruning mode argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum PruningArg {
+    /// Keep the state at every block (default).
+    Archive,
+    /// reth full node: recent state history only.
+    Full,
+    /// Full node without the transaction hash index.
+    MinimalPruned,
+}
+
+impl From<PruningArg> for kupcake_deploy::PruningMode {
+    fn from(arg: PruningArg) -> Self {
+        match arg {
+            PruningArg::Archive => kupcake_deploy::PruningMode::Archive,
+            PruningArg::Full => kupcake_deploy::PruningMode::Full,
+            PruningArg::MinimalPruned => kupcake_deploy::PruningMode::MinimalPruned,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, strum::EnumString)]
 #[strum(serialize_all = "kebab-case")]
 pub enum OutData {
@@ -100,6 +213,16 @@ pub enum OutData {
     Path(String),
 }
 
+/// Output format of kupcake's own logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable text (default).
+    #[default]
+    Text,
+    /// One JSON object per line, for log pipelines.
+    Json,
+}
+
 #[derive(Parser)]
 #[command(name = "kupcake")]
 #[command(
@@ -112,10 +235,29 @@ pub struct Cli {
     #[arg(short, long, env = "KUP_VERBOSITY", default_value_t = LevelFilter::INFO, global = true)]
     pub verbosity: LevelFilter,
 
+    /// Only log warnings and errors (caps --verbosity at `warn`).
+    #[arg(short, long, env = "KUP_QUIET", global = true)]
+    pub quiet: bool,
+
+    /// The log output format.
+    #[arg(long, env = "KUP_LOG_FORMAT", value_enum, default_value_t = LogFormat::Text, global = true)]
+    pub log_format: LogFormat,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+impl Cli {
+    /// The effective log level, taking `--quiet` into account.
+    pub fn log_level(&self) -> LevelFilter {
+        if self.quiet {
+            self.verbosity.min(LevelFilter::WARN)
+        } else {
+            self.verbosity
+        }
+    }
+}
+
 #[derive(Subcommand)]
 #[allow(clippy::large_enum_variant)]
 pub enum Commands {
@@ -128,12 +270,45 @@ pub enum Commands {
     /// then removes the associated Docker network (<prefix>-network).
     Cleanup(CleanupArgs),
 
+    /// Stop the containers of a network without removing them or their data.
+    ///
+    /// Stops all containers whose names start with the network name and keeps the
+    /// containers, the Docker network and the output directory. Redeploy with
+    /// `kupcake --config <outdata>/Kupcake.toml` to resume from the saved state.
+    Stop(StopArgs),
+
+    /// Replace a single service of a running network.
+    ///
+    /// Removes the container of op-batcher, op-proposer, op-challenger or an L2 node and
+    /// starts it again from the saved Kupcake.toml, e.g. after editing its flags. The rest
+    /// of the network keeps running.
+    Restart(RestartArgs),
+
     /// Send ETH to an L2 address via the OptimismPortal deposit mechanism.
     ///
     /// Bridges ETH from the L1 (Anvil) deployer account to a specified L2 address
     /// by calling depositTransaction on the OptimismPortalProxy contract.
     Faucet(FaucetArgs),
 
+    /// Withdraw ETH from L2 to L1 through the L2ToL1MessagePasser and the OptimismPortal.
+    ///
+    /// A withdrawal is initiated on L2, proven on L1 against a dispute game of
+    /// op-proposer, then finalized on L1 once the challenge period has passed.
+    Withdraw(WithdrawArgs),
+
+    /// Make a JSON-RPC call to a service of a deployed network.
+    ///
+    /// Resolves the service's host RPC URL from the network config and prints
+    /// the JSON result. Parameters that are valid JSON are sent as-is, anything
+    /// else is sent as a string.
+    Rpc(RpcArgs),
+
+    /// Show the container logs of a service of a deployed network.
+    ///
+    /// Fetches logs through the Docker API, optionally limited to recent lines
+    /// (--since, --tail) and filtered by substring (--grep).
+    Logs(LogsArgs),
+
     /// Generate continuous L2 traffic using Flashbots Contender.
     ///
     /// Runs a Contender Docker container against a deployed L2 network,
@@ -158,12 +333,32 @@ pub enum Commands {
     /// Shows container states, host URLs, block heights, sync status, and timestamps.
     /// Use --verbose for extended details (gas price, peer count, L1 origin).
     /// Use --json for machine-readable JSON output.
-    #[command(alias = "health", alias = "status")]
     Inspect(InspectArgs),
 
+    /// Check that a deployed network is healthy.
+    ///
+    /// Checks that every container runs, chain IDs match and blocks are produced.
+    /// Exits with an error if the network is unhealthy.
+    /// Use --json for machine-readable JSON output, --watch for a live view.
+    Health(HealthArgs),
+
+    /// Manage the Grafana instance of a running network.
+    ///
+    /// Imports dashboards through the Grafana API, so that they show up
+    /// without restarting the monitoring stack.
+    Grafana(GrafanaArgs),
+
     /// List all tracked devnets.
     List,
 
+    /// Show the kupcake networks that have containers in Docker.
+    ///
+    /// Groups the containers by network, with each service's state and published ports,
+    /// and the chain IDs from the network's Kupcake.toml when it is found.
+    /// With a network name, same as `inspect <NETWORK>`.
+    /// Use --json for machine-readable JSON output.
+    Status(StatusArgs),
+
     /// Remove all stopped devnets and their data directories.
     Prune(PruneArgs),
 
@@ -173,6 +368,20 @@ pub enum Commands {
     /// reth database into a .tar.gz archive compatible with --snapshot restore.
     Snapshot(SnapshotArgs),
 
+    /// Export the chain configuration of a deployed network.
+    ///
+    /// Copies rollup.json, genesis.json, the JWT secrets, Kupcake.toml and the
+    /// contract addresses to a directory, for external tools to use.
+    Export(ExportArgs),
+
+    /// Diagnose common deployment failures.
+    ///
+    /// Checks that Docker is reachable and recent enough, the images are present or
+    /// pullable, the data directory has free space, no container name or fixed host
+    /// port conflicts, and the L1 RPC (when forking) answers with the right chain ID.
+    /// Exits with an error if any check fails.
+    Doctor(DoctorArgs),
+
     /// Generate shell completion scripts.
     ///
     /// Prints the shell snippet needed to enable dynamic completions.
@@ -180,6 +389,43 @@ pub enum Commands {
     Completions(CompletionsArgs),
 }
 
+/// Arguments for the doctor command.
+#[derive(Parser)]
+pub struct DoctorArgs {
+    /// Network name or path to Kupcake.toml / outdata directory to diagnose.
+    ///
+    /// Without it, checks the requirements of a new deployment with the default
+    /// images, writing to the current directory.
+    #[arg(add = ArgValueCandidates::new(AllDevnetCompleter))]
+    pub config: Option<String>,
+
+    /// L1 source a new deployment would fork (chain name or RPC URL).
+    ///
+    /// Ignored when diagnosing a saved deployment, which records its own L1.
+    #[arg(long, env = "KUP_L1")]
+    pub l1: Option<L1Source>,
+
+    /// Output as JSON instead of human-readable text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for the status command.
+#[derive(Parser)]
+pub struct StatusArgs {
+    /// Inspect this network instead of listing all of them (see `inspect`).
+    #[arg(add = ArgValueCandidates::new(RunningDevnetCompleter))]
+    pub network: Option<String>,
+
+    /// Only list containers whose name starts with this prefix.
+    #[arg(long, default_value = kupcake_deploy::status::DEFAULT_NETWORK_PREFIX)]
+    pub prefix: String,
+
+    /// Output as JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+}
+
 /// Arguments for the prune command.
 #[derive(Parser)]
 pub struct PruneArgs {
@@ -204,6 +450,22 @@ pub struct SnapshotArgs {
     pub output: Option<std::path::PathBuf>,
 }
 
+/// Arguments for the export command.
+#[derive(Parser)]
+pub struct ExportArgs {
+    /// Network name or path to Kupcake.toml / outdata directory.
+    /// If a network name is given (e.g. "kup-nutty-songs"), loads
+    /// the config from the default path: ./data-<name>/Kupcake.toml
+    /// Otherwise treats the argument as a file/directory path.
+    #[arg(required = true, add = ArgValueCandidates::new(AllDevnetCompleter))]
+    pub config: String,
+
+    /// Directory to export the files to.
+    /// Defaults to ./<network-name>-chain-config in the current directory.
+    #[arg(long, short)]
+    pub output: Option<std::path::PathBuf>,
+}
+
 /// Shell type for completion script generation.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum ShellArg {
@@ -277,6 +539,35 @@ pub enum NodeAction {
     },
 }
 
+/// Arguments for the grafana command.
+#[derive(Parser)]
+pub struct GrafanaArgs {
+    /// Network name or path to Kupcake.toml / outdata directory.
+    ///
+    /// If a network name is given (e.g. "kup-nutty-songs"), loads
+    /// the config from the default path: ./data-<name>/Kupcake.toml
+    /// Otherwise treats the argument as a file/directory path.
+    #[arg(required = true, add = ArgValueCandidates::new(RunningDevnetCompleter))]
+    pub config: String,
+
+    /// The Grafana action to perform.
+    #[command(subcommand)]
+    pub action: GrafanaAction,
+}
+
+/// Grafana actions.
+#[derive(Subcommand)]
+pub enum GrafanaAction {
+    /// Create or update a dashboard from a JSON file.
+    ///
+    /// Accepts a dashboard model or a `{"dashboard": ...}` export. A dashboard
+    /// with the same UID is overwritten.
+    Import {
+        /// Path to the dashboard JSON file.
+        file: std::path::PathBuf,
+    },
+}
+
 /// Arguments for the inspect command.
 #[derive(Parser)]
 pub struct InspectArgs {
@@ -301,6 +592,30 @@ pub struct InspectArgs {
     pub service: Option<String>,
 }
 
+/// Arguments for the health command.
+#[derive(Parser)]
+pub struct HealthArgs {
+    /// The network name; its config is loaded from ./data-<name>/Kupcake.toml.
+    /// A path to Kupcake.toml or an outdata directory is accepted too.
+    #[arg(long, short, visible_alias = "name", add = ArgValueCandidates::new(RunningDevnetCompleter))]
+    pub network: String,
+
+    /// Output as JSON instead of human-readable text.
+    ///
+    /// With --watch, prints one JSON report per line.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Re-run the check every --interval seconds until Ctrl+C, then print how long
+    /// the network stayed healthy.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Seconds between two checks in --watch mode.
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..), requires = "watch")]
+    pub interval: u64,
+}
+
 /// Arguments for the bench command.
 #[derive(Parser)]
 pub struct BenchArgs {
@@ -364,17 +679,178 @@ pub struct FaucetArgs {
     #[arg(required = true, add = ArgValueCandidates::new(RunningDevnetCompleter))]
     pub config: String,
 
-    /// L2 address to receive the ETH (0x-prefixed, 40 hex chars).
+    /// L2 address to receive the ETH or tokens (0x-prefixed, 40 hex chars).
     #[arg(long)]
     pub to: String,
 
-    /// Amount of ETH to send.
-    #[arg(long, default_value_t = 1.0)]
-    pub amount: f64,
+    /// L1 address of an ERC-20 token to bridge instead of ETH, via the L1StandardBridge.
+    ///
+    /// The tokens are sent from the deployer account (Anvil account 0), which must hold them.
+    #[arg(long, value_name = "L1_ADDRESS", requires_all = ["l2_token", "token_amount"])]
+    pub token: Option<String>,
+
+    /// L2 address of the bridged token (an OptimismMintableERC20 whose remote token is `--token`).
+    #[arg(long, value_name = "L2_ADDRESS", requires = "token")]
+    pub l2_token: Option<String>,
+
+    /// Amount of tokens to bridge, in the token's smallest unit.
+    #[arg(long, value_name = "UNITS", requires = "token")]
+    pub token_amount: Option<u128>,
+
+    /// Amount of ETH to send, as a decimal (exact down to 1 wei, 18 decimal places).
+    #[arg(long, default_value = "1", conflicts_with = "token")]
+    pub amount: EthAmount,
 
     /// Wait for the deposit to appear on L2 before returning.
     #[arg(long)]
     pub wait: bool,
+
+    /// Timeout in seconds for each waiting phase (L1 confirmations, L2 arrival).
+    #[arg(long, value_name = "SECS", default_value_t = kupcake_deploy::faucet::DEFAULT_WAIT_TIMEOUT_SECS)]
+    pub wait_timeout: u64,
+
+    /// Number of L1 confirmations to wait for before considering the deposit sent.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub l1_confirmations: u64,
+}
+
+/// Arguments for the withdraw command.
+#[derive(Parser)]
+pub struct WithdrawArgs {
+    /// Network name or path to Kupcake.toml / outdata directory.
+    ///
+    /// If a network name is given (e.g. "kup-nutty-songs"), loads
+    /// the config from the default path: ./data-<name>/Kupcake.toml
+    /// Otherwise treats the argument as a file/directory path.
+    #[arg(required = true, add = ArgValueCandidates::new(RunningDevnetCompleter))]
+    pub config: String,
+
+    /// The withdrawal step to perform.
+    #[command(subcommand)]
+    pub action: WithdrawAction,
+}
+
+/// Withdrawal steps.
+#[derive(Subcommand)]
+pub enum WithdrawAction {
+    /// Send a withdrawal transaction on L2 and print the withdrawal hash.
+    ///
+    /// The ETH is withdrawn to the same address on L1.
+    Initiate {
+        /// Private key of the L2 account to withdraw from (hex, with or without 0x).
+        #[arg(long, env = "KUP_WITHDRAW_KEY", hide_env_values = true)]
+        key: String,
+
+        /// Amount of ETH to withdraw, as a decimal (exact down to 1 wei, 18 decimal places).
+        #[arg(long, default_value = "1")]
+        amount: EthAmount,
+
+        /// Wait until op-proposer has created a dispute game covering the withdrawal.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// Prove a withdrawal on L1 against the newest dispute game covering it.
+    Prove {
+        /// Withdrawal hash printed by `initiate`.
+        withdrawal_hash: String,
+    },
+
+    /// Finalize a proven withdrawal on L1, releasing the ETH.
+    Finalize {
+        /// Withdrawal hash printed by `initiate`.
+        withdrawal_hash: String,
+
+        /// Fast-forward Anvil's clock past the dispute game clock and the withdrawal
+        /// delays (evm_increaseTime), resolving the dispute game if needed.
+        #[arg(long)]
+        skip_challenge_period: bool,
+    },
+}
+
+/// Arguments for the rpc command.
+#[derive(Parser)]
+pub struct RpcArgs {
+    /// Network name or path to Kupcake.toml / outdata directory.
+    ///
+    /// If a network name is given (e.g. "kup-nutty-songs"), loads
+    /// the config from the default path: ./data-<name>/Kupcake.toml
+    /// Otherwise treats the argument as a file/directory path.
+    #[arg(required = true, add = ArgValueCandidates::new(RunningDevnetCompleter))]
+    pub config: String,
+
+    /// Target service (anvil, op-reth, kona-node, op-batcher, op-proposer, op-challenger, conductor).
+    pub service: String,
+
+    /// JSON-RPC method (e.g. "eth_blockNumber", "optimism_syncStatus").
+    pub method: String,
+
+    /// Method parameters (e.g. "latest", "true", '{"to":"0x..."}').
+    pub params: Vec<String>,
+
+    /// Node identifier for op-reth, kona-node and conductor (e.g. "sequencer", "validator-1").
+    #[arg(long, default_value = "sequencer")]
+    pub node: String,
+}
+
+/// Arguments for the logs command.
+#[derive(Parser)]
+pub struct LogsArgs {
+    /// Network name or path to Kupcake.toml / outdata directory.
+    ///
+    /// If a network name is given (e.g. "kup-nutty-songs"), loads
+    /// the config from the default path: ./data-<name>/Kupcake.toml
+    /// Otherwise treats the argument as a file/directory path.
+    ///
+    /// Omitted when the network is given with `--network`: the only positional
+    /// argument is then the service.
+    #[arg(value_name = "CONFIG", required_unless_present = "network", add = ArgValueCandidates::new(AllDevnetCompleter))]
+    pub config: Option<String>,
+
+    /// Service (anvil, op-reth, kona-node, op-batcher, op-proposer, op-challenger,
+    /// conductor, prometheus, grafana, loki, promtail, blockscout) or a full container name. Per-node services
+    /// accept a node suffix, e.g. "op-reth-validator-1".
+    #[arg(value_name = "SERVICE")]
+    pub service: Option<String>,
+
+    /// Network name, as an alternative to the CONFIG argument.
+    #[arg(long, short, visible_alias = "name", add = ArgValueCandidates::new(AllDevnetCompleter))]
+    pub network: Option<String>,
+
+    /// Node identifier for op-reth, kona-node and conductor (e.g. "sequencer", "validator-1").
+    #[arg(long, default_value = "sequencer")]
+    pub node: String,
+
+    /// Only show logs newer than a relative time (e.g. 30s, 10m, 2h, 1d).
+    #[arg(long, value_parser = kupcake_deploy::logs::parse_since)]
+    pub since: Option<std::time::Duration>,
+
+    /// Only show the last N lines (applied before --grep).
+    #[arg(long)]
+    pub tail: Option<u64>,
+
+    /// Keep streaming new lines until the container stops.
+    #[arg(short, long)]
+    pub follow: bool,
+
+    /// Only show lines containing this substring.
+    #[arg(long)]
+    pub grep: Option<String>,
+}
+
+impl LogsArgs {
+    /// The config (network name or path) and the service, from either
+    /// `<CONFIG> <SERVICE>` or `--network <NAME> <SERVICE>`.
+    pub fn config_and_service(&self) -> anyhow::Result<(&str, &str)> {
+        match (&self.network, &self.config, &self.service) {
+            (None, Some(config), Some(service)) => Ok((config, service)),
+            (Some(network), Some(service), None) => Ok((network, service)),
+            (Some(_), Some(_), Some(_)) => {
+                anyhow::bail!("Give either --network or the CONFIG argument, not both")
+            }
+            _ => anyhow::bail!("Missing the SERVICE argument"),
+        }
+    }
 }
 
 /// Arguments for the spam command.
@@ -389,8 +865,16 @@ pub struct SpamArgs {
     pub config: String,
 
     /// Scenario to run: built-in name (transfers, erc20, uni_v2) or path to a custom TOML file.
-    #[arg(long, default_value = "transfers")]
-    pub scenario: String,
+    ///
+    /// Defaults to transfers unless --scenario-file is given.
+    #[arg(long, conflicts_with = "scenario_file")]
+    pub scenario: Option<String>,
+
+    /// Custom contender scenario TOML file to run instead of a built-in scenario.
+    ///
+    /// Mounted read-only into the Contender container at /data/scenario.toml.
+    #[arg(long, value_name = "PATH")]
+    pub scenario_file: Option<std::path::PathBuf>,
 
     /// Transactions per second.
     #[arg(long, default_value_t = 10)]
@@ -421,9 +905,16 @@ pub struct SpamArgs {
     pub funder_account_index: usize,
 
     /// Generate a report after completion.
+    ///
+    /// Also writes a JSON summary of the run (TPS achieved, transactions, gas used,
+    /// failures) to <outdata>/contender/report.json.
     #[arg(long)]
     pub report: bool,
 
+    /// Write the JSON summary of the run to this path instead.
+    #[arg(long, value_name = "PATH")]
+    pub report_file: Option<std::path::PathBuf>,
+
     /// Docker image for Contender.
     #[arg(long, env = "KUP_CONTENDER_IMAGE", default_value = kupcake_deploy::spam::CONTENDER_DEFAULT_IMAGE)]
     pub contender_image: String,
@@ -463,8 +954,14 @@ impl SpamArgs {
             .op_reth
             .docker_rpc_url();
 
+        let scenario = match self.scenario_file {
+            Some(_) => String::new(),
+            None => self.scenario.unwrap_or_else(|| "transfers".to_string()),
+        };
+
         Ok(kupcake_deploy::spam::SpamConfig {
-            scenario: self.scenario,
+            scenario,
+            scenario_file: self.scenario_file,
             tps: self.tps,
             duration: self.duration,
             forever: self.forever,
@@ -491,6 +988,30 @@ pub struct CleanupArgs {
     /// and the network <prefix>-network will be removed.
     #[arg(required = true, add = ArgValueCandidates::new(AllDevnetCompleter))]
     pub prefix: String,
+
+    /// List the containers and network that would be removed, without removing them.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the stop command.
+#[derive(Parser)]
+pub struct StopArgs {
+    /// The network name, used as the container name prefix.
+    #[arg(long, short, visible_alias = "name", add = ArgValueCandidates::new(AllDevnetCompleter))]
+    pub network: String,
+}
+
+/// Arguments for the restart command.
+#[derive(Parser)]
+pub struct RestartArgs {
+    /// The network name; its config is loaded from ./data-<name>/Kupcake.toml.
+    #[arg(long, short, visible_alias = "name", add = ArgValueCandidates::new(RunningDevnetCompleter))]
+    pub network: String,
+
+    /// Service to replace (op-batcher, op-proposer, op-challenger, op-reth, kona-node, ...),
+    /// or its full container name.
+    pub service: String,
 }
 
 /// Arguments for the deploy command.
@@ -508,6 +1029,25 @@ pub struct DeployArgs {
     )]
     pub network: Option<String>,
 
+    /// What to do when existing containers already use the network name as a prefix.
+    ///
+    /// `kupcake cleanup` removes every container starting with the network name, so a
+    /// colliding deployment could be clobbered by (or clobber) another one.
+    ///
+    /// - "error" (default): refuse to deploy.
+    /// - "warn": log a warning and deploy anyway.
+    /// - "suffix": deploy under the first free `<network>-<n>` name.
+    ///
+    /// Generated network names are always suffixed.
+    #[arg(
+        long,
+        env = "KUP_NAME_COLLISION",
+        default_value = "error",
+        value_enum,
+        help_heading = "Network Configuration"
+    )]
+    pub name_collision: NameCollisionArg,
+
     /// The L1 chain source - either a chain name or RPC URL.
     ///
     /// Accepts:
@@ -524,50 +1064,267 @@ pub struct DeployArgs {
     )]
     pub l1: Option<L1Source>,
 
-    /// The L2 chain info (chain ID or name).
-    /// If not provided, the L2 chain id will be generated randomly.
+    /// RPC URL of an L1 node that is already running, used instead of starting Anvil.
+    ///
+    /// Must be reachable from the containers (not localhost). The L1 chain ID is
+    /// detected via eth_chainId. Requires --external-l1-deployer-key; the accounts must
+    /// be funded on the L1.
     #[arg(
         long,
-        alias = "l2",
-        env = "KUP_L2_CHAIN",
+        value_name = "URL",
+        env = "KUP_EXTERNAL_L1",
+        conflicts_with = "l1",
+        requires = "external_l1_deployer_key",
         help_heading = "Network Configuration"
     )]
-    pub l2_chain: Option<L2Chain>,
+    pub external_l1: Option<String>,
 
-    /// The block time in seconds for the L1 chain (Anvil) and L2 derivation.
-    ///
-    /// Defaults to 4 seconds to make the initial deployment faster.
+    /// WebSocket URL of the external L1, followed by the consensus clients and op-batcher.
     #[arg(
         long,
-        env = "KUP_BLOCK_TIME",
-        default_value_t = 4,
+        value_name = "URL",
+        env = "KUP_EXTERNAL_L1_WS",
+        requires = "external_l1",
         help_heading = "Network Configuration"
     )]
-    pub block_time: u64,
+    pub external_l1_ws: Option<String>,
 
-    /// Manually specify the L2 genesis timestamp (Unix timestamp in seconds).
-    ///
-    /// When forking from L1, the genesis timestamp is automatically calculated
-    /// as: latest_block_timestamp - (block_time * block_number)
-    /// This option overrides that calculation and sets an explicit genesis timestamp.
-    ///
-    /// Use this when you need a specific genesis timestamp for testing or alignment.
+    /// Private key deploying the L1 contracts on the external L1. Also backs every role
+    /// without a dedicated key. Hex, or `env:<NAME>` / `file:<PATH>` to keep it out of
+    /// Kupcake.toml.
     #[arg(
         long,
-        env = "KUP_GENESIS_TIMESTAMP",
+        value_name = "KEY",
+        env = "KUP_EXTERNAL_L1_DEPLOYER_KEY",
+        hide_env_values = true,
+        requires = "external_l1",
         help_heading = "Network Configuration"
     )]
-    pub genesis_timestamp: Option<u64>,
+    pub external_l1_deployer_key: Option<String>,
 
-    // ── L2 Nodes ──
-    /// The total number of L2 nodes to deploy.
-    ///
-    /// This is the sum of sequencers and validators.
-    /// Defaults to 5 (2 sequencers + 3 validators).
+    /// Private key of op-batcher on the external L1 [default: the deployer key].
     #[arg(
         long,
-        alias = "nodes",
-        env = "KUP_L2_NODES",
+        value_name = "KEY",
+        env = "KUP_EXTERNAL_L1_BATCHER_KEY",
+        hide_env_values = true,
+        requires = "external_l1",
+        help_heading = "Network Configuration"
+    )]
+    pub external_l1_batcher_key: Option<String>,
+
+    /// Private key of op-proposer on the external L1 [default: the deployer key].
+    #[arg(
+        long,
+        value_name = "KEY",
+        env = "KUP_EXTERNAL_L1_PROPOSER_KEY",
+        hide_env_values = true,
+        requires = "external_l1",
+        help_heading = "Network Configuration"
+    )]
+    pub external_l1_proposer_key: Option<String>,
+
+    /// Private key of op-challenger on the external L1 [default: the deployer key].
+    #[arg(
+        long,
+        value_name = "KEY",
+        env = "KUP_EXTERNAL_L1_CHALLENGER_KEY",
+        hide_env_values = true,
+        requires = "external_l1",
+        help_heading = "Network Configuration"
+    )]
+    pub external_l1_challenger_key: Option<String>,
+
+    /// The L2 chain info (chain ID or name).
+    /// If not provided, the L2 chain id will be generated randomly.
+    #[arg(
+        long,
+        alias = "l2",
+        env = "KUP_L2_CHAIN",
+        help_heading = "Network Configuration"
+    )]
+    pub l2_chain: Option<L2Chain>,
+
+    /// The block time in seconds for the L1 chain (Anvil) and L2 derivation.
+    ///
+    /// Defaults to 4 seconds to make the initial deployment faster.
+    #[arg(
+        long,
+        env = "KUP_BLOCK_TIME",
+        default_value_t = 4,
+        help_heading = "Network Configuration"
+    )]
+    pub block_time: u64,
+
+    /// L2 block time in seconds, independent of the L1 block time.
+    ///
+    /// Must divide `--block-time`. Defaults to the op-deployer default (2 seconds).
+    #[arg(
+        long,
+        env = "KUP_L2_BLOCK_TIME",
+        help_heading = "Network Configuration"
+    )]
+    pub l2_block_time: Option<u64>,
+
+    /// Address receiving the L2 sequencer fees (sequencer fee vault recipient).
+    ///
+    /// Baked into the L2 genesis; changing it redeploys the contracts. Defaults to an
+    /// Anvil account.
+    #[arg(
+        long,
+        env = "KUP_SEQUENCER_FEE_RECIPIENT",
+        help_heading = "Network Configuration"
+    )]
+    pub sequencer_fee_recipient: Option<String>,
+
+    /// L2 block gas limit, applied from the genesis block (e.g. 60000000 for load tests).
+    ///
+    /// Between 21000000 and 500000000. Defaults to the op-deployer default (60M).
+    #[arg(long, env = "KUP_L2_GAS_LIMIT", help_heading = "Network Configuration")]
+    pub l2_gas_limit: Option<u64>,
+
+    /// EIP-1559 base fee change denominator of the L2. Defaults to 250.
+    #[arg(
+        long,
+        env = "KUP_EIP1559_DENOMINATOR",
+        help_heading = "Network Configuration"
+    )]
+    pub eip1559_denominator: Option<u64>,
+
+    /// EIP-1559 elasticity multiplier of the L2. Defaults to 6.
+    #[arg(
+        long,
+        env = "KUP_EIP1559_ELASTICITY",
+        help_heading = "Network Configuration"
+    )]
+    pub eip1559_elasticity: Option<u64>,
+
+    /// op-deployer artifacts of the L1 contracts (`l1ContractsLocator` in the intent).
+    ///
+    /// A contracts release (`tag://op-contracts/v3.0.0`), a local build
+    /// (`file:///path/to/forge-artifacts`, mounted into op-deployer) or an
+    /// `http(s)://` tarball. Defaults to the op-deployer default.
+    #[arg(
+        long,
+        env = "KUP_L1_CONTRACTS_LOCATOR",
+        help_heading = "Network Configuration"
+    )]
+    pub l1_contracts_locator: Option<String>,
+
+    /// op-deployer artifacts of the L2 contracts (`l2ContractsLocator` in the intent).
+    ///
+    /// Same format as `--l1-contracts-locator`.
+    #[arg(
+        long,
+        env = "KUP_L2_CONTRACTS_LOCATOR",
+        help_heading = "Network Configuration"
+    )]
+    pub l2_contracts_locator: Option<String>,
+
+    /// L1 EVM hardfork for Anvil (e.g. cancun, prague).
+    ///
+    /// Defaults to the latest hardfork supported by Anvil.
+    #[arg(long, env = "KUP_L1_HARDFORK", help_heading = "Network Configuration")]
+    pub l1_hardfork: Option<String>,
+
+    /// Mnemonic Anvil generates its accounts from, which sets the batcher, proposer,
+    /// challenger and other role addresses.
+    ///
+    /// Defaults to Anvil's well-known test mnemonic.
+    #[arg(
+        long,
+        env = "KUP_ANVIL_MNEMONIC",
+        help_heading = "Network Configuration"
+    )]
+    pub anvil_mnemonic: Option<String>,
+
+    /// HD derivation path of the Anvil accounts, without the account index.
+    ///
+    /// Defaults to m/44'/60'/0'/0/.
+    #[arg(
+        long,
+        env = "KUP_ANVIL_DERIVATION_PATH",
+        help_heading = "Network Configuration"
+    )]
+    pub anvil_derivation_path: Option<String>,
+
+    /// Manually specify the L2 genesis timestamp (Unix timestamp in seconds).
+    ///
+    /// When forking from L1, the genesis timestamp is automatically calculated
+    /// as: latest_block_timestamp - (block_time * block_number)
+    /// This option overrides that calculation and sets an explicit genesis timestamp.
+    ///
+    /// Use this when you need a specific genesis timestamp for testing or alignment.
+    #[arg(
+        long,
+        env = "KUP_GENESIS_TIMESTAMP",
+        help_heading = "Network Configuration"
+    )]
+    pub genesis_timestamp: Option<u64>,
+
+    /// Fund an L2 account in the genesis allocations, as ADDRESS=ETH (repeatable).
+    ///
+    /// The balance is visible at block 0 on every L2 node. Existing allocations such as
+    /// predeploys keep their code and storage; only their balance is replaced.
+    #[arg(
+        long,
+        value_name = "ADDRESS=ETH",
+        env = "KUP_PREFUND",
+        value_delimiter = ',',
+        value_parser = parse_prefund_entry,
+        help_heading = "Network Configuration"
+    )]
+    pub prefund: Vec<String>,
+
+    /// Activate an L2 hardfork after genesis, as FORK=SECONDS (repeatable).
+    ///
+    /// The fork activates SECONDS after the L2 genesis instead of at genesis, e.g.
+    /// `isthmus=120`. Later forks active at genesis are moved to the same time. Known forks:
+    /// regolith, canyon, delta, ecotone, fjord, granite, holocene, isthmus, jovian.
+    #[arg(
+        long = "hardfork-offset",
+        value_name = "FORK=SECONDS",
+        env = "KUP_HARDFORK_OFFSET",
+        value_delimiter = ',',
+        value_parser = parse_hardfork_offset_entry,
+        help_heading = "Network Configuration"
+    )]
+    pub hardfork_offsets: Vec<String>,
+
+    /// Deploy an additional L2 chain with this chain ID on the same L1 (repeatable).
+    ///
+    /// Each additional chain runs a single sequencer with its own op-batcher, op-proposer
+    /// and op-challenger, named <network>-<CHAIN_ID>-<service>, and keeps its data in
+    /// l2-stack-<CHAIN_ID>. Only applies to new deployments.
+    #[arg(
+        long = "add-l2-chain",
+        value_name = "CHAIN_ID",
+        env = "KUP_ADDITIONAL_L2_CHAINS",
+        value_delimiter = ',',
+        help_heading = "Network Configuration"
+    )]
+    pub additional_l2_chains: Vec<u64>,
+
+    // ── L2 Nodes ──
+    /// Start from a ready-made topology.
+    ///
+    /// - "solo": 1 sequencer, no validators, 2s L1 blocks.
+    /// - "ha-sequencer": 3 sequencers with op-conductor and 2 validators.
+    /// - "large-fleet": 1 sequencer and 10 validators, with --long-running style logging.
+    ///
+    /// Explicit --l2-nodes, --sequencer-count, --block-time and logging flags override
+    /// the preset.
+    #[arg(long, env = "KUP_PRESET", value_enum, help_heading = "L2 Nodes")]
+    pub preset: Option<TopologyArg>,
+
+    /// The total number of L2 nodes to deploy.
+    ///
+    /// This is the sum of sequencers and validators.
+    /// Defaults to 5 (2 sequencers + 3 validators).
+    #[arg(
+        long,
+        alias = "nodes",
+        env = "KUP_L2_NODES",
         default_value_t = 5,
         help_heading = "L2 Nodes"
     )]
@@ -610,6 +1367,34 @@ pub struct DeployArgs {
     )]
     pub proofs_validators: usize,
 
+    /// Which historical state the op-reth nodes keep.
+    ///
+    /// - "archive" (default): state at every block, e.g. `eth_getBalance` at old blocks.
+    /// - "full": reth's `--full`, state history of the last 10064 blocks only.
+    /// - "minimal-pruned": "full" without the transaction hash index.
+    ///
+    /// Validators with the historical proofs ExEx stay archive nodes.
+    #[arg(
+        long,
+        value_enum,
+        env = "KUP_OP_RETH_PRUNING",
+        help_heading = "L2 Nodes"
+    )]
+    pub op_reth_pruning: Option<PruningArg>,
+
+    /// Consensus client to run on every L2 node.
+    ///
+    /// - "kona" (default): kona-node.
+    /// - "op-node": the Go op-node, for comparison against kona-node.
+    #[arg(
+        long,
+        env = "KUP_CONSENSUS_CLIENT",
+        default_value = "kona",
+        value_enum,
+        help_heading = "L2 Nodes"
+    )]
+    pub consensus_client: ConsensusClientArg,
+
     // ── Deployment ──
     /// Deployment target for OP Stack contracts.
     ///
@@ -625,6 +1410,20 @@ pub struct DeployArgs {
     )]
     pub deployment_target: DeploymentTargetArg,
 
+    /// Make the deployment reproducible from a seed, e.g. for golden-file tests.
+    ///
+    /// Two runs with the same seed and options produce identical genesis.json, rollup.json
+    /// and contract addresses. Derives the L2 chain ID, network name, genesis timestamp and
+    /// node P2P keys from the seed (explicit options still win) and defaults to the genesis
+    /// deployment target.
+    #[arg(
+        long,
+        value_name = "SEED",
+        env = "KUP_DETERMINISTIC",
+        help_heading = "Deployment"
+    )]
+    pub deterministic: Option<u64>,
+
     /// Path to an existing kupconf.toml configuration file to load.
     ///
     /// When provided, the deployment will use the configuration from this file
@@ -642,6 +1441,39 @@ pub struct DeployArgs {
     )]
     pub redeploy: bool,
 
+    /// Make re-runs idempotent: if the network is already running and healthy, do
+    /// nothing. A partially running network is an error, unless --restart-unhealthy is set.
+    #[arg(
+        long,
+        env = "KUP_REUSE_IF_HEALTHY",
+        default_value_t = false,
+        conflicts_with = "redeploy",
+        help_heading = "Deployment"
+    )]
+    pub reuse_if_healthy: bool,
+
+    /// With --reuse-if-healthy, restart the stopped or failing containers of a partially
+    /// running network instead of failing.
+    #[arg(
+        long,
+        env = "KUP_RESTART_UNHEALTHY",
+        default_value_t = false,
+        requires = "reuse_if_healthy",
+        help_heading = "Deployment"
+    )]
+    pub restart_unhealthy: bool,
+
+    /// When a loaded config's data directory no longer exists but the config file sits
+    /// next to deployment data (it was moved), use the config file's directory as the
+    /// data directory and save the updated config.
+    #[arg(
+        long,
+        env = "KUP_REBASE_DATA_DIR",
+        default_value_t = false,
+        help_heading = "Deployment"
+    )]
+    pub rebase_data_dir: bool,
+
     /// Disable op-proposer deployment.
     ///
     /// When set, the op-proposer service will not be started as part of the L2 stack.
@@ -654,6 +1486,88 @@ pub struct DeployArgs {
     #[arg(long, env = "KUP_NO_CHALLENGER", help_heading = "Deployment")]
     pub no_challenger: bool,
 
+    /// Number of hot-spare op-batchers, started with --stopped.
+    ///
+    /// Each spare has its own funded account. Start one with the admin_startBatcher RPC.
+    #[arg(
+        long,
+        value_name = "COUNT",
+        env = "KUP_SPARE_BATCHERS",
+        help_heading = "Deployment"
+    )]
+    pub spare_batchers: Option<usize>,
+
+    /// How op-batcher posts the batch data to L1.
+    ///
+    /// - "blobs" (default): EIP-4844 blob transactions.
+    /// - "calldata": calldata transactions, for an L1 without a working blob pool.
+    /// - "auto": whichever is cheaper on L1.
+    ///
+    /// Ignored with --alt-da-server, which always posts calldata commitments.
+    #[arg(
+        long,
+        value_enum,
+        env = "KUP_BATCHER_DA_TYPE",
+        help_heading = "Deployment"
+    )]
+    pub batcher_da_type: Option<DaTypeArg>,
+
+    /// Number of hot-spare op-proposers, stopped right after startup.
+    ///
+    /// Each spare has its own funded account. Start one with the admin_startProposer RPC.
+    #[arg(
+        long,
+        value_name = "COUNT",
+        env = "KUP_SPARE_PROPOSERS",
+        help_heading = "Deployment"
+    )]
+    pub spare_proposers: Option<usize>,
+
+    /// Post batch data to the alt-DA server at this URL instead of L1.
+    ///
+    /// The server must be reachable from the containers. Requires --consensus-client op-node.
+    #[arg(
+        long,
+        value_name = "URL",
+        env = "KUP_ALT_DA_SERVER",
+        help_heading = "Deployment"
+    )]
+    pub alt_da_server: Option<String>,
+
+    /// Alt-DA challenge window, in L1 blocks [default: 16].
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        env = "KUP_ALT_DA_CHALLENGE_WINDOW",
+        requires = "alt_da_server",
+        help_heading = "Deployment"
+    )]
+    pub alt_da_challenge_window: Option<u64>,
+
+    /// Alt-DA resolve window, in L1 blocks [default: 16].
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        env = "KUP_ALT_DA_RESOLVE_WINDOW",
+        requires = "alt_da_server",
+        help_heading = "Deployment"
+    )]
+    pub alt_da_resolve_window: Option<u64>,
+
+    /// Continue the deployment when a non-critical service fails to start.
+    ///
+    /// A failure of op-proposer, op-challenger or the monitoring stack is logged and
+    /// the service skipped. Anvil and the L2 nodes still abort the deployment.
+    #[arg(long, env = "KUP_KEEP_GOING", help_heading = "Deployment")]
+    pub keep_going: bool,
+
+    /// Check the L1 and L2 chain IDs end-to-end once the network is up.
+    ///
+    /// Compares the IDs of the op-deployer intent, rollup.json, genesis.json, the L1 and
+    /// every op-reth with the configured ones, and fails with a report of the mismatches.
+    #[arg(long, env = "KUP_VERIFY_CHAIN_IDS", help_heading = "Deployment")]
+    pub verify_chain_ids: bool,
+
     // ── State & Storage ──
     /// The path to the output data directory.
     ///
@@ -749,62 +1663,304 @@ pub struct DeployArgs {
     #[arg(long, env = "KUP_PUBLISH_ALL_PORTS", help_heading = "Runtime Behavior")]
     pub publish_all_ports: bool,
 
-    // ── Logging & Monitoring ──
-    /// Docker log file max size (e.g., "10m"). Enables json-file log driver with rotation.
-    #[arg(long, env = "KUP_LOG_MAX_SIZE", help_heading = "Logging & Monitoring")]
-    pub log_max_size: Option<String>,
-
-    /// Max number of rotated Docker log files.
-    #[arg(long, env = "KUP_LOG_MAX_FILE", help_heading = "Logging & Monitoring")]
-    pub log_max_file: Option<String>,
-
-    /// Quiet verbose services: suppress anvil output, set services to INFO log level.
+    /// Publish the services on fixed host ports instead of random ones.
+    ///
+    /// Each published port is bound to the host port of the same number (e.g. 8545 for
+    /// Anvil, 9545 for the sequencer's op-reth), shifted by 100 for every additional L2
+    /// node, spare batcher or spare proposer. The deployment fails if a port is in use.
+    #[arg(long, env = "KUP_FIXED_PORTS", help_heading = "Runtime Behavior")]
+    pub fixed_ports: bool,
+
+    /// Docker restart policy of the service containers: no (default), on-failure,
+    /// on-failure:N or unless-stopped.
+    ///
+    /// Keeps a detached network alive across transient crashes. unless-stopped
+    /// containers also come back after a Docker daemon restart.
     #[arg(
         long,
-        env = "KUP_QUIET_SERVICES",
-        help_heading = "Logging & Monitoring"
+        value_name = "POLICY",
+        env = "KUP_RESTART_POLICY",
+        help_heading = "Runtime Behavior"
     )]
-    pub quiet_services: bool,
+    pub restart_policy: Option<String>,
 
-    /// Stream container logs to tracing::debug!() in background.
-    #[arg(long, env = "KUP_STREAM_LOGS", help_heading = "Logging & Monitoring")]
-    pub stream_logs: bool,
+    /// Shell script to run against the devnet once it is deployed.
+    ///
+    /// Runs in a Foundry container on the deployment network, from the script's
+    /// directory, with L1_RPC, L2_RPC, L2_WS, ROLLUP_RPC, L1_CHAIN_ID, L2_CHAIN_ID,
+    /// DEPLOYER_ADDRESS and DEPLOYER_PRIVATE_KEY set. A non-zero exit fails the deployment.
+    #[arg(
+        long,
+        value_name = "PATH",
+        env = "KUP_POST_DEPLOY_SCRIPT",
+        help_heading = "Runtime Behavior"
+    )]
+    pub post_deploy_script: Option<String>,
 
-    /// Shorthand for long-running sessions: --log-max-size=10m --log-max-file=3 --quiet-services.
+    /// Extra /etc/hosts entry for every container, as HOST:IP (repeatable).
     ///
-    /// Explicit flags override the defaults set by --long-running.
-    #[arg(long, env = "KUP_LONG_RUNNING", help_heading = "Logging & Monitoring")]
-    pub long_running: bool,
+    /// The IP can be `host-gateway` to resolve to the Docker host.
+    #[arg(
+        long = "add-host",
+        value_name = "HOST:IP",
+        env = "KUP_ADD_HOST",
+        value_delimiter = ',',
+        help_heading = "Runtime Behavior"
+    )]
+    pub extra_hosts: Vec<String>,
 
-    /// Write deployment metrics to a TOML file.
+    /// Make the host reachable as `host.docker.internal` from every container.
     ///
-    /// When provided, per-service deploy timings and image sizes
-    /// are written in TOML format to the specified path after deployment.
-    #[arg(long, env = "KUP_METRICS_FILE", help_heading = "Logging & Monitoring")]
-    pub metrics_file: Option<String>,
+    /// Shorthand for --add-host host.docker.internal:host-gateway, e.g. to point
+    /// kona-node at an L1 beacon running on the host.
+    #[arg(long, env = "KUP_HOST_GATEWAY", help_heading = "Runtime Behavior")]
+    pub host_gateway: bool,
 
-    /// Write deployment endpoints to a TOML file.
+    /// Grant the NET_ADMIN capability to the op-reth and kona-node containers.
     ///
-    /// When provided, all service endpoints (both internal Docker network
-    /// and host-accessible URLs) are written in TOML format to the specified path.
-    #[arg(long, env = "KUP_PORTS_FILE", help_heading = "Logging & Monitoring")]
-    pub ports_file: Option<String>,
+    /// Lets `tc` run inside them to shape their traffic. Not needed for `kupcake`'s own
+    /// fault injection, which uses a helper container.
+    #[arg(long, env = "KUP_NET_ADMIN", help_heading = "Runtime Behavior")]
+    pub net_admin: bool,
 
-    // ── Docker Images & Binaries ──
-    /// Docker image overrides for all services.
-    #[clap(flatten)]
-    pub docker_images: DockerImageOverrides,
-}
+    /// Pin every Docker image to the digest its tag currently resolves to.
+    ///
+    /// The images are pulled before the deployment starts and their digests are saved in
+    /// Kupcake.toml, so redeploying it later runs the exact same binaries.
+    #[arg(long, env = "KUP_PIN_IMAGE_DIGESTS", help_heading = "Docker Images")]
+    pub pin_image_digests: bool,
 
-impl Default for DeployArgs {
-    fn default() -> Self {
-        Self {
+    /// Recreate the Anvil container from its last state dump if it exits.
+    ///
+    /// While attached, Anvil state is dumped every minute; if the container dies, it is
+    /// recreated under the same name and host port and L2 services resume. Blocks mined
+    /// after the last dump are lost. Not available in detached mode.
+    #[arg(
+        long,
+        env = "KUP_ANVIL_AUTO_RECOVER",
+        help_heading = "Runtime Behavior"
+    )]
+    pub anvil_auto_recover: bool,
+
+    /// Minimum free disk space for the data directory, in GiB.
+    ///
+    /// The deployment fails before any node starts if less is available, since a full
+    /// disk corrupts the op-reth database. While attached, a warning is logged every
+    /// 5 minutes when the disk runs low. 0 disables the check.
+    #[arg(
+        long,
+        value_name = "GIB",
+        env = "KUP_MIN_DISK_SPACE",
+        help_heading = "Runtime Behavior"
+    )]
+    pub min_disk_space: Option<u64>,
+
+    /// PEM bundle of extra CA certificates to trust, e.g. for a TLS-intercepting proxy.
+    ///
+    /// Mounted into every container and referenced through SSL_CERT_FILE and
+    /// NODE_EXTRA_CA_CERTS, and trusted by kupcake's own RPC client. SSL_CERT_FILE
+    /// replaces the system roots: include public roots if services reach public endpoints.
+    #[arg(
+        long,
+        value_name = "PATH",
+        env = "KUP_CA_BUNDLE",
+        help_heading = "Runtime Behavior"
+    )]
+    pub ca_bundle: Option<String>,
+
+    /// Proxy URL (http://, https:// or socks5://) for outbound HTTP and HTTPS traffic.
+    ///
+    /// Passed to every container as HTTP_PROXY/HTTPS_PROXY and used by kupcake's own
+    /// RPC client. Defaults to the HTTP_PROXY/HTTPS_PROXY environment variables. The
+    /// deployment's containers and localhost always bypass the proxy.
+    #[arg(
+        long,
+        value_name = "URL",
+        env = "KUP_PROXY",
+        help_heading = "Runtime Behavior"
+    )]
+    pub proxy: Option<String>,
+
+    /// Hosts reached without the proxy, in NO_PROXY format (comma-separated).
+    ///
+    /// Defaults to the NO_PROXY environment variable.
+    #[arg(
+        long,
+        value_name = "HOSTS",
+        env = "KUP_NO_PROXY",
+        value_delimiter = ',',
+        help_heading = "Runtime Behavior"
+    )]
+    pub no_proxy: Option<Vec<String>>,
+
+    /// Subnet (CIDR) of the Docker network, e.g. 172.30.0.0/16.
+    ///
+    /// Use it when the range picked by Docker collides with a VPN or corporate network.
+    #[arg(
+        long,
+        value_name = "CIDR",
+        env = "KUP_NETWORK_SUBNET",
+        help_heading = "Runtime Behavior"
+    )]
+    pub network_subnet: Option<String>,
+
+    /// Gateway of the Docker network, inside --network-subnet.
+    #[arg(
+        long,
+        value_name = "IP",
+        env = "KUP_NETWORK_GATEWAY",
+        requires = "network_subnet",
+        help_heading = "Runtime Behavior"
+    )]
+    pub network_gateway: Option<String>,
+
+    /// Attach the containers to this pre-existing Docker network instead of creating one.
+    ///
+    /// The network is neither created nor removed by kupcake.
+    #[arg(
+        long,
+        value_name = "NAME",
+        env = "KUP_EXTERNAL_NETWORK",
+        conflicts_with_all = ["network_subnet", "network_gateway"],
+        help_heading = "Runtime Behavior"
+    )]
+    pub external_network: Option<String>,
+
+    /// Timeout of kupcake's own RPC requests (health checks, faucet, readiness waiters),
+    /// in seconds [default: 5].
+    ///
+    /// Raise it on congested CI machines where slow responses fail health checks.
+    #[arg(
+        long,
+        value_name = "SECS",
+        env = "KUP_RPC_TIMEOUT",
+        help_heading = "Runtime Behavior"
+    )]
+    pub rpc_timeout: Option<u64>,
+
+    /// Connection timeout of kupcake's own RPC requests, in seconds.
+    #[arg(
+        long,
+        value_name = "SECS",
+        env = "KUP_RPC_CONNECT_TIMEOUT",
+        help_heading = "Runtime Behavior"
+    )]
+    pub rpc_connect_timeout: Option<u64>,
+
+    /// Maximum idle connections kept open per host by kupcake's own RPC client.
+    #[arg(
+        long,
+        value_name = "COUNT",
+        env = "KUP_RPC_POOL_SIZE",
+        help_heading = "Runtime Behavior"
+    )]
+    pub rpc_pool_size: Option<usize>,
+
+    // ── Logging & Monitoring ──
+    /// Docker log file max size (e.g., "10m"). Enables json-file log driver with rotation.
+    #[arg(long, env = "KUP_LOG_MAX_SIZE", help_heading = "Logging & Monitoring")]
+    pub log_max_size: Option<String>,
+
+    /// Max number of rotated Docker log files.
+    #[arg(long, env = "KUP_LOG_MAX_FILE", help_heading = "Logging & Monitoring")]
+    pub log_max_file: Option<String>,
+
+    /// Quiet verbose services: suppress anvil output, set services to INFO log level.
+    #[arg(
+        long,
+        env = "KUP_QUIET_SERVICES",
+        help_heading = "Logging & Monitoring"
+    )]
+    pub quiet_services: bool,
+
+    /// Set the log level of a service, as SERVICE=LEVEL (repeatable).
+    ///
+    /// Overrides --quiet-services for that service. Services: op-node, kona-node,
+    /// op-conductor, op-batcher, op-proposer, op-challenger. Levels: error, warn, info,
+    /// debug, trace.
+    #[arg(
+        long = "service-log-level",
+        value_name = "SERVICE=LEVEL",
+        env = "KUP_SERVICE_LOG_LEVEL",
+        value_delimiter = ',',
+        value_parser = parse_service_log_level_entry,
+        help_heading = "Logging & Monitoring"
+    )]
+    pub service_log_levels: Vec<String>,
+
+    /// Stream container logs to tracing::debug!() in background.
+    #[arg(long, env = "KUP_STREAM_LOGS", help_heading = "Logging & Monitoring")]
+    pub stream_logs: bool,
+
+    /// Publish the op-reth and kona-node metrics of every L2 node on known host ports.
+    ///
+    /// Node i (sequencers first, then validators) serves op-reth metrics on host port
+    /// 19001+i and kona-node metrics on 17300+i, e.g. for an external Prometheus.
+    #[arg(
+        long,
+        env = "KUP_EXPOSE_METRICS",
+        help_heading = "Logging & Monitoring"
+    )]
+    pub expose_metrics: bool,
+
+    /// Deploy a Blockscout explorer (backend + Postgres) indexing the primary sequencer.
+    ///
+    /// The explorer URL is printed with the other host endpoints.
+    #[arg(long, env = "KUP_BLOCKSCOUT", help_heading = "Logging & Monitoring")]
+    pub blockscout: bool,
+
+    /// Deploy Loki and Promtail to collect the logs of every container of the network.
+    ///
+    /// A Loki datasource is provisioned in Grafana: browse the logs in Explore, filtered
+    /// by the `container` label.
+    #[arg(long, env = "KUP_LOKI", help_heading = "Logging & Monitoring")]
+    pub loki: bool,
+
+    /// Shorthand for long-running sessions: --log-max-size=10m --log-max-file=3 --quiet-services.
+    ///
+    /// Explicit flags override the defaults set by --long-running.
+    #[arg(long, env = "KUP_LONG_RUNNING", help_heading = "Logging & Monitoring")]
+    pub long_running: bool,
+
+    /// Write deployment metrics to a TOML file.
+    ///
+    /// When provided, per-service deploy timings and image sizes
+    /// are written in TOML format to the specified path after deployment.
+    #[arg(long, env = "KUP_METRICS_FILE", help_heading = "Logging & Monitoring")]
+    pub metrics_file: Option<String>,
+
+    /// Write deployment endpoints to a TOML file.
+    ///
+    /// When provided, all service endpoints (both internal Docker network
+    /// and host-accessible URLs) are written in TOML format to the specified path.
+    #[arg(long, env = "KUP_PORTS_FILE", help_heading = "Logging & Monitoring")]
+    pub ports_file: Option<String>,
+
+    // ── Docker Images & Binaries ──
+    /// Docker image overrides for all services.
+    #[clap(flatten)]
+    pub docker_images: DockerImageOverrides,
+}
+
+impl Default for DeployArgs {
+    fn default() -> Self {
+        Self {
             network: None,
+            name_collision: NameCollisionArg::Error,
             l1: None, // Local mode by default (random chain ID)
+            external_l1: None,
+            external_l1_ws: None,
+            external_l1_deployer_key: None,
+            external_l1_batcher_key: None,
+            external_l1_proposer_key: None,
+            external_l1_challenger_key: None,
             l2_chain: None,
             snapshot: None,
             copy_snapshot: false,
             redeploy: false,
+            reuse_if_healthy: false,
+            restart_unhealthy: false,
+            rebase_data_dir: false,
             outdata: None,
             no_cleanup: false,
             dump_state: true,
@@ -814,20 +1970,67 @@ impl Default for DeployArgs {
             ports_file: None,
             spam: None,
             publish_all_ports: false,
+            fixed_ports: false,
+            restart_policy: None,
+            post_deploy_script: None,
+            extra_hosts: Vec::new(),
+            host_gateway: false,
+            net_admin: false,
+            pin_image_digests: false,
+            anvil_auto_recover: false,
+            min_disk_space: None,
+            ca_bundle: None,
+            proxy: None,
+            no_proxy: None,
+            network_subnet: None,
+            network_gateway: None,
+            external_network: None,
+            rpc_timeout: None,
+            rpc_connect_timeout: None,
+            rpc_pool_size: None,
             block_time: 12,
+            l2_block_time: None,
+            sequencer_fee_recipient: None,
+            l2_gas_limit: None,
+            eip1559_denominator: None,
+            eip1559_elasticity: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            l1_hardfork: None,
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
             genesis_timestamp: None,
+            prefund: Vec::new(),
+            hardfork_offsets: Vec::new(),
+            additional_l2_chains: Vec::new(),
+            preset: None,
             l2_nodes: 5,
             sequencer_count: 2,
             no_proposer: false,
             no_challenger: false,
+            spare_batchers: None,
+            batcher_da_type: None,
+            spare_proposers: None,
+            alt_da_server: None,
+            alt_da_challenge_window: None,
+            alt_da_resolve_window: None,
+            keep_going: false,
+            verify_chain_ids: false,
             flashblocks: false,
             proofs_validators: 0,
+            op_reth_pruning: None,
+            consensus_client: ConsensusClientArg::Kona,
             log_max_size: None,
             log_max_file: None,
             quiet_services: false,
+            service_log_levels: Vec::new(),
             stream_logs: false,
+            expose_metrics: false,
+            blockscout: false,
+            loki: false,
             long_running: false,
             deployment_target: DeploymentTargetArg::Live,
+            deterministic: None,
             config: None,
             docker_images: DockerImageOverrides::default(),
         }
@@ -862,6 +2065,14 @@ pub struct DockerImageOverrides {
     #[arg(long, env = "KUP_KONA_NODE_TAG", default_value = KONA_NODE_DEFAULT_TAG, help_heading = "Docker Images")]
     pub kona_node_tag: String,
 
+    /// Docker image for op-node (used with --consensus-client op-node).
+    #[arg(long, env = "KUP_OP_NODE_IMAGE", default_value = OP_NODE_DEFAULT_IMAGE, help_heading = "Docker Images")]
+    pub op_node_image: String,
+
+    /// Docker tag for op-node.
+    #[arg(long, env = "KUP_OP_NODE_TAG", default_value = OP_NODE_DEFAULT_TAG, help_heading = "Docker Images")]
+    pub op_node_tag: String,
+
     /// Docker image for op-batcher.
     #[arg(long, env = "KUP_OP_BATCHER_IMAGE", default_value = OP_BATCHER_DEFAULT_IMAGE, help_heading = "Docker Images")]
     pub op_batcher_image: String,
@@ -918,6 +2129,30 @@ pub struct DockerImageOverrides {
     #[arg(long, env = "KUP_GRAFANA_TAG", default_value = GRAFANA_DEFAULT_TAG, help_heading = "Docker Images")]
     pub grafana_tag: String,
 
+    /// Docker image for Loki.
+    #[arg(long, env = "KUP_LOKI_IMAGE", default_value = LOKI_DEFAULT_IMAGE, help_heading = "Docker Images")]
+    pub loki_image: String,
+
+    /// Docker tag for Loki.
+    #[arg(long, env = "KUP_LOKI_TAG", default_value = LOKI_DEFAULT_TAG, help_heading = "Docker Images")]
+    pub loki_tag: String,
+
+    /// Docker image for Promtail.
+    #[arg(long, env = "KUP_PROMTAIL_IMAGE", default_value = PROMTAIL_DEFAULT_IMAGE, help_heading = "Docker Images")]
+    pub promtail_image: String,
+
+    /// Docker tag for Promtail.
+    #[arg(long, env = "KUP_PROMTAIL_TAG", default_value = PROMTAIL_DEFAULT_TAG, help_heading = "Docker Images")]
+    pub promtail_tag: String,
+
+    /// Docker image for the Blockscout explorer backend.
+    #[arg(long, env = "KUP_BLOCKSCOUT_IMAGE", default_value = BLOCKSCOUT_DEFAULT_IMAGE, help_heading = "Docker Images")]
+    pub blockscout_image: String,
+
+    /// Docker tag for the Blockscout explorer backend.
+    #[arg(long, env = "KUP_BLOCKSCOUT_TAG", default_value = BLOCKSCOUT_DEFAULT_TAG, help_heading = "Docker Images")]
+    pub blockscout_tag: String,
+
     /// Docker image for op-rbuilder (flashblocks-enabled execution client).
     #[arg(long, env = "KUP_OP_RBUILDER_IMAGE", default_value = OP_RBUILDER_DEFAULT_IMAGE, help_heading = "Docker Images")]
     pub op_rbuilder_image: String,
@@ -1002,6 +2237,8 @@ impl Default for DockerImageOverrides {
             op_reth_tag: OP_RETH_DEFAULT_TAG.to_string(),
             kona_node_image: KONA_NODE_DEFAULT_IMAGE.to_string(),
             kona_node_tag: KONA_NODE_DEFAULT_TAG.to_string(),
+            op_node_image: OP_NODE_DEFAULT_IMAGE.to_string(),
+            op_node_tag: OP_NODE_DEFAULT_TAG.to_string(),
             op_batcher_image: OP_BATCHER_DEFAULT_IMAGE.to_string(),
             op_batcher_tag: OP_BATCHER_DEFAULT_TAG.to_string(),
             op_proposer_image: OP_PROPOSER_DEFAULT_IMAGE.to_string(),
@@ -1016,6 +2253,12 @@ impl Default for DockerImageOverrides {
             prometheus_tag: PROMETHEUS_DEFAULT_TAG.to_string(),
             grafana_image: GRAFANA_DEFAULT_IMAGE.to_string(),
             grafana_tag: GRAFANA_DEFAULT_TAG.to_string(),
+            loki_image: LOKI_DEFAULT_IMAGE.to_string(),
+            loki_tag: LOKI_DEFAULT_TAG.to_string(),
+            promtail_image: PROMTAIL_DEFAULT_IMAGE.to_string(),
+            promtail_tag: PROMTAIL_DEFAULT_TAG.to_string(),
+            blockscout_image: BLOCKSCOUT_DEFAULT_IMAGE.to_string(),
+            blockscout_tag: BLOCKSCOUT_DEFAULT_TAG.to_string(),
             op_rbuilder_image: OP_RBUILDER_DEFAULT_IMAGE.to_string(),
             op_rbuilder_tag: OP_RBUILDER_DEFAULT_TAG.to_string(),
             op_reth_binary: None,
@@ -1029,6 +2272,24 @@ impl Default for DockerImageOverrides {
     }
 }
 
+/// Validate an `ADDRESS=ETH` entry of `--prefund`.
+fn parse_prefund_entry(entry: &str) -> anyhow::Result<String> {
+    kupcake_deploy::parse_prefund(entry)?;
+    Ok(entry.to_string())
+}
+
+/// Validate a `SERVICE=LEVEL` entry of `--service-log-level`.
+fn parse_service_log_level_entry(entry: &str) -> anyhow::Result<String> {
+    kupcake_deploy::logs::parse_service_log_level(entry)?;
+    Ok(entry.to_string())
+}
+
+/// Validate a `FORK=SECONDS` entry of `--hardfork-offset`.
+fn parse_hardfork_offset_entry(entry: &str) -> anyhow::Result<String> {
+    kupcake_deploy::parse_hardfork_offset(entry)?;
+    Ok(entry.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1048,8 +2309,90 @@ mod tests {
         }
     }
 
+    // ── Logging flags ──
+
+    #[test]
+    fn test_reuse_if_healthy_conflicts_with_redeploy() {
+        let cli = parse_cli(&["deploy", "--reuse-if-healthy"]).unwrap();
+        assert!(deploy_args(&cli).reuse_if_healthy);
+        assert!(parse_cli(&["deploy", "--reuse-if-healthy", "--redeploy"]).is_err());
+
+        let cli = parse_cli(&["deploy", "--reuse-if-healthy", "--restart-unhealthy"]).unwrap();
+        assert!(deploy_args(&cli).restart_unhealthy);
+        assert!(parse_cli(&["deploy", "--restart-unhealthy"]).is_err());
+    }
+
+    #[test]
+    fn test_rebase_data_dir_flag() {
+        let cli = parse_cli(&["deploy", "--config", "Kupcake.toml", "--rebase-data-dir"]).unwrap();
+        assert!(deploy_args(&cli).rebase_data_dir);
+        assert!(!deploy_args(&parse_cli(&["deploy"]).unwrap()).rebase_data_dir);
+    }
+
+    #[test]
+    fn test_log_defaults() {
+        let cli = parse_cli(&[]).unwrap();
+        assert_eq!(cli.log_level(), LevelFilter::INFO);
+        assert_eq!(cli.log_format, LogFormat::Text);
+    }
+
+    #[test]
+    fn test_quiet_caps_log_level() {
+        let cli = parse_cli(&["--quiet", "-v", "debug"]).unwrap();
+        assert_eq!(cli.log_level(), LevelFilter::WARN);
+
+        // Quieter verbosity levels are kept
+        let cli = parse_cli(&["list", "-q", "-v", "error"]).unwrap();
+        assert_eq!(cli.log_level(), LevelFilter::ERROR);
+    }
+
+    #[test]
+    fn test_log_format_json() {
+        let cli = parse_cli(&["deploy", "--log-format", "json"]).unwrap();
+        assert_eq!(cli.log_format, LogFormat::Json);
+        assert!(parse_cli(&["--log-format", "xml"]).is_err());
+    }
+
     // ── --spam flag CLI parsing tests ──
 
+    #[test]
+    fn test_spam_scenario_file_flag() {
+        let cli = parse_cli(&["spam", "kup-test", "--scenario-file", "./custom.toml"]).unwrap();
+        let Some(Commands::Spam(args)) = cli.command else {
+            panic!("expected the spam command");
+        };
+        assert_eq!(
+            args.scenario_file,
+            Some(std::path::PathBuf::from("./custom.toml"))
+        );
+        assert!(args.scenario.is_none());
+
+        assert!(
+            parse_cli(&[
+                "spam",
+                "kup-test",
+                "--scenario",
+                "erc20",
+                "--scenario-file",
+                "./custom.toml"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_spam_report_file_flag() {
+        let cli = parse_cli(&["spam", "kup-test", "--report-file", "out/report.json"]).unwrap();
+        let Some(Commands::Spam(args)) = cli.command else {
+            panic!("expected the spam command");
+        };
+        assert_eq!(
+            args.report_file,
+            Some(std::path::PathBuf::from("out/report.json"))
+        );
+        assert!(!args.report);
+    }
+
     #[test]
     fn test_spam_flag_absent() {
         let cli = parse_cli(&["deploy"]).unwrap();
@@ -1122,6 +2465,420 @@ mod tests {
         assert_eq!(args.l2_nodes, 3);
     }
 
+    #[test]
+    fn test_post_deploy_script_flag() {
+        let cli = parse_cli(&["deploy", "--post-deploy-script", "./scripts/seed.sh"]).unwrap();
+        assert_eq!(
+            deploy_args(&cli).post_deploy_script.as_deref(),
+            Some("./scripts/seed.sh")
+        );
+    }
+
+    #[test]
+    fn test_add_host_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--add-host",
+            "beacon.local:10.0.0.5",
+            "--add-host",
+            "l1.local:10.0.0.6",
+            "--host-gateway",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(
+            args.extra_hosts,
+            ["beacon.local:10.0.0.5", "l1.local:10.0.0.6"]
+        );
+        assert!(args.host_gateway);
+    }
+
+    #[test]
+    fn test_deterministic_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert_eq!(deploy_args(&cli).deterministic, None);
+        let cli = parse_cli(&["deploy", "--deterministic", "42"]).unwrap();
+        assert_eq!(deploy_args(&cli).deterministic, Some(42));
+        assert!(parse_cli(&["deploy", "--deterministic", "abc"]).is_err());
+    }
+
+    #[test]
+    fn test_pin_image_digests_flag() {
+        let cli = parse_cli(&["deploy", "--pin-image-digests"]).unwrap();
+        assert!(deploy_args(&cli).pin_image_digests);
+    }
+
+    #[test]
+    fn test_op_reth_pruning_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert_eq!(deploy_args(&cli).op_reth_pruning, None);
+
+        let cli = parse_cli(&["deploy", "--op-reth-pruning", "minimal-pruned"]).unwrap();
+        assert_eq!(
+            deploy_args(&cli).op_reth_pruning,
+            Some(PruningArg::MinimalPruned)
+        );
+        assert!(parse_cli(&["deploy", "--op-reth-pruning", "light"]).is_err());
+    }
+
+    #[test]
+    fn test_net_admin_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(!deploy_args(&cli).net_admin);
+        let cli = parse_cli(&["deploy", "--net-admin"]).unwrap();
+        assert!(deploy_args(&cli).net_admin);
+    }
+
+    #[test]
+    fn test_fixed_ports_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(!deploy_args(&cli).fixed_ports);
+        let cli = parse_cli(&["deploy", "--fixed-ports", "--publish-all-ports"]).unwrap();
+        assert!(deploy_args(&cli).fixed_ports);
+        assert!(deploy_args(&cli).publish_all_ports);
+    }
+
+    #[test]
+    fn test_expose_metrics_flag() {
+        let cli = parse_cli(&["deploy", "--expose-metrics"]).unwrap();
+        assert!(deploy_args(&cli).expose_metrics);
+    }
+
+    #[test]
+    fn test_loki_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(!deploy_args(&cli).loki);
+        let cli = parse_cli(&["deploy", "--loki", "--promtail-tag", "3.4.2"]).unwrap();
+        assert!(deploy_args(&cli).loki);
+        assert_eq!(deploy_args(&cli).docker_images.promtail_tag, "3.4.2");
+    }
+
+    #[test]
+    fn test_blockscout_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(!deploy_args(&cli).blockscout);
+        let cli = parse_cli(&["deploy", "--blockscout", "--blockscout-tag", "v6"]).unwrap();
+        assert!(deploy_args(&cli).blockscout);
+        assert_eq!(deploy_args(&cli).docker_images.blockscout_tag, "v6");
+    }
+
+    #[test]
+    fn test_prefund_flag() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--prefund",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266=100",
+            "--prefund",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8=0.5",
+        ])
+        .unwrap();
+        assert_eq!(deploy_args(&cli).prefund.len(), 2);
+
+        assert!(parse_cli(&["deploy", "--prefund", "0xf39F=100"]).is_err());
+        assert!(parse_cli(&["deploy", "--prefund", "100"]).is_err());
+    }
+
+    #[test]
+    fn test_hardfork_offset_flag() {
+        let cli = parse_cli(&["deploy", "--hardfork-offset", "holocene=60,isthmus=120"]).unwrap();
+        assert_eq!(
+            deploy_args(&cli).hardfork_offsets,
+            vec!["holocene=60", "isthmus=120"]
+        );
+
+        assert!(parse_cli(&["deploy", "--hardfork-offset", "pectra=60"]).is_err());
+        assert!(parse_cli(&["deploy", "--hardfork-offset", "isthmus"]).is_err());
+    }
+
+    #[test]
+    fn test_service_log_level_flag() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--service-log-level",
+            "op-batcher=debug,kona-node=trace",
+        ])
+        .unwrap();
+        assert_eq!(
+            deploy_args(&cli).service_log_levels,
+            vec!["op-batcher=debug", "kona-node=trace"]
+        );
+
+        assert!(parse_cli(&["deploy", "--service-log-level", "op-reth=debug"]).is_err());
+        assert!(parse_cli(&["deploy", "--service-log-level", "op-batcher=loud"]).is_err());
+    }
+
+    #[test]
+    fn test_add_l2_chain_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(deploy_args(&cli).additional_l2_chains.is_empty());
+
+        let cli = parse_cli(&[
+            "deploy",
+            "--add-l2-chain",
+            "1235,1236",
+            "--add-l2-chain",
+            "1237",
+        ])
+        .unwrap();
+        assert_eq!(deploy_args(&cli).additional_l2_chains, [1235, 1236, 1237]);
+
+        assert!(parse_cli(&["deploy", "--add-l2-chain", "op-sepolia"]).is_err());
+    }
+
+    #[test]
+    fn test_anvil_mnemonic_flags() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(deploy_args(&cli).anvil_mnemonic.is_none());
+
+        let cli = parse_cli(&[
+            "deploy",
+            "--anvil-mnemonic",
+            "test test test test test test test test test test test junk",
+            "--anvil-derivation-path",
+            "m/44'/60'/1'/0/",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(
+            args.anvil_mnemonic.as_deref(),
+            Some("test test test test test test test test test test test junk")
+        );
+        assert_eq!(
+            args.anvil_derivation_path.as_deref(),
+            Some("m/44'/60'/1'/0/")
+        );
+    }
+
+    #[test]
+    fn test_anvil_auto_recover_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(!deploy_args(&cli).anvil_auto_recover);
+
+        let cli = parse_cli(&["deploy", "--anvil-auto-recover"]).unwrap();
+        assert!(deploy_args(&cli).anvil_auto_recover);
+    }
+
+    #[test]
+    fn test_name_collision_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert_eq!(deploy_args(&cli).name_collision, NameCollisionArg::Error);
+
+        let cli = parse_cli(&["deploy", "--name-collision", "suffix"]).unwrap();
+        assert_eq!(deploy_args(&cli).name_collision, NameCollisionArg::Suffix);
+    }
+
+    #[test]
+    fn test_keep_going_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert!(!deploy_args(&cli).keep_going);
+
+        let cli = parse_cli(&["deploy", "--keep-going"]).unwrap();
+        assert!(deploy_args(&cli).keep_going);
+    }
+
+    #[test]
+    fn test_spare_flags() {
+        let cli =
+            parse_cli(&["deploy", "--spare-batchers", "2", "--spare-proposers", "1"]).unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.spare_batchers, Some(2));
+        assert_eq!(args.spare_proposers, Some(1));
+    }
+
+    #[test]
+    fn test_alt_da_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--alt-da-server",
+            "http://da-server:3100",
+            "--alt-da-challenge-window",
+            "4",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.alt_da_server.as_deref(), Some("http://da-server:3100"));
+        assert_eq!(args.alt_da_challenge_window, Some(4));
+        assert_eq!(args.alt_da_resolve_window, None);
+
+        // The windows are meaningless without a DA server.
+        assert!(parse_cli(&["deploy", "--alt-da-resolve-window", "4"]).is_err());
+    }
+
+    #[test]
+    fn test_verify_chain_ids_flag() {
+        let cli = parse_cli(&["deploy", "--verify-chain-ids"]).unwrap();
+        assert!(deploy_args(&cli).verify_chain_ids);
+    }
+
+    #[test]
+    fn test_min_disk_space_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert_eq!(deploy_args(&cli).min_disk_space, None);
+
+        let cli = parse_cli(&["deploy", "--min-disk-space", "0"]).unwrap();
+        assert_eq!(deploy_args(&cli).min_disk_space, Some(0));
+    }
+
+    #[test]
+    fn test_ca_bundle_flag() {
+        let cli = parse_cli(&["deploy", "--ca-bundle", "/etc/ssl/corp-ca.pem"]).unwrap();
+        assert_eq!(
+            deploy_args(&cli).ca_bundle.as_deref(),
+            Some("/etc/ssl/corp-ca.pem")
+        );
+    }
+
+    #[test]
+    fn test_rpc_client_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--rpc-timeout",
+            "30",
+            "--rpc-connect-timeout",
+            "10",
+            "--rpc-pool-size",
+            "64",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.rpc_timeout, Some(30));
+        assert_eq!(args.rpc_connect_timeout, Some(10));
+        assert_eq!(args.rpc_pool_size, Some(64));
+    }
+
+    #[test]
+    fn test_proxy_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--proxy",
+            "http://proxy.corp:3128",
+            "--no-proxy",
+            ".corp,10.0.0.1",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.proxy.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(
+            args.no_proxy,
+            Some(vec![".corp".to_string(), "10.0.0.1".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_docker_network_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--network-subnet",
+            "172.30.0.0/16",
+            "--network-gateway",
+            "172.30.0.1",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.network_subnet.as_deref(), Some("172.30.0.0/16"));
+        assert_eq!(args.network_gateway.as_deref(), Some("172.30.0.1"));
+
+        let cli = parse_cli(&["deploy", "--external-network", "corp-net"]).unwrap();
+        assert_eq!(
+            deploy_args(&cli).external_network.as_deref(),
+            Some("corp-net")
+        );
+
+        assert!(parse_cli(&["deploy", "--network-gateway", "172.30.0.1"]).is_err());
+        assert!(
+            parse_cli(&[
+                "deploy",
+                "--external-network",
+                "corp-net",
+                "--network-subnet",
+                "172.30.0.0/16",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_preset_flag() {
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert_eq!(deploy_args(&cli).preset, None);
+
+        let cli = parse_cli(&["deploy", "--preset", "ha-sequencer"]).unwrap();
+        assert_eq!(deploy_args(&cli).preset, Some(TopologyArg::HaSequencer));
+
+        assert!(parse_cli(&["deploy", "--preset", "huge"]).is_err());
+    }
+
+    #[test]
+    fn test_l2_block_time_flag() {
+        let cli = parse_cli(&["deploy", "--block-time", "12", "--l2-block-time", "2"]).unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.block_time, 12);
+        assert_eq!(args.l2_block_time, Some(2));
+
+        let cli = parse_cli(&["deploy"]).unwrap();
+        assert_eq!(deploy_args(&cli).l2_block_time, None);
+    }
+
+    #[test]
+    fn test_sequencer_fee_recipient_flag() {
+        let recipient = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        let cli = parse_cli(&["deploy", "--sequencer-fee-recipient", recipient]).unwrap();
+        assert_eq!(
+            deploy_args(&cli).sequencer_fee_recipient.as_deref(),
+            Some(recipient)
+        );
+    }
+
+    #[test]
+    fn test_chain_fee_param_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--l2-gas-limit",
+            "60000000",
+            "--eip1559-denominator",
+            "8",
+            "--eip1559-elasticity",
+            "10",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.l2_gas_limit, Some(60_000_000));
+        assert_eq!(args.eip1559_denominator, Some(8));
+        assert_eq!(args.eip1559_elasticity, Some(10));
+    }
+
+    #[test]
+    fn test_grafana_import_command() {
+        let cli = parse_cli(&["grafana", "kup-test", "import", "dashboard.json"]).unwrap();
+        let Some(Commands::Grafana(args)) = cli.command else {
+            panic!("expected the grafana command");
+        };
+        assert_eq!(args.config, "kup-test");
+        let GrafanaAction::Import { file } = args.action;
+        assert_eq!(file, std::path::PathBuf::from("dashboard.json"));
+    }
+
+    #[test]
+    fn test_contracts_locator_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--l1-contracts-locator",
+            "tag://op-contracts/v3.0.0",
+            "--l2-contracts-locator",
+            "file:///tmp/forge-artifacts",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(
+            args.l1_contracts_locator.as_deref(),
+            Some("tag://op-contracts/v3.0.0")
+        );
+        assert_eq!(
+            args.l2_contracts_locator.as_deref(),
+            Some("file:///tmp/forge-artifacts")
+        );
+    }
+
     #[test]
     fn test_l1_source_parse_sepolia() {
         let source: L1Source = "sepolia".parse().unwrap();
@@ -1183,6 +2940,33 @@ mod tests {
         assert!(matches!(cli.command, Some(Commands::List)));
     }
 
+    #[test]
+    fn test_status_parses() {
+        let cli = parse_cli(&["status"]).unwrap();
+        match cli.command {
+            Some(Commands::Status(args)) => {
+                assert_eq!(args.prefix, "kup-");
+                assert!(!args.json);
+            }
+            _ => panic!("Expected Status command"),
+        }
+
+        let cli = parse_cli(&["status", "--prefix", "dev-", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::Status(args)) => {
+                assert_eq!(args.prefix, "dev-");
+                assert!(args.json);
+            }
+            _ => panic!("Expected Status command"),
+        }
+
+        let cli = parse_cli(&["status", "kup-test"]).unwrap();
+        match cli.command {
+            Some(Commands::Status(args)) => assert_eq!(args.network.as_deref(), Some("kup-test")),
+            _ => panic!("Expected Status command"),
+        }
+    }
+
     #[test]
     fn test_prune_parses() {
         let cli = parse_cli(&["prune"]).unwrap();
@@ -1192,6 +2976,128 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_logs_parses() {
+        let cli = parse_cli(&[
+            "logs",
+            "kup-test",
+            "op-batcher",
+            "--since",
+            "10m",
+            "--tail",
+            "200",
+            "--grep",
+            "channel",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Logs(args)) => {
+                assert_eq!(
+                    args.config_and_service().unwrap(),
+                    ("kup-test", "op-batcher")
+                );
+                assert_eq!(args.since, Some(std::time::Duration::from_secs(600)));
+                assert_eq!(args.tail, Some(200));
+                assert_eq!(args.grep.as_deref(), Some("channel"));
+                assert!(!args.follow);
+            }
+            _ => panic!("Expected Logs command"),
+        }
+        assert!(parse_cli(&["logs", "kup-test", "op-batcher", "--since", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_logs_network_flag() {
+        let cli =
+            parse_cli(&["logs", "--network", "kup-test", "op-reth-validator-1", "-f"]).unwrap();
+        match cli.command {
+            Some(Commands::Logs(args)) => {
+                assert_eq!(
+                    args.config_and_service().unwrap(),
+                    ("kup-test", "op-reth-validator-1")
+                );
+                assert!(args.follow);
+            }
+            _ => panic!("Expected Logs command"),
+        }
+
+        let cli = parse_cli(&["logs", "--network", "kup-test", "kup-test", "op-reth"]).unwrap();
+        match cli.command {
+            Some(Commands::Logs(args)) => assert!(args.config_and_service().is_err()),
+            _ => panic!("Expected Logs command"),
+        }
+        assert!(parse_cli(&["logs"]).is_err());
+    }
+
+    #[test]
+    fn test_external_l1_flags() {
+        let cli = parse_cli(&[
+            "deploy",
+            "--external-l1",
+            "http://10.0.0.5:8545",
+            "--external-l1-deployer-key",
+            "0x01",
+            "--external-l1-batcher-key",
+            "0x02",
+        ])
+        .unwrap();
+        let args = deploy_args(&cli);
+        assert_eq!(args.external_l1.as_deref(), Some("http://10.0.0.5:8545"));
+        assert_eq!(args.external_l1_deployer_key.as_deref(), Some("0x01"));
+        assert_eq!(args.external_l1_batcher_key.as_deref(), Some("0x02"));
+        assert_eq!(args.external_l1_proposer_key, None);
+
+        // The deployer key is required, and forking is exclusive with an external L1.
+        assert!(parse_cli(&["deploy", "--external-l1", "http://10.0.0.5:8545"]).is_err());
+        assert!(
+            parse_cli(&[
+                "deploy",
+                "--external-l1",
+                "http://10.0.0.5:8545",
+                "--external-l1-deployer-key",
+                "0x01",
+                "--l1",
+                "sepolia",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_stop_command() {
+        let cli = parse_cli(&["stop", "--network", "kup-test"]).unwrap();
+        match cli.command {
+            Some(Commands::Stop(args)) => assert_eq!(args.network, "kup-test"),
+            _ => panic!("Expected Stop command"),
+        }
+        assert!(parse_cli(&["stop"]).is_err());
+    }
+
+    #[test]
+    fn test_restart_command() {
+        let cli = parse_cli(&["restart", "--network", "kup-test", "op-batcher"]).unwrap();
+        match cli.command {
+            Some(Commands::Restart(args)) => {
+                assert_eq!(args.network, "kup-test");
+                assert_eq!(args.service, "op-batcher");
+            }
+            _ => panic!("Expected Restart command"),
+        }
+        assert!(parse_cli(&["restart", "--network", "kup-test"]).is_err());
+    }
+
+    #[test]
+    fn test_cleanup_dry_run_flag() {
+        let cli = parse_cli(&["cleanup", "kup-test", "--dry-run"]).unwrap();
+        match cli.command {
+            Some(Commands::Cleanup(args)) => {
+                assert_eq!(args.prefix, "kup-test");
+                assert!(args.dry_run);
+            }
+            _ => panic!("Expected Cleanup command"),
+        }
+    }
+
     #[test]
     fn test_prune_yes_flag() {
         let cli = parse_cli(&["prune", "--yes"]).unwrap();
@@ -1200,4 +3106,136 @@ mod tests {
             _ => panic!("Expected Prune command"),
         }
     }
+
+    #[test]
+    fn test_health_args() {
+        let cli = parse_cli(&["health", "--network", "kup-test", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::Health(args)) => {
+                assert_eq!(args.network, "kup-test");
+                assert!(args.json);
+            }
+            _ => panic!("expected health command"),
+        }
+
+        let cli = parse_cli(&["health", "-n", "kup-test", "--watch", "--interval", "2"]).unwrap();
+        match cli.command {
+            Some(Commands::Health(args)) => {
+                assert!(args.watch);
+                assert_eq!(args.interval, 2);
+            }
+            _ => panic!("expected health command"),
+        }
+        assert!(parse_cli(&["health", "-n", "kup-test", "--watch", "--interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_faucet_token_args() {
+        let to = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        let l1 = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let l2 = "0x4200000000000000000000000000000000000042";
+        let cli = parse_cli(&[
+            "faucet",
+            "kup-test",
+            "--to",
+            to,
+            "--token",
+            l1,
+            "--l2-token",
+            l2,
+            "--token-amount",
+            "1000",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Faucet(args)) => {
+                assert_eq!(args.token.as_deref(), Some(l1));
+                assert_eq!(args.l2_token.as_deref(), Some(l2));
+                assert_eq!(args.token_amount, Some(1000));
+            }
+            _ => panic!("expected faucet command"),
+        }
+
+        // The L2 token and amount are required with --token
+        assert!(parse_cli(&["faucet", "kup-test", "--to", to, "--token", l1]).is_err());
+        // --amount is for ETH deposits only
+        assert!(
+            parse_cli(&[
+                "faucet",
+                "kup-test",
+                "--to",
+                to,
+                "--token",
+                l1,
+                "--l2-token",
+                l2,
+                "--token-amount",
+                "1",
+                "--amount",
+                "2",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_withdraw_args() {
+        let cli = parse_cli(&[
+            "withdraw", "kup-test", "initiate", "--key", "0xac09", "--amount", "0.5", "--wait",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Withdraw(args)) => {
+                assert_eq!(args.config, "kup-test");
+                match args.action {
+                    WithdrawAction::Initiate { key, amount, wait } => {
+                        assert_eq!(key, "0xac09");
+                        assert_eq!(amount.to_string(), "0.5");
+                        assert!(wait);
+                    }
+                    _ => panic!("expected initiate"),
+                }
+            }
+            _ => panic!("expected withdraw command"),
+        }
+
+        let hash = format!("0x{}", "ab".repeat(32));
+        let cli = parse_cli(&[
+            "withdraw",
+            "kup-test",
+            "finalize",
+            &hash,
+            "--skip-challenge-period",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Withdraw(WithdrawArgs {
+                action:
+                    WithdrawAction::Finalize {
+                        withdrawal_hash,
+                        skip_challenge_period,
+                    },
+                ..
+            })) => {
+                assert_eq!(withdrawal_hash, hash);
+                assert!(skip_challenge_period);
+            }
+            _ => panic!("expected withdraw finalize"),
+        }
+
+        assert!(parse_cli(&["withdraw", "kup-test", "prove"]).is_err());
+    }
+
+    #[test]
+    fn test_doctor_args() {
+        let cli = parse_cli(&["doctor", "--l1", "sepolia", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::Doctor(args)) => {
+                assert!(args.config.is_none());
+                assert_eq!(args.l1.and_then(|l1| l1.chain_id()), Some(11155111));
+                assert!(args.json);
+            }
+            _ => panic!("Expected Doctor command"),
+        }
+    }
 }
@@ -8,6 +8,7 @@
 //! The resolved [`DeployConfig`] is then converted to a [`DeployerBuilder`]
 //! for deployment.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
@@ -18,7 +19,11 @@ use figment::{
 };
 use serde::{Deserialize, Serialize};
 
-use kupcake_deploy::{DeployerBuilder, DeploymentTarget, OutDataPath};
+use kupcake_deploy::{
+    AltDaConfig, ConsensusClient, DataAvailabilityType, DeployerBuilder, DeploymentTarget,
+    ExternalL1Config, L2ChainConfig, NameCollision, OutDataPath, ProxyConfig, PruningMode,
+    RestartPolicy, RpcClientConfig, Topology,
+};
 
 /// Flat deployment configuration struct.
 ///
@@ -28,22 +33,54 @@ use kupcake_deploy::{DeployerBuilder, DeploymentTarget, OutDataPath};
 pub struct DeployConfig {
     // ── Network Configuration ──
     pub network: Option<String>,
+    pub name_collision: Option<String>,
     pub l1: Option<String>,
+    pub external_l1: Option<String>,
+    pub external_l1_ws: Option<String>,
+    pub external_l1_deployer_key: Option<String>,
+    pub external_l1_batcher_key: Option<String>,
+    pub external_l1_proposer_key: Option<String>,
+    pub external_l1_challenger_key: Option<String>,
     pub l2_chain: Option<u64>,
     pub block_time: Option<u64>,
+    pub l2_block_time: Option<u64>,
+    pub sequencer_fee_recipient: Option<String>,
+    pub l2_gas_limit: Option<u64>,
+    pub eip1559_denominator: Option<u64>,
+    pub eip1559_elasticity: Option<u64>,
+    pub l1_contracts_locator: Option<String>,
+    pub l2_contracts_locator: Option<String>,
+    pub l1_hardfork: Option<String>,
+    pub anvil_mnemonic: Option<String>,
+    pub anvil_derivation_path: Option<String>,
     pub genesis_timestamp: Option<u64>,
+    pub prefund: Option<Vec<String>>,
+    pub hardfork_offsets: Option<Vec<String>>,
+    pub additional_l2_chains: Option<Vec<u64>>,
 
     // ── L2 Nodes ──
+    pub preset: Option<String>,
     pub l2_nodes: Option<usize>,
     #[serde(alias = "sequencers")]
     pub sequencer_count: Option<usize>,
     pub flashblocks: Option<bool>,
     pub proofs_validators: Option<usize>,
+    pub op_reth_pruning: Option<String>,
+    pub consensus_client: Option<String>,
 
     // ── Deployment ──
     pub deployment_target: Option<String>,
+    pub deterministic: Option<u64>,
     pub no_proposer: Option<bool>,
     pub no_challenger: Option<bool>,
+    pub spare_batchers: Option<usize>,
+    pub batcher_da_type: Option<String>,
+    pub spare_proposers: Option<usize>,
+    pub alt_da_server: Option<String>,
+    pub alt_da_challenge_window: Option<u64>,
+    pub alt_da_resolve_window: Option<u64>,
+    pub keep_going: Option<bool>,
+    pub verify_chain_ids: Option<bool>,
 
     // ── State & Storage ──
     pub outdata: Option<String>,
@@ -56,12 +93,34 @@ pub struct DeployConfig {
     pub no_cleanup: Option<bool>,
     pub detach: Option<bool>,
     pub publish_all_ports: Option<bool>,
+    pub fixed_ports: Option<bool>,
+    pub restart_policy: Option<String>,
+    pub post_deploy_script: Option<String>,
+    pub extra_hosts: Option<Vec<String>>,
+    pub host_gateway: Option<bool>,
+    pub net_admin: Option<bool>,
+    pub pin_image_digests: Option<bool>,
+    pub anvil_auto_recover: Option<bool>,
+    pub min_disk_space: Option<u64>,
+    pub ca_bundle: Option<String>,
+    pub proxy: Option<String>,
+    pub no_proxy: Option<Vec<String>>,
+    pub network_subnet: Option<String>,
+    pub network_gateway: Option<String>,
+    pub external_network: Option<String>,
+    pub rpc_timeout: Option<u64>,
+    pub rpc_connect_timeout: Option<u64>,
+    pub rpc_pool_size: Option<usize>,
 
     // ── Logging & Monitoring ──
     pub log_max_size: Option<String>,
     pub log_max_file: Option<String>,
     pub quiet_services: Option<bool>,
+    pub service_log_levels: Option<Vec<String>>,
     pub stream_logs: Option<bool>,
+    pub expose_metrics: Option<bool>,
+    pub blockscout: Option<bool>,
+    pub loki: Option<bool>,
     pub long_running: Option<bool>,
 
     // ── Docker Images ──
@@ -71,6 +130,8 @@ pub struct DeployConfig {
     pub op_reth_tag: Option<String>,
     pub kona_node_image: Option<String>,
     pub kona_node_tag: Option<String>,
+    pub op_node_image: Option<String>,
+    pub op_node_tag: Option<String>,
     pub op_batcher_image: Option<String>,
     pub op_batcher_tag: Option<String>,
     pub op_proposer_image: Option<String>,
@@ -85,6 +146,12 @@ pub struct DeployConfig {
     pub prometheus_tag: Option<String>,
     pub grafana_image: Option<String>,
     pub grafana_tag: Option<String>,
+    pub loki_image: Option<String>,
+    pub loki_tag: Option<String>,
+    pub promtail_image: Option<String>,
+    pub promtail_tag: Option<String>,
+    pub blockscout_image: Option<String>,
+    pub blockscout_tag: Option<String>,
     pub op_rbuilder_image: Option<String>,
     pub op_rbuilder_tag: Option<String>,
 
@@ -115,12 +182,51 @@ pub fn resolve_deploy_config(
         // Layer 2: CLI args (highest priority — only explicitly provided values)
         .merge(Serialized::defaults(cli_overrides));
 
-    figment
+    let config: DeployConfig = figment
         .extract()
-        .context("Failed to resolve deployment configuration")
+        .context("Failed to resolve deployment configuration")?;
+    for entry in config.prefund.iter().flatten() {
+        kupcake_deploy::parse_prefund(entry)?;
+    }
+    for entry in config.hardfork_offsets.iter().flatten() {
+        kupcake_deploy::parse_hardfork_offset(entry)?;
+    }
+    for entry in config.service_log_levels.iter().flatten() {
+        kupcake_deploy::logs::parse_service_log_level(entry)?;
+    }
+    if let Some(ref policy) = config.restart_policy {
+        policy.parse::<RestartPolicy>()?;
+    }
+
+    Ok(config)
 }
 
 impl DeployConfig {
+    /// Resolve `--preset` into the node counts, block time and logging fields it sets.
+    ///
+    /// Explicitly set fields are kept.
+    pub fn resolve_preset(&mut self) -> Result<()> {
+        let Some(ref preset) = self.preset else {
+            return Ok(());
+        };
+        let topology: Topology = preset
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Unknown preset '{}'", preset))?;
+
+        self.l2_nodes.get_or_insert(topology.l2_node_count());
+        self.sequencer_count
+            .get_or_insert(topology.sequencer_count());
+        self.block_time.get_or_insert(topology.block_time());
+        if let Some((max_size, max_file)) = topology.log_rotation() {
+            self.log_max_size
+                .get_or_insert_with(|| max_size.to_string());
+            self.log_max_file
+                .get_or_insert_with(|| max_file.to_string());
+            self.quiet_services.get_or_insert(true);
+        }
+        Ok(())
+    }
+
     /// Resolve `--long-running` shorthand into individual logging fields.
     pub fn resolve_long_running(&mut self) {
         if !self.long_running.unwrap_or(false) {
@@ -153,6 +259,9 @@ pub fn deploy_config_to_builder(
     let mut builder = DeployerBuilder::new(l1_chain_id)
         .maybe_l2_chain_id(config.l2_chain)
         .maybe_network_name(config.network.clone())
+        .name_collision(parse_name_collision(
+            config.name_collision.as_deref().unwrap_or("error"),
+        ))
         .maybe_outdata(config.outdata.as_ref().map(|o| {
             if o == "tempdir" {
                 OutDataPath::TempDir
@@ -166,7 +275,32 @@ pub fn deploy_config_to_builder(
         .maybe_override_state(config.override_state.as_ref().map(PathBuf::from))
         .detach(config.detach.unwrap_or(false))
         .publish_all_ports(config.publish_all_ports.unwrap_or(false))
+        .fixed_ports(config.fixed_ports.unwrap_or(false))
+        .restart_policy(parse_restart_policy(config))
+        .maybe_post_deploy_script(config.post_deploy_script.as_ref().map(PathBuf::from))
+        .extra_hosts(config.extra_hosts.clone().unwrap_or_default())
+        .host_gateway(config.host_gateway.unwrap_or(false))
+        .net_admin(config.net_admin.unwrap_or(false))
+        .pin_all_digests(config.pin_image_digests.unwrap_or(false))
+        .anvil_auto_recover(config.anvil_auto_recover.unwrap_or(false))
+        .maybe_min_disk_space_gib(config.min_disk_space)
+        .maybe_ca_bundle(config.ca_bundle.as_ref().map(PathBuf::from))
+        .maybe_proxy(proxy_config(config))
+        .maybe_network_subnet(config.network_subnet.clone())
+        .maybe_network_gateway(config.network_gateway.clone())
+        .maybe_external_network(config.external_network.clone())
+        .rpc_client_config(rpc_client_config(config, RpcClientConfig::default()))
         .block_time(config.block_time.unwrap_or(4))
+        .maybe_l2_block_time(config.l2_block_time)
+        .maybe_sequencer_fee_recipient(config.sequencer_fee_recipient.clone())
+        .maybe_gas_limit(config.l2_gas_limit)
+        .maybe_eip1559_denominator(config.eip1559_denominator)
+        .maybe_eip1559_elasticity(config.eip1559_elasticity)
+        .maybe_l1_contracts_locator(config.l1_contracts_locator.clone())
+        .maybe_l2_contracts_locator(config.l2_contracts_locator.clone())
+        .maybe_anvil_hardfork(config.l1_hardfork.clone())
+        .maybe_anvil_mnemonic(config.anvil_mnemonic.clone())
+        .maybe_anvil_derivation_path(config.anvil_derivation_path.clone())
         .maybe_genesis_timestamp(config.genesis_timestamp)
         .l2_node_count(config.l2_nodes.unwrap_or(5))
         .sequencer_count(config.sequencer_count.unwrap_or(2))
@@ -174,16 +308,53 @@ pub fn deploy_config_to_builder(
         .maybe_log_max_file(config.log_max_file.clone())
         .quiet_services(config.quiet_services.unwrap_or(false))
         .stream_logs(config.stream_logs.unwrap_or(false))
+        .expose_metrics(config.expose_metrics.unwrap_or(false))
+        .blockscout(config.blockscout.unwrap_or(false))
+        .logs_enabled(config.loki.unwrap_or(false))
         .no_proposer(config.no_proposer.unwrap_or(false))
         .no_challenger(config.no_challenger.unwrap_or(false))
+        .spare_batchers(config.spare_batchers.unwrap_or(0))
+        .batcher_da_type(parse_da_type(
+            config.batcher_da_type.as_deref().unwrap_or("blobs"),
+        ))
+        .spare_proposers(config.spare_proposers.unwrap_or(0))
+        .maybe_alt_da(config.alt_da_server.as_ref().map(|da_server| {
+            AltDaConfig::new(da_server)
+                .challenge_window(
+                    config
+                        .alt_da_challenge_window
+                        .unwrap_or(kupcake_deploy::alt_da::DEFAULT_CHALLENGE_WINDOW),
+                )
+                .resolve_window(
+                    config
+                        .alt_da_resolve_window
+                        .unwrap_or(kupcake_deploy::alt_da::DEFAULT_RESOLVE_WINDOW),
+                )
+        }))
+        .keep_going(config.keep_going.unwrap_or(false))
+        .verify_chain_ids(config.verify_chain_ids.unwrap_or(false))
         .flashblocks(config.flashblocks.unwrap_or(false))
         .proofs_validators(config.proofs_validators.unwrap_or(0))
+        .op_reth_pruning(parse_pruning(
+            config.op_reth_pruning.as_deref().unwrap_or("archive"),
+        ))
         .maybe_snapshot(config.snapshot.as_ref().map(PathBuf::from))
         .copy_snapshot(config.copy_snapshot.unwrap_or(false))
-        .deployment_target(parse_deployment_target(
-            config.deployment_target.as_deref().unwrap_or("live"),
+        .maybe_deterministic(config.deterministic)
+        .deployment_target(match config.deployment_target {
+            Some(ref target) => parse_deployment_target(target),
+            // Deterministic deployments cannot use the live target
+            None if config.deterministic.is_some() => DeploymentTarget::Genesis,
+            None => DeploymentTarget::Live,
+        })
+        .consensus_client(parse_consensus_client(
+            config.consensus_client.as_deref().unwrap_or("kona"),
         ));
 
+    if let Some(external_l1) = external_l1_config(config) {
+        builder = builder.external_l1_config(external_l1);
+    }
+
     // Docker images — use defaults from the builder if not overridden
     if let Some(ref v) = config.anvil_image {
         builder = builder.anvil_image(v.clone());
@@ -203,6 +374,12 @@ pub fn deploy_config_to_builder(
     if let Some(ref v) = config.kona_node_tag {
         builder = builder.kona_node_tag(v.clone());
     }
+    if let Some(ref v) = config.op_node_image {
+        builder = builder.op_node_image(v.clone());
+    }
+    if let Some(ref v) = config.op_node_tag {
+        builder = builder.op_node_tag(v.clone());
+    }
     if let Some(ref v) = config.op_batcher_image {
         builder = builder.op_batcher_image(v.clone());
     }
@@ -245,6 +422,24 @@ pub fn deploy_config_to_builder(
     if let Some(ref v) = config.grafana_tag {
         builder = builder.grafana_tag(v.clone());
     }
+    if let Some(ref v) = config.loki_image {
+        builder = builder.loki_image(v.clone());
+    }
+    if let Some(ref v) = config.loki_tag {
+        builder = builder.loki_tag(v.clone());
+    }
+    if let Some(ref v) = config.promtail_image {
+        builder = builder.promtail_image(v.clone());
+    }
+    if let Some(ref v) = config.promtail_tag {
+        builder = builder.promtail_tag(v.clone());
+    }
+    if let Some(ref v) = config.blockscout_image {
+        builder = builder.blockscout_image(v.clone());
+    }
+    if let Some(ref v) = config.blockscout_tag {
+        builder = builder.blockscout_tag(v.clone());
+    }
     if let Some(ref v) = config.op_rbuilder_image {
         builder = builder.op_rbuilder_image(v.clone());
     }
@@ -275,6 +470,27 @@ pub fn deploy_config_to_builder(
         builder = builder.with_op_rbuilder_binary(path);
     }
 
+    // Entries were validated by `resolve_deploy_config`.
+    for entry in config.prefund.iter().flatten() {
+        if let Ok((address, amount)) = kupcake_deploy::parse_prefund(entry) {
+            builder = builder.prefund(address, amount);
+        }
+    }
+    for entry in config.hardfork_offsets.iter().flatten() {
+        if let Ok((fork, seconds)) = kupcake_deploy::parse_hardfork_offset(entry) {
+            builder = builder.hardfork_offset(fork, seconds);
+        }
+    }
+    for entry in config.service_log_levels.iter().flatten() {
+        if let Ok((service, level)) = kupcake_deploy::logs::parse_service_log_level(entry) {
+            builder = builder.service_log_level(service, level);
+        }
+    }
+
+    for &chain_id in config.additional_l2_chains.iter().flatten() {
+        builder = builder.add_l2_chain(chain_id, L2ChainConfig::default());
+    }
+
     builder
 }
 
@@ -286,6 +502,70 @@ fn parse_deployment_target(s: &str) -> DeploymentTarget {
     }
 }
 
+fn parse_consensus_client(s: &str) -> ConsensusClient {
+    s.to_ascii_lowercase().parse().unwrap_or_default()
+}
+
+/// The `--restart-policy`, already validated by [`resolve_deploy_config`].
+fn parse_restart_policy(config: &DeployConfig) -> RestartPolicy {
+    config
+        .restart_policy
+        .as_deref()
+        .and_then(|policy| policy.parse().ok())
+        .unwrap_or_default()
+}
+
+fn parse_da_type(s: &str) -> DataAvailabilityType {
+    s.to_ascii_lowercase().parse().unwrap_or_default()
+}
+
+fn parse_pruning(s: &str) -> PruningMode {
+    s.to_ascii_lowercase().parse().unwrap_or_default()
+}
+
+fn parse_name_collision(s: &str) -> NameCollision {
+    s.to_ascii_lowercase().parse().unwrap_or_default()
+}
+
+/// The outbound proxy: `--proxy` for both HTTP and HTTPS, otherwise the standard proxy
+/// environment variables. `--no-proxy` replaces the `NO_PROXY` hosts.
+fn proxy_config(config: &DeployConfig) -> Option<ProxyConfig> {
+    let mut proxy = match config.proxy {
+        Some(ref url) => ProxyConfig {
+            http_proxy: Some(url.clone()),
+            https_proxy: Some(url.clone()),
+            no_proxy: Vec::new(),
+        },
+        None => ProxyConfig::from_env()?,
+    };
+    if let Some(ref hosts) = config.no_proxy {
+        proxy.no_proxy = hosts.clone();
+    }
+    Some(proxy)
+}
+
+/// The external L1 of `config`, if its RPC URL and deployer key are set.
+fn external_l1_config(config: &DeployConfig) -> Option<ExternalL1Config> {
+    let mut external_l1 = ExternalL1Config::new(
+        config.external_l1.clone()?,
+        config.external_l1_deployer_key.clone()?,
+    );
+    external_l1.ws_url = config.external_l1_ws.clone();
+    external_l1.batcher_private_key = config.external_l1_batcher_key.clone().map(Into::into);
+    external_l1.proposer_private_key = config.external_l1_proposer_key.clone().map(Into::into);
+    external_l1.challenger_private_key = config.external_l1_challenger_key.clone().map(Into::into);
+    Some(external_l1)
+}
+
+/// `base` with the RPC client settings of `config` applied.
+fn rpc_client_config(config: &DeployConfig, base: RpcClientConfig) -> RpcClientConfig {
+    RpcClientConfig {
+        request_timeout_secs: config.rpc_timeout.unwrap_or(base.request_timeout_secs),
+        connect_timeout_secs: config.rpc_connect_timeout.or(base.connect_timeout_secs),
+        pool_max_idle_per_host: config.rpc_pool_size.or(base.pool_max_idle_per_host),
+    }
+}
+
 /// Build a sparse [`DeployConfig`] containing only the CLI args that were explicitly
 /// provided by the user (not default values).
 ///
@@ -306,18 +586,82 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
     if is_explicit("network") {
         config.network = args.network.clone();
     }
+    if is_explicit("name_collision") {
+        config.name_collision = match args.name_collision {
+            crate::cli::NameCollisionArg::Error => Some("error".to_string()),
+            crate::cli::NameCollisionArg::Warn => Some("warn".to_string()),
+            crate::cli::NameCollisionArg::Suffix => Some("suffix".to_string()),
+        };
+    }
     if is_explicit("l1") {
         config.l1 = args.l1.as_ref().map(|s| s.rpc_url());
     }
+    if is_explicit("external_l1") {
+        config.external_l1 = args.external_l1.clone();
+    }
+    if is_explicit("external_l1_ws") {
+        config.external_l1_ws = args.external_l1_ws.clone();
+    }
+    if is_explicit("external_l1_deployer_key") {
+        config.external_l1_deployer_key = args.external_l1_deployer_key.clone();
+    }
+    if is_explicit("external_l1_batcher_key") {
+        config.external_l1_batcher_key = args.external_l1_batcher_key.clone();
+    }
+    if is_explicit("external_l1_proposer_key") {
+        config.external_l1_proposer_key = args.external_l1_proposer_key.clone();
+    }
+    if is_explicit("external_l1_challenger_key") {
+        config.external_l1_challenger_key = args.external_l1_challenger_key.clone();
+    }
     if is_explicit("l2_chain") {
         config.l2_chain = args.l2_chain.map(|c| c.chain_id());
     }
     if is_explicit("block_time") {
         config.block_time = Some(args.block_time);
     }
+    if is_explicit("l2_block_time") {
+        config.l2_block_time = args.l2_block_time;
+    }
+    if is_explicit("sequencer_fee_recipient") {
+        config.sequencer_fee_recipient = args.sequencer_fee_recipient.clone();
+    }
+    if is_explicit("l2_gas_limit") {
+        config.l2_gas_limit = args.l2_gas_limit;
+    }
+    if is_explicit("eip1559_denominator") {
+        config.eip1559_denominator = args.eip1559_denominator;
+    }
+    if is_explicit("eip1559_elasticity") {
+        config.eip1559_elasticity = args.eip1559_elasticity;
+    }
+    if is_explicit("l1_contracts_locator") {
+        config.l1_contracts_locator = args.l1_contracts_locator.clone();
+    }
+    if is_explicit("l2_contracts_locator") {
+        config.l2_contracts_locator = args.l2_contracts_locator.clone();
+    }
+    if is_explicit("l1_hardfork") {
+        config.l1_hardfork = args.l1_hardfork.clone();
+    }
+    if is_explicit("anvil_mnemonic") {
+        config.anvil_mnemonic = args.anvil_mnemonic.clone();
+    }
+    if is_explicit("anvil_derivation_path") {
+        config.anvil_derivation_path = args.anvil_derivation_path.clone();
+    }
     if is_explicit("genesis_timestamp") {
         config.genesis_timestamp = args.genesis_timestamp;
     }
+    if is_explicit("prefund") {
+        config.prefund = Some(args.prefund.clone());
+    }
+    if is_explicit("hardfork_offsets") {
+        config.hardfork_offsets = Some(args.hardfork_offsets.clone());
+    }
+    if is_explicit("additional_l2_chains") {
+        config.additional_l2_chains = Some(args.additional_l2_chains.clone());
+    }
 
     // L2 Nodes
     if is_explicit("l2_nodes") {
@@ -332,6 +676,22 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
     if is_explicit("proofs_validators") {
         config.proofs_validators = Some(args.proofs_validators);
     }
+    if is_explicit("op_reth_pruning") {
+        config.op_reth_pruning = args
+            .op_reth_pruning
+            .map(|arg| kupcake_deploy::PruningMode::from(arg).to_string());
+    }
+    if is_explicit("preset") {
+        config.preset = args
+            .preset
+            .map(|p| kupcake_deploy::Topology::from(p).to_string());
+    }
+    if is_explicit("consensus_client") {
+        config.consensus_client = match args.consensus_client {
+            crate::cli::ConsensusClientArg::Kona => Some("kona".to_string()),
+            crate::cli::ConsensusClientArg::OpNode => Some("op-node".to_string()),
+        };
+    }
 
     // Deployment
     if is_explicit("deployment_target") {
@@ -340,12 +700,41 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
             crate::cli::DeploymentTargetArg::Genesis => Some("genesis".to_string()),
         };
     }
+    if is_explicit("deterministic") {
+        config.deterministic = args.deterministic;
+    }
     if is_explicit("no_proposer") {
         config.no_proposer = Some(args.no_proposer);
     }
     if is_explicit("no_challenger") {
         config.no_challenger = Some(args.no_challenger);
     }
+    if is_explicit("spare_batchers") {
+        config.spare_batchers = args.spare_batchers;
+    }
+    if is_explicit("batcher_da_type") {
+        config.batcher_da_type = args
+            .batcher_da_type
+            .map(|arg| kupcake_deploy::DataAvailabilityType::from(arg).to_string());
+    }
+    if is_explicit("spare_proposers") {
+        config.spare_proposers = args.spare_proposers;
+    }
+    if is_explicit("alt_da_server") {
+        config.alt_da_server = args.alt_da_server.clone();
+    }
+    if is_explicit("alt_da_challenge_window") {
+        config.alt_da_challenge_window = args.alt_da_challenge_window;
+    }
+    if is_explicit("alt_da_resolve_window") {
+        config.alt_da_resolve_window = args.alt_da_resolve_window;
+    }
+    if is_explicit("keep_going") {
+        config.keep_going = Some(args.keep_going);
+    }
+    if is_explicit("verify_chain_ids") {
+        config.verify_chain_ids = Some(args.verify_chain_ids);
+    }
 
     // State & Storage
     if is_explicit("outdata") {
@@ -377,6 +766,60 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
     if is_explicit("publish_all_ports") {
         config.publish_all_ports = Some(args.publish_all_ports);
     }
+    if is_explicit("fixed_ports") {
+        config.fixed_ports = Some(args.fixed_ports);
+    }
+    if is_explicit("restart_policy") {
+        config.restart_policy = args.restart_policy.clone();
+    }
+    if is_explicit("post_deploy_script") {
+        config.post_deploy_script = args.post_deploy_script.clone();
+    }
+    if is_explicit("extra_hosts") {
+        config.extra_hosts = Some(args.extra_hosts.clone());
+    }
+    if is_explicit("host_gateway") {
+        config.host_gateway = Some(args.host_gateway);
+    }
+    if is_explicit("net_admin") {
+        config.net_admin = Some(args.net_admin);
+    }
+    if is_explicit("pin_image_digests") {
+        config.pin_image_digests = Some(args.pin_image_digests);
+    }
+    if is_explicit("anvil_auto_recover") {
+        config.anvil_auto_recover = Some(args.anvil_auto_recover);
+    }
+    if is_explicit("min_disk_space") {
+        config.min_disk_space = args.min_disk_space;
+    }
+    if is_explicit("ca_bundle") {
+        config.ca_bundle = args.ca_bundle.clone();
+    }
+    if is_explicit("proxy") {
+        config.proxy = args.proxy.clone();
+    }
+    if is_explicit("no_proxy") {
+        config.no_proxy = args.no_proxy.clone();
+    }
+    if is_explicit("network_subnet") {
+        config.network_subnet = args.network_subnet.clone();
+    }
+    if is_explicit("network_gateway") {
+        config.network_gateway = args.network_gateway.clone();
+    }
+    if is_explicit("external_network") {
+        config.external_network = args.external_network.clone();
+    }
+    if is_explicit("rpc_timeout") {
+        config.rpc_timeout = args.rpc_timeout;
+    }
+    if is_explicit("rpc_connect_timeout") {
+        config.rpc_connect_timeout = args.rpc_connect_timeout;
+    }
+    if is_explicit("rpc_pool_size") {
+        config.rpc_pool_size = args.rpc_pool_size;
+    }
 
     // Logging & Monitoring
     if is_explicit("log_max_size") {
@@ -388,9 +831,21 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
     if is_explicit("quiet_services") {
         config.quiet_services = Some(args.quiet_services);
     }
+    if is_explicit("service_log_levels") {
+        config.service_log_levels = Some(args.service_log_levels.clone());
+    }
     if is_explicit("stream_logs") {
         config.stream_logs = Some(args.stream_logs);
     }
+    if is_explicit("expose_metrics") {
+        config.expose_metrics = Some(args.expose_metrics);
+    }
+    if is_explicit("blockscout") {
+        config.blockscout = Some(args.blockscout);
+    }
+    if is_explicit("loki") {
+        config.loki = Some(args.loki);
+    }
     if is_explicit("long_running") {
         config.long_running = Some(args.long_running);
     }
@@ -414,6 +869,12 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
     if is_explicit("kona_node_tag") {
         config.kona_node_tag = Some(args.docker_images.kona_node_tag.clone());
     }
+    if is_explicit("op_node_image") {
+        config.op_node_image = Some(args.docker_images.op_node_image.clone());
+    }
+    if is_explicit("op_node_tag") {
+        config.op_node_tag = Some(args.docker_images.op_node_tag.clone());
+    }
     if is_explicit("op_batcher_image") {
         config.op_batcher_image = Some(args.docker_images.op_batcher_image.clone());
     }
@@ -456,6 +917,24 @@ fn build_cli_overrides(args: &crate::cli::DeployArgs, matches: &clap::ArgMatches
     if is_explicit("grafana_tag") {
         config.grafana_tag = Some(args.docker_images.grafana_tag.clone());
     }
+    if is_explicit("loki_image") {
+        config.loki_image = Some(args.docker_images.loki_image.clone());
+    }
+    if is_explicit("loki_tag") {
+        config.loki_tag = Some(args.docker_images.loki_tag.clone());
+    }
+    if is_explicit("promtail_image") {
+        config.promtail_image = Some(args.docker_images.promtail_image.clone());
+    }
+    if is_explicit("promtail_tag") {
+        config.promtail_tag = Some(args.docker_images.promtail_tag.clone());
+    }
+    if is_explicit("blockscout_image") {
+        config.blockscout_image = Some(args.docker_images.blockscout_image.clone());
+    }
+    if is_explicit("blockscout_tag") {
+        config.blockscout_tag = Some(args.docker_images.blockscout_tag.clone());
+    }
     if is_explicit("op_rbuilder_image") {
         config.op_rbuilder_image = Some(args.docker_images.op_rbuilder_image.clone());
     }
@@ -507,9 +986,109 @@ pub fn apply_cli_overrides(deployer: &mut kupcake_deploy::Deployer, config: &Dep
     if let Some(v) = config.publish_all_ports {
         deployer.docker.publish_all_ports = v;
     }
+    if let Some(ref script) = config.post_deploy_script {
+        deployer.post_deploy = Some(kupcake_deploy::PostDeployScript::new(script));
+    }
+    if let Some(ref hosts) = config.extra_hosts {
+        deployer.docker.extra_hosts = hosts.clone();
+    }
+    if config.host_gateway == Some(true) {
+        let entry = kupcake_deploy::HOST_GATEWAY_ENTRY.to_string();
+        if !deployer.docker.extra_hosts.contains(&entry) {
+            deployer.docker.extra_hosts.push(entry);
+        }
+    }
+    if let Some(v) = config.anvil_auto_recover {
+        deployer.anvil.auto_recover = v;
+    }
+    if let Some(ref entries) = config.prefund {
+        deployer.l2_stack.prefund.clear();
+        for (address, amount) in entries
+            .iter()
+            .filter_map(|e| kupcake_deploy::parse_prefund(e).ok())
+        {
+            deployer.l2_stack = std::mem::take(&mut deployer.l2_stack).prefund(address, amount);
+        }
+    }
+    if let Some(ref entries) = config.hardfork_offsets {
+        let offsets: BTreeMap<String, u64> = entries
+            .iter()
+            .filter_map(|e| kupcake_deploy::parse_hardfork_offset(e).ok())
+            .collect();
+        deployer.l2_stack.hardfork_offsets = offsets.clone();
+        for chain in &mut deployer.additional_l2_chains {
+            chain.l2_stack.hardfork_offsets = offsets.clone();
+        }
+    }
+    for entry in config.service_log_levels.iter().flatten() {
+        if let Ok((service, level)) = kupcake_deploy::logs::parse_service_log_level(entry) {
+            deployer.l2_stack = std::mem::take(&mut deployer.l2_stack).log_level(&service, &level);
+            for chain in &mut deployer.additional_l2_chains {
+                chain.l2_stack = std::mem::take(&mut chain.l2_stack).log_level(&service, &level);
+            }
+        }
+    }
+    if let Some(v) = config.min_disk_space {
+        deployer.min_disk_space_gib = v;
+    }
+    if let Some(ref path) = config.ca_bundle {
+        deployer.docker.ca_bundle = Some(PathBuf::from(path));
+    }
+    if let Some(external_l1) = external_l1_config(config) {
+        deployer.external_l1 = Some(external_l1);
+    }
+    if config.proxy.is_some() || config.no_proxy.is_some() {
+        deployer.docker.proxy = proxy_config(config);
+    }
+    if config.network_subnet.is_some() {
+        deployer.docker.subnet = config.network_subnet.clone();
+    }
+    if config.network_gateway.is_some() {
+        deployer.docker.gateway = config.network_gateway.clone();
+    }
+    if config.external_network.is_some() {
+        deployer.docker.external_network = config.external_network.clone();
+    }
+    deployer.docker.rpc_client = rpc_client_config(config, deployer.docker.rpc_client);
+    if let Some(v) = config.keep_going {
+        deployer.keep_going = v;
+    }
+    if let Some(v) = config.verify_chain_ids {
+        deployer.verify_chain_ids = v;
+    }
     if let Some(v) = config.stream_logs {
         deployer.docker.stream_logs = v;
     }
+    if config.restart_policy.is_some() {
+        deployer.docker.restart_policy = parse_restart_policy(config);
+    }
+    if let Some(v) = config.blockscout {
+        deployer.blockscout.enabled = v;
+    }
+    if let Some(v) = config.loki {
+        deployer.monitoring.logs.enabled = v;
+    }
+    if config.expose_metrics == Some(true) {
+        deployer.l2_stack = std::mem::take(&mut deployer.l2_stack).expose_metrics(true);
+    }
+    if config.net_admin == Some(true) {
+        deployer.l2_stack = std::mem::take(&mut deployer.l2_stack).net_admin(true);
+    }
+    if let Some(ref pruning) = config.op_reth_pruning {
+        deployer.l2_stack =
+            std::mem::take(&mut deployer.l2_stack).op_reth_pruning(parse_pruning(pruning));
+        for chain in &mut deployer.additional_l2_chains {
+            chain.l2_stack =
+                std::mem::take(&mut chain.l2_stack).op_reth_pruning(parse_pruning(pruning));
+        }
+    }
+    if let Some(ref da_type) = config.batcher_da_type {
+        let da_type = parse_da_type(da_type);
+        let stack = &mut deployer.l2_stack;
+        for batcher in std::iter::once(&mut stack.op_batcher).chain(&mut stack.spare_batchers) {
+            batcher.da_type = da_type;
+        }
+    }
 
     // Logging overrides
     if config.log_max_size.is_some() {
@@ -523,6 +1102,36 @@ pub fn apply_cli_overrides(deployer: &mut kupcake_deploy::Deployer, config: &Dep
     if let Some(block_time) = config.block_time {
         deployer.anvil.block_time = block_time;
     }
+    if let Some(l2_block_time) = config.l2_block_time {
+        deployer.op_deployer.l2_block_time = Some(l2_block_time);
+    }
+    if let Some(ref recipient) = config.sequencer_fee_recipient {
+        deployer.op_deployer.sequencer_fee_recipient = Some(recipient.clone());
+    }
+    if let Some(gas_limit) = config.l2_gas_limit {
+        deployer.op_deployer.gas_limit = Some(gas_limit);
+    }
+    if let Some(denominator) = config.eip1559_denominator {
+        deployer.op_deployer.eip1559_denominator = Some(denominator);
+    }
+    if let Some(elasticity) = config.eip1559_elasticity {
+        deployer.op_deployer.eip1559_elasticity = Some(elasticity);
+    }
+    if let Some(ref locator) = config.l1_contracts_locator {
+        deployer.op_deployer.l1_contracts_locator = Some(locator.clone());
+    }
+    if let Some(ref locator) = config.l2_contracts_locator {
+        deployer.op_deployer.l2_contracts_locator = Some(locator.clone());
+    }
+    if let Some(ref hardfork) = config.l1_hardfork {
+        deployer.anvil.hardfork = Some(hardfork.clone());
+    }
+    if let Some(ref mnemonic) = config.anvil_mnemonic {
+        deployer.anvil.mnemonic = Some(mnemonic.clone());
+    }
+    if let Some(ref path) = config.anvil_derivation_path {
+        deployer.anvil.derivation_path = Some(path.clone());
+    }
 }
 
 #[cfg(test)]
@@ -559,6 +1168,22 @@ mod tests {
         let _ = builder;
     }
 
+    #[test]
+    fn test_parse_restart_policy() {
+        let config = DeployConfig {
+            restart_policy: Some("on-failure:3".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_restart_policy(&config),
+            RestartPolicy::OnFailure { max_retries: 3 }
+        );
+        assert_eq!(
+            parse_restart_policy(&DeployConfig::default()),
+            RestartPolicy::No
+        );
+    }
+
     #[test]
     fn test_resolve_long_running() {
         let mut config = DeployConfig {
@@ -586,6 +1211,27 @@ mod tests {
         assert_eq!(config.quiet_services, Some(false)); // explicit false preserved
     }
 
+    #[test]
+    fn test_resolve_preset() {
+        let mut config = DeployConfig {
+            preset: Some("large-fleet".to_string()),
+            block_time: Some(2),
+            ..Default::default()
+        };
+        config.resolve_preset().unwrap();
+        assert_eq!(config.l2_nodes, Some(11));
+        assert_eq!(config.sequencer_count, Some(1));
+        assert_eq!(config.block_time, Some(2)); // explicit value preserved
+        assert_eq!(config.log_max_size.as_deref(), Some("10m"));
+        assert_eq!(config.quiet_services, Some(true));
+
+        let mut config = DeployConfig {
+            preset: Some("tiny".to_string()),
+            ..Default::default()
+        };
+        assert!(config.resolve_preset().is_err());
+    }
+
     #[test]
     fn test_parse_deployment_target() {
         assert!(matches!(
@@ -606,6 +1252,95 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_pruning() {
+        assert_eq!(parse_pruning("archive"), PruningMode::Archive);
+        assert_eq!(parse_pruning("Full"), PruningMode::Full);
+        assert_eq!(parse_pruning("minimal-pruned"), PruningMode::MinimalPruned);
+        assert_eq!(parse_pruning("unknown"), PruningMode::Archive);
+    }
+
+    #[test]
+    fn test_parse_da_type() {
+        assert_eq!(parse_da_type("blobs"), DataAvailabilityType::Blobs);
+        assert_eq!(parse_da_type("Calldata"), DataAvailabilityType::Calldata);
+        assert_eq!(parse_da_type("auto"), DataAvailabilityType::Auto);
+        assert_eq!(parse_da_type("unknown"), DataAvailabilityType::Blobs);
+    }
+
+    #[test]
+    fn test_parse_consensus_client() {
+        assert_eq!(parse_consensus_client("kona"), ConsensusClient::Kona);
+        assert_eq!(parse_consensus_client("op-node"), ConsensusClient::OpNode);
+        assert_eq!(parse_consensus_client("OP-NODE"), ConsensusClient::OpNode);
+        assert_eq!(parse_consensus_client("unknown"), ConsensusClient::Kona);
+    }
+
+    #[test]
+    fn test_parse_name_collision() {
+        assert_eq!(parse_name_collision("suffix"), NameCollision::Suffix);
+        assert_eq!(parse_name_collision("WARN"), NameCollision::Warn);
+        assert_eq!(parse_name_collision("unknown"), NameCollision::Error);
+    }
+
+    #[test]
+    fn test_proxy_config() {
+        let config = DeployConfig {
+            proxy: Some("socks5://127.0.0.1:1080".to_string()),
+            no_proxy: Some(vec![".corp".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            proxy_config(&config),
+            Some(ProxyConfig {
+                http_proxy: Some("socks5://127.0.0.1:1080".to_string()),
+                https_proxy: Some("socks5://127.0.0.1:1080".to_string()),
+                no_proxy: vec![".corp".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_external_l1_config() {
+        assert_eq!(external_l1_config(&DeployConfig::default()), None);
+
+        let config = DeployConfig {
+            external_l1: Some("http://10.0.0.5:8545".to_string()),
+            external_l1_deployer_key: Some("0x01".to_string()),
+            external_l1_proposer_key: Some("0x03".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            external_l1_config(&config),
+            Some(
+                ExternalL1Config::new("http://10.0.0.5:8545", "0x01").proposer_private_key("0x03")
+            )
+        );
+    }
+
+    #[test]
+    fn test_rpc_client_config() {
+        let base = RpcClientConfig {
+            request_timeout_secs: 20,
+            connect_timeout_secs: Some(3),
+            pool_max_idle_per_host: None,
+        };
+        let config = DeployConfig {
+            rpc_timeout: Some(60),
+            rpc_pool_size: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(
+            rpc_client_config(&config, base),
+            RpcClientConfig {
+                request_timeout_secs: 60,
+                connect_timeout_secs: Some(3),
+                pool_max_idle_per_host: Some(8),
+            }
+        );
+        assert_eq!(rpc_client_config(&DeployConfig::default(), base), base);
+    }
+
     #[test]
     fn test_figment_env_override() {
         // Simulate figment merging with an env var
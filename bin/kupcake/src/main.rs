@@ -12,12 +12,15 @@ use clap_complete::CompleteEnv;
 use comfy_table::{Attribute, Cell, Table};
 
 use cli::{
-    BenchArgs, CleanupArgs, Cli, Commands, CompletionsArgs, DeployArgs, FaucetArgs, InspectArgs,
-    L1Source, NodeAction, NodeArgs, PruneArgs, ShellArg, SnapshotArgs, SpamArgs,
+    BenchArgs, CleanupArgs, Cli, Commands, CompletionsArgs, DeployArgs, DoctorArgs, ExportArgs,
+    FaucetArgs, GrafanaAction, GrafanaArgs, HealthArgs, InspectArgs, L1Source, LogFormat, LogsArgs,
+    NodeAction, NodeArgs, PruneArgs, RestartArgs, RpcArgs, ShellArg, SnapshotArgs, SpamArgs,
+    StatusArgs, StopArgs, WithdrawAction, WithdrawArgs,
 };
 use config::{apply_cli_overrides, deploy_config_to_builder, resolve_deploy_config};
 use kupcake_deploy::{
     Deployer, DeployerBuilder, DeploymentResult, KupDocker, SpamPreset, cleanup_by_prefix,
+    list_by_prefix, stop_by_prefix,
 };
 
 #[tokio::main]
@@ -31,12 +34,16 @@ async fn main() -> Result<()> {
     let cli = Cli::from_arg_matches(&raw_matches)?;
 
     // Initialize the logger.
-    tracing_subscriber::fmt()
-        .with_max_level(cli.verbosity)
-        .init();
+    let subscriber = tracing_subscriber::fmt().with_max_level(cli.log_level());
+    match cli.log_format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
 
     match cli.command {
         Some(Commands::Cleanup(args)) => run_cleanup(args).await,
+        Some(Commands::Stop(args)) => run_stop(args).await,
+        Some(Commands::Restart(args)) => run_restart(args).await,
         Some(Commands::Deploy(args)) => {
             // Extract the deploy subcommand's ArgMatches for figment integration
             let deploy_matches = raw_matches
@@ -46,13 +53,21 @@ async fn main() -> Result<()> {
             run_deploy(args, &deploy_matches).await
         }
         Some(Commands::Faucet(args)) => run_faucet(args).await,
+        Some(Commands::Withdraw(args)) => run_withdraw(args).await,
+        Some(Commands::Rpc(args)) => run_rpc(args).await,
+        Some(Commands::Logs(args)) => run_logs(args).await,
         Some(Commands::Inspect(args)) => run_inspect(args).await,
+        Some(Commands::Health(args)) => run_health(args).await,
         Some(Commands::Spam(args)) => run_spam_cmd(args).await,
         Some(Commands::Bench(args)) => run_bench(args).await,
         Some(Commands::Node(args)) => run_node(args).await,
+        Some(Commands::Grafana(args)) => run_grafana(args).await,
         Some(Commands::List) => run_list().await,
+        Some(Commands::Status(args)) => run_status(args).await,
         Some(Commands::Prune(args)) => run_prune(args).await,
         Some(Commands::Snapshot(args)) => run_snapshot(args).await,
+        Some(Commands::Export(args)) => run_export(args),
+        Some(Commands::Doctor(args)) => run_doctor(args).await,
         Some(Commands::Completions(args)) => run_completions(args),
         // Default to deploy with default args when no subcommand is provided
         None => run_deploy(DeployArgs::default(), &clap::ArgMatches::default()).await,
@@ -60,6 +75,25 @@ async fn main() -> Result<()> {
 }
 
 async fn run_cleanup(args: CleanupArgs) -> Result<()> {
+    if args.dry_run {
+        let result = list_by_prefix(&args.prefix).await?;
+        if result.containers_removed.is_empty() && result.network_removed.is_none() {
+            tracing::info!("Nothing matches prefix '{}'", args.prefix);
+            return Ok(());
+        }
+        tracing::info!(
+            "Would remove {} container(s):",
+            result.containers_removed.len()
+        );
+        for name in &result.containers_removed {
+            tracing::info!("  - {}", name);
+        }
+        if let Some(network) = &result.network_removed {
+            tracing::info!("Would remove network: {}", network);
+        }
+        return Ok(());
+    }
+
     tracing::info!("Cleaning up network with prefix: {}", args.prefix);
 
     let result = cleanup_by_prefix(&args.prefix).await?;
@@ -82,6 +116,83 @@ async fn run_cleanup(args: CleanupArgs) -> Result<()> {
     Ok(())
 }
 
+async fn run_stop(args: StopArgs) -> Result<()> {
+    tracing::info!("Stopping network: {}", args.network);
+
+    // With the saved config, the Anvil state is dumped before stopping.
+    let result = match resolve_existing_config(None, Some(&args.network)) {
+        Some(config_path) => Deployer::load_from_file(&config_path)?.stop().await?,
+        None => {
+            tracing::warn!(
+                "No saved configuration for '{}': the Anvil state is not dumped",
+                args.network
+            );
+            stop_by_prefix(&args.network).await?
+        }
+    };
+
+    if result.containers_stopped.is_empty() && result.containers_skipped.is_empty() {
+        tracing::info!("No containers found with prefix '{}'", args.network);
+        return Ok(());
+    }
+    for name in &result.containers_skipped {
+        tracing::debug!("  - {} (not running)", name);
+    }
+    tracing::info!(
+        "Stopped {} container(s), {} already stopped",
+        result.containers_stopped.len(),
+        result.containers_skipped.len()
+    );
+    for name in &result.containers_stopped {
+        tracing::info!("  - {}", name);
+    }
+
+    Ok(())
+}
+
+async fn run_restart(args: RestartArgs) -> Result<()> {
+    let config_path = resolve_config_path(&args.network);
+    let deployer = Deployer::load_from_file(&config_path)?;
+
+    let mut docker = KupDocker::new(kupcake_deploy::KupDockerConfig {
+        no_cleanup: true,
+        ..deployer.docker.clone()
+    })
+    .await?;
+    let container =
+        kupcake_deploy::service_update::restart_service(&deployer, &mut docker, &args.service)
+            .await?;
+
+    tracing::info!("Replaced {}", container);
+    Ok(())
+}
+
+fn run_export(args: ExportArgs) -> Result<()> {
+    let config_path = resolve_config_path(&args.config);
+    let deployer = Deployer::load_from_file(&config_path)?;
+
+    let network_name = deployer
+        .docker
+        .net_name
+        .strip_suffix("-network")
+        .unwrap_or(&deployer.docker.net_name);
+    let output_dir = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}-chain-config", network_name)));
+
+    let exported = deployer.export_chain_config(&output_dir)?;
+    for path in &exported {
+        println!("{}", path.display());
+    }
+    tracing::info!(
+        output = %output_dir.display(),
+        files = exported.len(),
+        "Chain configuration exported"
+    );
+
+    Ok(())
+}
+
 async fn run_snapshot(args: SnapshotArgs) -> Result<()> {
     let config_path = resolve_config_path(&args.config);
     let deployer = Deployer::load_from_file(&config_path)?;
@@ -209,6 +320,49 @@ fn resolve_config_path(config: &str) -> PathBuf {
     }
 }
 
+async fn run_logs(args: LogsArgs) -> Result<()> {
+    let (config, service) = args.config_and_service()?;
+    let config_path = resolve_config_path(config);
+    let deployer = Deployer::load_from_file(&config_path)?;
+
+    let options = kupcake_deploy::logs::LogOptions {
+        since: args.since,
+        tail: args.tail,
+        follow: args.follow,
+        grep: args.grep.clone(),
+    };
+
+    deployer
+        .logs(service, &args.node, &options, &mut std::io::stdout())
+        .await
+}
+
+async fn run_rpc(args: RpcArgs) -> Result<()> {
+    let config_path = resolve_config_path(&args.config);
+    let deployer = Deployer::load_from_file(&config_path)?;
+
+    let service = args.service.parse()?;
+    let params = args
+        .params
+        .iter()
+        .map(|param| kupcake_deploy::service_rpc::parse_param(param))
+        .collect();
+
+    let result = kupcake_deploy::service_rpc::call_service(
+        &deployer,
+        service,
+        &args.node,
+        &args.method,
+        params,
+    )
+    .await?;
+
+    let json = serde_json::to_string_pretty(&result).context("Failed to serialize RPC result")?;
+    println!("{json}");
+
+    Ok(())
+}
+
 async fn run_inspect(args: InspectArgs) -> Result<()> {
     let config_path = resolve_config_path(&args.config);
     let deployer = Deployer::load_from_file(&config_path)?;
@@ -228,6 +382,129 @@ async fn run_inspect(args: InspectArgs) -> Result<()> {
     Ok(())
 }
 
+async fn run_health(args: HealthArgs) -> Result<()> {
+    let config_path = resolve_config_path(&args.network);
+    let deployer = Deployer::load_from_file(&config_path)?;
+
+    if args.watch {
+        return watch_health(&deployer, &args).await;
+    }
+
+    let report = kupcake_deploy::health::health_check(&deployer).await?;
+
+    if args.json {
+        let json =
+            serde_json::to_string_pretty(&report).context("Failed to serialize health report")?;
+        println!("{json}");
+    } else {
+        print!("{report}");
+    }
+
+    if !report.healthy {
+        anyhow::bail!("Network {} is unhealthy", args.network);
+    }
+
+    Ok(())
+}
+
+/// Re-run the health check every `--interval` seconds until Ctrl+C.
+///
+/// Nodes and services are listed in config order, so frames line up from one check to
+/// the next. The head history kept across checks also flags stalled nodes.
+async fn watch_health(deployer: &Deployer, args: &HealthArgs) -> Result<()> {
+    use std::io::{IsTerminal, Write};
+
+    use kupcake_deploy::{
+        head_tracker::HeadTracker,
+        health::{HealthUptime, health_check_with_tracker},
+    };
+
+    let interval = std::time::Duration::from_secs(args.interval);
+    let clear_screen = !args.json && std::io::stdout().is_terminal();
+    let mut tracker = HeadTracker::default();
+    let mut uptime = HealthUptime::default();
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        let report = tokio::select! {
+            signal = &mut ctrl_c => {
+                signal?;
+                break;
+            }
+            report = health_check_with_tracker(deployer, &mut tracker) => report?,
+        };
+        uptime.record(report.healthy);
+
+        if args.json {
+            let json =
+                serde_json::to_string(&report).context("Failed to serialize health report")?;
+            println!("{json}");
+        } else {
+            if clear_screen {
+                // Clear the screen and move the cursor to the top-left corner.
+                print!("\x1b[2J\x1b[H");
+            }
+            println!(
+                "Checking {} every {}s, press Ctrl+C to stop.\n",
+                args.network, args.interval
+            );
+            print!("{report}");
+            println!("\n{uptime}");
+        }
+        std::io::stdout().flush()?;
+
+        tokio::select! {
+            signal = &mut ctrl_c => {
+                signal?;
+                break;
+            }
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+
+    uptime.finish();
+    if args.json {
+        eprintln!("{uptime}");
+    } else {
+        println!("\n{uptime}");
+    }
+
+    Ok(())
+}
+
+async fn run_doctor(args: DoctorArgs) -> Result<()> {
+    use kupcake_deploy::doctor::{DoctorTarget, diagnose};
+
+    let target = match args.config {
+        Some(ref config) => {
+            let deployer = Deployer::load_from_file(&resolve_config_path(config))?;
+            DoctorTarget::from_deployer(&deployer)
+        }
+        None => DoctorTarget {
+            l1_rpc: args.l1.as_ref().map(|l1| (l1.rpc_url(), l1.chain_id())),
+            ..DoctorTarget::defaults(".")
+        },
+    };
+
+    let report = diagnose(&target).await;
+
+    if args.json {
+        let json =
+            serde_json::to_string_pretty(&report).context("Failed to serialize doctor report")?;
+        println!("{json}");
+    } else {
+        print!("{report}");
+    }
+
+    if report.has_failures() {
+        anyhow::bail!("Some checks failed, see the hints above");
+    }
+
+    Ok(())
+}
+
 async fn run_node(args: NodeArgs) -> Result<()> {
     let config_path = resolve_config_path(&args.config);
     let mut deployer = Deployer::load_from_file(&config_path)?;
@@ -285,6 +562,40 @@ async fn run_node(args: NodeArgs) -> Result<()> {
     Ok(())
 }
 
+async fn run_grafana(args: GrafanaArgs) -> Result<()> {
+    let config_path = resolve_config_path(&args.config);
+    let deployer = Deployer::load_from_file(&config_path)?;
+
+    if !deployer.monitoring.enabled {
+        anyhow::bail!("Monitoring is disabled for this network");
+    }
+
+    let grafana = deployer.monitoring.running_grafana().await?;
+
+    match args.action {
+        GrafanaAction::Import { file } => {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let dashboard = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+            let imported = grafana.import_dashboard(dashboard).await?;
+            let base_url = grafana.host_url.as_ref().unwrap_or(&grafana.url);
+            let url = base_url
+                .join(imported.url.trim_start_matches('/'))
+                .context("Failed to build dashboard URL")?;
+            tracing::info!(
+                uid = %imported.uid,
+                version = imported.version,
+                %url,
+                "Dashboard imported"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 async fn run_faucet(args: FaucetArgs) -> Result<()> {
     let config_path = resolve_config_path(&args.config);
 
@@ -293,18 +604,41 @@ async fn run_faucet(args: FaucetArgs) -> Result<()> {
     tracing::info!(
         config = %config_path.display(),
         to = %args.to,
-        amount = args.amount,
+        amount = %args.amount,
         wait = args.wait,
+        wait_timeout = args.wait_timeout,
+        l1_confirmations = args.l1_confirmations,
         "Running faucet deposit..."
     );
 
     let docker = KupDocker::new(deployer.docker.clone()).await?;
-    let result = kupcake_deploy::faucet::faucet_deposit(
+    let options = kupcake_deploy::faucet::FaucetOptions {
+        wait: args.wait,
+        wait_timeout_secs: args.wait_timeout,
+        l1_confirmations: args.l1_confirmations,
+    };
+
+    if let (Some(l1_token), Some(l2_token), Some(amount)) =
+        (&args.token, &args.l2_token, args.token_amount)
+    {
+        let result = kupcake_deploy::faucet::faucet_deposit_erc20_with_options(
+            &docker, &deployer, l1_token, l2_token, &args.to, amount, options,
+        )
+        .await?;
+
+        tracing::info!(tx_hash = %result.l1_tx_hash, "ERC-20 deposit sent on L1");
+        if let Some(balance) = result.l2_balance {
+            tracing::info!(l2_balance = %balance, "Token deposit confirmed on L2");
+        }
+        return Ok(());
+    }
+
+    let result = kupcake_deploy::faucet::faucet_deposit_with_options(
         &docker,
         &deployer,
         &args.to,
         args.amount,
-        args.wait,
+        options,
     )
     .await?;
 
@@ -312,6 +646,58 @@ async fn run_faucet(args: FaucetArgs) -> Result<()> {
     if let Some(balance) = result.l2_balance {
         tracing::info!(l2_balance = %balance, "Deposit confirmed on L2");
     }
+    if let Some(l2_tx_hash) = result.l2_tx_hash {
+        tracing::info!(l2_tx_hash = %l2_tx_hash, "L2 deposit transaction");
+    }
+
+    Ok(())
+}
+
+async fn run_withdraw(args: WithdrawArgs) -> Result<()> {
+    use kupcake_deploy::withdraw;
+
+    let config_path = resolve_config_path(&args.config);
+    let deployer = Deployer::load_from_file(&config_path)?;
+    let docker = KupDocker::new(deployer.docker.clone()).await?;
+
+    match args.action {
+        WithdrawAction::Initiate { key, amount, wait } => {
+            tracing::info!(config = %config_path.display(), amount = %amount, wait, "Initiating withdrawal...");
+            let result = withdraw::initiate(&docker, &deployer, &key, amount, wait).await?;
+            tracing::info!(
+                l2_tx_hash = %result.l2_tx_hash,
+                l2_block = result.l2_block,
+                "Withdrawal initiated on L2"
+            );
+            if result.provable {
+                tracing::info!("A dispute game covers the withdrawal, it can be proven now");
+            }
+            println!("{}", result.withdrawal_hash);
+        }
+        WithdrawAction::Prove { withdrawal_hash } => {
+            let result = withdraw::prove(&docker, &deployer, &withdrawal_hash).await?;
+            tracing::info!(
+                tx_hash = %result.l1_tx_hash,
+                dispute_game = %result.dispute_game,
+                l2_block = result.l2_block,
+                "Withdrawal proven on L1"
+            );
+        }
+        WithdrawAction::Finalize {
+            withdrawal_hash,
+            skip_challenge_period,
+        } => {
+            let result =
+                withdraw::finalize(&docker, &deployer, &withdrawal_hash, skip_challenge_period)
+                    .await?;
+            tracing::info!(
+                tx_hash = %result.l1_tx_hash,
+                target = %result.target,
+                value_wei = %result.value,
+                "Withdrawal finalized on L1"
+            );
+        }
+    }
 
     Ok(())
 }
@@ -320,17 +706,27 @@ async fn run_spam_cmd(args: SpamArgs) -> Result<()> {
     let config_path = resolve_config_path(&args.config);
     let deployer = Deployer::load_from_file(&config_path)?;
 
+    let report_path = match args.report_file {
+        Some(ref path) => Some(path.clone()),
+        None if args.report => Some(
+            deployer
+                .outdata
+                .join(kupcake_deploy::spam::SPAM_REPORT_PATH),
+        ),
+        None => None,
+    };
     let spam_config = args.into_config(&deployer)?;
 
     tracing::info!(
         config = %config_path.display(),
         scenario = %spam_config.scenario,
+        scenario_file = ?spam_config.scenario_file,
         tps = spam_config.tps,
         "Running spam..."
     );
 
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
-    kupcake_deploy::spam::run_spam(&mut docker, &deployer, &spam_config).await?;
+    kupcake_deploy::spam::run_spam(&mut docker, &deployer, &spam_config, report_path).await?;
     Ok(())
 }
 
@@ -387,6 +783,8 @@ fn apply_image_overrides(
         .op_reth_tag(images.op_reth_tag.clone())
         .kona_node_image(images.kona_node_image.clone())
         .kona_node_tag(images.kona_node_tag.clone())
+        .op_node_image(images.op_node_image.clone())
+        .op_node_tag(images.op_node_tag.clone())
         .op_batcher_image(images.op_batcher_image.clone())
         .op_batcher_tag(images.op_batcher_tag.clone())
         .op_proposer_image(images.op_proposer_image.clone())
@@ -455,6 +853,7 @@ async fn run_deploy(args: DeployArgs, deploy_matches: &clap::ArgMatches) -> Resu
 
     // Resolve layered config: defaults → env vars → CLI args
     let mut deploy_config = resolve_deploy_config(&args, deploy_matches)?;
+    deploy_config.resolve_preset()?;
     deploy_config.resolve_long_running();
 
     let metrics_file = args.metrics_file.as_ref().map(PathBuf::from);
@@ -467,8 +866,17 @@ async fn run_deploy(args: DeployArgs, deploy_matches: &clap::ArgMatches) -> Resu
     if let Some(config_path) = resolved_config_path {
         let mut deployer = Deployer::load_from_file(&config_path)?;
 
+        let stored_outdata = deployer.outdata.clone();
+        deployer.check_data_dir(&config_path, args.rebase_data_dir)?;
+        if deployer.outdata != stored_outdata {
+            deployer.save_config()?;
+        }
+
         // Apply CLI overrides to the loaded config (new: CLI args no longer silently ignored)
         apply_cli_overrides(&mut deployer, &deploy_config);
+        if deploy_config.fixed_ports == Some(true) {
+            deployer.fix_host_ports()?;
+        }
 
         tracing::info!(
             config_path = %config_path.display(),
@@ -478,20 +886,37 @@ async fn run_deploy(args: DeployArgs, deploy_matches: &clap::ArgMatches) -> Resu
             "Loading deployment from config file..."
         );
 
+        deployer.reuse_if_healthy = args.reuse_if_healthy;
+        deployer.restart_unhealthy = args.restart_unhealthy;
+
+        // A reused network is left as is, even if its config changed.
+        if !args.reuse_if_healthy && !args.redeploy && update_running_network(&deployer).await? {
+            return Ok(());
+        }
+
         if let Some(preset) = spam_preset {
             let user_no_cleanup = deployer.docker.no_cleanup;
             deployer.docker.no_cleanup = true;
             let mut docker = KupDocker::new(deployer.docker.clone()).await?;
-            let result = deployer.deploy(&mut docker, args.redeploy, false).await?;
+            let Some(result) = deployer.deploy(&mut docker, args.redeploy).await? else {
+                warn_output_files_skipped(&metrics_file, &ports_file);
+                // The reused network was not started by this run: leave it running.
+                return run_spam_after_deploy(&config_path, preset, true).await;
+            };
             write_output_files(&result, &metrics_file, &ports_file)?;
 
             return run_spam_after_deploy(&config_path, preset, user_no_cleanup).await;
         }
 
         let mut docker = KupDocker::new(deployer.docker.clone()).await?;
-        let result = deployer.deploy(&mut docker, args.redeploy, true).await?;
+        let watcher = deployer.clone();
+        let Some(mut result) = deployer.deploy(&mut docker, args.redeploy).await? else {
+            warn_output_files_skipped(&metrics_file, &ports_file);
+            return Ok(());
+        };
         write_output_files(&result, &metrics_file, &ports_file)?;
-        return Ok(());
+        watcher.persist_host_ports(&result)?;
+        return wait_for_exit(&watcher, &mut result, &mut docker).await;
     }
 
     // Validate: --snapshot requires --l1 (fork mode)
@@ -502,8 +927,15 @@ async fn run_deploy(args: DeployArgs, deploy_matches: &clap::ArgMatches) -> Resu
     }
 
     // Determine L1 chain ID and RPC URL
-    let l1_source = args.l1;
-    let (l1_chain_id, l1_rpc_url) = resolve_l1_config(l1_source).await?;
+    let (l1_chain_id, l1_rpc_url) = match deploy_config.external_l1 {
+        // An external L1 is used as-is: its chain ID is detected, nothing is forked.
+        Some(ref rpc_url) => {
+            let chain_id = fetch_chain_id(rpc_url).await?;
+            tracing::info!(l1_chain_id = chain_id, rpc_url = %rpc_url, "Using external L1");
+            (chain_id, None)
+        }
+        None => resolve_l1_config(args.l1).await?,
+    };
 
     // Force no_cleanup when spam or detach mode
     if spam_preset.is_some() {
@@ -521,7 +953,7 @@ async fn run_deploy(args: DeployArgs, deploy_matches: &clap::ArgMatches) -> Resu
 
     if let Some(preset) = spam_preset {
         let mut docker = KupDocker::new(deployer.docker.clone()).await?;
-        let result = deployer.deploy(&mut docker, args.redeploy, false).await?;
+        let result = deployer.deploy_detached(&mut docker, args.redeploy).await?;
         write_output_files(&result, &metrics_file, &ports_file)?;
         return run_spam_after_deploy(
             &config_path,
@@ -532,10 +964,71 @@ async fn run_deploy(args: DeployArgs, deploy_matches: &clap::ArgMatches) -> Resu
     }
 
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
-    let result = deployer.deploy(&mut docker, args.redeploy, true).await?;
+    let watcher = deployer.clone();
+    let mut result = deployer.deploy_detached(&mut docker, args.redeploy).await?;
     write_output_files(&result, &metrics_file, &ports_file)?;
+    // Redeploying from the saved config publishes on the same host ports.
+    watcher.persist_host_ports(&result)?;
 
-    Ok(())
+    wait_for_exit(&watcher, &mut result, &mut docker).await
+}
+
+/// Keep a deployed network in the foreground until Ctrl+C, or print how to manage it
+/// and return in detached mode.
+///
+/// The containers are removed when `docker` is dropped by the caller, unless
+/// `no_cleanup` is set.
+async fn wait_for_exit(
+    deployer: &Deployer,
+    result: &mut DeploymentResult,
+    docker: &mut KupDocker,
+) -> Result<()> {
+    if deployer.detach {
+        result.print_detached_info(&deployer.outdata, &docker.network_id);
+        return Ok(());
+    }
+
+    tracing::info!("Press Ctrl+C to stop all nodes and cleanup.");
+    tokio::select! {
+        signal = tokio::signal::ctrl_c() => Ok(signal?),
+        watch = deployer.watch(result, docker) => watch,
+    }
+}
+
+/// Apply config changes to the network from a loaded config if it is already running.
+///
+/// Returns `true` if the running network is up to date (possibly after recreating the
+/// services whose config changed), `false` if it is not running and should be deployed.
+async fn update_running_network(deployer: &Deployer) -> Result<bool> {
+    use kupcake_deploy::service_update::{UpdateOutcome, update_changed_services};
+
+    let network = &deployer.docker.net_name;
+    let mut docker = KupDocker::new(kupcake_deploy::KupDockerConfig {
+        no_cleanup: true,
+        ..deployer.docker.clone()
+    })
+    .await?;
+    match update_changed_services(deployer, &mut docker).await? {
+        UpdateOutcome::NotRunning => Ok(false),
+        UpdateOutcome::Unchanged => {
+            tracing::info!(network, "Network is already running with this config");
+            Ok(true)
+        }
+        UpdateOutcome::Updated(containers) => {
+            tracing::info!(
+                network,
+                ?containers,
+                "Recreated services with a changed config"
+            );
+            Ok(true)
+        }
+        UpdateOutcome::NeedsRedeploy { reason } => anyhow::bail!(
+            "Network '{}' is running but {}, which cannot be applied in place. \
+             Re-run with --redeploy, or remove it with `kupcake cleanup`",
+            network,
+            reason
+        ),
+    }
 }
 
 async fn run_list() -> Result<()> {
@@ -575,6 +1068,32 @@ async fn run_list() -> Result<()> {
     Ok(())
 }
 
+async fn run_status(args: StatusArgs) -> Result<()> {
+    if let Some(config) = args.network {
+        return run_inspect(InspectArgs {
+            config,
+            json: args.json,
+            verbose: false,
+            service: None,
+        })
+        .await;
+    }
+
+    let networks = kupcake_deploy::status::running_networks(&args.prefix).await?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&networks)
+            .context("Failed to serialize network status")?;
+        println!("{json}");
+    } else if networks.is_empty() {
+        println!("No networks with prefix '{}'.", args.prefix);
+    } else {
+        print!("{}", kupcake_deploy::status::RunningNetworks(&networks));
+    }
+
+    Ok(())
+}
+
 async fn run_prune(args: PruneArgs) -> Result<()> {
     let registry = kupcake_deploy::DevnetRegistry::new()?;
     let entries = registry.list()?;
@@ -629,6 +1148,17 @@ fn write_output_files(
     Ok(())
 }
 
+/// Warn that the metrics and ports files are not written for a reused network, whose
+/// deployment result is not available.
+fn warn_output_files_skipped(metrics_file: &Option<PathBuf>, ports_file: &Option<PathBuf>) {
+    for path in [metrics_file, ports_file].into_iter().flatten() {
+        tracing::warn!(
+            path = %path.display(),
+            "Network was reused, not writing the deployment output file"
+        );
+    }
+}
+
 /// Run spam after a successful deployment, then clean up if needed.
 ///
 /// Reloads the deployer from the saved config, creates a SpamConfig from the preset,
@@ -655,7 +1185,8 @@ async fn run_spam_after_deploy(
     );
 
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
-    let spam_result = kupcake_deploy::spam::run_spam(&mut docker, &deployer, &spam_config).await;
+    let spam_result =
+        kupcake_deploy::spam::run_spam(&mut docker, &deployer, &spam_config, None).await;
 
     // Clean up deployment containers if the user didn't explicitly set --no-cleanup
     if !user_no_cleanup {
@@ -13,18 +13,35 @@ use crate::AccountInfo;
 pub const ANVIL_DEFAULT_MNEMONIC: &str =
     "test test test test test test test test test test test junk";
 
+/// The standard Ethereum HD derivation path, without the account index.
+///
+/// Anvil appends the account index to its `--derivation-path`, which defaults to this.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/";
+
 /// Derive Ethereum accounts from a BIP-39 mnemonic phrase.
 ///
 /// Uses the standard Ethereum HD derivation path `m/44'/60'/0'/0/{index}`
 /// to derive `count` accounts. The resulting accounts match those generated
 /// by Anvil when started with the same mnemonic.
 pub fn derive_accounts_from_mnemonic(mnemonic: &str, count: usize) -> Result<Vec<AccountInfo>> {
+    derive_accounts_from_mnemonic_with_path(mnemonic, DEFAULT_DERIVATION_PATH, count)
+}
+
+/// Derive Ethereum accounts from a BIP-39 mnemonic phrase and a derivation path.
+///
+/// The account index is appended to `derivation_path`, as Anvil does with its
+/// `--derivation-path` flag, so `derivation_path` should end with a `/`.
+pub fn derive_accounts_from_mnemonic_with_path(
+    mnemonic: &str,
+    derivation_path: &str,
+    count: usize,
+) -> Result<Vec<AccountInfo>> {
     (0..count)
         .map(|index| {
             let wallet = MnemonicBuilder::<English>::default()
                 .phrase(mnemonic)
-                .index(index as u32)
-                .context("Failed to set derivation index")?
+                .derivation_path(format!("{}{}", derivation_path, index))
+                .with_context(|| format!("Invalid derivation path '{}'", derivation_path))?
                 .build()
                 .context("Failed to derive wallet from mnemonic")?;
 
@@ -67,6 +84,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_derive_accounts_with_path() {
+        let default = derive_accounts_from_mnemonic(ANVIL_DEFAULT_MNEMONIC, 2).unwrap();
+        let explicit = derive_accounts_from_mnemonic_with_path(
+            ANVIL_DEFAULT_MNEMONIC,
+            DEFAULT_DERIVATION_PATH,
+            2,
+        )
+        .unwrap();
+        assert_eq!(default[1].address, explicit[1].address);
+
+        let other =
+            derive_accounts_from_mnemonic_with_path(ANVIL_DEFAULT_MNEMONIC, "m/44'/60'/1'/0/", 2)
+                .unwrap();
+        assert_ne!(default[0].address, other[0].address);
+
+        assert!(derive_accounts_from_mnemonic_with_path(ANVIL_DEFAULT_MNEMONIC, "x/", 1).is_err());
+    }
+
     #[test]
     fn test_derive_accounts_deterministic() {
         let accounts1 = derive_accounts_from_mnemonic(ANVIL_DEFAULT_MNEMONIC, 5).unwrap();
@@ -0,0 +1,191 @@
+//! Alt-DA (alternative data availability) configuration.
+//!
+//! With alt-DA, op-batcher stores the batch data on a DA server and only posts its
+//! keccak commitment to L1; op-node fetches the data back from the same server. Anyone
+//! can challenge a commitment on the L1 `DataAvailabilityChallenge` contract during the
+//! challenge window, after which the data must be resolved on L1 within the resolve
+//! window. Both windows are set at contract deployment and recorded in `rollup.json`.
+//!
+//! The defaults are short so that challenges can be exercised within a test. Only op-node
+//! supports alt-DA: kona-node does not implement it.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Default challenge window, in L1 blocks.
+pub const DEFAULT_CHALLENGE_WINDOW: u64 = 16;
+/// Default resolve window, in L1 blocks.
+pub const DEFAULT_RESOLVE_WINDOW: u64 = 16;
+/// Default bond required to challenge a commitment, in wei.
+pub const DEFAULT_BOND_SIZE: u64 = 1_000_000;
+
+fn default_challenge_window() -> u64 {
+    DEFAULT_CHALLENGE_WINDOW
+}
+
+fn default_resolve_window() -> u64 {
+    DEFAULT_RESOLVE_WINDOW
+}
+
+fn default_bond_size() -> u64 {
+    DEFAULT_BOND_SIZE
+}
+
+/// Alt-DA settings of a deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AltDaConfig {
+    /// URL of the DA server, reachable from the op-batcher and op-node containers.
+    pub da_server: String,
+    /// Number of L1 blocks during which a commitment can be challenged.
+    #[serde(default = "default_challenge_window")]
+    pub challenge_window: u64,
+    /// Number of L1 blocks during which a challenged commitment can be resolved.
+    #[serde(default = "default_resolve_window")]
+    pub resolve_window: u64,
+    /// Bond required to challenge a commitment, in wei.
+    #[serde(default = "default_bond_size")]
+    pub bond_size: u64,
+    /// Percentage of the resolving cost refunded to the resolver from the bond.
+    #[serde(default)]
+    pub resolver_refund_percentage: u64,
+}
+
+impl AltDaConfig {
+    /// Alt-DA with the DA server at `da_server` and the default windows.
+    pub fn new(da_server: impl Into<String>) -> Self {
+        Self {
+            da_server: da_server.into(),
+            challenge_window: DEFAULT_CHALLENGE_WINDOW,
+            resolve_window: DEFAULT_RESOLVE_WINDOW,
+            bond_size: DEFAULT_BOND_SIZE,
+            resolver_refund_percentage: 0,
+        }
+    }
+
+    /// Set the challenge window, in L1 blocks.
+    pub fn challenge_window(mut self, blocks: u64) -> Self {
+        self.challenge_window = blocks;
+        self
+    }
+
+    /// Set the resolve window, in L1 blocks.
+    pub fn resolve_window(mut self, blocks: u64) -> Self {
+        self.resolve_window = blocks;
+        self
+    }
+
+    /// Set the challenge bond, in wei.
+    pub fn bond_size(mut self, wei: u64) -> Self {
+        self.bond_size = wei;
+        self
+    }
+
+    /// Set the percentage of the resolving cost refunded to the resolver.
+    pub fn resolver_refund_percentage(mut self, percentage: u64) -> Self {
+        self.resolver_refund_percentage = percentage;
+        self
+    }
+
+    /// The intent's `dangerousAltDAConfig` table, read by op-deployer to deploy the
+    /// `DataAvailabilityChallenge` contract.
+    pub(crate) fn intent_table(&self) -> Result<toml::Table> {
+        let integer = |value: u64, name: &str| {
+            i64::try_from(value)
+                .map(toml::Value::Integer)
+                .with_context(|| format!("alt-DA {} overflows i64", name))
+        };
+        Ok(toml::Table::from_iter([
+            ("useAltDA".to_string(), toml::Value::Boolean(true)),
+            (
+                "daCommitmentType".to_string(),
+                toml::Value::String("KeccakCommitment".to_string()),
+            ),
+            (
+                "daChallengeWindow".to_string(),
+                integer(self.challenge_window, "challenge window")?,
+            ),
+            (
+                "daResolveWindow".to_string(),
+                integer(self.resolve_window, "resolve window")?,
+            ),
+            (
+                "daBondSize".to_string(),
+                integer(self.bond_size, "bond size")?,
+            ),
+            (
+                "daResolverRefundPercentage".to_string(),
+                integer(
+                    self.resolver_refund_percentage,
+                    "resolver refund percentage",
+                )?,
+            ),
+        ]))
+    }
+
+    /// Check that the `alt_da` section of `rollup.json`, i.e. what op-node runs with,
+    /// matches the windows deployed by op-deployer from this configuration.
+    pub(crate) fn check_rollup(&self, rollup: &Value) -> Result<()> {
+        let alt_da = rollup
+            .get("alt_da")
+            .filter(|alt_da| alt_da.is_object())
+            .context("rollup.json has no alt_da section: was alt-DA enabled in the intent?")?;
+
+        for (key, expected) in [
+            ("da_challenge_window", self.challenge_window),
+            ("da_resolve_window", self.resolve_window),
+        ] {
+            let actual = alt_da[key].as_u64();
+            if actual != Some(expected) {
+                anyhow::bail!(
+                    "rollup.json alt_da.{} is {}, but {} was configured",
+                    key,
+                    actual.map_or_else(|| "missing".to_string(), |v| v.to_string()),
+                    expected
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intent_table() {
+        let table = AltDaConfig::new("http://da-server:3100")
+            .challenge_window(8)
+            .intent_table()
+            .unwrap();
+        assert_eq!(table["useAltDA"].as_bool(), Some(true));
+        assert_eq!(table["daChallengeWindow"].as_integer(), Some(8));
+        assert_eq!(
+            table["daResolveWindow"].as_integer(),
+            Some(DEFAULT_RESOLVE_WINDOW as i64)
+        );
+        assert_eq!(table["daCommitmentType"].as_str(), Some("KeccakCommitment"));
+    }
+
+    #[test]
+    fn test_check_rollup() {
+        let config = AltDaConfig::new("http://da-server:3100");
+        let rollup = serde_json::json!({
+            "alt_da": {
+                "da_commitment_type": "KeccakCommitment",
+                "da_challenge_window": 16,
+                "da_resolve_window": 16,
+            }
+        });
+        config.check_rollup(&rollup).unwrap();
+
+        let err = config
+            .clone()
+            .resolve_window(32)
+            .check_rollup(&rollup)
+            .unwrap_err();
+        assert!(err.to_string().contains("alt_da.da_resolve_window is 16"));
+        assert!(config.check_rollup(&serde_json::json!({})).is_err());
+    }
+}
@@ -189,6 +189,16 @@ async fn run_single_iteration(
         log_max_size: None,
         log_max_file: None,
         stream_logs: false,
+        restart_policy: Default::default(),
+        extra_hosts: Vec::new(),
+        ca_bundle: None,
+        proxy: None,
+        rpc_client: Default::default(),
+        run_as_host_user: crate::default_run_as_host_user(),
+        recorded_host_ports: Default::default(),
+        subnet: None,
+        gateway: None,
+        external_network: None,
     };
 
     let mut docker = KupDocker::new(docker_config)
@@ -196,7 +206,7 @@ async fn run_single_iteration(
         .context("Failed to create Docker client")?;
 
     let result = deployer
-        .deploy(&mut docker, true, false)
+        .deploy_detached(&mut docker, true)
         .await
         .context("Deployment failed")?;
 
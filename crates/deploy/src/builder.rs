@@ -4,23 +4,31 @@
 //! of a [`Deployer`] by handling network name generation, output directory creation,
 //! and genesis timestamp fetching from L1 RPC.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use alloy_core::primitives::Address;
 use anyhow::{Context, Result};
 use rand::Rng;
 use serde::Deserialize;
 
 use crate::{
-    ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG, AnvilConfig, Deployer, DockerImage,
-    GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG, GrafanaConfig, KONA_NODE_DEFAULT_IMAGE,
-    KONA_NODE_DEFAULT_TAG, KonaNodeBuilder, KupDockerConfig, L2NodeBuilder, L2NodeRole,
-    L2StackBuilder, MonitoringConfig, OP_BATCHER_DEFAULT_IMAGE, OP_BATCHER_DEFAULT_TAG,
-    OP_CHALLENGER_DEFAULT_IMAGE, OP_CHALLENGER_DEFAULT_TAG, OP_CONDUCTOR_DEFAULT_IMAGE,
-    OP_CONDUCTOR_DEFAULT_TAG, OP_DEPLOYER_DEFAULT_IMAGE, OP_DEPLOYER_DEFAULT_TAG,
+    ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG, ANVIL_KNOWN_HARDFORKS, AltDaConfig, AnvilConfig,
+    BLOCKSCOUT_DEFAULT_IMAGE, BLOCKSCOUT_DEFAULT_TAG, BlockscoutBuilder, ConsensusClient,
+    DEFAULT_CONSENSUS_METRICS_BASE_PORT, DEFAULT_OP_RETH_METRICS_BASE_PORT, DataAvailabilityType,
+    Deployer, DockerImage, ExternalL1Config, GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG,
+    GrafanaConfig, HOST_GATEWAY_ENTRY, KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG,
+    KonaNodeBuilder, KupDockerConfig, L2_HARDFORKS, L2ChainBuilder, L2NodeBuilder, L2NodeRole,
+    L2StackBuilder, LOKI_DEFAULT_IMAGE, LOKI_DEFAULT_TAG, LogsConfig, LokiConfig, MonitoringConfig,
+    OP_BATCHER_DEFAULT_IMAGE, OP_BATCHER_DEFAULT_TAG, OP_CHALLENGER_DEFAULT_IMAGE,
+    OP_CHALLENGER_DEFAULT_TAG, OP_CONDUCTOR_DEFAULT_IMAGE, OP_CONDUCTOR_DEFAULT_TAG,
+    OP_DEPLOYER_DEFAULT_IMAGE, OP_DEPLOYER_DEFAULT_TAG, OP_NODE_DEFAULT_IMAGE, OP_NODE_DEFAULT_TAG,
     OP_PROPOSER_DEFAULT_IMAGE, OP_PROPOSER_DEFAULT_TAG, OP_RBUILDER_DEFAULT_IMAGE,
     OP_RBUILDER_DEFAULT_TAG, OP_RETH_DEFAULT_IMAGE, OP_RETH_DEFAULT_TAG, OpBatcherBuilder,
     OpChallengerBuilder, OpConductorBuilder, OpDeployerConfig, OpProposerBuilder, OpRethBuilder,
-    PROMETHEUS_DEFAULT_IMAGE, PROMETHEUS_DEFAULT_TAG, PrometheusConfig,
+    PROMETHEUS_DEFAULT_IMAGE, PROMETHEUS_DEFAULT_TAG, PROMTAIL_DEFAULT_IMAGE, PROMTAIL_DEFAULT_TAG,
+    PostDeployScript, PrometheusConfig, PromtailConfig, ProxyConfig, PruningMode, RestartPolicy,
+    RpcClientConfig, default_run_as_host_user, faucet::EthAmount,
     services::kona_node::DEFAULT_FLASHBLOCKS_RELAY_PORT,
     services::op_reth::DEFAULT_FLASHBLOCKS_PORT,
 };
@@ -72,6 +80,228 @@ async fn fetch_latest_block(rpc_url: &str) -> Result<BlockInfo> {
     Ok(json.result)
 }
 
+/// A configuration problem detected by [`DeployerBuilder::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum ConfigError {
+    /// A chain ID is zero.
+    #[display("{field} must be non-zero")]
+    ZeroChainId { field: &'static str },
+    /// L1 and L2 use the same chain ID.
+    #[display("l2_chain_id ({chain_id}) must differ from l1_chain_id")]
+    SameChainIds { chain_id: u64 },
+    /// Block time is zero.
+    #[display("block_time must be at least 1 second")]
+    ZeroBlockTime,
+    /// The L2 block time does not evenly divide the L1 block time.
+    #[display(
+        "l2_block_time ({l2_block_time}s) must be at least 1 second and evenly divide block_time ({block_time}s)"
+    )]
+    InvalidL2BlockTime { l2_block_time: u64, block_time: u64 },
+    /// The Anvil hardfork is not one Anvil accepts.
+    #[display("anvil hardfork {hardfork:?} is not supported; expected one of: {}", ANVIL_KNOWN_HARDFORKS.join(", "))]
+    UnknownHardfork { hardfork: String },
+    /// The Anvil accounts cannot be derived from the mnemonic and derivation path.
+    #[display("invalid anvil mnemonic or derivation path: {reason}")]
+    InvalidAnvilAccounts { reason: String },
+    /// No sequencer is configured.
+    #[display("sequencer_count must be at least 1")]
+    NoSequencers,
+    /// More sequencers than L2 nodes.
+    #[display("sequencer_count ({sequencer_count}) cannot exceed l2_nodes ({l2_node_count})")]
+    TooManySequencers {
+        sequencer_count: usize,
+        l2_node_count: usize,
+    },
+    /// The network name cannot be used as a Docker container name prefix.
+    #[display(
+        "network_name {name:?} is invalid: use only alphanumerics, '_', '.' and '-', starting with an alphanumeric"
+    )]
+    InvalidNetworkName { name: String },
+    /// A Docker image name is empty.
+    #[display("{service} image name must not be empty")]
+    EmptyImage { service: &'static str },
+    /// A Docker image tag is empty or not a valid Docker tag.
+    #[display("{service} image tag {tag:?} is not a valid Docker tag")]
+    InvalidImageTag { service: &'static str, tag: String },
+    /// A Docker image digest is not a `sha256:` content digest.
+    #[display("{service} image digest {digest:?} is not a valid sha256 digest")]
+    InvalidImageDigest {
+        service: &'static str,
+        digest: String,
+    },
+    /// Genesis deployment mode combined with L1 forking.
+    #[display(
+        "Genesis deployment mode is incompatible with L1 forking (--l1). \
+         Genesis mode deploys contracts into the L1 genesis state and requires a local Anvil instance."
+    )]
+    GenesisWithFork,
+    /// The sequencer fee recipient is not an address.
+    #[display("sequencer_fee_recipient {address:?} is not a 0x-prefixed 20-byte address")]
+    InvalidSequencerFeeRecipient { address: String },
+    /// A contract artifacts locator is not understood by op-deployer.
+    #[display(
+        "{layer} contracts locator {locator:?} is invalid: use \"embedded\", tag://, file://, http:// or https://"
+    )]
+    InvalidContractsLocator {
+        layer: &'static str,
+        locator: String,
+    },
+    /// External L1 combined with an option that requires Anvil.
+    #[display("external L1 is incompatible with {option}, which requires a local Anvil instance")]
+    ExternalL1Conflict { option: &'static str },
+    /// External L1 keys cannot be parsed.
+    #[display("invalid external L1 configuration: {reason}")]
+    InvalidExternalL1 { reason: String },
+    /// Genesis deployment mode combined with an external Anvil state.
+    #[display(
+        "Genesis deployment mode is incompatible with --override-state. \
+         Genesis mode boots Anvil from a generated L1 genesis, not an external state file."
+    )]
+    GenesisWithOverrideState,
+    /// Deterministic mode with contracts deployed by transactions on a running L1.
+    #[display(
+        "deterministic mode requires the genesis deployment target: live deployments depend on wall-clock L1 blocks"
+    )]
+    DeterministicWithLiveTarget,
+    /// More spare batchers and proposers than funded extra accounts.
+    #[display(
+        "{requested} spare batchers and proposers need as many extra funded L1 accounts, but only {available} are available"
+    )]
+    NotEnoughSpareAccounts { requested: usize, available: usize },
+    /// An additional L2 chain reuses the chain ID of another chain.
+    #[display("L2 chain ID {chain_id} is used by more than one chain")]
+    DuplicateL2ChainId { chain_id: u64 },
+    /// Additional L2 chains combined with an option that only supports a single chain.
+    #[display("additional L2 chains are incompatible with {option}")]
+    AdditionalL2ChainConflict { option: &'static str },
+    /// More additional L2 chains than funded extra accounts for their submitters.
+    #[display(
+        "{chains} additional L2 chains and {spares} spare batchers and proposers need {requested} extra funded L1 accounts, but only {available} are available"
+    )]
+    NotEnoughL2ChainAccounts {
+        chains: usize,
+        spares: usize,
+        requested: usize,
+        available: usize,
+    },
+    /// RPC client configured with a zero timeout.
+    #[display("the RPC {which} timeout must be at least one second")]
+    ZeroRpcTimeout { which: &'static str },
+    /// Spare proposers requested with op-proposer disabled.
+    #[display("spare proposers require op-proposer, which is disabled")]
+    SpareProposersWithoutProposer,
+    /// Alt-DA combined with kona-node, which does not implement it.
+    #[display("alt-DA requires the op-node consensus client: kona-node does not support it")]
+    AltDaRequiresOpNode,
+    /// Alt-DA with an empty challenge or resolve window, or a refund above 100%.
+    #[display("invalid alt-DA configuration: {reason}")]
+    InvalidAltDa { reason: String },
+    /// L2 gas limit outside the range accepted by the `SystemConfig` contract.
+    #[display("gas_limit ({gas_limit}) must be between {MIN_L2_GAS_LIMIT} and {MAX_L2_GAS_LIMIT}")]
+    InvalidGasLimit { gas_limit: u64 },
+    /// EIP-1559 parameter that is zero or does not fit the `SystemConfig`'s `uint32`.
+    #[display("{field} ({value}) must be between 1 and {}", u32::MAX)]
+    InvalidEip1559Param { field: &'static str, value: u64 },
+    /// A hardfork offset names a fork that is not an L2 hardfork.
+    #[display(
+        "L2 hardfork {fork:?} is not supported; expected one of: {}",
+        L2_HARDFORKS.iter().map(|(name, _, _)| *name).collect::<Vec<_>>().join(", ")
+    )]
+    UnknownL2Hardfork { fork: String },
+    /// A hardfork is offset to activate before an earlier one.
+    #[display(
+        "L2 hardfork {fork} (+{offset}s) cannot activate before {previous} (+{previous_offset}s)"
+    )]
+    MisorderedL2Hardfork {
+        fork: String,
+        offset: u64,
+        previous: String,
+        previous_offset: u64,
+    },
+    /// Invalid subnet, gateway or external Docker network.
+    #[display("invalid Docker network configuration: {reason}")]
+    InvalidDockerNetwork { reason: String },
+    /// Log aggregation requested with monitoring disabled.
+    #[display("log aggregation requires monitoring, which is disabled")]
+    LogsWithoutMonitoring,
+    /// Unknown service or log level in a log level override.
+    #[display("invalid log level override: {reason}")]
+    InvalidServiceLogLevel { reason: String },
+    /// Two services are published on the same host port.
+    #[display("conflicting host ports: {reason}")]
+    ConflictingHostPorts { reason: String },
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Smallest L2 gas limit accepted by the `SystemConfig` contract (its maximum resource
+/// limit plus the system transaction gas).
+pub const MIN_L2_GAS_LIMIT: u64 = 21_000_000;
+/// Largest L2 gas limit accepted by the `SystemConfig` contract.
+pub const MAX_L2_GAS_LIMIT: u64 = 500_000_000;
+
+/// Whether `name` is usable as a Docker container/network name prefix.
+fn is_valid_docker_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Whether `tag` is a valid Docker tag (`[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}`).
+fn is_valid_docker_tag(tag: &str) -> bool {
+    let mut chars = tag.chars();
+    tag.len() <= 128
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Apply the [`NameCollision`] policy to `network_name`, returning the name to deploy under.
+///
+/// The check is skipped when Docker is unreachable; the deployment fails later anyway.
+async fn check_name_collision(network_name: String, policy: NameCollision) -> Result<String> {
+    let existing = match crate::list_by_prefix(&network_name).await {
+        Ok(existing) => existing.containers_removed,
+        Err(e) => {
+            tracing::debug!(error = %e, "Skipping network name collision check");
+            return Ok(network_name);
+        }
+    };
+
+    let collisions = colliding_containers(&network_name, &existing);
+    if collisions.is_empty() {
+        return Ok(network_name);
+    }
+
+    match policy {
+        NameCollision::Error => anyhow::bail!(
+            "Network name '{network_name}' collides with existing container(s): {}.\n\
+             Remove them with `kupcake cleanup {network_name}`, choose another --network, \
+             or pass --name-collision suffix to pick a free name automatically.",
+            collisions.join(", ")
+        ),
+        NameCollision::Warn => {
+            tracing::warn!(
+                network = %network_name,
+                containers = %collisions.join(", "),
+                "Network name collides with existing containers; a cleanup of this network will remove them too"
+            );
+            Ok(network_name)
+        }
+        NameCollision::Suffix => {
+            let suffixed = suffixed_network_name(&network_name, &existing)
+                .with_context(|| format!("No free suffixed name for network '{network_name}'"))?;
+            tracing::info!(
+                network = %network_name,
+                suffixed = %suffixed,
+                "Network name is taken by existing containers, using a suffixed name"
+            );
+            Ok(suffixed)
+        }
+    }
+}
+
 /// Specifies how the output data directory should be created.
 #[derive(Debug, Clone)]
 pub enum OutDataPath {
@@ -81,6 +311,133 @@ pub enum OutDataPath {
     Path(PathBuf),
 }
 
+/// What [`DeployerBuilder::build`] does when containers already exist under the chosen
+/// network name.
+///
+/// Containers are named `<network>-<service>` and `kupcake cleanup` removes everything
+/// starting with the network name, so two deployments sharing a prefix would clobber each
+/// other. Generated network names are always re-suffixed on collision.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum NameCollision {
+    /// Refuse to build.
+    #[default]
+    Error,
+    /// Log a warning and reuse the name.
+    Warn,
+    /// Pick the first free `<network>-<n>` name.
+    Suffix,
+}
+
+/// Ready-made network topology, applied with [`DeployerBuilder::preset`].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Topology {
+    /// A single sequencer and no validators, with fast L1 blocks.
+    Solo,
+    /// 3 sequencers coordinated by op-conductor and 2 validators.
+    HaSequencer,
+    /// A single sequencer and 10 validators, with bounded and quieter logs.
+    LargeFleet,
+}
+
+impl Topology {
+    /// Total number of L2 nodes (sequencers + validators).
+    pub fn l2_node_count(self) -> usize {
+        match self {
+            Self::Solo => 1,
+            Self::HaSequencer => 5,
+            Self::LargeFleet => 11,
+        }
+    }
+
+    /// Number of sequencer nodes.
+    pub fn sequencer_count(self) -> usize {
+        match self {
+            Self::Solo | Self::LargeFleet => 1,
+            Self::HaSequencer => 3,
+        }
+    }
+
+    /// L1 block time in seconds.
+    pub fn block_time(self) -> u64 {
+        match self {
+            Self::Solo => 2,
+            Self::HaSequencer | Self::LargeFleet => 4,
+        }
+    }
+
+    /// Docker log rotation (max size, max files) capping the disk used by the containers.
+    ///
+    /// Only set for topologies with enough containers for logs to fill the disk.
+    pub fn log_rotation(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Self::Solo | Self::HaSequencer => None,
+            Self::LargeFleet => Some(("10m", "3")),
+        }
+    }
+}
+
+/// Names of `containers` that a deployment named `network_name` would collide with.
+fn colliding_containers<'a>(network_name: &str, containers: &'a [String]) -> Vec<&'a str> {
+    containers
+        .iter()
+        .filter(|name| name.starts_with(network_name))
+        .map(String::as_str)
+        .collect()
+}
+
+/// First `<network_name>-<n>` (n >= 2) that none of `containers` collides with, if any.
+fn suffixed_network_name(network_name: &str, containers: &[String]) -> Option<String> {
+    (2..=u32::MAX)
+        .map(|n| format!("{network_name}-{n}"))
+        .find(|candidate| colliding_containers(candidate, containers).is_empty())
+}
+
+/// Topology of an additional L2 chain, see [`DeployerBuilder::add_l2_chain`].
+///
+/// The other settings (Docker images, consensus client, proposer and challenger, ...) are
+/// the ones of the primary chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L2ChainConfig {
+    /// Number of L2 nodes (sequencers + validators).
+    pub l2_node_count: usize,
+    /// Number of sequencer nodes.
+    pub sequencer_count: usize,
+}
+
+impl Default for L2ChainConfig {
+    fn default() -> Self {
+        Self {
+            l2_node_count: 1,
+            sequencer_count: 1,
+        }
+    }
+}
+
 /// Builder for creating a [`Deployer`] configuration.
 ///
 /// This builder handles:
@@ -124,18 +481,66 @@ pub struct DeployerBuilder {
     detach: bool,
     /// Whether to publish all exposed ports to random host ports.
     publish_all_ports: bool,
+    /// Whether to publish services on fixed host ports (see [`Deployer::fix_host_ports`]).
+    fixed_ports: bool,
     /// Path to custom dashboards directory.
     dashboards_path: Option<PathBuf>,
     /// Whether monitoring is enabled.
     monitoring_enabled: bool,
+    /// Whether Loki and Promtail are deployed alongside the monitoring stack.
+    logs_enabled: bool,
+    /// Whether the Blockscout explorer is deployed.
+    blockscout: bool,
+    /// Whether to publish the L2 node metrics on known host ports.
+    expose_metrics: bool,
+    /// Whether to grant `NET_ADMIN` to the op-reth and kona-node containers.
+    net_admin: bool,
+    /// Pruning mode of the op-reth nodes.
+    op_reth_pruning: PruningMode,
+    /// Seed of the deterministic deployment mode, if enabled.
+    deterministic_seed: Option<u64>,
+    /// Whether to pin every image to the digest of its tag at build time.
+    pin_all_digests: bool,
     /// Block time in seconds for both L1 (Anvil) and L2 derivation.
     block_time: u64,
+    /// L1 EVM hardfork for Anvil. None uses Anvil's default (latest).
+    anvil_hardfork: Option<String>,
+    /// Whether to recreate Anvil from its last state dump if it exits.
+    anvil_auto_recover: bool,
+    /// Mnemonic of the Anvil accounts. None uses Anvil's default mnemonic.
+    anvil_mnemonic: Option<String>,
+    /// Derivation path of the Anvil accounts. None uses Anvil's default path.
+    anvil_derivation_path: Option<String>,
+    /// L2 block time in seconds. None uses the op-deployer default (2s).
+    l2_block_time: Option<u64>,
+    /// User-supplied op-deployer intent file, used instead of `op-deployer init`.
+    intent_file: Option<PathBuf>,
+    /// Whether to patch the intent roles with the Anvil accounts.
+    patch_intent_roles: bool,
+    /// Recipient of the sequencer fee vault. None uses the Anvil account.
+    sequencer_fee_recipient: Option<String>,
+    /// L2 block gas limit. None uses the op-deployer default.
+    gas_limit: Option<u64>,
+    /// EIP-1559 base fee change denominator. None uses the op-deployer default.
+    eip1559_denominator: Option<u64>,
+    /// EIP-1559 elasticity multiplier. None uses the op-deployer default.
+    eip1559_elasticity: Option<u64>,
+    /// op-deployer artifacts locator of the L1 contracts. None uses the op-deployer default.
+    l1_contracts_locator: Option<String>,
+    /// op-deployer artifacts locator of the L2 contracts. None uses the op-deployer default.
+    l2_contracts_locator: Option<String>,
     /// Manual override for L2 genesis timestamp (Unix timestamp in seconds).
     genesis_timestamp: Option<u64>,
+    /// L2 accounts funded in the genesis allocations, with their balance in wei.
+    prefund: Vec<(Address, EthAmount)>,
+    /// L2 hardforks activated after genesis, with their offset from genesis in seconds.
+    hardfork_offsets: BTreeMap<String, u64>,
     /// Number of L2 nodes (sequencers + validators).
     l2_node_count: usize,
     /// Number of sequencer nodes.
     sequencer_count: usize,
+    /// Additional L2 chains settled on the same L1, by chain ID.
+    additional_l2_chains: Vec<(u64, L2ChainConfig)>,
 
     /// Whether flashblocks support is enabled.
     flashblocks: bool,
@@ -151,27 +556,77 @@ pub struct DeployerBuilder {
     /// Deployment target (live or genesis).
     deployment_target: crate::DeploymentTarget,
 
+    /// Consensus client backing the L2 nodes.
+    consensus_client: ConsensusClient,
+
     /// Whether to skip op-proposer deployment.
     no_proposer: bool,
     /// Whether to skip op-challenger deployment.
     no_challenger: bool,
+    /// Number of hot-spare op-batchers.
+    spare_batchers: usize,
+    /// Number of hot-spare op-proposers.
+    spare_proposers: usize,
+    /// Alt-DA settings, if the chain posts its batch data to a DA server.
+    alt_da: Option<AltDaConfig>,
+    batcher_da_type: DataAvailabilityType,
+    /// Whether to start sequencing on the op-conductor leader (multi-sequencer only).
+    start_sequencing: bool,
 
     /// Optional path to an external state file for Anvil to load via `--load-state`.
     override_state: Option<PathBuf>,
 
+    /// Externally-running L1 to deploy against instead of Anvil.
+    external_l1: Option<ExternalL1Config>,
+    /// Script run against the devnet once it is deployed.
+    post_deploy_script: Option<PathBuf>,
+    /// Minimum free disk space for the data directory, in GiB.
+    min_disk_space_gib: u64,
+    /// Whether non-critical service failures are skipped instead of aborting.
+    keep_going: bool,
+    /// Whether to check the chain IDs end-to-end at the end of the deployment.
+    verify_chain_ids: bool,
+    /// Whether to reuse the network when it is already running and healthy.
+    reuse_if_healthy: bool,
+    /// Whether to restart the unhealthy containers of a reused network.
+    restart_unhealthy: bool,
+
     /// Docker log file max size (e.g., "10m").
     log_max_size: Option<String>,
     /// Max number of rotated log files.
     log_max_file: Option<String>,
     /// Whether to quiet verbose services.
     quiet_services: bool,
+    /// Log level of individual services, overriding [`Self::quiet_services`].
+    service_log_levels: BTreeMap<String, String>,
     /// Whether to stream container logs to tracing::debug!().
     stream_logs: bool,
+    /// Restart policy of the service containers.
+    restart_policy: RestartPolicy,
+    /// Extra `/etc/hosts` entries of the service containers.
+    extra_hosts: Vec<String>,
+    /// PEM bundle of extra CA certificates trusted by the containers.
+    ca_bundle: Option<PathBuf>,
+    /// Outbound proxy of the containers and the RPC client.
+    proxy: Option<ProxyConfig>,
+    /// Subnet (CIDR) of the Docker network.
+    network_subnet: Option<String>,
+    /// Gateway of the Docker network.
+    network_gateway: Option<String>,
+    /// Pre-existing Docker network used instead of creating one.
+    external_network: Option<String>,
+    /// Timeouts and connection pool of the crate's own RPC client.
+    rpc_client: RpcClientConfig,
+    /// Whether the stateful services run as the owner of their data directory.
+    run_as_host_user: bool,
+    /// What to do when containers already exist under the network name.
+    name_collision: NameCollision,
 
     // Docker images
     anvil_docker: DockerImage,
     op_reth_docker: DockerImage,
     kona_node_docker: DockerImage,
+    op_node_docker: DockerImage,
     op_batcher_docker: DockerImage,
     op_proposer_docker: DockerImage,
     op_challenger_docker: DockerImage,
@@ -180,6 +635,9 @@ pub struct DeployerBuilder {
     op_deployer_docker: DockerImage,
     prometheus_docker: DockerImage,
     grafana_docker: DockerImage,
+    loki_docker: DockerImage,
+    promtail_docker: DockerImage,
+    blockscout_docker: DockerImage,
 }
 
 impl DeployerBuilder {
@@ -195,27 +653,76 @@ impl DeployerBuilder {
             dump_state: true,
             detach: false,
             publish_all_ports: false,
+            fixed_ports: false,
             dashboards_path: None,
             monitoring_enabled: true,
+            logs_enabled: false,
+            blockscout: false,
+            expose_metrics: false,
+            net_admin: false,
+            op_reth_pruning: PruningMode::default(),
+            deterministic_seed: None,
+            pin_all_digests: false,
             block_time: 12,
+            l2_block_time: None,
+            anvil_hardfork: None,
+            anvil_auto_recover: false,
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            intent_file: None,
+            patch_intent_roles: true,
+            sequencer_fee_recipient: None,
+            gas_limit: None,
+            eip1559_denominator: None,
+            eip1559_elasticity: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
             genesis_timestamp: None,
+            prefund: Vec::new(),
+            hardfork_offsets: BTreeMap::new(),
             l2_node_count: 1,
             sequencer_count: 1,
+            additional_l2_chains: Vec::new(),
             flashblocks: false,
             proofs_validators: 0,
             snapshot: None,
             copy_snapshot: false,
             deployment_target: crate::DeploymentTarget::default(),
+            consensus_client: ConsensusClient::default(),
             no_proposer: false,
             no_challenger: false,
+            spare_batchers: 0,
+            spare_proposers: 0,
+            alt_da: None,
+            batcher_da_type: DataAvailabilityType::default(),
+            start_sequencing: true,
             override_state: None,
+            external_l1: None,
+            post_deploy_script: None,
+            min_disk_space_gib: crate::DEFAULT_MIN_DISK_SPACE_GIB,
+            keep_going: false,
+            verify_chain_ids: false,
+            reuse_if_healthy: false,
+            restart_unhealthy: false,
             log_max_size: None,
             log_max_file: None,
             quiet_services: false,
+            service_log_levels: BTreeMap::new(),
             stream_logs: false,
+            restart_policy: RestartPolicy::No,
+            extra_hosts: Vec::new(),
+            ca_bundle: None,
+            proxy: None,
+            network_subnet: None,
+            network_gateway: None,
+            external_network: None,
+            rpc_client: RpcClientConfig::default(),
+            run_as_host_user: default_run_as_host_user(),
+            name_collision: NameCollision::default(),
             anvil_docker: DockerImage::new(ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG),
             op_reth_docker: DockerImage::new(OP_RETH_DEFAULT_IMAGE, OP_RETH_DEFAULT_TAG),
             kona_node_docker: DockerImage::new(KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG),
+            op_node_docker: DockerImage::new(OP_NODE_DEFAULT_IMAGE, OP_NODE_DEFAULT_TAG),
             op_batcher_docker: DockerImage::new(OP_BATCHER_DEFAULT_IMAGE, OP_BATCHER_DEFAULT_TAG),
             op_proposer_docker: DockerImage::new(
                 OP_PROPOSER_DEFAULT_IMAGE,
@@ -239,6 +746,9 @@ impl DeployerBuilder {
             ),
             prometheus_docker: DockerImage::new(PROMETHEUS_DEFAULT_IMAGE, PROMETHEUS_DEFAULT_TAG),
             grafana_docker: DockerImage::new(GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG),
+            loki_docker: DockerImage::new(LOKI_DEFAULT_IMAGE, LOKI_DEFAULT_TAG),
+            promtail_docker: DockerImage::new(PROMTAIL_DEFAULT_IMAGE, PROMTAIL_DEFAULT_TAG),
+            blockscout_docker: DockerImage::new(BLOCKSCOUT_DEFAULT_IMAGE, BLOCKSCOUT_DEFAULT_TAG),
         }
     }
 
@@ -251,6 +761,153 @@ impl DeployerBuilder {
         self
     }
 
+    /// Set the L2 block time (sequencer interval) in seconds, independently of the L1.
+    ///
+    /// Must evenly divide the L1 block time, e.g. 1s or 2s L2 blocks over a 12s L1.
+    /// Written to the op-deployer intent, so changing it redeploys the contracts.
+    pub fn l2_block_time(mut self, l2_block_time: u64) -> Self {
+        self.l2_block_time = Some(l2_block_time);
+        self
+    }
+
+    /// Set the L2 block time if `Some`, otherwise do nothing.
+    pub fn maybe_l2_block_time(mut self, l2_block_time: Option<u64>) -> Self {
+        if let Some(t) = l2_block_time {
+            self.l2_block_time = Some(t);
+        }
+        self
+    }
+
+    /// Use a complete op-deployer `intent.toml` instead of generating one.
+    ///
+    /// The file is copied into the work directory in place of `op-deployer init`.
+    /// Its `l1ChainID` must match the deployment's L1 chain. Role addresses are still
+    /// replaced with the Anvil accounts unless [`Self::patch_intent_roles`] is disabled,
+    /// and `l2_block_time` is still applied when set.
+    pub fn intent_file(mut self, intent_file: PathBuf) -> Self {
+        self.intent_file = Some(intent_file);
+        self
+    }
+
+    /// Set the intent file if `Some`, otherwise do nothing.
+    pub fn maybe_intent_file(mut self, intent_file: Option<PathBuf>) -> Self {
+        if let Some(path) = intent_file {
+            self.intent_file = Some(path);
+        }
+        self
+    }
+
+    /// Set whether the intent's role and fee recipient addresses are replaced with the
+    /// Anvil accounts (default: true).
+    pub fn patch_intent_roles(mut self, patch_intent_roles: bool) -> Self {
+        self.patch_intent_roles = patch_intent_roles;
+        self
+    }
+
+    /// Set the recipient of the L2 sequencer fee vault (0x-prefixed address).
+    ///
+    /// Written to the op-deployer intent and baked into the L2 genesis, so changing it
+    /// redeploys the contracts. Applied even when [`Self::patch_intent_roles`] is disabled.
+    pub fn sequencer_fee_recipient(mut self, address: impl Into<String>) -> Self {
+        self.sequencer_fee_recipient = Some(address.into());
+        self
+    }
+
+    /// Set the sequencer fee recipient if `Some`, otherwise do nothing.
+    pub fn maybe_sequencer_fee_recipient(mut self, address: Option<String>) -> Self {
+        if let Some(address) = address {
+            self.sequencer_fee_recipient = Some(address);
+        }
+        self
+    }
+
+    /// Set the L2 block gas limit, e.g. 60M for load testing.
+    ///
+    /// Written to the op-deployer intent, so it applies from the genesis block and
+    /// changing it redeploys the contracts. Must be between [`MIN_L2_GAS_LIMIT`] and
+    /// [`MAX_L2_GAS_LIMIT`].
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Set the L2 gas limit if `Some`, otherwise do nothing.
+    pub fn maybe_gas_limit(mut self, gas_limit: Option<u64>) -> Self {
+        if let Some(gas_limit) = gas_limit {
+            self.gas_limit = Some(gas_limit);
+        }
+        self
+    }
+
+    /// Set the EIP-1559 base fee change denominator of the L2 (op-deployer default: 250
+    /// since Canyon).
+    ///
+    /// Written to the op-deployer intent for both the pre- and post-Canyon values;
+    /// changing it redeploys the contracts. To change it on a running network, use
+    /// [`Deployer::set_eip1559_params`].
+    pub fn eip1559_denominator(mut self, denominator: u64) -> Self {
+        self.eip1559_denominator = Some(denominator);
+        self
+    }
+
+    /// Set the EIP-1559 denominator if `Some`, otherwise do nothing.
+    pub fn maybe_eip1559_denominator(mut self, denominator: Option<u64>) -> Self {
+        if let Some(denominator) = denominator {
+            self.eip1559_denominator = Some(denominator);
+        }
+        self
+    }
+
+    /// Set the EIP-1559 elasticity multiplier of the L2 (op-deployer default: 6).
+    ///
+    /// Written to the op-deployer intent; changing it redeploys the contracts.
+    pub fn eip1559_elasticity(mut self, elasticity: u64) -> Self {
+        self.eip1559_elasticity = Some(elasticity);
+        self
+    }
+
+    /// Set the EIP-1559 elasticity if `Some`, otherwise do nothing.
+    pub fn maybe_eip1559_elasticity(mut self, elasticity: Option<u64>) -> Self {
+        if let Some(elasticity) = elasticity {
+            self.eip1559_elasticity = Some(elasticity);
+        }
+        self
+    }
+
+    /// Set the op-deployer artifacts locator of the L1 contracts, e.g. a contracts release
+    /// (`tag://op-contracts/v3.0.0`) or a local build (`file:///path/to/forge-artifacts`).
+    ///
+    /// Written to the intent's `l1ContractsLocator`; changing it redeploys the contracts.
+    pub fn l1_contracts_locator(mut self, locator: impl Into<String>) -> Self {
+        self.l1_contracts_locator = Some(locator.into());
+        self
+    }
+
+    /// Set the L1 contracts locator if `Some`, otherwise do nothing.
+    pub fn maybe_l1_contracts_locator(mut self, locator: Option<String>) -> Self {
+        if let Some(locator) = locator {
+            self.l1_contracts_locator = Some(locator);
+        }
+        self
+    }
+
+    /// Set the op-deployer artifacts locator of the L2 contracts (predeploys).
+    ///
+    /// Same format as [`Self::l1_contracts_locator`]; written to the intent's
+    /// `l2ContractsLocator`.
+    pub fn l2_contracts_locator(mut self, locator: impl Into<String>) -> Self {
+        self.l2_contracts_locator = Some(locator.into());
+        self
+    }
+
+    /// Set the L2 contracts locator if `Some`, otherwise do nothing.
+    pub fn maybe_l2_contracts_locator(mut self, locator: Option<String>) -> Self {
+        if let Some(locator) = locator {
+            self.l2_contracts_locator = Some(locator);
+        }
+        self
+    }
+
     /// Manually override the L2 genesis timestamp.
     ///
     /// When provided, this timestamp will be used instead of the automatically
@@ -272,12 +929,34 @@ impl DeployerBuilder {
         self
     }
 
+    /// Fund an L2 account with `amount` in the genesis allocations.
+    ///
+    /// The balance is visible at block 0 on every L2 node. An account already present in
+    /// the allocations (e.g. a predeploy) keeps its code and storage; only its balance is
+    /// replaced. Calling this again for the same address overrides the previous amount.
+    pub fn prefund(mut self, address: Address, amount: EthAmount) -> Self {
+        self.prefund.push((address, amount));
+        self
+    }
+
+    /// Activate the L2 hardfork `fork` (e.g. `"isthmus"`) `seconds_after_genesis` after the
+    /// L2 genesis instead of at genesis, to exercise derivation across the fork boundary.
+    ///
+    /// The activation times are patched into `rollup.json` and `genesis.json` before op-reth
+    /// and the consensus clients start. Forks after `fork` that are active at genesis are
+    /// moved to the same time, as forks activate in order. Fork names and their ordering
+    /// are checked by [`Self::validate`].
+    pub fn hardfork_offset(mut self, fork: impl Into<String>, seconds_after_genesis: u64) -> Self {
+        self.hardfork_offsets
+            .insert(fork.into(), seconds_after_genesis);
+        self
+    }
+
     /// Set the number of L2 nodes to deploy.
     ///
     /// This is the total number of nodes (sequencers + validators).
-    /// Must be at least 1. Defaults to 1 (sequencer only).
+    /// Must be at least 1 (checked by [`DeployerBuilder::validate`]). Defaults to 1 (sequencer only).
     pub fn l2_node_count(mut self, count: usize) -> Self {
-        assert!(count >= 1, "At least one L2 node is required");
         self.l2_node_count = count;
         self
     }
@@ -286,16 +965,167 @@ impl DeployerBuilder {
     ///
     /// If more than 1 sequencer is specified, op-conductor will be deployed
     /// to coordinate the sequencers using Raft consensus.
-    /// Must be at least 1 and at most equal to l2_node_count.
-    /// Defaults to 1 (single sequencer).
+    /// Must be at least 1 and at most equal to l2_node_count (checked by
+    /// [`DeployerBuilder::validate`]). Defaults to 1 (single sequencer).
     pub fn sequencer_count(mut self, count: usize) -> Self {
-        assert!(count >= 1, "At least one sequencer is required");
         self.sequencer_count = count;
         self
     }
 
+    /// Deploy an additional L2 chain with ID `chain_id` on the same L1.
+    ///
+    /// The contracts of every chain are deployed by a single op-deployer intent. Each chain
+    /// gets its own L2 nodes, op-batcher, op-proposer and op-challenger, named
+    /// `<network>-<chain_id>-<service>`, and its own data directory `l2-stack-<chain_id>`.
+    /// Its batcher, proposer and challenger submit with dedicated L1 accounts (see
+    /// [`AnvilAccounts::for_l2_chain`](crate::AnvilAccounts::for_l2_chain)).
+    pub fn add_l2_chain(mut self, chain_id: u64, config: L2ChainConfig) -> Self {
+        self.additional_l2_chains.push((chain_id, config));
+        self
+    }
+
+    /// Set the minimum free disk space of the data directory, in GiB (default 5).
+    ///
+    /// Deployment fails before any node starts if less is available, and attached
+    /// deployments periodically warn when the disk runs low. 0 disables both.
+    pub fn min_disk_space_gib(mut self, min_gib: u64) -> Self {
+        self.min_disk_space_gib = min_gib;
+        self
+    }
+
+    /// Set the minimum free disk space if one is given.
+    pub fn maybe_min_disk_space_gib(mut self, min_gib: Option<u64>) -> Self {
+        if let Some(min_gib) = min_gib {
+            self.min_disk_space_gib = min_gib;
+        }
+        self
+    }
+
+    /// Apply a ready-made [`Topology`]: node counts, block time and, for large
+    /// topologies, log rotation and quiet services.
+    ///
+    /// Setters called after the preset override its values.
+    pub fn preset(mut self, topology: Topology) -> Self {
+        self.l2_node_count = topology.l2_node_count();
+        self.sequencer_count = topology.sequencer_count();
+        self.block_time = topology.block_time();
+        if let Some((max_size, max_file)) = topology.log_rotation() {
+            self.log_max_size = Some(max_size.to_string());
+            self.log_max_file = Some(max_file.to_string());
+            self.quiet_services = true;
+        }
+        self
+    }
+
+    /// Make the deployment reproducible: two builds with the same seed and configuration
+    /// produce byte-identical genesis and rollup files and the same contract addresses.
+    ///
+    /// Defaults the L2 chain ID, network name and genesis timestamp to seeded values, selects
+    /// the genesis deployment target and derives the P2P keys of the consensus clients from
+    /// the seed.
+    /// Setters called afterwards override these values. See [`crate::deterministic`] for
+    /// what stays non-deterministic.
+    pub fn deterministic(mut self, seed: u64) -> Self {
+        self.deterministic_seed = Some(seed);
+        self.l2_chain_id
+            .get_or_insert_with(|| crate::deterministic::seeded_l2_chain_id(seed));
+        self.network_name
+            .get_or_insert_with(|| crate::deterministic::seeded_network_name(seed));
+        self.genesis_timestamp
+            .get_or_insert(crate::deterministic::DETERMINISTIC_GENESIS_TIMESTAMP);
+        self.deployment_target = crate::DeploymentTarget::Genesis;
+        self
+    }
+
+    /// Pin every Docker image used by the deployment to the digest its tag resolves to
+    /// when [`Self::build`] runs, pulling the images if needed.
+    ///
+    /// The digests are saved in `Kupcake.toml`, so redeploying the saved configuration
+    /// later runs the exact same binaries even if the tags moved.
+    pub fn pin_all_digests(mut self, enabled: bool) -> Self {
+        self.pin_all_digests = enabled;
+        self
+    }
+
+    /// Enable the deterministic mode if a seed is given.
+    pub fn maybe_deterministic(self, seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => self.deterministic(seed),
+            None => self,
+        }
+    }
+
+    /// Apply a [`Topology`] if one is given.
+    pub fn maybe_preset(self, topology: Option<Topology>) -> Self {
+        match topology {
+            Some(topology) => self.preset(topology),
+            None => self,
+        }
+    }
+
     // ==================== Docker Image Setters ====================
 
+    /// Pin the L1 EVM hardfork of Anvil (`--hardfork`), e.g. "cancun" or "prague".
+    ///
+    /// Defaults to Anvil's latest supported hardfork, which is what op-deployer expects.
+    /// Checked against [`ANVIL_KNOWN_HARDFORKS`] by [`Self::validate`].
+    pub fn anvil_hardfork(mut self, hardfork: impl Into<String>) -> Self {
+        self.anvil_hardfork = Some(hardfork.into());
+        self
+    }
+
+    /// Set the Anvil hardfork if `Some`, otherwise do nothing.
+    pub fn maybe_anvil_hardfork(mut self, hardfork: Option<String>) -> Self {
+        if let Some(hardfork) = hardfork {
+            self.anvil_hardfork = Some(hardfork);
+        }
+        self
+    }
+
+    /// Generate the Anvil accounts, and so the batcher, proposer, challenger and other
+    /// role accounts, from `mnemonic` instead of Anvil's default one.
+    ///
+    /// The role addresses are then stable across machines but differ from the well-known
+    /// test accounts, e.g. to fund them ahead of time.
+    pub fn anvil_mnemonic(mut self, mnemonic: impl Into<String>) -> Self {
+        self.anvil_mnemonic = Some(mnemonic.into());
+        self
+    }
+
+    /// Set the Anvil mnemonic if `Some`, otherwise do nothing.
+    pub fn maybe_anvil_mnemonic(mut self, mnemonic: Option<String>) -> Self {
+        if let Some(mnemonic) = mnemonic {
+            self.anvil_mnemonic = Some(mnemonic);
+        }
+        self
+    }
+
+    /// Set the HD derivation path of the Anvil accounts, without the account index
+    /// (default: `m/44'/60'/0'/0/`).
+    pub fn anvil_derivation_path(mut self, path: impl Into<String>) -> Self {
+        self.anvil_derivation_path = Some(path.into());
+        self
+    }
+
+    /// Set the Anvil derivation path if `Some`, otherwise do nothing.
+    pub fn maybe_anvil_derivation_path(mut self, path: Option<String>) -> Self {
+        if let Some(path) = path {
+            self.anvil_derivation_path = Some(path);
+        }
+        self
+    }
+
+    /// Recreate the Anvil container from its last state dump if it exits while the
+    /// deployment is attached (default: false).
+    ///
+    /// The state is dumped periodically; see [`AnvilHandler::watch_and_recover`].
+    ///
+    /// [`AnvilHandler::watch_and_recover`]: crate::AnvilHandler::watch_and_recover
+    pub fn anvil_auto_recover(mut self, enabled: bool) -> Self {
+        self.anvil_auto_recover = enabled;
+        self
+    }
+
     /// Set Docker image for Anvil.
     pub fn anvil_image(mut self, image: impl Into<String>) -> Self {
         self.anvil_docker.image = Some(image.into());
@@ -332,6 +1162,18 @@ impl DeployerBuilder {
         self
     }
 
+    /// Set Docker image for op-node.
+    pub fn op_node_image(mut self, image: impl Into<String>) -> Self {
+        self.op_node_docker.image = Some(image.into());
+        self
+    }
+
+    /// Set Docker tag for op-node.
+    pub fn op_node_tag(mut self, tag: impl Into<String>) -> Self {
+        self.op_node_docker.tag = Some(tag.into());
+        self
+    }
+
     /// Set Docker image for op-batcher.
     pub fn op_batcher_image(mut self, image: impl Into<String>) -> Self {
         self.op_batcher_docker.image = Some(image.into());
@@ -409,6 +1251,12 @@ impl DeployerBuilder {
         self
     }
 
+    /// Set the consensus client backing the L2 nodes (kona-node by default).
+    pub fn consensus_client(mut self, client: ConsensusClient) -> Self {
+        self.consensus_client = client;
+        self
+    }
+
     /// Set the path to an external Anvil state file to load via `--load-state`.
     ///
     /// Only valid in live deployment mode. Genesis mode will error if this is set.
@@ -425,6 +1273,47 @@ impl DeployerBuilder {
         self
     }
 
+    /// Deploy against an externally-running L1 instead of starting Anvil.
+    ///
+    /// Contracts are deployed with `deployer_private_key`, which also backs every OP Stack
+    /// role. Use [`DeployerBuilder::external_l1_config`] to provide dedicated batcher,
+    /// proposer and challenger keys. Only valid in live deployment mode. A hex key is not
+    /// written to `Kupcake.toml`; pass an `env:<NAME>` or `file:<PATH>` reference to keep
+    /// it across redeploys (see [`crate::PrivateKeySource`]).
+    pub fn external_l1(
+        mut self,
+        rpc_url: impl Into<String>,
+        deployer_private_key: impl Into<crate::PrivateKeySource>,
+    ) -> Self {
+        self.external_l1 = Some(ExternalL1Config::new(rpc_url, deployer_private_key));
+        self
+    }
+
+    /// Deploy against an externally-running L1 with a full configuration.
+    pub fn external_l1_config(mut self, config: ExternalL1Config) -> Self {
+        self.external_l1 = Some(config);
+        self
+    }
+
+    /// Run a shell script against the devnet once it is deployed.
+    ///
+    /// The script runs in a Foundry container on the deployment network, from its own
+    /// directory, after the sequencer has produced its first block. It receives `L1_RPC`,
+    /// `L2_RPC`, `L2_WS`, `ROLLUP_RPC`, `L1_CHAIN_ID`, `L2_CHAIN_ID`, `DEPLOYER_ADDRESS`
+    /// and `DEPLOYER_PRIVATE_KEY`. A non-zero exit fails the deployment.
+    pub fn post_deploy_script(mut self, script: PathBuf) -> Self {
+        self.post_deploy_script = Some(script);
+        self
+    }
+
+    /// Set the post-deploy script if `Some`, otherwise do nothing.
+    pub fn maybe_post_deploy_script(mut self, script: Option<PathBuf>) -> Self {
+        if let Some(script) = script {
+            self.post_deploy_script = Some(script);
+        }
+        self
+    }
+
     /// Disable op-proposer deployment.
     pub fn no_proposer(mut self, no_proposer: bool) -> Self {
         self.no_proposer = no_proposer;
@@ -437,6 +1326,81 @@ impl DeployerBuilder {
         self
     }
 
+    /// Start `count` hot-spare op-batchers with `--stopped`, each with its own funded
+    /// account (see [`L2StackBuilder::spare_batchers`]).
+    pub fn spare_batchers(mut self, count: usize) -> Self {
+        self.spare_batchers = count;
+        self
+    }
+
+    /// Start `count` hot-spare op-proposers, stopped right after startup, each with its
+    /// own funded account (see [`L2StackBuilder::spare_proposers`]).
+    pub fn spare_proposers(mut self, count: usize) -> Self {
+        self.spare_proposers = count;
+        self
+    }
+
+    /// Post the batch data to an alt-DA server instead of L1, with the challenge and
+    /// resolve windows of `alt_da`. Requires the op-node consensus client.
+    pub fn alt_da(mut self, alt_da: AltDaConfig) -> Self {
+        self.alt_da = Some(alt_da);
+        self
+    }
+
+    /// Enable alt-DA if a configuration is given.
+    pub fn maybe_alt_da(mut self, alt_da: Option<AltDaConfig>) -> Self {
+        self.alt_da = alt_da;
+        self
+    }
+
+    /// Set how op-batcher posts the batch data to L1 (default: blobs).
+    ///
+    /// Calldata works on an L1 without EIP-4844 support. Ignored with alt-DA.
+    pub fn batcher_da_type(mut self, da_type: DataAvailabilityType) -> Self {
+        self.batcher_da_type = da_type;
+        self
+    }
+
+    /// Treat op-proposer, op-challenger and the monitoring stack as best-effort.
+    ///
+    /// A failure to start one of them is logged and recorded in
+    /// [`DeploymentResult::warnings`](crate::DeploymentResult) instead of aborting the
+    /// deployment. Anvil and the L2 nodes still abort on failure.
+    pub fn keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Check the chain IDs of the intent, `rollup.json`, `genesis.json`, the L1 and every
+    /// op-reth against the configured ones at the end of the deployment, and fail with a
+    /// report of the mismatches (see [`Deployer::chain_id_report`]).
+    pub fn verify_chain_ids(mut self, verify: bool) -> Self {
+        self.verify_chain_ids = verify;
+        self
+    }
+
+    /// Make [`Deployer::deploy`] leave the network as is when it is already running and
+    /// healthy, so that re-runs are idempotent.
+    pub fn reuse_if_healthy(mut self, reuse: bool) -> Self {
+        self.reuse_if_healthy = reuse;
+        self
+    }
+
+    /// With [`Self::reuse_if_healthy`], restart the stopped or failing containers of a
+    /// partially running network instead of failing.
+    pub fn restart_unhealthy(mut self, restart: bool) -> Self {
+        self.restart_unhealthy = restart;
+        self
+    }
+
+    /// Set whether to start sequencing on the op-conductor leader once it is elected.
+    ///
+    /// Only applies with more than one sequencer. Defaults to true.
+    pub fn start_sequencing(mut self, start_sequencing: bool) -> Self {
+        self.start_sequencing = start_sequencing;
+        self
+    }
+
     /// Set Docker log file max size (e.g., "10m").
     pub fn log_max_size(mut self, size: impl Into<String>) -> Self {
         self.log_max_size = Some(size.into());
@@ -471,25 +1435,164 @@ impl DeployerBuilder {
         self
     }
 
+    /// Run `service` (e.g. `"op-batcher"`) at log `level` (e.g. `"DEBUG"`) on every L2
+    /// chain, whether or not services are quiet.
+    ///
+    /// The service and level are checked by [`Self::validate`] (see
+    /// [`parse_service_log_level`](crate::logs::parse_service_log_level)).
+    pub fn service_log_level(
+        mut self,
+        service: impl Into<String>,
+        level: impl Into<String>,
+    ) -> Self {
+        self.service_log_levels.insert(service.into(), level.into());
+        self
+    }
+
     /// Set whether to stream container logs to tracing::debug!().
     pub fn stream_logs(mut self, stream: bool) -> Self {
         self.stream_logs = stream;
         self
     }
 
-    /// Set Docker image for op-rbuilder.
-    pub fn op_rbuilder_image(mut self, image: impl Into<String>) -> Self {
-        self.op_rbuilder_docker.image = Some(image.into());
+    /// Set the restart policy of the service containers (default: [`RestartPolicy::No`]).
+    ///
+    /// With [`RestartPolicy::UnlessStopped`], containers also come back when the Docker
+    /// daemon restarts; stop them with `kupcake cleanup` (or `docker stop`).
+    pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
         self
     }
 
-    /// Set Docker tag for op-rbuilder.
-    pub fn op_rbuilder_tag(mut self, tag: impl Into<String>) -> Self {
-        self.op_rbuilder_docker.tag = Some(tag.into());
+    /// Add extra `/etc/hosts` entries (`host:ip` format) to every container.
+    ///
+    /// The IP can be `host-gateway` to resolve to the Docker host.
+    pub fn extra_hosts(mut self, hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        for host in hosts.into_iter().map(Into::into) {
+            if !self.extra_hosts.contains(&host) {
+                self.extra_hosts.push(host);
+            }
+        }
         self
     }
 
-    /// Use a local binary or source directory for op-rbuilder instead of a Docker image.
+    /// Trust the CA certificates of a PEM bundle, e.g. a corporate TLS-intercepting proxy
+    /// or a private L1 endpoint with a self-signed certificate.
+    ///
+    /// The bundle is mounted into every container and referenced through
+    /// `SSL_CERT_FILE` and `NODE_EXTRA_CA_CERTS`, which replaces the system roots of most
+    /// services: include the public roots in the bundle when they reach public endpoints.
+    /// Image pulls are done by the Docker daemon and use its own trust store.
+    pub fn ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Optionally set the CA bundle trusted by the containers.
+    pub fn maybe_ca_bundle(mut self, path: Option<PathBuf>) -> Self {
+        self.ca_bundle = path;
+        self
+    }
+
+    /// Route outbound traffic of the containers and of the RPC client through a proxy,
+    /// e.g. [`ProxyConfig::from_env`].
+    ///
+    /// The deployment's own containers and `localhost` always bypass the proxy.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Optionally set the outbound proxy.
+    pub fn maybe_proxy(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Create the Docker network with this subnet (CIDR, e.g. `172.30.0.0/16`) instead of
+    /// one picked by Docker, e.g. when the default range collides with a VPN.
+    pub fn network_subnet(mut self, subnet: impl Into<String>) -> Self {
+        self.network_subnet = Some(subnet.into());
+        self
+    }
+
+    /// Optionally set the subnet of the Docker network.
+    pub fn maybe_network_subnet(mut self, subnet: Option<String>) -> Self {
+        self.network_subnet = subnet;
+        self
+    }
+
+    /// Set the gateway of the Docker network. Requires [`Self::network_subnet`].
+    pub fn network_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.network_gateway = Some(gateway.into());
+        self
+    }
+
+    /// Optionally set the gateway of the Docker network.
+    pub fn maybe_network_gateway(mut self, gateway: Option<String>) -> Self {
+        self.network_gateway = gateway;
+        self
+    }
+
+    /// Attach the containers to a pre-existing Docker network instead of creating one.
+    ///
+    /// The network must exist before the deployment and is left in place on cleanup.
+    pub fn external_network(mut self, name: impl Into<String>) -> Self {
+        self.external_network = Some(name.into());
+        self
+    }
+
+    /// Optionally set the pre-existing Docker network.
+    pub fn maybe_external_network(mut self, name: Option<String>) -> Self {
+        self.external_network = name;
+        self
+    }
+
+    /// Set the timeouts and connection pool of the RPC client used by the health checks,
+    /// the faucet and the readiness waiters, e.g. a longer request timeout on congested CI.
+    pub fn rpc_client_config(mut self, config: RpcClientConfig) -> Self {
+        self.rpc_client = config;
+        self
+    }
+
+    /// Run Anvil, op-reth, the consensus clients, op-conductor and op-challenger as the
+    /// owner of their data directory instead of the image default user. Enabled by
+    /// default on unix, so that their files do not end up owned by root.
+    pub fn run_as_host_user(mut self, enabled: bool) -> Self {
+        self.run_as_host_user = enabled;
+        self
+    }
+
+    /// Make the host reachable as `host.docker.internal` from every container, e.g. to
+    /// point kona-node at an L1 beacon running on the host.
+    pub fn host_gateway(self, enabled: bool) -> Self {
+        if enabled {
+            self.extra_hosts([HOST_GATEWAY_ENTRY])
+        } else {
+            self
+        }
+    }
+
+    /// Set what [`DeployerBuilder::build`] does when containers already exist under the
+    /// network name (default: [`NameCollision::Error`]).
+    pub fn name_collision(mut self, policy: NameCollision) -> Self {
+        self.name_collision = policy;
+        self
+    }
+
+    /// Set Docker image for op-rbuilder.
+    pub fn op_rbuilder_image(mut self, image: impl Into<String>) -> Self {
+        self.op_rbuilder_docker.image = Some(image.into());
+        self
+    }
+
+    /// Set Docker tag for op-rbuilder.
+    pub fn op_rbuilder_tag(mut self, tag: impl Into<String>) -> Self {
+        self.op_rbuilder_docker.tag = Some(tag.into());
+        self
+    }
+
+    /// Use a local binary or source directory for op-rbuilder instead of a Docker image.
     ///
     /// If a directory is provided, `cargo build --release --bin op-rbuilder` will be run
     /// automatically (cross-compiling for Linux on macOS).
@@ -546,6 +1649,42 @@ impl DeployerBuilder {
         self
     }
 
+    /// Set Docker image for Loki.
+    pub fn loki_image(mut self, image: impl Into<String>) -> Self {
+        self.loki_docker.image = Some(image.into());
+        self
+    }
+
+    /// Set Docker tag for Loki.
+    pub fn loki_tag(mut self, tag: impl Into<String>) -> Self {
+        self.loki_docker.tag = Some(tag.into());
+        self
+    }
+
+    /// Set Docker image for Promtail.
+    pub fn promtail_image(mut self, image: impl Into<String>) -> Self {
+        self.promtail_docker.image = Some(image.into());
+        self
+    }
+
+    /// Set Docker tag for Promtail.
+    pub fn promtail_tag(mut self, tag: impl Into<String>) -> Self {
+        self.promtail_docker.tag = Some(tag.into());
+        self
+    }
+
+    /// Set Docker image for Blockscout.
+    pub fn blockscout_image(mut self, image: impl Into<String>) -> Self {
+        self.blockscout_docker.image = Some(image.into());
+        self
+    }
+
+    /// Set Docker tag for Blockscout.
+    pub fn blockscout_tag(mut self, tag: impl Into<String>) -> Self {
+        self.blockscout_docker.tag = Some(tag.into());
+        self
+    }
+
     // ==================== Binary Path Setters ====================
 
     /// Use a local binary or source directory for op-reth instead of a Docker image.
@@ -715,6 +1854,18 @@ impl DeployerBuilder {
         self
     }
 
+    /// Publish each service on the host port equal to its container port, e.g. 9545 for
+    /// the op-reth HTTP RPC, instead of a Docker-assigned one. Further L2 nodes are
+    /// shifted by 100 (see [`Deployer::fix_host_ports`]). A port already in use on the
+    /// host fails the deployment.
+    ///
+    /// Composes with [`DeployerBuilder::publish_all_ports`], which still publishes the
+    /// remaining exposed ports on Docker-assigned host ports.
+    pub fn fixed_ports(mut self, fixed_ports: bool) -> Self {
+        self.fixed_ports = fixed_ports;
+        self
+    }
+
     /// Set the path to custom Grafana dashboards.
     pub fn dashboards_path(mut self, path: impl Into<PathBuf>) -> Self {
         self.dashboards_path = Some(path.into());
@@ -727,45 +1878,755 @@ impl DeployerBuilder {
         self
     }
 
-    /// Build the [`Deployer`] configuration.
+    /// Deploy Loki and Promtail to collect the logs of every container of the network, with
+    /// a Loki datasource provisioned in Grafana. Requires monitoring. Disabled by default.
+    pub fn logs_enabled(mut self, enabled: bool) -> Self {
+        self.logs_enabled = enabled;
+        self
+    }
+
+    /// Deploy a Blockscout explorer indexing the primary sequencer. Disabled by default.
+    pub fn blockscout(mut self, enabled: bool) -> Self {
+        self.blockscout = enabled;
+        self
+    }
+
+    /// Publish the op-reth and kona-node metrics of every L2 node on known host ports, for
+    /// scraping from outside Docker (see [`L2StackBuilder::expose_metrics`]).
+    pub fn expose_metrics(mut self, enabled: bool) -> Self {
+        self.expose_metrics = enabled;
+        self
+    }
+
+    /// Grant `NET_ADMIN` to the op-reth and kona-node of every L2 node, to shape their
+    /// traffic from inside the containers (see [`L2StackBuilder::net_admin`]).
+    pub fn net_admin(mut self, enabled: bool) -> Self {
+        self.net_admin = enabled;
+        self
+    }
+
+    /// Set which historical state the op-reth nodes keep (default: [`PruningMode::Archive`]).
     ///
-    /// This method:
-    /// 1. Generates a network name if not provided
-    /// 2. Generates a random L2 chain ID if not provided
-    /// 3. Creates the output data directory if it doesn't exist
-    /// 4. Fetches genesis timestamp from L1 RPC if an RPC URL is provided
-    pub async fn build(self) -> Result<Deployer> {
-        // Validate genesis mode constraints
+    /// Archive nodes answer state queries such as `eth_getBalance` at any block; pruned
+    /// nodes only for the last [`MINIMUM_PRUNING_DISTANCE`](crate::MINIMUM_PRUNING_DISTANCE)
+    /// blocks, with a smaller datadir. Nodes with the historical proofs ExEx stay archive.
+    pub fn op_reth_pruning(mut self, pruning: PruningMode) -> Self {
+        self.op_reth_pruning = pruning;
+        self
+    }
+
+    /// Pin the images of the services that will be deployed to their current digests.
+    async fn pin_image_digests(&mut self) -> Result<()> {
+        let docker = bollard::Docker::connect_with_local_defaults()
+            .context("Failed to connect to Docker. Is Docker running?")?;
+
+        let op_node = self.consensus_client == ConsensusClient::OpNode;
+        let images = [
+            (self.external_l1.is_none(), &mut self.anvil_docker),
+            (true, &mut self.op_reth_docker),
+            (!op_node, &mut self.kona_node_docker),
+            (op_node, &mut self.op_node_docker),
+            (true, &mut self.op_batcher_docker),
+            (!self.no_proposer, &mut self.op_proposer_docker),
+            (!self.no_challenger, &mut self.op_challenger_docker),
+            (self.sequencer_count > 1, &mut self.op_conductor_docker),
+            (self.flashblocks, &mut self.op_rbuilder_docker),
+            (true, &mut self.op_deployer_docker),
+            (self.monitoring_enabled, &mut self.prometheus_docker),
+            (self.monitoring_enabled, &mut self.grafana_docker),
+            (self.logs_enabled, &mut self.loki_docker),
+            (self.logs_enabled, &mut self.promtail_docker),
+            (self.blockscout, &mut self.blockscout_docker),
+        ];
+        for (used, image) in images {
+            if used {
+                image
+                    .pin_digest(&docker)
+                    .await
+                    .with_context(|| format!("Failed to pin the digest of {}", image))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Why the subnet, gateway and external network settings are inconsistent, if they are.
+    fn docker_network_error(&self) -> Option<String> {
+        if let Some(ref name) = self.external_network {
+            if !is_valid_docker_name(name) {
+                return Some(format!("external network name {name:?} is invalid"));
+            }
+            if self.network_subnet.is_some() || self.network_gateway.is_some() {
+                return Some(
+                    "the subnet and gateway of an external network cannot be set".to_string(),
+                );
+            }
+            return None;
+        }
+        let subnet = match self.network_subnet {
+            Some(ref subnet) => match crate::docker::parse_cidr(subnet) {
+                Some(subnet) => subnet,
+                None => return Some(format!("subnet {subnet:?} is not in CIDR notation")),
+            },
+            None if self.network_gateway.is_some() => {
+                return Some("a gateway requires a subnet".to_string());
+            }
+            None => return None,
+        };
+        let gateway = self.network_gateway.as_deref()?;
+        match gateway.parse() {
+            Ok(ip) if crate::docker::subnet_contains(subnet, ip) => None,
+            Ok(_) => Some(format!(
+                "gateway {gateway} is outside of subnet {}",
+                self.network_subnet.as_deref().unwrap_or_default()
+            )),
+            Err(_) => Some(format!("gateway {gateway:?} is not an IP address")),
+        }
+    }
+
+    /// Check the configuration for problems without building anything.
+    ///
+    /// Returns every detected problem at once rather than stopping at the first one.
+    /// Called by [`DeployerBuilder::build`].
+    pub fn validate(&self) -> std::result::Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        if self.l1_chain_id == 0 {
+            errors.push(ConfigError::ZeroChainId {
+                field: "l1_chain_id",
+            });
+        }
+        match self.l2_chain_id {
+            Some(0) => errors.push(ConfigError::ZeroChainId {
+                field: "l2_chain_id",
+            }),
+            Some(chain_id) if chain_id == self.l1_chain_id => {
+                errors.push(ConfigError::SameChainIds { chain_id })
+            }
+            _ => {}
+        }
+
+        if self.block_time == 0 {
+            errors.push(ConfigError::ZeroBlockTime);
+        } else if let Some(l2_block_time) = self.l2_block_time
+            && (l2_block_time == 0 || !self.block_time.is_multiple_of(l2_block_time))
+        {
+            errors.push(ConfigError::InvalidL2BlockTime {
+                l2_block_time,
+                block_time: self.block_time,
+            });
+        }
+
+        if let Some(ref hardfork) = self.anvil_hardfork
+            && !crate::services::anvil::is_known_hardfork(hardfork)
+        {
+            errors.push(ConfigError::UnknownHardfork {
+                hardfork: hardfork.clone(),
+            });
+        }
+
+        if (self.anvil_mnemonic.is_some() || self.anvil_derivation_path.is_some())
+            && let Err(e) = (AnvilConfig {
+                mnemonic: self.anvil_mnemonic.clone(),
+                derivation_path: self.anvil_derivation_path.clone(),
+                ..Default::default()
+            })
+            .derive_accounts()
+        {
+            errors.push(ConfigError::InvalidAnvilAccounts {
+                reason: format!("{:#}", e),
+            });
+        }
+
+        for (service, level) in &self.service_log_levels {
+            if let Err(e) = crate::logs::parse_service_log_level(&format!("{service}={level}")) {
+                errors.push(ConfigError::InvalidServiceLogLevel {
+                    reason: format!("{e:#}"),
+                });
+            }
+        }
+
+        if let Some(ref address) = self.sequencer_fee_recipient
+            && !crate::verification::is_address(address)
+        {
+            errors.push(ConfigError::InvalidSequencerFeeRecipient {
+                address: address.clone(),
+            });
+        }
+
+        let mut previous: Option<(&str, u64)> = None;
+        for (name, _, _) in L2_HARDFORKS {
+            let Some(&offset) = self.hardfork_offsets.get(*name) else {
+                continue;
+            };
+            if let Some((previous, previous_offset)) = previous
+                && offset < previous_offset
+            {
+                errors.push(ConfigError::MisorderedL2Hardfork {
+                    fork: name.to_string(),
+                    offset,
+                    previous: previous.to_string(),
+                    previous_offset,
+                });
+            }
+            previous = Some((name, offset));
+        }
+        for fork in self.hardfork_offsets.keys() {
+            if !L2_HARDFORKS.iter().any(|(name, _, _)| name == fork) {
+                errors.push(ConfigError::UnknownL2Hardfork { fork: fork.clone() });
+            }
+        }
+
+        if let Some(gas_limit) = self.gas_limit
+            && !(MIN_L2_GAS_LIMIT..=MAX_L2_GAS_LIMIT).contains(&gas_limit)
+        {
+            errors.push(ConfigError::InvalidGasLimit { gas_limit });
+        }
+        for (field, value) in [
+            ("eip1559_denominator", self.eip1559_denominator),
+            ("eip1559_elasticity", self.eip1559_elasticity),
+        ] {
+            if let Some(value) = value
+                && !(1..=u64::from(u32::MAX)).contains(&value)
+            {
+                errors.push(ConfigError::InvalidEip1559Param { field, value });
+            }
+        }
+
+        for (layer, locator) in [
+            ("L1", &self.l1_contracts_locator),
+            ("L2", &self.l2_contracts_locator),
+        ] {
+            if let Some(locator) = locator
+                && !crate::services::op_deployer::is_valid_contracts_locator(locator)
+            {
+                errors.push(ConfigError::InvalidContractsLocator {
+                    layer,
+                    locator: locator.clone(),
+                });
+            }
+        }
+
+        if self.sequencer_count == 0 {
+            errors.push(ConfigError::NoSequencers);
+        } else if self.sequencer_count > self.l2_node_count {
+            errors.push(ConfigError::TooManySequencers {
+                sequencer_count: self.sequencer_count,
+                l2_node_count: self.l2_node_count,
+            });
+        }
+
+        let mut l2_chain_ids: Vec<u64> = self.l2_chain_id.into_iter().collect();
+        for (chain_id, config) in &self.additional_l2_chains {
+            if *chain_id == 0 {
+                errors.push(ConfigError::ZeroChainId {
+                    field: "additional l2_chain_id",
+                });
+            } else if *chain_id == self.l1_chain_id {
+                errors.push(ConfigError::SameChainIds {
+                    chain_id: *chain_id,
+                });
+            } else if l2_chain_ids.contains(chain_id) {
+                errors.push(ConfigError::DuplicateL2ChainId {
+                    chain_id: *chain_id,
+                });
+            }
+            l2_chain_ids.push(*chain_id);
+
+            if config.sequencer_count == 0 {
+                errors.push(ConfigError::NoSequencers);
+            } else if config.sequencer_count > config.l2_node_count {
+                errors.push(ConfigError::TooManySequencers {
+                    sequencer_count: config.sequencer_count,
+                    l2_node_count: config.l2_node_count,
+                });
+            }
+        }
+        if !self.additional_l2_chains.is_empty() {
+            if self.snapshot.is_some() {
+                errors.push(ConfigError::AdditionalL2ChainConflict { option: "snapshot" });
+            }
+            if self.intent_file.is_some() {
+                errors.push(ConfigError::AdditionalL2ChainConflict {
+                    option: "a custom intent file",
+                });
+            }
+        }
+
+        if let Some(ref name) = self.network_name
+            && !is_valid_docker_name(name)
+        {
+            errors.push(ConfigError::InvalidNetworkName { name: name.clone() });
+        }
+
+        if let Some(reason) = self.docker_network_error() {
+            errors.push(ConfigError::InvalidDockerNetwork { reason });
+        }
+
+        let images = [
+            ("anvil", &self.anvil_docker),
+            ("op-reth", &self.op_reth_docker),
+            ("kona-node", &self.kona_node_docker),
+            ("op-node", &self.op_node_docker),
+            ("op-batcher", &self.op_batcher_docker),
+            ("op-proposer", &self.op_proposer_docker),
+            ("op-challenger", &self.op_challenger_docker),
+            ("op-conductor", &self.op_conductor_docker),
+            ("op-rbuilder", &self.op_rbuilder_docker),
+            ("op-deployer", &self.op_deployer_docker),
+            ("prometheus", &self.prometheus_docker),
+            ("grafana", &self.grafana_docker),
+            ("loki", &self.loki_docker),
+            ("promtail", &self.promtail_docker),
+            ("blockscout", &self.blockscout_docker),
+        ];
+        for (service, image) in images {
+            // Local binaries are packaged into an image at deploy time.
+            if image.binary.is_some() {
+                continue;
+            }
+            if image.image.as_deref().is_none_or(|i| i.trim().is_empty()) {
+                errors.push(ConfigError::EmptyImage { service });
+            }
+            if let Some(ref digest) = image.digest {
+                if !crate::docker::is_valid_digest(digest) {
+                    errors.push(ConfigError::InvalidImageDigest {
+                        service,
+                        digest: digest.clone(),
+                    });
+                }
+                // The tag of a pinned image is only informative.
+                if image.tag.is_none() {
+                    continue;
+                }
+            }
+            let tag = image.tag.as_deref().unwrap_or_default();
+            if !is_valid_docker_tag(tag) {
+                errors.push(ConfigError::InvalidImageTag {
+                    service,
+                    tag: tag.to_string(),
+                });
+            }
+        }
+
         if self.deployment_target == crate::DeploymentTarget::Genesis {
             if self.l1_rpc_url.is_some() {
-                anyhow::bail!(
-                    "Genesis deployment mode is incompatible with L1 forking (--l1). \
-                     Genesis mode deploys contracts into the L1 genesis state and requires a local Anvil instance."
-                );
+                errors.push(ConfigError::GenesisWithFork);
             }
             if self.override_state.is_some() {
-                anyhow::bail!(
-                    "Genesis deployment mode is incompatible with --override-state. \
-                     Genesis mode boots Anvil from a generated L1 genesis, not an external state file."
-                );
+                errors.push(ConfigError::GenesisWithOverrideState);
+            }
+        } else if self.deterministic_seed.is_some() {
+            errors.push(ConfigError::DeterministicWithLiveTarget);
+        }
+
+        if let Some(ref external_l1) = self.external_l1 {
+            if self.l1_rpc_url.is_some() {
+                errors.push(ConfigError::ExternalL1Conflict {
+                    option: "L1 forking",
+                });
+            }
+            if self.override_state.is_some() {
+                errors.push(ConfigError::ExternalL1Conflict {
+                    option: "override_state",
+                });
+            }
+            if self.snapshot.is_some() {
+                errors.push(ConfigError::ExternalL1Conflict { option: "snapshot" });
+            }
+            if self.anvil_hardfork.is_some() {
+                errors.push(ConfigError::ExternalL1Conflict {
+                    option: "anvil hardfork",
+                });
+            }
+            if self.anvil_auto_recover {
+                errors.push(ConfigError::ExternalL1Conflict {
+                    option: "anvil auto-recover",
+                });
             }
+            if self.anvil_mnemonic.is_some() || self.anvil_derivation_path.is_some() {
+                errors.push(ConfigError::ExternalL1Conflict {
+                    option: "anvil mnemonic",
+                });
+            }
+            if self.deployment_target == crate::DeploymentTarget::Genesis {
+                errors.push(ConfigError::ExternalL1Conflict {
+                    option: "genesis deployment mode",
+                });
+            }
+            if let Err(e) = external_l1.handler() {
+                errors.push(ConfigError::InvalidExternalL1 {
+                    reason: format!("{e:#}"),
+                });
+            }
+        }
+
+        // Spares submit with the extra accounts of Anvil; an external L1 provides none.
+        let requested = self.spare_batchers + self.spare_proposers;
+        let available = match self.external_l1 {
+            Some(_) => 0,
+            None => {
+                crate::services::anvil::DEFAULT_ACCOUNT_COUNT
+                    - crate::AnvilAccounts::MIN_REQUIRED_ACCOUNTS
+            }
+        };
+        if requested > available {
+            errors.push(ConfigError::NotEnoughSpareAccounts {
+                requested,
+                available,
+            });
+        } else if !self.additional_l2_chains.is_empty() {
+            let chains = self.additional_l2_chains.len();
+            let chain_accounts = chains * crate::AnvilAccounts::SUBMITTER_ACCOUNTS_PER_CHAIN;
+            if requested + chain_accounts > available {
+                errors.push(ConfigError::NotEnoughL2ChainAccounts {
+                    chains,
+                    spares: requested,
+                    requested: requested + chain_accounts,
+                    available,
+                });
+            }
+        }
+        if self.spare_proposers > 0 && self.no_proposer {
+            errors.push(ConfigError::SpareProposersWithoutProposer);
+        }
+
+        if self.rpc_client.request_timeout_secs == 0 {
+            errors.push(ConfigError::ZeroRpcTimeout { which: "request" });
+        }
+        if self.rpc_client.connect_timeout_secs == Some(0) {
+            errors.push(ConfigError::ZeroRpcTimeout { which: "connect" });
+        }
+
+        if self.logs_enabled && !self.monitoring_enabled {
+            errors.push(ConfigError::LogsWithoutMonitoring);
+        }
+
+        if let Some(ref alt_da) = self.alt_da {
+            if self.consensus_client != ConsensusClient::OpNode {
+                errors.push(ConfigError::AltDaRequiresOpNode);
+            }
+            let reason = if alt_da.da_server.trim().is_empty() {
+                Some("the DA server URL is empty".to_string())
+            } else if alt_da.challenge_window == 0 || alt_da.resolve_window == 0 {
+                Some("the challenge and resolve windows must be at least one L1 block".to_string())
+            } else if alt_da.resolver_refund_percentage > 100 {
+                Some(format!(
+                    "resolver refund percentage {} is above 100",
+                    alt_da.resolver_refund_percentage
+                ))
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                errors.push(ConfigError::InvalidAltDa { reason });
+            }
+        }
+
+        // The host ports depend on the layout, which is only meaningful for a valid config.
+        if errors.is_empty()
+            && let Some(reason) = self.host_port_conflict()
+        {
+            errors.push(ConfigError::ConflictingHostPorts { reason });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Lay out the services and describe the first host port published by two of them.
+    ///
+    /// Covers the fixed ports of [`Self::fixed_ports`] and the metrics ports of
+    /// [`Self::expose_metrics`].
+    fn host_port_conflict(&self) -> Option<String> {
+        let mut deployer = self.clone().assemble(
+            self.network_name.as_deref().unwrap_or("kup"),
+            self.l2_chain_id.unwrap_or_default(),
+            PathBuf::new(),
+            None,
+            None,
+        );
+        if self.fixed_ports
+            && let Err(e) = deployer.fix_host_ports()
+        {
+            return Some(format!("{e:#}"));
+        }
+        std::iter::once(&deployer.l2_stack)
+            .chain(deployer.additional_l2_chains.iter().map(|c| &c.l2_stack))
+            .find_map(|stack| stack.unique_metrics_host_ports().err())
+            .map(|e| format!("{e:#}"))
+    }
+
+    /// The L2 stack of one chain, with its containers named `<name_prefix>-<service>`.
+    ///
+    /// With [`Self::expose_metrics`], the metrics host ports start `metrics_offset` ports
+    /// above the default base ports, so that several chains don't collide. Spares are left
+    /// to the caller.
+    fn l2_stack_builder(
+        &self,
+        name_prefix: &str,
+        sequencer_count: usize,
+        l2_node_count: usize,
+        proofs_validators: usize,
+        metrics_offset: u16,
+    ) -> L2StackBuilder {
+        let validator_count = l2_node_count.saturating_sub(sequencer_count);
+        let needs_conductor = sequencer_count > 1;
+
+        // Build sequencer nodes, each with optional conductor config
+        let mut sequencers = Vec::with_capacity(sequencer_count);
+        for i in 0..sequencer_count {
+            let suffix = if i == 0 {
+                String::new()
+            } else {
+                format!("-sequencer-{}", i)
+            };
+
+            // Create conductor config for each sequencer if multi-sequencer setup
+            let op_conductor = if needs_conductor {
+                let conductor_suffix = if i == 0 {
+                    String::new()
+                } else {
+                    format!("-{}", i)
+                };
+                Some(
+                    OpConductorBuilder {
+                        docker_image: self.op_conductor_docker.clone(),
+                        container_name: format!("{}-op-conductor{}", name_prefix, conductor_suffix),
+                        log_level: self.quiet_services.then(|| "INFO".to_string()),
+                        ..Default::default()
+                    }
+                    .raft_tuning_for_l2_block_time(
+                        self.l2_block_time
+                            .unwrap_or(crate::services::op_deployer::DEFAULT_L2_BLOCK_TIME_SECS),
+                    ),
+                )
+            } else {
+                None
+            };
+
+            // When flashblocks is enabled, sequencers use op-rbuilder image
+            let sequencer_docker_image = if self.flashblocks {
+                self.op_rbuilder_docker.clone()
+            } else {
+                self.op_reth_docker.clone()
+            };
+
+            sequencers.push(L2NodeBuilder {
+                role: L2NodeRole::Sequencer,
+                op_reth: OpRethBuilder {
+                    docker_image: sequencer_docker_image,
+                    container_name: format!("{}-op-reth{}", name_prefix, suffix),
+                    flashblocks_enabled: self.flashblocks,
+                    flashblocks_port: self.flashblocks.then_some(DEFAULT_FLASHBLOCKS_PORT),
+                    log_filter: self.quiet_services.then(|| "info".to_string()),
+                    ..Default::default()
+                },
+                kona_node: KonaNodeBuilder {
+                    docker_image: self.kona_node_docker.clone(),
+                    container_name: format!("{}-kona-node{}", name_prefix, suffix),
+                    l1_slot_duration: self.block_time,
+                    rpc_host_port: Some(0), // Explicitly publish RPC port
+                    metrics_host_port: if self.publish_all_ports {
+                        Some(0)
+                    } else {
+                        None
+                    },
+                    flashblocks_enabled: self.flashblocks,
+                    flashblocks_relay_port: self
+                        .flashblocks
+                        .then_some(DEFAULT_FLASHBLOCKS_RELAY_PORT),
+                    verbosity: self.quiet_services.then(|| "-vvv".to_string()),
+                    ..Default::default()
+                },
+                op_node: None,
+                op_conductor,
+                sidecars: Default::default(),
+            });
+        }
+
+        // Build validator nodes (no conductors)
+        let mut validators = Vec::with_capacity(validator_count + proofs_validators);
+        for i in 0..validator_count {
+            validators.push(L2NodeBuilder {
+                role: L2NodeRole::Validator,
+                op_reth: OpRethBuilder {
+                    docker_image: self.op_reth_docker.clone(),
+                    container_name: format!("{}-op-reth-validator-{}", name_prefix, i + 1),
+                    log_filter: self.quiet_services.then(|| "info".to_string()),
+                    ..Default::default()
+                },
+                kona_node: KonaNodeBuilder {
+                    docker_image: self.kona_node_docker.clone(),
+                    container_name: format!("{}-kona-node-validator-{}", name_prefix, i + 1),
+                    l1_slot_duration: self.block_time,
+                    rpc_host_port: Some(0), // Explicitly publish RPC port
+                    metrics_host_port: if self.publish_all_ports {
+                        Some(0)
+                    } else {
+                        None
+                    },
+                    // Validators consume flashblocks but don't relay them
+                    flashblocks_enabled: self.flashblocks,
+                    verbosity: self.quiet_services.then(|| "-vvv".to_string()),
+                    ..Default::default()
+                },
+                op_node: None,
+                op_conductor: None,
+                sidecars: Default::default(),
+            });
+        }
+
+        // Build additional validator nodes with proofs history ExEx enabled
+        for i in 0..proofs_validators {
+            let idx = validator_count + i + 1;
+            validators.push(L2NodeBuilder {
+                role: L2NodeRole::Validator,
+                op_reth: OpRethBuilder {
+                    docker_image: self.op_reth_docker.clone(),
+                    container_name: format!("{}-op-reth-validator-{}", name_prefix, idx),
+                    proofs_history: true,
+                    log_filter: self.quiet_services.then(|| "info".to_string()),
+                    ..Default::default()
+                },
+                kona_node: KonaNodeBuilder {
+                    docker_image: self.kona_node_docker.clone(),
+                    container_name: format!("{}-kona-node-validator-{}", name_prefix, idx),
+                    l1_slot_duration: self.block_time,
+                    rpc_host_port: Some(0),
+                    metrics_host_port: if self.publish_all_ports {
+                        Some(0)
+                    } else {
+                        None
+                    },
+                    flashblocks_enabled: self.flashblocks,
+                    verbosity: self.quiet_services.then(|| "-vvv".to_string()),
+                    ..Default::default()
+                },
+                op_node: None,
+                op_conductor: None,
+                sidecars: Default::default(),
+            });
         }
 
-        // Generate L2 chain ID if not provided
-        let l2_chain_id = self
-            .l2_chain_id
-            .unwrap_or_else(|| rand::rng().random_range(10000..=99999));
+        let mut l2_stack = L2StackBuilder {
+            sequencers,
+            validators,
+            op_batcher: OpBatcherBuilder {
+                docker_image: self.op_batcher_docker.clone(),
+                container_name: format!("{}-op-batcher", name_prefix),
+                log_level: self.quiet_services.then(|| "INFO".to_string()),
+                da_type: self.batcher_da_type,
+                ..Default::default()
+            },
+            op_proposer: if self.no_proposer {
+                None
+            } else {
+                Some(OpProposerBuilder {
+                    docker_image: self.op_proposer_docker.clone(),
+                    container_name: format!("{}-op-proposer", name_prefix),
+                    log_level: self.quiet_services.then(|| "INFO".to_string()),
+                    ..Default::default()
+                })
+            },
+            spare_batchers: Vec::new(),
+            spare_proposers: Vec::new(),
+            op_challenger: if self.no_challenger {
+                None
+            } else {
+                Some(OpChallengerBuilder {
+                    docker_image: self.op_challenger_docker.clone(),
+                    container_name: format!("{}-op-challenger", name_prefix),
+                    log_level: self.quiet_services.then(|| "INFO".to_string()),
+                    ..Default::default()
+                })
+            },
+            genesis_patch: None,
+            prefund: Default::default(),
+            hardfork_offsets: self.hardfork_offsets.clone(),
+            start_sequencing: self.start_sequencing,
+        }
+        .consensus_client(self.consensus_client, self.op_node_docker.clone())
+        .net_admin(self.net_admin)
+        .op_reth_pruning(self.op_reth_pruning)
+        .maybe_p2p_keys_from_seed(self.deterministic_seed)
+        .alt_da_server(self.alt_da.as_ref().map(|alt_da| alt_da.da_server.clone()));
+        if self.expose_metrics {
+            l2_stack = l2_stack.metrics_base_ports(
+                DEFAULT_OP_RETH_METRICS_BASE_PORT.saturating_add(metrics_offset),
+                DEFAULT_CONSENSUS_METRICS_BASE_PORT.saturating_add(metrics_offset),
+            );
+        }
+        for &(address, amount) in &self.prefund {
+            l2_stack = l2_stack.prefund(address, amount);
+        }
+        // Overrides were checked by `validate`.
+        let log_levels = self
+            .service_log_levels
+            .iter()
+            .filter_map(|(service, level)| {
+                crate::logs::parse_service_log_level(&format!("{service}={level}")).ok()
+            });
+        for (service, level) in log_levels {
+            l2_stack = l2_stack.log_level(&service, &level);
+        }
+        l2_stack
+    }
+
+    /// Build the [`Deployer`] configuration.
+    ///
+    /// This method:
+    /// 0. Validates the configuration (see [`DeployerBuilder::validate`])
+    /// 1. Generates a network name if not provided, and checks that no existing container
+    ///    already uses it as a prefix (see [`NameCollision`])
+    /// 2. Generates a random L2 chain ID if not provided
+    /// 3. Creates the output data directory if it doesn't exist
+    /// 4. Fetches genesis timestamp from L1 RPC if an RPC URL is provided
+    pub async fn build(mut self) -> Result<Deployer> {
+        if let Err(errors) = self.validate() {
+            let details = errors
+                .iter()
+                .map(|e| format!("  - {e}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            anyhow::bail!("Invalid deployer configuration:\n{details}");
+        }
+
+        if self.pin_all_digests {
+            self.pin_image_digests().await?;
+        }
+
+        // Generate L2 chain ID if not provided, distinct from the additional chains
+        let l2_chain_id = self.l2_chain_id.unwrap_or_else(|| {
+            std::iter::repeat_with(|| rand::rng().random_range(10000..=99999))
+                .find(|id| {
+                    !self
+                        .additional_l2_chains
+                        .iter()
+                        .any(|(other, _)| other == id)
+                })
+                .expect("the iterator is infinite")
+        });
 
         // Generate network name if not provided
-        let network_name = self.network_name.unwrap_or_else(|| {
+        let generated = self.network_name.is_none();
+        let network_name = self.network_name.take().unwrap_or_else(|| {
             let name = names::Generator::default()
                 .next()
                 .unwrap_or_else(|| "unknown-network".to_string());
             format!("kup-{}", name)
         });
+        let policy = if generated {
+            NameCollision::Suffix
+        } else {
+            self.name_collision
+        };
+        let network_name = check_name_collision(network_name, policy).await?;
 
         // Determine output data path
-        let outdata_path = match self.outdata {
+        let outdata_path = match self.outdata.take() {
             None => PathBuf::from(format!("data-{}", network_name)),
             Some(OutDataPath::TempDir) => {
                 let temp_dir = tempdir::TempDir::new("data-kup-")
@@ -836,8 +2697,59 @@ impl DeployerBuilder {
             "Building OP Stack deployer configuration..."
         );
 
-        // Build the Deployer
-        let deployer = Deployer {
+        let fixed_ports = self.fixed_ports;
+        let mut deployer = self.assemble(
+            &network_name,
+            l2_chain_id,
+            outdata_path,
+            genesis_timestamp,
+            fork_block_number,
+        );
+        if fixed_ports {
+            deployer.fix_host_ports()?;
+        }
+
+        Ok(deployer)
+    }
+
+    /// Lay out the [`Deployer`] configuration once the values resolved by
+    /// [`DeployerBuilder::build`] are known.
+    fn assemble(
+        self,
+        network_name: &str,
+        l2_chain_id: u64,
+        outdata_path: PathBuf,
+        genesis_timestamp: Option<u64>,
+        fork_block_number: Option<u64>,
+    ) -> Deployer {
+        let l2_stack = self
+            .l2_stack_builder(
+                network_name,
+                self.sequencer_count,
+                self.l2_node_count,
+                self.proofs_validators,
+                0,
+            )
+            .spare_batchers(self.spare_batchers)
+            .spare_proposers(self.spare_proposers);
+        let mut metrics_offset = l2_stack.node_count();
+        let additional_l2_chains = self
+            .additional_l2_chains
+            .iter()
+            .map(|&(chain_id, config)| {
+                let l2_stack = self.l2_stack_builder(
+                    &format!("{}-{}", network_name, chain_id),
+                    config.sequencer_count,
+                    config.l2_node_count,
+                    0,
+                    u16::try_from(metrics_offset).unwrap_or(u16::MAX),
+                );
+                metrics_offset += l2_stack.node_count();
+                L2ChainBuilder { chain_id, l2_stack }
+            })
+            .collect();
+
+        Deployer {
             l1_chain_id: self.l1_chain_id,
             l2_chain_id,
             outdata: outdata_path,
@@ -850,6 +2762,10 @@ impl DeployerBuilder {
                 fork_block_number,
                 block_time: self.block_time,
                 quiet: self.quiet_services,
+                hardfork: self.anvil_hardfork,
+                auto_recover: self.anvil_auto_recover,
+                mnemonic: self.anvil_mnemonic,
+                derivation_path: self.anvil_derivation_path,
                 ..Default::default()
             },
 
@@ -860,181 +2776,40 @@ impl DeployerBuilder {
                 log_max_size: self.log_max_size,
                 log_max_file: self.log_max_file,
                 stream_logs: self.stream_logs,
+                restart_policy: self.restart_policy,
+                extra_hosts: self.extra_hosts,
+                ca_bundle: self.ca_bundle,
+                proxy: self.proxy,
+                subnet: self.network_subnet,
+                gateway: self.network_gateway,
+                external_network: self.external_network,
+                rpc_client: self.rpc_client,
+                run_as_host_user: self.run_as_host_user,
+                recorded_host_ports: Default::default(),
             },
 
             op_deployer: OpDeployerConfig {
                 docker_image: self.op_deployer_docker,
                 container_name: format!("{}-op-deployer", network_name),
+                l2_block_time: self.l2_block_time,
+                intent_file: self.intent_file,
+                patch_roles: self.patch_intent_roles,
+                sequencer_fee_recipient: self.sequencer_fee_recipient,
+                l1_contracts_locator: self.l1_contracts_locator,
+                l2_contracts_locator: self.l2_contracts_locator,
+                alt_da: self.alt_da.clone(),
+                additional_l2_chain_ids: self
+                    .additional_l2_chains
+                    .iter()
+                    .map(|(chain_id, _)| *chain_id)
+                    .collect(),
+                gas_limit: self.gas_limit,
+                eip1559_denominator: self.eip1559_denominator,
+                eip1559_elasticity: self.eip1559_elasticity,
             },
 
-            l2_stack: {
-                // Validate sequencer count
-                let sequencer_count = self.sequencer_count.min(self.l2_node_count);
-                let validator_count = self.l2_node_count.saturating_sub(sequencer_count);
-                let needs_conductor = sequencer_count > 1;
-
-                // Build sequencer nodes, each with optional conductor config
-                let mut sequencers = Vec::with_capacity(sequencer_count);
-                for i in 0..sequencer_count {
-                    let suffix = if i == 0 {
-                        String::new()
-                    } else {
-                        format!("-sequencer-{}", i)
-                    };
-
-                    // Create conductor config for each sequencer if multi-sequencer setup
-                    let op_conductor = if needs_conductor {
-                        let conductor_suffix = if i == 0 {
-                            String::new()
-                        } else {
-                            format!("-{}", i)
-                        };
-                        Some(OpConductorBuilder {
-                            docker_image: self.op_conductor_docker.clone(),
-                            container_name: format!(
-                                "{}-op-conductor{}",
-                                network_name, conductor_suffix
-                            ),
-                            log_level: self.quiet_services.then(|| "INFO".to_string()),
-                            ..Default::default()
-                        })
-                    } else {
-                        None
-                    };
-
-                    // When flashblocks is enabled, sequencers use op-rbuilder image
-                    let sequencer_docker_image = if self.flashblocks {
-                        self.op_rbuilder_docker.clone()
-                    } else {
-                        self.op_reth_docker.clone()
-                    };
-
-                    sequencers.push(L2NodeBuilder {
-                        role: L2NodeRole::Sequencer,
-                        op_reth: OpRethBuilder {
-                            docker_image: sequencer_docker_image,
-                            container_name: format!("{}-op-reth{}", network_name, suffix),
-                            flashblocks_enabled: self.flashblocks,
-                            flashblocks_port: self.flashblocks.then_some(DEFAULT_FLASHBLOCKS_PORT),
-                            log_filter: self.quiet_services.then(|| "info".to_string()),
-                            ..Default::default()
-                        },
-                        kona_node: KonaNodeBuilder {
-                            docker_image: self.kona_node_docker.clone(),
-                            container_name: format!("{}-kona-node{}", network_name, suffix),
-                            l1_slot_duration: self.block_time,
-                            rpc_host_port: Some(0), // Explicitly publish RPC port
-                            metrics_host_port: if self.publish_all_ports {
-                                Some(0)
-                            } else {
-                                None
-                            },
-                            flashblocks_enabled: self.flashblocks,
-                            flashblocks_relay_port: self
-                                .flashblocks
-                                .then_some(DEFAULT_FLASHBLOCKS_RELAY_PORT),
-                            verbosity: self.quiet_services.then(|| "-vvv".to_string()),
-                            ..Default::default()
-                        },
-                        op_conductor,
-                    });
-                }
-
-                // Build validator nodes (no conductors)
-                let mut validators = Vec::with_capacity(validator_count + self.proofs_validators);
-                for i in 0..validator_count {
-                    validators.push(L2NodeBuilder {
-                        role: L2NodeRole::Validator,
-                        op_reth: OpRethBuilder {
-                            docker_image: self.op_reth_docker.clone(),
-                            container_name: format!("{}-op-reth-validator-{}", network_name, i + 1),
-                            log_filter: self.quiet_services.then(|| "info".to_string()),
-                            ..Default::default()
-                        },
-                        kona_node: KonaNodeBuilder {
-                            docker_image: self.kona_node_docker.clone(),
-                            container_name: format!(
-                                "{}-kona-node-validator-{}",
-                                network_name,
-                                i + 1
-                            ),
-                            l1_slot_duration: self.block_time,
-                            rpc_host_port: Some(0), // Explicitly publish RPC port
-                            metrics_host_port: if self.publish_all_ports {
-                                Some(0)
-                            } else {
-                                None
-                            },
-                            // Validators consume flashblocks but don't relay them
-                            flashblocks_enabled: self.flashblocks,
-                            verbosity: self.quiet_services.then(|| "-vvv".to_string()),
-                            ..Default::default()
-                        },
-                        op_conductor: None,
-                    });
-                }
-
-                // Build additional validator nodes with proofs history ExEx enabled
-                for i in 0..self.proofs_validators {
-                    let idx = validator_count + i + 1;
-                    validators.push(L2NodeBuilder {
-                        role: L2NodeRole::Validator,
-                        op_reth: OpRethBuilder {
-                            docker_image: self.op_reth_docker.clone(),
-                            container_name: format!("{}-op-reth-validator-{}", network_name, idx),
-                            proofs_history: true,
-                            log_filter: self.quiet_services.then(|| "info".to_string()),
-                            ..Default::default()
-                        },
-                        kona_node: KonaNodeBuilder {
-                            docker_image: self.kona_node_docker.clone(),
-                            container_name: format!("{}-kona-node-validator-{}", network_name, idx),
-                            l1_slot_duration: self.block_time,
-                            rpc_host_port: Some(0),
-                            metrics_host_port: if self.publish_all_ports {
-                                Some(0)
-                            } else {
-                                None
-                            },
-                            flashblocks_enabled: self.flashblocks,
-                            verbosity: self.quiet_services.then(|| "-vvv".to_string()),
-                            ..Default::default()
-                        },
-                        op_conductor: None,
-                    });
-                }
-
-                L2StackBuilder {
-                    sequencers,
-                    validators,
-                    op_batcher: OpBatcherBuilder {
-                        docker_image: self.op_batcher_docker,
-                        container_name: format!("{}-op-batcher", network_name),
-                        log_level: self.quiet_services.then(|| "INFO".to_string()),
-                        ..Default::default()
-                    },
-                    op_proposer: if self.no_proposer {
-                        None
-                    } else {
-                        Some(OpProposerBuilder {
-                            docker_image: self.op_proposer_docker,
-                            container_name: format!("{}-op-proposer", network_name),
-                            log_level: self.quiet_services.then(|| "INFO".to_string()),
-                            ..Default::default()
-                        })
-                    },
-                    op_challenger: if self.no_challenger {
-                        None
-                    } else {
-                        Some(OpChallengerBuilder {
-                            docker_image: self.op_challenger_docker,
-                            container_name: format!("{}-op-challenger", network_name),
-                            log_level: self.quiet_services.then(|| "INFO".to_string()),
-                            ..Default::default()
-                        })
-                    },
-                }
-            },
+            l2_stack,
+            additional_l2_chains,
 
             monitoring: MonitoringConfig {
                 prometheus: PrometheusConfig {
@@ -1048,6 +2823,27 @@ impl DeployerBuilder {
                     ..Default::default()
                 },
                 enabled: self.monitoring_enabled,
+                logs: LogsConfig {
+                    loki: LokiConfig {
+                        docker_image: self.loki_docker,
+                        container_name: format!("{}-loki", network_name),
+                        ..Default::default()
+                    },
+                    promtail: PromtailConfig {
+                        docker_image: self.promtail_docker,
+                        container_name: format!("{}-promtail", network_name),
+                        ..Default::default()
+                    },
+                    enabled: self.logs_enabled,
+                },
+            },
+
+            blockscout: BlockscoutBuilder {
+                enabled: self.blockscout,
+                docker_image: self.blockscout_docker,
+                container_name: format!("{}-blockscout", network_name),
+                postgres_container_name: format!("{}-blockscout-db", network_name),
+                ..Default::default()
             },
 
             dashboards_path: self.dashboards_path,
@@ -1057,9 +2853,14 @@ impl DeployerBuilder {
             deployment_target: self.deployment_target,
             dump_state: self.dump_state,
             override_state: self.override_state,
-        };
-
-        Ok(deployer)
+            external_l1: self.external_l1,
+            post_deploy: self.post_deploy_script.map(PostDeployScript::new),
+            min_disk_space_gib: self.min_disk_space_gib,
+            keep_going: self.keep_going,
+            verify_chain_ids: self.verify_chain_ids,
+            reuse_if_healthy: self.reuse_if_healthy,
+            restart_unhealthy: self.restart_unhealthy,
+        }
     }
 }
 
@@ -1092,4 +2893,625 @@ mod tests {
         assert!(builder.no_cleanup);
         assert!(!builder.monitoring_enabled);
     }
+
+    #[test]
+    fn test_network_name_collisions() {
+        let containers = vec![
+            "kup-devnet-anvil".to_string(),
+            "kup-devnet-2-anvil".to_string(),
+            "other-anvil".to_string(),
+        ];
+
+        assert_eq!(
+            colliding_containers("kup-devnet", &containers),
+            ["kup-devnet-anvil", "kup-devnet-2-anvil"]
+        );
+        assert!(colliding_containers("kup-fresh", &containers).is_empty());
+        assert_eq!(
+            suffixed_network_name("kup-devnet", &containers).as_deref(),
+            Some("kup-devnet-3")
+        );
+        assert_eq!(
+            suffixed_network_name("other", &containers).as_deref(),
+            Some("other-2")
+        );
+    }
+
+    #[test]
+    fn test_builder_extra_hosts() {
+        let builder = DeployerBuilder::new(11155111)
+            .extra_hosts(["beacon.local:10.0.0.5"])
+            .host_gateway(true)
+            .host_gateway(true);
+
+        assert_eq!(
+            builder.extra_hosts,
+            ["beacon.local:10.0.0.5", HOST_GATEWAY_ENTRY]
+        );
+    }
+
+    #[test]
+    fn test_preset() {
+        let builder = DeployerBuilder::new(11155111).preset(Topology::HaSequencer);
+        assert_eq!(builder.l2_node_count, 5);
+        assert_eq!(builder.sequencer_count, 3);
+        assert!(builder.validate().is_ok());
+
+        let builder = DeployerBuilder::new(11155111).preset(Topology::LargeFleet);
+        assert_eq!(builder.l2_node_count, 11);
+        assert_eq!(builder.sequencer_count, 1);
+        assert_eq!(builder.log_max_size.as_deref(), Some("10m"));
+        assert!(builder.quiet_services);
+
+        // Explicit setters after the preset win.
+        let builder = DeployerBuilder::new(11155111)
+            .preset(Topology::Solo)
+            .l2_node_count(3)
+            .block_time(6);
+        assert_eq!(builder.l2_node_count, 3);
+        assert_eq!(builder.sequencer_count, 1);
+        assert_eq!(builder.block_time, 6);
+    }
+
+    #[test]
+    fn test_validate_defaults() {
+        assert_eq!(DeployerBuilder::new(11155111).validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_all_errors() {
+        let errors = DeployerBuilder::new(11155111)
+            .l2_chain_id(11155111)
+            .network_name("bad name")
+            .block_time(0)
+            .l2_node_count(1)
+            .sequencer_count(2)
+            .op_reth_image("")
+            .kona_node_tag("v1.0 ")
+            .validate()
+            .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                ConfigError::SameChainIds { chain_id: 11155111 },
+                ConfigError::ZeroBlockTime,
+                ConfigError::TooManySequencers {
+                    sequencer_count: 2,
+                    l2_node_count: 1,
+                },
+                ConfigError::InvalidNetworkName {
+                    name: "bad name".to_string(),
+                },
+                ConfigError::EmptyImage { service: "op-reth" },
+                ConfigError::InvalidImageTag {
+                    service: "kona-node",
+                    tag: "v1.0 ".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_host_ports() {
+        let builder = DeployerBuilder::new(11155111)
+            .network_name("devnet")
+            .fixed_ports(true);
+        assert_eq!(builder.clone().l2_node_count(10).validate(), Ok(()));
+
+        // The consensus RPC of the 11th node lands on the Anvil RPC port.
+        let errors = builder.l2_node_count(11).validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        let ConfigError::ConflictingHostPorts { ref reason } = errors[0] else {
+            panic!("unexpected error: {errors:?}");
+        };
+        assert!(reason.contains("8545"), "{reason}");
+        assert!(reason.contains("devnet-anvil"), "{reason}");
+    }
+
+    #[test]
+    fn test_validate_skips_binary_images() {
+        let builder = DeployerBuilder::new(11155111)
+            .op_reth_image("")
+            .with_op_reth_binary("/tmp/op-reth");
+        assert_eq!(builder.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_spares() {
+        let builder = DeployerBuilder::new(11155111).spare_batchers(1);
+        assert_eq!(builder.clone().spare_proposers(1).validate(), Ok(()));
+        assert_eq!(
+            builder.clone().spare_proposers(20).validate(),
+            Err(vec![ConfigError::NotEnoughSpareAccounts {
+                requested: 21,
+                available: 20,
+            }])
+        );
+        assert_eq!(
+            builder.spare_proposers(1).no_proposer(true).validate(),
+            Err(vec![ConfigError::SpareProposersWithoutProposer])
+        );
+    }
+
+    #[test]
+    fn test_validate_rpc_client_config() {
+        let builder = DeployerBuilder::new(11155111);
+        assert_eq!(
+            builder
+                .clone()
+                .rpc_client_config(RpcClientConfig {
+                    request_timeout_secs: 30,
+                    connect_timeout_secs: Some(10),
+                    pool_max_idle_per_host: Some(64),
+                })
+                .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            builder
+                .rpc_client_config(RpcClientConfig {
+                    request_timeout_secs: 0,
+                    connect_timeout_secs: Some(0),
+                    pool_max_idle_per_host: None,
+                })
+                .validate(),
+            Err(vec![
+                ConfigError::ZeroRpcTimeout { which: "request" },
+                ConfigError::ZeroRpcTimeout { which: "connect" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_docker_network() {
+        let builder = DeployerBuilder::new(11155111);
+        let reason = |builder: DeployerBuilder| match builder.validate() {
+            Ok(()) => None,
+            Err(errors) => match &errors[..] {
+                [ConfigError::InvalidDockerNetwork { reason }] => Some(reason.clone()),
+                _ => panic!("unexpected errors: {errors:?}"),
+            },
+        };
+
+        let valid = builder
+            .clone()
+            .network_subnet("172.30.0.0/16")
+            .network_gateway("172.30.0.1");
+        assert_eq!(reason(valid), None);
+        assert_eq!(reason(builder.clone().network_subnet("fd00:1::/64")), None);
+        assert_eq!(reason(builder.clone().external_network("corp-net")), None);
+
+        assert!(reason(builder.clone().network_subnet("172.30.0.0")).is_some());
+        assert!(reason(builder.clone().network_gateway("172.30.0.1")).is_some());
+        assert!(
+            reason(
+                builder
+                    .clone()
+                    .network_subnet("172.30.0.0/16")
+                    .network_gateway("172.31.0.1")
+            )
+            .is_some()
+        );
+        assert!(
+            reason(
+                builder
+                    .external_network("corp-net")
+                    .network_subnet("172.30.0.0/16")
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn test_logs_enabled() {
+        let builder = DeployerBuilder::new(11155111).logs_enabled(true);
+        assert_eq!(builder.clone().validate(), Ok(()));
+        assert_eq!(
+            builder.clone().monitoring_enabled(false).validate(),
+            Err(vec![ConfigError::LogsWithoutMonitoring])
+        );
+
+        let builder = builder.loki_tag("3.4.2").promtail_image("");
+        assert_eq!(builder.loki_docker.tag.as_deref(), Some("3.4.2"));
+        assert_eq!(
+            builder.validate(),
+            Err(vec![ConfigError::EmptyImage {
+                service: "promtail"
+            }])
+        );
+    }
+
+    #[test]
+    fn test_validate_alt_da() {
+        let alt_da = AltDaConfig::new("http://da-server:3100");
+        let builder = DeployerBuilder::new(11155111).alt_da(alt_da.clone());
+        assert_eq!(
+            builder.clone().validate(),
+            Err(vec![ConfigError::AltDaRequiresOpNode])
+        );
+
+        let builder = builder.consensus_client(ConsensusClient::OpNode);
+        assert_eq!(builder.clone().validate(), Ok(()));
+        assert!(matches!(
+            builder.clone().alt_da(alt_da.clone().challenge_window(0)).validate(),
+            Err(errors) if matches!(errors[..], [ConfigError::InvalidAltDa { .. }])
+        ));
+        assert!(matches!(
+            builder.alt_da(alt_da.resolver_refund_percentage(101)).validate(),
+            Err(errors) if matches!(errors[..], [ConfigError::InvalidAltDa { .. }])
+        ));
+    }
+
+    #[test]
+    fn test_validate_external_l1() {
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let builder = DeployerBuilder::new(11155111).external_l1("http://l1:8545", key);
+        assert_eq!(builder.validate(), Ok(()));
+        assert_eq!(
+            builder.clone().anvil_auto_recover(true).validate(),
+            Err(vec![ConfigError::ExternalL1Conflict {
+                option: "anvil auto-recover"
+            }])
+        );
+
+        let errors = builder
+            .l1_rpc_url("https://ethereum-sepolia-rpc.publicnode.com")
+            .external_l1("not a url", key)
+            .validate()
+            .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            ConfigError::ExternalL1Conflict {
+                option: "L1 forking"
+            }
+        );
+        assert!(matches!(errors[1], ConfigError::InvalidExternalL1 { .. }));
+    }
+
+    #[test]
+    fn test_validate_image_digest() {
+        let mut builder = DeployerBuilder::new(11155111);
+        builder.op_reth_docker = DockerImage::from_digest(OP_RETH_DEFAULT_IMAGE, "ab".repeat(32));
+        assert_eq!(builder.validate(), Ok(()));
+
+        builder.op_reth_docker.digest = Some("sha256:abc".to_string());
+        assert_eq!(
+            builder.validate(),
+            Err(vec![ConfigError::InvalidImageDigest {
+                service: "op-reth",
+                digest: "sha256:abc".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let builder = DeployerBuilder::new(11155111).deterministic(42);
+        assert_eq!(
+            builder.l2_chain_id,
+            Some(crate::deterministic::seeded_l2_chain_id(42))
+        );
+        assert_eq!(builder.network_name.as_deref(), Some("kup-det-42"));
+        assert_eq!(
+            builder.genesis_timestamp,
+            Some(crate::deterministic::DETERMINISTIC_GENESIS_TIMESTAMP)
+        );
+        assert_eq!(builder.validate(), Ok(()));
+
+        // Explicit values win, whatever the call order.
+        let builder = DeployerBuilder::new(11155111)
+            .l2_chain_id(1234)
+            .deterministic(42)
+            .genesis_timestamp(1_700_000_000);
+        assert_eq!(builder.l2_chain_id, Some(1234));
+        assert_eq!(builder.genesis_timestamp, Some(1_700_000_000));
+
+        assert_eq!(
+            builder
+                .clone()
+                .deployment_target(crate::DeploymentTarget::Live)
+                .validate(),
+            Err(vec![ConfigError::DeterministicWithLiveTarget])
+        );
+        assert_eq!(
+            builder
+                .l1_rpc_url("https://ethereum-sepolia-rpc.publicnode.com")
+                .validate(),
+            Err(vec![ConfigError::GenesisWithFork])
+        );
+    }
+
+    #[test]
+    fn test_validate_contracts_locators() {
+        let builder = DeployerBuilder::new(11155111)
+            .l1_contracts_locator("tag://op-contracts/v3.0.0")
+            .l2_contracts_locator("file:///tmp/forge-artifacts");
+        assert_eq!(builder.clone().validate(), Ok(()));
+        assert_eq!(
+            builder.l1_contracts_locator("v3.0.0").validate(),
+            Err(vec![ConfigError::InvalidContractsLocator {
+                layer: "L1",
+                locator: "v3.0.0".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_validate_l2_block_time() {
+        let builder = DeployerBuilder::new(11155111).block_time(12);
+        assert_eq!(builder.clone().l2_block_time(2).validate(), Ok(()));
+        assert_eq!(builder.clone().l2_block_time(12).validate(), Ok(()));
+
+        for l2_block_time in [0, 5, 24] {
+            assert_eq!(
+                builder.clone().l2_block_time(l2_block_time).validate(),
+                Err(vec![ConfigError::InvalidL2BlockTime {
+                    l2_block_time,
+                    block_time: 12,
+                }])
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_sequencer_fee_recipient() {
+        let builder = DeployerBuilder::new(11155111);
+        assert_eq!(
+            builder
+                .clone()
+                .sequencer_fee_recipient("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")
+                .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            builder.sequencer_fee_recipient("0x1234").validate(),
+            Err(vec![ConfigError::InvalidSequencerFeeRecipient {
+                address: "0x1234".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_validate_hardfork_offsets() {
+        let builder = DeployerBuilder::new(11155111);
+        assert_eq!(
+            builder
+                .clone()
+                .hardfork_offset("holocene", 60)
+                .hardfork_offset("isthmus", 120)
+                .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            builder
+                .hardfork_offset("holocene", 120)
+                .hardfork_offset("isthmus", 60)
+                .hardfork_offset("pectra", 0)
+                .validate(),
+            Err(vec![
+                ConfigError::MisorderedL2Hardfork {
+                    fork: "isthmus".to_string(),
+                    offset: 60,
+                    previous: "holocene".to_string(),
+                    previous_offset: 120,
+                },
+                ConfigError::UnknownL2Hardfork {
+                    fork: "pectra".to_string()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_chain_fee_params() {
+        let builder = DeployerBuilder::new(11155111);
+        assert_eq!(
+            builder
+                .clone()
+                .gas_limit(60_000_000)
+                .eip1559_denominator(8)
+                .eip1559_elasticity(10)
+                .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .gas_limit(1_000_000)
+                .eip1559_denominator(0)
+                .eip1559_elasticity(u64::from(u32::MAX) + 1)
+                .validate(),
+            Err(vec![
+                ConfigError::InvalidGasLimit {
+                    gas_limit: 1_000_000
+                },
+                ConfigError::InvalidEip1559Param {
+                    field: "eip1559_denominator",
+                    value: 0,
+                },
+                ConfigError::InvalidEip1559Param {
+                    field: "eip1559_elasticity",
+                    value: u64::from(u32::MAX) + 1,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_anvil_hardfork() {
+        let builder = DeployerBuilder::new(11155111);
+        assert_eq!(builder.clone().anvil_hardfork("cancun").validate(), Ok(()));
+        assert_eq!(builder.clone().anvil_hardfork("Prague").validate(), Ok(()));
+        assert_eq!(
+            builder.anvil_hardfork("pectra").validate(),
+            Err(vec![ConfigError::UnknownHardfork {
+                hardfork: "pectra".to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn test_validate_anvil_mnemonic() {
+        let builder = DeployerBuilder::new(11155111);
+        assert_eq!(
+            builder
+                .clone()
+                .anvil_mnemonic(crate::ANVIL_DEFAULT_MNEMONIC)
+                .anvil_derivation_path("m/44'/60'/1'/0/")
+                .validate(),
+            Ok(())
+        );
+        let errors = builder
+            .anvil_mnemonic("not a mnemonic")
+            .validate()
+            .unwrap_err();
+        assert!(matches!(
+            errors.as_slice(),
+            [ConfigError::InvalidAnvilAccounts { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_validate_additional_l2_chains() {
+        let builder = DeployerBuilder::new(11155111).l2_chain_id(1234);
+        assert_eq!(
+            builder
+                .clone()
+                .add_l2_chain(1235, L2ChainConfig::default())
+                .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .add_l2_chain(1234, L2ChainConfig::default())
+                .add_l2_chain(11155111, L2ChainConfig::default())
+                .validate(),
+            Err(vec![
+                ConfigError::DuplicateL2ChainId { chain_id: 1234 },
+                ConfigError::SameChainIds { chain_id: 11155111 },
+            ])
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .add_l2_chain(
+                    1235,
+                    L2ChainConfig {
+                        l2_node_count: 1,
+                        sequencer_count: 2,
+                    }
+                )
+                .snapshot("/tmp/snapshot")
+                .validate(),
+            Err(vec![
+                ConfigError::TooManySequencers {
+                    sequencer_count: 2,
+                    l2_node_count: 1,
+                },
+                ConfigError::AdditionalL2ChainConflict { option: "snapshot" },
+            ])
+        );
+
+        let builder = (0..6).fold(builder.spare_batchers(3), |builder, i| {
+            builder.add_l2_chain(2000 + i, L2ChainConfig::default())
+        });
+        assert_eq!(
+            builder.validate(),
+            Err(vec![ConfigError::NotEnoughL2ChainAccounts {
+                chains: 6,
+                spares: 3,
+                requested: 21,
+                available: 20,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_additional_l2_chain_stack() {
+        let builder = DeployerBuilder::new(11155111)
+            .expose_metrics(true)
+            .add_l2_chain(1235, L2ChainConfig::default());
+        let stack = builder.l2_stack_builder("kup-test-1235", 2, 3, 0, 1);
+
+        assert_eq!(stack.sequencers.len(), 2);
+        assert_eq!(stack.validators.len(), 1);
+        assert_eq!(
+            stack.sequencers[0].op_reth.container_name,
+            "kup-test-1235-op-reth"
+        );
+        assert_eq!(stack.op_batcher.container_name, "kup-test-1235-op-batcher");
+        assert_eq!(
+            stack.sequencers[0].op_reth.metrics_host_port,
+            Some(DEFAULT_OP_RETH_METRICS_BASE_PORT + 1)
+        );
+        assert!(stack.spare_batchers.is_empty());
+    }
+
+    #[test]
+    fn test_conductor_raft_tuning_follows_l2_block_time() {
+        // 12s L1 blocks with the default 2s L2 blocks: relaxed timings.
+        let builder = DeployerBuilder::new(11155111).block_time(12);
+        let stack = builder.clone().l2_stack_builder("kup-test", 2, 2, 0, 0);
+        let conductor = stack.sequencers[0].op_conductor.as_ref().unwrap();
+        assert_eq!(conductor.raft_heartbeat_timeout.as_deref(), Some("3s"));
+
+        let stack = builder
+            .l2_block_time(4)
+            .l2_stack_builder("kup-test", 2, 2, 0, 0);
+        let conductor = stack.sequencers[0].op_conductor.as_ref().unwrap();
+        assert_eq!(conductor.raft_heartbeat_timeout, None);
+    }
+
+    #[test]
+    fn test_op_reth_pruning() {
+        let builder = DeployerBuilder::new(11155111);
+        let stack = builder.clone().l2_stack_builder("kup-test", 1, 2, 0, 0);
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            assert_eq!(node.op_reth.pruning, PruningMode::Archive);
+        }
+
+        let stack = builder
+            .op_reth_pruning(PruningMode::Full)
+            .l2_stack_builder("kup-test", 1, 2, 1, 0);
+        assert_eq!(stack.sequencers[0].op_reth.pruning, PruningMode::Full);
+        assert_eq!(stack.validators[0].op_reth.pruning, PruningMode::Full);
+        // The proofs history validator keeps every block
+        assert!(stack.validators[1].op_reth.proofs_history);
+        assert_eq!(stack.validators[1].op_reth.pruning, PruningMode::Archive);
+    }
+
+    #[test]
+    fn test_service_log_level() {
+        let builder = DeployerBuilder::new(11155111)
+            .quiet_services(true)
+            .service_log_level("op-batcher", "debug");
+        assert_eq!(builder.validate(), Ok(()));
+        let stack = builder.l2_stack_builder("kup-test", 1, 1, 0, 0);
+        assert_eq!(stack.op_batcher.log_level.as_deref(), Some("DEBUG"));
+        assert_eq!(
+            stack.op_proposer.unwrap().log_level.as_deref(),
+            Some("INFO")
+        );
+
+        let errors = DeployerBuilder::new(11155111)
+            .service_log_level("op-reth", "debug")
+            .validate()
+            .unwrap_err();
+        assert!(
+            matches!(errors[..], [ConfigError::InvalidServiceLogLevel { .. }]),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_validate_no_sequencers() {
+        let errors = DeployerBuilder::new(11155111)
+            .sequencer_count(0)
+            .validate()
+            .unwrap_err();
+        assert_eq!(errors, vec![ConfigError::NoSequencers]);
+    }
 }
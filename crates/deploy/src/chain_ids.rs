@@ -0,0 +1,285 @@
+//! End-to-end check of the L1 and L2 chain IDs of a deployment.
+//!
+//! The chain IDs are configured once on the [`Deployer`], but end up in several places:
+//! the op-deployer intent, the Anvil (or external) L1, `rollup.json` and the genesis of
+//! every op-reth. A mismatch between any of them shows up as derivation failures far
+//! from its cause, so [`Deployer::chain_id_report`] reads them all back and compares
+//! them with the configured IDs.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use bollard::Docker;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    Deployer,
+    health::{build_host_rpc_url, query_chain_id},
+};
+
+/// The chain a chain ID belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, derive_more::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainKind {
+    #[display("L1")]
+    L1,
+    #[display("L2")]
+    L2,
+}
+
+/// A chain ID read back from one component of the deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainIdCheck {
+    /// Where the ID was read from, e.g. `rollup.json l2_chain_id`.
+    pub source: String,
+    /// The chain the ID identifies.
+    pub chain: ChainKind,
+    /// The chain ID configured on the deployer.
+    pub expected: u64,
+    /// The chain ID found, or `None` if it could not be read.
+    pub actual: Option<u64>,
+}
+
+impl ChainIdCheck {
+    /// Whether the chain ID was read and matches the configured one.
+    pub fn is_match(&self) -> bool {
+        self.actual == Some(self.expected)
+    }
+}
+
+/// Result of [`Deployer::chain_id_report`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainIdReport {
+    pub checks: Vec<ChainIdCheck>,
+}
+
+impl ChainIdReport {
+    /// Checks whose chain ID is missing or differs from the configured one.
+    pub fn mismatches(&self) -> Vec<&ChainIdCheck> {
+        self.checks
+            .iter()
+            .filter(|check| !check.is_match())
+            .collect()
+    }
+
+    /// Fail with the list of mismatches, if any.
+    pub fn ensure_consistent(&self) -> Result<()> {
+        let mismatches = self.mismatches();
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = mismatches
+            .iter()
+            .map(|check| match check.actual {
+                Some(actual) => format!(
+                    "{}: {} chain ID {} (expected {})",
+                    check.source, check.chain, actual, check.expected
+                ),
+                None => format!(
+                    "{}: {} chain ID could not be read (expected {})",
+                    check.source, check.chain, check.expected
+                ),
+            })
+            .collect();
+        anyhow::bail!("Chain ID mismatch:\n  {}", details.join("\n  "))
+    }
+}
+
+impl std::fmt::Display for ChainIdReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            let actual = check
+                .actual
+                .map_or_else(|| "unreadable".to_string(), |id| id.to_string());
+            let mark = if check.is_match() { "✓" } else { "✗" };
+            writeln!(
+                f,
+                "{} {} {}: {} (expected {})",
+                mark, check.chain, check.source, actual, check.expected
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a chain ID that is either a JSON number or a (possibly 32-byte) hex string.
+fn parse_chain_id(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(s) => match s.strip_prefix("0x") {
+            // 32-byte IDs overflow u64 unless their leading zeros are dropped.
+            Some(hex) => match hex.trim_start_matches('0') {
+                "" => Some(0),
+                hex => u64::from_str_radix(hex, 16).ok(),
+            },
+            None => s.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+/// Chain IDs of the op-deployer intent: the L1 ID and the ID of the first chain.
+fn intent_chain_ids(l2_stack_path: &Path) -> Result<(Option<u64>, Option<u64>)> {
+    let path = l2_stack_path.join("intent.toml");
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let intent: Value =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok((
+        parse_chain_id(&intent["l1ChainID"]),
+        parse_chain_id(&intent["chains"][0]["id"]),
+    ))
+}
+
+/// Read a JSON file of the `l2-stack` directory.
+fn read_json(l2_stack_path: &Path, name: &str) -> Result<Value> {
+    let path = l2_stack_path.join(name);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+impl Deployer {
+    /// Read the chain IDs back from the intent, `rollup.json`, `genesis.json`, the L1 RPC
+    /// and the RPC of every op-reth, and compare them with the configured IDs.
+    ///
+    /// Unreachable RPCs are reported as unreadable rather than failing the report; only
+    /// missing or malformed generated files are errors.
+    pub async fn chain_id_report(&self, docker: &Docker) -> Result<ChainIdReport> {
+        let (l1, l2) = (self.l1_chain_id, self.l2_chain_id);
+        let l2_stack_path = self.outdata.join("l2-stack");
+        let mut checks = Vec::new();
+        let mut check = |source: String, chain: ChainKind, actual: Option<u64>| {
+            let expected = match chain {
+                ChainKind::L1 => l1,
+                ChainKind::L2 => l2,
+            };
+            checks.push(ChainIdCheck {
+                source,
+                chain,
+                expected,
+                actual,
+            });
+        };
+
+        let (intent_l1, intent_l2) = intent_chain_ids(&l2_stack_path)?;
+        check(
+            "intent.toml l1ChainID".to_string(),
+            ChainKind::L1,
+            intent_l1,
+        );
+        check(
+            "intent.toml chains[0].id".to_string(),
+            ChainKind::L2,
+            intent_l2,
+        );
+
+        let rollup = read_json(&l2_stack_path, "rollup.json")?;
+        check(
+            "rollup.json l1_chain_id".to_string(),
+            ChainKind::L1,
+            parse_chain_id(&rollup["l1_chain_id"]),
+        );
+        check(
+            "rollup.json l2_chain_id".to_string(),
+            ChainKind::L2,
+            parse_chain_id(&rollup["l2_chain_id"]),
+        );
+
+        let genesis = read_json(&l2_stack_path, "genesis.json")?;
+        check(
+            "genesis.json config.chainId".to_string(),
+            ChainKind::L2,
+            parse_chain_id(&genesis["config"]["chainId"]),
+        );
+
+        let client = self.rpc_client()?;
+        let (l1_source, l1_url) = match self.external_l1 {
+            Some(ref external) => ("external L1 eth_chainId", Some(external.rpc_url.clone())),
+            None => (
+                "anvil eth_chainId",
+                build_host_rpc_url(docker, &self.anvil.container_name, self.anvil.port).await,
+            ),
+        };
+        let l1_actual = match l1_url {
+            Some(ref url) => query_chain_id(&client, url).await,
+            None => None,
+        };
+        check(l1_source.to_string(), ChainKind::L1, l1_actual);
+
+        let stack = &self.l2_stack;
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            let op_reth = &node.op_reth;
+            let actual = match build_host_rpc_url(
+                docker,
+                &op_reth.container_name,
+                op_reth.http_port,
+            )
+            .await
+            {
+                Some(ref url) => query_chain_id(&client, url).await,
+                None => None,
+            };
+            check(
+                format!("{} eth_chainId", op_reth.container_name),
+                ChainKind::L2,
+                actual,
+            );
+        }
+
+        Ok(ChainIdReport { checks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chain_id() {
+        assert_eq!(parse_chain_id(&serde_json::json!(900)), Some(900));
+        assert_eq!(parse_chain_id(&serde_json::json!("0x384")), Some(900));
+        assert_eq!(
+            parse_chain_id(&serde_json::json!(
+                "0x0000000000000000000000000000000000000000000000000000000000000385"
+            )),
+            Some(901)
+        );
+        assert_eq!(parse_chain_id(&serde_json::json!("901")), Some(901));
+        assert_eq!(parse_chain_id(&serde_json::json!(null)), None);
+    }
+
+    #[test]
+    fn test_chain_id_report_mismatches() {
+        let report = ChainIdReport {
+            checks: vec![
+                ChainIdCheck {
+                    source: "rollup.json l1_chain_id".to_string(),
+                    chain: ChainKind::L1,
+                    expected: 900,
+                    actual: Some(900),
+                },
+                ChainIdCheck {
+                    source: "kup-op-reth eth_chainId".to_string(),
+                    chain: ChainKind::L2,
+                    expected: 901,
+                    actual: Some(42),
+                },
+                ChainIdCheck {
+                    source: "anvil eth_chainId".to_string(),
+                    chain: ChainKind::L1,
+                    expected: 900,
+                    actual: None,
+                },
+            ],
+        };
+        assert_eq!(report.mismatches().len(), 2);
+
+        let err = report.ensure_consistent().unwrap_err().to_string();
+        assert!(err.contains("kup-op-reth eth_chainId: L2 chain ID 42 (expected 901)"));
+        assert!(err.contains("anvil eth_chainId: L1 chain ID could not be read"));
+        assert!(!err.contains("rollup.json"));
+        assert!(report.checks[0].is_match());
+    }
+}
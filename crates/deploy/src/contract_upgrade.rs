@@ -0,0 +1,144 @@
+//! Upgrade of the L1 contracts of a running network to a newer contracts release.
+//!
+//! [`Deployer::upgrade_contracts`] runs op-deployer against the L1 the network is already
+//! deployed on (see [`OpDeployerConfig::upgrade_contracts`](crate::OpDeployerConfig::upgrade_contracts)),
+//! then restarts the services that read the regenerated `rollup.json` and `genesis.json`.
+//! op-reth is not restarted: the L2 predeploys, hence its genesis, are unchanged.
+
+use anyhow::{Context, Result};
+
+use crate::{
+    AnvilHandler, Deployer, DeploymentConfigHash, DeploymentTarget, DeploymentVersion, KupDocker,
+    L2StackBuilder, deployment_hash::DEPLOYMENT_VERSION_FILENAME, health::build_host_rpc_url,
+    metrics::ContainerDeployTimings, verification::DeployedAddresses,
+};
+
+/// Containers that read `rollup.json` or the L2 `genesis.json` at startup: the consensus
+/// clients, the op-conductors and op-challenger.
+fn restarted_on_upgrade(stack: &L2StackBuilder) -> Vec<String> {
+    let mut names = Vec::new();
+    for node in stack.sequencers.iter().chain(&stack.validators) {
+        names.push(node.consensus_container_name().to_string());
+        if let Some(ref conductor) = node.op_conductor {
+            names.push(conductor.container_name.clone());
+        }
+    }
+    if let Some(ref challenger) = stack.op_challenger {
+        names.push(challenger.container_name.clone());
+    }
+    names
+}
+
+impl Deployer {
+    /// Handler for the L1 the network is deployed on, with the deployer account.
+    async fn running_l1(&self, docker: &KupDocker) -> Result<AnvilHandler> {
+        if let Some(ref external) = self.external_l1 {
+            return external.handler();
+        }
+
+        let name = &self.anvil.container_name;
+        if !docker.is_container_running(name).await? {
+            anyhow::bail!("Anvil container '{}' is not running", name);
+        }
+        let l1_host_url = build_host_rpc_url(docker, name, self.anvil.port)
+            .await
+            .map(|url| url.parse())
+            .transpose()
+            .context("Invalid Anvil host RPC URL")?;
+
+        Ok(AnvilHandler {
+            // Docker accepts container names wherever it expects an ID.
+            container_id: name.clone(),
+            container_name: name.clone(),
+            l1_rpc_url: KupDocker::build_http_url(name, self.anvil.port)?,
+            l1_host_url,
+            l1_ws_url: None,
+            accounts: self.derive_accounts()?,
+            deploy_timings: ContainerDeployTimings {
+                pull: Default::default(),
+                setup: Default::default(),
+            },
+        })
+    }
+
+    /// Upgrade the L1 contracts of the running network to `target_release`, e.g.
+    /// `tag://op-contracts/v4.0.0`, and restart the services depending on the
+    /// regenerated L2 config files.
+    ///
+    /// On success the new locator is saved to `Kupcake.toml` and to the deployment
+    /// version, so that the next `deploy` reuses the upgraded contracts instead of
+    /// redeploying them. Returns the contract addresses after the upgrade.
+    pub async fn upgrade_contracts(
+        &mut self,
+        docker: &mut KupDocker,
+        target_release: &str,
+    ) -> Result<DeployedAddresses> {
+        let l1 = self.running_l1(docker).await?;
+        let l2_nodes_data_path = self.outdata.join("l2-stack");
+
+        let addresses = self
+            .op_deployer
+            .upgrade_contracts(docker, &l2_nodes_data_path, &l1, target_release)
+            .await?;
+
+        // The regenerated rollup.json records the L1 genesis of the dev genesis, not the
+        // one Anvil computed at startup.
+        if self.deployment_target == DeploymentTarget::Genesis && !l1.is_external() {
+            let l1_host_url = l1
+                .l1_host_url
+                .as_ref()
+                .context("Anvil host URL required for genesis hash patching")?;
+            crate::l1_genesis::patch_rollup_l1_genesis_hash(
+                &docker.rpc_client,
+                &l2_nodes_data_path.join("rollup.json"),
+                l1_host_url,
+            )
+            .await
+            .context("Failed to patch rollup.json with actual L1 genesis hash")?;
+        }
+
+        self.op_deployer.l1_contracts_locator = Some(target_release.to_string());
+        let version_file_path = l2_nodes_data_path.join(DEPLOYMENT_VERSION_FILENAME);
+        let mut version = DeploymentVersion::load_from_file(&version_file_path)?;
+        version.config_hash = DeploymentConfigHash::from_deployer(self).compute_hash()?;
+        version
+            .save_to_file(&version_file_path)
+            .context("Failed to save deployment version")?;
+        self.save_config()
+            .context("Failed to save config after the contract upgrade")?;
+
+        for name in restarted_on_upgrade(&self.l2_stack) {
+            docker
+                .restart_container(&name)
+                .await
+                .with_context(|| format!("Failed to restart container '{}'", name))?;
+            tracing::info!(container = %name, "Restarted after contract upgrade");
+        }
+
+        Ok(addresses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpChallengerBuilder;
+
+    #[test]
+    fn test_restarted_on_upgrade() {
+        let mut stack = L2StackBuilder::with_counts(1, 1);
+        stack.op_challenger = None;
+        assert_eq!(
+            restarted_on_upgrade(&stack),
+            [
+                stack.sequencers[0].kona_node.container_name.clone(),
+                stack.validators[0].kona_node.container_name.clone(),
+            ]
+        );
+
+        stack.op_challenger = Some(OpChallengerBuilder::default());
+        let names = restarted_on_upgrade(&stack);
+        assert_eq!(names.len(), 3);
+        assert!(names.iter().all(|name| !name.contains("op-reth")));
+    }
+}
@@ -1,20 +1,23 @@
 use anyhow::{Context, Result};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
+use url::Url;
 
 use crate::{
     AnvilConfig, AnvilHandler, DeploymentConfigHash, DeploymentTarget, DeploymentVersion,
-    KupDocker, KupDockerConfig, L2StackBuilder, MetricsTarget, MonitoringConfig, OpBatcherBuilder,
-    OpBatcherHandler, OpChallengerBuilder, OpChallengerHandler, OpDeployerConfig,
+    KupDocker, KupDockerConfig, L2ChainBuilder, L2StackBuilder, MetricsTarget, MonitoringConfig,
+    OpBatcherBuilder, OpBatcherHandler, OpChallengerBuilder, OpChallengerHandler, OpDeployerConfig,
     OpProposerBuilder, OpProposerHandler, fs,
     metrics::{DeploymentMetrics, ServiceMetrics, get_image_size},
-    service::KupcakeService,
-    services,
+    service::{KupcakeService, ScrapeTarget},
     services::MonitoringHandler,
     services::anvil::AnvilInput,
+    services::blockscout::{BlockscoutBuilder, BlockscoutHandler, BlockscoutInput},
     services::l2_node::{L2NodeBuilder, L2NodeHandler},
+    warnings,
 };
 
 /// The default name for the kupcake configuration file.
@@ -31,20 +34,46 @@ pub struct L2StackHandler {
     pub op_proposer: Option<OpProposerHandler>,
     /// None when restored from snapshot (no state.json available).
     pub op_challenger: Option<OpChallengerHandler>,
+    /// Hot-spare op-batchers, started with `--stopped`.
+    pub spare_batchers: Vec<OpBatcherHandler>,
+    /// Hot-spare op-proposers, stopped after startup.
+    pub spare_proposers: Vec<OpProposerHandler>,
+}
+
+/// Handler for an additional L2 chain of the deployment.
+pub struct L2ChainHandler {
+    /// The L2 chain ID.
+    pub chain_id: u64,
+    /// Handlers for the L2 components of the chain.
+    pub l2_stack: L2StackHandler,
 }
 
 /// Deployment result containing all service handlers.
 ///
-/// This is returned by `Deployer::deploy()` and provides access to all running containers.
+/// This is returned by [`Deployer::deploy_detached`] and provides access to all running containers.
+///
+/// The stable API is made of the accessors: [`l1`](Self::l1), [`l2_stack`](Self::l2_stack),
+/// [`l2_chains`](Self::l2_chains),
+/// [`monitoring`](Self::monitoring), [`blockscout`](Self::blockscout),
+/// [`services`](Self::services) and the convenience
+/// getters for the primary sequencer ([`sequencer`](Self::sequencer),
+/// [`l1_rpc_url`](Self::l1_rpc_url), [`l2_rpc_url`](Self::l2_rpc_url),
+/// [`rollup_rpc_url`](Self::rollup_rpc_url)). The fields stay public for existing callers.
 pub struct DeploymentResult {
     /// Handler for the L1 Anvil instance.
     pub anvil: AnvilHandler,
     /// Handlers for all L2 stack components.
     pub l2_stack: L2StackHandler,
+    /// Handlers for the additional L2 chains, empty for a single-chain deployment.
+    pub l2_chains: Vec<L2ChainHandler>,
     /// Deployment metrics (per-service timings and image sizes).
     pub metrics: DeploymentMetrics,
     /// Monitoring stack handlers (if enabled).
     pub monitoring: Option<MonitoringHandler>,
+    /// Blockscout explorer handler (if enabled).
+    pub blockscout: Option<BlockscoutHandler>,
+    /// Non-fatal issues encountered during the deployment.
+    pub warnings: Vec<crate::DeployWarning>,
 }
 
 /// Endpoints for a single service.
@@ -77,6 +106,179 @@ impl DeploymentEndpoints {
 }
 
 impl DeploymentResult {
+    /// Print detached mode information including container names and stop command.
+    ///
+    /// `outdata` is the deployment's output directory and `network_id` the ID of its
+    /// Docker network ([`KupDocker::network_id`]).
+    pub fn print_detached_info(&self, outdata: &Path, network_id: &str) {
+        let (anvil, monitoring) = (&self.anvil, &self.monitoring);
+        let mut container_names = Vec::new();
+
+        // Add anvil container
+        if !anvil.is_external() {
+            container_names.push(anvil.container_name.clone());
+        }
+
+        for l2_stack in self.all_l2_stacks() {
+            // Add all L2 node containers (sequencers and validators)
+            for node in l2_stack.all_nodes() {
+                container_names.push(node.op_reth.container_name.clone());
+                container_names.push(node.kona_node.container_name.clone());
+
+                // Add op-conductor if present (for sequencer nodes)
+                if let Some(ref conductor) = node.op_conductor {
+                    container_names.push(conductor.container_name.clone());
+                }
+            }
+
+            // Add L2 stack service containers
+            container_names.push(l2_stack.op_batcher.container_name.clone());
+            if let Some(ref proposer) = l2_stack.op_proposer {
+                container_names.push(proposer.container_name.clone());
+            }
+            if let Some(ref challenger) = l2_stack.op_challenger {
+                container_names.push(challenger.container_name.clone());
+            }
+        }
+
+        // Add monitoring containers if present
+        if let Some(mon) = monitoring {
+            container_names.push(mon.prometheus.container_name.clone());
+            container_names.push(mon.grafana.container_name.clone());
+            if let Some(ref logs) = mon.logs {
+                container_names.push(logs.loki.container_name.clone());
+                container_names.push(logs.promtail.container_name.clone());
+            }
+        }
+
+        if let Some(ref blockscout) = self.blockscout {
+            container_names.push(blockscout.postgres_container_name.clone());
+            container_names.push(blockscout.container_name.clone());
+        }
+
+        // Build the docker stop command
+        let stop_command = format!(
+            "docker stop {} && docker network rm {}",
+            container_names.join(" "),
+            network_id
+        );
+
+        // Print the detached mode information
+        tracing::info!("✓ Detached mode enabled. Containers are running in the background.");
+        tracing::info!("");
+        tracing::info!(
+            "Configuration saved to: {}",
+            outdata.join(KUPCONF_FILENAME).display()
+        );
+        tracing::info!("");
+        tracing::info!("Running containers:");
+        for name in &container_names {
+            tracing::info!("  - {}", name);
+        }
+        tracing::info!("");
+        tracing::info!("Network: {}", network_id);
+        tracing::info!("");
+        tracing::info!("To stop all containers:");
+        tracing::info!("  {}", stop_command);
+        tracing::info!("");
+        tracing::info!("To view logs:");
+        tracing::info!("  docker logs <container-name>");
+    }
+
+    /// Handler for the L1 node (Anvil, or the external L1).
+    pub fn l1(&self) -> &AnvilHandler {
+        &self.anvil
+    }
+
+    /// Handlers for the L2 stack: sequencers, validators, batcher, proposer and challenger.
+    pub fn l2_stack(&self) -> &L2StackHandler {
+        &self.l2_stack
+    }
+
+    /// Handlers for the additional L2 chains, in the order they were added.
+    pub fn l2_chains(&self) -> &[L2ChainHandler] {
+        &self.l2_chains
+    }
+
+    /// Handlers for the L2 stack of every chain, the primary chain first.
+    fn all_l2_stacks(&self) -> impl Iterator<Item = &L2StackHandler> {
+        std::iter::once(&self.l2_stack).chain(self.l2_chains.iter().map(|chain| &chain.l2_stack))
+    }
+
+    /// Handlers for Prometheus, Grafana and, when log aggregation is enabled, Loki and
+    /// Promtail. None when monitoring is disabled.
+    pub fn monitoring(&self) -> Option<&MonitoringHandler> {
+        self.monitoring.as_ref()
+    }
+
+    /// Handler for the Blockscout explorer. None when it is disabled.
+    pub fn blockscout(&self) -> Option<&BlockscoutHandler> {
+        self.blockscout.as_ref()
+    }
+
+    /// Container names of all running services, in start order.
+    ///
+    /// An external L1 has no container and is not listed.
+    pub fn services(&self) -> Vec<&str> {
+        let mut services = Vec::new();
+        if !self.anvil.is_external() {
+            services.push(self.anvil.container_name.as_str());
+        }
+        for l2_stack in self.all_l2_stacks() {
+            for node in l2_stack.all_nodes() {
+                services.push(node.op_reth.container_name.as_str());
+                services.push(node.kona_node.container_name.as_str());
+                if let Some(ref conductor) = node.op_conductor {
+                    services.push(conductor.container_name.as_str());
+                }
+                services.extend(node.sidecars.iter().map(String::as_str));
+            }
+            services.push(l2_stack.op_batcher.container_name.as_str());
+            if let Some(ref proposer) = l2_stack.op_proposer {
+                services.push(proposer.container_name.as_str());
+            }
+            if let Some(ref challenger) = l2_stack.op_challenger {
+                services.push(challenger.container_name.as_str());
+            }
+        }
+
+        if let Some(ref monitoring) = self.monitoring {
+            services.push(monitoring.prometheus.container_name.as_str());
+            services.push(monitoring.grafana.container_name.as_str());
+            if let Some(ref logs) = monitoring.logs {
+                services.push(logs.loki.container_name.as_str());
+                services.push(logs.promtail.container_name.as_str());
+            }
+        }
+        if let Some(ref blockscout) = self.blockscout {
+            services.push(blockscout.postgres_container_name.as_str());
+            services.push(blockscout.container_name.as_str());
+        }
+        services
+    }
+
+    /// The primary sequencer (the first one).
+    pub fn sequencer(&self) -> &L2NodeHandler {
+        self.l2_stack.primary_sequencer()
+    }
+
+    /// Host URL of the L1 RPC. None if not published to the host.
+    pub fn l1_rpc_url(&self) -> Option<&Url> {
+        self.anvil.l1_host_url.as_ref()
+    }
+
+    /// Host URL of the primary sequencer's execution client HTTP RPC.
+    /// None if not published to the host.
+    pub fn l2_rpc_url(&self) -> Option<&Url> {
+        self.sequencer().op_reth.http_host_url.as_ref()
+    }
+
+    /// Host URL of the primary sequencer's consensus client RPC (`optimism_*` methods).
+    /// None if not published to the host.
+    pub fn rollup_rpc_url(&self) -> Option<&Url> {
+        self.sequencer().kona_node.rpc_host_url.as_ref()
+    }
+
     /// Collect all endpoints from the deployment into a structured format.
     pub fn endpoints(&self) -> DeploymentEndpoints {
         let monitoring = self.monitoring.as_ref();
@@ -96,63 +298,62 @@ impl DeploymentResult {
             );
         }
 
-        // Sequencer nodes
-        for (i, node) in self.l2_stack.sequencers.iter().enumerate() {
-            let label = if i == 0 {
-                "sequencer".to_string()
-            } else {
-                format!("sequencer-{}", i)
-            };
-            Self::collect_l2_node_endpoints(&mut services, node, &label);
-        }
+        for l2_stack in self.all_l2_stacks() {
+            // Sequencer nodes
+            for (i, node) in l2_stack.sequencers.iter().enumerate() {
+                let label = if i == 0 {
+                    "sequencer".to_string()
+                } else {
+                    format!("sequencer-{}", i)
+                };
+                Self::collect_l2_node_endpoints(&mut services, node, &label);
+            }
 
-        // Validator nodes
-        for (i, node) in self.l2_stack.validators.iter().enumerate() {
-            let label = format!("validator-{}", i + 1);
-            Self::collect_l2_node_endpoints(&mut services, node, &label);
-        }
+            // Validator nodes
+            for (i, node) in l2_stack.validators.iter().enumerate() {
+                let label = format!("validator-{}", i + 1);
+                Self::collect_l2_node_endpoints(&mut services, node, &label);
+            }
 
-        // op-batcher
-        {
-            let mut internal = BTreeMap::new();
-            let mut host = BTreeMap::new();
-            internal.insert(
-                "rpc".to_string(),
-                self.l2_stack.op_batcher.rpc_url.to_string(),
-            );
-            if let Some(ref url) = self.l2_stack.op_batcher.rpc_host_url {
-                host.insert("rpc".to_string(), url.to_string());
+            // op-batcher
+            {
+                let mut internal = BTreeMap::new();
+                let mut host = BTreeMap::new();
+                internal.insert("rpc".to_string(), l2_stack.op_batcher.rpc_url.to_string());
+                if let Some(ref url) = l2_stack.op_batcher.rpc_host_url {
+                    host.insert("rpc".to_string(), url.to_string());
+                }
+                services.insert(
+                    l2_stack.op_batcher.container_name.clone(),
+                    ServiceEndpoints { internal, host },
+                );
             }
-            services.insert(
-                self.l2_stack.op_batcher.container_name.clone(),
-                ServiceEndpoints { internal, host },
-            );
-        }
 
-        // op-proposer
-        if let Some(ref proposer) = self.l2_stack.op_proposer {
-            let mut internal = BTreeMap::new();
-            internal.insert("rpc".to_string(), proposer.rpc_url.to_string());
-            services.insert(
-                proposer.container_name.clone(),
-                ServiceEndpoints {
-                    internal,
-                    host: BTreeMap::new(),
-                },
-            );
-        }
+            // op-proposer
+            if let Some(ref proposer) = l2_stack.op_proposer {
+                let mut internal = BTreeMap::new();
+                internal.insert("rpc".to_string(), proposer.rpc_url.to_string());
+                services.insert(
+                    proposer.container_name.clone(),
+                    ServiceEndpoints {
+                        internal,
+                        host: BTreeMap::new(),
+                    },
+                );
+            }
 
-        // op-challenger
-        if let Some(ref challenger) = self.l2_stack.op_challenger {
-            let mut internal = BTreeMap::new();
-            internal.insert("metrics".to_string(), challenger.metrics_url.to_string());
-            services.insert(
-                challenger.container_name.clone(),
-                ServiceEndpoints {
-                    internal,
-                    host: BTreeMap::new(),
-                },
-            );
+            // op-challenger
+            if let Some(ref challenger) = l2_stack.op_challenger {
+                let mut internal = BTreeMap::new();
+                internal.insert("metrics".to_string(), challenger.metrics_url.to_string());
+                services.insert(
+                    challenger.container_name.clone(),
+                    ServiceEndpoints {
+                        internal,
+                        host: BTreeMap::new(),
+                    },
+                );
+            }
         }
 
         // Monitoring
@@ -181,6 +382,32 @@ impl DeploymentResult {
                     ServiceEndpoints { internal, host },
                 );
             }
+            if let Some(ref logs) = mon.logs {
+                let mut internal = BTreeMap::new();
+                let mut host = BTreeMap::new();
+                internal.insert("url".to_string(), logs.loki.url.to_string());
+                if let Some(ref url) = logs.loki.host_url {
+                    host.insert("url".to_string(), url.to_string());
+                }
+                services.insert(
+                    logs.loki.container_name.clone(),
+                    ServiceEndpoints { internal, host },
+                );
+            }
+        }
+
+        // Blockscout
+        if let Some(ref blockscout) = self.blockscout {
+            let mut internal = BTreeMap::new();
+            let mut host = BTreeMap::new();
+            internal.insert("url".to_string(), blockscout.url.to_string());
+            if let Some(ref url) = blockscout.host_url {
+                host.insert("url".to_string(), url.to_string());
+            }
+            services.insert(
+                blockscout.container_name.clone(),
+                ServiceEndpoints { internal, host },
+            );
         }
 
         DeploymentEndpoints { services }
@@ -265,6 +492,35 @@ impl L2StackHandler {
     pub fn all_nodes(&self) -> impl Iterator<Item = &L2NodeHandler> {
         self.sequencers.iter().chain(self.validators.iter())
     }
+
+    /// Scrape targets of the L2 nodes, op-batcher, op-proposer and op-challenger.
+    ///
+    /// Job names are the container names; see [`ScrapeTarget::metrics_target`].
+    pub fn metrics_targets(&self) -> Vec<MetricsTarget> {
+        let services = [
+            self.op_batcher.metrics_target(),
+            self.op_proposer
+                .as_ref()
+                .and_then(ScrapeTarget::metrics_target),
+            self.op_challenger
+                .as_ref()
+                .and_then(ScrapeTarget::metrics_target),
+        ];
+        self.all_nodes()
+            .flat_map(L2NodeHandler::metrics_targets)
+            .chain(services.into_iter().flatten())
+            .collect()
+    }
+}
+
+/// Prometheus job name of a container: its name without the network prefix
+/// (e.g. `kup-mynet-op-reth-validator-1` → `op-reth-validator-1`).
+fn metrics_job_name(net_name: &str, container_name: &str) -> String {
+    let network_prefix = format!("{}-", net_name.strip_suffix("-network").unwrap_or(net_name));
+    container_name
+        .strip_prefix(&network_prefix)
+        .unwrap_or(container_name)
+        .to_string()
 }
 
 /// Main deployer that orchestrates the entire OP Stack deployment.
@@ -306,8 +562,14 @@ pub struct Deployer<
     /// Configuration for all L2 components for the op-stack.
     #[serde(flatten)]
     pub l2_stack: L2StackBuilder<Node, B, P, C>,
+    /// Additional L2 chains settled on the same L1, each with its own L2 stack.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_l2_chains: Vec<L2ChainBuilder<Node, B, P, C>>,
     /// Configuration for the monitoring stack.
     pub monitoring: MonitoringConfig,
+    /// Configuration for the Blockscout explorer, deployed when enabled.
+    #[serde(default)]
+    pub blockscout: BlockscoutBuilder,
 
     /// Path to the dashboards directory (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -337,17 +599,87 @@ pub struct Deployer<
     /// Only valid in live mode; genesis mode will error if this is set.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub override_state: Option<PathBuf>,
+
+    /// Externally-running L1 node. When set, Anvil is not started and contracts are
+    /// deployed to this L1 instead. Only valid in live mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_l1: Option<crate::ExternalL1Config>,
+
+    /// Script run against the devnet once it is deployed. Not persisted, so a
+    /// redeploy from the saved config does not run it again.
+    #[serde(skip)]
+    pub post_deploy: Option<crate::PostDeployScript>,
+
+    /// Minimum free disk space for the data directory, in GiB. 0 disables the check.
+    #[serde(default = "default_min_disk_space_gib")]
+    pub min_disk_space_gib: u64,
+
+    /// Whether failures of non-critical services (op-proposer, op-challenger, monitoring,
+    /// Blockscout) are logged and recorded as warnings instead of aborting the deployment.
+    #[serde(default)]
+    pub keep_going: bool,
+
+    /// Whether to check the chain IDs of every component against the configured ones at
+    /// the end of the deployment (see [`Deployer::chain_id_report`]).
+    #[serde(default)]
+    pub verify_chain_ids: bool,
+
+    /// Whether [`Deployer::deploy`] leaves the network as is when it is already running
+    /// and healthy (see [`Deployer::reuse_running_network`]).
+    #[serde(skip)]
+    pub reuse_if_healthy: bool,
+
+    /// With [`Self::reuse_if_healthy`], restart the unhealthy containers of a partially
+    /// running network instead of failing.
+    #[serde(skip)]
+    pub restart_unhealthy: bool,
 }
 
 fn default_dump_state() -> bool {
     true
 }
 
+fn default_min_disk_space_gib() -> u64 {
+    crate::DEFAULT_MIN_DISK_SPACE_GIB
+}
+
+/// Time to wait for the deployed L1 contracts to have code, in seconds.
+const L1_CONTRACTS_TIMEOUT_SECS: u64 = 60;
+
+/// L1 contracts that op-batcher, op-proposer and op-challenger depend on.
+const REQUIRED_L1_CONTRACTS: &[&str] = &["OptimismPortalProxy", "DisputeGameFactoryProxy"];
+
+/// Maximum number of images pulled at once by [`Deployer::prefetch_images`].
+pub const MAX_CONCURRENT_IMAGE_PULLS: usize = 4;
+
+/// Addresses of the [`REQUIRED_L1_CONTRACTS`] of the first chain in op-deployer's `state.json`.
+fn l1_contract_addresses(state: &serde_json::Value) -> Result<Vec<(&'static str, String)>> {
+    REQUIRED_L1_CONTRACTS
+        .iter()
+        .map(|name| Ok((*name, crate::json_file::state_chain_address(state, name)?)))
+        .collect()
+}
+
 impl Deployer {
+    /// Build the RPC client of this deployment from its timeouts, CA bundle and proxy
+    /// (see [`rpc::build_client`](crate::rpc::build_client)).
+    ///
+    /// Build it once and reuse it, so that its connection pool is shared.
+    pub fn rpc_client(&self) -> Result<reqwest::Client> {
+        crate::rpc::build_client(
+            &self.docker.rpc_client,
+            self.docker.ca_bundle.as_deref(),
+            self.docker.proxy.as_ref(),
+        )
+    }
+
     /// Save the configuration to a TOML file.
+    ///
+    /// Relative host paths are stored as absolute paths (resolved against the current
+    /// directory), so that the config keeps working when loaded from elsewhere.
     pub fn save_to_file(&self, path: &PathBuf) -> Result<()> {
-        let content =
-            toml::to_string_pretty(self).context("Failed to serialize deployer config to TOML")?;
+        let content = toml::to_string_pretty(&self.with_absolute_paths()?)
+            .context("Failed to serialize deployer config to TOML")?;
         std::fs::write(path, content)
             .context(format!("Failed to write config to {}", path.display()))?;
         tracing::info!(path = %path.display(), "Configuration saved");
@@ -388,6 +720,87 @@ impl Deployer {
         Ok(config)
     }
 
+    /// Copy of the configuration with its relative host paths made absolute.
+    fn with_absolute_paths(&self) -> Result<Self> {
+        fn absolute(path: &mut PathBuf) -> Result<()> {
+            if path.is_relative() {
+                *path = std::path::absolute(&*path)
+                    .with_context(|| format!("Failed to make {} absolute", path.display()))?;
+            }
+            Ok(())
+        }
+
+        let mut config = self.clone();
+        absolute(&mut config.outdata)?;
+        for path in [
+            config.dashboards_path.as_mut(),
+            config.override_state.as_mut(),
+            config.op_deployer.intent_file.as_mut(),
+            config.docker.ca_bundle.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            absolute(path)?;
+        }
+        Ok(config)
+    }
+
+    /// Check that the data directory of a config loaded from `config_path` still exists.
+    ///
+    /// A missing data directory is expected for a config that was never deployed. But a
+    /// config file next to deployment outputs (an `l2-stack` directory) was most likely
+    /// moved together with its data: with `rebase`, `outdata` is pointed at the config
+    /// file's directory, otherwise this fails instead of creating an empty data directory
+    /// at the old location.
+    pub fn check_data_dir(&mut self, config_path: &Path, rebase: bool) -> Result<()> {
+        if self.outdata.exists() {
+            return Ok(());
+        }
+
+        let config_dir = if config_path.is_dir() {
+            config_path
+        } else {
+            config_path.parent().unwrap_or(Path::new("."))
+        };
+        let config_dir = std::path::absolute(config_dir)
+            .with_context(|| format!("Failed to resolve {}", config_dir.display()))?;
+        if !config_dir.join("l2-stack").is_dir() {
+            return Ok(());
+        }
+
+        if !rebase {
+            anyhow::bail!(
+                "Data directory {} does not exist, but the config at {} sits next to deployment \
+                 data (was it moved?). Re-run with --rebase-data-dir to use {} as the data directory",
+                self.outdata.display(),
+                config_path.display(),
+                config_dir.display()
+            );
+        }
+
+        tracing::info!(
+            from = %self.outdata.display(),
+            to = %config_dir.display(),
+            "Rebasing data directory onto the config file's directory"
+        );
+        self.outdata = config_dir;
+        Ok(())
+    }
+
+    /// Return the configuration as it would be after a save/load cycle.
+    ///
+    /// Serializes to TOML and back, which fills serde defaults for missing fields,
+    /// drops runtime-only fields (`snapshot`, `copy_snapshot`, node sidecars) and
+    /// normalizes values TOML cannot represent, e.g. an unpublished Prometheus port
+    /// (`None`) becomes `Some(0)`. Two configurations that would deploy the same
+    /// network compare equal once canonicalized, and `canonicalize` is idempotent.
+    pub fn canonicalize(&self) -> Result<Self> {
+        let content =
+            toml::to_string(self).context("Failed to serialize deployer config to TOML")?;
+        toml::from_str(&content).context("Failed to deserialize canonicalized deployer config")
+    }
+
     /// Save the deployer's configuration to the default location (kupconf.toml in outdata).
     pub fn save_config(&self) -> Result<PathBuf> {
         let config_path = self.outdata.join(KUPCONF_FILENAME);
@@ -395,6 +808,184 @@ impl Deployer {
         Ok(config_path)
     }
 
+    /// Stop the containers of the network without removing them, see
+    /// [`stop_by_prefix`](crate::stop_by_prefix).
+    ///
+    /// With [`dump_state`](Self::dump_state), the state of a local Anvil is dumped to
+    /// `anvil/state.json` first, so that the next deployment from the saved config reloads
+    /// the L1 chain instead of starting a new one.
+    pub async fn stop(&self) -> Result<crate::StopResult> {
+        let net_name = &self.docker.net_name;
+        let prefix = net_name.strip_suffix("-network").unwrap_or(net_name);
+
+        if self.dump_state && self.external_l1.is_none() {
+            let docker = bollard::Docker::connect_with_local_defaults()
+                .context("Failed to connect to Docker daemon")?;
+            let container_name = &self.anvil.container_name;
+            match crate::health::build_host_rpc_url(&docker, container_name, self.anvil.port).await
+            {
+                Some(url) => {
+                    crate::rpc::anvil_dump_state(&url, &self.outdata.join("anvil/state.json"))
+                        .await
+                        .context("Failed to dump the Anvil state before stopping")?;
+                }
+                None => tracing::debug!(
+                    container = %container_name,
+                    "Anvil is not running or not published to the host, skipping state dump"
+                ),
+            }
+        }
+
+        crate::stop_by_prefix(prefix).await
+    }
+
+    /// Names of the containers of the deployment, i.e. the hosts its services reach each
+    /// other by.
+    pub fn container_names(&self) -> Result<Vec<String>> {
+        let mut names: Vec<String> = crate::deployment_hash::service_hashes(self)?
+            .into_keys()
+            .collect();
+        names.push(self.op_deployer.container_name.clone());
+        Ok(names)
+    }
+
+    /// Registry images of the services that will be deployed, without duplicates.
+    ///
+    /// Images built from local binaries are left out: they are built when their service
+    /// starts.
+    pub fn images(&self) -> Vec<&crate::DockerImage> {
+        let mut images = Vec::new();
+        if self.external_l1.is_none() {
+            images.push(&self.anvil.docker_image);
+        }
+        images.push(&self.op_deployer.docker_image);
+
+        let additional_stacks = self
+            .additional_l2_chains
+            .iter()
+            .map(|chain| &chain.l2_stack);
+        for stack in std::iter::once(&self.l2_stack).chain(additional_stacks) {
+            for node in stack.sequencers.iter().chain(&stack.validators) {
+                images.push(&node.op_reth.docker_image);
+                match node.op_node {
+                    Some(ref op_node) => images.push(&op_node.docker_image),
+                    None => images.push(&node.kona_node.docker_image),
+                }
+                if let Some(ref conductor) = node.op_conductor {
+                    images.push(&conductor.docker_image);
+                }
+            }
+            images.push(&stack.op_batcher.docker_image);
+            images.extend(stack.spare_batchers.iter().map(|b| &b.docker_image));
+            images.extend(stack.op_proposer.iter().map(|p| &p.docker_image));
+            images.extend(stack.spare_proposers.iter().map(|p| &p.docker_image));
+            images.extend(stack.op_challenger.iter().map(|c| &c.docker_image));
+        }
+
+        if self.monitoring.enabled {
+            images.push(&self.monitoring.prometheus.docker_image);
+            images.push(&self.monitoring.grafana.docker_image);
+            if self.monitoring.logs.enabled {
+                images.push(&self.monitoring.logs.loki.docker_image);
+                images.push(&self.monitoring.logs.promtail.docker_image);
+            }
+        }
+        if self.blockscout.enabled {
+            images.push(&self.blockscout.postgres_docker_image);
+            images.push(&self.blockscout.docker_image);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        images.retain(|image| !image.is_local_binary() && seen.insert(image.to_string()));
+        images
+    }
+
+    /// Pull the images of every service concurrently, at most
+    /// [`MAX_CONCURRENT_IMAGE_PULLS`] at a time, so that services do not pull them one
+    /// after the other as they start.
+    ///
+    /// Images already available locally are skipped. A failed pull is only logged: the
+    /// service pulls its image again when it starts and reports the error then.
+    /// Returns the time spent.
+    pub async fn prefetch_images(&self, docker: &KupDocker) -> Duration {
+        let start = Instant::now();
+        let images = self.images();
+        tracing::info!(count = images.len(), "Pulling Docker images...");
+
+        let mut pulls = futures::stream::iter(
+            images
+                .into_iter()
+                .map(|image| async move { (image, image.pull(docker).await) }),
+        )
+        .buffer_unordered(MAX_CONCURRENT_IMAGE_PULLS);
+        while let Some((image, result)) = pulls.next().await {
+            if let Err(e) = result {
+                tracing::warn!(%image, error = %e, "Failed to prefetch image");
+            }
+        }
+
+        let elapsed = start.elapsed();
+        tracing::info!(elapsed = ?elapsed, "Docker images ready");
+        elapsed
+    }
+
+    /// Copy what external tools (op-node, explorers, ...) need to follow the chain into
+    /// `dir`: `rollup.json`, `genesis.json`, the JWT secrets of the L2 nodes,
+    /// `Kupcake.toml` and, when `state.json` exists, the contract addresses as
+    /// `addresses.json`.
+    ///
+    /// Returns the paths of the exported files.
+    pub fn export_chain_config(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let l2_stack_path = self.outdata.join("l2-stack");
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+        let mut files = vec![PathBuf::from("rollup.json"), PathBuf::from("genesis.json")];
+        for entry in std::fs::read_dir(&l2_stack_path)
+            .with_context(|| format!("Failed to read {}", l2_stack_path.display()))?
+        {
+            let name = PathBuf::from(entry?.file_name());
+            let name_str = name.to_string_lossy();
+            if name_str.starts_with("jwt-") && name_str.ends_with(".hex") {
+                files.push(name);
+            }
+        }
+
+        let mut exported = Vec::new();
+        for file in files {
+            let from = l2_stack_path.join(&file);
+            let to = dir.join(&file);
+            std::fs::copy(&from, &to).with_context(|| {
+                format!(
+                    "Failed to copy {} (is the network deployed?)",
+                    from.display()
+                )
+            })?;
+            exported.push(to);
+        }
+
+        let config_path = dir.join(KUPCONF_FILENAME);
+        self.save_to_file(&config_path)?;
+        exported.push(config_path);
+
+        let state_path = l2_stack_path.join("state.json");
+        if state_path.exists() {
+            let state = crate::json_file::read_state_json(&l2_stack_path)?;
+            let addresses = crate::verification::DeployedAddresses::from_state(&state)?;
+
+            let addresses_path = dir.join("addresses.json");
+            std::fs::write(
+                &addresses_path,
+                serde_json::to_string_pretty(&addresses)
+                    .context("Failed to serialize contract addresses")?,
+            )
+            .with_context(|| format!("Failed to write {}", addresses_path.display()))?;
+            exported.push(addresses_path);
+        }
+
+        Ok(exported)
+    }
+
     /// Determine if contract deployment is needed based on configuration hash.
     ///
     /// Returns `true` if contracts should be deployed, `false` if they can be skipped.
@@ -464,7 +1055,7 @@ impl Deployer {
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<()>>,
     {
-        let version_file_path = l2_nodes_data_path.join(".deployment-version.json");
+        let version_file_path = l2_nodes_data_path.join(crate::DEPLOYMENT_VERSION_FILENAME);
         let needs_deployment = Self::needs_contract_deployment(
             force_deploy,
             l2_nodes_data_path,
@@ -486,14 +1077,10 @@ impl Deployer {
         Ok(needs_deployment)
     }
 
-    /// Derive Anvil accounts from the default mnemonic.
-    pub fn derive_accounts() -> Result<crate::AnvilAccounts> {
-        let account_infos = crate::derive_accounts_from_mnemonic(
-            crate::ANVIL_DEFAULT_MNEMONIC,
-            crate::services::anvil::DEFAULT_ACCOUNT_COUNT,
-        )
-        .context("Failed to derive accounts from mnemonic")?;
-        crate::anvil_accounts_from_infos(account_infos).context("Failed to create named accounts")
+    /// Derive the Anvil accounts from the configured mnemonic (see
+    /// [`AnvilConfig::derive_accounts`]).
+    pub fn derive_accounts(&self) -> Result<crate::AnvilAccounts> {
+        self.anvil.derive_accounts()
     }
 
     /// Resolve Anvil init mode from the filesystem.
@@ -531,7 +1118,7 @@ impl Deployer {
         init_mode: Option<crate::AnvilInitMode>,
         l2_nodes_data_path: &Path,
     ) -> Result<AnvilHandler> {
-        let accounts = Self::derive_accounts()?;
+        let accounts = anvil_config.derive_accounts()?;
 
         tracing::info!(anvil_config = ?anvil_config, "Starting Anvil...");
         let anvil = anvil_config
@@ -553,9 +1140,13 @@ impl Deployer {
                 .l1_host_url
                 .as_ref()
                 .context("Anvil host URL required for genesis hash patching")?;
-            crate::l1_genesis::patch_rollup_l1_genesis_hash(&rollup_json_path, anvil_host_url)
-                .await
-                .context("Failed to patch rollup.json with actual L1 genesis hash")?;
+            crate::l1_genesis::patch_rollup_l1_genesis_hash(
+                &docker.rpc_client,
+                &rollup_json_path,
+                anvil_host_url,
+            )
+            .await
+            .context("Failed to patch rollup.json with actual L1 genesis hash")?;
         }
 
         Ok(anvil)
@@ -585,7 +1176,7 @@ impl Deployer {
                 .as_secs(),
         };
 
-        let accounts = Self::derive_accounts()?;
+        let accounts = anvil_config.derive_accounts()?;
         let mut op_deployer_duration = Duration::ZERO;
         let op_deployer_start = Instant::now();
         let deployed =
@@ -662,7 +1253,7 @@ impl Deployer {
         current_hash: &str,
         override_state: Option<&PathBuf>,
     ) -> Result<(AnvilHandler, Duration)> {
-        let accounts = Self::derive_accounts()?;
+        let accounts = anvil_config.derive_accounts()?;
 
         // Determine init mode for live deployment:
         // 1. --override-state takes precedence: copy file into anvil data dir, use --load-state
@@ -716,92 +1307,99 @@ impl Deployer {
         Ok((anvil, op_deployer_duration))
     }
 
-    /// Build metrics targets for Prometheus scraping from L2 stack handlers.
-    fn build_metrics_targets(l2_stack: &L2StackHandler) -> Vec<MetricsTarget> {
-        use services::kona_node::DEFAULT_METRICS_PORT as KONA_METRICS_PORT;
-        use services::op_batcher::DEFAULT_METRICS_PORT as BATCHER_METRICS_PORT;
-        use services::op_challenger::DEFAULT_METRICS_PORT as CHALLENGER_METRICS_PORT;
-        use services::op_proposer::DEFAULT_METRICS_PORT as PROPOSER_METRICS_PORT;
-        use services::op_reth::DEFAULT_METRICS_PORT as RETH_METRICS_PORT;
-
-        let mut targets = Vec::new();
-
-        // Add metrics targets for sequencer nodes
-        for (i, node) in l2_stack.sequencers.iter().enumerate() {
-            let suffix = if i == 0 {
-                String::new()
-            } else {
-                format!("-sequencer-{}", i)
-            };
-
-            targets.push(MetricsTarget {
-                job_name: format!("op-reth{}", suffix),
-                container_name: node.op_reth.container_name.clone(),
-                port: RETH_METRICS_PORT,
-                service_label: "op-reth-sequencer".to_string(),
-                layer_label: "execution".to_string(),
-            });
-
-            targets.push(MetricsTarget {
-                job_name: format!("kona-node{}", suffix),
-                container_name: node.kona_node.container_name.clone(),
-                port: KONA_METRICS_PORT,
-                service_label: "kona-node-sequencer".to_string(),
-                layer_label: "consensus".to_string(),
-            });
+    /// Wait until the core L1 contracts recorded in `state.json` have code on the L1.
+    ///
+    /// op-deployer can report success while its last transactions are still pending on
+    /// a slow L1; op-batcher and op-proposer would then start against missing contracts.
+    /// Skipped when there is no `state.json` (snapshot restore) or no host L1 URL.
+    async fn wait_for_l1_contracts(
+        client: &reqwest::Client,
+        l1: &AnvilHandler,
+        l2_nodes_data_path: &Path,
+    ) -> Result<()> {
+        let state_path = l2_nodes_data_path.join("state.json");
+        let Some(ref l1_url) = l1.l1_host_url else {
+            tracing::debug!("L1 RPC not published to host, skipping L1 contract code check");
+            return Ok(());
+        };
+        if !state_path.exists() {
+            tracing::debug!("No state.json, skipping L1 contract code check");
+            return Ok(());
         }
 
-        // Add metrics targets for validator nodes
-        for (i, node) in l2_stack.validators.iter().enumerate() {
-            let suffix = format!("-validator-{}", i + 1);
+        let state = crate::json_file::read_state_json(l2_nodes_data_path)?;
+        let contracts = l1_contract_addresses(&state)?;
 
-            targets.push(MetricsTarget {
-                job_name: format!("op-reth{}", suffix),
-                container_name: node.op_reth.container_name.clone(),
-                port: RETH_METRICS_PORT,
-                service_label: "op-reth-validator".to_string(),
-                layer_label: "execution".to_string(),
-            });
+        crate::rpc::wait_until_ready("L1 contracts", L1_CONTRACTS_TIMEOUT_SECS, || async {
+            for (name, address) in &contracts {
+                let code: String = crate::rpc::json_rpc_call(
+                    client,
+                    l1_url.as_str(),
+                    "eth_getCode",
+                    vec![serde_json::json!(address), serde_json::json!("latest")],
+                )
+                .await?;
+                if code.trim_start_matches("0x").is_empty() {
+                    anyhow::bail!("L1 contract {} at {} has no code", name, address);
+                }
+            }
+            Ok(())
+        })
+        .await?;
 
-            targets.push(MetricsTarget {
-                job_name: format!("kona-node{}", suffix),
-                container_name: node.kona_node.container_name.clone(),
-                port: KONA_METRICS_PORT,
-                service_label: "kona-node-validator".to_string(),
-                layer_label: "consensus".to_string(),
-            });
-        }
+        tracing::info!(count = contracts.len(), "L1 contracts have code on L1");
+        Ok(())
+    }
 
-        // Add metrics targets for batcher, proposer, and challenger
-        targets.push(MetricsTarget {
-            job_name: "op-batcher".to_string(),
-            container_name: l2_stack.op_batcher.container_name.clone(),
-            port: BATCHER_METRICS_PORT,
-            service_label: "op-batcher".to_string(),
-            layer_label: "batcher".to_string(),
-        });
+    /// Deploy contracts to an externally-running L1, without starting Anvil.
+    #[allow(clippy::too_many_arguments)]
+    async fn deploy_with_external_l1(
+        docker: &mut KupDocker,
+        external_l1: &crate::ExternalL1Config,
+        op_deployer: &OpDeployerConfig,
+        l2_nodes_data_path: &Path,
+        l1_chain_id: u64,
+        l2_chain_id: u64,
+        force_deploy: bool,
+        current_hash: &str,
+    ) -> Result<(AnvilHandler, Duration)> {
+        // Preflight: make sure we are talking to the configured L1
+        external_l1
+            .check_chain_id(&docker.rpc_client, l1_chain_id)
+            .await
+            .context("External L1 preflight check failed")?;
+        external_l1
+            .check_ws_connection(&docker.rpc_client)
+            .await
+            .context("External L1 WebSocket preflight check failed")?;
 
-        if let Some(ref proposer) = l2_stack.op_proposer {
-            targets.push(MetricsTarget {
-                job_name: "op-proposer".to_string(),
-                container_name: proposer.container_name.clone(),
-                port: PROPOSER_METRICS_PORT,
-                service_label: "op-proposer".to_string(),
-                layer_label: "proposer".to_string(),
-            });
-        }
+        let l1 = external_l1.handler()?;
+        tracing::info!(rpc_url = %l1.l1_rpc_url, "Using external L1, skipping Anvil");
 
-        if let Some(ref challenger) = l2_stack.op_challenger {
-            targets.push(MetricsTarget {
-                job_name: "op-challenger".to_string(),
-                container_name: challenger.container_name.clone(),
-                port: CHALLENGER_METRICS_PORT,
-                service_label: "op-challenger".to_string(),
-                layer_label: "challenger".to_string(),
-            });
-        }
+        let op_deployer_start = Instant::now();
+        Self::with_deployment_check(force_deploy, l2_nodes_data_path, current_hash, || async {
+            tracing::info!("Deploying L1 contracts to external L1...");
+
+            op_deployer
+                .deploy_contracts(docker, l2_nodes_data_path, &l1, l1_chain_id, l2_chain_id)
+                .await
+        })
+        .await?;
+        let op_deployer_duration = op_deployer_start.elapsed();
 
-        targets
+        Ok((l1, op_deployer_duration))
+    }
+
+    /// Build metrics targets for Prometheus scraping from L2 stack handlers.
+    fn build_metrics_targets(l2_stack: &L2StackHandler, net_name: &str) -> Vec<MetricsTarget> {
+        l2_stack
+            .metrics_targets()
+            .into_iter()
+            .map(|mut target| {
+                target.job_name = metrics_job_name(net_name, &target.job_name);
+                target
+            })
+            .collect()
     }
 
     /// Build metrics targets for Prometheus scraping from the deployer's builder config.
@@ -810,21 +1408,8 @@ impl Deployer {
     /// are not available but Prometheus scrape targets need to be regenerated.
     /// All values (ports, job names, labels) are derived from the saved config.
     pub fn build_metrics_targets_from_config(&self) -> Vec<MetricsTarget> {
-        // Derive the network prefix from the Docker network name.
-        // E.g., "kup-mynet-network" → "kup-mynet-"
-        let network_prefix = self
-            .docker
-            .net_name
-            .strip_suffix("-network")
-            .unwrap_or(&self.docker.net_name);
-        let network_prefix = format!("{}-", network_prefix);
-
-        let job_name = |container_name: &str| -> String {
-            container_name
-                .strip_prefix(&network_prefix)
-                .unwrap_or(container_name)
-                .to_string()
-        };
+        let job_name =
+            |container_name: &str| metrics_job_name(&self.docker.net_name, container_name);
 
         let mut targets = Vec::new();
 
@@ -840,10 +1425,10 @@ impl Deployer {
             });
 
             targets.push(MetricsTarget {
-                job_name: job_name(&node.kona_node.container_name),
-                container_name: node.kona_node.container_name.clone(),
+                job_name: job_name(node.consensus_container_name()),
+                container_name: node.consensus_container_name().to_string(),
                 port: node.kona_node.metrics_port,
-                service_label: format!("kona-node-{}", role),
+                service_label: format!("{}-{}", node.consensus_client().service_name(), role),
                 layer_label: "consensus".to_string(),
             });
         }
@@ -860,10 +1445,10 @@ impl Deployer {
             });
 
             targets.push(MetricsTarget {
-                job_name: job_name(&node.kona_node.container_name),
-                container_name: node.kona_node.container_name.clone(),
+                job_name: job_name(node.consensus_container_name()),
+                container_name: node.consensus_container_name().to_string(),
                 port: node.kona_node.metrics_port,
-                service_label: format!("kona-node-{}", role),
+                service_label: format!("{}-{}", node.consensus_client().service_name(), role),
                 layer_label: "consensus".to_string(),
             });
         }
@@ -899,74 +1484,6 @@ impl Deployer {
         targets
     }
 
-    /// Print detached mode information including container names and stop command.
-    fn print_detached_info(
-        outdata: &Path,
-        anvil: &AnvilHandler,
-        l2_stack: &L2StackHandler,
-        monitoring: &Option<MonitoringHandler>,
-        network_id: &str,
-    ) {
-        let mut container_names = Vec::new();
-
-        // Add anvil container
-        container_names.push(anvil.container_name.clone());
-
-        // Add all L2 node containers (sequencers and validators)
-        for node in l2_stack.all_nodes() {
-            container_names.push(node.op_reth.container_name.clone());
-            container_names.push(node.kona_node.container_name.clone());
-
-            // Add op-conductor if present (for sequencer nodes)
-            if let Some(ref conductor) = node.op_conductor {
-                container_names.push(conductor.container_name.clone());
-            }
-        }
-
-        // Add L2 stack service containers
-        container_names.push(l2_stack.op_batcher.container_name.clone());
-        if let Some(ref proposer) = l2_stack.op_proposer {
-            container_names.push(proposer.container_name.clone());
-        }
-        if let Some(ref challenger) = l2_stack.op_challenger {
-            container_names.push(challenger.container_name.clone());
-        }
-
-        // Add monitoring containers if present
-        if let Some(mon) = monitoring {
-            container_names.push(mon.prometheus.container_name.clone());
-            container_names.push(mon.grafana.container_name.clone());
-        }
-
-        // Build the docker stop command
-        let stop_command = format!(
-            "docker stop {} && docker network rm {}",
-            container_names.join(" "),
-            network_id
-        );
-
-        // Print the detached mode information
-        tracing::info!("✓ Detached mode enabled. Containers are running in the background.");
-        tracing::info!("");
-        tracing::info!(
-            "Configuration saved to: {}",
-            outdata.join(KUPCONF_FILENAME).display()
-        );
-        tracing::info!("");
-        tracing::info!("Running containers:");
-        for name in &container_names {
-            tracing::info!("  - {}", name);
-        }
-        tracing::info!("");
-        tracing::info!("Network: {}", network_id);
-        tracing::info!("");
-        tracing::info!("To stop all containers:");
-        tracing::info!("  {}", stop_command);
-        tracing::info!("");
-        tracing::info!("To view logs:");
-        tracing::info!("  docker logs <container-name>");
-    }
-
     /// Restore L2 config files from an existing op-reth snapshot.
     ///
     /// Steps:
@@ -1110,11 +1627,123 @@ impl Deployer {
         Ok(())
     }
 
+    /// Supervise a deployment returned by [`Deployer::deploy_detached`] for as long as
+    /// the caller keeps polling: recreates Anvil from its last state dump if it exits
+    /// (with `anvil.auto_recover`) and warns while the disk runs low (with
+    /// `min_disk_space_gib`).
+    ///
+    /// `self` is the deployer the network was deployed from, cloned before the deployment
+    /// consumed it. Never returns unless the Anvil recovery fails; callers race it
+    /// against their own shutdown signal.
+    pub async fn watch(&self, result: &mut DeploymentResult, docker: &mut KupDocker) -> Result<()> {
+        let recover = async {
+            if self.anvil.auto_recover && !result.anvil.is_external() {
+                tracing::info!("Anvil auto-recovery enabled");
+                result
+                    .anvil
+                    .watch_and_recover(
+                        &self.anvil,
+                        docker,
+                        &self.outdata.join("anvil"),
+                        self.l1_chain_id,
+                    )
+                    .await
+            } else {
+                std::future::pending().await
+            }
+        };
+        let watch_disk = async {
+            if self.min_disk_space_gib > 0 {
+                fs::FsHandler::watch_disk_space(&self.outdata, self.min_disk_space_gib).await
+            } else {
+                std::future::pending().await
+            }
+        };
+        tokio::select! {
+            result = recover => result,
+            _ = watch_disk => Ok(()),
+        }
+    }
+
+    /// Deploy the network, or reuse it when [`Self::reuse_if_healthy`] is set and it is
+    /// already running (see [`Deployer::reuse_running_network`]).
+    ///
+    /// Returns None when the running network was reused; see [`Deployer::deploy_detached`]
+    /// otherwise.
     pub async fn deploy(
+        self,
+        docker: &mut KupDocker,
+        force_deploy: bool,
+    ) -> Result<Option<DeploymentResult>> {
+        if self.reuse_if_healthy && self.reuse_running_network(docker).await? {
+            return Ok(None);
+        }
+        self.deploy_detached(docker, force_deploy).await.map(Some)
+    }
+
+    /// Whether the network is already running and can be used as is.
+    ///
+    /// Returns false when no container of the network is running. A partially running
+    /// network is an error unless [`Self::restart_unhealthy`] is set, in which case its
+    /// stopped or failing containers are restarted.
+    pub async fn reuse_running_network(&self, docker: &KupDocker) -> Result<bool> {
+        use crate::health::ExistingDeployment;
+
+        let network = &self.docker.net_name;
+        let containers = match crate::health::check_existing_deployment(self).await? {
+            ExistingDeployment::NotRunning => {
+                tracing::info!(network, "Network is not running, deploying...");
+                return Ok(false);
+            }
+            ExistingDeployment::Healthy => {
+                tracing::info!(
+                    network,
+                    "Network is already running and healthy, nothing to do"
+                );
+                return Ok(true);
+            }
+            ExistingDeployment::Unhealthy { containers } => containers,
+        };
+
+        if containers.is_empty() {
+            anyhow::bail!(
+                "Network '{}' is running but failing health checks, and none of its containers \
+                 is stopped. Run `kupcake inspect` for details, re-run with --redeploy, or \
+                 remove it with `kupcake cleanup`",
+                network
+            );
+        }
+        if !self.restart_unhealthy {
+            anyhow::bail!(
+                "Network '{}' is partially running, unhealthy containers: {}. Re-run with \
+                 --restart-unhealthy to restart them, with --redeploy, or remove it with \
+                 `kupcake cleanup`",
+                network,
+                containers.join(", ")
+            );
+        }
+
+        for name in &containers {
+            docker.restart_container(name).await?;
+            tracing::info!(container = %name, "Container restarted");
+        }
+        Ok(true)
+    }
+
+    /// Deploy the network and return once every service is up and healthy.
+    ///
+    /// Never waits for Ctrl+C and installs no signal handler: the containers keep
+    /// running after this returns and the caller drives their lifecycle, e.g. with
+    /// [`Deployer::watch`], [`Deployer::stop`] or by dropping `docker` (which removes
+    /// them unless `no_cleanup` is set). The returned [`DeploymentResult`] holds the
+    /// L1, L2 stack and monitoring handlers.
+    ///
+    /// With `force_deploy`, the L1 contracts are redeployed even if the saved
+    /// deployment version matches the current configuration.
+    pub async fn deploy_detached(
         mut self,
         docker: &mut KupDocker,
         force_deploy: bool,
-        wait_for_exit: bool,
     ) -> Result<DeploymentResult> {
         let deploy_start = Instant::now();
         let mut metrics = DeploymentMetrics::default();
@@ -1128,6 +1757,24 @@ impl Deployer {
             );
         }
 
+        if !self.additional_l2_chains.is_empty() && self.snapshot.is_some() {
+            anyhow::bail!("Additional L2 chains cannot be restored from a snapshot.");
+        }
+
+        if self.min_disk_space_gib > 0 {
+            fs::FsHandler::check_disk_space(&self.outdata, self.min_disk_space_gib)?;
+        }
+
+        if let Some(ref proxy) = self.docker.proxy {
+            proxy
+                .check_connectivity()
+                .await
+                .context("Proxy preflight check failed")?;
+            docker.internal_hosts = self.container_names()?;
+        }
+
+        metrics.image_prefetch = self.prefetch_images(docker).await;
+
         // Compute hash of current deployment configuration before any moves occur
         let current_config = DeploymentConfigHash::from_deployer(&self);
         let current_hash = current_config
@@ -1135,8 +1782,13 @@ impl Deployer {
             .context("Failed to compute deployment config hash")?;
 
         // Save values we'll need after self is consumed
-        let detach = self.detach;
+        let keep_going = self.keep_going;
+        let mut warnings = Vec::new();
         let outdata = self.outdata.clone();
+        let post_deploy = self.post_deploy.take();
+        // Parts of self are moved into the services as they start.
+        let chain_id_deployer = self.verify_chain_ids.then(|| self.clone());
+        let (l1_chain_id, l2_chain_id) = (self.l1_chain_id, self.l2_chain_id);
 
         let l2_nodes_data_path = self.outdata.join("l2-stack");
         let anvil_data_path = self.outdata.join("anvil");
@@ -1148,7 +1800,29 @@ impl Deployer {
         let op_deployer_image = self.op_deployer.docker_image.clone();
         let op_deployer_name = self.op_deployer.container_name.clone();
         let anvil_start = Instant::now();
-        let (anvil, op_deployer_duration) = if let Some(ref snapshot_path) = self.snapshot {
+        let (anvil, op_deployer_duration) = if let Some(ref external_l1) = self.external_l1 {
+            if self.snapshot.is_some()
+                || self.override_state.is_some()
+                || self.deployment_target == DeploymentTarget::Genesis
+            {
+                anyhow::bail!(
+                    "An external L1 is only supported in live deployment mode, \
+                     without snapshots or --override-state."
+                );
+            }
+
+            Self::deploy_with_external_l1(
+                docker,
+                external_l1,
+                &self.op_deployer,
+                &l2_nodes_data_path,
+                self.l1_chain_id,
+                self.l2_chain_id,
+                force_deploy,
+                &current_hash,
+            )
+            .await?
+        } else if let Some(ref snapshot_path) = self.snapshot {
             // Snapshot: restore L2 files + Anvil state from snapshot, then start Anvil
             let op_deployer_start = Instant::now();
             let sequencer_name = self.l2_stack.sequencers[0].op_reth.container_name.clone();
@@ -1232,18 +1906,24 @@ impl Deployer {
             }
         };
 
+        let contracts_wait = Instant::now();
+        Self::wait_for_l1_contracts(&docker.rpc_client, &anvil, &l2_nodes_data_path).await?;
+        warnings::check_readiness("L1 contracts", contracts_wait.elapsed(), &mut warnings);
+
         // Record Anvil metrics (subtract op-deployer time from Anvil total)
-        let anvil_total = anvil_start.elapsed().saturating_sub(op_deployer_duration);
-        let anvil_size = get_image_size(docker, &anvil.container_id).await;
-        metrics.record(
-            anvil.container_name.clone(),
-            ServiceMetrics::from_timings(
-                anvil_total,
-                &anvil.deploy_timings,
-                anvil_size,
-                &anvil_docker_image,
-            ),
-        );
+        if !anvil.is_external() {
+            let anvil_total = anvil_start.elapsed().saturating_sub(op_deployer_duration);
+            let anvil_size = get_image_size(docker, &anvil.container_id).await;
+            metrics.record(
+                anvil.container_name.clone(),
+                ServiceMetrics::from_timings(
+                    anvil_total,
+                    &anvil.deploy_timings,
+                    anvil_size,
+                    &anvil_docker_image,
+                ),
+            );
+        }
 
         // Record op-deployer metrics separately
         metrics.record(
@@ -1261,7 +1941,9 @@ impl Deployer {
 
         // Register RPC-based state dump so Anvil L1 state is persisted before
         // containers are stopped. Both modes use this unified approach.
+        // An external L1 persists its own state.
         if self.dump_state
+            && !anvil.is_external()
             && let Some(ref host_url) = anvil.l1_host_url
         {
             docker.anvil_state_dump = Some(crate::AnvilStateDumpConfig {
@@ -1294,6 +1976,13 @@ impl Deployer {
             services_label,
         );
 
+        self.l2_stack
+            .unique_metrics_host_ports()
+            .context("Invalid L2 node metrics host ports")?;
+        self.l2_stack
+            .prepare_datadirs(&l2_nodes_data_path)
+            .context("Failed to prepare op-reth datadirs")?;
+
         let l2_stack = self
             .l2_stack
             .start(
@@ -1302,6 +1991,8 @@ impl Deployer {
                 &anvil,
                 self.l1_chain_id,
                 &mut metrics,
+                keep_going,
+                &mut warnings,
             )
             .await
             .context("Failed to start L2 stack")?;
@@ -1310,6 +2001,68 @@ impl Deployer {
         // and can be used to compute enodes for adding nodes to a running network.
         self.l2_stack.persist_p2p_keys(&l2_stack);
 
+        // Start the additional L2 chains, whose contracts were deployed with the primary one
+        let mut l2_chains = Vec::with_capacity(self.additional_l2_chains.len());
+        for (i, chain) in self.additional_l2_chains.iter_mut().enumerate() {
+            let chain_id = chain.chain_id;
+            let chain_data_path = chain.data_path(&outdata);
+            tracing::info!(
+                l2_chain_id = chain_id,
+                node_count = chain.l2_stack.node_count(),
+                "Starting L2 stack of chain {}...",
+                chain_id
+            );
+
+            self.op_deployer
+                .generate_additional_chain_files(
+                    docker,
+                    &l2_nodes_data_path,
+                    chain_id,
+                    &chain_data_path,
+                )
+                .await
+                .with_context(|| {
+                    format!("Failed to generate the config files of chain {}", chain_id)
+                })?;
+            if self.deployment_target == DeploymentTarget::Genesis
+                && let Some(ref anvil_host_url) = anvil.l1_host_url
+            {
+                crate::l1_genesis::patch_rollup_l1_genesis_hash(
+                    &docker.rpc_client,
+                    &chain_data_path.join("rollup.json"),
+                    anvil_host_url,
+                )
+                .await
+                .context("Failed to patch rollup.json with actual L1 genesis hash")?;
+            }
+
+            chain
+                .l2_stack
+                .unique_metrics_host_ports()
+                .context("Invalid L2 node metrics host ports")?;
+            chain
+                .l2_stack
+                .prepare_datadirs(&chain_data_path)
+                .context("Failed to prepare op-reth datadirs")?;
+
+            let l1 = anvil.for_l2_chain(i + 1)?;
+            let l2_stack = chain
+                .l2_stack
+                .start(
+                    docker,
+                    chain_data_path,
+                    &l1,
+                    self.l1_chain_id,
+                    &mut metrics,
+                    keep_going,
+                    &mut warnings,
+                )
+                .await
+                .with_context(|| format!("Failed to start the L2 stack of chain {}", chain_id))?;
+            chain.l2_stack.persist_p2p_keys(&l2_stack);
+            l2_chains.push(L2ChainHandler { chain_id, l2_stack });
+        }
+
         // Re-save the config with P2P keys by loading and patching.
         // We cannot call self.save_config() because self.anvil has been moved.
         {
@@ -1318,61 +2071,167 @@ impl Deployer {
                 && let Ok(mut saved) = Deployer::load_from_file(&config_path)
             {
                 saved.l2_stack = self.l2_stack.clone();
+                saved.additional_l2_chains = self.additional_l2_chains.clone();
                 if let Err(e) = saved.save_to_file(&config_path) {
                     tracing::warn!(error = %e, "Failed to re-save config with P2P keys");
                 }
+
+                // Record what each service was started with, so that a later deploy of
+                // an edited config only recreates the services that changed.
+                let version_file_path = l2_nodes_data_path.join(crate::DEPLOYMENT_VERSION_FILENAME);
+                let recorded = DeploymentVersion::load_from_file(&version_file_path).and_then(
+                    |mut version| {
+                        version.service_hashes = crate::service_hashes(&saved)?;
+                        version.save_to_file(&version_file_path)
+                    },
+                );
+                if let Err(e) = recorded {
+                    tracing::debug!(error = %e, "Failed to record service config hashes");
+                }
             }
         }
 
         // Start monitoring stack if enabled
         let monitoring = if self.monitoring.enabled {
-            tracing::info!("Starting monitoring stack (Prometheus + Grafana)...");
+            if self.monitoring.logs.enabled {
+                tracing::info!("Starting monitoring stack (Prometheus + Grafana + Loki)...");
+            } else {
+                tracing::info!("Starting monitoring stack (Prometheus + Grafana)...");
+            }
 
             let monitoring_data_path = self.outdata.join("monitoring");
-            let metrics_targets = Self::build_metrics_targets(&l2_stack);
+            let mut metrics_targets = Self::build_metrics_targets(&l2_stack, &self.docker.net_name);
+            for chain in &l2_chains {
+                metrics_targets.extend(Self::build_metrics_targets(
+                    &chain.l2_stack,
+                    &self.docker.net_name,
+                ));
+            }
 
             let mon_start = Instant::now();
-            let mon_handler = self
+            let result = self
                 .monitoring
                 .start(
                     docker,
                     monitoring_data_path,
+                    self.docker.attached_network(),
                     metrics_targets,
                     self.dashboards_path,
                 )
                 .await
-                .context("Failed to start monitoring stack")?;
+                .context("Failed to start monitoring stack");
+            let mon_handler =
+                warnings::best_effort("monitoring", result, keep_going, &mut warnings)?;
             let mon_total = mon_start.elapsed();
 
-            let prom_size = get_image_size(docker, &mon_handler.prometheus.container_id).await;
-            metrics.record(
-                mon_handler.prometheus.container_name.clone(),
-                ServiceMetrics::composite(
-                    mon_total,
-                    prom_size,
-                    self.monitoring.prometheus.docker_image.to_string(),
-                ),
-            );
-            let grafana_size = get_image_size(docker, &mon_handler.grafana.container_id).await;
-            metrics.record(
-                mon_handler.grafana.container_name.clone(),
-                ServiceMetrics::composite(
-                    mon_total,
-                    grafana_size,
-                    self.monitoring.grafana.docker_image.to_string(),
-                ),
-            );
+            if let Some(ref mon_handler) = mon_handler {
+                let prom_size = get_image_size(docker, &mon_handler.prometheus.container_id).await;
+                metrics.record(
+                    mon_handler.prometheus.container_name.clone(),
+                    ServiceMetrics::composite(
+                        mon_total,
+                        prom_size,
+                        self.monitoring.prometheus.docker_image.to_string(),
+                    ),
+                );
+                let grafana_size = get_image_size(docker, &mon_handler.grafana.container_id).await;
+                metrics.record(
+                    mon_handler.grafana.container_name.clone(),
+                    ServiceMetrics::composite(
+                        mon_total,
+                        grafana_size,
+                        self.monitoring.grafana.docker_image.to_string(),
+                    ),
+                );
+                if let Some(ref logs) = mon_handler.logs {
+                    let loki_size = get_image_size(docker, &logs.loki.container_id).await;
+                    metrics.record(
+                        logs.loki.container_name.clone(),
+                        ServiceMetrics::composite(
+                            mon_total,
+                            loki_size,
+                            self.monitoring.logs.loki.docker_image.to_string(),
+                        ),
+                    );
+                    let promtail_size = get_image_size(docker, &logs.promtail.container_id).await;
+                    metrics.record(
+                        logs.promtail.container_name.clone(),
+                        ServiceMetrics::composite(
+                            mon_total,
+                            promtail_size,
+                            self.monitoring.logs.promtail.docker_image.to_string(),
+                        ),
+                    );
+                }
+            }
 
-            Some(mon_handler)
+            mon_handler
         } else {
             None
         };
 
+        // Start the Blockscout explorer if enabled, indexing the primary sequencer
+        let blockscout = if self.blockscout.enabled {
+            tracing::info!("Starting Blockscout explorer...");
+
+            let op_reth = &l2_stack.primary_sequencer().op_reth;
+            let input = BlockscoutInput {
+                chain_id: l2_chain_id,
+                l2_rpc_url: op_reth.http_rpc_url.clone(),
+                l2_ws_url: Some(op_reth.ws_rpc_url.clone()),
+            };
+
+            let blockscout_start = Instant::now();
+            let result = self
+                .blockscout
+                .deploy(docker, &self.outdata.join("blockscout"), input)
+                .await
+                .context("Failed to start Blockscout");
+            let handler = warnings::best_effort("blockscout", result, keep_going, &mut warnings)?;
+
+            if let Some(ref handler) = handler {
+                let size = get_image_size(docker, &handler.container_id).await;
+                metrics.record(
+                    handler.container_name.clone(),
+                    ServiceMetrics::from_timings(
+                        blockscout_start.elapsed(),
+                        &handler.deploy_timings,
+                        size,
+                        &self.blockscout.docker_image,
+                    ),
+                );
+            }
+
+            handler
+        } else {
+            None
+        };
+
+        if let Some(ref deployer) = chain_id_deployer {
+            let report = deployer
+                .chain_id_report(docker)
+                .await
+                .context("Failed to check chain IDs")?;
+            tracing::info!("Chain IDs:\n{}", report);
+            report.ensure_consistent()?;
+        }
+
         // Finalize and log deployment metrics
         metrics.total = deploy_start.elapsed();
         metrics.log_summary();
 
-        tracing::info!("✓ Deployment complete!");
+        warnings.append(&mut docker.warnings);
+        if warnings.is_empty() {
+            tracing::info!("✓ Deployment complete!");
+        } else {
+            tracing::warn!(
+                count = warnings.len(),
+                "Deployment complete, with warnings:"
+            );
+            for warning in &warnings {
+                tracing::warn!("  - {}", warning);
+            }
+        }
         tracing::info!("");
         tracing::info!("=== Host-accessible endpoints (curl from your terminal) ===");
         if let Some(ref url) = anvil.l1_host_url {
@@ -1421,6 +2280,17 @@ impl Deployer {
         if let Some(ref url) = l2_stack.op_batcher.rpc_host_url {
             tracing::info!("L2 (op-batcher) RPC:  {}", url);
         }
+
+        // Log the sequencer endpoints of the additional chains
+        for chain in &l2_chains {
+            let sequencer = &chain.l2_stack.primary_sequencer();
+            if let Some(ref url) = sequencer.op_reth.http_host_url {
+                tracing::info!("L2 chain {} (op-reth) HTTP:    {}", chain.chain_id, url);
+            }
+            if let Some(ref url) = sequencer.kona_node.rpc_host_url {
+                tracing::info!("L2 chain {} (kona-node) RPC:   {}", chain.chain_id, url);
+            }
+        }
         if let Some(ref mon) = monitoring {
             if let Some(ref url) = mon.prometheus.host_url {
                 tracing::info!("Prometheus:           {}", url);
@@ -1428,6 +2298,12 @@ impl Deployer {
             if let Some(ref url) = mon.grafana.host_url {
                 tracing::info!("Grafana:              {}", url);
             }
+            if let Some(ref url) = mon.logs.as_ref().and_then(|l| l.loki.host_url.as_ref()) {
+                tracing::info!("Loki:                 {}", url);
+            }
+        }
+        if let Some(ref url) = blockscout.as_ref().and_then(|b| b.host_url.as_ref()) {
+            tracing::info!("Blockscout:           {}", url);
         }
         tracing::info!("");
         tracing::info!("=== Internal Docker network endpoints ===");
@@ -1484,6 +2360,12 @@ impl Deployer {
 
         tracing::info!("");
 
+        if let Some(ref script) = post_deploy {
+            script
+                .run(docker, l1_chain_id, l2_chain_id, &anvil, &l2_stack)
+                .await?;
+        }
+
         // Register devnet in the global registry
         let network_name = docker
             .config
@@ -1497,28 +2379,353 @@ impl Deployer {
         }
         docker.registry_name = Some(network_name.to_string());
 
-        if wait_for_exit {
-            if detach {
-                // Detached mode: print management info and exit
-                Self::print_detached_info(
-                    &outdata,
-                    &anvil,
-                    &l2_stack,
-                    &monitoring,
-                    &docker.network_id,
-                );
-            } else {
-                // Normal mode: wait for Ctrl+C
-                tracing::info!("Press Ctrl+C to stop all nodes and cleanup.");
-                tokio::signal::ctrl_c().await?;
-            }
-        }
-
         Ok(DeploymentResult {
             anvil,
             l2_stack,
+            l2_chains,
             metrics,
             monitoring,
+            blockscout,
+            warnings,
         })
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::fmt::Debug;
+
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::{
+        ExternalL1Config, KonaNodeBuilder, OpConductorBuilder, OpNodeBuilder, OpRethBuilder,
+        services::{GrafanaConfig, PrometheusConfig},
+    };
+
+    /// Assert that a config value survives a TOML and a JSON round-trip unchanged.
+    fn assert_round_trip<T>(value: &T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let toml = toml::to_string_pretty(value).unwrap();
+        let from_toml: T =
+            toml::from_str(&toml).unwrap_or_else(|e| panic!("TOML round-trip failed: {e}\n{toml}"));
+        assert_eq!(
+            &from_toml, value,
+            "TOML round-trip changed the value:\n{toml}"
+        );
+
+        let json = serde_json::to_string(value).unwrap();
+        let from_json: T = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("JSON round-trip failed: {e}\n{json}"));
+        assert_eq!(
+            &from_json, value,
+            "JSON round-trip changed the value:\n{json}"
+        );
+    }
+
+    #[test]
+    fn test_service_configs_round_trip() {
+        assert_round_trip(&AnvilConfig::default());
+        assert_round_trip(&OpRethBuilder::default());
+        assert_round_trip(&KonaNodeBuilder::default());
+        assert_round_trip(&OpNodeBuilder::default());
+        assert_round_trip(&OpConductorBuilder::default());
+        assert_round_trip(&OpBatcherBuilder::default());
+        assert_round_trip(&OpBatcherBuilder {
+            da_type: crate::DataAvailabilityType::Calldata,
+            ..Default::default()
+        });
+        assert_round_trip(&OpProposerBuilder::default());
+        assert_round_trip(&OpChallengerBuilder::default());
+        assert_round_trip(&OpDeployerConfig::default());
+        assert_round_trip(&KupDockerConfig {
+            net_name: "kup-test-network".to_string(),
+            no_cleanup: false,
+            publish_all_ports: true,
+            log_max_size: Some("10m".to_string()),
+            log_max_file: None,
+            stream_logs: false,
+            restart_policy: crate::RestartPolicy::OnFailure { max_retries: 3 },
+            extra_hosts: vec![crate::docker::HOST_GATEWAY_ENTRY.to_string()],
+            ca_bundle: None,
+            proxy: None,
+            rpc_client: crate::RpcClientConfig {
+                request_timeout_secs: 30,
+                connect_timeout_secs: Some(5),
+                pool_max_idle_per_host: None,
+            },
+            run_as_host_user: false,
+            recorded_host_ports: Default::default(),
+            subnet: Some("172.30.0.0/16".to_string()),
+            gateway: Some("172.30.0.1".to_string()),
+            external_network: None,
+        });
+        assert_round_trip(&PrometheusConfig::default());
+        assert_round_trip(&GrafanaConfig::default());
+        assert_round_trip(&MonitoringConfig::default());
+        assert_round_trip(
+            &ExternalL1Config::new("http://l1:8545", "env:L1_DEPLOYER_KEY")
+                .batcher_private_key("file:/secrets/batcher.key"),
+        );
+    }
+
+    #[test]
+    fn test_l2_configs_round_trip() {
+        assert_round_trip(&L2NodeBuilder::sequencer());
+        assert_round_trip(
+            &L2NodeBuilder::validator().with_conductor(OpConductorBuilder::default()),
+        );
+        assert_round_trip(&L2NodeBuilder {
+            op_node: Some(OpNodeBuilder::default()),
+            ..L2NodeBuilder::validator()
+        });
+        assert_round_trip(&L2StackBuilder::default());
+        assert_round_trip(&L2ChainBuilder {
+            chain_id: 1235,
+            l2_stack: L2StackBuilder::with_counts(1, 1),
+        });
+    }
+
+    #[test]
+    fn test_additional_l2_chains_round_trip() {
+        let mut deployer = test_deployer(PathBuf::from("/tmp/test"));
+        let toml = toml::to_string(&deployer).unwrap();
+        assert!(!toml.contains("additional_l2_chains"), "{toml}");
+
+        deployer.additional_l2_chains = vec![L2ChainBuilder {
+            chain_id: 1235,
+            l2_stack: L2StackBuilder::with_counts(1, 1),
+        }];
+        assert_round_trip(&deployer);
+    }
+
+    #[test]
+    fn test_post_deploy_script_is_not_saved() {
+        let mut deployer = test_deployer(PathBuf::from("/tmp/test"));
+        deployer.post_deploy = Some(crate::PostDeployScript::new("./bootstrap.sh"));
+
+        let toml = toml::to_string(&deployer).unwrap();
+        assert!(!toml.contains("post_deploy"), "{toml}");
+        let loaded: Deployer = toml::from_str(&toml).unwrap();
+        assert_eq!(loaded.post_deploy, None);
+    }
+
+    #[test]
+    fn test_images() {
+        let mut deployer = test_deployer(PathBuf::from("/tmp/test"));
+        deployer.monitoring.enabled = false;
+        let image_count = deployer.images().len();
+        assert!(
+            deployer
+                .images()
+                .contains(&&deployer.l2_stack.sequencers[0].op_reth.docker_image)
+        );
+
+        // Additional chains run the same images
+        deployer.additional_l2_chains = vec![L2ChainBuilder {
+            chain_id: 1235,
+            l2_stack: L2StackBuilder::with_counts(1, 1),
+        }];
+        assert_eq!(deployer.images().len(), image_count);
+
+        deployer.l2_stack.op_batcher.docker_image =
+            crate::DockerImage::from_binary("/tmp/op-batcher");
+        deployer.additional_l2_chains.clear();
+        assert_eq!(deployer.images().len(), image_count - 1);
+    }
+
+    #[test]
+    fn test_default_batcher_da_type_is_not_serialized() {
+        let toml = toml::to_string(&OpBatcherBuilder::default()).unwrap();
+        assert!(!toml.contains("da_type"), "{toml}");
+
+        let calldata = OpBatcherBuilder {
+            da_type: crate::DataAvailabilityType::Calldata,
+            ..Default::default()
+        };
+        let toml = toml::to_string(&calldata).unwrap();
+        assert!(toml.contains("da_type = \"calldata\""), "{toml}");
+    }
+
+    #[test]
+    fn test_metrics_job_name() {
+        assert_eq!(
+            metrics_job_name("kup-mynet-network", "kup-mynet-op-reth-validator-1"),
+            "op-reth-validator-1"
+        );
+        assert_eq!(
+            metrics_job_name("kup-mynet-network", "custom-op-batcher"),
+            "custom-op-batcher"
+        );
+    }
+
+    #[test]
+    fn test_l1_contract_addresses() {
+        let state = serde_json::json!({
+            "opChainDeployments": [{
+                "OptimismPortalProxy": "0x01",
+                "DisputeGameFactoryProxy": "0x02",
+                "SystemConfigProxy": "0x03"
+            }]
+        });
+        assert_eq!(
+            l1_contract_addresses(&state).unwrap(),
+            vec![
+                ("OptimismPortalProxy", "0x01".to_string()),
+                ("DisputeGameFactoryProxy", "0x02".to_string())
+            ]
+        );
+
+        let missing =
+            serde_json::json!({ "opChainDeployments": [{ "OptimismPortalProxy": "0x01" }] });
+        let err = l1_contract_addresses(&missing).unwrap_err().to_string();
+        assert!(err.contains("DisputeGameFactoryProxy"), "{err}");
+    }
+
+    #[test]
+    fn test_unpublished_prometheus_port_is_not_representable() {
+        let prometheus = PrometheusConfig {
+            host_port: None,
+            ..Default::default()
+        };
+
+        let toml = toml::to_string(&prometheus).unwrap();
+        let loaded: PrometheusConfig = toml::from_str(&toml).unwrap();
+        assert_eq!(loaded.host_port, Some(0));
+    }
+
+    pub(crate) fn test_deployer(outdata: PathBuf) -> Deployer {
+        Deployer {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            outdata,
+            anvil: AnvilConfig::default(),
+            op_deployer: OpDeployerConfig::default(),
+            docker: KupDockerConfig {
+                net_name: "test-net".to_string(),
+                no_cleanup: false,
+                publish_all_ports: false,
+                log_max_size: None,
+                log_max_file: None,
+                stream_logs: false,
+                restart_policy: Default::default(),
+                extra_hosts: Vec::new(),
+                ca_bundle: None,
+                proxy: None,
+                rpc_client: Default::default(),
+                run_as_host_user: true,
+                recorded_host_ports: Default::default(),
+                subnet: None,
+                gateway: None,
+                external_network: None,
+            },
+            l2_stack: L2StackBuilder::default(),
+            monitoring: MonitoringConfig::default(),
+            blockscout: Default::default(),
+            dashboards_path: Some(PathBuf::from("grafana/dashboards")),
+            detach: false,
+            snapshot: None,
+            copy_snapshot: false,
+            deployment_target: crate::DeploymentTarget::Live,
+            dump_state: true,
+            override_state: None,
+            external_l1: None,
+            post_deploy: None,
+            min_disk_space_gib: crate::DEFAULT_MIN_DISK_SPACE_GIB,
+            keep_going: false,
+            verify_chain_ids: false,
+            reuse_if_healthy: false,
+            restart_unhealthy: false,
+            additional_l2_chains: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_save_stores_absolute_paths() {
+        let dir = tempdir::TempDir::new("kupcake-test").unwrap();
+        let config_path = dir.path().join(KUPCONF_FILENAME);
+
+        test_deployer(PathBuf::from("data-test"))
+            .save_to_file(&config_path)
+            .unwrap();
+        let loaded = Deployer::load_from_file(&config_path).unwrap();
+
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(loaded.outdata, cwd.join("data-test"));
+        assert_eq!(loaded.dashboards_path, Some(cwd.join("grafana/dashboards")));
+    }
+
+    #[test]
+    fn test_export_chain_config() {
+        let outdata = tempdir::TempDir::new("kupcake-test").unwrap();
+        let export_dir = outdata.path().join("export");
+        let l2_stack = outdata.path().join("l2-stack");
+        std::fs::create_dir(&l2_stack).unwrap();
+        for file in [
+            "rollup.json",
+            "genesis.json",
+            "jwt-sequencer-0.hex",
+            "intent.toml",
+        ] {
+            std::fs::write(l2_stack.join(file), "{}").unwrap();
+        }
+
+        let deployer = test_deployer(outdata.path().to_path_buf());
+        let exported = deployer.export_chain_config(&export_dir).unwrap();
+
+        let mut names: Vec<_> = exported
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "Kupcake.toml",
+                "genesis.json",
+                "jwt-sequencer-0.hex",
+                "rollup.json"
+            ]
+        );
+        assert!(exported.iter().all(|path| path.starts_with(&export_dir)));
+
+        // Contract addresses are exported once state.json exists.
+        let state = serde_json::json!({
+            "opChainDeployments": [{
+                "id": "0xa455",
+                "SystemConfigProxy": "0x1111111111111111111111111111111111111111",
+            }],
+        });
+        std::fs::write(l2_stack.join("state.json"), state.to_string()).unwrap();
+        let exported = deployer.export_chain_config(&export_dir).unwrap();
+        assert!(exported.contains(&export_dir.join("addresses.json")));
+
+        std::fs::remove_file(l2_stack.join("rollup.json")).unwrap();
+        assert!(deployer.export_chain_config(&export_dir).is_err());
+    }
+
+    #[test]
+    fn test_check_data_dir() {
+        let dir = tempdir::TempDir::new("kupcake-test").unwrap();
+        let config_path = dir.path().join(KUPCONF_FILENAME);
+        let moved_from = PathBuf::from("/nonexistent/data-test");
+
+        // Never deployed: a missing data directory is created by the deployment.
+        let mut deployer = test_deployer(moved_from.clone());
+        deployer.check_data_dir(&config_path, false).unwrap();
+        assert_eq!(deployer.outdata, moved_from);
+
+        // Moved along with its deployment data.
+        std::fs::create_dir(dir.path().join("l2-stack")).unwrap();
+        let err = deployer
+            .check_data_dir(&config_path, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--rebase-data-dir"), "{err}");
+
+        deployer.check_data_dir(&config_path, true).unwrap();
+        assert_eq!(deployer.outdata, dir.path());
+    }
+}
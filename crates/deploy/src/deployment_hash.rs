@@ -1,10 +1,14 @@
+use alloy_core::primitives::{Address, U256};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 use crate::Deployer;
 
+/// Name of the deployment version file, stored in the `l2-stack` output directory.
+pub const DEPLOYMENT_VERSION_FILENAME: &str = ".deployment-version.json";
+
 /// Configuration parameters that affect contract deployment.
 ///
 /// This struct contains only the deployment-relevant parameters that, when changed,
@@ -23,13 +27,54 @@ pub struct DeploymentConfigHash {
     /// Genesis timestamp - affects genesis timestamp alignment
     pub timestamp: Option<u64>,
 
-    // EIP-1559 parameters (currently hardcoded in op-deployer intent generation)
-    // These are included for future extensibility when they become configurable
+    // EIP-1559 parameters - op-deployer defaults unless overridden
     pub eip1559_denominator: u64,
     pub eip1559_denominator_canyon: u64,
     pub eip1559_elasticity: u64,
     /// Deployment target (live or genesis) - affects how contracts are deployed
     pub deployment_target: crate::DeploymentTarget,
+    /// External L1 RPC URL - contracts live on a different chain when it changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_l1_rpc_url: Option<String>,
+    /// L2 block time - baked into the rollup config and SystemConfig
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_block_time: Option<u64>,
+    /// SHA-256 of a user-supplied intent file - it replaces the generated intent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intent_file_digest: Option<String>,
+    /// Intent roles left as-is instead of being replaced with the Anvil accounts
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keep_intent_roles: bool,
+    /// Sequencer fee vault recipient - baked into the L2 genesis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequencer_fee_recipient: Option<String>,
+    /// Contract artifacts of the L1 contracts - a different release deploys different code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_contracts_locator: Option<String>,
+    /// Contract artifacts of the L2 predeploys - baked into the L2 genesis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_contracts_locator: Option<String>,
+    /// Alt-DA challenge settings - deployed in the DataAvailabilityChallenge contract.
+    /// The DA server URL is runtime-only and left empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_da: Option<crate::AltDaConfig>,
+    /// Accounts funded in the L2 genesis allocations
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub l2_prefund: BTreeMap<Address, U256>,
+    /// Mnemonic and derivation path of the Anvil accounts - they set the role addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anvil_mnemonic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anvil_derivation_path: Option<String>,
+    /// Chain IDs of the additional L2 chains - deployed by the same intent
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_l2_chain_ids: Vec<u64>,
+    /// L2 gas limit - written to the SystemConfig and the L2 genesis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// L2 hardforks activated after genesis - patched into the generated rollup config
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub l2_hardfork_offsets: BTreeMap<String, u64>,
 }
 
 impl DeploymentConfigHash {
@@ -41,12 +86,39 @@ impl DeploymentConfigHash {
             fork_url: deployer.anvil.fork_url.clone(),
             fork_block_number: deployer.anvil.fork_block_number,
             timestamp: deployer.anvil.timestamp,
-            // Hardcoded values from op-deployer intent generation
-            // See: crates/deploy/src/services/op_deployer/mod.rs:231-233
-            eip1559_denominator: 50,
-            eip1559_denominator_canyon: 250,
-            eip1559_elasticity: 6,
+            // op-deployer intent defaults, see `update_intent_with_accounts`. An override
+            // of the denominator applies to both the pre- and post-Canyon values.
+            eip1559_denominator: deployer.op_deployer.eip1559_denominator.unwrap_or(50),
+            eip1559_denominator_canyon: deployer.op_deployer.eip1559_denominator.unwrap_or(250),
+            eip1559_elasticity: deployer.op_deployer.eip1559_elasticity.unwrap_or(6),
             deployment_target: deployer.deployment_target,
+            external_l1_rpc_url: deployer.external_l1.as_ref().map(|l1| l1.rpc_url.clone()),
+            l2_block_time: deployer.op_deployer.l2_block_time,
+            // An unreadable intent file fails the deployment itself, so None is fine here
+            intent_file_digest: deployer
+                .op_deployer
+                .intent_file
+                .as_ref()
+                .and_then(|path| std::fs::read(path).ok())
+                .map(|content| hex::encode(Sha256::digest(content))),
+            keep_intent_roles: !deployer.op_deployer.patch_roles,
+            sequencer_fee_recipient: deployer.op_deployer.sequencer_fee_recipient.clone(),
+            l1_contracts_locator: deployer.op_deployer.l1_contracts_locator.clone(),
+            l2_contracts_locator: deployer.op_deployer.l2_contracts_locator.clone(),
+            alt_da: deployer
+                .op_deployer
+                .alt_da
+                .clone()
+                .map(|alt_da| crate::AltDaConfig {
+                    da_server: String::new(),
+                    ..alt_da
+                }),
+            l2_prefund: deployer.l2_stack.prefund.clone(),
+            anvil_mnemonic: deployer.anvil.mnemonic.clone(),
+            anvil_derivation_path: deployer.anvil.derivation_path.clone(),
+            additional_l2_chain_ids: deployer.op_deployer.additional_l2_chain_ids.clone(),
+            gas_limit: deployer.op_deployer.gas_limit,
+            l2_hardfork_offsets: deployer.l2_stack.hardfork_offsets.clone(),
         }
     }
 
@@ -56,17 +128,95 @@ impl DeploymentConfigHash {
     /// The configuration is serialized to JSON (with sorted keys) before hashing to ensure
     /// consistent ordering.
     pub fn compute_hash(&self) -> Result<String> {
-        // Serialize to JSON with sorted keys for consistent hashing
-        let json =
-            serde_json::to_string(self).context("Failed to serialize DeploymentConfigHash")?;
+        hash_json(self).context("Failed to serialize DeploymentConfigHash")
+    }
+}
+
+/// SHA-256 of the JSON serialization of `value`, hex-encoded.
+fn hash_json<T: Serialize>(value: &T) -> Result<String> {
+    let json = serde_json::to_string(value)?;
+    Ok(hex::encode(Sha256::digest(json.as_bytes())))
+}
+
+/// Config hash of every service container, keyed by container name.
+pub type ServiceHashes = BTreeMap<String, String>;
+
+/// Compute the config hash of every service container of a deployment.
+///
+/// Each hash covers the full configuration of one service (image, ports, flags, ...),
+/// so a service whose hash changed between two deployments has to be recreated while
+/// the others can keep running. An external L1 and disabled services have no entry.
+pub fn service_hashes(deployer: &Deployer) -> Result<ServiceHashes> {
+    let mut hashes = ServiceHashes::new();
+    let mut insert = |name: &str, hash: Result<String>| -> Result<()> {
+        let hash = hash.with_context(|| format!("Failed to hash the config of {}", name))?;
+        hashes.insert(name.to_string(), hash);
+        Ok(())
+    };
+
+    if deployer.external_l1.is_none() {
+        insert(&deployer.anvil.container_name, hash_json(&deployer.anvil))?;
+    }
 
-        let mut hasher = Sha256::new();
-        hasher.update(json.as_bytes());
-        let result = hasher.finalize();
+    let additional_stacks = deployer
+        .additional_l2_chains
+        .iter()
+        .map(|chain| &chain.l2_stack);
+    for stack in std::iter::once(&deployer.l2_stack).chain(additional_stacks) {
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            insert(&node.op_reth.container_name, hash_json(&node.op_reth))?;
+            match node.op_node {
+                Some(ref op_node) => insert(&op_node.container_name, hash_json(op_node))?,
+                None => insert(&node.kona_node.container_name, hash_json(&node.kona_node))?,
+            }
+            if let Some(ref conductor) = node.op_conductor {
+                insert(&conductor.container_name, hash_json(conductor))?;
+            }
+        }
+        insert(
+            &stack.op_batcher.container_name,
+            hash_json(&stack.op_batcher),
+        )?;
+        if let Some(ref proposer) = stack.op_proposer {
+            insert(&proposer.container_name, hash_json(proposer))?;
+        }
+        if let Some(ref challenger) = stack.op_challenger {
+            insert(&challenger.container_name, hash_json(challenger))?;
+        }
+    }
+
+    if deployer.monitoring.enabled {
+        let monitoring = &deployer.monitoring;
+        insert(
+            &monitoring.prometheus.container_name,
+            hash_json(&monitoring.prometheus),
+        )?;
+        insert(
+            &monitoring.grafana.container_name,
+            hash_json(&monitoring.grafana),
+        )?;
+        if monitoring.logs.enabled {
+            insert(
+                &monitoring.logs.loki.container_name,
+                hash_json(&monitoring.logs.loki),
+            )?;
+            insert(
+                &monitoring.logs.promtail.container_name,
+                hash_json(&monitoring.logs.promtail),
+            )?;
+        }
+    }
 
-        // Return hex-encoded hash
-        Ok(hex::encode(result))
+    if deployer.blockscout.enabled {
+        let blockscout = &deployer.blockscout;
+        insert(
+            &blockscout.postgres_container_name,
+            hash_json(&blockscout.postgres_docker_image),
+        )?;
+        insert(&blockscout.container_name, hash_json(blockscout))?;
     }
+
+    Ok(hashes)
 }
 
 /// Deployment version metadata stored alongside deployment artifacts.
@@ -81,6 +231,10 @@ pub struct DeploymentVersion {
     pub deployed_at: u64,
     /// Kupcake version that created this deployment
     pub kupcake_version: String,
+    /// Config hash of every service container when the deployment last started
+    /// (see [`service_hashes`]). Empty for deployments made by older versions.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub service_hashes: ServiceHashes,
 }
 
 impl DeploymentVersion {
@@ -96,9 +250,20 @@ impl DeploymentVersion {
                 .context("System time is before Unix epoch")?
                 .as_secs(),
             kupcake_version: env!("CARGO_PKG_VERSION").to_string(),
+            service_hashes: ServiceHashes::new(),
         })
     }
 
+    /// Containers whose config hash in `current` differs from the recorded one, or that
+    /// were not part of the recorded deployment.
+    pub fn changed_services(&self, current: &ServiceHashes) -> Vec<String> {
+        current
+            .iter()
+            .filter(|(name, hash)| self.service_hashes.get(*name) != Some(*hash))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
     /// Save this version metadata to a file.
     ///
     /// The file is written as formatted JSON for human readability.
@@ -151,6 +316,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let hash1 = config.compute_hash().unwrap();
@@ -160,6 +339,43 @@ mod tests {
         assert_eq!(hash1.len(), 64, "SHA-256 hash should be 64 hex characters");
     }
 
+    #[test]
+    fn test_hash_is_stable_across_machines() {
+        // Golden value: the hash only depends on the configuration, so deterministic
+        // deployments get the same hash everywhere. Update it only on purpose, as it
+        // makes every existing deployment look outdated.
+        let config = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(crate::deterministic::DETERMINISTIC_GENESIS_TIMESTAMP),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Genesis,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            config.compute_hash().unwrap(),
+            "c61909f51983cba09b0d48375b27e53fa55b5c8fe8cc689cb34d423c081ae2de"
+        );
+    }
+
     #[test]
     fn test_hash_changes_with_l1_chain_id() {
         let config1 = DeploymentConfigHash {
@@ -172,6 +388,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let mut config2 = config1.clone();
@@ -196,6 +426,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let mut config2 = config1.clone();
@@ -220,6 +464,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let mut config2 = config1.clone();
@@ -244,6 +502,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let mut config2 = config1.clone();
@@ -268,6 +540,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let mut config2 = config1.clone();
@@ -292,6 +578,20 @@ mod tests {
             eip1559_denominator_canyon: 250,
             eip1559_elasticity: 6,
             deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
         };
 
         let mut config2 = config1.clone();
@@ -304,6 +604,262 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hash_changes_with_l2_block_time() {
+        let config1 = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(1737316800),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        let mut config2 = config1.clone();
+        config2.l2_block_time = Some(1);
+
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config2.compute_hash().unwrap(),
+            "Hash should change when the L2 block time changes"
+        );
+    }
+
+    #[test]
+    fn test_hash_changes_with_sequencer_fee_recipient() {
+        let config1 = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(1737316800),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        let mut config2 = config1.clone();
+        config2.sequencer_fee_recipient =
+            Some("0x0000000000000000000000000000000000000001".to_string());
+
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config2.compute_hash().unwrap(),
+            "Hash should change when the sequencer fee recipient changes"
+        );
+    }
+
+    #[test]
+    fn test_hash_changes_with_chain_fee_params() {
+        let config1 = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(1737316800),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        let mut config2 = config1.clone();
+        config2.gas_limit = Some(60_000_000);
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config2.compute_hash().unwrap(),
+            "Hash should change when the gas limit is set"
+        );
+
+        let mut config3 = config1.clone();
+        config3.eip1559_elasticity = 10;
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config3.compute_hash().unwrap(),
+            "Hash should change when the EIP-1559 elasticity changes"
+        );
+
+        let mut config4 = config1.clone();
+        config4
+            .l2_hardfork_offsets
+            .insert("isthmus".to_string(), 120);
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config4.compute_hash().unwrap(),
+            "Hash should change when a hardfork is offset"
+        );
+    }
+
+    #[test]
+    fn test_hash_changes_with_l2_prefund() {
+        let config1 = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(1737316800),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        let mut config2 = config1.clone();
+        config2.l2_prefund.insert(Address::ZERO, U256::from(1));
+
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config2.compute_hash().unwrap(),
+            "Hash should change when the prefunded L2 accounts change"
+        );
+    }
+
+    #[test]
+    fn test_hash_changes_with_anvil_mnemonic() {
+        let config1 = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(1737316800),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        let mut config2 = config1.clone();
+        config2.anvil_mnemonic = Some(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+                .to_string(),
+        );
+
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config2.compute_hash().unwrap(),
+            "Hash should change when the Anvil accounts change"
+        );
+    }
+
+    #[test]
+    fn test_hash_changes_with_custom_intent() {
+        let config1 = DeploymentConfigHash {
+            l1_chain_id: 11155111,
+            l2_chain_id: 42069,
+            fork_url: None,
+            fork_block_number: None,
+            timestamp: Some(1737316800),
+            eip1559_denominator: 50,
+            eip1559_denominator_canyon: 250,
+            eip1559_elasticity: 6,
+            deployment_target: crate::DeploymentTarget::Live,
+            external_l1_rpc_url: None,
+            l2_block_time: None,
+            intent_file_digest: None,
+            keep_intent_roles: false,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            l2_prefund: BTreeMap::new(),
+            anvil_mnemonic: None,
+            anvil_derivation_path: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            l2_hardfork_offsets: BTreeMap::new(),
+        };
+
+        let mut config2 = config1.clone();
+        config2.intent_file_digest = Some("ab".repeat(32));
+        let mut config3 = config2.clone();
+        config3.keep_intent_roles = true;
+
+        assert_ne!(
+            config1.compute_hash().unwrap(),
+            config2.compute_hash().unwrap(),
+            "Hash should change when a custom intent file is used"
+        );
+        assert_ne!(
+            config2.compute_hash().unwrap(),
+            config3.compute_hash().unwrap(),
+            "Hash should change when the intent roles are not patched"
+        );
+    }
+
     #[test]
     fn test_version_save_and_load() {
         let temp_dir = TempDir::new("kupcake-test").expect("Failed to create temp dir");
@@ -313,6 +869,7 @@ mod tests {
             config_hash: "a7f3c2b1d8e5f4a9b2c3d4e5f6a7b8c9".to_string(),
             deployed_at: 1737316800,
             kupcake_version: "0.1.0".to_string(),
+            service_hashes: ServiceHashes::from([("kup-op-batcher".to_string(), "ab".repeat(32))]),
         };
 
         // Save
@@ -354,14 +911,13 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_from_deployer() {
+    fn test_deployer() -> Deployer {
         use crate::{
             AnvilConfig, KupDockerConfig, L2StackBuilder, MonitoringConfig, OpDeployerConfig,
         };
         use std::path::PathBuf;
 
-        let deployer = Deployer {
+        Deployer {
             l1_chain_id: 11155111,
             l2_chain_id: 42069,
             outdata: PathBuf::from("/tmp/test"),
@@ -379,9 +935,20 @@ mod tests {
                 log_max_size: None,
                 log_max_file: None,
                 stream_logs: false,
+                restart_policy: Default::default(),
+                extra_hosts: Vec::new(),
+                ca_bundle: None,
+                proxy: None,
+                rpc_client: Default::default(),
+                run_as_host_user: true,
+                recorded_host_ports: Default::default(),
+                subnet: None,
+                gateway: None,
+                external_network: None,
             },
             l2_stack: L2StackBuilder::default(),
             monitoring: MonitoringConfig::default(),
+            blockscout: Default::default(),
             dashboards_path: None,
             detach: false,
             snapshot: None,
@@ -389,8 +956,20 @@ mod tests {
             deployment_target: crate::DeploymentTarget::Live,
             dump_state: true,
             override_state: None,
-        };
+            external_l1: None,
+            post_deploy: None,
+            min_disk_space_gib: crate::DEFAULT_MIN_DISK_SPACE_GIB,
+            keep_going: false,
+            verify_chain_ids: false,
+            reuse_if_healthy: false,
+            restart_unhealthy: false,
+            additional_l2_chains: Vec::new(),
+        }
+    }
 
+    #[test]
+    fn test_from_deployer() {
+        let deployer = test_deployer();
         let config_hash = DeploymentConfigHash::from_deployer(&deployer);
 
         assert_eq!(config_hash.l1_chain_id, 11155111);
@@ -405,4 +984,34 @@ mod tests {
         assert_eq!(config_hash.eip1559_denominator_canyon, 250);
         assert_eq!(config_hash.eip1559_elasticity, 6);
     }
+
+    #[test]
+    fn test_service_hashes_track_each_service() {
+        let deployer = test_deployer();
+        let hashes = service_hashes(&deployer).unwrap();
+
+        let batcher = deployer.l2_stack.op_batcher.container_name.clone();
+        assert!(hashes.contains_key(&deployer.anvil.container_name));
+        assert!(hashes.contains_key(&batcher));
+        assert!(hashes.contains_key(&deployer.l2_stack.sequencers[0].op_reth.container_name));
+
+        let mut bumped = test_deployer();
+        bumped.l2_stack.op_batcher.docker_image.tag = Some("v9.9.9".to_string());
+        let bumped_hashes = service_hashes(&bumped).unwrap();
+
+        let version = DeploymentVersion {
+            config_hash: String::new(),
+            deployed_at: 0,
+            kupcake_version: String::new(),
+            service_hashes: hashes.clone(),
+        };
+        assert_eq!(version.changed_services(&hashes), Vec::<String>::new());
+        assert_eq!(version.changed_services(&bumped_hashes), vec![batcher]);
+
+        let legacy = DeploymentVersion {
+            service_hashes: ServiceHashes::new(),
+            ..version
+        };
+        assert_eq!(legacy.changed_services(&hashes).len(), hashes.len());
+    }
 }
@@ -0,0 +1,126 @@
+//! Seeded values of the deterministic deployment mode.
+//!
+//! [`DeployerBuilder::deterministic`](crate::DeployerBuilder::deterministic) replaces the
+//! random or time-dependent inputs of a deployment with values derived from a seed, so
+//! that two runs with the same seed and configuration produce byte-identical `genesis.json`,
+//! `rollup.json` and `state.json`, the same contract addresses and the same deployment
+//! version hash, on any machine:
+//!
+//! - the L2 chain ID and network name, unless set explicitly;
+//! - a fixed genesis timestamp ([`DETERMINISTIC_GENESIS_TIMESTAMP`]), unless set explicitly;
+//! - the genesis deployment target, so that the L1 does not depend on wall-clock blocks;
+//! - the P2P keys of the consensus clients (kona-node or op-node), hence their peer IDs.
+//!
+//! Role accounts come from Anvil's mnemonic (the default one unless configured) and are
+//! already stable.
+//!
+//! Not covered, as none of them end up in the generated chain files: container IDs,
+//! OS-assigned host ports, the per-deployment JWT secrets, the op-reth P2P keys, and the
+//! absolute data directory. Docker images referenced by a moving tag (`latest`, `develop`)
+//! may also resolve to different binaries over time; pin release tags or
+//! digests ([`DeployerBuilder::pin_all_digests`](crate::DeployerBuilder::pin_all_digests))
+//! for reproducible runs.
+
+use alloy_core::primitives::keccak256;
+
+use crate::L2StackBuilder;
+
+/// Genesis timestamp of deterministic deployments (2025-01-01T00:00:00Z).
+///
+/// The sequencer catches up from it on startup; set a recent genesis timestamp explicitly
+/// for long-running deterministic networks.
+pub const DETERMINISTIC_GENESIS_TIMESTAMP: u64 = 1_735_689_600;
+
+/// 32 bytes derived from the seed and a label.
+fn seeded_bytes(seed: u64, label: &str) -> [u8; 32] {
+    keccak256(format!("kupcake-deterministic:{}:{}", seed, label)).0
+}
+
+/// L2 chain ID derived from the seed, in the range of the randomly generated ones.
+pub fn seeded_l2_chain_id(seed: u64) -> u64 {
+    let bytes = seeded_bytes(seed, "l2-chain-id");
+    let value = u64::from_be_bytes(bytes[..8].try_into().expect("8 bytes"));
+    10000 + value % 90000
+}
+
+/// Network name derived from the seed.
+pub fn seeded_network_name(seed: u64) -> String {
+    format!("kup-det-{}", seed)
+}
+
+/// P2P secret key (hex) of the container named `container_name`.
+fn seeded_p2p_secret_key(seed: u64, container_name: &str) -> String {
+    hex::encode(seeded_bytes(seed, &format!("p2p:{}", container_name)))
+}
+
+impl L2StackBuilder {
+    /// Derive the P2P keys of the consensus clients that have none from `seed` and the
+    /// container name.
+    ///
+    /// Call this after the consensus client is chosen, so op-node keys are set as well.
+    pub fn p2p_keys_from_seed(mut self, seed: u64) -> Self {
+        for node in self.sequencers.iter_mut().chain(self.validators.iter_mut()) {
+            let kona_node = &mut node.kona_node;
+            kona_node
+                .p2p_secret_key
+                .get_or_insert_with(|| seeded_p2p_secret_key(seed, &kona_node.container_name));
+            if let Some(ref mut op_node) = node.op_node {
+                op_node
+                    .p2p_secret_key
+                    .get_or_insert_with(|| seeded_p2p_secret_key(seed, &op_node.container_name));
+            }
+        }
+        self
+    }
+
+    /// Derive the P2P keys from `seed` if one is given.
+    pub fn maybe_p2p_keys_from_seed(self, seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => self.p2p_keys_from_seed(seed),
+            None => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::kona_node::P2pKeypair;
+
+    #[test]
+    fn test_seeded_values_are_stable() {
+        assert_eq!(seeded_l2_chain_id(42), seeded_l2_chain_id(42));
+        assert_ne!(seeded_l2_chain_id(42), seeded_l2_chain_id(43));
+        assert!((10000..=99999).contains(&seeded_l2_chain_id(42)));
+        assert_eq!(seeded_network_name(42), "kup-det-42");
+    }
+
+    #[test]
+    fn test_p2p_keys_from_seed() {
+        let stack = L2StackBuilder::with_counts(2, 1).p2p_keys_from_seed(7);
+        let again = L2StackBuilder::with_counts(2, 1).p2p_keys_from_seed(7);
+        assert_eq!(stack, again);
+
+        let mut keys: Vec<&String> = stack
+            .sequencers
+            .iter()
+            .chain(&stack.validators)
+            .map(|node| node.kona_node.p2p_secret_key.as_ref().unwrap())
+            .collect();
+        for key in &keys {
+            P2pKeypair::from_private_key(key).unwrap();
+        }
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), 3);
+
+        // Keys already set are kept.
+        let mut stack = L2StackBuilder::with_counts(1, 0);
+        stack.sequencers[0].kona_node.p2p_secret_key = Some("11".repeat(32));
+        let stack = stack.p2p_keys_from_seed(7);
+        assert_eq!(
+            stack.sequencers[0].kona_node.p2p_secret_key,
+            Some("11".repeat(32))
+        );
+    }
+}
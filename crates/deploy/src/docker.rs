@@ -2,10 +2,11 @@
 
 use std::{
     collections::HashMap,
-    collections::HashSet,
+    collections::{BTreeSet, HashSet},
     fs,
     io::Read as _,
     mem,
+    net::IpAddr,
     path::{Path, PathBuf},
     process::Stdio,
     time::Duration,
@@ -23,13 +24,15 @@ use bollard::{
     },
     image::{BuildImageOptions, CreateImageOptions},
     network::CreateNetworkOptions,
-    secret::{HostConfig, PortBinding},
+    secret::{HostConfig, Ipam, IpamConfig, PortBinding},
 };
 use derive_more::Deref;
 use futures::{StreamExt, executor::block_on, future::join_all};
 use tokio::{io::AsyncBufReadExt, task::JoinHandle, time::timeout};
 use url::Url;
 
+use crate::{DeployWarning, rpc::RpcClientConfig};
+
 /// Timeout for shutting down docker and cleaning up containers.
 const DOCKER_DROP_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -70,7 +73,7 @@ pub enum PortProtocol {
 }
 
 impl PortProtocol {
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             PortProtocol::Tcp => "tcp",
             PortProtocol::Udp => "udp",
@@ -168,6 +171,76 @@ impl ExposedPort {
     }
 }
 
+/// Docker restart policy of a service container.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Never restart (Docker default).
+    #[default]
+    No,
+    /// Restart when the container exits with a non-zero code.
+    OnFailure {
+        /// Maximum number of restarts. 0 means no limit.
+        max_retries: u32,
+    },
+    /// Always restart, unless the container was explicitly stopped. Also restarts the
+    /// container when the Docker daemon restarts.
+    UnlessStopped,
+}
+
+impl std::fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartPolicy::No => write!(f, "no"),
+            RestartPolicy::OnFailure { max_retries: 0 } => write!(f, "on-failure"),
+            RestartPolicy::OnFailure { max_retries } => write!(f, "on-failure:{max_retries}"),
+            RestartPolicy::UnlessStopped => write!(f, "unless-stopped"),
+        }
+    }
+}
+
+/// Parse a restart policy in the `docker run --restart` syntax: `no`, `on-failure`,
+/// `on-failure:N` or `unless-stopped`.
+impl std::str::FromStr for RestartPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            None if s == "no" => Ok(RestartPolicy::No),
+            None if s == "on-failure" => Ok(RestartPolicy::OnFailure { max_retries: 0 }),
+            None if s == "unless-stopped" => Ok(RestartPolicy::UnlessStopped),
+            Some(("on-failure", max_retries)) => Ok(RestartPolicy::OnFailure {
+                max_retries: max_retries
+                    .parse()
+                    .with_context(|| format!("Invalid restart count in '{s}'"))?,
+            }),
+            _ => anyhow::bail!(
+                "Invalid restart policy '{s}' (expected no, on-failure[:N] or unless-stopped)"
+            ),
+        }
+    }
+}
+
+impl RestartPolicy {
+    fn to_bollard(self) -> bollard::models::RestartPolicy {
+        use bollard::models::RestartPolicyNameEnum;
+
+        let (name, maximum_retry_count) = match self {
+            RestartPolicy::No => (RestartPolicyNameEnum::NO, None),
+            RestartPolicy::OnFailure { max_retries } => {
+                (RestartPolicyNameEnum::ON_FAILURE, Some(max_retries as i64))
+            }
+            RestartPolicy::UnlessStopped => (RestartPolicyNameEnum::UNLESS_STOPPED, None),
+        };
+        bollard::models::RestartPolicy {
+            name: Some(name),
+            maximum_retry_count,
+        }
+    }
+}
+
 /// Configuration for starting a service container.
 #[derive(Debug, Clone)]
 pub struct ServiceConfig {
@@ -189,8 +262,25 @@ pub struct ServiceConfig {
     pub env: Option<Vec<String>>,
     /// User to run the container as (e.g., "1000:1000" for UID:GID).
     pub user: Option<String>,
+    /// Restart policy. None uses the deployment-wide policy of [`KupDockerConfig`].
+    pub restart_policy: Option<RestartPolicy>,
+    /// Extra `/etc/hosts` entries (`host:ip` format), added to the deployment-wide ones.
+    pub extra_hosts: Vec<String>,
+    /// Linux capabilities to add (e.g. `NET_ADMIN`).
+    pub cap_add: Vec<String>,
+    /// Linux capabilities to drop.
+    pub cap_drop: Vec<String>,
+    /// Run the container in privileged mode, with all capabilities and host devices.
+    pub privileged: bool,
+    /// Network mode overriding the deployment network, e.g. `container:<name>` to share
+    /// the network namespace of another container. Port publishing and extra hosts are
+    /// not applied in that case, as Docker rejects them.
+    pub network_mode: Option<String>,
 }
 
+/// Extra host entry resolving `host.docker.internal` to the host, on all platforms.
+pub const HOST_GATEWAY_ENTRY: &str = "host.docker.internal:host-gateway";
+
 impl ServiceConfig {
     /// Create a new service config with the given image.
     pub fn new(image: DockerImage) -> Self {
@@ -203,6 +293,12 @@ impl ServiceConfig {
             binds: Vec::new(),
             env: None,
             user: None,
+            restart_policy: None,
+            extra_hosts: Vec::new(),
+            cap_add: Vec::new(),
+            cap_drop: Vec::new(),
+            privileged: false,
+            network_mode: None,
         }
     }
 
@@ -218,6 +314,36 @@ impl ServiceConfig {
         self
     }
 
+    /// Add Linux capabilities (e.g. `NET_ADMIN`).
+    pub fn cap_add(mut self, capabilities: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.cap_add
+            .extend(capabilities.into_iter().map(Into::into));
+        self
+    }
+
+    /// Drop Linux capabilities.
+    pub fn cap_drop(mut self, capabilities: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.cap_drop
+            .extend(capabilities.into_iter().map(Into::into));
+        self
+    }
+
+    /// Run the container in privileged mode.
+    ///
+    /// Gives the container every capability and access to the host devices; prefer
+    /// [`Self::cap_add`] with the capabilities actually needed.
+    pub fn privileged(mut self, privileged: bool) -> Self {
+        self.privileged = privileged;
+        self
+    }
+
+    /// Share the network namespace of another container instead of joining the
+    /// deployment network.
+    pub fn network_of(mut self, container_name: &str) -> Self {
+        self.network_mode = Some(format!("container:{}", container_name));
+        self
+    }
+
     /// Add a port to expose within the Docker network (container-to-container).
     pub fn expose(mut self, port: ExposedPort) -> Self {
         self.exposed_ports.push(port);
@@ -270,6 +396,31 @@ impl ServiceConfig {
         self.user = Some(user.into());
         self
     }
+
+    /// Set the user to run the container as, if one is given.
+    pub fn maybe_user(mut self, user: Option<String>) -> Self {
+        if let Some(user) = user {
+            self.user = Some(user);
+        }
+        self
+    }
+
+    /// Set the restart policy, overriding the deployment-wide one.
+    pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = Some(policy);
+        self
+    }
+
+    /// Add extra `/etc/hosts` entries (`host:ip` format).
+    pub fn extra_hosts(mut self, hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.extra_hosts.extend(hosts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Make the host reachable from the container as `host.docker.internal`.
+    pub fn host_gateway(self) -> Self {
+        self.extra_hosts([HOST_GATEWAY_ENTRY])
+    }
 }
 
 /// Handler returned after starting a service.
@@ -297,6 +448,11 @@ impl ServiceHandler {
         self.get_host_port(container_port, "tcp")
     }
 
+    /// Get the bound host port for a UDP container port.
+    pub fn get_udp_host_port(&self, container_port: u16) -> Option<u16> {
+        self.get_host_port(container_port, "udp")
+    }
+
     /// Build a host-accessible URL from the bound port for a given container port.
     ///
     /// Returns `Ok(None)` if the port is not published to the host.
@@ -312,7 +468,48 @@ impl ServiceHandler {
     }
 }
 
-/// A Docker image reference with image name and tag.
+/// Error building the URL of a container with [`KupDocker::build_container_url`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum ContainerUrlError {
+    /// The container name is not a valid Docker DNS name.
+    #[display("container name {name:?} is not a valid Docker DNS name: {reason}")]
+    InvalidContainerName { name: String, reason: &'static str },
+    /// The URL built from the scheme, name, port and path does not parse.
+    #[display("invalid container URL {url:?}: {reason}")]
+    InvalidUrl { url: String, reason: String },
+}
+
+impl std::error::Error for ContainerUrlError {}
+
+/// Check that a container name resolves through Docker's embedded DNS.
+///
+/// Each dot-separated label holds 1 to 63 alphanumerics, `-` or `_` and does not start
+/// or end with `-`.
+fn validate_dns_name(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        return Err("name is empty");
+    }
+    if name.len() > 253 {
+        return Err("name is longer than 253 characters");
+    }
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err("each label must be 1 to 63 characters long");
+        }
+        if !label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err("only alphanumerics, '-', '_' and '.' are allowed");
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err("labels cannot start or end with '-'");
+        }
+    }
+    Ok(())
+}
+
+/// A Docker image reference with image name and tag or digest.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct DockerImage {
     /// The image name (e.g., "ghcr.io/foundry-rs/foundry").
@@ -321,6 +518,10 @@ pub struct DockerImage {
     /// The image tag (e.g., "latest" or "v1.0.0").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag: Option<String>,
+    /// Content digest (`sha256:<hex>`) pinning the image. Takes precedence over the tag,
+    /// which is then only informative.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
     /// Path to local binary or source directory (takes precedence over image/tag).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub binary: Option<PathBuf>,
@@ -336,9 +537,37 @@ impl DockerImage {
         Self {
             image: Some(image.into()),
             tag: Some(tag.into()),
+            digest: None,
+            binary: None,
+            bin_name: None,
+        }
+    }
+
+    /// Create a DockerImage pinned to a content digest, e.g. `sha256:3f5a...`.
+    ///
+    /// The `sha256:` prefix is optional.
+    pub fn from_digest(image: impl Into<String>, digest: impl AsRef<str>) -> Self {
+        Self {
+            image: Some(image.into()),
+            tag: None,
+            digest: None,
             binary: None,
             bin_name: None,
         }
+        .with_digest(digest)
+    }
+
+    /// Pin this image to a content digest, keeping its tag for display.
+    ///
+    /// The digest is what gets pulled and run. The `sha256:` prefix is optional.
+    pub fn with_digest(mut self, digest: impl AsRef<str>) -> Self {
+        let digest = digest.as_ref();
+        self.digest = Some(if digest.starts_with(DIGEST_PREFIX) {
+            digest.to_string()
+        } else {
+            format!("{}{}", DIGEST_PREFIX, digest)
+        });
+        self
     }
 
     /// Create a DockerImage from a local binary path.
@@ -346,6 +575,7 @@ impl DockerImage {
         Self {
             image: None,
             tag: None,
+            digest: None,
             binary: Some(path.into()),
             bin_name: None,
         }
@@ -358,6 +588,7 @@ impl DockerImage {
         Self {
             image: None,
             tag: None,
+            digest: None,
             binary: Some(path.into()),
             bin_name: Some(bin_name.into()),
         }
@@ -373,15 +604,49 @@ impl DockerImage {
         self.binary.as_deref()
     }
 
-    /// Get the image reference string (image:tag).
+    /// Get the image reference string (`image@digest` if pinned, `image:tag` otherwise).
     /// Panics if called on a local binary image (use ensure_image_ready instead).
     pub fn image_ref(&self) -> String {
-        match (&self.image, &self.tag) {
-            (Some(image), Some(tag)) => format!("{}:{}", image, tag),
+        match (&self.image, &self.digest, &self.tag) {
+            (Some(image), Some(digest), _) => format!("{}@{}", image, digest),
+            (Some(image), None, Some(tag)) => format!("{}:{}", image, tag),
             _ => panic!("image_ref() called on local binary DockerImage"),
         }
     }
 
+    /// Pin the image to the digest its tag currently resolves to, pulling it if needed.
+    ///
+    /// Does nothing for local binaries and images that are already pinned.
+    pub async fn pin_digest(&mut self, docker: &Docker) -> Result<()> {
+        if self.is_local_binary() || self.digest.is_some() {
+            return Ok(());
+        }
+        let image = self.image.as_ref().context("Missing image name")?;
+        let tag = self.tag.as_ref().context("Missing image tag")?;
+        let image_ref = pull_image(docker, image, tag).await?;
+
+        let inspect = docker
+            .inspect_image(&image_ref)
+            .await
+            .with_context(|| format!("Failed to inspect image '{}'", image_ref))?;
+        // Images built locally have no repo digest and cannot be pinned.
+        let digest = inspect
+            .repo_digests
+            .unwrap_or_default()
+            .iter()
+            .find_map(|repo_digest| repo_digest.split_once('@').map(|(_, d)| d.to_string()))
+            .with_context(|| {
+                format!(
+                    "Image '{}' has no registry digest; only pulled images can be pinned",
+                    image_ref
+                )
+            })?;
+
+        tracing::debug!(image = %image_ref, %digest, "Pinned image digest");
+        self.digest = Some(digest);
+        Ok(())
+    }
+
     /// Pull the image, ensuring it is available locally.
     ///
     /// This will check if the image exists locally and pull it if necessary.
@@ -391,15 +656,34 @@ impl DockerImage {
             anyhow::bail!("Cannot pull a local binary image. Use ensure_image_ready() instead.");
         }
         let image = self.image.as_ref().context("Missing image name")?;
-        let tag = self.tag.as_ref().context("Missing image tag")?;
-        docker.pull_image(image, tag).await
+        let reference = self
+            .digest
+            .as_ref()
+            .or(self.tag.as_ref())
+            .context("Missing image tag")?;
+        docker.pull_image(image, reference).await
     }
 }
 
+/// Prefix of the content digests of Docker images.
+const DIGEST_PREFIX: &str = "sha256:";
+
+/// Check that `digest` is a `sha256:` content digest.
+pub fn is_valid_digest(digest: &str) -> bool {
+    digest.strip_prefix(DIGEST_PREFIX).is_some_and(|hex| {
+        hex.len() == 64 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+    })
+}
+
 impl std::fmt::Display for DockerImage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(binary) = &self.binary {
             write!(f, "local:{}", binary.display())
+        } else if let (Some(image), Some(digest)) = (&self.image, &self.digest) {
+            match self.tag {
+                Some(ref tag) => write!(f, "{}:{}@{}", image, tag, digest),
+                None => write!(f, "{}@{}", image, digest),
+            }
         } else if let (Some(image), Some(tag)) = (&self.image, &self.tag) {
             write!(f, "{}:{}", image, tag)
         } else {
@@ -427,6 +711,219 @@ pub struct KupDockerConfig {
     /// Stream container logs to tracing::debug!() in background.
     #[serde(default)]
     pub stream_logs: bool,
+    /// Restart policy of the service containers. One-shot containers (op-deployer,
+    /// commands) are never restarted.
+    #[serde(default, skip_serializing_if = "is_default_restart_policy")]
+    pub restart_policy: RestartPolicy,
+    /// Extra `/etc/hosts` entries (`host:ip` format) added to every container, e.g.
+    /// [`HOST_GATEWAY_ENTRY`] to reach services running on the host.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_hosts: Vec<String>,
+    /// PEM bundle of extra CA certificates, mounted into every container at
+    /// [`CA_BUNDLE_CONTAINER_PATH`] and trusted by the crate's own RPC client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+    /// Outbound proxy passed to every container and used by the crate's own RPC client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyConfig>,
+    /// Timeouts and connection pool of the crate's own RPC client.
+    #[serde(default, skip_serializing_if = "RpcClientConfig::is_default")]
+    pub rpc_client: RpcClientConfig,
+    /// Run the stateful services (Anvil, op-reth, the consensus clients, op-conductor and
+    /// op-challenger) as the owner of their data directory, so that the files they
+    /// create stay accessible from the host. Defaults to true on unix.
+    #[serde(default = "default_run_as_host_user")]
+    pub run_as_host_user: bool,
+    /// Host ports picked by Docker and recorded after a deployment (see
+    /// [`Deployer::persist_host_ports`](crate::Deployer::persist_host_ports)). When one of
+    /// them is already in use, the service is published on a Docker-assigned port, with a
+    /// warning, instead of failing. Explicit and fixed host ports never move.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub recorded_host_ports: BTreeSet<u16>,
+    /// Subnet (CIDR) of the created network, e.g. to stay clear of a range routed
+    /// through a VPN. Picked by Docker when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subnet: Option<String>,
+    /// Gateway of the created network, inside [`Self::subnet`]. Picked by Docker when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    /// Pre-existing Docker network to attach the containers to instead of creating
+    /// [`Self::net_name`]. It is neither created nor removed by kupcake.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_network: Option<String>,
+}
+
+impl KupDockerConfig {
+    /// Docker network the containers are attached to: [`Self::external_network`] when
+    /// set, [`Self::net_name`] otherwise.
+    pub fn attached_network(&self) -> &str {
+        self.external_network.as_deref().unwrap_or(&self.net_name)
+    }
+}
+
+/// Parse a CIDR subnet (`10.10.0.0/16`, `fd00::/64`) into its address and prefix length.
+pub fn parse_cidr(cidr: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = cidr.split_once('/')?;
+    let addr: IpAddr = addr.parse().ok()?;
+    let prefix: u8 = prefix.parse().ok()?;
+    let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+    (prefix <= max_prefix).then_some((addr, prefix))
+}
+
+/// Whether `ip` belongs to the subnet `(network, prefix)` returned by [`parse_cidr`].
+pub fn subnet_contains((network, prefix): (IpAddr, u8), ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Default of [`KupDockerConfig::run_as_host_user`].
+pub fn default_run_as_host_user() -> bool {
+    cfg!(unix)
+}
+
+/// `uid:gid` owning `host_path`. None on platforms without Unix ownership.
+pub fn host_path_owner(host_path: &Path) -> Result<Option<String>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = std::fs::metadata(host_path)
+            .with_context(|| format!("Failed to get metadata for {}", host_path.display()))?;
+        Ok(Some(format!("{}:{}", metadata.uid(), metadata.gid())))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = host_path;
+        Ok(None)
+    }
+}
+
+/// Outbound HTTP(S) or SOCKS proxy, for networks without direct internet access.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ProxyConfig {
+    /// Proxy URL for `http://` requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_proxy: Option<String>,
+    /// Proxy URL for `https://` requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    /// Hosts reached without the proxy, in `NO_PROXY` format. The deployment's own
+    /// containers and `localhost` are always exempt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_proxy: Vec<String>,
+}
+
+impl ProxyConfig {
+    /// Read the proxy from the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment
+    /// variables (or their lowercase forms). Returns `None` when no proxy is set.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name.to_uppercase())
+                .or_else(|_| std::env::var(name))
+                .ok()
+                .filter(|value| !value.is_empty())
+        };
+        let proxy = Self {
+            http_proxy: var("http_proxy"),
+            https_proxy: var("https_proxy"),
+            no_proxy: var("no_proxy")
+                .map(|hosts| Self::split_hosts(&hosts))
+                .unwrap_or_default(),
+        };
+        (proxy.http_proxy.is_some() || proxy.https_proxy.is_some()).then_some(proxy)
+    }
+
+    /// Split a comma-separated `NO_PROXY` list.
+    pub fn split_hosts(hosts: &str) -> Vec<String> {
+        hosts
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// The `NO_PROXY` list: the configured hosts, `localhost` and `internal_hosts`.
+    pub fn no_proxy_list(&self, internal_hosts: &[String]) -> String {
+        let mut hosts: Vec<&str> = vec!["localhost", "127.0.0.1"];
+        for host in self.no_proxy.iter().chain(internal_hosts) {
+            if !hosts.contains(&host.as_str()) {
+                hosts.push(host);
+            }
+        }
+        hosts.join(",")
+    }
+
+    /// Proxy environment of a container. Both the uppercase and lowercase variables are
+    /// set, as tools disagree on which one they read.
+    fn container_env(&self, internal_hosts: &[String]) -> Vec<String> {
+        let no_proxy = self.no_proxy_list(internal_hosts);
+        [
+            ("HTTP_PROXY", self.http_proxy.as_deref()),
+            ("HTTPS_PROXY", self.https_proxy.as_deref()),
+            ("NO_PROXY", Some(no_proxy.as_str())),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .flat_map(|(name, value)| {
+            [
+                format!("{}={}", name, value),
+                format!("{}={}", name.to_lowercase(), value),
+            ]
+        })
+        .collect()
+    }
+
+    /// Check that every configured proxy accepts TCP connections.
+    pub async fn check_connectivity(&self) -> Result<()> {
+        for proxy in [&self.http_proxy, &self.https_proxy].into_iter().flatten() {
+            let url = Url::parse(proxy).with_context(|| format!("Invalid proxy URL {}", proxy))?;
+            let host = url
+                .host_str()
+                .with_context(|| format!("Proxy URL {} has no host", proxy))?;
+            let port = url.port_or_known_default().unwrap_or(1080);
+
+            timeout(
+                Duration::from_secs(5),
+                tokio::net::TcpStream::connect((host, port)),
+            )
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|connected| connected.map_err(anyhow::Error::from))
+            .with_context(|| format!("Cannot connect to proxy {}", proxy))?;
+        }
+        Ok(())
+    }
+}
+
+/// Path at which [`KupDockerConfig::ca_bundle`] is mounted inside the containers.
+pub const CA_BUNDLE_CONTAINER_PATH: &str = "/etc/kupcake/ca.pem";
+
+/// Mount `ca_bundle` into the container and point the TLS stacks of the services at it:
+/// `SSL_CERT_FILE` for the Go (op-*) and OpenSSL based clients, `NODE_EXTRA_CA_CERTS`
+/// for Node.js tooling.
+fn apply_ca_bundle(config: &mut ServiceConfig, ca_bundle: &Path) {
+    config.binds.push(format!(
+        "{}:{}:ro",
+        ca_bundle.display(),
+        CA_BUNDLE_CONTAINER_PATH
+    ));
+    config.env.get_or_insert_with(Vec::new).extend([
+        format!("SSL_CERT_FILE={}", CA_BUNDLE_CONTAINER_PATH),
+        format!("NODE_EXTRA_CA_CERTS={}", CA_BUNDLE_CONTAINER_PATH),
+    ]);
+}
+
+fn is_default_restart_policy(policy: &RestartPolicy) -> bool {
+    *policy == RestartPolicy::No
 }
 
 /// Configuration for dumping Anvil state before cleanup.
@@ -455,11 +952,22 @@ pub struct KupDocker {
 
     pub config: KupDockerConfig,
 
+    /// RPC client of the deployment, built from [`KupDockerConfig::rpc_client`], its CA
+    /// bundle and proxy. Cheap to clone; clones share the connection pool.
+    pub rpc_client: reqwest::Client,
+
     /// If set, dump Anvil state via RPC before stopping containers during cleanup.
     pub anvil_state_dump: Option<AnvilStateDumpConfig>,
 
     /// Network name for devnet registry tracking (set after successful deployment).
     pub registry_name: Option<String>,
+
+    /// Container names of the deployment, exempt from [`KupDockerConfig::proxy`].
+    pub internal_hosts: Vec<String>,
+
+    /// Warnings raised while starting containers, moved into
+    /// [`DeploymentResult::warnings`](crate::DeploymentResult) at the end of the deployment.
+    pub warnings: Vec<DeployWarning>,
 }
 
 pub struct CreateAndStartContainerResult {
@@ -515,6 +1023,11 @@ impl Drop for KupDocker {
                 .into_iter()
                 .collect::<Result<Vec<_>>>()?;
 
+            // An external network belongs to whoever created it
+            if self.config.external_network.is_some() {
+                return Ok(());
+            }
+
             // Only remove the network if no other containers are still using it.
             // This prevents a secondary KupDocker instance (e.g. from `kupcake spam`)
             // from tearing down the network that the devnet containers rely on.
@@ -584,63 +1097,72 @@ impl Drop for KupDocker {
     }
 }
 
-impl KupDocker {
-    const STOP_CONTAINER_TIMEOUT: Duration = Duration::from_secs(5);
+/// Pull `image` at `reference` (a tag or a `sha256:` digest) unless it is available
+/// locally. Returns the full image reference.
+async fn pull_image(docker: &Docker, image: &str, reference: &str) -> Result<String> {
+    let full_image = if reference.starts_with(DIGEST_PREFIX) {
+        format!("{}@{}", image, reference)
+    } else {
+        format!("{}:{}", image, reference)
+    };
 
-    pub async fn pull_image(&self, image: &str, tag: &str) -> Result<String> {
-        let full_image = format!("{}:{}", image, tag);
+    // Check if image is already available locally
+    if docker.inspect_image(&full_image).await.is_ok() {
+        tracing::debug!(image = %full_image, "Image already available locally, skipping pull");
+        return Ok(full_image);
+    }
 
-        // Check if image is already available locally
-        if self.docker.inspect_image(&full_image).await.is_ok() {
-            tracing::debug!(image = %full_image, "Image already available locally, skipping pull");
-            return Ok(full_image);
+    tracing::debug!(image = %full_image, "Image not found locally, pulling...");
+
+    let pull = || async {
+        let mut stream = docker.create_image(
+            Some(CreateImageOptions {
+                from_image: image.to_string(),
+                tag: reference.to_string(),
+                ..Default::default()
+            }),
+            None,
+            None,
+        );
+
+        while let Some(result) = stream.next().await
+            && let Some(status) = result
+                .map_err(|e| anyhow::anyhow!("Failed to pull image '{}': {}", full_image, e))?
+                .status
+        {
+            tracing::trace!(status, "Image pull");
         }
 
-        tracing::debug!(image = %full_image, "Image not found locally, pulling...");
+        Ok::<(), anyhow::Error>(())
+    };
 
-        let docker = &self.docker;
-        let pull = || async {
-            let mut stream = docker.create_image(
-                Some(CreateImageOptions {
-                    from_image: image.to_string(),
-                    tag: tag.to_string(),
-                    ..Default::default()
-                }),
-                None,
-                None,
+    let backoff = backon::ExponentialBuilder::default()
+        .with_min_delay(std::time::Duration::from_secs(2))
+        .with_max_delay(std::time::Duration::from_secs(15))
+        .with_max_times(3);
+
+    pull.retry(backoff)
+        .notify(|err, dur| {
+            tracing::warn!(
+                image = %full_image,
+                error = %err,
+                retry_in = ?dur,
+                "Docker image pull failed, retrying..."
             );
+        })
+        .await
+        .with_context(|| format!("Failed to pull image '{}' after retries", full_image))?;
 
-            while let Some(result) = stream.next().await
-                && let Some(status) = result
-                    .map_err(|e| {
-                        anyhow::anyhow!("Failed to pull image '{}:{}': {}", image, tag, e)
-                    })?
-                    .status
-            {
-                tracing::trace!(status, "Image pull");
-            }
-
-            Ok::<(), anyhow::Error>(())
-        };
+    Ok(full_image)
+}
 
-        let backoff = backon::ExponentialBuilder::default()
-            .with_min_delay(std::time::Duration::from_secs(2))
-            .with_max_delay(std::time::Duration::from_secs(15))
-            .with_max_times(3);
-
-        pull.retry(backoff)
-            .notify(|err, dur| {
-                tracing::warn!(
-                    image = %full_image,
-                    error = %err,
-                    retry_in = ?dur,
-                    "Docker image pull failed, retrying..."
-                );
-            })
-            .await
-            .with_context(|| format!("Failed to pull image '{}' after retries", full_image))?;
+impl KupDocker {
+    const STOP_CONTAINER_TIMEOUT: Duration = Duration::from_secs(5);
 
-        Ok(full_image)
+    /// Pull `image` at `reference` (a tag or a `sha256:` digest) unless it is available
+    /// locally. Returns the full image reference.
+    pub async fn pull_image(&self, image: &str, reference: &str) -> Result<String> {
+        pull_image(&self.docker, image, reference).await
     }
 
     /// Build a Docker image from a local binary file.
@@ -1061,23 +1583,48 @@ ENTRYPOINT [\"/binary\"]
         let docker = Docker::connect_with_local_defaults()
             .context("Failed to connect to Docker. Is Docker running?")?;
 
-        let network_id = Self::create_network(&docker, &config.net_name).await?;
+        let rpc_client = crate::rpc::build_client(
+            &config.rpc_client,
+            config.ca_bundle.as_deref(),
+            config.proxy.as_ref(),
+        )?;
+
+        let network_id = match config.external_network {
+            Some(ref name) => Self::external_network_id(&docker, name).await?,
+            None => {
+                Self::create_network(
+                    &docker,
+                    &config.net_name,
+                    config.subnet.as_deref(),
+                    config.gateway.as_deref(),
+                )
+                .await?
+            }
+        };
 
         Ok(Self {
             docker,
             config,
+            rpc_client,
             network_id,
             containers: HashSet::new(),
             anvil_state_dump: None,
             registry_name: None,
+            internal_hosts: Vec::new(),
+            warnings: Vec::new(),
         })
     }
 
     /// Create a Docker network for container communication.
     ///
     /// If the network already exists, this function will use the existing network
-    /// instead of failing.
-    pub async fn create_network(docker: &Docker, network_name: &str) -> Result<String> {
+    /// instead of failing. `subnet` and `gateway` only apply to a newly created network.
+    pub async fn create_network(
+        docker: &Docker,
+        network_name: &str,
+        subnet: Option<&str>,
+        gateway: Option<&str>,
+    ) -> Result<String> {
         tracing::info!("Creating Docker network: {}", network_name);
 
         // First, check if the network already exists
@@ -1096,10 +1643,28 @@ ENTRYPOINT [\"/binary\"]
             }
         }
 
+        let ipam = if subnet.is_some() || gateway.is_some() {
+            Ipam {
+                config: Some(vec![IpamConfig {
+                    subnet: subnet.map(str::to_string),
+                    gateway: gateway.map(str::to_string),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }
+        } else {
+            Ipam::default()
+        };
+        let enable_ipv6 = subnet
+            .and_then(parse_cidr)
+            .is_some_and(|(addr, _)| addr.is_ipv6());
+
         let create_network_options = CreateNetworkOptions {
             name: network_name.to_string(),
             check_duplicate: true,
             driver: "bridge".to_string(),
+            ipam,
+            enable_ipv6,
             ..Default::default()
         };
 
@@ -1120,6 +1685,21 @@ ENTRYPOINT [\"/binary\"]
         Ok(network_id)
     }
 
+    /// ID of the pre-existing network `network_name`, see [`KupDockerConfig::external_network`].
+    async fn external_network_id(docker: &Docker, network_name: &str) -> Result<String> {
+        let network_info = docker
+            .inspect_network::<String>(network_name, None)
+            .await
+            .with_context(|| format!("External Docker network '{}' not found", network_name))?;
+        let network_id = network_info.id.unwrap_or_else(|| network_name.to_string());
+        tracing::info!(
+            network_id = %network_id,
+            network_name = %network_name,
+            "Using external Docker network"
+        );
+        Ok(network_id)
+    }
+
     /// Wait for a container to complete and return its exit code.
     ///
     /// This method blocks until the container exits and returns the exit code.
@@ -1216,6 +1796,26 @@ ENTRYPOINT [\"/binary\"]
     /// If the container exits on its own, checks the exit code and returns an error
     /// if non-zero.
     pub async fn stream_logs(&self, container_name: &str) -> Result<()> {
+        let Some(exit_code) = self.follow_logs(container_name).await? else {
+            return Ok(());
+        };
+
+        if exit_code != 0 {
+            anyhow::bail!(
+                "Container {} exited with code {}",
+                container_name,
+                exit_code
+            );
+        }
+
+        tracing::info!(container_name, "Container completed successfully");
+        Ok(())
+    }
+
+    /// Stream container logs to stdout until the container exits or Ctrl+C is received.
+    ///
+    /// Returns the exit code of the container, or `None` if Ctrl+C was received first.
+    pub async fn follow_logs(&self, container_name: &str) -> Result<Option<i64>> {
         let log_options = LogsOptions::<String> {
             stdout: true,
             stderr: true,
@@ -1250,27 +1850,16 @@ ENTRYPOINT [\"/binary\"]
         }
 
         if cancelled {
-            return Ok(());
+            return Ok(None);
         }
 
-        // Container exited on its own — check exit code
+        // Container exited on its own — read its exit code
         let inspect = self
             .inspect_container(container_name, None)
             .await
             .context("Failed to inspect container")?;
 
-        let exit_code = inspect.state.and_then(|s| s.exit_code).unwrap_or(-1);
-
-        if exit_code != 0 {
-            anyhow::bail!(
-                "Container {} exited with code {}",
-                container_name,
-                exit_code
-            );
-        }
-
-        tracing::info!(container_name, "Container completed successfully");
-        Ok(())
+        Ok(Some(inspect.state.and_then(|s| s.exit_code).unwrap_or(-1)))
     }
 
     /// Create and start a container.
@@ -1280,6 +1869,16 @@ ENTRYPOINT [\"/binary\"]
         config: Config<String>,
         options: CreateAndStartContainerOptions,
     ) -> Result<CreateAndStartContainerResult> {
+        // A container left stopped by `kupcake stop` is recreated: its data is on the host.
+        if self.get_container_state(container_name).await == ContainerState::Stopped {
+            tracing::debug!(
+                container_name,
+                "Removing stopped container before recreating it"
+            );
+            Self::stop_and_remove_container_static(&self.docker, &container_name.to_string())
+                .await?;
+        }
+
         tracing::trace!(container_name, "Creating container");
         // Create the container
         let container = self
@@ -1526,10 +2125,20 @@ ENTRYPOINT [\"/binary\"]
     /// Build Docker container configuration from a ServiceConfig.
     fn build_container_config(
         &self,
-        config: ServiceConfig,
+        mut config: ServiceConfig,
         image: String,
         options: ContainerConfigOptions,
     ) -> Config<String> {
+        if let Some(ref ca_bundle) = self.config.ca_bundle {
+            apply_ca_bundle(&mut config, ca_bundle);
+        }
+        if let Some(ref proxy) = self.config.proxy {
+            config
+                .env
+                .get_or_insert_with(Vec::new)
+                .extend(proxy.container_env(&self.internal_hosts));
+        }
+
         // Build port bindings from the port_bindings (ports published to host)
         // When host_port is 0, we pass an empty string to let Docker assign a random available port
         let port_bindings: HashMap<String, Option<Vec<PortBinding>>> = config
@@ -1567,16 +2176,27 @@ ENTRYPOINT [\"/binary\"]
                 .or_default();
         }
 
-        let has_port_bindings = !port_bindings.is_empty();
-        let has_exposed_ports = !exposed_ports.is_empty();
+        let shared_network = config.network_mode.is_some();
+        let has_port_bindings = !port_bindings.is_empty() && !shared_network;
+        let has_exposed_ports = !exposed_ports.is_empty() && !shared_network;
 
         let host_config = HostConfig {
             port_bindings: has_port_bindings.then_some(port_bindings),
             binds: (!config.binds.is_empty()).then_some(config.binds),
-            network_mode: Some(self.network_id.clone()),
+            network_mode: Some(
+                config
+                    .network_mode
+                    .unwrap_or_else(|| self.network_id.clone()),
+            ),
             auto_remove: options.auto_remove.then_some(true),
-            publish_all_ports: self.config.publish_all_ports.then_some(true),
+            publish_all_ports: (self.config.publish_all_ports && !shared_network).then_some(true),
             log_config: self.build_log_config(),
+            restart_policy: config.restart_policy.map(RestartPolicy::to_bollard),
+            extra_hosts: (!config.extra_hosts.is_empty() && !shared_network)
+                .then_some(config.extra_hosts),
+            cap_add: (!config.cap_add.is_empty()).then_some(config.cap_add),
+            cap_drop: (!config.cap_drop.is_empty()).then_some(config.cap_drop),
+            privileged: config.privileged.then_some(true),
             ..Default::default()
         };
 
@@ -1609,6 +2229,42 @@ ENTRYPOINT [\"/binary\"]
             .ensure_image_ready(&config.image, container_name)
             .await?;
 
+        let mut extra_hosts = self.config.extra_hosts.clone();
+        for host in config.extra_hosts.iter() {
+            if !extra_hosts.contains(host) {
+                extra_hosts.push(host.clone());
+            }
+        }
+        let config = ServiceConfig {
+            restart_policy: config.restart_policy.or(Some(self.config.restart_policy)),
+            extra_hosts,
+            ..config
+        };
+
+        if config.privileged {
+            tracing::warn!(
+                container_name,
+                "Starting container in privileged mode: it has full access to the host"
+            );
+        }
+
+        // Fixed host ports stay reserved until the container owns them.
+        let mut config = config;
+        let (_port_reservation, moved_ports) = crate::ports::reserve_host_ports(
+            &mut config.port_bindings,
+            &self.config.recorded_host_ports,
+        )
+        .await
+        .with_context(|| format!("Cannot publish the ports of {}", container_name))?;
+        for port in moved_ports {
+            let warning = DeployWarning::HostPortMoved {
+                service: container_name.to_string(),
+                port,
+            };
+            tracing::warn!("{}", warning);
+            self.warnings.push(warning);
+        }
+
         let container_config =
             self.build_container_config(config, image, ContainerConfigOptions::default());
 
@@ -1646,18 +2302,51 @@ ENTRYPOINT [\"/binary\"]
         })
     }
 
+    /// User a stateful service storing its data in `host_path` runs as: the owner of
+    /// `host_path` if [`KupDockerConfig::run_as_host_user`] is set, None to keep the image
+    /// default.
+    pub fn service_user(&self, host_path: &Path) -> Result<Option<String>> {
+        if !self.config.run_as_host_user {
+            return Ok(None);
+        }
+        host_path_owner(host_path)
+    }
+
     /// Build an HTTP RPC URL for a container.
     ///
     /// The URL uses the container name as the hostname (for Docker network communication).
-    pub fn build_http_url(container_name: &str, port: u16) -> Result<Url> {
-        Url::parse(&format!("http://{}:{}/", container_name, port))
-            .context("Failed to parse HTTP URL")
+    pub fn build_http_url(container_name: &str, port: u16) -> Result<Url, ContainerUrlError> {
+        Self::build_container_url("http", container_name, port, "/")
     }
 
     /// Build a WebSocket RPC URL for a container.
-    pub fn build_ws_url(container_name: &str, port: u16) -> Result<Url> {
-        Url::parse(&format!("ws://{}:{}/", container_name, port))
-            .context("Failed to parse WebSocket URL")
+    pub fn build_ws_url(container_name: &str, port: u16) -> Result<Url, ContainerUrlError> {
+        Self::build_container_url("ws", container_name, port, "/")
+    }
+
+    /// Build a URL reaching `port` of a container over the Docker network, with any
+    /// scheme and path (e.g. `https` or `/rpc`).
+    ///
+    /// Fails if the container name cannot be resolved by Docker's embedded DNS.
+    pub fn build_container_url(
+        scheme: &str,
+        container_name: &str,
+        port: u16,
+        path: &str,
+    ) -> Result<Url, ContainerUrlError> {
+        validate_dns_name(container_name).map_err(|reason| {
+            ContainerUrlError::InvalidContainerName {
+                name: container_name.to_string(),
+                reason,
+            }
+        })?;
+
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let url = format!("{}://{}:{}/{}", scheme, container_name, port, path);
+        Url::parse(&url).map_err(|e| ContainerUrlError::InvalidUrl {
+            url,
+            reason: e.to_string(),
+        })
     }
 
     /// Run a command in a temporary container and capture its stdout.
@@ -1750,18 +2439,16 @@ struct ContainerConfigOptions {
     auto_remove: bool,
 }
 
-/// Clean up containers and network by name prefix.
-///
-/// This is a standalone function that doesn't require a `KupDocker` instance.
-/// It finds all containers whose names start with the given prefix, stops and removes them,
-/// then removes the associated network.
-pub async fn cleanup_by_prefix(prefix: &str) -> Result<CleanupResult> {
-    let docker =
-        Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
-
-    let mut result = CleanupResult::default();
+/// A container whose name starts with a prefix, see [`containers_by_prefix`].
+struct PrefixedContainer {
+    id: String,
+    name: String,
+    /// Docker state, e.g. `running` or `exited`.
+    state: Option<String>,
+}
 
-    // List all containers (including stopped ones) that match the prefix
+/// Containers (including stopped ones) whose name starts with `prefix`.
+async fn containers_by_prefix(docker: &Docker, prefix: &str) -> Result<Vec<PrefixedContainer>> {
     let filters: HashMap<String, Vec<String>> = HashMap::new();
     let options = ListContainersOptions {
         all: true,
@@ -1774,20 +2461,105 @@ pub async fn cleanup_by_prefix(prefix: &str) -> Result<CleanupResult> {
         .await
         .context("Failed to list containers")?;
 
-    // Filter containers by name prefix
-    let matching_containers: Vec<_> = containers
+    Ok(containers
         .into_iter()
-        .filter(|c| {
-            c.names.as_ref().is_some_and(|names| {
-                names.iter().any(|name| {
-                    // Container names from Docker API start with "/"
-                    let name = name.strip_prefix('/').unwrap_or(name);
-                    name.starts_with(prefix)
-                })
+        .filter_map(|c| {
+            // Container names from Docker API start with "/"
+            let name = c
+                .names?
+                .into_iter()
+                .map(|name| name.strip_prefix('/').map(String::from).unwrap_or(name))
+                .find(|name| name.starts_with(prefix))?;
+            Some(PrefixedContainer {
+                id: c.id.unwrap_or_default(),
+                name,
+                state: c.state,
             })
         })
+        .collect())
+}
+
+/// List the containers and network that [`cleanup_by_prefix`] would remove, without
+/// removing anything.
+///
+/// A short prefix can match containers of other networks; use this to preview a cleanup.
+pub async fn list_by_prefix(prefix: &str) -> Result<CleanupResult> {
+    let docker =
+        Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
+
+    let containers_removed = containers_by_prefix(&docker, prefix)
+        .await?
+        .into_iter()
+        .map(|container| container.name)
         .collect();
 
+    let network_name = format!("{}-network", prefix);
+    let network_removed = docker
+        .inspect_network::<String>(&network_name, None)
+        .await
+        .is_ok()
+        .then_some(network_name);
+
+    Ok(CleanupResult {
+        containers_removed,
+        network_removed,
+    })
+}
+
+/// Container roles in the order [`cleanup_by_prefix`] stops them, one tier at a time.
+///
+/// Services are stopped before the ones they depend on (monitoring and tools, fault
+/// proofs, submitters, consensus, execution, op-deployer, then the L1), so that they do
+/// not log connection errors while their upstreams go away.
+const SHUTDOWN_ORDER: &[&[&str]] = &[
+    &[
+        "grafana",
+        "prometheus",
+        "promtail",
+        "loki",
+        "blockscout",
+        "contender",
+        "post-deploy",
+    ],
+    &["op-challenger"],
+    &["op-proposer", "op-batcher", "op-conductor"],
+    &["kona-node", "op-node", "op-supervisor"],
+    &["op-reth"],
+    &["op-deployer"],
+    &["anvil"],
+];
+
+/// Shutdown tier of a container, its index in [`SHUTDOWN_ORDER`], from the role in its
+/// name after `prefix`. Containers with an unknown role are stopped last.
+fn shutdown_tier(prefix: &str, name: &str) -> usize {
+    let role = format!(
+        "-{}-",
+        name.strip_prefix(prefix).unwrap_or(name).trim_matches('-')
+    );
+    SHUTDOWN_ORDER
+        .iter()
+        .position(|roles| {
+            roles
+                .iter()
+                .any(|known| role.contains(&format!("-{}-", known)))
+        })
+        .unwrap_or(SHUTDOWN_ORDER.len())
+}
+
+/// Clean up containers and network by name prefix.
+///
+/// This is a standalone function that doesn't require a `KupDocker` instance.
+/// It finds all containers whose names start with the given prefix, stops them in reverse
+/// dependency order (see [`SHUTDOWN_ORDER`]) and removes them, then removes the associated
+/// network. See [`list_by_prefix`] for a dry run.
+pub async fn cleanup_by_prefix(prefix: &str) -> Result<CleanupResult> {
+    let docker =
+        Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
+
+    let mut result = CleanupResult::default();
+
+    let mut matching_containers = containers_by_prefix(&docker, prefix).await?;
+
     if matching_containers.is_empty() {
         tracing::info!("No containers found with prefix '{}'", prefix);
     } else {
@@ -1797,26 +2569,38 @@ pub async fn cleanup_by_prefix(prefix: &str) -> Result<CleanupResult> {
             prefix
         );
 
-        // Stop and remove each container
-        for container in matching_containers {
-            let container_id = container.id.unwrap_or_default();
-            let container_name = container
-                .names
-                .as_ref()
-                .and_then(|n| n.first())
-                .map(|n| n.strip_prefix('/').unwrap_or(n))
-                .unwrap_or(&container_id)
-                .to_string();
-
-            tracing::debug!("Stopping and removing container: {}", container_name);
-
-            // Stop the container (ignore errors if already stopped)
-            docker
-                .stop_container(&container_id, Some(StopContainerOptions { t: 5 }))
-                .await
-                .ok();
+        matching_containers.sort_by_key(|container| shutdown_tier(prefix, &container.name));
+
+        // Stop each tier, concurrently within the tier, before moving on to the next one
+        for tier in matching_containers
+            .chunk_by(|a, b| shutdown_tier(prefix, &a.name) == shutdown_tier(prefix, &b.name))
+        {
+            let stops = tier
+                .iter()
+                .filter(|container| is_stoppable(container.state.as_deref()))
+                .map(|container| {
+                    let docker = &docker;
+                    async move {
+                        tracing::debug!("Stopping container: {}", container.name);
+                        // Ignore errors: the container is removed with force below
+                        docker
+                            .stop_container(&container.id, Some(StopContainerOptions { t: 5 }))
+                            .await
+                            .ok();
+                    }
+                });
+            join_all(stops).await;
+        }
+
+        // Remove each container
+        for PrefixedContainer {
+            id: container_id,
+            name: container_name,
+            ..
+        } in matching_containers
+        {
+            tracing::debug!("Removing container: {}", container_name);
 
-            // Remove the container
             if let Err(e) = docker
                 .remove_container(
                     &container_id,
@@ -1867,7 +2651,84 @@ pub async fn cleanup_by_prefix(prefix: &str) -> Result<CleanupResult> {
     Ok(result)
 }
 
+/// Grace period given to each container by [`stop_by_prefix`] before it is killed, in
+/// seconds. op-reth flushes its database on SIGTERM.
+const STOP_BY_PREFIX_TIMEOUT_SECS: i64 = 30;
+
+/// Whether a container in Docker state `state` has processes to stop.
+fn is_stoppable(state: Option<&str>) -> bool {
+    matches!(state, Some("running" | "paused" | "restarting"))
+}
+
+/// Stop, without removing, the containers whose name starts with `prefix`.
+///
+/// Unlike [`cleanup_by_prefix`], the containers, the network and the output directory
+/// are kept, so redeploying from the saved `Kupcake.toml` resumes from the existing
+/// state. Containers that are not running are skipped.
+pub async fn stop_by_prefix(prefix: &str) -> Result<StopResult> {
+    let docker =
+        Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
+
+    let (running, stopped): (Vec<_>, Vec<_>) = containers_by_prefix(&docker, prefix)
+        .await?
+        .into_iter()
+        .partition(|container| is_stoppable(container.state.as_deref()));
+
+    let mut result = StopResult {
+        containers_skipped: stopped
+            .into_iter()
+            .map(|container| container.name)
+            .collect(),
+        ..Default::default()
+    };
+
+    let stops = running.into_iter().map(|container| {
+        let docker = &docker;
+        async move {
+            tracing::debug!("Stopping container: {}", container.name);
+            let stop = docker
+                .stop_container(
+                    &container.id,
+                    Some(StopContainerOptions {
+                        t: STOP_BY_PREFIX_TIMEOUT_SECS,
+                    }),
+                )
+                .await;
+            (container.name, stop)
+        }
+    });
+    for (name, stop) in join_all(stops).await {
+        match stop {
+            Ok(()) => result.containers_stopped.push(name),
+            // Stopped between the listing and the stop request.
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 304, ..
+            }) => result.containers_skipped.push(name),
+            Err(e) => tracing::warn!("Failed to stop container {}: {}", name, e),
+        }
+    }
+
+    if !result.containers_stopped.is_empty()
+        && let Err(e) = crate::DevnetRegistry::new().and_then(|r| r.mark_stopped(prefix))
+    {
+        tracing::warn!(error = %e, "Failed to update devnet registry");
+    }
+
+    Ok(result)
+}
+
+/// Result of [`stop_by_prefix`].
+#[derive(Debug, Default)]
+pub struct StopResult {
+    /// Names of containers that were stopped.
+    pub containers_stopped: Vec<String>,
+    /// Names of matching containers that were not running.
+    pub containers_skipped: Vec<String>,
+}
+
 /// Result of a cleanup operation.
+///
+/// For [`list_by_prefix`], the containers and network that would be removed.
 #[derive(Debug, Default)]
 pub struct CleanupResult {
     /// Names of containers that were removed.
@@ -1875,3 +2736,249 @@ pub struct CleanupResult {
     /// Name of the network that was removed, if any.
     pub network_removed: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutdown_tier() {
+        let tier = |name| shutdown_tier("kup-test", name);
+        let order = [
+            "kup-test-grafana",
+            "kup-test-blockscout-db",
+            "kup-test-op-challenger",
+            "kup-test-op-batcher-spare-1",
+            "kup-test-42070-op-proposer",
+            "kup-test-op-conductor-1",
+            "kup-test-kona-node-validator-2",
+            "kup-test-op-node",
+            "kup-test-op-reth-sequencer-1",
+            "kup-test-op-deployer",
+            "kup-test-anvil",
+            "kup-test-custom-sidecar",
+        ];
+        for pair in order.windows(2) {
+            assert!(tier(pair[0]) <= tier(pair[1]), "{} <= {}", pair[0], pair[1]);
+        }
+        assert!(tier("kup-test-op-challenger") < tier("kup-test-op-batcher"));
+        assert!(tier("kup-test-kona-node") < tier("kup-test-op-reth"));
+        assert!(tier("kup-test-op-reth") < tier("kup-test-anvil"));
+        assert_eq!(tier("kup-test-custom-sidecar"), SHUTDOWN_ORDER.len());
+        // Roles in the network name itself are ignored
+        assert_eq!(
+            shutdown_tier("anvil-net", "anvil-net-op-reth"),
+            tier("kup-test-op-reth")
+        );
+    }
+
+    #[test]
+    fn test_parse_cidr() {
+        let subnet = parse_cidr("172.30.0.0/16").unwrap();
+        assert!(subnet_contains(subnet, "172.30.0.1".parse().unwrap()));
+        assert!(!subnet_contains(subnet, "172.31.0.1".parse().unwrap()));
+        assert!(!subnet_contains(subnet, "fd00::1".parse().unwrap()));
+
+        let subnet = parse_cidr("fd00:1::/64").unwrap();
+        assert!(subnet_contains(subnet, "fd00:1::1".parse().unwrap()));
+        assert!(!subnet_contains(subnet, "fd00:2::1".parse().unwrap()));
+
+        let any = parse_cidr("0.0.0.0/0").unwrap();
+        assert!(subnet_contains(any, "10.0.0.1".parse().unwrap()));
+
+        for invalid in [
+            "172.30.0.0",
+            "172.30.0.0/33",
+            "fd00::/129",
+            "not-an-ip/8",
+            "10.0.0.0/x",
+        ] {
+            assert_eq!(parse_cidr(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_is_stoppable() {
+        assert!(is_stoppable(Some("running")));
+        assert!(is_stoppable(Some("paused")));
+        assert!(!is_stoppable(Some("exited")));
+        assert!(!is_stoppable(Some("created")));
+        assert!(!is_stoppable(None));
+    }
+
+    #[test]
+    fn test_restart_policy_from_str() {
+        assert_eq!("no".parse::<RestartPolicy>().unwrap(), RestartPolicy::No);
+        assert_eq!(
+            "unless-stopped".parse::<RestartPolicy>().unwrap(),
+            RestartPolicy::UnlessStopped
+        );
+        assert_eq!(
+            "on-failure".parse::<RestartPolicy>().unwrap(),
+            RestartPolicy::OnFailure { max_retries: 0 }
+        );
+        assert_eq!(
+            "on-failure:5".parse::<RestartPolicy>().unwrap(),
+            RestartPolicy::OnFailure { max_retries: 5 }
+        );
+        assert!("on-failure:x".parse::<RestartPolicy>().is_err());
+        assert!("always".parse::<RestartPolicy>().is_err());
+
+        for policy in [
+            RestartPolicy::No,
+            RestartPolicy::OnFailure { max_retries: 0 },
+            RestartPolicy::OnFailure { max_retries: 3 },
+            RestartPolicy::UnlessStopped,
+        ] {
+            assert_eq!(policy.to_string().parse::<RestartPolicy>().unwrap(), policy);
+        }
+    }
+
+    #[test]
+    fn test_build_container_urls() {
+        assert_eq!(
+            KupDocker::build_http_url("kup-net-1-op-reth-validator-2", 8545)
+                .unwrap()
+                .as_str(),
+            "http://kup-net-1-op-reth-validator-2:8545/"
+        );
+        assert_eq!(
+            KupDocker::build_ws_url("7a1b-anvil", 8546)
+                .unwrap()
+                .as_str(),
+            "ws://7a1b-anvil:8546/"
+        );
+        assert_eq!(
+            KupDocker::build_container_url("https", "kup_net.anvil", 8443, "/rpc")
+                .unwrap()
+                .as_str(),
+            "https://kup_net.anvil:8443/rpc"
+        );
+        assert_eq!(
+            KupDocker::build_container_url("http", "kup-anvil", 8545, "rpc/v1")
+                .unwrap()
+                .path(),
+            "/rpc/v1"
+        );
+    }
+
+    #[test]
+    fn test_get_udp_host_port() {
+        // With publish_all_ports, Docker binds the TCP and UDP sides of the P2P port to
+        // distinct random host ports.
+        let handler = ServiceHandler {
+            container_id: "id".to_string(),
+            container_name: "kup-op-reth".to_string(),
+            bound_ports: [
+                ("8545/tcp".to_string(), 49152),
+                ("30303/tcp".to_string(), 49153),
+                ("30303/udp".to_string(), 49154),
+            ]
+            .into(),
+        };
+        assert_eq!(handler.get_udp_host_port(30303), Some(49154));
+        assert_eq!(handler.get_tcp_host_port(30303), Some(49153));
+        assert_eq!(handler.get_udp_host_port(8545), None);
+    }
+
+    #[test]
+    fn test_image_digest() {
+        let hex = "3f5a".repeat(16);
+        let pinned = DockerImage::from_digest("nicolaka/netshoot", &hex);
+        assert_eq!(pinned.digest, Some(format!("sha256:{hex}")));
+        assert_eq!(
+            pinned.image_ref(),
+            format!("nicolaka/netshoot@sha256:{hex}")
+        );
+        assert_eq!(
+            DockerImage::from_digest("nicolaka/netshoot", format!("sha256:{hex}")),
+            pinned
+        );
+        assert!(is_valid_digest(pinned.digest.as_deref().unwrap()));
+        assert!(!is_valid_digest(&hex));
+        assert!(!is_valid_digest("sha256:3f5a"));
+
+        // The digest wins over the tag, which is kept for display.
+        let tagged = DockerImage::new("nicolaka/netshoot", "latest");
+        assert_eq!(tagged.image_ref(), "nicolaka/netshoot:latest");
+        let tagged = tagged.with_digest(&hex);
+        assert_eq!(
+            tagged.image_ref(),
+            format!("nicolaka/netshoot@sha256:{hex}")
+        );
+        assert_eq!(
+            tagged.to_string(),
+            format!("nicolaka/netshoot:latest@sha256:{hex}")
+        );
+
+        let toml = toml::to_string(&pinned).unwrap();
+        assert!(!toml.contains("tag"));
+        assert_eq!(toml::from_str::<DockerImage>(&toml).unwrap(), pinned);
+        let toml = toml::to_string(&tagged).unwrap();
+        assert_eq!(toml::from_str::<DockerImage>(&toml).unwrap(), tagged);
+    }
+
+    #[test]
+    fn test_build_container_url_rejects_invalid_names() {
+        let long = "a".repeat(64);
+        for name in [
+            "",
+            "-anvil",
+            "anvil-",
+            "kup net",
+            "kup..anvil",
+            "kup/anvil",
+            &long,
+        ] {
+            assert!(
+                matches!(
+                    KupDocker::build_http_url(name, 8545),
+                    Err(ContainerUrlError::InvalidContainerName { .. })
+                ),
+                "{name:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_ca_bundle() {
+        let mut config = ServiceConfig::new(DockerImage::new("op-node", "latest"))
+            .bind_str("/data:/data")
+            .env(vec!["RUST_LOG=info".to_string()]);
+        apply_ca_bundle(&mut config, Path::new("/etc/ssl/corp-ca.pem"));
+
+        assert_eq!(
+            config.binds,
+            vec![
+                "/data:/data".to_string(),
+                "/etc/ssl/corp-ca.pem:/etc/kupcake/ca.pem:ro".to_string(),
+            ]
+        );
+        assert_eq!(
+            config.env.unwrap(),
+            vec![
+                "RUST_LOG=info".to_string(),
+                "SSL_CERT_FILE=/etc/kupcake/ca.pem".to_string(),
+                "NODE_EXTRA_CA_CERTS=/etc/kupcake/ca.pem".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proxy_container_env() {
+        let proxy = ProxyConfig {
+            http_proxy: None,
+            https_proxy: Some("http://proxy.corp:3128".to_string()),
+            no_proxy: vec![".corp".to_string(), "localhost".to_string()],
+        };
+        assert_eq!(
+            proxy.container_env(&["kup-op-reth".to_string()]),
+            vec![
+                "HTTPS_PROXY=http://proxy.corp:3128".to_string(),
+                "https_proxy=http://proxy.corp:3128".to_string(),
+                "NO_PROXY=localhost,127.0.0.1,.corp,kup-op-reth".to_string(),
+                "no_proxy=localhost,127.0.0.1,.corp,kup-op-reth".to_string(),
+            ]
+        );
+    }
+}
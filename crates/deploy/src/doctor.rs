@@ -0,0 +1,707 @@
+//! Diagnostics for the usual reasons a deployment fails to start.
+//!
+//! [`diagnose`] runs a set of preflight checks against a [`DoctorTarget`] (a saved
+//! deployment, or the defaults of a new one) and returns a [`DoctorReport`] with one
+//! [`DoctorCheck`] per concern, each with a remediation hint when it does not pass.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use bollard::Docker;
+use comfy_table::{Attribute, Cell, Color, Table};
+
+use crate::{
+    ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG, DEFAULT_MIN_DISK_SPACE_GIB, Deployer, DockerImage, GIB,
+    GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG, KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG,
+    KupcakeService, OP_BATCHER_DEFAULT_IMAGE, OP_BATCHER_DEFAULT_TAG, OP_CHALLENGER_DEFAULT_IMAGE,
+    OP_CHALLENGER_DEFAULT_TAG, OP_CONDUCTOR_DEFAULT_IMAGE, OP_CONDUCTOR_DEFAULT_TAG,
+    OP_DEPLOYER_DEFAULT_IMAGE, OP_DEPLOYER_DEFAULT_TAG, OP_PROPOSER_DEFAULT_IMAGE,
+    OP_PROPOSER_DEFAULT_TAG, OP_RETH_DEFAULT_IMAGE, OP_RETH_DEFAULT_TAG, PROMETHEUS_DEFAULT_IMAGE,
+    PROMETHEUS_DEFAULT_TAG, PortMapping, RECOMMENDED_DISK_SPACE_GIB, rpc,
+};
+
+/// Oldest Docker API version kupcake is tested with (Docker 20.10, which also
+/// introduced `host-gateway`).
+pub const MIN_DOCKER_API_VERSION: (u64, u64) = (1, 41);
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Not fatal, but worth a look.
+    Warn,
+    Fail,
+    /// Not applicable, or depends on a check that failed.
+    Skip,
+}
+
+/// Result of a single check, with a hint on how to fix it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Checks run by [`diagnose`], in order.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Whether any check failed.
+    pub fn has_failures(&self) -> bool {
+        self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+}
+
+/// What to diagnose: the resources a deployment needs.
+#[derive(Debug, Clone, Default)]
+pub struct DoctorTarget {
+    /// Directory the deployment writes its data to.
+    pub data_dir: PathBuf,
+    /// Docker images of the services, keyed by service name.
+    pub images: Vec<(String, DockerImage)>,
+    /// Names of the containers the deployment creates.
+    pub containers: Vec<String>,
+    /// Host ports published by the containers, keyed by container name.
+    pub host_ports: Vec<(String, PortMapping)>,
+    /// L1 RPC the deployment forks or deploys to, with its expected chain ID if known.
+    pub l1_rpc: Option<(String, Option<u64>)>,
+}
+
+impl DoctorTarget {
+    /// Resources of a new deployment with the default images, writing to `data_dir`.
+    pub fn defaults(data_dir: impl Into<PathBuf>) -> Self {
+        let images = [
+            ("anvil", ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG),
+            (
+                "op-deployer",
+                OP_DEPLOYER_DEFAULT_IMAGE,
+                OP_DEPLOYER_DEFAULT_TAG,
+            ),
+            ("op-reth", OP_RETH_DEFAULT_IMAGE, OP_RETH_DEFAULT_TAG),
+            ("kona-node", KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG),
+            (
+                "op-conductor",
+                OP_CONDUCTOR_DEFAULT_IMAGE,
+                OP_CONDUCTOR_DEFAULT_TAG,
+            ),
+            (
+                "op-batcher",
+                OP_BATCHER_DEFAULT_IMAGE,
+                OP_BATCHER_DEFAULT_TAG,
+            ),
+            (
+                "op-proposer",
+                OP_PROPOSER_DEFAULT_IMAGE,
+                OP_PROPOSER_DEFAULT_TAG,
+            ),
+            (
+                "op-challenger",
+                OP_CHALLENGER_DEFAULT_IMAGE,
+                OP_CHALLENGER_DEFAULT_TAG,
+            ),
+            (
+                "prometheus",
+                PROMETHEUS_DEFAULT_IMAGE,
+                PROMETHEUS_DEFAULT_TAG,
+            ),
+            ("grafana", GRAFANA_DEFAULT_IMAGE, GRAFANA_DEFAULT_TAG),
+        ]
+        .into_iter()
+        .map(|(name, image, tag)| (name.to_string(), DockerImage::new(image, tag)))
+        .collect();
+
+        Self {
+            data_dir: data_dir.into(),
+            images,
+            ..Default::default()
+        }
+    }
+
+    /// Resources of a saved deployment.
+    pub fn from_deployer(deployer: &Deployer) -> Self {
+        let mut target = Self {
+            data_dir: deployer.outdata.clone(),
+            ..Default::default()
+        };
+
+        target.add_service(
+            &deployer.op_deployer.container_name,
+            "op-deployer",
+            &deployer.op_deployer.docker_image,
+            [],
+        );
+
+        if let Some(ref external) = deployer.external_l1 {
+            target.l1_rpc = Some((external.rpc_url.clone(), Some(deployer.l1_chain_id)));
+        } else {
+            let anvil = &deployer.anvil;
+            target.add_service(
+                &anvil.container_name,
+                "anvil",
+                &anvil.docker_image,
+                [PortMapping::tcp_optional(anvil.port, anvil.host_port)],
+            );
+            // A fork keeps the chain ID of the forked chain.
+            target.l1_rpc = anvil
+                .fork_url
+                .clone()
+                .map(|url| (url, Some(deployer.l1_chain_id)));
+        }
+
+        let stack = &deployer.l2_stack;
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            let reth = &node.op_reth;
+            target.add_service(
+                &reth.container_name,
+                "op-reth",
+                &reth.docker_image,
+                [
+                    PortMapping::tcp_optional(reth.http_port, reth.http_host_port),
+                    PortMapping::tcp_optional(reth.ws_port, reth.ws_host_port),
+                    PortMapping::tcp_optional(reth.authrpc_port, reth.authrpc_host_port),
+                    PortMapping::tcp_optional(reth.metrics_port, reth.metrics_host_port),
+                    PortMapping::tcp_optional(reth.listen_port, reth.listen_host_port),
+                    PortMapping::udp_optional(reth.discovery_port, reth.discovery_host_port),
+                ],
+            );
+
+            if let Some(ref op_node) = node.op_node {
+                target.add_service(
+                    &op_node.container_name,
+                    "op-node",
+                    &op_node.docker_image,
+                    [
+                        PortMapping::tcp_optional(op_node.rpc_port, op_node.rpc_host_port),
+                        PortMapping::tcp_optional(op_node.metrics_port, op_node.metrics_host_port),
+                    ],
+                );
+            } else {
+                let kona = &node.kona_node;
+                target.add_service(
+                    &kona.container_name,
+                    "kona-node",
+                    &kona.docker_image,
+                    [
+                        PortMapping::tcp_optional(kona.rpc_port, kona.rpc_host_port),
+                        PortMapping::tcp_optional(kona.metrics_port, kona.metrics_host_port),
+                    ],
+                );
+            }
+
+            if let Some(ref conductor) = node.op_conductor {
+                target.add_service(
+                    &conductor.container_name,
+                    "op-conductor",
+                    &conductor.docker_image,
+                    [
+                        PortMapping::tcp_optional(conductor.rpc_port, conductor.rpc_host_port),
+                        PortMapping::tcp_optional(
+                            conductor.consensus_port,
+                            conductor.consensus_host_port,
+                        ),
+                    ],
+                );
+            }
+
+            for sidecar in node.sidecars.iter() {
+                target.containers.push(sidecar.container_name().to_string());
+            }
+        }
+
+        let batcher = &stack.op_batcher;
+        target.add_service(
+            batcher.container_name(),
+            "op-batcher",
+            batcher.docker_image(),
+            [
+                PortMapping::tcp_optional(batcher.rpc_port, batcher.rpc_host_port),
+                PortMapping::tcp_optional(batcher.metrics_port, batcher.metrics_host_port),
+            ],
+        );
+        if let Some(ref proposer) = stack.op_proposer {
+            target.add_service(
+                proposer.container_name(),
+                "op-proposer",
+                proposer.docker_image(),
+                [
+                    PortMapping::tcp_optional(proposer.rpc_port, proposer.rpc_host_port),
+                    PortMapping::tcp_optional(proposer.metrics_port, proposer.metrics_host_port),
+                ],
+            );
+        }
+        if let Some(ref challenger) = stack.op_challenger {
+            target.add_service(
+                challenger.container_name(),
+                "op-challenger",
+                challenger.docker_image(),
+                [PortMapping::tcp_optional(
+                    challenger.metrics_port,
+                    challenger.metrics_host_port,
+                )],
+            );
+        }
+
+        if deployer.monitoring.enabled {
+            let prometheus = &deployer.monitoring.prometheus;
+            target.add_service(
+                &prometheus.container_name,
+                "prometheus",
+                &prometheus.docker_image,
+                [PortMapping::tcp_optional(
+                    prometheus.port,
+                    prometheus.host_port,
+                )],
+            );
+            let grafana = &deployer.monitoring.grafana;
+            target.add_service(
+                &grafana.container_name,
+                "grafana",
+                &grafana.docker_image,
+                [PortMapping::tcp_optional(grafana.port, grafana.host_port)],
+            );
+            if deployer.monitoring.logs.enabled {
+                let loki = &deployer.monitoring.logs.loki;
+                target.add_service(
+                    &loki.container_name,
+                    "loki",
+                    &loki.docker_image,
+                    [PortMapping::tcp_optional(loki.port, loki.host_port)],
+                );
+                let promtail = &deployer.monitoring.logs.promtail;
+                target.add_service(
+                    &promtail.container_name,
+                    "promtail",
+                    &promtail.docker_image,
+                    [],
+                );
+            }
+        }
+
+        target
+    }
+
+    fn add_service(
+        &mut self,
+        container_name: &str,
+        service: &str,
+        image: &DockerImage,
+        ports: impl IntoIterator<Item = Option<PortMapping>>,
+    ) {
+        self.containers.push(container_name.to_string());
+        if !self.images.iter().any(|(_, i)| i == image) {
+            self.images.push((service.to_string(), image.clone()));
+        }
+        self.host_ports.extend(
+            ports
+                .into_iter()
+                .flatten()
+                .map(|port| (container_name.to_string(), port)),
+        );
+    }
+}
+
+/// Run every check against `target`.
+///
+/// Checks that need Docker are skipped when the daemon cannot be reached.
+pub async fn diagnose(target: &DoctorTarget) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    let docker = match Docker::connect_with_local_defaults() {
+        Ok(docker) => {
+            let (check, reachable) = check_docker(&docker).await;
+            checks.push(check);
+            reachable.then_some(docker)
+        }
+        Err(e) => {
+            checks.push(DoctorCheck::fail(
+                "Docker",
+                format!("cannot connect: {e}"),
+                "Start the Docker daemon, or set DOCKER_HOST to its socket",
+            ));
+            None
+        }
+    };
+
+    let existing = match docker {
+        Some(ref docker) => {
+            checks.push(check_images(docker, &target.images).await);
+            let (check, existing) = check_containers(docker, &target.containers).await;
+            checks.push(check);
+            existing
+        }
+        None => {
+            checks.push(DoctorCheck::skip("Images", "Docker unreachable"));
+            checks.push(DoctorCheck::skip("Containers", "Docker unreachable"));
+            Vec::new()
+        }
+    };
+
+    checks.push(check_disk_space(&target.data_dir));
+    checks.push(check_host_ports(&target.host_ports, &existing));
+    checks.push(check_l1_rpc(target.l1_rpc.as_ref()).await);
+
+    DoctorReport { checks }
+}
+
+/// Check the Docker daemon version. Also returns whether the daemon responded.
+async fn check_docker(docker: &Docker) -> (DoctorCheck, bool) {
+    const NAME: &str = "Docker";
+
+    let version = match docker.version().await {
+        Ok(version) => version,
+        Err(e) => {
+            let check = DoctorCheck::fail(
+                NAME,
+                format!("daemon not responding: {e}"),
+                "Start the Docker daemon and check that your user can access its socket \
+                 (e.g. is in the `docker` group)",
+            );
+            return (check, false);
+        }
+    };
+
+    let server = version.version.unwrap_or_else(|| "unknown".to_string());
+    let api = version.api_version.unwrap_or_default();
+    let detail = format!("Docker {server} (API {api})");
+    let check = match parse_api_version(&api) {
+        Some(v) if v < MIN_DOCKER_API_VERSION => DoctorCheck::fail(
+            NAME,
+            detail,
+            format!(
+                "Upgrade Docker: kupcake needs API {}.{} (Docker 20.10) or newer",
+                MIN_DOCKER_API_VERSION.0, MIN_DOCKER_API_VERSION.1
+            ),
+        ),
+        _ => DoctorCheck::pass(NAME, detail),
+    };
+    (check, true)
+}
+
+/// Parse a Docker API version ("1.47") into (major, minor).
+fn parse_api_version(version: &str) -> Option<(u64, u64)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+async fn check_images(docker: &Docker, images: &[(String, DockerImage)]) -> DoctorCheck {
+    const NAME: &str = "Images";
+
+    let mut to_pull = Vec::new();
+    let mut missing = Vec::new();
+    for (service, image) in images {
+        if let Some(path) = image.binary_path() {
+            if !path.exists() {
+                missing.push(format!("{service} binary {}", path.display()));
+            }
+            continue;
+        }
+
+        let image_ref = image.image_ref();
+        if docker.inspect_image(&image_ref).await.is_ok() {
+            continue;
+        }
+        match docker.inspect_registry_image(&image_ref, None).await {
+            Ok(_) => to_pull.push(image_ref),
+            Err(e) => missing.push(format!("{image_ref} ({e})")),
+        }
+    }
+
+    if !missing.is_empty() {
+        DoctorCheck::fail(
+            NAME,
+            format!("not available: {}", missing.join(", ")),
+            "Check the image names and tags (or binary paths) in your config, and that \
+             you are logged in to private registries (`docker login`)",
+        )
+    } else if !to_pull.is_empty() {
+        DoctorCheck::warn(
+            NAME,
+            format!("{} to pull: {}", to_pull.len(), to_pull.join(", ")),
+            "The images are pulled on deploy; pull them ahead with `docker pull` on slow \
+             connections",
+        )
+    } else {
+        DoctorCheck::pass(NAME, format!("{} present", images.len()))
+    }
+}
+
+/// Check for existing containers with the deployment's names. Returns the names found.
+async fn check_containers(docker: &Docker, containers: &[String]) -> (DoctorCheck, Vec<String>) {
+    const NAME: &str = "Containers";
+
+    if containers.is_empty() {
+        return (
+            DoctorCheck::skip(NAME, "network name not known yet"),
+            Vec::new(),
+        );
+    }
+
+    let mut existing = Vec::new();
+    for name in containers {
+        if docker.inspect_container(name, None).await.is_ok() {
+            existing.push(name.clone());
+        }
+    }
+
+    let check = if existing.is_empty() {
+        DoctorCheck::pass(NAME, format!("no conflict for {} names", containers.len()))
+    } else {
+        DoctorCheck::warn(
+            NAME,
+            format!("already exist: {}", existing.join(", ")),
+            "The network is (partly) deployed: inspect it with `kupcake inspect`, or \
+             remove it with `kupcake cleanup <network>` before redeploying",
+        )
+    };
+    (check, existing)
+}
+
+fn check_disk_space(data_dir: &Path) -> DoctorCheck {
+    const NAME: &str = "Disk space";
+
+    // The data directory of a new deployment does not exist yet.
+    let Some(dir) = data_dir.ancestors().find(|p| p.exists()) else {
+        return DoctorCheck::skip(NAME, format!("{} not found", data_dir.display()));
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+
+    let available = match fs2::available_space(dir) {
+        Ok(available) => available,
+        Err(e) => {
+            return DoctorCheck::warn(
+                NAME,
+                format!("cannot read free space of {}: {e}", dir.display()),
+                "Check the permissions of the data directory",
+            );
+        }
+    };
+
+    let detail = format!(
+        "{:.1} GiB free in {}",
+        available as f64 / GIB as f64,
+        dir.display()
+    );
+    if available < DEFAULT_MIN_DISK_SPACE_GIB * GIB {
+        DoctorCheck::fail(
+            NAME,
+            detail,
+            "Free up space, remove old devnets with `kupcake prune`, or deploy to another \
+             disk with --outdata",
+        )
+    } else if available < RECOMMENDED_DISK_SPACE_GIB * GIB {
+        DoctorCheck::warn(
+            NAME,
+            detail,
+            "Long-running devnets grow their databases; use --long-running to rotate logs",
+        )
+    } else {
+        DoctorCheck::pass(NAME, detail)
+    }
+}
+
+/// Check that the fixed host ports are free, unless held by the deployment's own
+/// containers.
+fn check_host_ports(ports: &[(String, PortMapping)], existing: &[String]) -> DoctorCheck {
+    const NAME: &str = "Host ports";
+
+    let fixed: Vec<_> = ports
+        .iter()
+        .filter(|(container, port)| port.host_port != 0 && !existing.contains(container))
+        .collect();
+    if fixed.is_empty() {
+        return DoctorCheck::skip(NAME, "no fixed host port to check");
+    }
+
+    let busy: Vec<String> = fixed
+        .iter()
+        .filter(|(_, port)| crate::ports::probe_port(port.host_port, port.protocol).is_err())
+        .map(|(container, port)| {
+            format!(
+                "{}/{} ({container})",
+                port.host_port,
+                port.protocol.as_str()
+            )
+        })
+        .collect();
+
+    if busy.is_empty() {
+        DoctorCheck::pass(NAME, format!("{} free", fixed.len()))
+    } else {
+        DoctorCheck::fail(
+            NAME,
+            format!("in use: {}", busy.join(", ")),
+            "Stop the process holding the port (`lsof -i :<port>`), or set the host port \
+             to 0 in Kupcake.toml to let Docker pick one",
+        )
+    }
+}
+
+async fn check_l1_rpc(l1_rpc: Option<&(String, Option<u64>)>) -> DoctorCheck {
+    const NAME: &str = "L1 RPC";
+
+    let Some((url, expected)) = l1_rpc else {
+        return DoctorCheck::skip(NAME, "local L1, no fork");
+    };
+
+    let chain_id = async {
+        let client = rpc::create_client()?;
+        let result: String = rpc::json_rpc_call(&client, url, "eth_chainId", vec![]).await?;
+        Ok::<_, anyhow::Error>(u64::from_str_radix(result.trim_start_matches("0x"), 16)?)
+    }
+    .await;
+
+    match (chain_id, expected) {
+        (Err(e), _) => DoctorCheck::fail(
+            NAME,
+            format!("{url} unreachable: {e:#}"),
+            "Check the URL and your network; public endpoints can be rate limited, \
+             try another provider",
+        ),
+        (Ok(chain_id), Some(expected)) if chain_id != *expected => DoctorCheck::fail(
+            NAME,
+            format!("{url} has chain ID {chain_id}, expected {expected}"),
+            "Point --l1 at an RPC of the chain the deployment was created for",
+        ),
+        (Ok(chain_id), _) => DoctorCheck::pass(NAME, format!("{url} (chain ID {chain_id})")),
+    }
+}
+
+fn status_cell(status: CheckStatus) -> Cell {
+    match status {
+        CheckStatus::Pass => Cell::new("✓ pass").fg(Color::Green),
+        CheckStatus::Warn => Cell::new("! warn").fg(Color::Yellow),
+        CheckStatus::Fail => Cell::new("✗ fail").fg(Color::Red),
+        CheckStatus::Skip => Cell::new("- skip").fg(Color::DarkGrey),
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new();
+        table.set_header(vec![
+            Cell::new("Check").add_attribute(Attribute::Bold),
+            Cell::new("Status").add_attribute(Attribute::Bold),
+            Cell::new("Details").add_attribute(Attribute::Bold),
+        ]);
+        for check in &self.checks {
+            table.add_row(vec![
+                Cell::new(check.name),
+                status_cell(check.status),
+                Cell::new(&check.detail),
+            ]);
+        }
+        writeln!(f, "{table}")?;
+
+        let hints: Vec<_> = self
+            .checks
+            .iter()
+            .filter_map(|c| c.hint.as_ref().map(|hint| (c.name, hint)))
+            .collect();
+        if !hints.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Hints")?;
+            for (name, hint) in hints {
+                writeln!(f, "  {name}: {hint}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_api_version() {
+        assert_eq!(parse_api_version("1.47"), Some((1, 47)));
+        assert_eq!(parse_api_version("garbage"), None);
+        assert!(parse_api_version("1.40").unwrap() < MIN_DOCKER_API_VERSION);
+    }
+
+    #[test]
+    fn test_busy_host_port_fails() {
+        let listener = TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let ports = vec![("kup-test-anvil".to_string(), PortMapping::tcp(8545, port))];
+
+        let check = check_host_ports(&ports, &[]);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains(&port.to_string()), "{}", check.detail);
+
+        // Ports of the deployment's own containers are not conflicts.
+        let check = check_host_ports(&ports, &["kup-test-anvil".to_string()]);
+        assert_eq!(check.status, CheckStatus::Skip);
+    }
+
+    #[test]
+    fn test_report_fails_on_any_failure() {
+        let mut report = DoctorReport {
+            checks: vec![
+                DoctorCheck::pass("Docker", "Docker 27.3.1 (API 1.47)"),
+                DoctorCheck::warn("Images", "1 to pull", "pull them"),
+            ],
+        };
+        assert!(!report.has_failures());
+        assert!(report.to_string().contains("Images: pull them"));
+
+        report.checks.push(DoctorCheck::fail(
+            "Disk space",
+            "0.5 GiB free",
+            "free space",
+        ));
+        assert!(report.has_failures());
+    }
+}
@@ -0,0 +1,409 @@
+//! Externally-running L1 support.
+//!
+//! Instead of starting Anvil, kupcake can deploy the OP Stack against an L1 node that is
+//! already running. Contracts are deployed with a provided deployer key and the OP Stack
+//! role accounts come from provided keys rather than from Anvil's default mnemonic.
+
+use std::path::PathBuf;
+
+use alloy_core::primitives::Bytes;
+use alloy_signer_local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    AccountInfo, AnvilAccounts, AnvilHandler, metrics::ContainerDeployTimings, rpc,
+    services::anvil::EXTERNAL_L1_NAME,
+};
+
+/// Where an external L1 private key is read from.
+///
+/// Written to `Kupcake.toml` as `env:<NAME>` or `file:<PATH>`. A key given inline is only
+/// kept in memory: it is written as an empty string and must be given again to redeploy.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum PrivateKeySource {
+    /// The hex key itself. Never written to `Kupcake.toml`.
+    Inline(String),
+    /// Environment variable holding the hex key (`env:<NAME>`).
+    Env(String),
+    /// File holding the hex key (`file:<PATH>`).
+    File(PathBuf),
+}
+
+impl PrivateKeySource {
+    /// Read the hex key.
+    pub fn resolve(&self) -> Result<String> {
+        match self {
+            Self::Inline(key) if key.is_empty() => anyhow::bail!(
+                "The key was given inline and is not stored in Kupcake.toml; \
+                 give it again, or as env:<NAME> or file:<PATH>"
+            ),
+            Self::Inline(key) => Ok(key.clone()),
+            Self::Env(name) => std::env::var(name)
+                .with_context(|| format!("Environment variable {name} is not set")),
+            Self::File(path) => std::fs::read_to_string(path)
+                .map(|key| key.trim().to_string())
+                .with_context(|| format!("Failed to read key file {}", path.display())),
+        }
+    }
+}
+
+impl From<String> for PrivateKeySource {
+    fn from(value: String) -> Self {
+        if let Some(name) = value.strip_prefix("env:") {
+            Self::Env(name.to_string())
+        } else if let Some(path) = value.strip_prefix("file:") {
+            Self::File(PathBuf::from(path))
+        } else {
+            Self::Inline(value)
+        }
+    }
+}
+
+impl From<&str> for PrivateKeySource {
+    fn from(value: &str) -> Self {
+        Self::from(value.to_string())
+    }
+}
+
+impl From<PrivateKeySource> for String {
+    fn from(source: PrivateKeySource) -> Self {
+        match source {
+            PrivateKeySource::Inline(_) => String::new(),
+            PrivateKeySource::Env(name) => format!("env:{name}"),
+            PrivateKeySource::File(path) => format!("file:{}", path.display()),
+        }
+    }
+}
+
+/// Configuration for an externally-running L1 node.
+///
+/// The RPC URL must be reachable both from the host and from the Docker containers
+/// (op-deployer, L2 nodes and services), so `localhost` URLs generally do not work.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExternalL1Config {
+    /// RPC URL of the L1 node.
+    pub rpc_url: String,
+    /// WebSocket URL of the L1 node (`ws://` or `wss://`).
+    ///
+    /// When set, the consensus clients and op-batcher follow the L1 over this URL
+    /// instead of polling `rpc_url`. Contract deployment and beacon queries still use HTTP.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_url: Option<String>,
+    /// Private key of the account that deploys the L1 contracts.
+    ///
+    /// Also used for the fee vault recipients, proxy admin owners, system config
+    /// owner and unsafe block signer.
+    pub deployer_private_key: PrivateKeySource,
+    /// Private key for op-batcher. Defaults to the deployer key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batcher_private_key: Option<PrivateKeySource>,
+    /// Private key for op-proposer. Defaults to the deployer key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposer_private_key: Option<PrivateKeySource>,
+    /// Private key for op-challenger. Defaults to the deployer key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenger_private_key: Option<PrivateKeySource>,
+}
+
+impl ExternalL1Config {
+    /// Create a new external L1 configuration.
+    pub fn new(
+        rpc_url: impl Into<String>,
+        deployer_private_key: impl Into<PrivateKeySource>,
+    ) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            ws_url: None,
+            deployer_private_key: deployer_private_key.into(),
+            batcher_private_key: None,
+            proposer_private_key: None,
+            challenger_private_key: None,
+        }
+    }
+
+    /// Set the WebSocket URL used by the consensus clients and op-batcher.
+    pub fn ws_url(mut self, url: impl Into<String>) -> Self {
+        self.ws_url = Some(url.into());
+        self
+    }
+
+    /// Set the op-batcher private key.
+    pub fn batcher_private_key(mut self, key: impl Into<PrivateKeySource>) -> Self {
+        self.batcher_private_key = Some(key.into());
+        self
+    }
+
+    /// Set the op-proposer private key.
+    pub fn proposer_private_key(mut self, key: impl Into<PrivateKeySource>) -> Self {
+        self.proposer_private_key = Some(key.into());
+        self
+    }
+
+    /// Set the op-challenger private key.
+    pub fn challenger_private_key(mut self, key: impl Into<PrivateKeySource>) -> Self {
+        self.challenger_private_key = Some(key.into());
+        self
+    }
+
+    /// Build the OP Stack role accounts from the provided keys.
+    pub fn accounts(&self) -> Result<AnvilAccounts> {
+        let account = |key: &PrivateKeySource, role: &str| {
+            key.resolve()
+                .and_then(|key| account_from_private_key(&key))
+                .with_context(|| format!("Invalid {role} key"))
+        };
+        let deployer = account(&self.deployer_private_key, "deployer")?;
+        let role_account = |key: &Option<PrivateKeySource>, role: &str| match key {
+            Some(key) => account(key, role),
+            None => Ok(deployer.clone()),
+        };
+
+        let batcher = role_account(&self.batcher_private_key, "batcher")?;
+        let proposer = role_account(&self.proposer_private_key, "proposer")?;
+        let challenger = role_account(&self.challenger_private_key, "challenger")?;
+
+        if batcher.address == proposer.address
+            || proposer.address == challenger.address
+            || batcher.address == challenger.address
+        {
+            tracing::warn!(
+                "External L1 batcher, proposer and challenger share an account; \
+                 their transactions may conflict on nonces"
+            );
+        }
+
+        Ok(AnvilAccounts {
+            l1_fee_vault_recipient: deployer.clone(),
+            sequencer_fee_vault_recipient: deployer.clone(),
+            l1_proxy_admin_owner: deployer.clone(),
+            l2_proxy_admin_owner: deployer.clone(),
+            system_config_owner: deployer.clone(),
+            unsafe_block_signer: deployer.clone(),
+            deployer,
+            batcher,
+            proposer,
+            challenger,
+            extra_accounts: Vec::new(),
+        })
+    }
+
+    /// Build an L1 handler pointing at the external node.
+    ///
+    /// The handler has no container; the same URL is used from the host and from Docker.
+    pub fn handler(&self) -> Result<AnvilHandler> {
+        let url = Url::parse(&self.rpc_url).context("Invalid external L1 RPC URL")?;
+        let ws_url = self
+            .ws_url
+            .as_deref()
+            .map(|ws_url| {
+                let ws_url = Url::parse(ws_url).context("Invalid external L1 WebSocket URL")?;
+                if !matches!(ws_url.scheme(), "ws" | "wss") {
+                    anyhow::bail!(
+                        "External L1 WebSocket URL must use ws:// or wss://, got {}",
+                        ws_url
+                    );
+                }
+                Ok(ws_url)
+            })
+            .transpose()?;
+
+        Ok(AnvilHandler {
+            container_id: String::new(),
+            container_name: EXTERNAL_L1_NAME.to_string(),
+            l1_rpc_url: url.clone(),
+            l1_host_url: Some(url),
+            l1_ws_url: ws_url,
+            accounts: self.accounts()?,
+            deploy_timings: ContainerDeployTimings {
+                pull: Default::default(),
+                setup: Default::default(),
+            },
+        })
+    }
+
+    /// Check that the external L1 reports the expected chain ID.
+    pub async fn check_chain_id(&self, client: &reqwest::Client, expected: u64) -> Result<()> {
+        let result: String = rpc::json_rpc_call(client, &self.rpc_url, "eth_chainId", vec![])
+            .await
+            .with_context(|| format!("Failed to query chain ID from {}", self.rpc_url))?;
+        let chain_id = u64::from_str_radix(result.trim_start_matches("0x"), 16)
+            .context("Failed to parse external L1 chain ID")?;
+
+        if chain_id != expected {
+            anyhow::bail!(
+                "External L1 at {} has chain ID {}, but the configured L1 chain ID is {}",
+                self.rpc_url,
+                chain_id,
+                expected
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Check that the WebSocket URL, if any, accepts a WebSocket connection.
+    ///
+    /// Performs the opening handshake and expects `101 Switching Protocols`.
+    pub async fn check_ws_connection(&self, client: &reqwest::Client) -> Result<()> {
+        let Some(ws_url) = self.handler()?.l1_ws_url else {
+            return Ok(());
+        };
+
+        // reqwest only speaks HTTP(S); the handshake is a plain HTTP upgrade request.
+        let mut http_url = ws_url.clone();
+        let scheme = if ws_url.scheme() == "wss" {
+            "https"
+        } else {
+            "http"
+        };
+        http_url
+            .set_scheme(scheme)
+            .map_err(|_| anyhow::anyhow!("Cannot convert {} to an HTTP URL", ws_url))?;
+
+        let key: [u8; 16] = rand::random();
+        let response = client
+            .get(http_url)
+            .header(reqwest::header::CONNECTION, "Upgrade")
+            .header(reqwest::header::UPGRADE, "websocket")
+            .header(reqwest::header::SEC_WEBSOCKET_VERSION, "13")
+            .header(
+                reqwest::header::SEC_WEBSOCKET_KEY,
+                base64::engine::general_purpose::STANDARD.encode(key),
+            )
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", ws_url))?;
+
+        if response.status() != reqwest::StatusCode::SWITCHING_PROTOCOLS {
+            anyhow::bail!(
+                "External L1 at {} did not accept a WebSocket connection (HTTP {})",
+                ws_url,
+                response.status()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Derive an account from a hex-encoded private key (with or without `0x` prefix).
+fn account_from_private_key(key: &str) -> Result<AccountInfo> {
+    let bytes = hex::decode(key.trim_start_matches("0x")).context("Private key is not hex")?;
+    let signer =
+        PrivateKeySigner::from_slice(&bytes).context("Private key is not a valid secp256k1 key")?;
+
+    Ok(AccountInfo {
+        address: Bytes::copy_from_slice(signer.address().as_slice()),
+        private_key: Bytes::copy_from_slice(&bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // First two accounts of Anvil's default mnemonic.
+    const KEY_0: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const KEY_1: &str = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    #[test]
+    fn test_accounts_from_keys() {
+        let accounts = ExternalL1Config::new("http://l1:8545", KEY_0)
+            .batcher_private_key(KEY_1)
+            .accounts()
+            .unwrap();
+
+        assert_eq!(
+            format!("0x{}", hex::encode(&accounts.deployer.address)),
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+        assert_eq!(
+            format!("0x{}", hex::encode(&accounts.batcher.address)),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+        assert_eq!(accounts.proposer.address, accounts.deployer.address);
+        assert_eq!(
+            accounts.all_accounts().len(),
+            AnvilAccounts::MIN_REQUIRED_ACCOUNTS
+        );
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert!(
+            ExternalL1Config::new("http://l1:8545", "0xnothex")
+                .accounts()
+                .is_err()
+        );
+        assert!(
+            ExternalL1Config::new("http://l1:8545", KEY_0)
+                .challenger_private_key("0x00")
+                .accounts()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_inline_keys_not_serialized() {
+        let config = ExternalL1Config::new("http://l1:8545", KEY_0).batcher_private_key(KEY_1);
+        let toml = toml::to_string(&config).unwrap();
+        assert!(!toml.contains(KEY_0.trim_start_matches("0x")));
+        assert!(!toml.contains(KEY_1));
+
+        // Reloaded inline keys must be given again rather than falling back to the deployer.
+        let loaded: ExternalL1Config = toml::from_str(&toml).unwrap();
+        assert_eq!(
+            loaded.batcher_private_key,
+            Some(PrivateKeySource::Inline(String::new()))
+        );
+        assert!(loaded.accounts().is_err());
+    }
+
+    #[test]
+    fn test_key_references() {
+        let dir = tempdir::TempDir::new("kupcake-external-l1").unwrap();
+        let key_file = dir.path().join("batcher.key");
+        std::fs::write(&key_file, format!("{KEY_1}\n")).unwrap();
+
+        let config = ExternalL1Config::new("http://l1:8545", "env:KUPCAKE_TEST_UNSET_L1_KEY")
+            .batcher_private_key(format!("file:{}", key_file.display()));
+        let loaded: ExternalL1Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(loaded, config);
+        assert_eq!(
+            loaded.batcher_private_key.unwrap().resolve().unwrap(),
+            KEY_1
+        );
+        assert!(config.accounts().is_err());
+    }
+
+    #[test]
+    fn test_handler_has_no_container() {
+        let handler = ExternalL1Config::new("http://l1:8545", KEY_0)
+            .handler()
+            .unwrap();
+        assert!(handler.is_external());
+        assert_eq!(handler.l1_rpc_url.as_str(), "http://l1:8545/");
+        assert_eq!(handler.l1_host_url, Some(handler.l1_rpc_url.clone()));
+        assert_eq!(handler.l1_ws_url, None);
+    }
+
+    #[test]
+    fn test_handler_ws_url() {
+        let handler = ExternalL1Config::new("http://l1:8545", KEY_0)
+            .ws_url("ws://l1:8546")
+            .handler()
+            .unwrap();
+        assert_eq!(handler.l1_ws_url.unwrap().as_str(), "ws://l1:8546/");
+
+        assert!(
+            ExternalL1Config::new("http://l1:8545", KEY_0)
+                .ws_url("http://l1:8546")
+                .handler()
+                .is_err()
+        );
+    }
+}
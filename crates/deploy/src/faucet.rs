@@ -1,7 +1,11 @@
-//! Faucet module for bridging ETH from L1 (Anvil) to L2 via OptimismPortal deposit.
+//! Faucet module for bridging ETH from L1 (Anvil) to L2 via OptimismPortal deposit,
+//! and ERC-20 tokens via the L1StandardBridge.
 
+use std::fmt;
 use std::path::Path;
+use std::str::FromStr;
 
+use alloy_core::primitives::U256;
 use anyhow::{Context, Result};
 use serde_json::Value;
 
@@ -14,95 +18,576 @@ pub struct FaucetResult {
     pub l1_tx_hash: String,
     /// L2 balance after deposit (if `--wait` was used).
     pub l2_balance: Option<String>,
+    /// Hash of the L2 deposit transaction (if `--wait` was used and it was found).
+    pub l2_tx_hash: Option<String>,
+}
+
+/// Number of wei in one ETH.
+const WEI_PER_ETH: u128 = 1_000_000_000_000_000_000;
+
+/// Number of decimal places of an ETH amount expressed in wei.
+const ETH_DECIMALS: usize = 18;
+
+/// An exact amount of ETH, stored in wei.
+///
+/// Parsing from a decimal string (`"0.123456789012345678"`) is exact down to the wei;
+/// more than 18 decimal places is an error rather than a silent rounding.
+/// [`EthAmount::from_eth_f64`] is a convenience for callers holding an `f64`: it goes
+/// through the float's shortest decimal representation, so `0.1` is exactly 10^17 wei,
+/// but floats cannot carry more than ~17 significant digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EthAmount(u128);
+
+impl EthAmount {
+    /// An amount in wei.
+    pub const fn from_wei(wei: u128) -> Self {
+        Self(wei)
+    }
+
+    /// A whole number of ETH.
+    pub const fn from_ether(eth: u64) -> Self {
+        Self(eth as u128 * WEI_PER_ETH)
+    }
+
+    /// Convert an `f64` ETH amount, via its shortest decimal representation.
+    ///
+    /// Digits beyond 18 decimal places (below one wei) are truncated.
+    pub fn from_eth_f64(eth: f64) -> Result<Self> {
+        if !eth.is_finite() || eth < 0.0 {
+            anyhow::bail!("Invalid ETH amount: {}", eth);
+        }
+
+        let decimal = eth.to_string();
+        let decimal = match decimal.split_once('.') {
+            Some((int, frac)) if frac.len() > ETH_DECIMALS => {
+                format!("{}.{}", int, &frac[..ETH_DECIMALS])
+            }
+            _ => decimal,
+        };
+        decimal.parse()
+    }
+
+    /// The amount in wei.
+    pub const fn wei(self) -> u128 {
+        self.0
+    }
+}
+
+impl FromStr for EthAmount {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (int.is_empty() && frac.is_empty()) || !is_digits(int) || !is_digits(frac) {
+            anyhow::bail!("Invalid ETH amount '{}': expected a decimal number", s);
+        }
+        if frac.len() > ETH_DECIMALS {
+            anyhow::bail!(
+                "Invalid ETH amount '{}': at most {} decimal places (1 wei) are supported",
+                s,
+                ETH_DECIMALS
+            );
+        }
+
+        let int_wei = if int.is_empty() {
+            0
+        } else {
+            int.parse::<u128>()
+                .ok()
+                .and_then(|eth| eth.checked_mul(WEI_PER_ETH))
+                .with_context(|| format!("ETH amount '{}' is too large", s))?
+        };
+        let frac_wei = if frac.is_empty() {
+            0
+        } else {
+            format!("{:0<width$}", frac, width = ETH_DECIMALS).parse::<u128>()?
+        };
+
+        int_wei
+            .checked_add(frac_wei)
+            .map(Self)
+            .with_context(|| format!("ETH amount '{}' is too large", s))
+    }
+}
+
+impl fmt::Display for EthAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let int = self.0 / WEI_PER_ETH;
+        let frac = self.0 % WEI_PER_ETH;
+        if frac == 0 {
+            return write!(f, "{}", int);
+        }
+        let frac = format!("{:0>width$}", frac, width = ETH_DECIMALS);
+        write!(f, "{}.{}", int, frac.trim_end_matches('0'))
+    }
+}
+
+/// Default time to wait for L1 confirmations and for the deposit to land on L2, in seconds.
+pub const DEFAULT_WAIT_TIMEOUT_SECS: u64 = 120;
+
+/// Options controlling how long and for what a faucet deposit waits.
+#[derive(Debug, Clone, Copy)]
+pub struct FaucetOptions {
+    /// Wait for the deposit to appear on L2 before returning.
+    pub wait: bool,
+    /// Timeout for each waiting phase (L1 confirmations, L2 arrival), in seconds.
+    pub wait_timeout_secs: u64,
+    /// Number of L1 confirmations the deposit transaction needs before it is
+    /// considered sent. 0 returns as soon as the transaction is submitted.
+    pub l1_confirmations: u64,
+}
+
+impl Default for FaucetOptions {
+    fn default() -> Self {
+        Self {
+            wait: false,
+            wait_timeout_secs: DEFAULT_WAIT_TIMEOUT_SECS,
+            l1_confirmations: 0,
+        }
+    }
 }
 
 /// Execute a faucet deposit: bridge ETH from L1 to L2 via OptimismPortal.
 ///
-/// Sends `amount_eth` from the Anvil deployer account (index 0) to the
+/// Sends `amount` from the Anvil deployer account (index 0) to the
 /// `OptimismPortalProxy` contract, which creates a deposit transaction on L2
 /// that mints the corresponding ETH to `to_address`.
 pub async fn faucet_deposit(
     docker: &KupDocker,
     deployer: &Deployer,
     to_address: &str,
-    amount_eth: f64,
+    amount: EthAmount,
     wait: bool,
+) -> Result<FaucetResult> {
+    faucet_deposit_with_options(
+        docker,
+        deployer,
+        to_address,
+        amount,
+        FaucetOptions {
+            wait,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Execute a faucet deposit with explicit waiting options.
+///
+/// See [`faucet_deposit`]. Use this on slow or forked L1s where the default
+/// timeout is too short, or to require L1 confirmations before returning.
+pub async fn faucet_deposit_with_options(
+    docker: &KupDocker,
+    deployer: &Deployer,
+    to_address: &str,
+    amount: EthAmount,
+    options: FaucetOptions,
 ) -> Result<FaucetResult> {
     validate_address(to_address)?;
 
-    let client = rpc::create_client()?;
+    let client = docker.rpc_client.clone();
 
-    let deployer_address = load_deployer_address(&deployer.outdata)?;
+    let deployer_address = load_deployer_address(&deployer.outdata).await?;
     let portal_address = load_optimism_portal_address(&deployer.outdata)?;
 
     let l1_url = build_host_rpc_url(docker, &deployer.anvil.container_name, deployer.anvil.port)
         .await
         .context("Failed to build L1 RPC URL - is Anvil running?")?;
 
-    let amount_wei = eth_to_wei(amount_eth);
+    // Sampled before sending, as the deposit can land on L2 before the wait starts.
+    let l2_start = if options.wait {
+        let l2_url = l2_rpc_url(docker, deployer).await?;
+        let start = sample_l2_deposit_start(&client, &l2_url, to_address).await?;
+        Some((l2_url, start))
+    } else {
+        None
+    };
+
+    let amount_wei = amount.wei();
     let calldata = encode_deposit_transaction(to_address, amount_wei, 100_000);
     let value_hex = format!("0x{:x}", amount_wei);
 
-    let tx_hash: String = rpc::json_rpc_call(
+    let tx_hash = rpc::send_unlocked_transaction(
         &client,
         &l1_url,
-        "eth_sendTransaction",
-        vec![serde_json::json!({
+        serde_json::json!({
             "from": deployer_address,
             "to": portal_address,
             "value": value_hex,
             "data": calldata,
             "gas": "0x100000"
-        })],
+        }),
+        "deposit",
     )
-    .await
-    .context("Failed to send deposit transaction")?;
+    .await?;
 
     tracing::info!(tx_hash = %tx_hash, "Deposit transaction sent on L1");
 
-    let l2_balance = if wait {
-        Some(wait_for_l2_deposit(docker, &client, deployer, to_address, 120).await?)
-    } else {
-        None
+    if options.l1_confirmations > 0 {
+        wait_for_l1_confirmations(
+            &client,
+            &l1_url,
+            &tx_hash,
+            options.l1_confirmations,
+            options.wait_timeout_secs,
+        )
+        .await?;
+    }
+
+    let (l2_balance, l2_tx_hash) = match l2_start {
+        Some((l2_url, start)) => {
+            let deposit = wait_for_l2_deposit(
+                &client,
+                &l2_url,
+                &start,
+                &deployer_address,
+                to_address,
+                amount_wei,
+                options.wait_timeout_secs,
+            )
+            .await?;
+            (Some(deposit.balance), deposit.tx_hash)
+        }
+        None => (None, None),
     };
 
     Ok(FaucetResult {
         l1_tx_hash: tx_hash,
         l2_balance,
+        l2_tx_hash,
     })
 }
 
-/// Wait for an L2 deposit by polling `eth_getBalance` until the balance changes.
-async fn wait_for_l2_deposit(
-    kup_docker: &KupDocker,
-    client: &reqwest::Client,
+/// Minimum L2 gas limit of the ERC-20 deposit message relayed by the bridge.
+const ERC20_DEPOSIT_MIN_GAS_LIMIT: u32 = 200_000;
+
+/// Result of an ERC-20 faucet deposit.
+#[derive(Debug)]
+pub struct Erc20FaucetResult {
+    /// Hash of the L1 `depositERC20To` transaction.
+    pub l1_tx_hash: String,
+    /// L2 token balance of the recipient after the deposit (if `--wait` was used),
+    /// in the token's smallest unit.
+    pub l2_balance: Option<U256>,
+}
+
+/// Execute an ERC-20 faucet deposit: bridge tokens from L1 to L2 via the L1StandardBridge.
+///
+/// Approves the `L1StandardBridgeProxy` to spend `amount` of `l1_token` from the Anvil
+/// deployer account (index 0), then calls `depositERC20To`, which mints `amount` of
+/// `l2_token` to `to_address` on L2. The deployer account must hold the L1 tokens.
+///
+/// `l2_token` must be an `OptimismMintableERC20` deployed on L2 whose remote token is
+/// `l1_token`, otherwise the deposit is rejected before anything is sent. `amount` is
+/// expressed in the token's smallest unit.
+pub async fn faucet_deposit_erc20(
+    docker: &KupDocker,
     deployer: &Deployer,
+    l1_token: &str,
+    l2_token: &str,
     to_address: &str,
-    timeout_secs: u64,
-) -> Result<String> {
-    let seq = &deployer.l2_stack.sequencers[0];
-    let l2_url = build_host_rpc_url(
-        kup_docker,
-        &seq.op_reth.container_name,
-        seq.op_reth.http_port,
+    amount: u128,
+    wait: bool,
+) -> Result<Erc20FaucetResult> {
+    faucet_deposit_erc20_with_options(
+        docker,
+        deployer,
+        l1_token,
+        l2_token,
+        to_address,
+        amount,
+        FaucetOptions {
+            wait,
+            ..Default::default()
+        },
     )
     .await
-    .context("Failed to build L2 RPC URL - is the sequencer running?")?;
+}
+
+/// Execute an ERC-20 faucet deposit with explicit waiting options.
+///
+/// See [`faucet_deposit_erc20`] and [`faucet_deposit_with_options`].
+pub async fn faucet_deposit_erc20_with_options(
+    docker: &KupDocker,
+    deployer: &Deployer,
+    l1_token: &str,
+    l2_token: &str,
+    to_address: &str,
+    amount: u128,
+    options: FaucetOptions,
+) -> Result<Erc20FaucetResult> {
+    validate_address(l1_token).context("Invalid L1 token")?;
+    validate_address(l2_token).context("Invalid L2 token")?;
+    validate_address(to_address)?;
+
+    let client = docker.rpc_client.clone();
+
+    let deployer_address = load_deployer_address(&deployer.outdata).await?;
+    let bridge_address = load_l1_standard_bridge_address(&deployer.outdata)?;
+
+    let l2_url = l2_rpc_url(docker, deployer).await?;
+    check_l2_token_pair(&client, &l2_url, l1_token, l2_token).await?;
+
+    let l1_url = build_host_rpc_url(docker, &deployer.anvil.container_name, deployer.anvil.port)
+        .await
+        .context("Failed to build L1 RPC URL - is Anvil running?")?;
+
+    let initial_balance = if options.wait {
+        Some(erc20_balance_of(&client, &l2_url, l2_token, to_address).await?)
+    } else {
+        None
+    };
+
+    // Anvil mines the transactions of an account in nonce order, so the deposit
+    // always sees the approval.
+    let approve_hash = rpc::send_unlocked_transaction(
+        &client,
+        &l1_url,
+        serde_json::json!({
+            "from": deployer_address,
+            "to": l1_token,
+            "data": encode_approve(&bridge_address, amount),
+            "gas": "0x100000"
+        }),
+        "token approval",
+    )
+    .await?;
+
+    tracing::info!(tx_hash = %approve_hash, "Token approval sent on L1");
 
-    let initial_balance: String = rpc::json_rpc_call(
+    let tx_hash = rpc::send_unlocked_transaction(
+        &client,
+        &l1_url,
+        serde_json::json!({
+            "from": deployer_address,
+            "to": bridge_address,
+            "data": encode_deposit_erc20_to(
+                l1_token,
+                l2_token,
+                to_address,
+                amount,
+                ERC20_DEPOSIT_MIN_GAS_LIMIT,
+            ),
+            "gas": "0x100000"
+        }),
+        "ERC-20 deposit",
+    )
+    .await?;
+
+    tracing::info!(tx_hash = %tx_hash, "ERC-20 deposit transaction sent on L1");
+
+    if options.l1_confirmations > 0 {
+        wait_for_l1_confirmations(
+            &client,
+            &l1_url,
+            &tx_hash,
+            options.l1_confirmations,
+            options.wait_timeout_secs,
+        )
+        .await?;
+    }
+
+    let l2_balance = match initial_balance {
+        Some(initial) => {
+            tracing::info!(initial_balance = %initial, "Waiting for L2 token deposit...");
+            rpc::wait_until_ready("L2 token deposit", options.wait_timeout_secs, || async {
+                let balance = erc20_balance_of(&client, &l2_url, l2_token, to_address).await?;
+                if balance != initial {
+                    Ok(())
+                } else {
+                    anyhow::bail!("Token balance unchanged: {}", balance)
+                }
+            })
+            .await?;
+            Some(erc20_balance_of(&client, &l2_url, l2_token, to_address).await?)
+        }
+        None => None,
+    };
+
+    Ok(Erc20FaucetResult {
+        l1_tx_hash: tx_hash,
+        l2_balance,
+    })
+}
+
+/// Check that `l2_token` is an `OptimismMintableERC20` bridged from `l1_token`.
+async fn check_l2_token_pair(
+    client: &reqwest::Client,
+    l2_url: &str,
+    l1_token: &str,
+    l2_token: &str,
+) -> Result<()> {
+    let result: String = rpc::json_rpc_call(
+        client,
+        l2_url,
+        "eth_call",
+        vec![
+            serde_json::json!({ "to": l2_token, "data": REMOTE_TOKEN_CALLDATA }),
+            serde_json::json!("latest"),
+        ],
+    )
+    .await
+    .with_context(|| {
+        format!("L2 token {l2_token} is not a bridged token (remoteToken() call failed)")
+    })?;
+
+    let remote = decode_address(&result).with_context(|| {
+        format!("L2 token {l2_token} is not a bridged token (no remoteToken() on L2)")
+    })?;
+    if !remote.eq_ignore_ascii_case(l1_token) {
+        anyhow::bail!(
+            "L2 token {} is bridged from {}, not from L1 token {}",
+            l2_token,
+            remote,
+            l1_token
+        );
+    }
+
+    Ok(())
+}
+
+/// Query the `balanceOf` of `owner` on an ERC-20 contract.
+async fn erc20_balance_of(
+    client: &reqwest::Client,
+    url: &str,
+    token: &str,
+    owner: &str,
+) -> Result<U256> {
+    let result: String = rpc::json_rpc_call(
         client,
-        &l2_url,
+        url,
+        "eth_call",
+        vec![
+            serde_json::json!({ "to": token, "data": encode_balance_of(owner) }),
+            serde_json::json!("latest"),
+        ],
+    )
+    .await
+    .context("Failed to query token balance")?;
+
+    U256::from_str_radix(result.trim_start_matches("0x"), 16)
+        .with_context(|| format!("Invalid balanceOf result: '{}'", result))
+}
+
+/// Host RPC URL of the first sequencer's op-reth.
+pub(crate) async fn l2_rpc_url(docker: &KupDocker, deployer: &Deployer) -> Result<String> {
+    let seq = &deployer.l2_stack.sequencers[0];
+    build_host_rpc_url(docker, &seq.op_reth.container_name, seq.op_reth.http_port)
+        .await
+        .context("Failed to build L2 RPC URL - is the sequencer running?")
+}
+
+/// Wait until the L1 deposit transaction has `confirmations` confirmations.
+///
+/// Fails if the transaction reverted.
+async fn wait_for_l1_confirmations(
+    client: &reqwest::Client,
+    l1_url: &str,
+    tx_hash: &str,
+    confirmations: u64,
+    timeout_secs: u64,
+) -> Result<()> {
+    tracing::info!(confirmations, "Waiting for L1 deposit confirmations...");
+
+    rpc::wait_until_ready("L1 deposit confirmations", timeout_secs, || async {
+        let receipt: Value = rpc::json_rpc_call(
+            client,
+            l1_url,
+            "eth_getTransactionReceipt",
+            vec![serde_json::json!(tx_hash)],
+        )
+        .await?;
+        let mined_in = hex_field(&receipt, "blockNumber").context("Deposit not mined yet")?;
+
+        let latest: String = rpc::json_rpc_call(client, l1_url, "eth_blockNumber", vec![]).await?;
+        let latest = u64::from_str_radix(latest.trim_start_matches("0x"), 16)?;
+
+        let current = confirmation_count(mined_in, latest);
+        if current >= confirmations {
+            Ok(())
+        } else {
+            anyhow::bail!("{current}/{confirmations} confirmations")
+        }
+    })
+    .await?;
+
+    rpc::wait_for_receipt(client, l1_url, tx_hash, "L1 deposit", timeout_secs).await?;
+    Ok(())
+}
+
+/// Number of confirmations of a transaction mined in `mined_in`, given the latest block.
+fn confirmation_count(mined_in: u64, latest: u64) -> u64 {
+    (latest + 1).saturating_sub(mined_in)
+}
+
+/// Parse a hex-encoded quantity field of a JSON-RPC object.
+pub(crate) fn hex_field(value: &Value, key: &str) -> Option<u64> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|v| u64::from_str_radix(v.trim_start_matches("0x"), 16).ok())
+}
+
+/// L2 state sampled before a deposit is sent, against which [`wait_for_l2_deposit`]
+/// detects it.
+struct L2DepositStart {
+    /// Recipient balance before the deposit.
+    balance: String,
+    /// Latest L2 block before the deposit.
+    block: u64,
+}
+
+/// Sample the recipient balance and the latest L2 block before sending a deposit.
+async fn sample_l2_deposit_start(
+    client: &reqwest::Client,
+    l2_url: &str,
+    to_address: &str,
+) -> Result<L2DepositStart> {
+    let balance: String = rpc::json_rpc_call(
+        client,
+        l2_url,
         "eth_getBalance",
         vec![serde_json::json!(to_address), serde_json::json!("latest")],
     )
     .await
     .context("Failed to get initial L2 balance")?;
 
-    tracing::info!(initial_balance = %initial_balance, "Waiting for L2 deposit...");
+    let block: String = rpc::json_rpc_call(client, l2_url, "eth_blockNumber", vec![])
+        .await
+        .context("Failed to get initial L2 block number")?;
+    let block = u64::from_str_radix(block.trim_start_matches("0x"), 16)
+        .context("Failed to parse L2 block number")?;
+
+    Ok(L2DepositStart { balance, block })
+}
+
+/// An L2 deposit observed by [`wait_for_l2_deposit`].
+struct L2Deposit {
+    /// Recipient balance after the deposit.
+    balance: String,
+    /// Hash of the matching deposit transaction, if found.
+    tx_hash: Option<String>,
+}
+
+/// Wait for an L2 deposit by polling `eth_getBalance` until the balance differs from
+/// `start`, then look up the deposit transaction in the blocks produced since.
+async fn wait_for_l2_deposit(
+    client: &reqwest::Client,
+    l2_url: &str,
+    start: &L2DepositStart,
+    from_address: &str,
+    to_address: &str,
+    value_wei: u128,
+    timeout_secs: u64,
+) -> Result<L2Deposit> {
+    tracing::info!(initial_balance = %start.balance, "Waiting for L2 deposit...");
 
     let client_ref = client.clone();
-    let l2_url_ref = l2_url.clone();
-    let initial_ref = initial_balance.clone();
+    let l2_url_ref = l2_url.to_string();
+    let initial_ref = start.balance.clone();
     let to_ref = to_address.to_string();
 
     rpc::wait_until_ready("L2 deposit", timeout_secs, || {
@@ -130,44 +615,126 @@ async fn wait_for_l2_deposit(
 
     let final_balance: String = rpc::json_rpc_call(
         client,
-        &l2_url,
+        l2_url,
         "eth_getBalance",
         vec![serde_json::json!(to_address), serde_json::json!("latest")],
     )
     .await
     .context("Failed to get final L2 balance")?;
 
-    Ok(final_balance)
+    let tx_hash = find_l2_deposit_tx(
+        client,
+        l2_url,
+        start.block,
+        from_address,
+        to_address,
+        value_wei,
+    )
+    .await?;
+    if tx_hash.is_none() {
+        tracing::warn!("L2 balance changed but no matching deposit transaction was found");
+    }
+
+    Ok(L2Deposit {
+        balance: final_balance,
+        tx_hash,
+    })
+}
+
+/// Scan the L2 blocks after `after_block`, up to the latest one, for the deposit
+/// transaction.
+///
+/// `after_block` is sampled before the deposit is sent: an identical deposit made
+/// earlier cannot match, while one that landed before the wait started still does.
+async fn find_l2_deposit_tx(
+    client: &reqwest::Client,
+    l2_url: &str,
+    after_block: u64,
+    from_address: &str,
+    to_address: &str,
+    value_wei: u128,
+) -> Result<Option<String>> {
+    let latest: String = rpc::json_rpc_call(client, l2_url, "eth_blockNumber", vec![])
+        .await
+        .context("Failed to get latest L2 block number")?;
+    let latest = u64::from_str_radix(latest.trim_start_matches("0x"), 16)
+        .context("Failed to parse L2 block number")?;
+
+    for number in after_block + 1..=latest {
+        let block: Value = rpc::json_rpc_call(
+            client,
+            l2_url,
+            "eth_getBlockByNumber",
+            vec![
+                serde_json::json!(format!("0x{number:x}")),
+                serde_json::json!(true),
+            ],
+        )
+        .await
+        .with_context(|| format!("Failed to get L2 block {number}"))?;
+
+        if let Some(hash) = match_deposit_tx(&block, from_address, to_address, value_wei) {
+            return Ok(Some(hash));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Find a user deposit transaction (type `0x7e`) in a block by from/to/value.
+fn match_deposit_tx(block: &Value, from: &str, to: &str, value_wei: u128) -> Option<String> {
+    let field_eq = |tx: &Value, key: &str, expected: &str| {
+        tx[key]
+            .as_str()
+            .is_some_and(|v| v.eq_ignore_ascii_case(expected))
+    };
+
+    block["transactions"]
+        .as_array()?
+        .iter()
+        .find(|tx| {
+            tx["type"].as_str() == Some("0x7e")
+                && field_eq(tx, "from", from)
+                && field_eq(tx, "to", to)
+                && tx["value"]
+                    .as_str()
+                    .and_then(|v| u128::from_str_radix(v.trim_start_matches("0x"), 16).ok())
+                    == Some(value_wei)
+        })
+        .and_then(|tx| tx["hash"].as_str())
+        .map(String::from)
 }
 
 /// Load the deployer address (account index 0) from `anvil.json`.
-fn load_deployer_address(outdata: &Path) -> Result<String> {
-    let anvil_path = outdata.join("anvil/anvil.json");
-    let content = std::fs::read_to_string(&anvil_path)
-        .with_context(|| format!("Failed to read {}", anvil_path.display()))?;
-    let data: Value = serde_json::from_str(&content).context("Failed to parse anvil.json")?;
+pub(crate) async fn load_deployer_address(outdata: &Path) -> Result<String> {
+    let data = crate::json_file::read_anvil_json(outdata).await?;
 
     data["available_accounts"][0]
         .as_str()
-        .context("Deployer address (account 0) not found in anvil.json")
+        .with_context(|| {
+            format!(
+                "Deployer address (account 0) not found in anvil.json; expected {}",
+                crate::json_file::ANVIL_JSON_SHAPE
+            )
+        })
         .map(String::from)
 }
 
 /// Load the `OptimismPortalProxy` address from `state.json`.
-fn load_optimism_portal_address(outdata: &Path) -> Result<String> {
-    let state_path = outdata.join("l2-stack/state.json");
-    let content = std::fs::read_to_string(&state_path)
-        .with_context(|| format!("Failed to read {}", state_path.display()))?;
-    let data: Value = serde_json::from_str(&content).context("Failed to parse state.json")?;
+pub(crate) fn load_optimism_portal_address(outdata: &Path) -> Result<String> {
+    let state = crate::json_file::read_state_json(&outdata.join("l2-stack"))?;
+    crate::json_file::state_chain_address(&state, "OptimismPortalProxy")
+}
 
-    data["opChainDeployments"][0]["OptimismPortalProxy"]
-        .as_str()
-        .context("OptimismPortalProxy address not found in state.json")
-        .map(String::from)
+/// Load the `L1StandardBridgeProxy` address from `state.json`.
+fn load_l1_standard_bridge_address(outdata: &Path) -> Result<String> {
+    let state = crate::json_file::read_state_json(&outdata.join("l2-stack"))?;
+    crate::json_file::state_chain_address(&state, "L1StandardBridgeProxy")
+        .context("L1StandardBridge is not configured for this network")
 }
 
 /// Validate an Ethereum address format (0x-prefixed, 40 hex chars).
-fn validate_address(addr: &str) -> Result<()> {
+pub(crate) fn validate_address(addr: &str) -> Result<()> {
     if !addr.starts_with("0x") || addr.len() != 42 {
         anyhow::bail!(
             "Invalid address format: expected 0x-prefixed 40 hex chars, got '{}'",
@@ -182,15 +749,6 @@ fn validate_address(addr: &str) -> Result<()> {
     Ok(())
 }
 
-/// Convert ETH amount (f64) to wei.
-///
-/// Rounds to gwei precision (9 decimal places) to avoid floating-point noise,
-/// then scales to wei. Gwei precision is more than sufficient for a dev faucet.
-fn eth_to_wei(eth: f64) -> u128 {
-    let gwei = (eth * 1e9).round() as u128;
-    gwei * 1_000_000_000u128
-}
-
 /// ABI-encode a `depositTransaction` call.
 ///
 /// Function: `depositTransaction(address,uint256,uint64,bool,bytes)`
@@ -222,6 +780,58 @@ fn encode_deposit_transaction(to: &str, value: u128, gas_limit: u64) -> String {
     )
 }
 
+/// Calldata of `remoteToken()` (selector `0xd6c0b2c4`).
+const REMOTE_TOKEN_CALLDATA: &str = "0xd6c0b2c4";
+
+/// ABI-encode an address as a 32-byte word (without `0x`).
+fn abi_address(addr: &str) -> String {
+    format!("{:0>64}", addr.trim_start_matches("0x").to_lowercase())
+}
+
+/// Decode the address returned by a call as a 32-byte word.
+fn decode_address(result: &str) -> Option<String> {
+    let word = result.trim_start_matches("0x");
+    if word.len() != 64 || !word.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("0x{}", &word[24..]))
+}
+
+/// ABI-encode an `approve(address,uint256)` call (selector `0x095ea7b3`).
+fn encode_approve(spender: &str, amount: u128) -> String {
+    format!("0x095ea7b3{}{:064x}", abi_address(spender), amount)
+}
+
+/// ABI-encode a `balanceOf(address)` call (selector `0x70a08231`).
+fn encode_balance_of(owner: &str) -> String {
+    format!("0x70a08231{}", abi_address(owner))
+}
+
+/// ABI-encode a `depositERC20To` call with empty extra data.
+///
+/// Function: `depositERC20To(address,address,address,uint256,uint32,bytes)`
+/// Selector: `0x838b2520`
+fn encode_deposit_erc20_to(
+    l1_token: &str,
+    l2_token: &str,
+    to: &str,
+    amount: u128,
+    min_gas_limit: u32,
+) -> String {
+    format!(
+        "0x838b2520{}{}{}{:064x}{:064x}{:064x}{:064x}",
+        abi_address(l1_token),
+        abi_address(l2_token),
+        abi_address(to),
+        amount,
+        min_gas_limit,
+        // Offset to the `bytes` data: 6 head words * 32 bytes
+        192u64,
+        // Empty bytes: length = 0
+        0u64,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,12 +852,123 @@ mod tests {
     }
 
     #[test]
-    fn test_eth_to_wei() {
-        assert_eq!(eth_to_wei(1.0), 1_000_000_000_000_000_000);
-        assert_eq!(eth_to_wei(0.1), 100_000_000_000_000_000);
-        assert_eq!(eth_to_wei(10.0), 10_000_000_000_000_000_000);
+    fn test_eth_amount_from_f64() {
+        let wei = |eth: f64| EthAmount::from_eth_f64(eth).unwrap().wei();
+        assert_eq!(wei(1.0), 1_000_000_000_000_000_000);
+        assert_eq!(wei(0.1), 100_000_000_000_000_000);
+        assert_eq!(wei(10.0), 10_000_000_000_000_000_000);
         // Verify precision: 0.7 ETH = 700000000000000000 wei
-        assert_eq!(eth_to_wei(0.7), 700_000_000_000_000_000);
+        assert_eq!(wei(0.7), 700_000_000_000_000_000);
+        assert_eq!(wei(0.123456789012), 123_456_789_012_000_000);
+        assert!(EthAmount::from_eth_f64(-1.0).is_err());
+        assert!(EthAmount::from_eth_f64(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_eth_amount_parse_is_exact() {
+        let parse = |s: &str| s.parse::<EthAmount>().map(EthAmount::wei);
+        assert_eq!(
+            parse("0.123456789012345678").unwrap(),
+            123_456_789_012_345_678
+        );
+        assert_eq!(parse("1").unwrap(), 1_000_000_000_000_000_000);
+        assert_eq!(parse(".5").unwrap(), 500_000_000_000_000_000);
+        assert_eq!(parse("2.").unwrap(), 2_000_000_000_000_000_000);
+        assert_eq!(parse("0.000000000000000001").unwrap(), 1);
+        assert!(parse("0.0000000000000000001").is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse("1e18").is_err());
+        assert!(parse(".").is_err());
+        assert!(parse("").is_err());
+        assert!(parse("1000000000000000000000000").is_err());
+    }
+
+    #[test]
+    fn test_eth_amount_display() {
+        for s in ["0", "1", "0.5", "10", "0.123456789012345678"] {
+            assert_eq!(s.parse::<EthAmount>().unwrap().to_string(), s);
+        }
+        assert_eq!(EthAmount::from_wei(1).to_string(), "0.000000000000000001");
+        assert_eq!(EthAmount::from_ether(3).to_string(), "3");
+    }
+
+    #[test]
+    fn test_match_deposit_tx() {
+        let from = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+        let to = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        let block = serde_json::json!({
+            "transactions": [
+                // L1 info deposit
+                { "type": "0x7e", "hash": "0x01", "from": "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001",
+                  "to": "0x4200000000000000000000000000000000000015", "value": "0x0" },
+                // Same recipient, different value
+                { "type": "0x7e", "hash": "0x02", "from": from, "to": to, "value": "0x1" },
+                { "type": "0x7e", "hash": "0x03", "from": from,
+                  "to": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8", "value": "0xde0b6b3a7640000" },
+            ]
+        });
+
+        assert_eq!(
+            match_deposit_tx(&block, from, to, EthAmount::from_ether(1).wei()),
+            Some("0x03".to_string())
+        );
+        assert_eq!(
+            match_deposit_tx(&block, from, to, EthAmount::from_ether(2).wei()),
+            None
+        );
+        assert_eq!(match_deposit_tx(&serde_json::json!({}), from, to, 1), None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_l2_deposit_landed_before_the_wait() {
+        const FROM: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+        const TO: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+
+        // The deposit landed in block 6, before the wait started. Block 5, the latest
+        // one when the deposit was sent, holds an identical earlier deposit.
+        let l2_url = crate::rpc::tests::serve_json_rpc(|method, params| match method {
+            "eth_getBalance" => serde_json::json!("0x2"),
+            "eth_blockNumber" => serde_json::json!("0x6"),
+            "eth_getBlockByNumber" => {
+                let hash = match params[0].as_str().unwrap() {
+                    "0x5" => "0x05",
+                    "0x6" => "0x06",
+                    number => panic!("unexpected block {number}"),
+                };
+                serde_json::json!({ "transactions": [
+                    { "type": "0x7e", "hash": hash, "from": FROM, "to": TO, "value": "0x1" },
+                ]})
+            }
+            method => panic!("unexpected method {method}"),
+        })
+        .await;
+
+        let start = L2DepositStart {
+            balance: "0x1".to_string(),
+            block: 5,
+        };
+        let deposit = wait_for_l2_deposit(
+            &crate::rpc::create_client().unwrap(),
+            &l2_url,
+            &start,
+            FROM,
+            TO,
+            1,
+            5,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(deposit.balance, "0x2");
+        assert_eq!(deposit.tx_hash.as_deref(), Some("0x06"));
+    }
+
+    #[test]
+    fn test_confirmation_count() {
+        assert_eq!(confirmation_count(10, 10), 1);
+        assert_eq!(confirmation_count(10, 12), 3);
+        // Latest lagging behind the receipt (e.g. load-balanced RPC)
+        assert_eq!(confirmation_count(10, 9), 0);
     }
 
     #[test]
@@ -285,4 +1006,48 @@ mod tests {
             "0000000000000000000000000000000000000000000000000de0b6b3a7640000"
         );
     }
+
+    #[test]
+    fn test_encode_deposit_erc20_to() {
+        let l1 = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let l2 = "0x4200000000000000000000000000000000000042";
+        let to = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        let calldata = encode_deposit_erc20_to(l1, l2, to, 1000, 200_000);
+
+        assert!(calldata.starts_with("0x838b2520"));
+        // "0x" + 8 (selector) + 7 words of 64 hex chars
+        assert_eq!(calldata.len(), 2 + 8 + 7 * 64);
+
+        let word = |i: usize| &calldata[10 + i * 64..10 + (i + 1) * 64];
+        assert_eq!(word(0), abi_address(l1));
+        assert_eq!(word(1), abi_address(l2));
+        assert_eq!(word(2), abi_address(to));
+        assert_eq!(u64::from_str_radix(word(3), 16).unwrap(), 1000);
+        assert_eq!(u64::from_str_radix(word(4), 16).unwrap(), 200_000);
+        assert_eq!(u64::from_str_radix(word(5), 16).unwrap(), 192);
+        assert_eq!(u64::from_str_radix(word(6), 16).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_encode_approve_and_balance_of() {
+        let addr = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        assert_eq!(
+            encode_approve(addr, 1),
+            format!("0x095ea7b3{}{:064x}", abi_address(addr), 1)
+        );
+        assert_eq!(
+            encode_balance_of(addr),
+            "0x70a0823100000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+    }
+
+    #[test]
+    fn test_decode_address() {
+        assert_eq!(
+            decode_address("0x0000000000000000000000005fbdb2315678afecb367f032d93f642f64180aa3"),
+            Some("0x5fbdb2315678afecb367f032d93f642f64180aa3".to_string())
+        );
+        // Call to an account without code
+        assert_eq!(decode_address("0x"), None);
+    }
 }
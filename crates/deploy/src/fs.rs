@@ -6,6 +6,18 @@ use anyhow::Context;
 use notify::{Event, RecursiveMode, Watcher};
 use tokio::sync::watch;
 
+/// Bytes in a GiB.
+pub const GIB: u64 = 1024 * 1024 * 1024;
+
+/// Default minimum free disk space for the data directory, in GiB.
+pub const DEFAULT_MIN_DISK_SPACE_GIB: u64 = 5;
+
+/// Free disk space below which long-running devnets run out of space, in GiB.
+pub const RECOMMENDED_DISK_SPACE_GIB: u64 = 10;
+
+/// How often [`FsHandler::watch_disk_space`] checks the available disk space.
+const DISK_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
 pub struct FsHandler;
 
 impl FsHandler {
@@ -49,6 +61,67 @@ impl FsHandler {
         Ok(())
     }
 
+    /// Disk space available to unprivileged users on the filesystem holding `path`.
+    ///
+    /// `path` does not need to exist yet: its closest existing ancestor is checked.
+    pub fn available_space(path: &Path) -> anyhow::Result<u64> {
+        let absolute = std::path::absolute(path)
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+        let existing = absolute
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .context("No existing ancestor directory")?;
+        fs2::available_space(existing).with_context(|| {
+            format!(
+                "Failed to read the available disk space of {}",
+                existing.display()
+            )
+        })
+    }
+
+    /// Fail if less than `min_gib` GiB are available for `path`.
+    ///
+    /// Running out of space mid-run corrupts the op-reth database, so this is checked
+    /// before any node starts.
+    pub fn check_disk_space(path: &Path, min_gib: u64) -> anyhow::Result<()> {
+        let available = Self::available_space(path)?;
+        if available < min_gib.saturating_mul(GIB) {
+            anyhow::bail!(
+                "Only {:.1} GiB of disk space available for {}, at least {} GiB are required. \
+                 Free some space or lower the minimum with --min-disk-space (0 disables the check).",
+                available as f64 / GIB as f64,
+                path.display(),
+                min_gib
+            );
+        }
+        tracing::debug!(
+            path = %path.display(),
+            available_gib = available / GIB,
+            "Disk space check passed"
+        );
+        Ok(())
+    }
+
+    /// Periodically log a warning while less than `min_gib` GiB are available for `path`.
+    ///
+    /// Never returns; meant to run alongside a long-running deployment.
+    pub async fn watch_disk_space(path: &Path, min_gib: u64) {
+        let mut interval = tokio::time::interval(DISK_SPACE_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            match Self::available_space(path) {
+                Ok(available) if available < min_gib.saturating_mul(GIB) => tracing::warn!(
+                    path = %path.display(),
+                    available_gib = format!("{:.1}", available as f64 / GIB as f64),
+                    min_gib,
+                    "Low disk space: op-reth may corrupt its database if the disk fills up"
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::debug!(error = %e, "Failed to check disk space"),
+            }
+        }
+    }
+
     pub fn set_writable(path: &Path) -> anyhow::Result<()> {
         #[cfg(unix)]
         {
@@ -147,3 +220,22 @@ impl FsHandler {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_disk_space() {
+        let dir = tempdir::TempDir::new("kupcake-test").unwrap();
+        let missing = dir.path().join("data-test/l2-stack");
+
+        assert!(FsHandler::available_space(&missing).unwrap() > 0);
+        FsHandler::check_disk_space(&missing, 0).unwrap();
+
+        let err = FsHandler::check_disk_space(&missing, u64::MAX)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--min-disk-space"), "{err}");
+    }
+}
@@ -0,0 +1,160 @@
+//! In-memory history of L2 heads, to tell a stalled node from an advancing one.
+//!
+//! A single `optimism_syncStatus` snapshot cannot tell whether a node at block 1000 is
+//! still producing blocks. [`HeadTracker`] keeps the last few samples of each node with
+//! their timestamps, and derives the unsafe head rate from the oldest and newest one.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+use crate::services::SyncStatus;
+
+/// Default number of samples kept per node.
+pub const DEFAULT_HEAD_SAMPLES: usize = 10;
+
+/// L2 heads of a node at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadSample {
+    pub at: Instant,
+    pub unsafe_l2: u64,
+    pub safe_l2: u64,
+    pub finalized_l2: u64,
+}
+
+impl HeadSample {
+    /// Sample the heads of a sync status, taken now.
+    pub fn from_sync_status(status: &SyncStatus) -> Self {
+        Self {
+            at: Instant::now(),
+            unsafe_l2: status.unsafe_l2.number,
+            safe_l2: status.safe_l2.number,
+            finalized_l2: status.finalized_l2.number,
+        }
+    }
+}
+
+/// Rolling window of [`HeadSample`]s per node, keyed by node label.
+#[derive(Debug, Clone)]
+pub struct HeadTracker {
+    capacity: usize,
+    samples: HashMap<String, VecDeque<HeadSample>>,
+}
+
+impl Default for HeadTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_HEAD_SAMPLES)
+    }
+}
+
+impl HeadTracker {
+    /// Create a tracker keeping the last `capacity` samples of each node (at least 2).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(2),
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Record a sample for a node, dropping its oldest sample when the window is full.
+    pub fn record(&mut self, node: &str, sample: HeadSample) {
+        let samples = self.samples.entry(node.to_string()).or_default();
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Record the heads of a sync status for a node, taken now.
+    pub fn record_sync_status(&mut self, node: &str, status: &SyncStatus) {
+        self.record(node, HeadSample::from_sync_status(status));
+    }
+
+    /// Samples of a node, oldest first.
+    pub fn samples(&self, node: &str) -> impl Iterator<Item = &HeadSample> {
+        self.samples.get(node).into_iter().flatten()
+    }
+
+    /// Unsafe head rate of a node over the window, in blocks per second.
+    ///
+    /// None until the node has two samples taken at different times. A head that went
+    /// backwards (reorg, restart from a snapshot) counts as zero progress.
+    pub fn blocks_per_sec(&self, node: &str) -> Option<f64> {
+        let samples = self.samples.get(node)?;
+        let (first, last) = (samples.front()?, samples.back()?);
+        let elapsed = last.at.checked_duration_since(first.at)?.as_secs_f64();
+        if elapsed == 0.0 {
+            return None;
+        }
+        Some(last.unsafe_l2.saturating_sub(first.unsafe_l2) as f64 / elapsed)
+    }
+
+    /// Whether the unsafe head of a node moved over the window.
+    ///
+    /// None until the node has two samples taken at different times.
+    pub fn is_advancing(&self, node: &str) -> Option<bool> {
+        self.blocks_per_sec(node).map(|rate| rate > 0.0)
+    }
+
+    /// Forget the samples of a node, e.g. after it was restarted.
+    pub fn reset(&mut self, node: &str) {
+        self.samples.remove(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn sample(start: Instant, secs: u64, unsafe_l2: u64) -> HeadSample {
+        HeadSample {
+            at: start + Duration::from_secs(secs),
+            unsafe_l2,
+            safe_l2: 0,
+            finalized_l2: 0,
+        }
+    }
+
+    #[test]
+    fn test_blocks_per_sec() {
+        let start = Instant::now();
+        let mut tracker = HeadTracker::default();
+        assert_eq!(tracker.blocks_per_sec("sequencer"), None);
+
+        tracker.record("sequencer", sample(start, 0, 100));
+        assert_eq!(tracker.is_advancing("sequencer"), None);
+
+        tracker.record("sequencer", sample(start, 4, 102));
+        tracker.record("sequencer", sample(start, 8, 104));
+        assert_eq!(tracker.blocks_per_sec("sequencer"), Some(0.5));
+        assert_eq!(tracker.is_advancing("sequencer"), Some(true));
+    }
+
+    #[test]
+    fn test_stalled_node_is_not_advancing() {
+        let start = Instant::now();
+        let mut tracker = HeadTracker::default();
+        tracker.record("validator-1", sample(start, 0, 1000));
+        tracker.record("validator-1", sample(start, 10, 1000));
+
+        assert_eq!(tracker.blocks_per_sec("validator-1"), Some(0.0));
+        assert_eq!(tracker.is_advancing("validator-1"), Some(false));
+
+        tracker.reset("validator-1");
+        assert_eq!(tracker.is_advancing("validator-1"), None);
+    }
+
+    #[test]
+    fn test_window_drops_oldest_samples() {
+        let start = Instant::now();
+        let mut tracker = HeadTracker::new(2);
+        tracker.record("sequencer", sample(start, 0, 0));
+        tracker.record("sequencer", sample(start, 10, 10));
+        // The sequencer stalls: once the advancing samples leave the window, it shows.
+        tracker.record("sequencer", sample(start, 20, 10));
+
+        assert_eq!(tracker.samples("sequencer").count(), 2);
+        assert_eq!(tracker.is_advancing("sequencer"), Some(false));
+    }
+}
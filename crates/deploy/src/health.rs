@@ -1,30 +1,54 @@
 //! Health check module for verifying a deployed Kupcake network.
 
-use std::fmt;
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result};
 use bollard::Docker;
 use comfy_table::{Attribute, Cell, Color, Table};
+use serde::Serialize;
 use serde_json::Value;
 
-use crate::{Deployer, rpc};
+use crate::{
+    ContainerState, Deployer, TargetStatus,
+    head_tracker::{HeadSample, HeadTracker},
+    rpc,
+    status::container_state,
+};
 
 /// Health report for the entire network.
+///
+/// Serializes to JSON for machine consumption: values that could not be read, e.g.
+/// because an RPC was unreachable, are `null` rather than failing the whole report.
+#[derive(Debug, Serialize)]
 pub struct HealthReport {
     /// L1 (Anvil) health status.
     pub l1: L1Health,
-    /// Per-node health status (sequencers and validators).
+    /// Per-node health status: sequencers, then validators, in config order.
     pub nodes: Vec<NodeHealth>,
     /// Service health (batcher, proposer, challenger).
     pub services: Vec<ServiceHealth>,
-    /// Overall health: all containers running, chain IDs match, blocks advancing.
+    /// Monitoring stack health (Prometheus + Grafana). None if monitoring is disabled.
+    pub monitoring: Option<MonitoringHealth>,
+    /// Overall health: all containers running, chain IDs match, blocks advancing,
+    /// and (if enabled) monitoring endpoints up with every scrape target reporting `up`.
     pub healthy: bool,
 }
 
 /// Health status for the L1 (Anvil) node.
+#[derive(Debug, Serialize)]
 pub struct L1Health {
     pub container_name: String,
+    /// Whether the L1 is an external node (no container managed by kupcake).
+    pub external: bool,
     pub running: bool,
+    /// Container state, e.g. `Restarting` while Docker restarts a crashed container
+    /// under a restart policy, as opposed to `Stopped`. `Running` for a reachable
+    /// external L1.
+    pub state: ContainerState,
     pub chain_id: Option<u64>,
     pub expected_chain_id: u64,
     pub block_number: Option<u64>,
@@ -37,17 +61,27 @@ impl L1Health {
 }
 
 /// Health status for an L2 node (op-reth + kona-node pair).
+#[derive(Debug, Serialize)]
 pub struct NodeHealth {
     pub role: String,
     pub label: String,
     pub execution: ExecutionHealth,
     pub consensus: ConsensusHealth,
+    /// Whether the unsafe head moved across the samples of a [`HeadTracker`].
+    /// None when checked without a tracker or with fewer than two samples.
+    pub advancing: Option<bool>,
+    /// Unsafe head rate across the tracker samples, in blocks per second.
+    pub blocks_per_sec: Option<f64>,
 }
 
 /// Health status for an op-reth execution client.
+#[derive(Debug, Serialize)]
 pub struct ExecutionHealth {
     pub container_name: String,
     pub running: bool,
+    /// Container state, e.g. `Restarting` while Docker restarts a crashed container
+    /// under a restart policy, as opposed to `Stopped`.
+    pub state: ContainerState,
     pub chain_id: Option<u64>,
     pub expected_chain_id: u64,
     pub block_number: Option<u64>,
@@ -60,19 +94,66 @@ impl ExecutionHealth {
 }
 
 /// Health status for a kona-node consensus client.
+#[derive(Debug, Serialize)]
 pub struct ConsensusHealth {
     pub container_name: String,
     pub running: bool,
+    /// Container state (see [`ExecutionHealth::state`]).
+    pub state: ContainerState,
     pub unsafe_l2: Option<u64>,
     pub safe_l2: Option<u64>,
     pub finalized_l2: Option<u64>,
 }
 
 /// Health status for an infrastructure service (batcher, proposer, challenger).
+#[derive(Debug, Serialize)]
 pub struct ServiceHealth {
     pub name: String,
     pub container_name: String,
     pub running: bool,
+    /// Container state (see [`ExecutionHealth::state`]).
+    pub state: ContainerState,
+    /// RPC probe result. None when the service has no RPC, or it is not published on
+    /// the host, or the container is down.
+    pub rpc: Option<rpc::OpServiceProbe>,
+}
+
+impl ServiceHealth {
+    /// Running, with a responding RPC when it could be probed.
+    fn is_ok(&self) -> bool {
+        self.running && self.rpc.as_ref().is_none_or(|probe| probe.responding)
+    }
+}
+
+/// Health status for the monitoring stack.
+#[derive(Debug, Serialize)]
+pub struct MonitoringHealth {
+    pub prometheus: EndpointHealth,
+    pub grafana: EndpointHealth,
+    /// Expected Prometheus scrape targets and their reported state.
+    pub targets: Vec<ScrapeTargetHealth>,
+}
+
+/// Health status for an HTTP service exposing a health endpoint.
+#[derive(Debug, Serialize)]
+pub struct EndpointHealth {
+    pub name: String,
+    pub container_name: String,
+    pub running: bool,
+    /// Container state (see [`ExecutionHealth::state`]).
+    pub state: ContainerState,
+    /// Whether the health endpoint responded with a success status.
+    pub responding: bool,
+}
+
+/// State of a Prometheus scrape target.
+#[derive(Debug, Serialize)]
+pub struct ScrapeTargetHealth {
+    pub job_name: String,
+    /// Service label of the scraped service (e.g. `op-batcher`).
+    pub service: String,
+    /// Whether Prometheus reports every instance of the job as `up`.
+    pub up: bool,
 }
 
 /// Shared EVM node RPC data (chain_id + block_number).
@@ -86,16 +167,34 @@ pub async fn health_check(deployer: &Deployer) -> Result<HealthReport> {
     let docker =
         Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
 
-    let client = rpc::create_client()?;
+    let client = deployer.rpc_client()?;
 
-    // Check L1 (Anvil)
-    let l1 = {
+    // Check L1 (Anvil, or the external L1 node if configured)
+    let l1 = if let Some(ref external_l1) = deployer.external_l1 {
+        let chain_id = query_chain_id(&client, &external_l1.rpc_url).await;
+        L1Health {
+            container_name: external_l1.rpc_url.clone(),
+            external: true,
+            running: chain_id.is_some(),
+            state: if chain_id.is_some() {
+                ContainerState::Running
+            } else {
+                ContainerState::Stopped
+            },
+            chain_id,
+            expected_chain_id: deployer.l1_chain_id,
+            block_number: query_block_number(&client, &external_l1.rpc_url).await,
+        }
+    } else {
         let name = &deployer.anvil.container_name;
-        let running = is_running(&docker, name).await;
+        let state = container_state(&docker, name).await;
+        let running = state == ContainerState::Running;
         let evm = query_evm_node(&docker, &client, name, deployer.anvil.port, running).await;
         L1Health {
             container_name: name.clone(),
+            external: false,
             running,
+            state,
             chain_id: evm.chain_id,
             expected_chain_id: deployer.l1_chain_id,
             block_number: evm.block_number,
@@ -118,7 +217,7 @@ pub async fn health_check(deployer: &Deployer) -> Result<HealthReport> {
             &label,
             &seq.op_reth.container_name,
             seq.op_reth.http_port,
-            &seq.kona_node.container_name,
+            seq.consensus_container_name(),
             seq.kona_node.rpc_port,
             deployer.l2_chain_id,
         )
@@ -135,7 +234,7 @@ pub async fn health_check(deployer: &Deployer) -> Result<HealthReport> {
             &label,
             &val.op_reth.container_name,
             val.op_reth.http_port,
-            &val.kona_node.container_name,
+            val.consensus_container_name(),
             val.kona_node.rpc_port,
             deployer.l2_chain_id,
         )
@@ -144,37 +243,291 @@ pub async fn health_check(deployer: &Deployer) -> Result<HealthReport> {
     }
 
     // Check infrastructure services
+    let batcher = &deployer.l2_stack.op_batcher;
     let mut services = vec![
         check_service(
             &docker,
+            &client,
             "op-batcher",
-            &deployer.l2_stack.op_batcher.container_name,
+            &batcher.container_name,
+            Some(batcher.rpc_port),
         )
         .await,
     ];
     if let Some(ref proposer) = deployer.l2_stack.op_proposer {
-        services.push(check_service(&docker, "op-proposer", &proposer.container_name).await);
+        services.push(
+            check_service(
+                &docker,
+                &client,
+                "op-proposer",
+                &proposer.container_name,
+                Some(proposer.rpc_port),
+            )
+            .await,
+        );
     }
     if let Some(ref challenger) = deployer.l2_stack.op_challenger {
-        services.push(check_service(&docker, "op-challenger", &challenger.container_name).await);
+        services.push(
+            check_service(
+                &docker,
+                &client,
+                "op-challenger",
+                &challenger.container_name,
+                None,
+            )
+            .await,
+        );
     }
 
-    let healthy = compute_healthy(&l1, &nodes, &services);
+    let monitoring = if deployer.monitoring.enabled {
+        Some(check_monitoring(&docker, &client, deployer).await)
+    } else {
+        None
+    };
+
+    let healthy = compute_healthy(&l1, &nodes, &services, monitoring.as_ref());
 
     Ok(HealthReport {
         l1,
         nodes,
         services,
+        monitoring,
         healthy,
     })
 }
 
+/// Run a health check and record each node's heads in `tracker`.
+///
+/// Calling this periodically with the same tracker fills in [`NodeHealth::advancing`]
+/// and [`NodeHealth::blocks_per_sec`]; a node whose unsafe head stopped moving makes
+/// the report unhealthy even though its block number is non-zero.
+pub async fn health_check_with_tracker(
+    deployer: &Deployer,
+    tracker: &mut HeadTracker,
+) -> Result<HealthReport> {
+    let mut report = health_check(deployer).await?;
+    report.record_heads(tracker);
+    Ok(report)
+}
+
+/// Time a network stayed healthy across repeated health checks, e.g. `kupcake health --watch`.
+///
+/// The time between two checks counts as healthy when the earlier check was healthy.
+#[derive(Debug, Clone, Default)]
+pub struct HealthUptime {
+    /// Time and verdict of the latest check.
+    last: Option<(Instant, bool)>,
+    healthy: Duration,
+    watched: Duration,
+    checks: usize,
+    healthy_checks: usize,
+}
+
+impl HealthUptime {
+    /// Record the verdict of a check taken now.
+    pub fn record(&mut self, healthy: bool) {
+        self.record_at(Instant::now(), healthy);
+    }
+
+    /// Record the verdict of a check taken at `at`.
+    pub fn record_at(&mut self, at: Instant, healthy: bool) {
+        self.advance(at);
+        self.last = Some((at, healthy));
+        self.checks += 1;
+        if healthy {
+            self.healthy_checks += 1;
+        }
+    }
+
+    /// Count the time since the latest check, e.g. when the watch is interrupted.
+    pub fn finish(&mut self) {
+        self.advance(Instant::now());
+    }
+
+    fn advance(&mut self, now: Instant) {
+        if let Some((at, healthy)) = self.last {
+            let elapsed = now.saturating_duration_since(at);
+            self.watched += elapsed;
+            if healthy {
+                self.healthy += elapsed;
+            }
+            self.last = Some((now, healthy));
+        }
+    }
+
+    /// Time during which the network was healthy.
+    pub fn healthy_duration(&self) -> Duration {
+        self.healthy
+    }
+
+    /// Time since the first check.
+    pub fn watched_duration(&self) -> Duration {
+        self.watched
+    }
+
+    /// Number of checks recorded, and how many of them were healthy.
+    pub fn checks(&self) -> (usize, usize) {
+        (self.checks, self.healthy_checks)
+    }
+}
+
+impl fmt::Display for HealthUptime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ratio = if self.watched.is_zero() {
+            0.0
+        } else {
+            self.healthy.as_secs_f64() / self.watched.as_secs_f64() * 100.0
+        };
+        write!(
+            f,
+            "Healthy for {}s of {}s ({:.1}%), {}/{} checks healthy",
+            self.healthy.as_secs(),
+            self.watched.as_secs(),
+            ratio,
+            self.healthy_checks,
+            self.checks
+        )
+    }
+}
+
+impl HealthReport {
+    /// Record the consensus heads of each node in `tracker`, then update the
+    /// advancing state of the nodes and the overall health from it.
+    pub fn record_heads(&mut self, tracker: &mut HeadTracker) {
+        for node in &mut self.nodes {
+            let consensus = &node.consensus;
+            match consensus.unsafe_l2 {
+                Some(unsafe_l2) => tracker.record(
+                    &node.label,
+                    HeadSample {
+                        at: std::time::Instant::now(),
+                        unsafe_l2,
+                        safe_l2: consensus.safe_l2.unwrap_or(0),
+                        finalized_l2: consensus.finalized_l2.unwrap_or(0),
+                    },
+                ),
+                // The node is down or restarting; start over once it is back.
+                None => tracker.reset(&node.label),
+            }
+            node.advancing = tracker.is_advancing(&node.label);
+            node.blocks_per_sec = tracker.blocks_per_sec(&node.label);
+        }
+
+        self.healthy = compute_healthy(
+            &self.l1,
+            &self.nodes,
+            &self.services,
+            self.monitoring.as_ref(),
+        );
+    }
+
+    /// Whether any container of the network is running.
+    ///
+    /// An external L1 is not counted since kupcake does not manage it.
+    pub fn any_running(&self) -> bool {
+        (!self.l1.external && self.l1.running)
+            || self
+                .nodes
+                .iter()
+                .any(|node| node.execution.running || node.consensus.running)
+            || self.services.iter().any(|s| s.running)
+            || self
+                .monitoring
+                .as_ref()
+                .is_some_and(|m| m.prometheus.running || m.grafana.running)
+    }
+
+    /// Containers of critical components that are stopped or failing their checks.
+    ///
+    /// These are the containers worth restarting to bring the network back to health.
+    pub fn unhealthy_containers(&self) -> Vec<String> {
+        let mut containers = Vec::new();
+
+        let l1_ok = self.l1.running && self.l1.chain_id_match() && self.l1.block_number.is_some();
+        if !self.l1.external && !l1_ok {
+            containers.push(self.l1.container_name.clone());
+        }
+
+        for node in &self.nodes {
+            let execution = &node.execution;
+            let execution_ok = execution.running
+                && execution.chain_id_match()
+                && execution.block_number.unwrap_or(0) > 0;
+            if !execution_ok {
+                containers.push(execution.container_name.clone());
+            }
+            if !node.consensus.running {
+                containers.push(node.consensus.container_name.clone());
+            }
+        }
+
+        containers.extend(
+            self.services
+                .iter()
+                .filter(|s| !NON_CRITICAL_SERVICES.contains(&s.name.as_str()) && !s.is_ok())
+                .map(|s| s.container_name.clone()),
+        );
+
+        if let Some(ref monitoring) = self.monitoring {
+            containers.extend(
+                [&monitoring.prometheus, &monitoring.grafana]
+                    .into_iter()
+                    .filter(|e| !(e.running && e.responding))
+                    .map(|e| e.container_name.clone()),
+            );
+        }
+
+        containers
+    }
+}
+
+/// State of a previously deployed network, used to make re-runs idempotent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExistingDeployment {
+    /// No container of the network is running.
+    NotRunning,
+    /// The network is up and passes the health check.
+    Healthy,
+    /// Some containers are up, but the network fails the health check.
+    Unhealthy {
+        /// Containers that are stopped or failing their checks.
+        containers: Vec<String>,
+    },
+}
+
+/// Check whether a network is already deployed and healthy.
+pub async fn check_existing_deployment(deployer: &Deployer) -> Result<ExistingDeployment> {
+    let report = health_check(deployer).await?;
+
+    Ok(classify_existing(&report))
+}
+
+fn classify_existing(report: &HealthReport) -> ExistingDeployment {
+    if report.healthy {
+        ExistingDeployment::Healthy
+    } else if !report.any_running() {
+        ExistingDeployment::NotRunning
+    } else {
+        ExistingDeployment::Unhealthy {
+            containers: report.unhealthy_containers(),
+        }
+    }
+}
+
 /// Services that are not required to be running for a healthy network.
 /// op-challenger is excluded because it requires additional configuration
 /// (prestates) that is not yet automated.
 const NON_CRITICAL_SERVICES: &[&str] = &["op-challenger"];
 
-fn compute_healthy(l1: &L1Health, nodes: &[NodeHealth], services: &[ServiceHealth]) -> bool {
+/// Job name of Prometheus' self-monitoring scrape target.
+const PROMETHEUS_SELF_JOB: &str = "prometheus";
+
+fn compute_healthy(
+    l1: &L1Health,
+    nodes: &[NodeHealth],
+    services: &[ServiceHealth],
+    monitoring: Option<&MonitoringHealth>,
+) -> bool {
     l1.running
         && l1.chain_id_match()
         && l1.block_number.is_some()
@@ -183,11 +536,144 @@ fn compute_healthy(l1: &L1Health, nodes: &[NodeHealth], services: &[ServiceHealt
                 && node.execution.chain_id_match()
                 && node.execution.block_number.unwrap_or(0) > 0
                 && node.consensus.running
+                && node.advancing != Some(false)
         })
         && services
             .iter()
             .filter(|s| !NON_CRITICAL_SERVICES.contains(&s.name.as_str()))
-            .all(|s| s.running)
+            .all(ServiceHealth::is_ok)
+        && monitoring.is_none_or(|m| {
+            m.prometheus.running
+                && m.prometheus.responding
+                && m.grafana.running
+                && m.grafana.responding
+                && m.targets
+                    .iter()
+                    .filter(|t| !NON_CRITICAL_SERVICES.contains(&t.service.as_str()))
+                    .all(|t| t.up)
+        })
+}
+
+/// Check Prometheus and Grafana health endpoints and Prometheus scrape target states.
+async fn check_monitoring(
+    docker: &Docker,
+    client: &reqwest::Client,
+    deployer: &Deployer,
+) -> MonitoringHealth {
+    let prometheus_config = &deployer.monitoring.prometheus;
+    let grafana_config = &deployer.monitoring.grafana;
+
+    let prometheus_state = container_state(docker, &prometheus_config.container_name).await;
+    let prometheus_running = prometheus_state == ContainerState::Running;
+    let prometheus_url = if prometheus_running {
+        build_host_rpc_url(
+            docker,
+            &prometheus_config.container_name,
+            prometheus_config.port,
+        )
+        .await
+    } else {
+        None
+    };
+
+    let grafana_state = container_state(docker, &grafana_config.container_name).await;
+    let grafana_running = grafana_state == ContainerState::Running;
+    let grafana_url = if grafana_running {
+        build_host_rpc_url(docker, &grafana_config.container_name, grafana_config.port).await
+    } else {
+        None
+    };
+
+    let prometheus = EndpointHealth {
+        name: "prometheus".to_string(),
+        container_name: prometheus_config.container_name.clone(),
+        running: prometheus_running,
+        state: prometheus_state,
+        responding: match prometheus_url {
+            Some(ref url) => http_ok(client, &format!("{url}-/healthy")).await,
+            None => false,
+        },
+    };
+
+    let grafana = EndpointHealth {
+        name: "grafana".to_string(),
+        container_name: grafana_config.container_name.clone(),
+        running: grafana_running,
+        state: grafana_state,
+        responding: match grafana_url {
+            Some(ref url) => http_ok(client, &format!("{url}api/health")).await,
+            None => false,
+        },
+    };
+
+    let target_states = match prometheus_url {
+        Some(ref url) => query_scrape_targets(client, url).await,
+        None => HashMap::new(),
+    };
+
+    let expected = deployer
+        .build_metrics_targets_from_config()
+        .into_iter()
+        .map(|t| (t.job_name, t.service_label))
+        .chain(std::iter::once((
+            PROMETHEUS_SELF_JOB.to_string(),
+            PROMETHEUS_SELF_JOB.to_string(),
+        )));
+
+    let targets = expected
+        .map(|(job_name, service)| ScrapeTargetHealth {
+            up: target_states.get(&job_name).copied().unwrap_or(false),
+            job_name,
+            service,
+        })
+        .collect();
+
+    MonitoringHealth {
+        prometheus,
+        grafana,
+        targets,
+    }
+}
+
+/// Returns true if a GET request to `url` succeeds with a 2xx status.
+async fn http_ok(client: &reqwest::Client, url: &str) -> bool {
+    client
+        .get(url)
+        .send()
+        .await
+        .is_ok_and(|resp| resp.status().is_success())
+}
+
+/// Query Prometheus' active scrape targets.
+///
+/// Returns a map from job name to whether every instance of that job is `up`.
+async fn query_scrape_targets(client: &reqwest::Client, url: &str) -> HashMap<String, bool> {
+    let Ok(resp) = client
+        .get(format!("{url}api/v1/targets?state=active"))
+        .send()
+        .await
+    else {
+        return HashMap::new();
+    };
+
+    match resp.json::<Value>().await {
+        Ok(value) => parse_scrape_targets(&value),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Parse a Prometheus `/api/v1/targets` response into per-job `up` states.
+fn parse_scrape_targets(value: &Value) -> HashMap<String, bool> {
+    let mut states = HashMap::new();
+
+    for target in TargetStatus::from_targets_response(value) {
+        states
+            .entry(target.job_name)
+            .and_modify(|all_up| *all_up &= target.up)
+            .or_insert(target.up);
+    }
+
+    states
 }
 
 /// Query chain_id and block_number from an EVM node if it's running.
@@ -231,10 +717,12 @@ async fn check_l2_node(
     kona_port: u16,
     expected_chain_id: u64,
 ) -> NodeHealth {
-    let reth_running = is_running(docker, reth_name).await;
+    let reth_state = container_state(docker, reth_name).await;
+    let reth_running = reth_state == ContainerState::Running;
     let evm = query_evm_node(docker, client, reth_name, reth_port, reth_running).await;
 
-    let kona_running = is_running(docker, kona_name).await;
+    let kona_state = container_state(docker, kona_name).await;
+    let kona_running = kona_state == ContainerState::Running;
     let (unsafe_l2, safe_l2, finalized_l2) = if kona_running {
         match build_host_rpc_url(docker, kona_name, kona_port).await {
             Some(url) => query_sync_status(client, &url).await,
@@ -250,6 +738,7 @@ async fn check_l2_node(
         execution: ExecutionHealth {
             container_name: reth_name.to_string(),
             running: reth_running,
+            state: reth_state,
             chain_id: evm.chain_id,
             expected_chain_id,
             block_number: evm.block_number,
@@ -257,33 +746,44 @@ async fn check_l2_node(
         consensus: ConsensusHealth {
             container_name: kona_name.to_string(),
             running: kona_running,
+            state: kona_state,
             unsafe_l2,
             safe_l2,
             finalized_l2,
         },
+        advancing: None,
+        blocks_per_sec: None,
     }
 }
 
-/// Check if an infrastructure service container is running.
-async fn check_service(docker: &Docker, name: &str, container_name: &str) -> ServiceHealth {
+/// Check if an infrastructure service container is running, and probe its RPC.
+async fn check_service(
+    docker: &Docker,
+    client: &reqwest::Client,
+    name: &str,
+    container_name: &str,
+    rpc_port: Option<u16>,
+) -> ServiceHealth {
+    let state = container_state(docker, container_name).await;
+    let running = state == ContainerState::Running;
+    let rpc_url = match rpc_port {
+        Some(port) if running => build_host_rpc_url(docker, container_name, port).await,
+        _ => None,
+    };
+    let rpc = match rpc_url {
+        Some(url) => Some(rpc::probe_op_service(client, &url).await),
+        None => None,
+    };
+
     ServiceHealth {
         name: name.to_string(),
         container_name: container_name.to_string(),
-        running: is_running(docker, container_name).await,
+        running,
+        state,
+        rpc,
     }
 }
 
-/// Check if a container is running via Docker inspect.
-async fn is_running(docker: &Docker, container_name: &str) -> bool {
-    docker
-        .inspect_container(container_name, None)
-        .await
-        .ok()
-        .and_then(|info| info.state)
-        .and_then(|s| s.running)
-        .unwrap_or(false)
-}
-
 /// Build a host-accessible RPC URL by inspecting the container's bound ports.
 ///
 /// Returns the `http://localhost:<host_port>/` URL if the container has
@@ -305,7 +805,7 @@ pub(crate) async fn build_host_rpc_url(
 }
 
 /// Query eth_chainId and parse the hex result to u64.
-async fn query_chain_id(client: &reqwest::Client, url: &str) -> Option<u64> {
+pub(crate) async fn query_chain_id(client: &reqwest::Client, url: &str) -> Option<u64> {
     let result: String = rpc::json_rpc_call(client, url, "eth_chainId", vec![])
         .await
         .ok()?;
@@ -347,6 +847,17 @@ async fn query_sync_status(
 
 // -- Display helpers --
 
+/// State of a container: restarting and paused containers are told apart from
+/// stopped ones.
+fn state_cell(state: ContainerState) -> Cell {
+    match state {
+        ContainerState::Running => Cell::new("OK").fg(Color::Green),
+        ContainerState::Restarting => Cell::new("RESTARTING").fg(Color::Yellow),
+        ContainerState::Paused => Cell::new("PAUSED").fg(Color::Yellow),
+        ContainerState::Stopped | ContainerState::NotFound => Cell::new("DOWN").fg(Color::Red),
+    }
+}
+
 fn running_cell(running: bool) -> Cell {
     if running {
         Cell::new("OK").fg(Color::Green)
@@ -355,6 +866,29 @@ fn running_cell(running: bool) -> Cell {
     }
 }
 
+/// RPC probe of a service: its version, or the error it answered with.
+fn probe_cell(probe: Option<&rpc::OpServiceProbe>) -> Cell {
+    let Some(probe) = probe else {
+        return Cell::new("-");
+    };
+    match (probe.responding, &probe.error) {
+        (true, None) => Cell::new(probe.version.as_deref().unwrap_or("OK")).fg(Color::Green),
+        (true, Some(error)) => Cell::new(format!("OK ({error})")).fg(Color::Yellow),
+        (false, error) => {
+            Cell::new(format!("DOWN: {}", error.as_deref().unwrap_or("unknown"))).fg(Color::Red)
+        }
+    }
+}
+
+/// Unsafe head rate of a node, empty without tracker samples.
+fn rate_cell(advancing: Option<bool>, blocks_per_sec: Option<f64>) -> Cell {
+    match (advancing, blocks_per_sec) {
+        (Some(false), _) => Cell::new("STALLED").fg(Color::Red),
+        (_, Some(rate)) => Cell::new(format!("{rate:.2} blk/s")).fg(Color::Green),
+        _ => Cell::new(""),
+    }
+}
+
 fn chain_id_cell(chain_id: Option<u64>, expected: u64) -> Cell {
     match chain_id {
         Some(cid) if cid == expected => Cell::new(format!("{cid}")).fg(Color::Green),
@@ -397,7 +931,7 @@ impl fmt::Display for HealthReport {
             ]);
             table.add_row(vec![
                 Cell::new(&self.l1.container_name),
-                running_cell(self.l1.running),
+                state_cell(self.l1.state),
                 chain_id_cell(self.l1.chain_id, self.l1.expected_chain_id),
                 Cell::new(val_or_dash(self.l1.block_number)),
             ]);
@@ -423,7 +957,7 @@ impl fmt::Display for HealthReport {
                     Cell::new(&node.label).add_attribute(Attribute::Bold),
                     Cell::new("op-reth"),
                     Cell::new(&ex.container_name),
-                    running_cell(ex.running),
+                    state_cell(ex.state),
                     chain_id_cell(ex.chain_id, ex.expected_chain_id),
                     Cell::new(val_or_dash(ex.block_number)),
                 ]);
@@ -443,9 +977,9 @@ impl fmt::Display for HealthReport {
                     Cell::new(""),
                     Cell::new("kona-node"),
                     Cell::new(&cn.container_name),
-                    running_cell(cn.running),
+                    state_cell(cn.state),
                     Cell::new(if heads.is_empty() { "-" } else { &heads }),
-                    Cell::new(""),
+                    rate_cell(node.advancing, node.blocks_per_sec),
                 ]);
             }
             writeln!(f, "{table}")?;
@@ -460,6 +994,7 @@ impl fmt::Display for HealthReport {
                 header("Service"),
                 header("Container"),
                 header("Status"),
+                header("RPC"),
                 header("Note"),
             ]);
             for svc in &self.services {
@@ -471,13 +1006,50 @@ impl fmt::Display for HealthReport {
                 table.add_row(vec![
                     Cell::new(&svc.name),
                     Cell::new(&svc.container_name),
-                    running_cell(svc.running),
+                    state_cell(svc.state),
+                    probe_cell(svc.rpc.as_ref()),
                     Cell::new(note),
                 ]);
             }
             writeln!(f, "{table}")?;
         }
 
+        // Monitoring table
+        if let Some(ref mon) = self.monitoring {
+            writeln!(f)?;
+            writeln!(f, "Monitoring")?;
+            let mut table = Table::new();
+            table.set_header(vec![
+                header("Service"),
+                header("Container"),
+                header("Status"),
+                header("Health Endpoint"),
+            ]);
+            for endpoint in [&mon.prometheus, &mon.grafana] {
+                table.add_row(vec![
+                    Cell::new(&endpoint.name),
+                    Cell::new(&endpoint.container_name),
+                    state_cell(endpoint.state),
+                    running_cell(endpoint.responding),
+                ]);
+            }
+            writeln!(f, "{table}")?;
+
+            let mut table = Table::new();
+            table.set_header(vec![header("Scrape Target"), header("State")]);
+            for target in &mon.targets {
+                let state = if target.up {
+                    Cell::new("UP").fg(Color::Green)
+                } else if NON_CRITICAL_SERVICES.contains(&target.service.as_str()) {
+                    Cell::new("DOWN (non-critical)").fg(Color::Yellow)
+                } else {
+                    Cell::new("DOWN").fg(Color::Red)
+                };
+                table.add_row(vec![Cell::new(&target.job_name), state]);
+            }
+            writeln!(f, "{table}")?;
+        }
+
         Ok(())
     }
 }
@@ -489,7 +1061,9 @@ mod tests {
     fn healthy_l1() -> L1Health {
         L1Health {
             container_name: "kup-test-anvil".to_string(),
+            external: false,
             running: true,
+            state: ContainerState::Running,
             chain_id: Some(11155111),
             expected_chain_id: 11155111,
             block_number: Some(100),
@@ -503,6 +1077,7 @@ mod tests {
             execution: ExecutionHealth {
                 container_name: "kup-test-op-reth".to_string(),
                 running: true,
+                state: ContainerState::Running,
                 chain_id: Some(42069),
                 expected_chain_id: 42069,
                 block_number: Some(50),
@@ -510,10 +1085,13 @@ mod tests {
             consensus: ConsensusHealth {
                 container_name: "kup-test-kona-node".to_string(),
                 running: true,
+                state: ContainerState::Running,
                 unsafe_l2: Some(50),
                 safe_l2: Some(40),
                 finalized_l2: Some(30),
             },
+            advancing: None,
+            blocks_per_sec: None,
         }
     }
 
@@ -523,16 +1101,22 @@ mod tests {
                 name: "op-batcher".to_string(),
                 container_name: "kup-test-op-batcher".to_string(),
                 running: true,
+                state: ContainerState::Running,
+                rpc: None,
             },
             ServiceHealth {
                 name: "op-proposer".to_string(),
                 container_name: "kup-test-op-proposer".to_string(),
                 running: true,
+                state: ContainerState::Running,
+                rpc: None,
             },
             ServiceHealth {
                 name: "op-challenger".to_string(),
                 container_name: "kup-test-op-challenger".to_string(),
                 running: true,
+                state: ContainerState::Running,
+                rpc: None,
             },
         ]
     }
@@ -542,10 +1126,77 @@ mod tests {
         assert!(compute_healthy(
             &healthy_l1(),
             &[healthy_node()],
-            &healthy_services()
+            &healthy_services(),
+            None
         ));
     }
 
+    #[test]
+    fn test_restarting_container() {
+        let mut node = healthy_node();
+        node.execution.running = false;
+        node.execution.state = ContainerState::Restarting;
+        let mut services = healthy_services();
+        services[0].running = false;
+        services[0].state = ContainerState::Stopped;
+        let report = HealthReport {
+            l1: healthy_l1(),
+            nodes: vec![node],
+            services,
+            monitoring: None,
+            healthy: false,
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["nodes"][0]["execution"]["state"], "Restarting");
+        assert_eq!(json["services"][0]["state"], "Stopped");
+
+        let table = report.to_string();
+        assert!(table.contains("RESTARTING"), "{table}");
+        assert!(table.contains("DOWN"), "{table}");
+    }
+
+    #[test]
+    fn test_report_json_keeps_unreachable_fields() {
+        let mut node = healthy_node();
+        node.execution.block_number = None;
+        node.consensus.unsafe_l2 = None;
+        let report = HealthReport {
+            l1: healthy_l1(),
+            nodes: vec![node],
+            services: healthy_services(),
+            monitoring: None,
+            healthy: false,
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["healthy"], false);
+        assert_eq!(json["l1"]["chain_id"], 11155111);
+        assert_eq!(json["nodes"][0]["execution"]["chain_id"], 42069);
+        assert!(json["nodes"][0]["execution"]["block_number"].is_null());
+        assert!(json["nodes"][0]["consensus"]["unsafe_l2"].is_null());
+        assert_eq!(json["services"][0]["running"], true);
+        assert!(json["monitoring"].is_null());
+    }
+
+    #[test]
+    fn test_health_uptime() {
+        let start = Instant::now();
+        let mut uptime = HealthUptime::default();
+        uptime.record_at(start, true);
+        uptime.record_at(start + Duration::from_secs(10), false);
+        uptime.record_at(start + Duration::from_secs(15), true);
+        uptime.record_at(start + Duration::from_secs(20), true);
+
+        assert_eq!(uptime.healthy_duration(), Duration::from_secs(15));
+        assert_eq!(uptime.watched_duration(), Duration::from_secs(20));
+        assert_eq!(uptime.checks(), (4, 3));
+        assert_eq!(
+            uptime.to_string(),
+            "Healthy for 15s of 20s (75.0%), 3/4 checks healthy"
+        );
+    }
+
     #[test]
     fn test_unhealthy_stopped_container() {
         let mut services = healthy_services();
@@ -554,7 +1205,38 @@ mod tests {
         assert!(!compute_healthy(
             &healthy_l1(),
             &[healthy_node()],
-            &services
+            &services,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_unhealthy_wedged_service_rpc() {
+        let mut services = healthy_services();
+        // op-batcher is running, but its RPC does not answer
+        services[0].rpc = Some(rpc::OpServiceProbe {
+            responding: false,
+            version: None,
+            error: Some("connection refused".to_string()),
+        });
+        assert!(!compute_healthy(
+            &healthy_l1(),
+            &[healthy_node()],
+            &services,
+            None
+        ));
+
+        // An error response still proves the RPC is alive
+        services[0].rpc = Some(rpc::OpServiceProbe {
+            responding: true,
+            version: None,
+            error: Some("method not found".to_string()),
+        });
+        assert!(compute_healthy(
+            &healthy_l1(),
+            &[healthy_node()],
+            &services,
+            None
         ));
     }
 
@@ -563,7 +1245,12 @@ mod tests {
         let mut services = healthy_services();
         // op-challenger is non-critical
         services[2].running = false;
-        assert!(compute_healthy(&healthy_l1(), &[healthy_node()], &services));
+        assert!(compute_healthy(
+            &healthy_l1(),
+            &[healthy_node()],
+            &services,
+            None
+        ));
     }
 
     #[test]
@@ -572,25 +1259,192 @@ mod tests {
             chain_id: Some(999),
             ..healthy_l1()
         };
-        assert!(!compute_healthy(&l1, &[], &[]));
+        assert!(!compute_healthy(&l1, &[], &[], None));
     }
 
     #[test]
     fn test_unhealthy_zero_blocks() {
         let mut node = healthy_node();
         node.execution.block_number = Some(0);
-        assert!(!compute_healthy(&healthy_l1(), &[node], &[]));
+        assert!(!compute_healthy(&healthy_l1(), &[node], &[], None));
+    }
+
+    #[test]
+    fn test_unhealthy_stalled_node() {
+        let mut report = HealthReport {
+            l1: healthy_l1(),
+            nodes: vec![healthy_node()],
+            services: healthy_services(),
+            monitoring: None,
+            healthy: true,
+        };
+        let mut tracker = HeadTracker::default();
+        tracker.record(
+            "sequencer",
+            HeadSample {
+                at: std::time::Instant::now() - std::time::Duration::from_secs(10),
+                unsafe_l2: 50,
+                safe_l2: 40,
+                finalized_l2: 30,
+            },
+        );
+
+        report.record_heads(&mut tracker);
+        assert_eq!(report.nodes[0].advancing, Some(false));
+        assert_eq!(report.nodes[0].blocks_per_sec, Some(0.0));
+        assert!(!report.healthy);
+
+        // A node that went down loses its history instead of being reported stalled.
+        report.nodes[0].consensus.unsafe_l2 = None;
+        report.record_heads(&mut tracker);
+        assert_eq!(report.nodes[0].advancing, None);
+        assert!(tracker.samples("sequencer").next().is_none());
     }
 
     #[test]
     fn test_unhealthy_l1_not_running() {
         let l1 = L1Health {
             running: false,
+            state: ContainerState::Stopped,
             chain_id: None,
             block_number: None,
             ..healthy_l1()
         };
-        assert!(!compute_healthy(&l1, &[], &[]));
+        assert!(!compute_healthy(&l1, &[], &[], None));
+    }
+
+    fn healthy_monitoring() -> MonitoringHealth {
+        let endpoint = |name: &str| EndpointHealth {
+            name: name.to_string(),
+            container_name: format!("kup-test-{name}"),
+            running: true,
+            state: ContainerState::Running,
+            responding: true,
+        };
+        let target = |job: &str, service: &str| ScrapeTargetHealth {
+            job_name: job.to_string(),
+            service: service.to_string(),
+            up: true,
+        };
+        MonitoringHealth {
+            prometheus: endpoint("prometheus"),
+            grafana: endpoint("grafana"),
+            targets: vec![
+                target("op-batcher", "op-batcher"),
+                target("op-challenger", "op-challenger"),
+                target("prometheus", "prometheus"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_monitoring_health() {
+        let nodes = [healthy_node()];
+        let services = healthy_services();
+        let healthy =
+            |mon: &MonitoringHealth| compute_healthy(&healthy_l1(), &nodes, &services, Some(mon));
+
+        assert!(healthy(&healthy_monitoring()));
+
+        let mut grafana_down = healthy_monitoring();
+        grafana_down.grafana.responding = false;
+        assert!(!healthy(&grafana_down));
+
+        let mut batcher_target_down = healthy_monitoring();
+        batcher_target_down.targets[0].up = false;
+        assert!(!healthy(&batcher_target_down));
+
+        // op-challenger's target is non-critical, like its container
+        let mut challenger_target_down = healthy_monitoring();
+        challenger_target_down.targets[1].up = false;
+        assert!(healthy(&challenger_target_down));
+    }
+
+    #[test]
+    fn test_parse_scrape_targets() {
+        let response = serde_json::json!({
+            "status": "success",
+            "data": {
+                "activeTargets": [
+                    { "labels": { "job": "op-batcher" }, "health": "up" },
+                    { "labels": { "job": "op-reth-sequencer" }, "health": "up" },
+                    { "labels": { "job": "op-reth-sequencer" }, "health": "down" },
+                    { "labels": { "job": "prometheus" }, "health": "unknown" }
+                ]
+            }
+        });
+
+        let states = parse_scrape_targets(&response);
+        assert_eq!(states.get("op-batcher"), Some(&true));
+        assert_eq!(states.get("op-reth-sequencer"), Some(&false));
+        assert_eq!(states.get("prometheus"), Some(&false));
+        assert!(parse_scrape_targets(&serde_json::json!({})).is_empty());
+    }
+
+    fn report(l1: L1Health, nodes: Vec<NodeHealth>) -> HealthReport {
+        let services = healthy_services();
+        let healthy = compute_healthy(&l1, &nodes, &services, None);
+        HealthReport {
+            l1,
+            nodes,
+            services,
+            monitoring: None,
+            healthy,
+        }
+    }
+
+    #[test]
+    fn test_classify_existing() {
+        assert_eq!(
+            classify_existing(&report(healthy_l1(), vec![healthy_node()])),
+            ExistingDeployment::Healthy
+        );
+
+        let mut stopped = report(
+            L1Health {
+                running: false,
+                state: ContainerState::Stopped,
+                ..healthy_l1()
+            },
+            vec![healthy_node()],
+        );
+        stopped.nodes[0].execution.running = false;
+        stopped.nodes[0].consensus.running = false;
+        stopped.services.iter_mut().for_each(|s| s.running = false);
+        assert_eq!(classify_existing(&stopped), ExistingDeployment::NotRunning);
+
+        let mut node = healthy_node();
+        node.consensus.running = false;
+        node.execution.block_number = Some(0);
+        let mut partial = report(healthy_l1(), vec![node]);
+        // Non-critical services are not reported
+        partial.services[2].running = false;
+        assert_eq!(
+            classify_existing(&partial),
+            ExistingDeployment::Unhealthy {
+                containers: vec![
+                    "kup-test-op-reth".to_string(),
+                    "kup-test-kona-node".to_string()
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_external_l1_not_counted() {
+        let l1 = L1Health {
+            external: true,
+            container_name: "http://l1:8545".to_string(),
+            ..healthy_l1()
+        };
+        let mut node = healthy_node();
+        node.execution.running = false;
+        node.consensus.running = false;
+        let mut report = report(l1, vec![node]);
+        report.services.iter_mut().for_each(|s| s.running = false);
+
+        assert!(!report.any_running());
+        assert_eq!(classify_existing(&report), ExistingDeployment::NotRunning);
     }
 
     #[test]
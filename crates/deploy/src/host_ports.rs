@@ -0,0 +1,482 @@
+//! Recording of the host ports Docker picked for a deployment.
+//!
+//! Published ports default to host port 0, so Docker assigns a different port on every
+//! deployment and the host URLs change when the network is redeployed from its saved
+//! config. [`Deployer::persist_host_ports`] writes the ports actually bound back into
+//! `Kupcake.toml`, so that the next deployment publishes on the same ports. A recorded
+//! port found busy then falls back to a Docker-assigned one with a
+//! [`DeployWarning::HostPortMoved`](crate::DeployWarning::HostPortMoved); explicit and
+//! fixed ports never move.
+//!
+//! [`Deployer::fix_host_ports`] instead publishes every service on a fixed port derived
+//! from its container port before the first deployment, failing if a port is busy.
+
+use std::{collections::BTreeSet, path::PathBuf};
+
+use anyhow::{Context, Result};
+use url::Url;
+
+use crate::{
+    Deployer, DeploymentResult, DeploymentVersion,
+    deployer::KUPCONF_FILENAME,
+    deployment_hash::{DEPLOYMENT_VERSION_FILENAME, service_hashes},
+};
+
+/// Offset between the fixed host ports of consecutive L2 nodes, spare batchers and
+/// spare proposers (see [`Deployer::fix_host_ports`]).
+pub const FIXED_PORT_STRIDE: u16 = 100;
+
+/// Replace a Docker-assigned host port (`Some(0)`) with the port bound in `url`.
+/// Returns the recorded port.
+///
+/// Unpublished (None) and fixed ports are left as they are.
+fn record(host_port: &mut Option<u16>, url: Option<&Url>) -> Option<u16> {
+    if *host_port == Some(0)
+        && let Some(port) = url.and_then(Url::port)
+    {
+        *host_port = Some(port);
+        return Some(port);
+    }
+    None
+}
+
+/// Fixed host ports assigned by [`Deployer::fix_host_ports`], checked for collisions.
+struct FixedPorts {
+    /// Host ports recorded from a previous deployment, replaced like Docker-assigned ones.
+    recorded: BTreeSet<u16>,
+    /// Resulting host port of each service.
+    ports: Vec<(String, u16)>,
+}
+
+impl FixedPorts {
+    fn new(recorded: BTreeSet<u16>) -> Self {
+        Self {
+            recorded,
+            ports: Vec::new(),
+        }
+    }
+
+    /// Replace a Docker-assigned (`Some(0)`) or recorded host port of `service` with
+    /// `port`, and remember the resulting host port, if any.
+    fn fix(&mut self, service: &str, host_port: &mut Option<u16>, port: u16) {
+        if host_port.is_some_and(|p| p == 0 || self.recorded.contains(&p)) {
+            *host_port = Some(port);
+        }
+        if let Some(port) = *host_port
+            && port != 0
+        {
+            self.ports.push((service.to_string(), port));
+        }
+    }
+
+    /// Fail if two services are published on the same host port.
+    fn check(&self) -> Result<()> {
+        for (i, (service, port)) in self.ports.iter().enumerate() {
+            if let Some((other, _)) = self.ports[..i].iter().find(|(_, p)| p == port) {
+                anyhow::bail!(
+                    "Fixed host port {} is used by both {} and {}; set one of their host ports explicitly",
+                    port,
+                    other,
+                    service
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Container port `port` of the `index`-th instance of a service, shifted by
+/// [`FIXED_PORT_STRIDE`] per instance.
+fn nth_port(port: u16, index: usize) -> Result<u16> {
+    u16::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_mul(FIXED_PORT_STRIDE))
+        .and_then(|offset| port.checked_add(offset))
+        .with_context(|| format!("No fixed host port for instance {index} of port {port}"))
+}
+
+impl Deployer {
+    /// Publish the services on fixed host ports instead of Docker-assigned ones.
+    ///
+    /// Every host port set to 0 is replaced with the container port: the Anvil RPC, the
+    /// op-reth HTTP and WebSocket RPCs, the consensus RPC and metrics, the op-conductor,
+    /// op-batcher and op-proposer RPCs, the op-challenger metrics, Prometheus, Grafana
+    /// and Blockscout. The ports of the n-th L2 node (sequencers first, then validators)
+    /// and of the n-th spare batcher or proposer are shifted by n × [`FIXED_PORT_STRIDE`],
+    /// e.g. 9545, 9645, 9745 for the op-reth HTTP RPCs. The services of the additional L2
+    /// chains continue this numbering after those of the primary chain. Host ports recorded
+    /// by [`Deployer::persist_host_ports`] are replaced as well, and no longer fall back to
+    /// a Docker-assigned port. Unpublished and explicit host ports are left as they are.
+    ///
+    /// Fails if two services end up on the same host port. A fixed port already in use
+    /// on the host fails the deployment of its service.
+    pub fn fix_host_ports(&mut self) -> Result<()> {
+        let mut fixed = FixedPorts::new(std::mem::take(&mut self.docker.recorded_host_ports));
+
+        if self.external_l1.is_none() {
+            let anvil = &mut self.anvil;
+            fixed.fix(&anvil.container_name, &mut anvil.host_port, anvil.port);
+        }
+
+        // The additional L2 chains continue the numbering of the primary one.
+        let (mut node_index, mut batcher_index, mut proposer_index) = (0, 0, 0);
+        let stacks = std::iter::once(&mut self.l2_stack).chain(
+            self.additional_l2_chains
+                .iter_mut()
+                .map(|chain| &mut chain.l2_stack),
+        );
+        for (chain_index, stack) in stacks.enumerate() {
+            let nodes = stack
+                .sequencers
+                .iter_mut()
+                .chain(stack.validators.iter_mut());
+            for node in nodes {
+                let i = node_index;
+                node_index += 1;
+                let op_reth = &mut node.op_reth;
+                let name = op_reth.container_name.clone();
+                fixed.fix(
+                    &name,
+                    &mut op_reth.http_host_port,
+                    nth_port(op_reth.http_port, i)?,
+                );
+                fixed.fix(
+                    &name,
+                    &mut op_reth.ws_host_port,
+                    nth_port(op_reth.ws_port, i)?,
+                );
+
+                match node.op_node {
+                    Some(ref mut op_node) => {
+                        let name = op_node.container_name.clone();
+                        fixed.fix(
+                            &name,
+                            &mut op_node.rpc_host_port,
+                            nth_port(op_node.rpc_port, i)?,
+                        );
+                        fixed.fix(
+                            &name,
+                            &mut op_node.metrics_host_port,
+                            nth_port(op_node.metrics_port, i)?,
+                        );
+                    }
+                    None => {
+                        let kona_node = &mut node.kona_node;
+                        let name = kona_node.container_name.clone();
+                        fixed.fix(
+                            &name,
+                            &mut kona_node.rpc_host_port,
+                            nth_port(kona_node.rpc_port, i)?,
+                        );
+                        fixed.fix(
+                            &name,
+                            &mut kona_node.metrics_host_port,
+                            nth_port(kona_node.metrics_port, i)?,
+                        );
+                    }
+                }
+
+                if let Some(ref mut conductor) = node.op_conductor {
+                    let name = conductor.container_name.clone();
+                    fixed.fix(
+                        &name,
+                        &mut conductor.rpc_host_port,
+                        nth_port(conductor.rpc_port, i)?,
+                    );
+                }
+            }
+
+            let batchers = std::iter::once(&mut stack.op_batcher).chain(&mut stack.spare_batchers);
+            for batcher in batchers {
+                let i = batcher_index;
+                batcher_index += 1;
+                let name = batcher.container_name.clone();
+                fixed.fix(
+                    &name,
+                    &mut batcher.rpc_host_port,
+                    nth_port(batcher.rpc_port, i)?,
+                );
+            }
+            let proposers = stack
+                .op_proposer
+                .iter_mut()
+                .chain(&mut stack.spare_proposers);
+            for proposer in proposers {
+                let i = proposer_index;
+                proposer_index += 1;
+                let name = proposer.container_name.clone();
+                fixed.fix(
+                    &name,
+                    &mut proposer.rpc_host_port,
+                    nth_port(proposer.rpc_port, i)?,
+                );
+            }
+            if let Some(ref mut challenger) = stack.op_challenger {
+                let name = challenger.container_name.clone();
+                fixed.fix(
+                    &name,
+                    &mut challenger.metrics_host_port,
+                    nth_port(challenger.metrics_port, chain_index)?,
+                );
+            }
+        }
+
+        if self.monitoring.enabled {
+            let prometheus = &mut self.monitoring.prometheus;
+            fixed.fix(
+                &prometheus.container_name,
+                &mut prometheus.host_port,
+                prometheus.port,
+            );
+            let grafana = &mut self.monitoring.grafana;
+            fixed.fix(
+                &grafana.container_name,
+                &mut grafana.host_port,
+                grafana.port,
+            );
+            if self.monitoring.logs.enabled {
+                let loki = &mut self.monitoring.logs.loki;
+                fixed.fix(&loki.container_name, &mut loki.host_port, loki.port);
+            }
+        }
+
+        if self.blockscout.enabled {
+            let blockscout = &mut self.blockscout;
+            fixed.fix(
+                &blockscout.container_name,
+                &mut blockscout.host_port,
+                blockscout.port,
+            );
+        }
+
+        fixed.check()
+    }
+
+    /// Replace the Docker-assigned host ports of the config with the ports `result` is
+    /// published on: the Anvil RPC, the op-reth HTTP and WebSocket RPCs, the consensus,
+    /// op-conductor, op-batcher and op-proposer RPCs, Prometheus, Grafana and Blockscout.
+    ///
+    /// `self` is the deployer `result` was deployed from, cloned before the deployment
+    /// consumed it.
+    pub fn record_host_ports(&mut self, result: &DeploymentResult) {
+        let mut recorded = BTreeSet::new();
+        if self.external_l1.is_none() {
+            recorded.extend(record(
+                &mut self.anvil.host_port,
+                result.anvil.l1_host_url.as_ref(),
+            ));
+        }
+
+        let stack = &mut self.l2_stack;
+        let nodes = stack
+            .sequencers
+            .iter_mut()
+            .chain(stack.validators.iter_mut());
+        let handlers = result
+            .l2_stack
+            .sequencers
+            .iter()
+            .chain(&result.l2_stack.validators);
+        for (node, handler) in nodes.zip(handlers) {
+            recorded.extend(record(
+                &mut node.op_reth.http_host_port,
+                handler.op_reth.http_host_url.as_ref(),
+            ));
+            recorded.extend(record(
+                &mut node.op_reth.ws_host_port,
+                handler.op_reth.ws_host_url.as_ref(),
+            ));
+            let consensus_host_url = handler.kona_node.rpc_host_url.as_ref();
+            recorded.extend(match node.op_node {
+                Some(ref mut op_node) => record(&mut op_node.rpc_host_port, consensus_host_url),
+                None => record(&mut node.kona_node.rpc_host_port, consensus_host_url),
+            });
+            if let (Some(conductor), Some(conductor_handler)) =
+                (node.op_conductor.as_mut(), handler.op_conductor.as_ref())
+            {
+                recorded.extend(record(
+                    &mut conductor.rpc_host_port,
+                    conductor_handler.rpc_host_url.as_ref(),
+                ));
+            }
+        }
+
+        recorded.extend(record(
+            &mut stack.op_batcher.rpc_host_port,
+            result.l2_stack.op_batcher.rpc_host_url.as_ref(),
+        ));
+        if let (Some(proposer), Some(proposer_handler)) = (
+            stack.op_proposer.as_mut(),
+            result.l2_stack.op_proposer.as_ref(),
+        ) {
+            recorded.extend(record(
+                &mut proposer.rpc_host_port,
+                proposer_handler.rpc_host_url.as_ref(),
+            ));
+        }
+
+        if let Some(ref monitoring) = result.monitoring {
+            recorded.extend(record(
+                &mut self.monitoring.prometheus.host_port,
+                monitoring.prometheus.host_url.as_ref(),
+            ));
+            recorded.extend(record(
+                &mut self.monitoring.grafana.host_port,
+                monitoring.grafana.host_url.as_ref(),
+            ));
+            if let Some(ref logs) = monitoring.logs {
+                recorded.extend(record(
+                    &mut self.monitoring.logs.loki.host_port,
+                    logs.loki.host_url.as_ref(),
+                ));
+            }
+        }
+
+        if let Some(ref blockscout) = result.blockscout {
+            recorded.extend(record(
+                &mut self.blockscout.host_port,
+                blockscout.host_url.as_ref(),
+            ));
+        }
+
+        // Recorded ports may be taken by the next deployment; fixed ones must not move.
+        self.docker.recorded_host_ports.extend(recorded);
+    }
+
+    /// Record the host ports of `result` (see [`Deployer::record_host_ports`]) in the
+    /// saved `Kupcake.toml`, so that the next deployment from it reuses them.
+    ///
+    /// The saved config is loaded and patched rather than overwritten with `self`, as the
+    /// deployment adds to it (e.g. the P2P keys). The service hashes of the deployment
+    /// version are refreshed as well: the running containers already use these ports, so
+    /// they must not count as a config change. Returns the path of the saved config.
+    pub fn persist_host_ports(&self, result: &DeploymentResult) -> Result<PathBuf> {
+        let config_path = self.outdata.join(KUPCONF_FILENAME);
+        let mut saved = if config_path.exists() {
+            Deployer::load_from_file(&config_path)?
+        } else {
+            self.clone()
+        };
+        saved.record_host_ports(result);
+        saved
+            .save_to_file(&config_path)
+            .context("Failed to save the recorded host ports")?;
+
+        let version_file_path = self
+            .outdata
+            .join("l2-stack")
+            .join(DEPLOYMENT_VERSION_FILENAME);
+        if let Ok(mut version) = DeploymentVersion::load_from_file(&version_file_path)
+            && !version.service_hashes.is_empty()
+        {
+            version.service_hashes = service_hashes(&saved)?;
+            version
+                .save_to_file(&version_file_path)
+                .context("Failed to save deployment version")?;
+        }
+
+        Ok(config_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let url = Url::parse("http://localhost:32768/").ok();
+
+        let mut assigned = Some(0);
+        assert_eq!(record(&mut assigned, url.as_ref()), Some(32768));
+        assert_eq!(assigned, Some(32768));
+
+        let mut fixed = Some(8545);
+        assert_eq!(record(&mut fixed, url.as_ref()), None);
+        assert_eq!(fixed, Some(8545));
+
+        let mut unpublished = None;
+        assert_eq!(record(&mut unpublished, url.as_ref()), None);
+        assert_eq!(unpublished, None);
+
+        let mut unbound = Some(0);
+        assert_eq!(record(&mut unbound, None), None);
+        assert_eq!(unbound, Some(0));
+    }
+
+    #[test]
+    fn test_fix_host_ports() {
+        let mut deployer = crate::deployer::tests::test_deployer(PathBuf::from("/tmp/test"));
+        deployer.l2_stack = crate::L2StackBuilder::with_counts(1, 2).spare_batchers(1);
+        deployer.l2_stack.validators[1].op_reth.ws_host_port = None;
+        deployer.l2_stack.op_batcher.rpc_host_port = Some(18548);
+        deployer.fix_host_ports().unwrap();
+
+        assert_eq!(deployer.anvil.host_port, Some(deployer.anvil.port));
+        let stack = &deployer.l2_stack;
+        assert_eq!(stack.sequencers[0].op_reth.http_host_port, Some(9545));
+        assert_eq!(stack.validators[0].op_reth.http_host_port, Some(9645));
+        assert_eq!(stack.validators[1].op_reth.http_host_port, Some(9745));
+        assert_eq!(stack.validators[1].op_reth.ws_host_port, None);
+        assert_eq!(stack.op_batcher.rpc_host_port, Some(18548));
+        assert_eq!(
+            stack.spare_batchers[0].rpc_host_port,
+            Some(stack.op_batcher.rpc_port + FIXED_PORT_STRIDE)
+        );
+    }
+
+    #[test]
+    fn test_fix_host_ports_additional_chain() {
+        let mut deployer = crate::deployer::tests::test_deployer(PathBuf::from("/tmp/test"));
+        deployer.l2_stack = crate::L2StackBuilder::with_counts(1, 1);
+        let mut l2_stack = crate::L2StackBuilder::with_counts(1, 0);
+        l2_stack.sequencers[0].op_reth.container_name = "kupcake-1235-op-reth".to_string();
+        deployer.additional_l2_chains = vec![crate::L2ChainBuilder {
+            chain_id: 1235,
+            l2_stack,
+        }];
+        deployer.fix_host_ports().unwrap();
+
+        let chain_stack = &deployer.additional_l2_chains[0].l2_stack;
+        assert_eq!(chain_stack.sequencers[0].op_reth.http_host_port, Some(9745));
+        assert_eq!(
+            chain_stack.op_batcher.rpc_host_port,
+            Some(chain_stack.op_batcher.rpc_port + FIXED_PORT_STRIDE)
+        );
+    }
+
+    #[test]
+    fn test_fix_host_ports_replaces_recorded_ports() {
+        let dir = tempdir::TempDir::new("kupcake-recorded-ports-test").unwrap();
+        let path = dir.path().join(KUPCONF_FILENAME);
+        let mut deployer = crate::deployer::tests::test_deployer(dir.path().to_path_buf());
+        deployer.anvil.host_port = Some(32768);
+        deployer.l2_stack.op_batcher.rpc_host_port = Some(18548);
+        deployer.docker.recorded_host_ports = BTreeSet::from([32768]);
+        deployer.save_to_file(&path).unwrap();
+
+        let mut loaded = Deployer::load_from_file(&path).unwrap();
+        assert_eq!(loaded.docker.recorded_host_ports, BTreeSet::from([32768]));
+        loaded.fix_host_ports().unwrap();
+
+        assert_eq!(loaded.anvil.host_port, Some(loaded.anvil.port));
+        assert_eq!(loaded.l2_stack.op_batcher.rpc_host_port, Some(18548));
+        assert!(loaded.docker.recorded_host_ports.is_empty());
+    }
+
+    #[test]
+    fn test_fix_host_ports_collision() {
+        let mut deployer = crate::deployer::tests::test_deployer(PathBuf::from("/tmp/test"));
+        deployer.l2_stack.op_batcher.rpc_host_port = Some(9545);
+
+        let err = deployer.fix_host_ports().unwrap_err().to_string();
+        assert!(err.contains("Fixed host port 9545"), "{err}");
+    }
+
+    #[test]
+    fn test_nth_port() {
+        assert_eq!(nth_port(9545, 0).unwrap(), 9545);
+        assert_eq!(nth_port(9545, 2).unwrap(), 9745);
+        assert!(nth_port(65000, 6).is_err());
+    }
+}
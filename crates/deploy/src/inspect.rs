@@ -129,7 +129,7 @@ pub async fn inspect_network(
 ) -> Result<InspectReport> {
     let docker =
         Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
-    let client = rpc::create_client()?;
+    let client = deployer.rpc_client()?;
     let ctx = InspectCtx {
         docker: &docker,
         client: &client,
@@ -160,7 +160,7 @@ pub async fn inspect_network(
             &label,
             &seq.op_reth.container_name,
             seq.op_reth.http_port,
-            &seq.kona_node.container_name,
+            seq.consensus_container_name(),
             seq.kona_node.rpc_port,
             seq.op_conductor
                 .as_ref()
@@ -177,7 +177,7 @@ pub async fn inspect_network(
             &label,
             &val.op_reth.container_name,
             val.op_reth.http_port,
-            &val.kona_node.container_name,
+            val.consensus_container_name(),
             val.kona_node.rpc_port,
             None,
         )
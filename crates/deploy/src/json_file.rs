@@ -0,0 +1,181 @@
+//! Reading of the JSON files written by Anvil and op-deployer.
+//!
+//! A truncated `anvil.json` or a `state.json` from an unexpected op-deployer version
+//! otherwise fails with a bare serde error. The helpers here name the file, quote the
+//! offending content and describe the shape kupcake expects.
+
+use std::{path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use backon::{ConstantBuilder, Retryable};
+use serde_json::Value;
+
+/// Shape of `anvil/anvil.json`.
+pub(crate) const ANVIL_JSON_SHAPE: &str =
+    r#"{"available_accounts": ["0x<address>", ...], "private_keys": ["0x<key>", ...]}"#;
+
+/// Shape of op-deployer's `state.json`, restricted to the fields kupcake reads.
+pub(crate) const STATE_JSON_SHAPE: &str = r#"{"opChainDeployments": [{"id": "0x<chain id>", "<Contract>Proxy": "0x<address>", ...}], ...}"#;
+
+/// Number of reads of `anvil.json` before giving up on a truncated file.
+const ANVIL_JSON_READ_ATTEMPTS: u32 = 5;
+
+/// Delay between two reads of a truncated `anvil.json`.
+const ANVIL_JSON_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Characters of context quoted on each side of a parse error.
+const SNIPPET_RADIUS: usize = 40;
+
+/// Up to [`SNIPPET_RADIUS`] characters on each side of `line`:`column` (1-based, as
+/// reported by serde_json), on a single line.
+fn snippet(content: &str, line: usize, column: usize) -> String {
+    let Some(text) = content.lines().nth(line.saturating_sub(1)) else {
+        return String::new();
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let at = column.saturating_sub(1).min(chars.len());
+    let start = at.saturating_sub(SNIPPET_RADIUS);
+    let end = (at + SNIPPET_RADIUS).min(chars.len());
+
+    let mut snippet: String = chars[start..end].iter().collect();
+    if start > 0 {
+        snippet.insert_str(0, "...");
+    }
+    if end < chars.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+/// Parse `content`, read from `path`, describing `expected` on failure.
+fn parse_json(path: &Path, content: &str, expected: &str) -> Result<Value> {
+    serde_json::from_str(content).map_err(|e| {
+        let near = if content.trim().is_empty() {
+            "the file is empty".to_string()
+        } else {
+            format!("near {:?}", snippet(content, e.line(), e.column()))
+        };
+        anyhow::anyhow!(
+            "Failed to parse {}: {} ({}); expected {}",
+            path.display(),
+            e,
+            near,
+            expected
+        )
+    })
+}
+
+/// Whether a parse failure looks like a file still being written.
+fn is_truncated(content: &str) -> bool {
+    serde_json::from_str::<Value>(content).is_err_and(|e| e.is_eof())
+}
+
+/// Read `anvil/anvil.json` from the output directory.
+///
+/// The file is rewritten on every deployment; a truncated read is retried a few times
+/// before failing, in case the deployment is writing it concurrently.
+pub(crate) async fn read_anvil_json(outdata: &Path) -> Result<Value> {
+    let path = outdata.join("anvil/anvil.json");
+    let backoff = ConstantBuilder::default()
+        .with_delay(ANVIL_JSON_RETRY_DELAY)
+        .with_max_times(ANVIL_JSON_READ_ATTEMPTS as usize - 1);
+
+    // A truncated read is the only retried error; it carries the content read.
+    let read = (|| async {
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()));
+        match content {
+            Ok(content) if is_truncated(&content) => Err(content),
+            Ok(content) => Ok(parse_json(&path, &content, ANVIL_JSON_SHAPE)),
+            Err(e) => Ok(Err(e)),
+        }
+    })
+    .retry(backoff)
+    .notify(|_, _| tracing::debug!(path = %path.display(), "anvil.json is truncated, retrying"))
+    .await;
+
+    read.unwrap_or_else(|content| parse_json(&path, &content, ANVIL_JSON_SHAPE))
+}
+
+/// Read op-deployer's `state.json` from `dir`, usually the `l2-stack` output directory.
+pub(crate) fn read_state_json(dir: &Path) -> Result<Value> {
+    let path = dir.join("state.json");
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_json(&path, &content, STATE_JSON_SHAPE)
+}
+
+/// Address of `contract` in the first chain of a parsed `state.json`.
+pub(crate) fn state_chain_address(state: &Value, contract: &str) -> Result<String> {
+    state["opChainDeployments"][0][contract]
+        .as_str()
+        .map(String::from)
+        .with_context(|| {
+            format!(
+                "{} address not found in state.json opChainDeployments[0]; expected {}",
+                contract, STATE_JSON_SHAPE
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_names_file_and_shape() {
+        let path = Path::new("/out/l2-stack/state.json");
+        let err = parse_json(path, r#"{"opChainDeployments": [}"#, STATE_JSON_SHAPE)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("/out/l2-stack/state.json"), "{err}");
+        assert!(
+            err.contains(r#"near "{\"opChainDeployments\": [}""#),
+            "{err}"
+        );
+        assert!(err.contains("expected {\"opChainDeployments\""), "{err}");
+
+        let err = parse_json(path, "", STATE_JSON_SHAPE).unwrap_err();
+        assert!(err.to_string().contains("the file is empty"));
+    }
+
+    #[test]
+    fn test_snippet_is_bounded() {
+        let line = format!("{}X{}", "a".repeat(100), "b".repeat(100));
+        let snippet = snippet(&line, 1, 101);
+        assert_eq!(
+            snippet,
+            format!("...{}X{}...", "a".repeat(40), "b".repeat(39))
+        );
+        assert_eq!(super::snippet("{}", 3, 1), "");
+    }
+
+    #[tokio::test]
+    async fn test_read_anvil_json_truncated() {
+        let dir = tempdir::TempDir::new("json-file-test").unwrap();
+        std::fs::create_dir_all(dir.path().join("anvil")).unwrap();
+        std::fs::write(
+            dir.path().join("anvil/anvil.json"),
+            r#"{"available_accounts": ["0xf39F"#,
+        )
+        .unwrap();
+
+        let err = read_anvil_json(dir.path()).await.unwrap_err().to_string();
+        assert!(err.contains("anvil.json"), "{err}");
+        assert!(err.contains("available_accounts"), "{err}");
+    }
+
+    #[test]
+    fn test_state_chain_address() {
+        let state = serde_json::json!({
+            "opChainDeployments": [{"SystemConfigProxy": "0x0000000000000000000000000000000000000001"}]
+        });
+        assert_eq!(
+            state_chain_address(&state, "SystemConfigProxy").unwrap(),
+            "0x0000000000000000000000000000000000000001"
+        );
+        let err = state_chain_address(&state, "OptimismPortalProxy").unwrap_err();
+        assert!(err.to_string().contains("expected {\"opChainDeployments\""));
+    }
+}
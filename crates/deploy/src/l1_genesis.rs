@@ -158,10 +158,10 @@ pub fn extract_l1_genesis(
 /// This function queries Anvil's block 0, extracts the actual hash, and patches
 /// `genesis.l1.hash` in rollup.json to match.
 pub async fn patch_rollup_l1_genesis_hash(
+    client: &reqwest::Client,
     rollup_json_path: &Path,
     anvil_url: &url::Url,
 ) -> Result<()> {
-    let client = crate::rpc::create_client()?;
     let url = anvil_url.as_str().to_string();
 
     // Wait for Anvil to be ready (it may not have opened its RPC port yet,
@@ -192,7 +192,7 @@ pub async fn patch_rollup_l1_genesis_hash(
 
     // Now fetch the actual genesis block hash
     let block: serde_json::Value = crate::rpc::json_rpc_call(
-        &client,
+        client,
         anvil_url.as_str(),
         "eth_getBlockByNumber",
         vec![serde_json::json!("0x0"), serde_json::json!(false)],
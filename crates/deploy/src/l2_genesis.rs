@@ -0,0 +1,637 @@
+//! L2 genesis patching.
+//!
+//! op-deployer generates the L2 `genesis.json` consumed by op-reth and the
+//! `rollup.json` consumed by kona-node. Some experiments need non-standard genesis
+//! fields (custom precompiles, EIP-1559 elasticity, extra allocations, a different gas
+//! limit), so this module deep-merges a user-provided JSON patch into `genesis.json`
+//! before op-reth starts.
+//!
+//! Accounts can also be prefunded: their balance is set in the genesis `alloc`, so they
+//! hold ETH from block 0.
+//!
+//! op-deployer activates every hardfork at genesis. To test derivation across a fork
+//! boundary, [`apply_hardfork_offsets`] moves chosen forks to a number of seconds after
+//! the L2 genesis, in both `rollup.json` and `genesis.json`.
+//!
+//! The rollup config embeds the L2 genesis block hash, which kona-node checks against
+//! the block produced by op-reth. Patching the genesis header or allocations, prefunding
+//! and hardfork offsets change that hash, so once the first op-reth is up,
+//! [`patch_rollup_l2_genesis_hash`] records the hash it actually serves in `rollup.json`.
+//! Chain ID and hardfork activation times must agree with `rollup.json` and are validated
+//! after the patch is applied.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use alloy_core::primitives::{Address, U256};
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::faucet::EthAmount;
+
+/// Timeout for op-reth to serve the L2 genesis block, in seconds.
+const OP_RETH_GENESIS_READY_TIMEOUT_SECS: u64 = 60;
+
+/// Top-level genesis fields that feed into the L2 genesis block hash.
+///
+/// Changing any of these makes the genesis hash diverge from the one recorded in `rollup.json`.
+const GENESIS_HASH_FIELDS: &[&str] = &[
+    "alloc",
+    "baseFeePerGas",
+    "blobGasUsed",
+    "coinbase",
+    "difficulty",
+    "excessBlobGas",
+    "extraData",
+    "gasLimit",
+    "mixHash",
+    "nonce",
+    "number",
+    "parentHash",
+    "stateRoot",
+    "timestamp",
+];
+
+/// Hardfork activation times, as `(genesis.json config key, rollup.json key)`.
+const HARDFORK_TIME_FIELDS: &[(&str, &str)] = &[
+    ("regolithTime", "regolith_time"),
+    ("canyonTime", "canyon_time"),
+    ("deltaTime", "delta_time"),
+    ("ecotoneTime", "ecotone_time"),
+    ("fjordTime", "fjord_time"),
+    ("graniteTime", "granite_time"),
+    ("holoceneTime", "holocene_time"),
+    ("isthmusTime", "isthmus_time"),
+    ("jovianTime", "jovian_time"),
+];
+
+/// L2 hardforks in activation order, as `(name, rollup.json key, genesis.json config keys)`.
+///
+/// Canyon, Ecotone and Isthmus also activate an L1 fork (Shanghai, Cancun, Prague) on the
+/// execution layer, at the same time.
+pub const L2_HARDFORKS: &[(&str, &str, &[&str])] = &[
+    ("regolith", "regolith_time", &["regolithTime"]),
+    ("canyon", "canyon_time", &["canyonTime", "shanghaiTime"]),
+    ("delta", "delta_time", &["deltaTime"]),
+    ("ecotone", "ecotone_time", &["ecotoneTime", "cancunTime"]),
+    ("fjord", "fjord_time", &["fjordTime"]),
+    ("granite", "granite_time", &["graniteTime"]),
+    ("holocene", "holocene_time", &["holoceneTime"]),
+    ("isthmus", "isthmus_time", &["isthmusTime", "pragueTime"]),
+    ("jovian", "jovian_time", &["jovianTime"]),
+];
+
+/// Position of `name` in [`L2_HARDFORKS`], if it is a known L2 hardfork.
+pub fn l2_hardfork_index(name: &str) -> Option<usize> {
+    L2_HARDFORKS.iter().position(|(fork, _, _)| *fork == name)
+}
+
+/// Deep-merge `patch` into `target`.
+///
+/// Objects are merged key by key; any other value in the patch replaces the target value.
+pub fn merge_json(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch.clone(),
+    }
+}
+
+/// Returns the top-level fields of `patch` that change the L2 genesis block hash.
+pub fn genesis_hash_fields_touched(patch: &Value) -> Vec<&str> {
+    patch
+        .as_object()
+        .map(|obj| {
+            obj.keys()
+                .map(String::as_str)
+                .filter(|key| GENESIS_HASH_FIELDS.contains(key))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Check that a patched genesis still agrees with the rollup config.
+///
+/// Verifies the L2 chain ID and every hardfork activation time present in both files.
+pub fn validate_against_rollup(genesis: &Value, rollup: &Value) -> Result<()> {
+    let config = &genesis["config"];
+
+    let genesis_chain_id = config["chainId"].as_u64();
+    let rollup_chain_id = rollup["l2_chain_id"].as_u64();
+    if genesis_chain_id != rollup_chain_id {
+        anyhow::bail!(
+            "Patched genesis chainId ({:?}) does not match rollup.json l2_chain_id ({:?})",
+            genesis_chain_id,
+            rollup_chain_id
+        );
+    }
+
+    for (genesis_key, rollup_key) in HARDFORK_TIME_FIELDS {
+        let (Some(genesis_time), Some(rollup_time)) =
+            (config[genesis_key].as_u64(), rollup[rollup_key].as_u64())
+        else {
+            continue;
+        };
+
+        if genesis_time != rollup_time {
+            anyhow::bail!(
+                "Patched genesis {} ({}) does not match rollup.json {} ({})",
+                genesis_key,
+                genesis_time,
+                rollup_key,
+                rollup_time
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply a JSON patch to `{host_config_path}/genesis.json`.
+///
+/// The patch is deep-merged into the existing genesis, validated against `rollup.json`
+/// and written back in place. Patches touching [`GENESIS_HASH_FIELDS`] change the genesis
+/// block hash, which [`patch_rollup_l2_genesis_hash`] then updates in `rollup.json`.
+/// Applying the same patch twice is a no-op, so this is safe to call on redeploys that
+/// reuse existing L2 configs.
+pub fn apply_genesis_patch(host_config_path: &Path, patch: &Value) -> Result<()> {
+    let genesis_path = host_config_path.join("genesis.json");
+    let rollup_path = host_config_path.join("rollup.json");
+
+    let genesis_content = std::fs::read_to_string(&genesis_path)
+        .with_context(|| format!("Failed to read {}", genesis_path.display()))?;
+    let mut genesis: Value =
+        serde_json::from_str(&genesis_content).context("Failed to parse L2 genesis.json")?;
+
+    let rollup_content = std::fs::read_to_string(&rollup_path)
+        .with_context(|| format!("Failed to read {}", rollup_path.display()))?;
+    let rollup: Value =
+        serde_json::from_str(&rollup_content).context("Failed to parse rollup.json")?;
+
+    let touched = genesis_hash_fields_touched(patch);
+    if !touched.is_empty() {
+        tracing::info!(
+            fields = ?touched,
+            "Genesis patch changes fields that affect the L2 genesis block hash; \
+             rollup.json is updated with op-reth's genesis hash once it starts"
+        );
+    }
+
+    merge_json(&mut genesis, patch);
+    validate_against_rollup(&genesis, &rollup)?;
+
+    let content =
+        serde_json::to_string_pretty(&genesis).context("Failed to serialize L2 genesis.json")?;
+    std::fs::write(&genesis_path, content)
+        .with_context(|| format!("Failed to write {}", genesis_path.display()))?;
+
+    tracing::info!(?genesis_path, "Applied genesis patch to L2 genesis.json");
+
+    Ok(())
+}
+
+/// Set the balance of `accounts` (in wei) in the `alloc` of an L2 genesis.
+///
+/// Accounts already in the allocations (predeploys, precompiles, dev accounts) keep their
+/// code, storage and nonce, only their balance is replaced; new accounts are added with
+/// the key format of the existing ones. Setting the same balances twice is a no-op.
+pub fn prefund_accounts(genesis: &mut Value, accounts: &BTreeMap<Address, U256>) -> Result<()> {
+    let alloc = genesis
+        .get_mut("alloc")
+        .and_then(Value::as_object_mut)
+        .context("L2 genesis.json has no alloc")?;
+    let prefixed = alloc.keys().next().is_none_or(|key| key.starts_with("0x"));
+
+    for (address, balance) in accounts {
+        let unprefixed = hex::encode(address);
+        let key = alloc
+            .keys()
+            .find(|key| {
+                key.trim_start_matches("0x")
+                    .eq_ignore_ascii_case(&unprefixed)
+            })
+            .cloned()
+            .unwrap_or_else(|| {
+                if prefixed {
+                    format!("0x{}", unprefixed)
+                } else {
+                    unprefixed
+                }
+            });
+
+        alloc
+            .entry(key)
+            .or_insert_with(|| Value::Object(Default::default()))
+            .as_object_mut()
+            .with_context(|| format!("Genesis allocation of {} is not an object", address))?
+            .insert(
+                "balance".to_string(),
+                Value::String(format!("{:#x}", balance)),
+            );
+    }
+
+    Ok(())
+}
+
+/// Activate the forks of `offsets` that many seconds after the L2 genesis.
+///
+/// Activation times are absolute timestamps, set to `genesis.l2_time` of `rollup.json`
+/// plus the offset in both files. Later forks that are scheduled are moved so that they
+/// do not activate before an earlier one; unscheduled forks stay unscheduled unless they
+/// are in `offsets`. Applying the same offsets twice is a no-op.
+pub fn set_hardfork_offsets(
+    genesis: &mut Value,
+    rollup: &mut Value,
+    offsets: &BTreeMap<String, u64>,
+) -> Result<()> {
+    let l2_time = rollup
+        .pointer("/genesis/l2_time")
+        .and_then(Value::as_u64)
+        .context("rollup.json missing genesis.l2_time")?;
+    let rollup = rollup
+        .as_object_mut()
+        .context("rollup.json is not an object")?;
+    let config = genesis
+        .get_mut("config")
+        .and_then(Value::as_object_mut)
+        .context("genesis.json missing config object")?;
+
+    let mut floor = 0;
+    for (name, rollup_key, genesis_keys) in L2_HARDFORKS {
+        let current = rollup.get(*rollup_key).and_then(Value::as_u64);
+        let time = match (offsets.get(*name), current) {
+            (Some(offset), _) => l2_time
+                .checked_add(*offset)
+                .with_context(|| format!("{} offset {} overflows", name, offset))?,
+            (None, Some(time)) => time,
+            (None, None) => continue,
+        }
+        .max(floor);
+        floor = time;
+
+        if current == Some(time) && !offsets.contains_key(*name) {
+            continue;
+        }
+        rollup.insert(rollup_key.to_string(), Value::from(time));
+        for key in *genesis_keys {
+            config.insert(key.to_string(), Value::from(time));
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply hardfork offsets to `{host_config_path}/rollup.json` and `genesis.json` (see
+/// [`set_hardfork_offsets`]).
+pub fn apply_hardfork_offsets(
+    host_config_path: &Path,
+    offsets: &BTreeMap<String, u64>,
+) -> Result<()> {
+    let genesis_path = host_config_path.join("genesis.json");
+    let rollup_path = host_config_path.join("rollup.json");
+
+    let mut genesis: Value = serde_json::from_str(
+        &std::fs::read_to_string(&genesis_path)
+            .with_context(|| format!("Failed to read {}", genesis_path.display()))?,
+    )
+    .context("Failed to parse L2 genesis.json")?;
+    let mut rollup: Value = serde_json::from_str(
+        &std::fs::read_to_string(&rollup_path)
+            .with_context(|| format!("Failed to read {}", rollup_path.display()))?,
+    )
+    .context("Failed to parse rollup.json")?;
+
+    set_hardfork_offsets(&mut genesis, &mut rollup, offsets)?;
+
+    for (path, value) in [(&genesis_path, &genesis), (&rollup_path, &rollup)] {
+        let content = serde_json::to_string_pretty(value)
+            .with_context(|| format!("Failed to serialize {}", path.display()))?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    tracing::info!(?offsets, "Set L2 hardfork activation offsets");
+    Ok(())
+}
+
+/// Parse an `ADDRESS=ETH` prefund entry, e.g. `0xf39F...2266=100` or `0xf39F...2266=0.5`.
+pub fn parse_prefund(entry: &str) -> Result<(Address, EthAmount)> {
+    let (address, amount) = entry
+        .split_once('=')
+        .with_context(|| format!("Invalid prefund entry '{}': expected ADDRESS=ETH", entry))?;
+    let address: Address = address
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid prefund address '{}'", address))?;
+    let amount: EthAmount = amount
+        .parse()
+        .with_context(|| format!("Invalid prefund amount '{}'", amount))?;
+    Ok((address, amount))
+}
+
+/// Parse a `FORK=SECONDS` hardfork offset entry, e.g. `isthmus=120`.
+pub fn parse_hardfork_offset(entry: &str) -> Result<(String, u64)> {
+    let (fork, seconds) = entry
+        .split_once('=')
+        .with_context(|| format!("Invalid hardfork offset '{}': expected FORK=SECONDS", entry))?;
+    let fork = fork.trim().to_lowercase();
+    if l2_hardfork_index(&fork).is_none() {
+        anyhow::bail!(
+            "Unknown L2 hardfork '{}': expected one of {}",
+            fork,
+            L2_HARDFORKS
+                .iter()
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let seconds: u64 = seconds
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid hardfork offset '{}' for {}", seconds, fork))?;
+    Ok((fork, seconds))
+}
+
+/// Prefund `accounts` in `{host_config_path}/genesis.json` (see [`prefund_accounts`]).
+pub fn apply_prefund(host_config_path: &Path, accounts: &BTreeMap<Address, U256>) -> Result<()> {
+    let genesis_path = host_config_path.join("genesis.json");
+
+    let genesis_content = std::fs::read_to_string(&genesis_path)
+        .with_context(|| format!("Failed to read {}", genesis_path.display()))?;
+    let mut genesis: Value =
+        serde_json::from_str(&genesis_content).context("Failed to parse L2 genesis.json")?;
+
+    prefund_accounts(&mut genesis, accounts)?;
+
+    let content =
+        serde_json::to_string_pretty(&genesis).context("Failed to serialize L2 genesis.json")?;
+    std::fs::write(&genesis_path, content)
+        .with_context(|| format!("Failed to write {}", genesis_path.display()))?;
+
+    tracing::info!(
+        accounts = accounts.len(),
+        "Prefunded accounts in L2 genesis.json"
+    );
+
+    Ok(())
+}
+
+/// Patch rollup.json with the L2 genesis block hash served by op-reth.
+///
+/// Needed when the allocations or header of `genesis.json` were changed after op-deployer
+/// generated it, as the hash recorded in `genesis.l2.hash` is then stale. Must run before
+/// the consensus clients start, as they read `rollup.json` at startup.
+pub async fn patch_rollup_l2_genesis_hash(
+    client: &reqwest::Client,
+    rollup_json_path: &Path,
+    op_reth_url: &str,
+) -> Result<()> {
+    let get_genesis_block = || {
+        crate::rpc::json_rpc_call::<Value>(
+            client,
+            op_reth_url,
+            "eth_getBlockByNumber",
+            vec![serde_json::json!("0x0"), serde_json::json!(false)],
+        )
+    };
+
+    crate::rpc::wait_until_ready(
+        "op-reth genesis block",
+        OP_RETH_GENESIS_READY_TIMEOUT_SECS,
+        || async { get_genesis_block().await.map(|_| ()) },
+    )
+    .await
+    .context(format!(
+        "op-reth not ready after {}s",
+        OP_RETH_GENESIS_READY_TIMEOUT_SECS
+    ))?;
+
+    let block = get_genesis_block()
+        .await
+        .context("Failed to query op-reth for the L2 genesis block")?;
+    let actual_hash = block
+        .get("hash")
+        .and_then(|h| h.as_str())
+        .context("op-reth response missing hash for block 0")?;
+
+    let content = std::fs::read_to_string(rollup_json_path)
+        .with_context(|| format!("Failed to read {}", rollup_json_path.display()))?;
+    let mut rollup: Value =
+        serde_json::from_str(&content).context("Failed to parse rollup.json")?;
+
+    let l2_hash = rollup
+        .pointer_mut("/genesis/l2/hash")
+        .context("rollup.json missing genesis.l2.hash field")?;
+    if l2_hash.as_str() == Some(actual_hash) {
+        return Ok(());
+    }
+    let original_hash = std::mem::replace(l2_hash, Value::String(actual_hash.to_string()));
+
+    let patched =
+        serde_json::to_string_pretty(&rollup).context("Failed to serialize patched rollup.json")?;
+    std::fs::write(rollup_json_path, patched)
+        .with_context(|| format!("Failed to write patched {}", rollup_json_path.display()))?;
+
+    tracing::info!(
+        original = %original_hash,
+        patched = %actual_hash,
+        "Patched rollup.json L2 genesis hash to match the patched genesis"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_json_nested_objects() {
+        let mut target = json!({
+            "config": { "chainId": 42069, "optimism": { "eip1559Elasticity": 6 } },
+            "gasLimit": "0x1c9c380"
+        });
+        merge_json(
+            &mut target,
+            &json!({ "config": { "optimism": { "eip1559Elasticity": 10 } } }),
+        );
+
+        assert_eq!(target["config"]["chainId"], 42069);
+        assert_eq!(target["config"]["optimism"]["eip1559Elasticity"], 10);
+        assert_eq!(target["gasLimit"], "0x1c9c380");
+    }
+
+    #[test]
+    fn test_merge_json_is_idempotent() {
+        let patch = json!({ "config": { "customField": [1, 2, 3] } });
+        let mut once = json!({ "config": { "chainId": 1 } });
+        merge_json(&mut once, &patch);
+        let mut twice = once.clone();
+        merge_json(&mut twice, &patch);
+
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_genesis_hash_fields_touched() {
+        let patch = json!({ "alloc": {}, "config": {}, "gasLimit": "0x1" });
+        let mut touched = genesis_hash_fields_touched(&patch);
+        touched.sort();
+
+        assert_eq!(touched, vec!["alloc", "gasLimit"]);
+        assert!(genesis_hash_fields_touched(&json!({ "config": {} })).is_empty());
+    }
+
+    #[test]
+    fn test_apply_genesis_patch_with_hash_fields() {
+        let dir = tempdir::TempDir::new("kupcake-test").unwrap();
+        let genesis = json!({
+            "config": { "chainId": 42069 },
+            "gasLimit": "0x1c9c380",
+            "alloc": { "0000000000000000000000000000000000000001": { "balance": "0x1" } }
+        });
+        std::fs::write(dir.path().join("genesis.json"), genesis.to_string()).unwrap();
+        std::fs::write(
+            dir.path().join("rollup.json"),
+            json!({ "l2_chain_id": 42069 }).to_string(),
+        )
+        .unwrap();
+
+        let patch = json!({
+            "alloc": { "00000000000000000000000000000000000000aa": { "balance": "0xff" } },
+            "gasLimit": "0x3938700"
+        });
+        apply_genesis_patch(dir.path(), &patch).unwrap();
+
+        let patched = std::fs::read_to_string(dir.path().join("genesis.json")).unwrap();
+        let patched: Value = serde_json::from_str(&patched).unwrap();
+        assert_eq!(patched["gasLimit"], "0x3938700");
+        assert_eq!(patched["alloc"].as_object().unwrap().len(), 2);
+        assert_eq!(
+            patched["alloc"]["00000000000000000000000000000000000000aa"]["balance"],
+            "0xff"
+        );
+    }
+
+    #[test]
+    fn test_prefund_accounts() {
+        let precompile = json!({ "code": "0x00", "balance": "0x1" });
+        let mut genesis = json!({
+            "alloc": {
+                "0000000000000000000000000000000000000001": precompile,
+                "f39fd6e51aad88f6f4ce6ab8827279cfffb92266": { "balance": "0x0", "nonce": "0x1" }
+            }
+        });
+        let dev: Address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+            .parse()
+            .unwrap();
+        let new: Address = "0x00000000000000000000000000000000000000aa"
+            .parse()
+            .unwrap();
+        let accounts = BTreeMap::from([
+            (dev, U256::from(10u64).pow(U256::from(18))),
+            (new, U256::from(255)),
+        ]);
+
+        prefund_accounts(&mut genesis, &accounts).unwrap();
+        let once = genesis.clone();
+        prefund_accounts(&mut genesis, &accounts).unwrap();
+        assert_eq!(genesis, once);
+
+        let alloc = genesis["alloc"].as_object().unwrap();
+        assert_eq!(alloc.len(), 3);
+        assert_eq!(
+            alloc["0000000000000000000000000000000000000001"],
+            precompile
+        );
+        assert_eq!(
+            alloc["f39fd6e51aad88f6f4ce6ab8827279cfffb92266"],
+            json!({ "balance": "0xde0b6b3a7640000", "nonce": "0x1" })
+        );
+        assert_eq!(
+            alloc["00000000000000000000000000000000000000aa"]["balance"],
+            "0xff"
+        );
+    }
+
+    #[test]
+    fn test_parse_prefund() {
+        let (address, amount) =
+            parse_prefund("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266=1.5").unwrap();
+        assert_eq!(
+            address,
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+                .parse::<Address>()
+                .unwrap()
+        );
+        assert_eq!(amount.wei(), 1_500_000_000_000_000_000);
+
+        assert!(parse_prefund("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").is_err());
+        assert!(parse_prefund("0x1234=1").is_err());
+        assert!(parse_prefund("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266=-1").is_err());
+    }
+
+    #[test]
+    fn test_set_hardfork_offsets() {
+        let mut rollup = json!({
+            "l2_chain_id": 42069,
+            "genesis": { "l2_time": 1000 },
+            "canyon_time": 0,
+            "ecotone_time": 0,
+            "granite_time": 0,
+            "holocene_time": 0,
+            "isthmus_time": 0,
+        });
+        let mut genesis = json!({
+            "config": {
+                "chainId": 42069,
+                "shanghaiTime": 0,
+                "canyonTime": 0,
+                "holoceneTime": 0,
+                "isthmusTime": 0,
+                "pragueTime": 0,
+            }
+        });
+        let offsets = BTreeMap::from([("holocene".to_string(), 60)]);
+
+        set_hardfork_offsets(&mut genesis, &mut rollup, &offsets).unwrap();
+
+        assert_eq!(rollup["granite_time"], 0);
+        assert_eq!(rollup["holocene_time"], 1060);
+        // Isthmus cannot activate before Holocene
+        assert_eq!(rollup["isthmus_time"], 1060);
+        assert!(rollup.get("jovian_time").is_none());
+        assert_eq!(genesis["config"]["canyonTime"], 0);
+        assert_eq!(genesis["config"]["holoceneTime"], 1060);
+        assert_eq!(genesis["config"]["pragueTime"], 1060);
+        validate_against_rollup(&genesis, &rollup).unwrap();
+
+        let (genesis_before, rollup_before) = (genesis.clone(), rollup.clone());
+        set_hardfork_offsets(&mut genesis, &mut rollup, &offsets).unwrap();
+        assert_eq!((genesis, rollup), (genesis_before, rollup_before));
+    }
+
+    #[test]
+    fn test_validate_against_rollup() {
+        let rollup = json!({ "l2_chain_id": 42069, "canyon_time": 0 });
+
+        let genesis = json!({ "config": { "chainId": 42069, "canyonTime": 0 } });
+        assert!(validate_against_rollup(&genesis, &rollup).is_ok());
+
+        let wrong_chain = json!({ "config": { "chainId": 1, "canyonTime": 0 } });
+        assert!(validate_against_rollup(&wrong_chain, &rollup).is_err());
+
+        let wrong_fork = json!({ "config": { "chainId": 42069, "canyonTime": 10 } });
+        assert!(validate_against_rollup(&wrong_fork, &rollup).is_err());
+    }
+}
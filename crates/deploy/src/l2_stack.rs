@@ -1,21 +1,29 @@
 //! L2 Stack configuration and deployment.
 
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
+use alloy_core::primitives::{Address, U256};
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     AnvilHandler, KupDocker, OpBatcherBuilder, OpBatcherHandler, OpChallengerBuilder,
-    OpChallengerHandler, OpConductorBuilder, OpProposerBuilder, OpProposerHandler,
+    OpChallengerHandler, OpConductorBuilder, OpProposerBuilder, OpProposerHandler, PruningMode,
     deployer::L2StackHandler,
-    fs,
+    faucet::EthAmount,
+    fs, l2_genesis,
     metrics::{DeploymentMetrics, ServiceMetrics, get_image_info, get_image_size},
+    rpc,
     service::KupcakeService,
     services::{
         OpBatcherInput, OpChallengerInput, OpProposerInput,
-        l2_node::{ConductorContext, L2NodeBuilder, L2NodeHandler, L2NodeInput},
+        l2_node::{ConductorContext, ConsensusClient, L2NodeBuilder, L2NodeHandler, L2NodeInput},
+        op_node::OpNodeBuilder,
     },
+    warnings::{DeployWarning, best_effort, check_readiness},
 };
 
 /// Combined configuration for all L2 components for the op-stack.
@@ -53,8 +61,66 @@ pub struct L2StackBuilder<
     /// Configuration for op-challenger (None to skip deployment).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub op_challenger: Option<C>,
+    /// Hot-spare op-batchers, started stopped with their own account.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spare_batchers: Vec<B>,
+    /// Hot-spare op-proposers, stopped right after startup, with their own account.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spare_proposers: Vec<P>,
+    /// JSON patch deep-merged into the L2 `genesis.json` before op-reth starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_patch: Option<serde_json::Value>,
+    /// L2 accounts funded at genesis, with their balance in wei.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prefund: BTreeMap<Address, U256>,
+    /// L2 hardforks activated after genesis, by name, with their offset from the L2
+    /// genesis timestamp in seconds.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hardfork_offsets: BTreeMap<String, u64>,
+    /// With op-conductor, make sure the elected leader's sequencer is producing blocks
+    /// once all sequencers are up. Disable to leave sequencing to the conductors.
+    #[serde(default = "default_start_sequencing")]
+    pub start_sequencing: bool,
+}
+
+/// An additional L2 chain of the deployment, settled on the same L1 as the primary chain.
+///
+/// See [`DeployerBuilder::add_l2_chain`](crate::DeployerBuilder::add_l2_chain).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "Node: Serialize, B: Serialize, P: Serialize, C: Serialize",
+    deserialize = "Node: serde::de::DeserializeOwned, B: serde::de::DeserializeOwned, P: serde::de::DeserializeOwned, C: serde::de::DeserializeOwned"
+))]
+pub struct L2ChainBuilder<
+    Node = L2NodeBuilder,
+    B = OpBatcherBuilder,
+    P = OpProposerBuilder,
+    C = OpChallengerBuilder,
+> {
+    /// The L2 chain ID.
+    pub chain_id: u64,
+    /// Configuration of the L2 components of the chain.
+    #[serde(flatten)]
+    pub l2_stack: L2StackBuilder<Node, B, P, C>,
+}
+
+impl<Node, B, P, C> L2ChainBuilder<Node, B, P, C> {
+    /// Data directory of the chain, `l2-stack-<chain_id>` under `outdata`.
+    pub fn data_path(&self, outdata: &Path) -> PathBuf {
+        outdata.join(format!("l2-stack-{}", self.chain_id))
+    }
 }
 
+fn default_start_sequencing() -> bool {
+    true
+}
+
+/// Host port of the first node's op-reth metrics with [`L2StackBuilder::expose_metrics`].
+pub const DEFAULT_OP_RETH_METRICS_BASE_PORT: u16 = 19001;
+/// Host port of the first node's consensus client metrics with
+/// [`L2StackBuilder::expose_metrics`].
+pub const DEFAULT_CONSENSUS_METRICS_BASE_PORT: u16 = 17300;
+
 impl Default for L2StackBuilder {
     fn default() -> Self {
         Self {
@@ -63,6 +129,12 @@ impl Default for L2StackBuilder {
             op_batcher: OpBatcherBuilder::default(),
             op_proposer: Some(OpProposerBuilder::default()),
             op_challenger: Some(OpChallengerBuilder::default()),
+            spare_batchers: Vec::new(),
+            spare_proposers: Vec::new(),
+            genesis_patch: None,
+            prefund: BTreeMap::new(),
+            hardfork_offsets: BTreeMap::new(),
+            start_sequencing: true,
         }
     }
 }
@@ -117,6 +189,12 @@ impl L2StackBuilder {
             op_batcher: OpBatcherBuilder::default(),
             op_proposer: Some(OpProposerBuilder::default()),
             op_challenger: Some(OpChallengerBuilder::default()),
+            spare_batchers: Vec::new(),
+            spare_proposers: Vec::new(),
+            genesis_patch: None,
+            prefund: BTreeMap::new(),
+            hardfork_offsets: BTreeMap::new(),
+            start_sequencing: true,
         }
     }
 
@@ -185,6 +263,194 @@ impl L2StackBuilder {
         self.sequencers.iter().any(|s| s.op_conductor.is_some())
     }
 
+    /// Select the consensus client for all nodes (sequencers and validators).
+    ///
+    /// With [`ConsensusClient::OpNode`], each node gets an op-node configuration derived
+    /// from its kona-node one, using `op_node_image`. With [`ConsensusClient::Kona`],
+    /// any op-node configuration is dropped.
+    pub fn consensus_client(
+        mut self,
+        client: ConsensusClient,
+        op_node_image: crate::docker::DockerImage,
+    ) -> Self {
+        for node in self.sequencers.iter_mut().chain(self.validators.iter_mut()) {
+            node.op_node = match client {
+                ConsensusClient::Kona => None,
+                ConsensusClient::OpNode => Some(OpNodeBuilder::from_kona_node(
+                    &node.kona_node,
+                    op_node_image.clone(),
+                )),
+            };
+        }
+        self
+    }
+
+    /// Add `count` hot-spare op-batchers, copies of the primary one named
+    /// `<op-batcher>-spare-<i>` and started with `--stopped`.
+    ///
+    /// Each spare submits with its own funded account (the first extra Anvil accounts),
+    /// so taking over also requires pointing the `SystemConfig` batcher at its address.
+    pub fn spare_batchers(mut self, count: usize) -> Self {
+        self.spare_batchers = (1..=count)
+            .map(|i| OpBatcherBuilder {
+                container_name: format!("{}-spare-{}", self.op_batcher.container_name, i),
+                rpc_host_port: Some(0),
+                metrics_host_port: None,
+                stopped: true,
+                ..self.op_batcher.clone()
+            })
+            .collect();
+        self
+    }
+
+    /// Add `count` hot-spare op-proposers, copies of the primary one named
+    /// `<op-proposer>-spare-<i>` and stopped right after startup.
+    ///
+    /// Each spare uses its own funded account, taken after those of the spare batchers.
+    /// Does nothing when op-proposer is disabled.
+    pub fn spare_proposers(mut self, count: usize) -> Self {
+        let Some(ref proposer) = self.op_proposer else {
+            return self;
+        };
+        self.spare_proposers = (1..=count)
+            .map(|i| OpProposerBuilder {
+                container_name: format!("{}-spare-{}", proposer.container_name, i),
+                rpc_host_port: Some(0),
+                metrics_host_port: None,
+                stopped: true,
+                ..proposer.clone()
+            })
+            .collect();
+        self
+    }
+
+    /// Publish the metrics of every node on known host ports, e.g. for an external Prometheus.
+    ///
+    /// Uses [`DEFAULT_OP_RETH_METRICS_BASE_PORT`] and [`DEFAULT_CONSENSUS_METRICS_BASE_PORT`];
+    /// see [`Self::metrics_base_ports`]. Does nothing when `enabled` is false.
+    pub fn expose_metrics(self, enabled: bool) -> Self {
+        if enabled {
+            self.metrics_base_ports(
+                DEFAULT_OP_RETH_METRICS_BASE_PORT,
+                DEFAULT_CONSENSUS_METRICS_BASE_PORT,
+            )
+        } else {
+            self
+        }
+    }
+
+    /// Grant `NET_ADMIN` to the op-reth and kona-node of every node, so that traffic
+    /// shaping (`tc`) can run inside them. Does nothing when `enabled` is false.
+    pub fn net_admin(mut self, enabled: bool) -> Self {
+        if !enabled {
+            return self;
+        }
+        for node in self.sequencers.iter_mut().chain(self.validators.iter_mut()) {
+            for cap_add in [&mut node.op_reth.cap_add, &mut node.kona_node.cap_add] {
+                if !cap_add.iter().any(|cap| cap == "NET_ADMIN") {
+                    cap_add.push("NET_ADMIN".to_string());
+                }
+            }
+        }
+        self
+    }
+
+    /// Set the pruning mode of the op-reth of every node.
+    ///
+    /// Nodes with the historical proofs ExEx stay archive nodes.
+    pub fn op_reth_pruning(mut self, pruning: PruningMode) -> Self {
+        for node in self.sequencers.iter_mut().chain(self.validators.iter_mut()) {
+            if !node.op_reth.proofs_history {
+                node.op_reth.pruning = pruning;
+            }
+        }
+        self
+    }
+
+    /// Point op-batcher and the op-node of every node at the alt-DA server `url`.
+    ///
+    /// Call this after the consensus client is chosen and before adding spare batchers.
+    /// kona-node has no alt-DA support and is left unchanged.
+    pub fn alt_da_server(mut self, url: Option<String>) -> Self {
+        self.op_batcher.alt_da_server = url.clone();
+        for node in self.sequencers.iter_mut().chain(self.validators.iter_mut()) {
+            if let Some(ref mut op_node) = node.op_node {
+                op_node.alt_da_server = url.clone();
+            }
+        }
+        self
+    }
+
+    /// Run every instance of `service` at log `level`, overriding the default.
+    ///
+    /// `service` and `level` are those accepted by
+    /// [`parse_service_log_level`](crate::logs::parse_service_log_level). kona-node has no
+    /// log level flag, so the level is translated to its verbosity (`-vvv` for `INFO`).
+    /// Unknown services are ignored.
+    pub fn log_level(mut self, service: &str, level: &str) -> Self {
+        let level = level.to_string();
+        match service {
+            "op-batcher" => {
+                for batcher in std::iter::once(&mut self.op_batcher).chain(&mut self.spare_batchers)
+                {
+                    batcher.log_level = Some(level.clone());
+                }
+            }
+            "op-proposer" => {
+                for proposer in self.op_proposer.iter_mut().chain(&mut self.spare_proposers) {
+                    proposer.log_level = Some(level.clone());
+                }
+            }
+            "op-challenger" => {
+                if let Some(ref mut challenger) = self.op_challenger {
+                    challenger.log_level = Some(level);
+                }
+            }
+            "op-node" => {
+                let nodes = self.sequencers.iter_mut().chain(self.validators.iter_mut());
+                for op_node in nodes.filter_map(|node| node.op_node.as_mut()) {
+                    op_node.log_level = Some(level.clone());
+                }
+            }
+            "kona-node" => {
+                let verbosity = crate::logs::LOG_LEVELS
+                    .iter()
+                    .position(|l| *l == level)
+                    .map(|i| format!("-{}", "v".repeat(i + 1)));
+                for node in self.sequencers.iter_mut().chain(self.validators.iter_mut()) {
+                    node.kona_node.verbosity = verbosity.clone();
+                }
+            }
+            "op-conductor" => {
+                let nodes = self.sequencers.iter_mut().chain(self.validators.iter_mut());
+                for conductor in nodes.filter_map(|node| node.op_conductor.as_mut()) {
+                    conductor.log_level = Some(level.clone());
+                }
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Publish the metrics of every node on consecutive host ports.
+    ///
+    /// Node `i` (sequencers first, then validators) gets its op-reth metrics on
+    /// `op_reth_base + i` and its kona-node (or op-node) metrics on `consensus_base + i`.
+    /// Call this after all nodes are added; [`Self::unique_metrics_host_ports`] checks
+    /// that the resulting ports do not overlap.
+    pub fn metrics_base_ports(mut self, op_reth_base: u16, consensus_base: u16) -> Self {
+        let nodes = self.sequencers.iter_mut().chain(self.validators.iter_mut());
+        for (i, node) in (0u16..).zip(nodes) {
+            let consensus_port = consensus_base.saturating_add(i);
+            node.op_reth.metrics_host_port = Some(op_reth_base.saturating_add(i));
+            node.kona_node.metrics_host_port = Some(consensus_port);
+            if let Some(ref mut op_node) = node.op_node {
+                op_node.metrics_host_port = Some(consensus_port);
+            }
+        }
+        self
+    }
+
     /// Set the binary path or source directory for op-reth for all nodes (sequencers and validators).
     pub fn set_op_reth_binary(mut self, path: impl Into<PathBuf>) -> Self {
         let docker_image = crate::docker::DockerImage::from_binary_with_name(path, "op-reth");
@@ -255,6 +521,37 @@ impl<Node, B, P, C> L2StackBuilder<Node, B, P, C> {
     pub fn node_count(&self) -> usize {
         self.sequencers.len() + self.validators.len()
     }
+
+    /// Set a JSON patch to merge into the L2 `genesis.json` generated by op-deployer.
+    ///
+    /// Objects are merged recursively, other values are replaced. The patch is applied
+    /// before op-reth consumes the genesis, and the result is validated against `rollup.json`.
+    pub fn genesis_patch(mut self, patch: serde_json::Value) -> Self {
+        self.genesis_patch = Some(patch);
+        self
+    }
+
+    /// Fund `address` with `amount` in the L2 genesis, so that it holds ETH from block 0.
+    /// Existing allocations (predeploys, precompiles) keep their code and storage.
+    pub fn prefund(mut self, address: Address, amount: EthAmount) -> Self {
+        self.prefund.insert(address, U256::from(amount.wei()));
+        self
+    }
+
+    /// Activate the L2 hardfork `fork` (e.g. `"isthmus"`) `seconds` after the L2 genesis,
+    /// instead of at genesis. Later forks are pushed back so that they do not activate first.
+    pub fn hardfork_offset(mut self, fork: impl Into<String>, seconds: u64) -> Self {
+        self.hardfork_offsets.insert(fork.into(), seconds);
+        self
+    }
+
+    /// Whether `genesis.json` is changed after op-deployer generated it, in which case the
+    /// L2 genesis hash of `rollup.json` has to be updated from op-reth.
+    fn genesis_modified(&self) -> bool {
+        self.genesis_patch.is_some()
+            || !self.prefund.is_empty()
+            || !self.hardfork_offsets.is_empty()
+    }
 }
 
 // Concrete-type methods for P2P key persistence and enode computation.
@@ -268,12 +565,109 @@ impl L2StackBuilder {
             builder.op_reth.p2p_secret_key = Some(handler.op_reth.p2p_keypair.private_key.clone());
             builder.kona_node.p2p_secret_key =
                 Some(handler.kona_node.p2p_keypair.private_key.clone());
+            if let Some(ref mut op_node) = builder.op_node {
+                op_node.p2p_secret_key = Some(handler.kona_node.p2p_keypair.private_key.clone());
+            }
         }
         for (builder, handler) in self.validators.iter_mut().zip(handlers.validators.iter()) {
             builder.op_reth.p2p_secret_key = Some(handler.op_reth.p2p_keypair.private_key.clone());
             builder.kona_node.p2p_secret_key =
                 Some(handler.kona_node.p2p_keypair.private_key.clone());
+            if let Some(ref mut op_node) = builder.op_node {
+                op_node.p2p_secret_key = Some(handler.kona_node.p2p_keypair.private_key.clone());
+            }
+        }
+    }
+
+    /// Names of the op-reth datadirs of all nodes, in node order.
+    ///
+    /// Datadirs are named after the op-reth container and live under the shared L2
+    /// bind mount, so nodes with colliding container names would share (and corrupt)
+    /// a single datadir. Fails if any two nodes share one.
+    pub fn unique_datadirs(&self) -> anyhow::Result<Vec<String>> {
+        let sequencers = self.sequencers.iter().enumerate().map(|(i, node)| {
+            let label = match i {
+                0 => "sequencer".to_string(),
+                i => format!("sequencer-{}", i),
+            };
+            (label, node)
+        });
+        let validators = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (format!("validator-{}", i + 1), node));
+
+        let mut owners: HashMap<String, String> = HashMap::new();
+        let mut datadirs = Vec::with_capacity(self.node_count());
+        for (label, node) in sequencers.chain(validators) {
+            let datadir = node.op_reth.datadir_name();
+            if let Some(owner) = owners.insert(datadir.clone(), label.clone()) {
+                anyhow::bail!(
+                    "L2 nodes {} and {} share the op-reth datadir '{}' \
+                     (duplicate op-reth container name '{}')",
+                    owner,
+                    label,
+                    datadir,
+                    node.op_reth.container_name
+                );
+            }
+            datadirs.push(datadir);
         }
+
+        Ok(datadirs)
+    }
+
+    /// Check that no two nodes publish their metrics on the same fixed host port.
+    ///
+    /// Unpublished and OS-assigned (`0`) ports are ignored.
+    pub fn unique_metrics_host_ports(&self) -> anyhow::Result<()> {
+        let labels = (0..self.sequencers.len())
+            .map(|i| match i {
+                0 => "sequencer".to_string(),
+                i => format!("sequencer-{}", i),
+            })
+            .chain((1..=self.validators.len()).map(|i| format!("validator-{}", i)));
+
+        let mut owners: HashMap<u16, String> = HashMap::new();
+        for (label, node) in labels.zip(self.sequencers.iter().chain(self.validators.iter())) {
+            let consensus_port = match node.op_node {
+                Some(ref op_node) => op_node.metrics_host_port,
+                None => node.kona_node.metrics_host_port,
+            };
+            let ports = [
+                (node.op_reth.metrics_host_port, "op-reth"),
+                (consensus_port, node.consensus_client().service_name()),
+            ];
+            for (port, service) in ports {
+                let Some(port) = port.filter(|&p| p != 0) else {
+                    continue;
+                };
+                let owner = format!("{} {}", label, service);
+                if let Some(previous) = owners.insert(port, owner.clone()) {
+                    anyhow::bail!(
+                        "{} and {} both publish their metrics on host port {}",
+                        previous,
+                        owner,
+                        port
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every node has its own op-reth datadir and create the missing ones.
+    pub fn prepare_datadirs(&self, host_config_path: &Path) -> anyhow::Result<()> {
+        for datadir in self.unique_datadirs()? {
+            let path = host_config_path.join(datadir);
+            if !path.exists() {
+                fs::FsHandler::create_host_config_directory(&path)?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Compute all op-reth enodes from persisted P2P keys in the config.
@@ -292,7 +686,7 @@ impl L2StackBuilder {
             .collect()
     }
 
-    /// Compute all kona-node enodes from persisted P2P keys in the config.
+    /// Compute all consensus client (kona-node or op-node) enodes from persisted P2P keys.
     ///
     /// Returns enodes for all nodes (sequencers + validators) that have
     /// persisted P2P secret keys. Nodes without keys are skipped.
@@ -303,9 +697,9 @@ impl L2StackBuilder {
             .iter()
             .chain(self.validators.iter())
             .filter_map(|node| {
-                let key = node.kona_node.p2p_secret_key.as_ref()?;
+                let key = node.consensus_p2p_secret_key()?;
                 let keypair = crate::services::kona_node::P2pKeypair::from_private_key(key).ok()?;
-                Some(keypair.to_enode(&node.kona_node.container_name, DEFAULT_P2P_PORT))
+                Some(keypair.to_enode(node.consensus_container_name(), DEFAULT_P2P_PORT))
             })
             .collect()
     }
@@ -322,6 +716,9 @@ where
     ///
     /// This starts sequencer nodes first (with their op-conductors if configured),
     /// then validator nodes, then op-batcher, and optionally op-proposer and op-challenger.
+    ///
+    /// With `keep_going`, a failure of op-proposer or op-challenger is pushed to
+    /// `warnings` and the service is skipped instead of aborting.
     /// Each L2 node pair (op-reth + kona-node) generates its own JWT for authentication.
     /// P2P peer discovery is enabled by passing enodes between nodes.
     ///
@@ -331,6 +728,7 @@ where
     /// * `anvil_handler` - Handler for the L1 Anvil instance
     /// * `l1_chain_id` - L1 chain ID (used to determine if we need a custom L1 config for kona-node)
     /// * `metrics` - Deployment metrics to record per-service timings
+    #[allow(clippy::too_many_arguments)]
     pub async fn start(
         &self,
         docker: &mut KupDocker,
@@ -338,20 +736,52 @@ where
         anvil_handler: &AnvilHandler,
         l1_chain_id: u64,
         metrics: &mut DeploymentMetrics,
+        keep_going: bool,
+        warnings: &mut Vec<DeployWarning>,
     ) -> Result<L2StackHandler, anyhow::Error> {
         if !host_config_path.exists() {
             fs::FsHandler::create_host_config_directory(&host_config_path)?;
         }
 
+        if !self.hardfork_offsets.is_empty() {
+            l2_genesis::apply_hardfork_offsets(&host_config_path, &self.hardfork_offsets)
+                .context("Failed to set L2 hardfork activation times")?;
+        }
+        if let Some(ref patch) = self.genesis_patch {
+            l2_genesis::apply_genesis_patch(&host_config_path, patch)
+                .context("Failed to apply L2 genesis patch")?;
+        }
+        if !self.prefund.is_empty() {
+            l2_genesis::apply_prefund(&host_config_path, &self.prefund)
+                .context("Failed to prefund L2 genesis accounts")?;
+        }
+
         // Extract raw data from anvil_handler for decoupled inputs
         let l1_rpc_url = anvil_handler.l1_rpc_url.as_str();
         let l1_host_url = anvil_handler.l1_host_url.as_ref().map(|u| u.as_str());
+        let l1_ws_url = anvil_handler.l1_ws_url.as_ref().map(|u| u.to_string());
         let unsafe_block_signer_key =
             hex::encode(&anvil_handler.accounts.unsafe_block_signer.private_key);
         let batcher_private_key = anvil_handler.accounts.batcher.private_key.to_string();
         let proposer_private_key = anvil_handler.accounts.proposer.private_key.to_string();
         let challenger_private_key = anvil_handler.accounts.challenger.private_key.to_string();
 
+        // Spare batchers, then spare proposers, submit with the extra accounts in order.
+        let spare_count = self.spare_batchers.len() + self.spare_proposers.len();
+        let extra_accounts = &anvil_handler.accounts.extra_accounts;
+        if spare_count > extra_accounts.len() {
+            anyhow::bail!(
+                "{} spare batchers and {} spare proposers need {} funded accounts, but the L1 only has {} extra accounts",
+                self.spare_batchers.len(),
+                self.spare_proposers.len(),
+                spare_count,
+                extra_accounts.len()
+            );
+        }
+        let mut spare_keys = extra_accounts
+            .iter()
+            .map(|account| account.private_key.to_string());
+
         // Mutable lists of peer enodes for P2P discovery
         let mut kona_node_enodes: Vec<String> = Vec::new();
         let mut op_reth_enodes: Vec<String> = Vec::new();
@@ -388,6 +818,7 @@ where
                     L2NodeInput {
                         l1_rpc_url: l1_rpc_url.to_string(),
                         l1_host_url: l1_host_url.map(|s| s.to_string()),
+                        l1_ws_url: l1_ws_url.clone(),
                         unsafe_block_signer_key: unsafe_block_signer_key.clone(),
                         sequencer_rpc: None,
                         kona_node_enodes: kona_node_enodes.clone(),
@@ -396,6 +827,7 @@ where
                         conductor_context,
                         sequencer_flashblocks_relay_url: None,
                         op_reth_p2p_secret_key: None,
+                        patch_genesis_hash: i == 0 && self.genesis_modified(),
                     },
                 )
                 .await
@@ -434,6 +866,7 @@ where
                     L2NodeInput {
                         l1_rpc_url: l1_rpc_url.to_string(),
                         l1_host_url: l1_host_url.map(|s| s.to_string()),
+                        l1_ws_url: l1_ws_url.clone(),
                         unsafe_block_signer_key: unsafe_block_signer_key.clone(),
                         sequencer_rpc: Some(sequencer_rpc.clone()),
                         kona_node_enodes: kona_node_enodes.clone(),
@@ -442,6 +875,7 @@ where
                         conductor_context: ConductorContext::None,
                         sequencer_flashblocks_relay_url: sequencer_flashblocks_relay_url.clone(),
                         op_reth_p2p_secret_key: None,
+                        patch_genesis_hash: false,
                     },
                 )
                 .await
@@ -470,6 +904,12 @@ where
             "All L2 nodes started with P2P peer discovery"
         );
 
+        if needs_conductor && self.start_sequencing {
+            activate_leader_sequencer(&docker.rpc_client, &sequencer_handlers, warnings)
+                .await
+                .context("Failed to start sequencing on the op-conductor leader")?;
+        }
+
         // Get references to the primary sequencer for the remaining components
         let primary_sequencer = &sequencer_handlers[0];
 
@@ -484,6 +924,7 @@ where
                 &host_config_path,
                 OpBatcherInput {
                     l1_rpc_url: l1_rpc_url.to_string(),
+                    l1_ws_url: l1_ws_url.clone(),
                     l2_rpc_url: primary_sequencer.op_reth.http_rpc_url.to_string(),
                     rollup_rpc_url: primary_sequencer.kona_node.rpc_url.to_string(),
                     batcher_private_key: batcher_private_key.clone(),
@@ -502,10 +943,32 @@ where
             ),
         );
 
+        let mut spare_batcher_handlers = Vec::with_capacity(self.spare_batchers.len());
+        for (spare, private_key) in self.spare_batchers.iter().zip(spare_keys.by_ref()) {
+            tracing::info!(
+                container_name = spare.container_name(),
+                "Starting spare op-batcher..."
+            );
+            let handler = spare
+                .deploy(
+                    docker,
+                    &host_config_path,
+                    OpBatcherInput {
+                        l1_rpc_url: l1_rpc_url.to_string(),
+                        l1_ws_url: l1_ws_url.clone(),
+                        l2_rpc_url: primary_sequencer.op_reth.http_rpc_url.to_string(),
+                        rollup_rpc_url: primary_sequencer.kona_node.rpc_url.to_string(),
+                        batcher_private_key: private_key,
+                    },
+                )
+                .await?;
+            spare_batcher_handlers.push(handler);
+        }
+
         let op_proposer_handler = if let Some(ref proposer_config) = self.op_proposer {
             tracing::info!("Starting op-proposer...");
             let proposer_start = std::time::Instant::now();
-            let handler = proposer_config
+            let result = proposer_config
                 .deploy(
                     docker,
                     &host_config_path,
@@ -515,51 +978,82 @@ where
                         proposer_private_key: proposer_private_key.clone(),
                     },
                 )
-                .await?;
-            let proposer_total = proposer_start.elapsed();
-            let proposer_size = get_image_size(docker, &handler.container_id).await;
-            metrics.record(
-                handler.container_name.clone(),
-                ServiceMetrics::from_timings(
-                    proposer_total,
-                    &handler.deploy_timings,
-                    proposer_size,
-                    proposer_config.docker_image(),
-                ),
-            );
-            Some(handler)
+                .await;
+            let handler = best_effort("op-proposer", result, keep_going, warnings)?;
+            if let Some(ref handler) = handler {
+                let proposer_total = proposer_start.elapsed();
+                let proposer_size = get_image_size(docker, &handler.container_id).await;
+                metrics.record(
+                    handler.container_name.clone(),
+                    ServiceMetrics::from_timings(
+                        proposer_total,
+                        &handler.deploy_timings,
+                        proposer_size,
+                        proposer_config.docker_image(),
+                    ),
+                );
+            }
+            handler
         } else {
             tracing::info!("Skipping op-proposer (disabled)");
             None
         };
 
+        let mut spare_proposer_handlers = Vec::with_capacity(self.spare_proposers.len());
+        for (spare, private_key) in self.spare_proposers.iter().zip(spare_keys) {
+            tracing::info!(
+                container_name = spare.container_name(),
+                "Starting spare op-proposer..."
+            );
+            let result = spare
+                .deploy(
+                    docker,
+                    &host_config_path,
+                    OpProposerInput {
+                        l1_rpc_url: l1_rpc_url.to_string(),
+                        rollup_rpc_url: primary_sequencer.kona_node.rpc_url.to_string(),
+                        proposer_private_key: private_key,
+                    },
+                )
+                .await;
+            if let Some(handler) =
+                best_effort(spare.container_name(), result, keep_going, warnings)?
+            {
+                spare_proposer_handlers.push(handler);
+            }
+        }
+
         let op_challenger_handler = if let Some(ref challenger_config) = self.op_challenger {
             tracing::info!("Starting op-challenger...");
             let challenger_start = std::time::Instant::now();
-            let handler = challenger_config
+            let result = challenger_config
                 .deploy(
                     docker,
                     &host_config_path,
                     OpChallengerInput {
                         l1_rpc_url: l1_rpc_url.to_string(),
+                        l1_host_url: l1_host_url.map(|s| s.to_string()),
                         l2_rpc_url: primary_sequencer.op_reth.http_rpc_url.to_string(),
                         rollup_rpc_url: primary_sequencer.kona_node.rpc_url.to_string(),
                         challenger_private_key: challenger_private_key.clone(),
                     },
                 )
-                .await?;
-            let challenger_total = challenger_start.elapsed();
-            let challenger_size = get_image_size(docker, &handler.container_id).await;
-            metrics.record(
-                handler.container_name.clone(),
-                ServiceMetrics::from_timings(
-                    challenger_total,
-                    &handler.deploy_timings,
-                    challenger_size,
-                    challenger_config.docker_image(),
-                ),
-            );
-            Some(handler)
+                .await;
+            let handler = best_effort("op-challenger", result, keep_going, warnings)?;
+            if let Some(ref handler) = handler {
+                let challenger_total = challenger_start.elapsed();
+                let challenger_size = get_image_size(docker, &handler.container_id).await;
+                metrics.record(
+                    handler.container_name.clone(),
+                    ServiceMetrics::from_timings(
+                        challenger_total,
+                        &handler.deploy_timings,
+                        challenger_size,
+                        challenger_config.docker_image(),
+                    ),
+                );
+            }
+            handler
         } else {
             tracing::info!("Skipping op-challenger (disabled)");
             None
@@ -606,8 +1100,98 @@ where
             op_batcher: op_batcher_handler,
             op_proposer: op_proposer_handler,
             op_challenger: op_challenger_handler,
+            spare_batchers: spare_batcher_handlers,
+            spare_proposers: spare_proposer_handlers,
+        })
+    }
+}
+
+/// Time to wait for the op-conductor cluster to elect a leader, in seconds.
+const CONDUCTOR_LEADER_TIMEOUT_SECS: u64 = 120;
+
+/// Make sure the sequencer behind the elected op-conductor leader produces blocks.
+///
+/// Waits until one conductor reports itself leader, resumes it if it is paused, then
+/// starts its sequencer at the current unsafe head with `admin_startSequencer` unless
+/// it is already active. Uses the host-published RPCs of the conductors and consensus
+/// clients; skipped with a [`DeployWarning::SequencingNotStarted`] when they are not
+/// published.
+async fn activate_leader_sequencer(
+    client: &reqwest::Client,
+    sequencers: &[L2NodeHandler],
+    warnings: &mut Vec<DeployWarning>,
+) -> anyhow::Result<()> {
+    let targets: Vec<(&str, &str)> = sequencers
+        .iter()
+        .filter_map(|node| {
+            let conductor = node.op_conductor.as_ref()?.rpc_host_url.as_ref()?;
+            let consensus = node.kona_node.rpc_host_url.as_ref()?;
+            Some((conductor.as_str(), consensus.as_str()))
         })
+        .collect();
+    if targets.len() != sequencers.len() {
+        let warning = DeployWarning::SequencingNotStarted {
+            reason: "op-conductor or consensus RPC not published to host".to_string(),
+        };
+        tracing::warn!("{}", warning);
+        warnings.push(warning);
+        return Ok(());
+    }
+
+    let find_leader = || async {
+        for (i, (conductor_url, _)) in targets.iter().enumerate() {
+            let is_leader: bool =
+                rpc::json_rpc_call(client, conductor_url, "conductor_leader", vec![]).await?;
+            if is_leader {
+                return Ok(i);
+            }
+        }
+        anyhow::bail!("No op-conductor leader elected yet")
+    };
+
+    let election_start = std::time::Instant::now();
+    rpc::wait_until_ready(
+        "op-conductor leader",
+        CONDUCTOR_LEADER_TIMEOUT_SECS,
+        || async { find_leader().await.map(|_| ()) },
+    )
+    .await?;
+    check_readiness("op-conductor leader", election_start.elapsed(), warnings);
+    let leader = find_leader().await?;
+    let (conductor_url, consensus_url) = targets[leader];
+
+    let paused: bool =
+        rpc::json_rpc_call(client, conductor_url, "conductor_paused", vec![]).await?;
+    if paused {
+        let _: serde_json::Value =
+            rpc::json_rpc_call(client, conductor_url, "conductor_resume", vec![]).await?;
+    }
+
+    let active: bool =
+        rpc::json_rpc_call(client, consensus_url, "admin_sequencerActive", vec![]).await?;
+    if !active {
+        let status: serde_json::Value =
+            rpc::json_rpc_call(client, consensus_url, "optimism_syncStatus", vec![]).await?;
+        let unsafe_head = status["unsafe_l2"]["hash"]
+            .as_str()
+            .context("Sync status has no unsafe L2 head")?
+            .to_string();
+        let _: serde_json::Value = rpc::json_rpc_call(
+            client,
+            consensus_url,
+            "admin_startSequencer",
+            vec![serde_json::json!(unsafe_head)],
+        )
+        .await?;
     }
+
+    tracing::info!(
+        leader = %sequencers[leader].kona_node.container_name,
+        resumed = paused,
+        started = !active,
+        "Sequencing active on op-conductor leader"
+    );
+    Ok(())
 }
 
 /// Record per-child metrics for an L2 node handler (op-reth, kona-node, and optional op-conductor).
@@ -664,6 +1248,154 @@ async fn record_l2_node_metrics(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validators_have_distinct_datadirs() {
+        let stack = L2StackBuilder::with_counts(2, 3);
+
+        let datadirs = stack.unique_datadirs().unwrap();
+        assert_eq!(datadirs.len(), 5);
+        let unique: std::collections::HashSet<_> = datadirs.iter().collect();
+        assert_eq!(unique.len(), datadirs.len());
+
+        let dir = tempdir::TempDir::new("kupcake-datadir-test").unwrap();
+        stack.prepare_datadirs(dir.path()).unwrap();
+        for datadir in &datadirs {
+            assert!(dir.path().join(datadir).is_dir());
+        }
+    }
+
+    #[test]
+    fn test_colliding_container_names_rejected() {
+        let mut stack = L2StackBuilder::with_counts(1, 2);
+        stack.validators[1].op_reth.container_name =
+            stack.validators[0].op_reth.container_name.clone();
+
+        let err = stack.unique_datadirs().unwrap_err().to_string();
+        assert!(err.contains("validator-1 and validator-2"), "{err}");
+
+        let dir = tempdir::TempDir::new("kupcake-datadir-test").unwrap();
+        assert!(stack.prepare_datadirs(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_spare_batchers_and_proposers() {
+        let stack = L2StackBuilder::with_counts(1, 0)
+            .spare_batchers(2)
+            .spare_proposers(1);
+
+        let names: Vec<&str> = stack
+            .spare_batchers
+            .iter()
+            .map(|b| b.container_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            ["kupcake-op-batcher-spare-1", "kupcake-op-batcher-spare-2"]
+        );
+        assert!(stack.spare_batchers.iter().all(|b| b.stopped));
+        assert!(!stack.op_batcher.stopped);
+
+        assert_eq!(stack.spare_proposers.len(), 1);
+        assert_eq!(
+            stack.spare_proposers[0].container_name,
+            "kupcake-op-proposer-spare-1"
+        );
+        assert!(stack.spare_proposers[0].stopped);
+
+        let mut stack = L2StackBuilder::with_counts(1, 0);
+        stack.op_proposer = None;
+        assert!(stack.spare_proposers(1).spare_proposers.is_empty());
+    }
+
+    #[test]
+    fn test_metrics_base_ports() {
+        let stack = L2StackBuilder::with_counts(2, 1).metrics_base_ports(19001, 17300);
+
+        let nodes: Vec<_> = stack.sequencers.iter().chain(&stack.validators).collect();
+        for (i, node) in (0u16..).zip(&nodes) {
+            assert_eq!(node.op_reth.metrics_host_port, Some(19001 + i));
+            assert_eq!(node.kona_node.metrics_host_port, Some(17300 + i));
+        }
+        stack.unique_metrics_host_ports().unwrap();
+
+        // Overlapping ranges: validator-1's kona-node lands on the sequencer's op-reth port.
+        let stack = L2StackBuilder::with_counts(2, 1).metrics_base_ports(9000, 8998);
+        let err = stack.unique_metrics_host_ports().unwrap_err().to_string();
+        assert!(
+            err.contains("sequencer op-reth and validator-1 kona-node"),
+            "{err}"
+        );
+
+        // The default OS-assigned ports never collide.
+        L2StackBuilder::with_counts(2, 2)
+            .unique_metrics_host_ports()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_log_level() {
+        let stack = L2StackBuilder::with_counts(2, 1)
+            .spare_batchers(1)
+            .log_level("op-batcher", "DEBUG")
+            .log_level("kona-node", "INFO")
+            .log_level("op-conductor", "WARN");
+
+        assert_eq!(stack.op_batcher.log_level.as_deref(), Some("DEBUG"));
+        assert_eq!(stack.spare_batchers[0].log_level.as_deref(), Some("DEBUG"));
+        assert_eq!(stack.op_proposer.unwrap().log_level, None);
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            assert_eq!(node.kona_node.verbosity.as_deref(), Some("-vvv"));
+        }
+        for node in &stack.sequencers {
+            let conductor = node.op_conductor.as_ref().unwrap();
+            assert_eq!(conductor.log_level.as_deref(), Some("WARN"));
+        }
+    }
+
+    #[test]
+    fn test_net_admin() {
+        let stack = L2StackBuilder::with_counts(1, 1);
+        assert!(stack.sequencers[0].op_reth.cap_add.is_empty());
+
+        // Applying twice does not duplicate the capability.
+        let stack = stack.net_admin(true).net_admin(true);
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            assert_eq!(node.op_reth.cap_add, ["NET_ADMIN"]);
+            assert_eq!(node.kona_node.cap_add, ["NET_ADMIN"]);
+            assert!(!node.op_reth.privileged);
+        }
+    }
+
+    #[test]
+    fn test_alt_da_server() {
+        let url = Some("http://da-server:3100".to_string());
+        let stack = L2StackBuilder::with_counts(1, 1)
+            .consensus_client(
+                ConsensusClient::OpNode,
+                OpNodeBuilder::default().docker_image,
+            )
+            .alt_da_server(url.clone())
+            .spare_batchers(1);
+
+        assert_eq!(stack.op_batcher.alt_da_server, url);
+        assert_eq!(stack.spare_batchers[0].alt_da_server, url);
+        for node in stack.sequencers.iter().chain(&stack.validators) {
+            assert_eq!(node.op_node.as_ref().unwrap().alt_da_server, url);
+        }
+    }
+
+    #[test]
+    fn test_genesis_patch_updates_genesis_hash() {
+        let stack = L2StackBuilder::with_counts(1, 0);
+        assert!(!stack.genesis_modified());
+
+        // A patch of the allocations changes the genesis hash recorded in rollup.json.
+        let stack = stack.genesis_patch(serde_json::json!({
+            "alloc": { "00000000000000000000000000000000000000aa": { "balance": "0xff" } }
+        }));
+        assert!(stack.genesis_modified());
+    }
+
     #[test]
     fn test_single_sequencer_no_conductor() {
         let stack = L2StackBuilder::with_counts(1, 0);
@@ -674,6 +1406,17 @@ mod tests {
         assert!(stack.sequencers[0].op_conductor.is_none());
     }
 
+    #[test]
+    fn test_start_sequencing_defaults_to_true() {
+        let stack = L2StackBuilder::with_counts(2, 0);
+        assert!(stack.start_sequencing);
+
+        let mut value = toml::Value::try_from(&stack).unwrap();
+        value.as_table_mut().unwrap().remove("start_sequencing");
+        let restored: L2StackBuilder = value.try_into().unwrap();
+        assert!(restored.start_sequencing);
+    }
+
     #[test]
     fn test_multi_sequencer_has_conductors() {
         let stack = L2StackBuilder::with_counts(2, 0);
@@ -838,4 +1581,26 @@ mod tests {
                 .contains("validator-3")
         );
     }
+
+    #[test]
+    fn test_consensus_client_op_node() {
+        let image = crate::docker::DockerImage::new("op-node", "latest");
+        let stack = L2StackBuilder::with_counts(2, 1)
+            .consensus_client(ConsensusClient::OpNode, image.clone());
+
+        for node in stack.sequencers.iter().chain(stack.validators.iter()) {
+            assert_eq!(node.consensus_client(), ConsensusClient::OpNode);
+            assert!(node.consensus_container_name().contains("op-node"));
+            assert_eq!(node.op_node.as_ref().unwrap().docker_image, image);
+        }
+
+        // Switching back to kona drops the op-node configs
+        let stack = stack.consensus_client(ConsensusClient::Kona, image);
+        assert!(stack.validators[0].op_node.is_none());
+        assert!(
+            stack.validators[0]
+                .consensus_container_name()
+                .contains("kona-node")
+        );
+    }
 }
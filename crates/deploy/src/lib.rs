@@ -6,70 +6,133 @@
 use alloy_core::primitives::Bytes;
 use serde::{Deserialize, Serialize};
 
+pub mod alt_da;
+pub use alt_da::AltDaConfig;
 pub mod bench;
 
 mod accounts;
-pub use accounts::{ANVIL_DEFAULT_MNEMONIC, derive_accounts_from_mnemonic};
+pub use accounts::{
+    ANVIL_DEFAULT_MNEMONIC, DEFAULT_DERIVATION_PATH, derive_accounts_from_mnemonic,
+    derive_accounts_from_mnemonic_with_path,
+};
 
 mod builder;
-pub use builder::{DeployerBuilder, OutDataPath};
+pub use builder::{
+    ConfigError, DeployerBuilder, L2ChainConfig, MAX_L2_GAS_LIMIT, MIN_L2_GAS_LIMIT, NameCollision,
+    OutDataPath, Topology,
+};
 
 mod config_resolve;
+mod contract_upgrade;
 
 pub mod metrics;
 pub mod service;
-pub use service::KupcakeService;
+pub use service::{KupcakeService, ScrapeTarget};
 
 mod deployer;
-pub use deployer::Deployer;
+pub use deployer::{Deployer, MAX_CONCURRENT_IMAGE_PULLS};
 
 mod deployment_hash;
-pub use deployment_hash::{DeploymentConfigHash, DeploymentVersion};
+pub use deployment_hash::{
+    DEPLOYMENT_VERSION_FILENAME, DeploymentConfigHash, DeploymentVersion, ServiceHashes,
+    service_hashes,
+};
+pub mod deterministic;
+
+mod external_l1;
+pub use external_l1::{ExternalL1Config, PrivateKeySource};
 
 mod l1_genesis;
+mod l2_genesis;
+pub use l2_genesis::{L2_HARDFORKS, parse_hardfork_offset, parse_prefund};
 
 mod docker;
+pub mod doctor;
 pub mod faucet;
+mod host_ports;
+mod ports;
+mod post_deploy;
+pub use post_deploy::PostDeployScript;
+
+mod warnings;
+pub use warnings::DeployWarning;
+
+mod chain_ids;
+pub use chain_ids::{ChainIdCheck, ChainIdReport, ChainKind};
 
 pub mod registry;
 pub use registry::{DevnetEntry, DevnetRegistry, DevnetState};
 mod fs;
+pub use fs::{DEFAULT_MIN_DISK_SPACE_GIB, GIB, RECOMMENDED_DISK_SPACE_GIB};
+pub mod head_tracker;
 pub mod health;
 pub mod inspect;
+mod json_file;
+pub mod logs;
+pub mod network_fault;
 pub mod node_lifecycle;
 pub mod rpc;
+pub mod service_rpc;
+pub mod service_update;
 pub mod services;
 pub mod spam;
 pub mod status;
+pub mod system_config;
+pub mod verification;
+pub mod withdraw;
+pub use rpc::RpcClientConfig;
 pub use spam::SpamPreset;
 
 pub use docker::{
-    AnvilStateDumpConfig, CleanupResult, ContainerState, CreateAndStartContainerOptions,
-    DockerImage, ExposedPort, KupDocker, KupDockerConfig, PortMapping, PortProtocol, ServiceConfig,
-    ServiceHandler, cleanup_by_prefix,
+    AnvilStateDumpConfig, CleanupResult, ContainerState, ContainerUrlError,
+    CreateAndStartContainerOptions, DockerImage, ExposedPort, HOST_GATEWAY_ENTRY, KupDocker,
+    KupDockerConfig, PortMapping, PortProtocol, ProxyConfig, RestartPolicy, ServiceConfig,
+    ServiceHandler, StopResult, cleanup_by_prefix, default_run_as_host_user, list_by_prefix,
+    parse_cidr, stop_by_prefix,
 };
 pub use services::{
     // Docker image defaults
     ANVIL_DEFAULT_IMAGE,
     ANVIL_DEFAULT_TAG,
+    ANVIL_KNOWN_HARDFORKS,
     AnvilAccounts,
     AnvilConfig,
     AnvilHandler,
     AnvilInitMode,
+    BLOCKSCOUT_DEFAULT_IMAGE,
+    BLOCKSCOUT_DEFAULT_TAG,
+    BlockscoutBuilder,
+    BlockscoutHandler,
+    BlockscoutInput,
     // L2 Node types
     ConductorContext,
+    ConsensusClient,
+    DataAvailabilityType,
+    FaultProofVm,
     GRAFANA_DEFAULT_IMAGE,
     GRAFANA_DEFAULT_TAG,
     GrafanaConfig,
+    GrafanaHandler,
+    ImportedDashboard,
     KONA_NODE_DEFAULT_IMAGE,
     KONA_NODE_DEFAULT_TAG,
     KonaNodeBuilder,
     KonaNodeHandler,
     L2NodeBuilder,
+    L2NodeContext,
     L2NodeHandler,
     L2NodeRole,
+    L2NodeSidecar,
+    LOKI_DEFAULT_IMAGE,
+    LOKI_DEFAULT_TAG,
+    LogsConfig,
+    LogsHandler,
+    LokiConfig,
+    LokiHandler,
+    MINIMUM_PRUNING_DISTANCE,
     MetricsTarget,
     MonitoringConfig,
+    MonitoringHandler,
     OP_BATCHER_DEFAULT_IMAGE,
     OP_BATCHER_DEFAULT_TAG,
     OP_CHALLENGER_DEFAULT_IMAGE,
@@ -78,12 +141,16 @@ pub use services::{
     OP_CONDUCTOR_DEFAULT_TAG,
     OP_DEPLOYER_DEFAULT_IMAGE,
     OP_DEPLOYER_DEFAULT_TAG,
+    OP_NODE_DEFAULT_IMAGE,
+    OP_NODE_DEFAULT_TAG,
     OP_PROPOSER_DEFAULT_IMAGE,
     OP_PROPOSER_DEFAULT_TAG,
     OP_RBUILDER_DEFAULT_IMAGE,
     OP_RBUILDER_DEFAULT_TAG,
     OP_RETH_DEFAULT_IMAGE,
     OP_RETH_DEFAULT_TAG,
+    OP_SUPERVISOR_DEFAULT_IMAGE,
+    OP_SUPERVISOR_DEFAULT_TAG,
     OpBatcherBuilder,
     OpBatcherHandler,
     OpChallengerBuilder,
@@ -91,19 +158,33 @@ pub use services::{
     OpConductorBuilder,
     OpConductorHandler,
     OpDeployerConfig,
+    OpNodeBuilder,
     OpProposerBuilder,
     OpProposerHandler,
     OpRethBuilder,
     OpRethHandler,
+    OpSupervisorBuilder,
+    OpSupervisorHandler,
+    OpSupervisorInput,
     PROMETHEUS_DEFAULT_IMAGE,
     PROMETHEUS_DEFAULT_TAG,
+    PROMTAIL_DEFAULT_IMAGE,
+    PROMTAIL_DEFAULT_TAG,
     PrometheusConfig,
+    PromtailConfig,
+    PromtailHandler,
+    PruningMode,
+    SupervisedChain,
+    TargetStatus,
     anvil_accounts_from_infos,
 };
 
 mod l2_stack;
-pub use deployer::{DeploymentEndpoints, DeploymentResult, L2StackHandler};
-pub use l2_stack::L2StackBuilder;
+pub use deployer::{DeploymentEndpoints, DeploymentResult, L2ChainHandler, L2StackHandler};
+pub use l2_stack::{
+    DEFAULT_CONSENSUS_METRICS_BASE_PORT, DEFAULT_OP_RETH_METRICS_BASE_PORT, L2ChainBuilder,
+    L2StackBuilder,
+};
 
 /// Account information from Anvil.
 #[derive(Debug, Clone)]
@@ -0,0 +1,293 @@
+//! Reading the container logs of a deployed network's services.
+//!
+//! Resolves a service name (and node identifier for per-node services) to its
+//! container, fetches the logs through the Docker API with `since`/`tail`, and
+//! optionally keeps only the lines containing a substring.
+
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use bollard::{Docker, container::LogsOptions};
+use futures::StreamExt;
+
+use crate::{
+    Deployer,
+    service_rpc::{RpcService, rpc_target},
+};
+
+/// Options for [`Deployer::logs`].
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// Only show logs newer than this (relative to now).
+    pub since: Option<Duration>,
+    /// Only show the last N lines (before filtering). All lines when None.
+    pub tail: Option<u64>,
+    /// Keep streaming new lines until the container stops.
+    pub follow: bool,
+    /// Only show lines containing this substring.
+    pub grep: Option<String>,
+}
+
+/// Services whose log level can be overridden (see [`parse_service_log_level`]).
+pub const LOG_LEVEL_SERVICES: &[&str] = &[
+    "op-node",
+    "kona-node",
+    "op-conductor",
+    "op-batcher",
+    "op-proposer",
+    "op-challenger",
+];
+
+/// Log levels accepted by [`parse_service_log_level`], from the least to the most verbose.
+pub const LOG_LEVELS: &[&str] = &["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+
+/// Parse a `SERVICE=LEVEL` log level override, e.g. `op-batcher=debug`.
+///
+/// Returns the service name and the upper-case level.
+pub fn parse_service_log_level(entry: &str) -> Result<(String, String)> {
+    let (service, level) = entry.split_once('=').with_context(|| {
+        format!(
+            "Invalid log level override '{}': expected SERVICE=LEVEL",
+            entry
+        )
+    })?;
+    let service = service.trim().to_lowercase();
+    if !LOG_LEVEL_SERVICES.contains(&service.as_str()) {
+        anyhow::bail!(
+            "Cannot set the log level of '{}': expected one of {}",
+            service,
+            LOG_LEVEL_SERVICES.join(", ")
+        );
+    }
+    let level = level.trim().to_uppercase();
+    if !LOG_LEVELS.contains(&level.as_str()) {
+        anyhow::bail!(
+            "Unknown log level '{}' for {}: expected one of {}",
+            level,
+            service,
+            LOG_LEVELS.join(", ")
+        );
+    }
+    Ok((service, level))
+}
+
+/// Parse a relative time such as `30s`, `10m`, `2h` or `1d`. A bare number is seconds.
+pub fn parse_since(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let (number, unit_secs) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 60 * 60),
+        Some((i, 'd')) => (&value[..i], 24 * 60 * 60),
+        _ => (value, 1),
+    };
+
+    let number: u64 = number.parse().with_context(|| {
+        format!(
+            "Invalid duration '{}': expected e.g. 30s, 10m, 2h or 1d",
+            value
+        )
+    })?;
+    let secs = number
+        .checked_mul(unit_secs)
+        .with_context(|| format!("Duration '{}' is too large", value))?;
+    Ok(Duration::from_secs(secs))
+}
+
+/// Split a node identifier suffix off a service name, e.g. `op-reth-validator-1` into
+/// `op-reth` and `validator-1`. Returns None when the name has no such suffix.
+fn split_node_suffix(service: &str) -> Option<(&str, &str)> {
+    ["-sequencer", "-validator-"].iter().find_map(|marker| {
+        let at = service.find(marker)?;
+        Some((&service[..at], &service[at + 1..]))
+    })
+}
+
+/// Resolve the container name of a service.
+///
+/// Accepts the service names of `kupcake rpc` plus `op-challenger`, `prometheus`,
+/// `grafana`, `loki`, `promtail` and `blockscout`, or a full container name. `node` is a node identifier and is only used
+/// for per-node services (op-reth, kona-node, conductor). A node suffix on the service
+/// name, as in `op-reth-validator-1` or `kona-node-sequencer-1`, takes precedence over
+/// `node`.
+pub fn service_container(deployer: &Deployer, service: &str, node: &str) -> Result<String> {
+    let stack = &deployer.l2_stack;
+    let monitoring = &deployer.monitoring;
+    let network_prefix = deployer
+        .docker
+        .net_name
+        .strip_suffix("-network")
+        .unwrap_or(&deployer.docker.net_name);
+
+    match service.trim() {
+        "op-challenger" | "challenger" => stack
+            .op_challenger
+            .as_ref()
+            .map(|c| c.container_name.clone())
+            .context("op-challenger is disabled in this deployment"),
+        "prometheus" => Ok(monitoring.prometheus.container_name.clone()),
+        "grafana" => Ok(monitoring.grafana.container_name.clone()),
+        "loki" => Ok(monitoring.logs.loki.container_name.clone()),
+        "promtail" => Ok(monitoring.logs.promtail.container_name.clone()),
+        "blockscout" => Ok(deployer.blockscout.container_name.clone()),
+        name if name.starts_with(&format!("{}-", network_prefix)) => Ok(name.to_string()),
+        name => {
+            let (name, node) = split_node_suffix(name).unwrap_or((name, node));
+            let service: RpcService = name.parse()?;
+            rpc_target(deployer, service, node).map(|(container_name, _)| container_name)
+        }
+    }
+}
+
+impl Deployer {
+    /// Write the logs of a service container to `out`, one line at a time.
+    ///
+    /// See [`service_container`] for the accepted service names. With `follow`, this
+    /// returns once the container stops.
+    pub async fn logs(
+        &self,
+        service: &str,
+        node: &str,
+        options: &LogOptions,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let container_name = service_container(self, service, node)?;
+        let docker =
+            Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
+
+        let since = match options.since {
+            Some(since) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+                now.saturating_sub(since).as_secs() as i64
+            }
+            None => 0,
+        };
+        let logs_options = LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            follow: options.follow,
+            since,
+            tail: options
+                .tail
+                .map_or_else(|| "all".to_string(), |n| n.to_string()),
+            ..Default::default()
+        };
+
+        tracing::debug!(%container_name, ?options, "Reading container logs");
+
+        let mut stream = docker.logs(&container_name, Some(logs_options));
+        let mut pending = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.with_context(|| format!("Failed to read logs of {}", container_name))?;
+            for line in take_lines(&mut pending, &chunk.to_string()) {
+                write_line(out, &line, options.grep.as_deref())?;
+            }
+        }
+        if !pending.is_empty() {
+            write_line(out, &pending, options.grep.as_deref())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Append a chunk to the pending buffer and return the complete lines it holds.
+///
+/// Docker log frames are not line-aligned, so a trailing partial line is kept in
+/// `pending` until the next chunk.
+fn take_lines(pending: &mut String, chunk: &str) -> Vec<String> {
+    pending.push_str(chunk);
+    let Some(end) = pending.rfind('\n') else {
+        return Vec::new();
+    };
+
+    let rest = pending.split_off(end + 1);
+    let complete = std::mem::replace(pending, rest);
+    complete.lines().map(String::from).collect()
+}
+
+fn write_line(out: &mut impl Write, line: &str, grep: Option<&str>) -> Result<()> {
+    if grep.is_none_or(|pattern| line.contains(pattern)) {
+        writeln!(out, "{}", line).context("Failed to write log line")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_since("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_since("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_since("1d").unwrap(), Duration::from_secs(86400));
+        assert_eq!(parse_since("45").unwrap(), Duration::from_secs(45));
+        assert!(parse_since("10w").is_err());
+        assert!(parse_since("m").is_err());
+
+        let err = parse_since(&format!("{}d", u64::MAX / 2)).unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_service_log_level() {
+        assert_eq!(
+            parse_service_log_level("op-batcher=debug").unwrap(),
+            ("op-batcher".to_string(), "DEBUG".to_string())
+        );
+        assert_eq!(
+            parse_service_log_level(" Kona-Node = Trace ").unwrap(),
+            ("kona-node".to_string(), "TRACE".to_string())
+        );
+        assert!(parse_service_log_level("op-batcher").is_err());
+        assert!(parse_service_log_level("op-reth=debug").is_err());
+        assert!(parse_service_log_level("op-batcher=verbose").is_err());
+    }
+
+    #[test]
+    fn test_split_node_suffix() {
+        assert_eq!(
+            split_node_suffix("op-reth-validator-1"),
+            Some(("op-reth", "validator-1"))
+        );
+        assert_eq!(
+            split_node_suffix("kona-node-sequencer"),
+            Some(("kona-node", "sequencer"))
+        );
+        assert_eq!(
+            split_node_suffix("conductor-sequencer-2"),
+            Some(("conductor", "sequencer-2"))
+        );
+        assert_eq!(split_node_suffix("op-batcher"), None);
+    }
+
+    #[test]
+    fn test_take_lines_keeps_partial_line() {
+        let mut pending = String::new();
+        assert!(take_lines(&mut pending, "chann").is_empty());
+        assert_eq!(
+            take_lines(&mut pending, "el opened\nblock 1\nbl"),
+            vec!["channel opened", "block 1"]
+        );
+        assert_eq!(pending, "bl");
+        assert_eq!(take_lines(&mut pending, "ock 2\n"), vec!["block 2"]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_write_line_grep() {
+        let mut out = Vec::new();
+        write_line(&mut out, "channel closed", Some("channel")).unwrap();
+        write_line(&mut out, "block built", Some("channel")).unwrap();
+        write_line(&mut out, "block built", None).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "channel closed\nblock built\n"
+        );
+    }
+}
@@ -82,6 +82,8 @@ pub struct ContainerDeployTimings {
 pub struct DeploymentMetrics {
     /// Per-service metrics keyed by container name.
     pub services: BTreeMap<String, ServiceMetrics>,
+    /// Time spent pulling the images of all services before they start.
+    pub image_prefetch: Duration,
     /// Total deployment wall-clock time.
     pub total: Duration,
 }
@@ -102,6 +104,7 @@ struct ServiceMetricsToml {
 #[derive(Serialize)]
 struct DeploymentMetricsToml {
     total_ms: u64,
+    image_prefetch_ms: u64,
     services: BTreeMap<String, ServiceMetricsToml>,
 }
 
@@ -139,6 +142,12 @@ impl DeploymentMetrics {
             .ok();
         }
 
+        writeln!(
+            out,
+            "  Image prefetch: {}",
+            format_duration(self.image_prefetch)
+        )
+        .ok();
         writeln!(out, "  Total: {}", format_duration(self.total)).ok();
         out
     }
@@ -154,6 +163,7 @@ impl DeploymentMetrics {
 
         let toml_data = DeploymentMetricsToml {
             total_ms: self.total.as_millis() as u64,
+            image_prefetch_ms: self.image_prefetch.as_millis() as u64,
             services: self
                 .services
                 .iter()
@@ -0,0 +1,177 @@
+//! Network fault injection (latency, packet loss, bandwidth) with `tc netem`.
+//!
+//! The service images do not ship `tc` and run without `NET_ADMIN`, so the qdisc is set
+//! from a short-lived helper container that shares the network namespace of the target
+//! and is granted `NET_ADMIN`. The fault applies to all traffic leaving the target's
+//! interface and lasts until [`Deployer::clear_network_fault`] or the container restarts.
+
+use anyhow::{Context, Result};
+
+use crate::{
+    Deployer, KupDocker,
+    docker::{DockerImage, ServiceConfig},
+    logs::service_container,
+};
+
+/// Default Docker image providing `tc` for fault injection.
+pub const DEFAULT_NETEM_DOCKER_IMAGE: &str = "nicolaka/netshoot";
+/// Default Docker tag for the fault injection image.
+pub const DEFAULT_NETEM_DOCKER_TAG: &str = "latest";
+
+/// Interface of a container on the deployment network.
+const CONTAINER_INTERFACE: &str = "eth0";
+
+/// Impairments applied to the outgoing traffic of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NetemSpec {
+    /// Added latency in milliseconds.
+    pub delay_ms: u64,
+    /// Percentage of dropped packets, between 0 and 100.
+    pub loss_pct: f64,
+    /// Bandwidth limit in kbit/s. None leaves the bandwidth unlimited.
+    pub rate_kbit: Option<u64>,
+}
+
+impl NetemSpec {
+    /// Arguments of the `netem` qdisc for this spec.
+    ///
+    /// Fails if the loss is out of range or the spec impairs nothing.
+    fn netem_args(&self) -> Result<Vec<String>> {
+        if !(0.0..=100.0).contains(&self.loss_pct) {
+            anyhow::bail!(
+                "Packet loss must be between 0 and 100%, got {}",
+                self.loss_pct
+            );
+        }
+
+        let mut args = Vec::new();
+        if self.delay_ms > 0 {
+            args.extend(["delay".to_string(), format!("{}ms", self.delay_ms)]);
+        }
+        if self.loss_pct > 0.0 {
+            args.extend(["loss".to_string(), format!("{}%", self.loss_pct)]);
+        }
+        if let Some(rate) = self.rate_kbit {
+            if rate == 0 {
+                anyhow::bail!("Bandwidth limit must be greater than 0 kbit/s");
+            }
+            args.extend(["rate".to_string(), format!("{}kbit", rate)]);
+        }
+        if args.is_empty() {
+            anyhow::bail!("Network fault has no delay, loss or bandwidth limit");
+        }
+        Ok(args)
+    }
+}
+
+/// Run a shell script calling `tc` in the network namespace of `container_name`.
+async fn run_tc(docker: &mut KupDocker, container_name: &str, script: String) -> Result<()> {
+    let config = ServiceConfig::new(DockerImage::new(
+        DEFAULT_NETEM_DOCKER_IMAGE,
+        DEFAULT_NETEM_DOCKER_TAG,
+    ))
+    .entrypoint(vec!["sh".to_string(), "-c".to_string()])
+    .cmd(vec![script])
+    .cap_add(["NET_ADMIN"])
+    .network_of(container_name);
+
+    docker
+        .run_command(config)
+        .await
+        .with_context(|| format!("Failed to run tc in the network of {}", container_name))?;
+    Ok(())
+}
+
+impl Deployer {
+    /// Add latency, packet loss and/or a bandwidth limit to the outgoing traffic of a
+    /// service container, replacing any previous fault.
+    ///
+    /// `service` and `node` are resolved as in [`service_container`]. The container must
+    /// be running.
+    pub async fn inject_network_fault(
+        &self,
+        docker: &mut KupDocker,
+        service: &str,
+        node: &str,
+        spec: NetemSpec,
+    ) -> Result<()> {
+        let container_name = service_container(self, service, node)?;
+        let args = spec.netem_args()?;
+
+        run_tc(
+            docker,
+            &container_name,
+            format!(
+                "tc qdisc replace dev {} root netem {}",
+                CONTAINER_INTERFACE,
+                args.join(" ")
+            ),
+        )
+        .await?;
+
+        tracing::info!(
+            container = %container_name,
+            delay_ms = spec.delay_ms,
+            loss_pct = spec.loss_pct,
+            rate_kbit = ?spec.rate_kbit,
+            "Injected network fault"
+        );
+        Ok(())
+    }
+
+    /// Remove the fault injected by [`Self::inject_network_fault`]. Succeeds if there is
+    /// none.
+    pub async fn clear_network_fault(
+        &self,
+        docker: &mut KupDocker,
+        service: &str,
+        node: &str,
+    ) -> Result<()> {
+        let container_name = service_container(self, service, node)?;
+
+        // Deleting the root qdisc fails when only the default one is installed.
+        run_tc(
+            docker,
+            &container_name,
+            format!(
+                "tc qdisc del dev {} root 2>/dev/null || true",
+                CONTAINER_INTERFACE
+            ),
+        )
+        .await?;
+
+        tracing::info!(container = %container_name, "Cleared network fault");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_netem_args() {
+        let spec = NetemSpec {
+            delay_ms: 100,
+            loss_pct: 2.5,
+            rate_kbit: Some(1000),
+        };
+        assert_eq!(
+            spec.netem_args().unwrap(),
+            ["delay", "100ms", "loss", "2.5%", "rate", "1000kbit"]
+        );
+
+        let delay_only = NetemSpec {
+            delay_ms: 50,
+            ..Default::default()
+        };
+        assert_eq!(delay_only.netem_args().unwrap(), ["delay", "50ms"]);
+
+        assert!(NetemSpec::default().netem_args().is_err());
+        let too_lossy = NetemSpec {
+            loss_pct: 150.0,
+            ..Default::default()
+        };
+        assert!(too_lossy.netem_args().is_err());
+    }
+}
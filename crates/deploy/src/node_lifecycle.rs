@@ -6,7 +6,10 @@ use anyhow::{Context, Result};
 use crate::{
     Deployer, KupDocker,
     service::KupcakeService,
-    services::l2_node::{ConductorContext, L2NodeBuilder, L2NodeHandler, L2NodeInput},
+    services::{
+        l2_node::{ConductorContext, L2NodeBuilder, L2NodeHandler, L2NodeInput},
+        op_node::OpNodeBuilder,
+    },
 };
 
 /// Location of a node within the deployer config.
@@ -95,7 +98,7 @@ pub fn node_container_names(deployer: &Deployer, loc: &NodeLocation) -> Vec<Stri
     let node = get_node_builder(deployer, loc);
     let mut names = vec![
         node.op_reth.container_name.clone(),
-        node.kona_node.container_name.clone(),
+        node.consensus_container_name().to_string(),
     ];
     if let Some(ref conductor) = node.op_conductor {
         names.push(conductor.container_name.clone());
@@ -136,6 +139,23 @@ pub async fn add_validator(
     new_validator.op_reth.docker_image = primary.op_reth.docker_image.clone();
     new_validator.kona_node.docker_image = primary.kona_node.docker_image.clone();
     new_validator.kona_node.l1_slot_duration = primary.kona_node.l1_slot_duration;
+    new_validator.op_node = primary.op_node.as_ref().map(|op_node| OpNodeBuilder {
+        docker_image: op_node.docker_image.clone(),
+        container_name: format!("{}-op-node-validator-{}", network_prefix, validator_index),
+        alt_da_server: op_node.alt_da_server.clone(),
+        ..Default::default()
+    });
+
+    // Indices are derived from the validator count, so removing a validator other than
+    // the last one makes the next index collide with an existing node.
+    let new_datadir = new_validator.op_reth.datadir_name();
+    if deployer.l2_stack.unique_datadirs()?.contains(&new_datadir) {
+        anyhow::bail!(
+            "Cannot add validator-{}: op-reth datadir '{}' is already used by an existing node",
+            validator_index,
+            new_datadir
+        );
+    }
 
     // Compute enodes from all existing nodes' persisted P2P keys
     let op_reth_enodes = deployer.l2_stack.compute_op_reth_enodes();
@@ -156,7 +176,7 @@ pub async fn add_validator(
     );
 
     // Derive accounts from mnemonic for the block signer key
-    let accounts = Deployer::derive_accounts()?;
+    let accounts = deployer.derive_accounts()?;
     let unsafe_block_signer_key = hex::encode(&accounts.unsafe_block_signer.private_key);
 
     // Get the primary sequencer's internal RPC URL for the validator to follow
@@ -175,6 +195,7 @@ pub async fn add_validator(
     let input = L2NodeInput {
         l1_rpc_url,
         l1_host_url: None, // New validators don't need host URL for L1 config (already generated)
+        l1_ws_url: None,
         unsafe_block_signer_key,
         sequencer_rpc: Some(sequencer_rpc_url),
         kona_node_enodes,
@@ -183,8 +204,17 @@ pub async fn add_validator(
         conductor_context: ConductorContext::None,
         sequencer_flashblocks_relay_url: None,
         op_reth_p2p_secret_key: None,
+        patch_genesis_hash: false,
     };
 
+    if deployer.docker.proxy.is_some() {
+        docker.internal_hosts = deployer.container_names()?;
+        docker.internal_hosts.extend([
+            new_validator.op_reth.container_name.clone(),
+            new_validator.consensus_container_name().to_string(),
+        ]);
+    }
+
     let handler = new_validator
         .deploy(docker, &l2_nodes_data_path, input)
         .await
@@ -201,6 +231,9 @@ pub async fn add_validator(
     let mut builder = new_validator;
     builder.op_reth.p2p_secret_key = Some(handler.op_reth.p2p_keypair.private_key.clone());
     builder.kona_node.p2p_secret_key = Some(handler.kona_node.p2p_keypair.private_key.clone());
+    if let Some(ref mut op_node) = builder.op_node {
+        op_node.p2p_secret_key = Some(handler.kona_node.p2p_keypair.private_key.clone());
+    }
 
     deployer.l2_stack.validators.push(builder);
     deployer
@@ -254,6 +287,7 @@ pub async fn remove_node(
     let container_names = node_container_names(deployer, &loc);
     let node = get_node_builder(deployer, &loc);
     let op_reth_name = node.op_reth.container_name.clone();
+    let reth_datadir = node.op_reth.datadir_name();
 
     tracing::info!(
         node = %loc,
@@ -281,7 +315,7 @@ pub async fn remove_node(
     // Clean up data directories if requested
     if cleanup_data {
         let l2_data = deployer.outdata.join("l2-stack");
-        let reth_data = l2_data.join(format!("reth-data-{}", op_reth_name));
+        let reth_data = l2_data.join(&reth_datadir);
         if reth_data.exists() {
             std::fs::remove_dir_all(&reth_data)
                 .with_context(|| format!("Failed to remove reth data: {}", reth_data.display()))?;
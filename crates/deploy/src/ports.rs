@@ -0,0 +1,160 @@
+//! Host port reservation for fixed port mappings.
+//!
+//! Docker picks a free port for mappings with host port 0, but a fixed host port is only
+//! checked when the container starts. Two deployments started in parallel can both see
+//! the port as free, and one of them fails halfway through. Before creating a container
+//! with fixed host ports, kupcake takes a machine-wide lock and binds each port to check
+//! it is free. The lock is held until the container has started and Docker owns the ports.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::net::{TcpListener, UdpSocket};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use backon::{ConstantBuilder, Retryable};
+use fs2::FileExt;
+
+use crate::docker::{PortMapping, PortProtocol};
+
+/// Number of bind attempts for a fixed host port before giving up.
+const PROBE_ATTEMPTS: u32 = 10;
+
+/// Delay between bind attempts. Ports of a container that was just removed can take a
+/// moment to be released.
+#[cfg(not(test))]
+const PROBE_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Tests only hold ports that stay busy: do not wait for them to be released.
+#[cfg(test)]
+const PROBE_RETRY_DELAY: Duration = Duration::from_millis(1);
+
+/// Lock file shared by all kupcake processes on the machine.
+fn lock_path() -> std::path::PathBuf {
+    std::env::temp_dir().join("kupcake-ports.lock")
+}
+
+/// Held while the ports of a container are being published. Unlocks on drop.
+pub(crate) struct PortReservation(Option<File>);
+
+impl Drop for PortReservation {
+    fn drop(&mut self) {
+        if let Some(ref file) = self.0
+            && let Err(e) = FileExt::unlock(file)
+        {
+            tracing::warn!(error = %e, "Failed to release port lock");
+        }
+    }
+}
+
+/// Check that the fixed host ports of `mappings` are free, holding the port lock.
+///
+/// Mappings with host port 0 are assigned by Docker and need no reservation; when all
+/// mappings are like that, no lock is taken. A busy port listed in `recorded` is replaced
+/// with 0 (a Docker-assigned port) instead of failing, and returned alongside the
+/// reservation. Keep the reservation alive until the container is started.
+pub(crate) async fn reserve_host_ports(
+    mappings: &mut [PortMapping],
+    recorded: &BTreeSet<u16>,
+) -> Result<(PortReservation, Vec<u16>)> {
+    let mut moved = Vec::new();
+    if mappings.iter().all(|m| m.host_port == 0) {
+        return Ok((PortReservation(None), moved));
+    }
+
+    let path = lock_path();
+    let file = File::create(&path)
+        .with_context(|| format!("Failed to open port lock file {}", path.display()))?;
+    let file = tokio::task::spawn_blocking(move || file.lock_exclusive().map(|_| file))
+        .await
+        .context("Port lock task failed")?
+        .context("Failed to acquire port lock")?;
+    let reservation = PortReservation(Some(file));
+
+    for mapping in mappings.iter_mut().filter(|m| m.host_port != 0) {
+        let (port, protocol) = (mapping.host_port, mapping.protocol);
+        let backoff = ConstantBuilder::default()
+            .with_delay(PROBE_RETRY_DELAY)
+            .with_max_times(PROBE_ATTEMPTS as usize - 1);
+        let probe = (|| async { probe_port(port, protocol) })
+            .retry(backoff)
+            .notify(|e, _| tracing::debug!(port, error = %e, "Host port busy, retrying"))
+            .await;
+
+        if let Err(e) = probe {
+            if !recorded.contains(&port) {
+                anyhow::bail!(
+                    "Host port {}/{} is already in use: {}",
+                    port,
+                    protocol.as_str(),
+                    e
+                );
+            }
+            tracing::warn!(
+                port,
+                protocol = protocol.as_str(),
+                "Recorded host port is in use, publishing on a Docker-assigned port instead"
+            );
+            mapping.host_port = 0;
+            moved.push(port);
+        }
+    }
+
+    Ok((reservation, moved))
+}
+
+/// Bind a host port on all interfaces and release it right away.
+pub(crate) fn probe_port(port: u16, protocol: PortProtocol) -> std::io::Result<()> {
+    match protocol {
+        PortProtocol::Tcp => TcpListener::bind(("0.0.0.0", port)).map(drop),
+        PortProtocol::Udp => UdpSocket::bind(("0.0.0.0", port)).map(drop),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_port_detects_busy_port() {
+        let listener = TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(probe_port(port, PortProtocol::Tcp).is_err());
+        drop(listener);
+        assert!(probe_port(port, PortProtocol::Tcp).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_docker_assigned_ports_take_no_lock() {
+        let (reservation, _) =
+            reserve_host_ports(&mut [PortMapping::tcp(8545, 0)], &BTreeSet::new())
+                .await
+                .unwrap();
+        assert!(reservation.0.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_busy_port_fallback() {
+        let listener = TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut mappings = [PortMapping::tcp(8545, port)];
+        let (_, moved) = reserve_host_ports(&mut mappings, &BTreeSet::from([port]))
+            .await
+            .unwrap();
+        assert_eq!(mappings[0].host_port, 0);
+        assert_eq!(moved, vec![port]);
+    }
+
+    #[tokio::test]
+    async fn test_busy_explicit_port_does_not_move() {
+        let listener = TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut mappings = [PortMapping::tcp(8545, port)];
+        let recorded = BTreeSet::from([port.wrapping_add(1)]);
+        assert!(reserve_host_ports(&mut mappings, &recorded).await.is_err());
+        assert_eq!(mappings[0].host_port, port);
+    }
+}
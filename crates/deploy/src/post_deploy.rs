@@ -0,0 +1,185 @@
+//! Post-deploy script run against the devnet once it is up.
+//!
+//! The script runs in a Foundry container on the deployment's Docker network, so
+//! `forge` and `cast` are available and the services are reachable by container name.
+//! The script's directory is mounted at `/workspace` (read-write, so `forge` can write
+//! its build outputs) and the script is run from there with `sh`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AnvilHandler, L2NodeHandler, L2StackHandler,
+    docker::{CreateAndStartContainerOptions, DockerImage, KupDocker, ServiceConfig},
+    rpc,
+    services::{ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG},
+};
+
+/// Directory the script's parent directory is mounted at in the container.
+const WORKSPACE_PATH: &str = "/workspace";
+
+/// Time to wait for the sequencer to produce its first L2 block, in seconds.
+const L2_READY_TIMEOUT_SECS: u64 = 120;
+
+/// A shell script run once after deployment.
+///
+/// Not saved to `Kupcake.toml`: redeploying from the config does not run it again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostDeployScript {
+    /// Path to the script on the host.
+    pub path: PathBuf,
+    /// Image the script runs in. Defaults to the Foundry image.
+    #[serde(default = "default_docker_image")]
+    pub docker_image: DockerImage,
+}
+
+fn default_docker_image() -> DockerImage {
+    DockerImage::new(ANVIL_DEFAULT_IMAGE, ANVIL_DEFAULT_TAG)
+}
+
+impl PostDeployScript {
+    /// Run `path` in the default Foundry image.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            docker_image: default_docker_image(),
+        }
+    }
+
+    /// Wait for the sequencer to produce blocks, then run the script to completion.
+    ///
+    /// Fails if the script exits with a non-zero code.
+    pub async fn run(
+        &self,
+        docker: &mut KupDocker,
+        l1_chain_id: u64,
+        l2_chain_id: u64,
+        anvil: &AnvilHandler,
+        l2_stack: &L2StackHandler,
+    ) -> Result<()> {
+        let script = self
+            .path
+            .canonicalize()
+            .with_context(|| format!("Post-deploy script not found: {}", self.path.display()))?;
+        let workspace = script
+            .parent()
+            .context("Post-deploy script has no parent directory")?;
+        let script_name = script
+            .file_name()
+            .context("Invalid post-deploy script path")?
+            .to_string_lossy()
+            .to_string();
+
+        let sequencer = l2_stack
+            .sequencers
+            .first()
+            .context("No sequencer to run the post-deploy script against")?;
+        wait_for_l2_blocks(&docker.rpc_client, sequencer).await?;
+
+        let network_prefix = docker
+            .config
+            .net_name
+            .strip_suffix("-network")
+            .unwrap_or(&docker.config.net_name);
+        let container_name = format!("{}-post-deploy", network_prefix);
+        let env = script_env(l1_chain_id, l2_chain_id, anvil, sequencer);
+        let mut service_config = ServiceConfig::new(self.docker_image.clone())
+            .entrypoint(vec!["sh".to_string(), "-c".to_string()])
+            .cmd(vec![format!("cd {WORKSPACE_PATH} && sh ./{script_name}")])
+            .bind(workspace, Path::new(WORKSPACE_PATH), "rw")
+            .env(env);
+
+        // Run as the current user so files written to the workspace stay editable.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = std::fs::metadata(workspace)
+                .context("Failed to get metadata for post-deploy workspace")?;
+            service_config = service_config.user(format!("{}:{}", metadata.uid(), metadata.gid()));
+        }
+
+        tracing::info!(script = %script.display(), "Running post-deploy script...");
+
+        let result = docker
+            .start_service(
+                &container_name,
+                service_config,
+                CreateAndStartContainerOptions {
+                    stream_logs: true,
+                    wait_for_container: true,
+                    start_options: None,
+                    collect_logs: false,
+                },
+            )
+            .await;
+
+        // The script has exited either way; don't leave its container behind.
+        if let Err(e) = docker.stop_and_remove_container(&container_name).await {
+            tracing::warn!(container = %container_name, error = %e, "Failed to remove post-deploy container");
+        }
+        result.with_context(|| format!("Post-deploy script {} failed", script.display()))?;
+
+        tracing::info!("✓ Post-deploy script completed");
+
+        Ok(())
+    }
+}
+
+/// Environment passed to the script. URLs are on the internal Docker network.
+fn script_env(
+    l1_chain_id: u64,
+    l2_chain_id: u64,
+    anvil: &AnvilHandler,
+    sequencer: &L2NodeHandler,
+) -> Vec<String> {
+    let deployer = &anvil.accounts.deployer;
+    vec![
+        format!("L1_RPC={}", anvil.l1_rpc_url),
+        format!("L2_RPC={}", sequencer.op_reth.http_rpc_url),
+        format!("L2_WS={}", sequencer.op_reth.ws_rpc_url),
+        format!("ROLLUP_RPC={}", sequencer.kona_node.rpc_url),
+        format!("L1_CHAIN_ID={}", l1_chain_id),
+        format!("L2_CHAIN_ID={}", l2_chain_id),
+        format!("DEPLOYER_ADDRESS={}", deployer.address),
+        format!("DEPLOYER_PRIVATE_KEY={}", deployer.private_key),
+    ]
+}
+
+/// Wait until the sequencer has produced an L2 block.
+///
+/// Needs the sequencer's op-reth RPC to be published on the host; otherwise the
+/// check is skipped.
+async fn wait_for_l2_blocks(client: &reqwest::Client, sequencer: &L2NodeHandler) -> Result<()> {
+    let Some(url) = sequencer.op_reth.http_host_url.clone() else {
+        tracing::debug!("Sequencer RPC not published on the host, not waiting for L2 blocks");
+        return Ok(());
+    };
+
+    rpc::wait_until_ready("L2 sequencer", L2_READY_TIMEOUT_SECS, || {
+        let client = client.clone();
+        let url = url.clone();
+        async move {
+            let block: String =
+                rpc::json_rpc_call(&client, url.as_str(), "eth_blockNumber", vec![]).await?;
+            let number = u64::from_str_radix(block.trim_start_matches("0x"), 16)?;
+            anyhow::ensure!(number > 0, "no L2 block produced yet");
+            Ok(())
+        }
+    })
+    .await
+    .context("L2 sequencer did not produce blocks before the post-deploy script")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_deploy_script_defaults_to_foundry_image() {
+        let script: PostDeployScript = toml::from_str(r#"path = "./bootstrap.sh""#).unwrap();
+        assert_eq!(script, PostDeployScript::new("./bootstrap.sh"));
+        assert_eq!(script.docker_image, default_docker_image());
+    }
+}
@@ -5,21 +5,131 @@ use std::time::Duration;
 
 use anyhow::Context;
 use backon::{ConstantBuilder, Retryable};
-use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 
-/// Default timeout for RPC requests.
-const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default timeout for RPC requests, in seconds.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 5;
 
 /// Default interval between polling attempts when waiting for readiness.
 const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
-/// Create an HTTP client configured for JSON-RPC requests.
+fn default_request_timeout_secs() -> u64 {
+    DEFAULT_REQUEST_TIMEOUT_SECS
+}
+
+/// Timeouts and connection pool of a deployment's RPC client (see [`build_client`]), used
+/// by the health checks, the faucet and the readiness waiters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RpcClientConfig {
+    /// Timeout of a whole request, in seconds.
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Timeout of connection establishment, in seconds. None bounds it by the request
+    /// timeout only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// Maximum idle connections kept open per host. None keeps reqwest's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_max_idle_per_host: Option<usize>,
+}
+
+impl Default for RpcClientConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            connect_timeout_secs: None,
+            pool_max_idle_per_host: None,
+        }
+    }
+}
+
+impl RpcClientConfig {
+    /// Whether this is the default configuration.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply the timeouts and pool size to `builder`.
+    fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        let mut builder = builder.timeout(Duration::from_secs(self.request_timeout_secs));
+        if let Some(secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(size) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(size);
+        }
+        builder
+    }
+}
+
+/// Create an HTTP client configured for JSON-RPC requests, with the default timeouts.
+///
+/// Deployments build theirs with [`build_client`] from their own configuration instead
+/// (see [`Deployer::rpc_client`](crate::Deployer::rpc_client)).
 pub fn create_client() -> Result<reqwest::Client, anyhow::Error> {
-    reqwest::Client::builder()
-        .timeout(DEFAULT_TIMEOUT)
-        .build()
-        .context("Failed to create HTTP client")
+    build_client(&RpcClientConfig::default(), None, None)
+}
+
+/// Create an HTTP client for JSON-RPC requests with the timeouts and pool of `config`.
+///
+/// The CA certificates of the PEM bundle at `ca_bundle` are trusted in addition to the
+/// system roots. With `proxy`, its HTTP(S) proxies are used instead of the system ones;
+/// SOCKS proxies are only passed to the containers, as the client is built without SOCKS
+/// support and connects directly.
+///
+/// The returned client keeps its connection pool: clone it rather than building a new
+/// one per request.
+pub fn build_client(
+    config: &RpcClientConfig,
+    ca_bundle: Option<&Path>,
+    proxy: Option<&crate::ProxyConfig>,
+) -> Result<reqwest::Client, anyhow::Error> {
+    let mut builder = config.apply(reqwest::Client::builder());
+    if let Some(path) = ca_bundle {
+        for certificate in read_ca_bundle(path)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    if let Some(proxy) = proxy {
+        builder = proxies(proxy)?
+            .into_iter()
+            .fold(builder.no_proxy(), |builder, proxy| builder.proxy(proxy));
+    }
+    builder.build().context("Failed to create HTTP client")
+}
+
+/// Read the CA certificates of the PEM bundle at `path`.
+fn read_ca_bundle(path: &Path) -> Result<Vec<reqwest::Certificate>, anyhow::Error> {
+    let pem = std::fs::read(path)
+        .with_context(|| format!("Failed to read CA bundle {}", path.display()))?;
+    let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+        .with_context(|| format!("Failed to parse CA bundle {}", path.display()))?;
+    if certificates.is_empty() {
+        anyhow::bail!("CA bundle {} contains no certificates", path.display());
+    }
+    Ok(certificates)
+}
+
+/// The HTTP(S) proxies of `proxy`, skipping SOCKS ones.
+fn proxies(proxy: &crate::ProxyConfig) -> Result<Vec<reqwest::Proxy>, anyhow::Error> {
+    let no_proxy = reqwest::NoProxy::from_string(&proxy.no_proxy_list(&[]));
+    let mut proxies = Vec::new();
+    for (url, https) in [(&proxy.http_proxy, false), (&proxy.https_proxy, true)] {
+        let Some(url) = url else { continue };
+        if url.starts_with("socks") {
+            tracing::warn!(proxy = %url, "SOCKS proxies are not supported for kupcake's own RPC calls");
+            continue;
+        }
+        let built = if https {
+            reqwest::Proxy::https(url.as_str())
+        } else {
+            reqwest::Proxy::http(url.as_str())
+        }
+        .with_context(|| format!("Invalid proxy URL {}", url))?;
+        proxies.push(built.no_proxy(no_proxy.clone()));
+    }
+    Ok(proxies)
 }
 
 /// Make a JSON-RPC call and deserialize the result.
@@ -74,6 +184,126 @@ pub async fn json_rpc_call<T: DeserializeOwned>(
         .with_context(|| format!("Failed to deserialize {} result", method))
 }
 
+/// Default time [`wait_for_receipt`] waits for a transaction to be mined, in seconds.
+pub const DEFAULT_RECEIPT_TIMEOUT_SECS: u64 = 60;
+
+/// Send `tx` (an `eth_sendTransaction` object) from an account unlocked on the node, such
+/// as Anvil's dev accounts. Returns the transaction hash without waiting for it to be mined.
+pub async fn send_unlocked_transaction(
+    client: &reqwest::Client,
+    url: &str,
+    tx: Value,
+    what: &str,
+) -> Result<String, anyhow::Error> {
+    json_rpc_call(client, url, "eth_sendTransaction", vec![tx])
+        .await
+        .with_context(|| format!("Failed to send {what} transaction"))
+}
+
+/// Wait up to `timeout_secs` for `tx_hash` to be mined. Returns its receipt, or fails if
+/// the transaction reverted.
+pub async fn wait_for_receipt(
+    client: &reqwest::Client,
+    url: &str,
+    tx_hash: &str,
+    what: &str,
+    timeout_secs: u64,
+) -> Result<Value, anyhow::Error> {
+    let get_receipt = || async {
+        let receipt: Value = json_rpc_call(
+            client,
+            url,
+            "eth_getTransactionReceipt",
+            vec![serde_json::json!(tx_hash)],
+        )
+        .await?;
+        if receipt.is_null() {
+            anyhow::bail!("Transaction not mined yet");
+        }
+        Ok(receipt)
+    };
+    wait_until_ready(&format!("{what} transaction"), timeout_secs, || async {
+        get_receipt().await.map(|_| ())
+    })
+    .await?;
+
+    let receipt = get_receipt().await?;
+    if receipt["status"].as_str() != Some("0x1") {
+        anyhow::bail!("{} transaction {} reverted", what, tx_hash);
+    }
+    Ok(receipt)
+}
+
+/// [`send_unlocked_transaction`], then [`wait_for_receipt`] with the default timeout.
+/// Returns the transaction hash.
+pub async fn send_unlocked_transaction_and_wait(
+    client: &reqwest::Client,
+    url: &str,
+    tx: Value,
+    what: &str,
+) -> Result<String, anyhow::Error> {
+    let tx_hash = send_unlocked_transaction(client, url, tx, what).await?;
+    wait_for_receipt(client, url, &tx_hash, what, DEFAULT_RECEIPT_TIMEOUT_SECS).await?;
+    Ok(tx_hash)
+}
+
+/// Liveness of the RPC of an op-service (op-batcher, op-proposer, op-conductor, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OpServiceProbe {
+    /// Whether the RPC answered. An error response still counts as an answer.
+    pub responding: bool,
+    /// Version reported by `opp_version`.
+    pub version: Option<String>,
+    /// Transport error, or message of the error response.
+    pub error: Option<String>,
+}
+
+impl OpServiceProbe {
+    fn from_response(body: &Value) -> Self {
+        let error = body.get("error").map(|error| {
+            error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown")
+                .to_string()
+        });
+        Self {
+            responding: true,
+            version: body["result"].as_str().map(String::from),
+            error,
+        }
+    }
+}
+
+/// Probe the RPC of an op-service with `opp_version`, the method every op-service
+/// exposes.
+pub async fn probe_op_service(client: &reqwest::Client, url: &str) -> OpServiceProbe {
+    let response = client
+        .post(url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "opp_version",
+            "params": [],
+            "id": 1
+        }))
+        .send()
+        .await;
+
+    let body = match response {
+        Ok(response) => response.json::<Value>().await,
+        Err(e) => Err(e),
+    };
+
+    match body {
+        Ok(body) => OpServiceProbe::from_response(&body),
+        Err(e) => OpServiceProbe {
+            responding: false,
+            version: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
 /// Get the timestamp of the latest block from an Ethereum JSON-RPC endpoint.
 pub async fn get_latest_block_timestamp(rpc_url: &str) -> Result<u64, anyhow::Error> {
     let client = create_client()?;
@@ -129,6 +359,65 @@ pub async fn evm_set_interval_mining(rpc_url: &str, block_time: u64) -> Result<(
     Ok(())
 }
 
+/// Advance Anvil's clock by `seconds` via `evm_increaseTime`, then mine a block
+/// so the new time is visible to `eth_call`.
+pub async fn evm_increase_time(rpc_url: &str, seconds: u64) -> Result<(), anyhow::Error> {
+    let client = create_client()?;
+    let _: serde_json::Value = json_rpc_call(
+        &client,
+        rpc_url,
+        "evm_increaseTime",
+        vec![serde_json::json!(seconds)],
+    )
+    .await
+    .context("evm_increaseTime RPC failed")?;
+    let _: serde_json::Value = json_rpc_call(&client, rpc_url, "evm_mine", vec![])
+        .await
+        .context("evm_mine RPC failed")?;
+    Ok(())
+}
+
+/// Replace the runtime bytecode at an address via `anvil_setCode`.
+pub async fn anvil_set_code(
+    rpc_url: &str,
+    address: &str,
+    bytecode: &str,
+) -> Result<(), anyhow::Error> {
+    let client = create_client()?;
+    let _: serde_json::Value = json_rpc_call(
+        &client,
+        rpc_url,
+        "anvil_setCode",
+        vec![serde_json::json!(address), serde_json::json!(bytecode)],
+    )
+    .await
+    .context("anvil_setCode RPC failed")?;
+    Ok(())
+}
+
+/// Write a storage slot of an address via `anvil_setStorageAt`.
+pub async fn anvil_set_storage_at(
+    rpc_url: &str,
+    address: &str,
+    slot: &str,
+    value: &str,
+) -> Result<(), anyhow::Error> {
+    let client = create_client()?;
+    let _: serde_json::Value = json_rpc_call(
+        &client,
+        rpc_url,
+        "anvil_setStorageAt",
+        vec![
+            serde_json::json!(address),
+            serde_json::json!(slot),
+            serde_json::json!(value),
+        ],
+    )
+    .await
+    .context("anvil_setStorageAt RPC failed")?;
+    Ok(())
+}
+
 /// Dump Anvil state via `anvil_dumpState` RPC and write to disk.
 ///
 /// Called before cleanup to persist Anvil L1 state via RPC. The returned hex
@@ -214,7 +503,73 @@ where
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use super::*;
+
+    /// Serve `respond(method, params)` as a JSON-RPC endpoint on localhost, one request
+    /// per connection, and return its URL.
+    pub(crate) async fn serve_json_rpc(respond: fn(&str, &Value) -> Value) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let body = loop {
+                    let mut chunk = [0u8; 4096];
+                    let read = stream.read(&mut chunk).await.unwrap();
+                    request.extend_from_slice(&chunk[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break serde_json::from_str::<Value>(body).unwrap();
+                    }
+                };
+                let result = respond(body["method"].as_str().unwrap(), &body["params"]);
+                let response =
+                    serde_json::json!({ "jsonrpc": "2.0", "id": body["id"], "result": result })
+                        .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_op_service_probe_from_response() {
+        let probe = OpServiceProbe::from_response(&serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "result": "v1.13.0"
+        }));
+        assert!(probe.responding);
+        assert_eq!(probe.version.as_deref(), Some("v1.13.0"));
+        assert_eq!(probe.error, None);
+
+        // A service answering with an error is alive, but the error is reported.
+        let probe = OpServiceProbe::from_response(&serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "method not found" }
+        }));
+        assert!(probe.responding);
+        assert_eq!(probe.error.as_deref(), Some("method not found"));
+    }
+
     #[test]
     fn test_parse_block_timestamp_hex() {
         let block: serde_json::Value = serde_json::json!({
@@ -229,4 +584,20 @@ mod tests {
 
         assert_eq!(timestamp, 0x6613fa00);
     }
+
+    #[test]
+    fn test_build_client_rejects_invalid_ca_bundles() {
+        let dir = tempdir::TempDir::new("kupcake-test").unwrap();
+        let config = RpcClientConfig::default();
+        let missing = dir.path().join("missing.pem");
+        assert!(build_client(&config, Some(&missing), None).is_err());
+
+        let empty = dir.path().join("empty.pem");
+        std::fs::write(&empty, "not a certificate\n").unwrap();
+        let err = build_client(&config, Some(&empty), None).unwrap_err();
+        assert!(err.to_string().contains("contains no certificates"));
+
+        // Each client has its own settings: a bad bundle does not affect the others.
+        assert!(build_client(&config, None, None).is_ok());
+    }
 }
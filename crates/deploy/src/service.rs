@@ -10,6 +10,7 @@ use crate::docker::{
     CreateAndStartContainerOptions, DockerImage, KupDocker, ServiceConfig, ServiceHandler,
 };
 use crate::metrics::ContainerDeployTimings;
+use crate::services::MetricsTarget;
 
 /// Common interface for all deployable services in Kupcake.
 ///
@@ -43,6 +44,18 @@ pub trait KupcakeService: Send + Sync + 'static {
     ) -> impl Future<Output = Result<Self::Output>> + Send + 'a;
 }
 
+/// A running service whose metrics Prometheus can scrape.
+///
+/// Implemented by the handlers of services exposing a metrics endpoint, so that the
+/// monitoring stage collects its scrape targets without knowing each service's port.
+pub trait ScrapeTarget {
+    /// Scrape target of this service, or None if it exposes no metrics.
+    ///
+    /// The job name is the container name; the deployer shortens it by stripping the
+    /// network prefix.
+    fn metrics_target(&self) -> Option<MetricsTarget>;
+}
+
 /// Common deploy pipeline for single-container services.
 ///
 /// Ensures the image is ready, then starts the container with the given config.
@@ -0,0 +1,192 @@
+//! Ad-hoc JSON-RPC calls against the services of a deployed network.
+//!
+//! Resolves a service name (and node identifier for per-node services) to the
+//! host-published RPC URL of its container, then forwards the call through
+//! [`rpc::json_rpc_call`].
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use bollard::Docker;
+use serde_json::Value;
+
+use crate::{
+    Deployer,
+    health::build_host_rpc_url,
+    node_lifecycle::{NodeLocation, resolve_node},
+    rpc,
+};
+
+/// A service exposing a JSON-RPC endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcService {
+    /// The L1 node (Anvil or the external L1).
+    Anvil,
+    /// op-reth execution client of an L2 node.
+    OpReth,
+    /// Consensus client of an L2 node (kona-node or op-node).
+    KonaNode,
+    /// op-batcher admin RPC.
+    OpBatcher,
+    /// op-proposer admin RPC.
+    OpProposer,
+    /// op-challenger (has no RPC server).
+    OpChallenger,
+    /// op-conductor of a sequencer node.
+    Conductor,
+}
+
+impl RpcService {
+    /// All supported service names, as accepted by [`FromStr`].
+    pub const NAMES: &[&str] = &[
+        "anvil",
+        "op-reth",
+        "kona-node",
+        "op-batcher",
+        "op-proposer",
+        "op-challenger",
+        "conductor",
+    ];
+}
+
+impl FromStr for RpcService {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "anvil" | "l1" => Ok(Self::Anvil),
+            "op-reth" | "reth" => Ok(Self::OpReth),
+            "kona-node" | "kona" | "op-node" => Ok(Self::KonaNode),
+            "op-batcher" | "batcher" => Ok(Self::OpBatcher),
+            "op-proposer" | "proposer" => Ok(Self::OpProposer),
+            "op-challenger" | "challenger" => Ok(Self::OpChallenger),
+            "conductor" | "op-conductor" => Ok(Self::Conductor),
+            other => anyhow::bail!(
+                "Unknown service '{}'. Expected one of: {}",
+                other,
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
+}
+
+/// Resolve the container name and RPC port of a service.
+///
+/// `node` is a node identifier (see [`resolve_node`]) and is only used for per-node
+/// services (op-reth, kona-node, conductor).
+pub fn rpc_target(deployer: &Deployer, service: RpcService, node: &str) -> Result<(String, u16)> {
+    let node_builder = || -> Result<_> {
+        Ok(match resolve_node(deployer, node)? {
+            NodeLocation::Sequencer(i) => &deployer.l2_stack.sequencers[i],
+            NodeLocation::Validator(i) => &deployer.l2_stack.validators[i],
+        })
+    };
+
+    match service {
+        RpcService::Anvil => Ok((deployer.anvil.container_name.clone(), deployer.anvil.port)),
+        RpcService::OpReth => {
+            let op_reth = &node_builder()?.op_reth;
+            Ok((op_reth.container_name.clone(), op_reth.http_port))
+        }
+        RpcService::KonaNode => {
+            let node = node_builder()?;
+            let port = node
+                .op_node
+                .as_ref()
+                .map_or(node.kona_node.rpc_port, |op_node| op_node.rpc_port);
+            Ok((node.consensus_container_name().to_string(), port))
+        }
+        RpcService::OpBatcher => {
+            let batcher = &deployer.l2_stack.op_batcher;
+            Ok((batcher.container_name.clone(), batcher.rpc_port))
+        }
+        RpcService::OpProposer => {
+            let proposer = deployer
+                .l2_stack
+                .op_proposer
+                .as_ref()
+                .context("op-proposer is disabled in this deployment")?;
+            Ok((proposer.container_name.clone(), proposer.rpc_port))
+        }
+        RpcService::OpChallenger => {
+            anyhow::bail!("op-challenger does not expose an RPC server (only metrics)")
+        }
+        RpcService::Conductor => {
+            let conductor = node_builder()?
+                .op_conductor
+                .as_ref()
+                .with_context(|| format!("Node '{}' has no op-conductor", node))?;
+            Ok((conductor.container_name.clone(), conductor.rpc_port))
+        }
+    }
+}
+
+/// Parse a CLI parameter into a JSON value.
+///
+/// Valid JSON (numbers, booleans, objects, arrays, quoted strings) is used as-is;
+/// anything else, such as `0x`-prefixed hex or `latest`, is passed as a string.
+pub fn parse_param(param: &str) -> Value {
+    serde_json::from_str(param).unwrap_or_else(|_| Value::String(param.to_string()))
+}
+
+/// Make a JSON-RPC call to a service of a deployed network and return the raw result.
+pub async fn call_service(
+    deployer: &Deployer,
+    service: RpcService,
+    node: &str,
+    method: &str,
+    params: Vec<Value>,
+) -> Result<Value> {
+    let url = match (&deployer.external_l1, service) {
+        (Some(external_l1), RpcService::Anvil) => external_l1.rpc_url.clone(),
+        _ => {
+            let (container_name, port) = rpc_target(deployer, service, node)?;
+            let docker = Docker::connect_with_local_defaults()
+                .context("Failed to connect to Docker daemon")?;
+            build_host_rpc_url(&docker, &container_name, port)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Port {} of {} is not published to the host - is it running?",
+                        port, container_name
+                    )
+                })?
+        }
+    };
+
+    tracing::debug!(%url, method, "Forwarding JSON-RPC call");
+
+    let client = deployer.rpc_client()?;
+    rpc::json_rpc_call(&client, &url, method, params)
+        .await
+        .with_context(|| format!("{} call to {} failed", method, url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_service() {
+        for name in RpcService::NAMES {
+            assert!(name.parse::<RpcService>().is_ok(), "{name}");
+        }
+        assert_eq!(
+            "op-node".parse::<RpcService>().unwrap(),
+            RpcService::KonaNode
+        );
+        assert!("geth".parse::<RpcService>().is_err());
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(parse_param("latest"), Value::String("latest".into()));
+        assert_eq!(parse_param("0x10"), Value::String("0x10".into()));
+        assert_eq!(parse_param("true"), Value::Bool(true));
+        assert_eq!(parse_param("42"), serde_json::json!(42));
+        assert_eq!(
+            parse_param(r#"{"to":"0x00"}"#),
+            serde_json::json!({ "to": "0x00" })
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! In-place update of a running network after its configuration changed.
+//!
+//! Compares the per-service config hashes recorded in the deployment version file with
+//! the current configuration and recreates only the services whose configuration changed.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{Context, Result};
+use url::Url;
+
+use crate::{
+    AnvilAccounts, ContainerState, Deployer, DeploymentConfigHash, DeploymentVersion, KupDocker,
+    L2StackBuilder,
+    deployment_hash::{DEPLOYMENT_VERSION_FILENAME, service_hashes},
+    health::build_host_rpc_url,
+    service::KupcakeService,
+    services::{
+        l2_node::{ConductorContext, L2NodeInput},
+        op_batcher::OpBatcherInput,
+        op_challenger::OpChallengerInput,
+        op_proposer::OpProposerInput,
+    },
+};
+
+/// Result of [`update_changed_services`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// The network is not running; a regular deployment is needed.
+    NotRunning,
+    /// No service configuration changed.
+    Unchanged,
+    /// The listed containers were recreated with their new configuration.
+    Updated(Vec<String>),
+    /// The change cannot be applied in place; the network must be redeployed.
+    NeedsRedeploy {
+        /// Why an in-place update is not possible.
+        reason: String,
+    },
+}
+
+/// Recreate the services of a running network whose configuration changed since it was
+/// deployed.
+///
+/// op-batcher, op-proposer, op-challenger and the L2 nodes without an op-conductor are
+/// recreated in place, on every L2 chain. An L2 node's op-reth and consensus client are
+/// recreated together, since they share a JWT secret; their datadirs are bind-mounted, so
+/// the new containers resume from the existing chain state. A change to any other service
+/// (Anvil, op-conductor nodes, monitoring, ...), to the contract deployment inputs, or a
+/// deployment without recorded service hashes yields [`UpdateOutcome::NeedsRedeploy`] and
+/// leaves the network untouched.
+pub async fn update_changed_services(
+    deployer: &Deployer,
+    docker: &mut KupDocker,
+) -> Result<UpdateOutcome> {
+    let batcher_name = &deployer.l2_stack.op_batcher.container_name;
+    if docker.get_container_state(batcher_name).await != ContainerState::Running {
+        return Ok(UpdateOutcome::NotRunning);
+    }
+
+    let l2_nodes_data_path = deployer.outdata.join("l2-stack");
+    let version_file_path = l2_nodes_data_path.join(DEPLOYMENT_VERSION_FILENAME);
+    let mut version = DeploymentVersion::load_from_file(&version_file_path)
+        .context("Failed to load the deployment version of the running network")?;
+
+    let config_hash = DeploymentConfigHash::from_deployer(deployer).compute_hash()?;
+    if version.config_hash != config_hash {
+        return Ok(UpdateOutcome::NeedsRedeploy {
+            reason: "the contract deployment configuration changed".to_string(),
+        });
+    }
+    if version.service_hashes.is_empty() {
+        return Ok(UpdateOutcome::NeedsRedeploy {
+            reason: "the running network has no recorded service hashes".to_string(),
+        });
+    }
+
+    let current = service_hashes(deployer)?;
+    let changed = version.changed_services(&current);
+    if changed.is_empty() {
+        return Ok(UpdateOutcome::Unchanged);
+    }
+
+    let updatable = recreatable_services(deployer);
+    let mut targets = Vec::new();
+    for name in &changed {
+        let Some(target) = updatable.get(name.as_str()) else {
+            return Ok(UpdateOutcome::NeedsRedeploy {
+                reason: format!("the configuration of {} changed", name),
+            });
+        };
+        if !targets.contains(target) {
+            targets.push(*target);
+        }
+    }
+
+    if deployer.docker.proxy.is_some() {
+        docker.internal_hosts = deployer.container_names()?;
+    }
+    let l1 = RunningL1::from_config(deployer, docker).await?;
+    for target in targets {
+        recreate(deployer, docker, &l1, target).await?;
+    }
+
+    version.service_hashes = current;
+    version
+        .save_to_file(&version_file_path)
+        .context("Failed to save deployment version")?;
+
+    Ok(UpdateOutcome::Updated(changed))
+}
+
+/// Connection details of the L1, which keeps running while services are recreated,
+/// rebuilt from the config.
+struct RunningL1 {
+    rpc_url: String,
+    host_url: Option<String>,
+    ws_url: Option<String>,
+    accounts: AnvilAccounts,
+}
+
+impl RunningL1 {
+    async fn from_config(deployer: &Deployer, docker: &KupDocker) -> Result<Self> {
+        Ok(match deployer.external_l1 {
+            Some(ref external) => {
+                let handler = external.handler()?;
+                Self {
+                    rpc_url: handler.l1_rpc_url.to_string(),
+                    host_url: handler.l1_host_url.map(|u| u.to_string()),
+                    ws_url: handler.l1_ws_url.map(|u| u.to_string()),
+                    accounts: handler.accounts,
+                }
+            }
+            None => Self {
+                rpc_url: format!(
+                    "http://{}:{}/",
+                    deployer.anvil.container_name, deployer.anvil.port
+                ),
+                host_url: build_host_rpc_url(
+                    docker,
+                    &deployer.anvil.container_name,
+                    deployer.anvil.port,
+                )
+                .await,
+                ws_url: None,
+                accounts: deployer.derive_accounts()?,
+            },
+        })
+    }
+}
+
+/// A service that can be recreated in place, within the L2 chain at `chain` (0 for the
+/// primary chain, then the additional chains in order).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Recreatable {
+    /// The op-reth and consensus client of a sequencer.
+    Sequencer { chain: usize, index: usize },
+    /// The op-reth and consensus client of a validator.
+    Validator { chain: usize, index: usize },
+    /// op-batcher.
+    Batcher { chain: usize },
+    /// op-proposer.
+    Proposer { chain: usize },
+    /// op-challenger.
+    Challenger { chain: usize },
+}
+
+/// The L2 stacks of a deployment with their data directories, primary chain first.
+fn l2_stacks(deployer: &Deployer) -> Vec<(&L2StackBuilder, PathBuf)> {
+    std::iter::once((&deployer.l2_stack, deployer.outdata.join("l2-stack")))
+        .chain(
+            deployer
+                .additional_l2_chains
+                .iter()
+                .map(|chain| (&chain.l2_stack, chain.data_path(&deployer.outdata))),
+        )
+        .collect()
+}
+
+/// The services that can be recreated in place, keyed by container name.
+///
+/// Nodes with an op-conductor are left out: recreating them would disrupt the Raft
+/// cluster that decides which sequencer is active.
+fn recreatable_services(deployer: &Deployer) -> HashMap<&str, Recreatable> {
+    let mut services = HashMap::new();
+    for (chain, (stack, _)) in l2_stacks(deployer).into_iter().enumerate() {
+        let nodes = stack
+            .sequencers
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node, Recreatable::Sequencer { chain, index }))
+            .chain(
+                stack
+                    .validators
+                    .iter()
+                    .enumerate()
+                    .map(|(index, node)| (node, Recreatable::Validator { chain, index })),
+            );
+        for (node, target) in nodes.filter(|(node, _)| node.op_conductor.is_none()) {
+            services.insert(node.op_reth.container_name.as_str(), target);
+            services.insert(node.consensus_container_name(), target);
+        }
+
+        services.insert(
+            stack.op_batcher.container_name.as_str(),
+            Recreatable::Batcher { chain },
+        );
+        if let Some(ref proposer) = stack.op_proposer {
+            services.insert(
+                proposer.container_name.as_str(),
+                Recreatable::Proposer { chain },
+            );
+        }
+        if let Some(ref challenger) = stack.op_challenger {
+            services.insert(
+                challenger.container_name.as_str(),
+                Recreatable::Challenger { chain },
+            );
+        }
+    }
+    services
+}
+
+/// Remove the containers of `target` and start them again from the config.
+async fn recreate(
+    deployer: &Deployer,
+    docker: &mut KupDocker,
+    l1: &RunningL1,
+    target: Recreatable,
+) -> Result<()> {
+    let stacks = l2_stacks(deployer);
+    let chain = match target {
+        Recreatable::Sequencer { chain, .. }
+        | Recreatable::Validator { chain, .. }
+        | Recreatable::Batcher { chain }
+        | Recreatable::Proposer { chain }
+        | Recreatable::Challenger { chain } => chain,
+    };
+    let (stack, data_path) = &stacks[chain];
+    let accounts = l1.accounts.for_l2_chain(chain)?;
+
+    let primary = &stack.sequencers[0];
+    let consensus_rpc_port = primary
+        .op_node
+        .as_ref()
+        .map_or(primary.kona_node.rpc_port, |op_node| op_node.rpc_port);
+    let l2_rpc_url = primary.op_reth.docker_rpc_url();
+    let rollup_rpc_url = format!(
+        "http://{}:{}/",
+        primary.consensus_container_name(),
+        consensus_rpc_port
+    );
+
+    let remove = async |docker: &mut KupDocker, name: &str| {
+        tracing::info!(container = %name, "Recreating service with its new configuration");
+        docker
+            .stop_and_remove_container(&name.to_string())
+            .await
+            .with_context(|| format!("Failed to remove container '{}'", name))
+    };
+
+    match target {
+        Recreatable::Sequencer { index, .. } | Recreatable::Validator { index, .. } => {
+            let is_validator = matches!(target, Recreatable::Validator { .. });
+            let node = if is_validator {
+                &stack.validators[index]
+            } else {
+                &stack.sequencers[index]
+            };
+            remove(docker, &node.op_reth.container_name).await?;
+            remove(docker, node.consensus_container_name()).await?;
+
+            // Peers are every other node, as when the stack was started.
+            let is_peer = |enode: &String| {
+                !enode.contains(&format!("@{}:", node.op_reth.container_name))
+                    && !enode.contains(&format!("@{}:", node.consensus_container_name()))
+            };
+            let (sequencer_rpc, sequencer_flashblocks_relay_url) = if is_validator {
+                let relay = primary
+                    .kona_node
+                    .flashblocks_relay_port
+                    .map(|port| KupDocker::build_ws_url(primary.consensus_container_name(), port))
+                    .transpose()?;
+                (Some(Url::parse(&l2_rpc_url)?), relay)
+            } else {
+                (None, None)
+            };
+
+            node.deploy(
+                docker,
+                data_path,
+                L2NodeInput {
+                    l1_rpc_url: l1.rpc_url.clone(),
+                    l1_host_url: l1.host_url.clone(),
+                    l1_ws_url: l1.ws_url.clone(),
+                    unsafe_block_signer_key: hex::encode(&accounts.unsafe_block_signer.private_key),
+                    sequencer_rpc,
+                    kona_node_enodes: stack
+                        .compute_kona_node_enodes()
+                        .into_iter()
+                        .filter(is_peer)
+                        .collect(),
+                    op_reth_enodes: stack
+                        .compute_op_reth_enodes()
+                        .into_iter()
+                        .filter(is_peer)
+                        .collect(),
+                    l1_chain_id: deployer.l1_chain_id,
+                    conductor_context: ConductorContext::None,
+                    sequencer_flashblocks_relay_url,
+                    op_reth_p2p_secret_key: node.op_reth.p2p_secret_key.clone(),
+                    patch_genesis_hash: false,
+                },
+            )
+            .await
+            .with_context(|| format!("Failed to recreate {}", node.op_reth.container_name))?;
+        }
+        Recreatable::Batcher { .. } => {
+            remove(docker, &stack.op_batcher.container_name).await?;
+            stack
+                .op_batcher
+                .deploy(
+                    docker,
+                    data_path,
+                    OpBatcherInput {
+                        l1_rpc_url: l1.rpc_url.clone(),
+                        l1_ws_url: l1.ws_url.clone(),
+                        l2_rpc_url,
+                        rollup_rpc_url,
+                        batcher_private_key: accounts.batcher.private_key.to_string(),
+                    },
+                )
+                .await
+                .context("Failed to recreate op-batcher")?;
+        }
+        Recreatable::Proposer { .. } => {
+            let proposer = stack
+                .op_proposer
+                .as_ref()
+                .context("op-proposer is disabled in this deployment")?;
+            remove(docker, &proposer.container_name).await?;
+            proposer
+                .deploy(
+                    docker,
+                    data_path,
+                    OpProposerInput {
+                        l1_rpc_url: l1.rpc_url.clone(),
+                        rollup_rpc_url,
+                        proposer_private_key: accounts.proposer.private_key.to_string(),
+                    },
+                )
+                .await
+                .context("Failed to recreate op-proposer")?;
+        }
+        Recreatable::Challenger { .. } => {
+            let challenger = stack
+                .op_challenger
+                .as_ref()
+                .context("op-challenger is disabled in this deployment")?;
+            remove(docker, &challenger.container_name).await?;
+            challenger
+                .deploy(
+                    docker,
+                    data_path,
+                    OpChallengerInput {
+                        l1_rpc_url: l1.rpc_url.clone(),
+                        l1_host_url: l1.host_url.clone(),
+                        l2_rpc_url,
+                        rollup_rpc_url,
+                        challenger_private_key: accounts.challenger.private_key.to_string(),
+                    },
+                )
+                .await
+                .context("Failed to recreate op-challenger")?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace a single service of a running network: remove its container and start it
+/// again from the loaded config, e.g. after editing its flags in `Kupcake.toml`.
+///
+/// `service` is resolved as by `kupcake logs` (see [`crate::logs::service_container`]).
+/// The services of [`update_changed_services`] can be replaced; replacing an L2 node's
+/// op-reth or consensus client replaces both. The new configuration hashes are recorded
+/// in the deployment version. Returns the container name.
+pub async fn restart_service(
+    deployer: &Deployer,
+    docker: &mut KupDocker,
+    service: &str,
+) -> Result<String> {
+    let name = crate::logs::service_container(deployer, service, "sequencer")?;
+    let Some(&target) = recreatable_services(deployer).get(name.as_str()) else {
+        anyhow::bail!(
+            "'{}' cannot be replaced in place: only op-batcher, op-proposer, op-challenger \
+             and L2 nodes without op-conductor can. Use `kupcake node restart` to restart it",
+            name
+        );
+    };
+    let batcher_name = &deployer.l2_stack.op_batcher.container_name;
+    if docker.get_container_state(batcher_name).await != ContainerState::Running {
+        anyhow::bail!("The network is not running; deploy it first");
+    }
+
+    if deployer.docker.proxy.is_some() {
+        docker.internal_hosts = deployer.container_names()?;
+    }
+    let l1 = RunningL1::from_config(deployer, docker).await?;
+    recreate(deployer, docker, &l1, target).await?;
+
+    let version_file_path = deployer
+        .outdata
+        .join("l2-stack")
+        .join(DEPLOYMENT_VERSION_FILENAME);
+    if let Ok(mut version) = DeploymentVersion::load_from_file(&version_file_path)
+        && !version.service_hashes.is_empty()
+    {
+        let replaced = recreatable_services(deployer)
+            .into_iter()
+            .filter(|(_, other)| *other == target)
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        for (name, hash) in service_hashes(deployer)? {
+            if replaced.contains(&name) {
+                version.service_hashes.insert(name, hash);
+            }
+        }
+        version
+            .save_to_file(&version_file_path)
+            .context("Failed to save deployment version")?;
+    }
+
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_recreatable_services() {
+        let mut deployer = crate::deployer::tests::test_deployer(PathBuf::from("/tmp/test"));
+        deployer.l2_stack = L2StackBuilder::with_counts(2, 1);
+        let mut l2_stack = L2StackBuilder::with_counts(1, 0);
+        l2_stack.sequencers[0].op_reth.container_name = "kupcake-1235-op-reth".to_string();
+        l2_stack.sequencers[0].kona_node.container_name = "kupcake-1235-kona-node".to_string();
+        l2_stack.op_batcher.container_name = "kupcake-1235-op-batcher".to_string();
+        l2_stack.op_proposer = None;
+        l2_stack.op_challenger = None;
+        deployer.additional_l2_chains = vec![crate::L2ChainBuilder {
+            chain_id: 1235,
+            l2_stack,
+        }];
+
+        let services = recreatable_services(&deployer);
+        let stack = &deployer.l2_stack;
+
+        // Sequencers with an op-conductor are left out.
+        assert!(!services.contains_key(stack.sequencers[0].op_reth.container_name.as_str()));
+        assert!(!services.contains_key(stack.sequencers[1].consensus_container_name()));
+
+        let validator = &stack.validators[0];
+        let target = Recreatable::Validator { chain: 0, index: 0 };
+        assert_eq!(
+            services.get(validator.op_reth.container_name.as_str()),
+            Some(&target)
+        );
+        assert_eq!(
+            services.get(validator.consensus_container_name()),
+            Some(&target)
+        );
+        assert_eq!(
+            services.get(stack.op_batcher.container_name.as_str()),
+            Some(&Recreatable::Batcher { chain: 0 })
+        );
+
+        assert_eq!(
+            services.get("kupcake-1235-op-reth"),
+            Some(&Recreatable::Sequencer { chain: 1, index: 0 })
+        );
+        assert_eq!(
+            services.get("kupcake-1235-kona-node"),
+            Some(&Recreatable::Sequencer { chain: 1, index: 0 })
+        );
+        assert_eq!(
+            services.get("kupcake-1235-op-batcher"),
+            Some(&Recreatable::Batcher { chain: 1 })
+        );
+    }
+}
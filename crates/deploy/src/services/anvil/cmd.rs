@@ -2,6 +2,8 @@
 
 use std::path::Path;
 
+use crate::services::extra_args::merge_extra_args;
+
 /// Specifies how Anvil should load initial state.
 #[derive(Debug, Clone)]
 pub enum AnvilInitMode {
@@ -26,6 +28,9 @@ pub struct AnvilCmdBuilder {
     timestamp: Option<u64>,
     fork_block_number: Option<u64>,
     quiet: bool,
+    hardfork: Option<String>,
+    mnemonic: Option<String>,
+    derivation_path: Option<String>,
     extra_args: Vec<String>,
     no_mining: bool,
 }
@@ -44,6 +49,9 @@ impl AnvilCmdBuilder {
             timestamp: None,
             fork_block_number: None,
             quiet: false,
+            hardfork: None,
+            mnemonic: None,
+            derivation_path: None,
             extra_args: Vec::new(),
             no_mining: false,
         }
@@ -103,7 +111,27 @@ impl AnvilCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Set the EVM hardfork (`--hardfork`). None keeps Anvil's default.
+    pub fn hardfork(mut self, hardfork: Option<String>) -> Self {
+        self.hardfork = hardfork;
+        self
+    }
+
+    /// Set the mnemonic the dev accounts are generated from (`--mnemonic`).
+    /// None keeps Anvil's default mnemonic.
+    pub fn mnemonic(mut self, mnemonic: Option<String>) -> Self {
+        self.mnemonic = mnemonic;
+        self
+    }
+
+    /// Set the derivation path of the dev accounts (`--derivation-path`), to which Anvil
+    /// appends the account index. None keeps Anvil's default, `m/44'/60'/0'/0/`.
+    pub fn derivation_path(mut self, derivation_path: Option<String>) -> Self {
+        self.derivation_path = derivation_path;
+        self
+    }
+
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -179,13 +207,26 @@ impl AnvilCmdBuilder {
             cmd.push(config_out);
         }
 
+        if let Some(hardfork) = self.hardfork {
+            cmd.push("--hardfork".to_string());
+            cmd.push(hardfork);
+        }
+
+        if let Some(mnemonic) = self.mnemonic {
+            cmd.push("--mnemonic".to_string());
+            cmd.push(mnemonic);
+        }
+
+        if let Some(derivation_path) = self.derivation_path {
+            cmd.push("--derivation-path".to_string());
+            cmd.push(derivation_path);
+        }
+
         if self.quiet {
             cmd.push("--quiet".to_string());
         }
 
-        cmd.extend(self.extra_args);
-
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
@@ -244,4 +285,37 @@ mod tests {
         assert!(cmd.contains(&"/data/anvil.json".to_string()));
         assert!(!cmd.contains(&"--load-state".to_string()));
     }
+
+    #[test]
+    fn test_anvil_cmd_builder_hardfork() {
+        let cmd = AnvilCmdBuilder::new(900)
+            .hardfork(Some("cancun".to_string()))
+            .build();
+        let pos = cmd.iter().position(|a| a == "--hardfork").unwrap();
+        assert_eq!(cmd[pos + 1], "cancun");
+
+        let cmd = AnvilCmdBuilder::new(900).build();
+        assert!(!cmd.contains(&"--hardfork".to_string()));
+    }
+
+    #[test]
+    fn test_anvil_cmd_builder_mnemonic() {
+        let cmd = AnvilCmdBuilder::new(900)
+            .mnemonic(Some(
+                "test test test test test test test test test test test junk".to_string(),
+            ))
+            .derivation_path(Some("m/44'/60'/1'/0/".to_string()))
+            .build();
+        let pos = cmd.iter().position(|a| a == "--mnemonic").unwrap();
+        assert_eq!(
+            cmd[pos + 1],
+            "test test test test test test test test test test test junk"
+        );
+        let pos = cmd.iter().position(|a| a == "--derivation-path").unwrap();
+        assert_eq!(cmd[pos + 1], "m/44'/60'/1'/0/");
+
+        let cmd = AnvilCmdBuilder::new(900).build();
+        assert!(!cmd.contains(&"--mnemonic".to_string()));
+        assert!(!cmd.contains(&"--derivation-path".to_string()));
+    }
 }
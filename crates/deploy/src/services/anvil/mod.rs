@@ -2,7 +2,10 @@
 
 mod cmd;
 
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use anyhow::Context;
 use backon::{ConstantBuilder, Retryable};
@@ -13,7 +16,7 @@ pub use cmd::{AnvilCmdBuilder, AnvilInitMode};
 
 use crate::{
     AccountInfo,
-    docker::{DockerImage, ExposedPort, KupDocker, PortMapping, ServiceConfig},
+    docker::{ContainerState, DockerImage, ExposedPort, KupDocker, PortMapping, ServiceConfig},
     fs::FsHandler,
     metrics::ContainerDeployTimings,
     service::{self, KupcakeService},
@@ -40,6 +43,10 @@ pub struct AnvilInput {
 /// - Index 8: proposer
 /// - Index 9: challenger
 /// - Index 10+: extra_accounts
+///
+/// The additional L2 chains of a deployment submit with their own batcher, proposer and
+/// challenger accounts, taken from the end of the extra accounts (see
+/// [`AnvilAccounts::for_l2_chain`]).
 #[derive(Debug, Clone)]
 pub struct AnvilAccounts {
     /// The deployer account (index 0). Also used as base_fee_vault_recipient.
@@ -70,6 +77,84 @@ impl AnvilAccounts {
     /// The minimum number of accounts required for OP Stack deployment.
     pub const MIN_REQUIRED_ACCOUNTS: usize = 10;
 
+    /// Map a list of accounts to the OP Stack roles, by index (see the type docs).
+    ///
+    /// Returns an error if fewer than [`Self::MIN_REQUIRED_ACCOUNTS`] accounts are provided.
+    pub fn from_accounts(accounts: Vec<AccountInfo>) -> Result<Self, anyhow::Error> {
+        if accounts.len() < Self::MIN_REQUIRED_ACCOUNTS {
+            anyhow::bail!(
+                "Not enough accounts provided. Need at least {}, got {}",
+                Self::MIN_REQUIRED_ACCOUNTS,
+                accounts.len()
+            );
+        }
+
+        let mut iter = accounts.into_iter();
+
+        // Length was validated above, so these nexts are guaranteed to succeed.
+        let result = Self {
+            deployer: iter.next().context("missing deployer account")?,
+            l1_fee_vault_recipient: iter
+                .next()
+                .context("missing l1_fee_vault_recipient account")?,
+            sequencer_fee_vault_recipient: iter
+                .next()
+                .context("missing sequencer_fee_vault_recipient account")?,
+            l1_proxy_admin_owner: iter
+                .next()
+                .context("missing l1_proxy_admin_owner account")?,
+            l2_proxy_admin_owner: iter
+                .next()
+                .context("missing l2_proxy_admin_owner account")?,
+            system_config_owner: iter.next().context("missing system_config_owner account")?,
+            unsafe_block_signer: iter.next().context("missing unsafe_block_signer account")?,
+            batcher: iter.next().context("missing batcher account")?,
+            proposer: iter.next().context("missing proposer account")?,
+            challenger: iter.next().context("missing challenger account")?,
+            extra_accounts: iter.collect(),
+        };
+
+        Ok(result)
+    }
+
+    /// Extra accounts used as batcher, proposer and challenger by each additional L2 chain.
+    pub const SUBMITTER_ACCOUNTS_PER_CHAIN: usize = 3;
+
+    /// The accounts of the L2 chain at `index` in the deployment (0 being the primary one).
+    ///
+    /// Additional chains share the roles of the primary chain, except the batcher, proposer
+    /// and challenger: two batchers submitting with the same key would race for nonces. Chain
+    /// `index` takes its three submitters from the end of the extra accounts, so that they
+    /// don't overlap with the spare batchers and proposers, which are taken from the start.
+    pub fn for_l2_chain(&self, index: usize) -> Result<Self, anyhow::Error> {
+        if index == 0 {
+            return Ok(self.clone());
+        }
+
+        let end = self
+            .extra_accounts
+            .len()
+            .checked_sub(Self::SUBMITTER_ACCOUNTS_PER_CHAIN * (index - 1))
+            .filter(|end| *end >= Self::SUBMITTER_ACCOUNTS_PER_CHAIN)
+            .with_context(|| {
+                format!(
+                    "L2 chain {} needs {} extra L1 accounts, but only {} are available",
+                    index,
+                    Self::SUBMITTER_ACCOUNTS_PER_CHAIN * index,
+                    self.extra_accounts.len()
+                )
+            })?;
+        let [batcher, proposer, challenger] =
+            [end - 3, end - 2, end - 1].map(|i| self.extra_accounts[i].clone());
+
+        Ok(Self {
+            batcher,
+            proposer,
+            challenger,
+            ..self.clone()
+        })
+    }
+
     /// Returns all accounts as a slice, in order (named accounts first, then extra).
     pub fn all_accounts(&self) -> Vec<&AccountInfo> {
         let mut accounts = vec![
@@ -127,7 +212,7 @@ impl AnvilAccounts {
 pub const DEFAULT_PORT: u16 = 8545;
 
 /// Number of accounts Anvil generates from its HD mnemonic.
-/// Must match the count passed to `derive_accounts_from_mnemonic` in genesis mode.
+/// Must match the count derived by [`AnvilConfig::derive_accounts`].
 pub const DEFAULT_ACCOUNT_COUNT: usize = 30;
 
 /// Default Docker image for Anvil (Foundry).
@@ -135,6 +220,52 @@ pub const DEFAULT_DOCKER_IMAGE: &str = "ghcr.io/foundry-rs/foundry";
 /// Default Docker tag for Anvil (Foundry).
 pub const DEFAULT_DOCKER_TAG: &str = "latest";
 
+/// How often the state is dumped to `state.json` when [`AnvilConfig::auto_recover`] is set.
+///
+/// Blocks mined after the last dump are lost when Anvil crashes.
+pub const AUTO_RECOVER_DUMP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the Anvil container is checked when [`AnvilConfig::auto_recover`] is set.
+const AUTO_RECOVER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Name used in place of a container name when the L1 is an external node.
+pub const EXTERNAL_L1_NAME: &str = "external-l1";
+
+/// Values accepted by Anvil's `--hardfork`, oldest first.
+///
+/// Anvil exits right after starting when given any other value, so configurations are
+/// checked against this list before deploying.
+pub const KNOWN_HARDFORKS: &[&str] = &[
+    "frontier",
+    "homestead",
+    "dao",
+    "tangerine",
+    "spuriousdragon",
+    "byzantium",
+    "constantinople",
+    "petersburg",
+    "istanbul",
+    "muirglacier",
+    "berlin",
+    "london",
+    "arrowglacier",
+    "grayglacier",
+    "paris",
+    "merge",
+    "shanghai",
+    "cancun",
+    "prague",
+    "osaka",
+    "latest",
+];
+
+/// Whether `hardfork` is one of the [`KNOWN_HARDFORKS`] (case-insensitive).
+pub fn is_known_hardfork(hardfork: &str) -> bool {
+    KNOWN_HARDFORKS
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(hardfork.trim()))
+}
+
 /// Configuration for Anvil.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AnvilConfig {
@@ -161,9 +292,25 @@ pub struct AnvilConfig {
     /// Suppress non-essential Anvil output.
     #[serde(default)]
     pub quiet: bool,
+    /// L1 EVM hardfork passed to Anvil's `--hardfork` (e.g. "cancun", "prague").
+    /// None uses Anvil's default, the latest hardfork it supports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardfork: Option<String>,
+    /// Mnemonic Anvil generates its dev accounts from, and so the OP Stack role accounts
+    /// (see [`AnvilAccounts`]). None uses [`ANVIL_DEFAULT_MNEMONIC`](crate::ANVIL_DEFAULT_MNEMONIC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    /// HD derivation path of the dev accounts, without the account index.
+    /// None uses [`DEFAULT_DERIVATION_PATH`](crate::DEFAULT_DERIVATION_PATH).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
     /// Extra arguments to pass to Anvil.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
+    /// Recreate the Anvil container from its last state dump if it exits while kupcake
+    /// is attached (see [`AnvilHandler::watch_and_recover`]).
+    #[serde(default)]
+    pub auto_recover: bool,
 }
 
 impl Default for AnvilConfig {
@@ -179,7 +326,11 @@ impl Default for AnvilConfig {
             timestamp: None,
             fork_block_number: None,
             quiet: false,
+            hardfork: None,
+            mnemonic: None,
+            derivation_path: None,
             extra_args: Vec::new(),
+            auto_recover: false,
         }
     }
 }
@@ -194,16 +345,239 @@ pub struct AnvilHandler {
     pub l1_rpc_url: Url,
     /// The RPC URL accessible from host (if published). None if not published.
     pub l1_host_url: Option<Url>,
+    /// WebSocket URL of the L1 for services that follow it (external L1 only).
+    /// None to use `l1_rpc_url`.
+    pub l1_ws_url: Option<Url>,
     /// Named accounts from Anvil matching the OP Stack participant roles.
     pub accounts: AnvilAccounts,
     /// Deploy timings for metrics.
     pub deploy_timings: ContainerDeployTimings,
 }
 
+impl AnvilHandler {
+    /// A handler for the same L1 with the accounts of the L2 chain at `index` (see
+    /// [`AnvilAccounts::for_l2_chain`]).
+    pub fn for_l2_chain(&self, index: usize) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            container_id: self.container_id.clone(),
+            container_name: self.container_name.clone(),
+            l1_rpc_url: self.l1_rpc_url.clone(),
+            l1_host_url: self.l1_host_url.clone(),
+            l1_ws_url: self.l1_ws_url.clone(),
+            accounts: self.accounts.for_l2_chain(index)?,
+            deploy_timings: ContainerDeployTimings {
+                pull: std::time::Duration::ZERO,
+                setup: std::time::Duration::ZERO,
+            },
+        })
+    }
+
+    /// Whether this handler points at an external L1 node rather than an Anvil container.
+    pub fn is_external(&self) -> bool {
+        self.container_id.is_empty()
+    }
+
+    /// Replace the runtime bytecode of an L1 account via `anvil_setCode`.
+    ///
+    /// Useful to mock L1 contracts, e.g. on a forked L1, without redeploying.
+    /// `bytecode` is 0x-prefixed hex; an empty `0x` removes the code.
+    pub async fn set_code(&self, address: &str, bytecode: &str) -> Result<(), anyhow::Error> {
+        let address = parse_hex(address, "address", Some(20))?;
+        let bytecode = parse_hex(bytecode, "bytecode", None)?;
+        let url = self.anvil_rpc_url()?;
+
+        crate::rpc::anvil_set_code(url.as_str(), &address, &bytecode).await?;
+        tracing::info!(%address, code_size = (bytecode.len() - 2) / 2, "Replaced L1 contract code");
+        Ok(())
+    }
+
+    /// Write a storage slot of an L1 account via `anvil_setStorageAt`.
+    ///
+    /// `slot` and `value` are 0x-prefixed hex of at most 32 bytes and are left-padded
+    /// to a full word.
+    pub async fn set_storage_at(
+        &self,
+        address: &str,
+        slot: &str,
+        value: &str,
+    ) -> Result<(), anyhow::Error> {
+        let address = parse_hex(address, "address", Some(20))?;
+        let slot = parse_word(slot, "storage slot")?;
+        let value = parse_word(value, "storage value")?;
+        let url = self.anvil_rpc_url()?;
+
+        crate::rpc::anvil_set_storage_at(url.as_str(), &address, &slot, &value).await?;
+        tracing::info!(%address, %slot, %value, "Overrode L1 storage slot");
+        Ok(())
+    }
+
+    /// Keep Anvil alive for as long as the returned future is polled.
+    ///
+    /// The state is dumped to `state.json` under `host_config_path` every
+    /// [`AUTO_RECOVER_DUMP_INTERVAL`]. When the container exits, it is recreated under
+    /// the same name and host port from the last dump, so L2 services, which address Anvil
+    /// by container name, resume without being restarted. Blocks mined after the last dump
+    /// are lost, which the L2 nodes see as an L1 reorg.
+    ///
+    /// Only returns on a failed recovery.
+    pub async fn watch_and_recover(
+        &mut self,
+        config: &AnvilConfig,
+        docker: &mut KupDocker,
+        host_config_path: &Path,
+        chain_id: u64,
+    ) -> Result<(), anyhow::Error> {
+        let Some(host_port) = self.l1_host_url.as_ref().and_then(Url::port) else {
+            tracing::warn!(
+                "Anvil auto-recovery requires the Anvil RPC to be published to the host"
+            );
+            return std::future::pending().await;
+        };
+        let state_path = host_config_path.join("state.json");
+        let mut last_dump: Option<Instant> = None;
+
+        let mut poll = tokio::time::interval(AUTO_RECOVER_POLL_INTERVAL);
+        // A recovery takes longer than the interval; don't poll in a burst after it.
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            poll.tick().await;
+            match docker.get_container_state(&self.container_name).await {
+                ContainerState::Running => {
+                    if last_dump.is_none_or(|at| at.elapsed() >= AUTO_RECOVER_DUMP_INTERVAL) {
+                        let url = self.anvil_rpc_url()?.to_string();
+                        match crate::rpc::anvil_dump_state(&url, &state_path).await {
+                            Ok(()) => last_dump = Some(Instant::now()),
+                            Err(e) => {
+                                tracing::warn!(error = %e, "Periodic Anvil state dump failed")
+                            }
+                        }
+                    }
+                }
+                ContainerState::Stopped | ContainerState::NotFound => {
+                    if !state_path.exists() {
+                        anyhow::bail!(
+                            "Anvil container {} exited before its state could be dumped",
+                            self.container_name
+                        );
+                    }
+                    tracing::warn!(
+                        container_name = %self.container_name,
+                        "Anvil container exited, recreating it from the last state dump..."
+                    );
+
+                    docker.stop_and_remove_container(&self.container_id).await?;
+
+                    // Pin the host port so that host URLs handed out stay valid.
+                    let config = AnvilConfig {
+                        host_port: Some(host_port),
+                        ..config.clone()
+                    };
+                    let input = AnvilInput {
+                        chain_id,
+                        init_mode: Some(AnvilInitMode::LoadState("/data/state.json".to_string())),
+                        accounts: self.accounts.clone(),
+                    };
+                    *self = config
+                        .deploy(docker, host_config_path, input)
+                        .await
+                        .context("Failed to recover Anvil from its state dump")?;
+                    last_dump = Some(Instant::now());
+
+                    tracing::info!(
+                        container_name = %self.container_name,
+                        "Anvil recovered from its last state dump"
+                    );
+                }
+                ContainerState::Paused | ContainerState::Restarting => {}
+            }
+        }
+    }
+
+    /// Host RPC URL for `anvil_*` methods, which only an Anvil container supports.
+    fn anvil_rpc_url(&self) -> Result<&Url, anyhow::Error> {
+        if self.is_external() {
+            anyhow::bail!("anvil_* RPC methods are not available on an external L1");
+        }
+        self.l1_host_url
+            .as_ref()
+            .context("Anvil RPC port is not published to the host")
+    }
+}
+
+/// Validate a 0x-prefixed hex string and return it lowercased.
+///
+/// When `len` is set, the value must be exactly that many bytes.
+fn parse_hex(value: &str, what: &str, len: Option<usize>) -> Result<String, anyhow::Error> {
+    let digits = value
+        .strip_prefix("0x")
+        .with_context(|| format!("Invalid {what} '{value}': expected 0x-prefixed hex"))?;
+
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid {what} '{value}': contains non-hex characters");
+    }
+    if digits.len() % 2 != 0 {
+        anyhow::bail!("Invalid {what} '{value}': odd number of hex digits");
+    }
+    if let Some(len) = len
+        && digits.len() != len * 2
+    {
+        anyhow::bail!(
+            "Invalid {what} '{value}': expected {len} bytes, got {}",
+            digits.len() / 2
+        );
+    }
+
+    Ok(format!("0x{}", digits.to_lowercase()))
+}
+
+/// Validate a hex value of at most 32 bytes and left-pad it to a full word.
+fn parse_word(value: &str, what: &str) -> Result<String, anyhow::Error> {
+    let digits = value
+        .strip_prefix("0x")
+        .with_context(|| format!("Invalid {what} '{value}': expected 0x-prefixed hex"))?;
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid {what} '{value}': expected hex digits");
+    }
+    if digits.len() > 64 {
+        anyhow::bail!("Invalid {what} '{value}': longer than 32 bytes");
+    }
+
+    Ok(format!("0x{:0>64}", digits.to_lowercase()))
+}
+
 /// Anvil listens on port 8545 inside the container.
 const ANVIL_INTERNAL_PORT: u16 = 8545;
 
 impl AnvilConfig {
+    /// Derivation path of the dev accounts, ending with the `/` Anvil appends the
+    /// account index to.
+    fn derivation_path_prefix(&self) -> Option<String> {
+        self.derivation_path.as_ref().map(|path| {
+            if path.ends_with('/') {
+                path.clone()
+            } else {
+                format!("{}/", path)
+            }
+        })
+    }
+
+    /// Derive the accounts Anvil generates from the configured mnemonic and derivation
+    /// path, mapped to the OP Stack roles.
+    pub fn derive_accounts(&self) -> Result<AnvilAccounts, anyhow::Error> {
+        let account_infos = crate::derive_accounts_from_mnemonic_with_path(
+            self.mnemonic
+                .as_deref()
+                .unwrap_or(crate::ANVIL_DEFAULT_MNEMONIC),
+            self.derivation_path_prefix()
+                .as_deref()
+                .unwrap_or(crate::DEFAULT_DERIVATION_PATH),
+            DEFAULT_ACCOUNT_COUNT,
+        )
+        .context("Failed to derive accounts from mnemonic")?;
+        AnvilAccounts::from_accounts(account_infos).context("Failed to create named accounts")
+    }
+
     /// Build the Docker command arguments for Anvil.
     pub fn build_cmd(
         &self,
@@ -217,6 +591,9 @@ impl AnvilConfig {
             .timestamp(self.timestamp)
             .fork_block_number(self.fork_block_number)
             .quiet(self.quiet)
+            .hardfork(self.hardfork.clone())
+            .mnemonic(self.mnemonic.clone())
+            .derivation_path(self.derivation_path_prefix())
             .extra_args(self.extra_args.clone());
 
         if let Some(ref mode) = input.init_mode {
@@ -252,6 +629,17 @@ impl KupcakeService for AnvilConfig {
         host_config_path: &'a Path,
         input: AnvilInput,
     ) -> Result<AnvilHandler, anyhow::Error> {
+        // Anvil exits right after start on an unknown hardfork; fail with a clear message.
+        if let Some(ref hardfork) = self.hardfork
+            && !is_known_hardfork(hardfork)
+        {
+            anyhow::bail!(
+                "Unsupported Anvil hardfork '{}'. Expected one of: {}",
+                hardfork,
+                KNOWN_HARDFORKS.join(", ")
+            );
+        }
+
         if !host_config_path.exists() {
             FsHandler::create_host_config_directory(&host_config_path.to_path_buf())?;
         }
@@ -271,7 +659,8 @@ impl KupcakeService for AnvilConfig {
             .cmd(cmd)
             .expose(ExposedPort::tcp(ANVIL_INTERNAL_PORT))
             .ports(port_mappings)
-            .bind(host_config_path, &container_config_path, "rw");
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?);
 
         let (mut handler, timings) = service::deploy_container(
             docker,
@@ -362,7 +751,127 @@ impl KupcakeService for AnvilConfig {
             accounts: input.accounts,
             l1_rpc_url,
             l1_host_url,
+            l1_ws_url: None,
             deploy_timings: timings,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ANVIL_DEFAULT_MNEMONIC, derive_accounts_from_mnemonic};
+
+    #[test]
+    fn test_from_accounts_maps_roles_by_index() {
+        let infos = derive_accounts_from_mnemonic(ANVIL_DEFAULT_MNEMONIC, 12).unwrap();
+        let accounts = AnvilAccounts::from_accounts(infos.clone()).unwrap();
+
+        assert_eq!(accounts.deployer.address, infos[0].address);
+        assert_eq!(accounts.batcher.address, infos[7].address);
+        assert_eq!(accounts.challenger.address, infos[9].address);
+        assert_eq!(accounts.extra_accounts.len(), 2);
+        assert_eq!(accounts.all_accounts().len(), infos.len());
+    }
+
+    #[test]
+    fn test_for_l2_chain_uses_distinct_submitters() {
+        let accounts = AnvilConfig::default().derive_accounts().unwrap();
+        assert_eq!(
+            accounts.for_l2_chain(0).unwrap().batcher.address,
+            accounts.batcher.address
+        );
+
+        let last = accounts.extra_accounts.len() - 1;
+        let chain_1 = accounts.for_l2_chain(1).unwrap();
+        assert_eq!(
+            chain_1.challenger.address,
+            accounts.extra_accounts[last].address
+        );
+        assert_eq!(
+            chain_1.batcher.address,
+            accounts.extra_accounts[last - 2].address
+        );
+        assert_eq!(chain_1.deployer.address, accounts.deployer.address);
+
+        let chain_2 = accounts.for_l2_chain(2).unwrap();
+        assert_eq!(
+            chain_2.challenger.address,
+            accounts.extra_accounts[last - 3].address
+        );
+
+        let too_many = accounts.extra_accounts.len() / 3 + 1;
+        assert!(accounts.for_l2_chain(too_many).is_err());
+    }
+
+    #[test]
+    fn test_derive_accounts_from_custom_mnemonic() {
+        let default = AnvilConfig::default().derive_accounts().unwrap();
+        assert_eq!(
+            hex::encode(&default.deployer.address),
+            "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+
+        let config = AnvilConfig {
+            mnemonic: Some(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+                    .to_string(),
+            ),
+            derivation_path: Some("m/44'/60'/0'/0".to_string()),
+            ..Default::default()
+        };
+        let accounts = config.derive_accounts().unwrap();
+        assert_ne!(accounts.batcher.address, default.batcher.address);
+        assert_eq!(
+            accounts.batcher.address,
+            config.derive_accounts().unwrap().batcher.address
+        );
+
+        let cmd = config
+            .build_cmd(
+                Path::new("/tmp"),
+                &AnvilInput {
+                    chain_id: 900,
+                    init_mode: None,
+                    accounts,
+                },
+            )
+            .unwrap();
+        let pos = cmd.iter().position(|a| a == "--derivation-path").unwrap();
+        assert_eq!(cmd[pos + 1], "m/44'/60'/0'/0/");
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(
+            parse_hex("0xABCD", "bytecode", None).unwrap(),
+            "0xabcd".to_string()
+        );
+        assert_eq!(parse_hex("0x", "bytecode", None).unwrap(), "0x");
+        assert!(parse_hex("abcd", "bytecode", None).is_err());
+        assert!(parse_hex("0xabc", "bytecode", None).is_err());
+        assert!(parse_hex("0xzz", "bytecode", None).is_err());
+        assert!(parse_hex("0x1234", "address", Some(20)).is_err());
+        assert!(parse_hex(&format!("0x{}", "11".repeat(20)), "address", Some(20)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_word_pads_to_32_bytes() {
+        assert_eq!(
+            parse_word("0x1", "slot").unwrap(),
+            format!("0x{}1", "0".repeat(63))
+        );
+        assert!(parse_word("0x", "slot").is_err());
+        assert!(parse_word(&format!("0x{}", "f".repeat(65)), "slot").is_err());
+    }
+
+    #[test]
+    fn test_from_accounts_requires_min_accounts() {
+        let infos = derive_accounts_from_mnemonic(
+            ANVIL_DEFAULT_MNEMONIC,
+            AnvilAccounts::MIN_REQUIRED_ACCOUNTS - 1,
+        )
+        .unwrap();
+        assert!(AnvilAccounts::from_accounts(infos).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! Merging of user-provided `extra_args` into the commands built by the cmd builders.
+//!
+//! A flag in `extra_args` that the cmd builder already sets overrides it: the managed
+//! occurrence (and its value) is removed before `extra_args` are appended, so the service
+//! never sees the flag twice. Only long flags (`--name` or `--name=value`) are matched;
+//! short flags and positional arguments are appended as-is.
+
+/// Flags the cmd builders set without a value.
+///
+/// Any other managed flag without an inline `=value` is followed by its value.
+const BOOLEAN_FLAGS: &[&str] = &[
+    // anvil
+    "quiet",
+    // op-reth
+    "http",
+    "ws",
+    "disable-discovery",
+    "enable-discv5-discovery",
+    "disable-discv4-discovery",
+    "flashblocks.enabled",
+    "proofs-history",
+    "engine.stateless",
+    "full",
+    "prune.senderrecovery.full",
+    "prune.transactionlookup.full",
+    // kona-node and op-node
+    "metrics.enabled",
+    "p2p.no-discovery",
+    "sequencer.enabled",
+    "sequencer.stopped",
+    "conductor.enabled",
+    "flashblocks",
+    "l1.trustrpc",
+    "altda.enabled",
+    // op-batcher, op-proposer, op-supervisor and op-conductor
+    "rpc.enable-admin",
+    "stopped",
+    "raft.bootstrap",
+    "paused",
+];
+
+/// Name of a long flag, without its inline `=value`. None for short flags and values.
+fn flag_name(arg: &str) -> Option<&str> {
+    let flag = arg.strip_prefix("--")?;
+    let name = flag.split_once('=').map_or(flag, |(name, _)| name);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Append `extra_args` to the `managed` command, removing the managed flags they override.
+///
+/// A managed flag without an inline value takes the next argument as its value, unless it
+/// is one of [`BOOLEAN_FLAGS`] or the next argument is a flag.
+pub(crate) fn merge_extra_args(managed: Vec<String>, extra_args: Vec<String>) -> Vec<String> {
+    let overridden: Vec<&str> = extra_args.iter().filter_map(|arg| flag_name(arg)).collect();
+
+    let mut cmd = Vec::with_capacity(managed.len() + extra_args.len());
+    let mut args = managed.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(name) = flag_name(&arg).filter(|name| overridden.contains(name)) else {
+            cmd.push(arg);
+            continue;
+        };
+        tracing::warn!(
+            flag = %format!("--{}", name),
+            "extra_args overrides a flag set by kupcake"
+        );
+        if !arg.contains('=') && !BOOLEAN_FLAGS.contains(&name) {
+            args.next_if(|value| !value.starts_with('-'));
+        }
+    }
+
+    cmd.extend(extra_args);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_flag_name() {
+        assert_eq!(flag_name("--http.port"), Some("http.port"));
+        assert_eq!(flag_name("--nat=extaddr:foo"), Some("nat"));
+        assert_eq!(flag_name("-vvvv"), None);
+        assert_eq!(flag_name("8545"), None);
+        assert_eq!(flag_name("--"), None);
+    }
+
+    #[test]
+    fn test_merge_extra_args_overrides_managed_flags() {
+        let managed = args(&[
+            "node",
+            "--http",
+            "--http.port",
+            "8545",
+            "--nat=extaddr:op-reth",
+            "--disable-discovery",
+            "--rpc.port",
+            "9545",
+        ]);
+        let extra = args(&[
+            "--http.port=9000",
+            "--nat",
+            "none",
+            "--disable-discovery",
+            "--txpool.nolocals",
+        ]);
+
+        assert_eq!(
+            merge_extra_args(managed, extra),
+            args(&[
+                "node",
+                "--http",
+                "--rpc.port",
+                "9545",
+                "--http.port=9000",
+                "--nat",
+                "none",
+                "--disable-discovery",
+                "--txpool.nolocals",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_extra_args_keeps_positional_after_boolean_flag() {
+        // kona-node sets its metrics flags before the `node` subcommand.
+        let managed = args(&["--metrics.enabled", "node", "--mode", "validator"]);
+        let extra = args(&["--metrics.enabled"]);
+        assert_eq!(
+            merge_extra_args(managed, extra),
+            args(&["node", "--mode", "validator", "--metrics.enabled"])
+        );
+    }
+
+    #[test]
+    fn test_merge_extra_args_without_conflicts() {
+        let managed = args(&["--port", "8545", "-j", "0"]);
+        let extra = args(&["-j", "4", "--quiet"]);
+        assert_eq!(
+            merge_extra_args(managed, extra),
+            args(&["--port", "8545", "-j", "0", "-j", "4", "--quiet"])
+        );
+    }
+}
@@ -1,9 +1,11 @@
-//! Grafana and Prometheus deployment for metrics collection and visualization.
+//! Grafana and Prometheus deployment for metrics collection and visualization, with
+//! optional Loki + Promtail log aggregation.
 
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use url::Url;
 
 use crate::{
@@ -14,9 +16,10 @@ use crate::{
     fs::FsHandler,
 };
 
-/// Default ports for monitoring components.
+/// Default container ports for monitoring components.
 pub const DEFAULT_PROMETHEUS_PORT: u16 = 9099;
-pub const DEFAULT_GRAFANA_PORT: u16 = 3019;
+pub const DEFAULT_GRAFANA_PORT: u16 = 3000;
+pub const DEFAULT_LOKI_PORT: u16 = 3100;
 
 /// Configuration for Prometheus.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -81,7 +84,8 @@ pub struct GrafanaConfig {
     /// Host for the Grafana server.
     pub host: String,
 
-    /// Port for the Grafana server (container port).
+    /// Port for the Grafana server (container port). Grafana is configured to listen on
+    /// it; use `host_port` to choose where it is published on the host.
     pub port: u16,
 
     /// Host port for Grafana. If None, not published to host. If Some(0), OS picks port.
@@ -117,6 +121,107 @@ impl Default for GrafanaConfig {
     }
 }
 
+impl GrafanaConfig {
+    /// Container configuration of Grafana, listening on `port` and published on
+    /// `host_port`.
+    fn service_config(&self, provisioning_path: &Path) -> ServiceConfig {
+        let env = vec![
+            format!("GF_SERVER_HTTP_PORT={}", self.port),
+            format!("GF_SECURITY_ADMIN_USER={}", self.admin_user),
+            format!("GF_SECURITY_ADMIN_PASSWORD={}", self.admin_password),
+            "GF_USERS_ALLOW_SIGN_UP=false".to_string(),
+            "GF_AUTH_ANONYMOUS_ENABLED=true".to_string(),
+            "GF_AUTH_ANONYMOUS_ORG_ROLE=Viewer".to_string(),
+        ];
+
+        ServiceConfig::new(self.docker_image.clone())
+            .ports(PortMapping::tcp_optional(self.port, self.host_port))
+            .expose(ExposedPort::tcp(self.port))
+            .bind_str(format!(
+                "{}:/etc/grafana/provisioning:ro",
+                provisioning_path.display()
+            ))
+            .env(env)
+    }
+}
+
+/// Configuration for Loki.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LokiConfig {
+    /// Docker image configuration for Loki.
+    pub docker_image: DockerImage,
+
+    /// Container name for Loki.
+    pub container_name: String,
+
+    /// Port for the Loki HTTP API (container port).
+    pub port: u16,
+
+    /// Host port for Loki. If None, not published to host. If Some(0), OS picks port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_port: Option<u16>,
+}
+
+/// Default Docker image for Loki.
+pub const DEFAULT_LOKI_DOCKER_IMAGE: &str = "grafana/loki";
+/// Default Docker tag for Loki.
+pub const DEFAULT_LOKI_DOCKER_TAG: &str = "latest";
+
+impl Default for LokiConfig {
+    fn default() -> Self {
+        Self {
+            docker_image: DockerImage::new(DEFAULT_LOKI_DOCKER_IMAGE, DEFAULT_LOKI_DOCKER_TAG),
+            container_name: "kupcake-loki".to_string(),
+            port: DEFAULT_LOKI_PORT,
+            host_port: Some(0), // Let OS pick an available port
+        }
+    }
+}
+
+/// Configuration for Promtail, which ships the logs of the network's containers to Loki.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PromtailConfig {
+    /// Docker image configuration for Promtail.
+    pub docker_image: DockerImage,
+
+    /// Container name for Promtail.
+    pub container_name: String,
+
+    /// Path of the Docker socket on the host, used to discover containers and read their logs.
+    pub docker_socket: PathBuf,
+}
+
+/// Default Docker image for Promtail.
+pub const DEFAULT_PROMTAIL_DOCKER_IMAGE: &str = "grafana/promtail";
+/// Default Docker tag for Promtail.
+pub const DEFAULT_PROMTAIL_DOCKER_TAG: &str = "latest";
+
+impl Default for PromtailConfig {
+    fn default() -> Self {
+        Self {
+            docker_image: DockerImage::new(
+                DEFAULT_PROMTAIL_DOCKER_IMAGE,
+                DEFAULT_PROMTAIL_DOCKER_TAG,
+            ),
+            container_name: "kupcake-promtail".to_string(),
+            docker_socket: PathBuf::from("/var/run/docker.sock"),
+        }
+    }
+}
+
+/// Configuration for log aggregation (Loki + Promtail). Disabled by default.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LogsConfig {
+    /// Configuration for Loki.
+    pub loki: LokiConfig,
+
+    /// Configuration for Promtail.
+    pub promtail: PromtailConfig,
+
+    /// Whether log aggregation is enabled. Requires monitoring to be enabled.
+    pub enabled: bool,
+}
+
 /// Combined configuration for monitoring stack.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MonitoringConfig {
@@ -128,6 +233,10 @@ pub struct MonitoringConfig {
 
     /// Whether monitoring is enabled.
     pub enabled: bool,
+
+    /// Configuration for log aggregation, provisioned as a Grafana datasource.
+    #[serde(default)]
+    pub logs: LogsConfig,
 }
 
 impl Default for MonitoringConfig {
@@ -136,6 +245,7 @@ impl Default for MonitoringConfig {
             prometheus: PrometheusConfig::default(),
             grafana: GrafanaConfig::default(),
             enabled: true,
+            logs: LogsConfig::default(),
         }
     }
 }
@@ -162,12 +272,219 @@ pub struct GrafanaHandler {
 
     /// The URL accessible from host (if published). None if not published.
     pub host_url: Option<Url>,
+
+    /// Admin username, used for API calls.
+    pub admin_user: String,
+
+    /// Admin password, used for API calls.
+    pub admin_password: String,
+}
+
+/// Dashboard created or updated by [`GrafanaHandler::import_dashboard`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ImportedDashboard {
+    /// UID of the dashboard.
+    pub uid: String,
+    /// Path of the dashboard page, relative to the Grafana URL (e.g. `/d/<uid>/<slug>`).
+    pub url: String,
+    /// Version of the dashboard after the import.
+    pub version: u64,
+}
+
+/// Body of a `POST /api/dashboards/db` request importing `dashboard`.
+///
+/// Accepts both a bare dashboard model and a `{"dashboard": ...}` wrapper (as returned by
+/// `GET /api/dashboards/uid/<uid>`). The numeric `id` is dropped so that the dashboard is
+/// matched by UID, and an existing dashboard with the same UID is overwritten.
+fn import_request(dashboard: Value) -> Value {
+    let mut dashboard = match dashboard {
+        Value::Object(mut wrapper) if wrapper.contains_key("dashboard") => {
+            wrapper.remove("dashboard").unwrap_or_default()
+        }
+        dashboard => dashboard,
+    };
+    if let Some(model) = dashboard.as_object_mut() {
+        model.insert("id".to_string(), Value::Null);
+    }
+    serde_json::json!({ "dashboard": dashboard, "overwrite": true })
+}
+
+/// UID of the built-in dashboard linked by [`MonitoringHandler::default_dashboard_url`]
+/// (`grafana/dashboards/reth-overview.json`).
+pub const DEFAULT_DASHBOARD_UID: &str = "2k8BXz24x";
+
+impl GrafanaHandler {
+    /// Host URL of a dashboard showing the time range `from`..`to`.
+    ///
+    /// `from` and `to` take any Grafana time expression: relative (`now-1h`, `now`) or
+    /// epoch milliseconds. Returns None if Grafana is not published to the host.
+    pub fn dashboard_url(&self, uid: &str, from: &str, to: &str) -> Option<Url> {
+        let mut url = self.host_url.clone()?;
+        url.path_segments_mut()
+            .ok()?
+            .pop_if_empty()
+            .push("d")
+            .push(uid);
+        url.query_pairs_mut()
+            .append_pair("from", from)
+            .append_pair("to", to);
+        Some(url)
+    }
+
+    /// Create or update a dashboard through the Grafana API (`POST /api/dashboards/db`).
+    ///
+    /// Unlike provisioned dashboards, which Grafana only reads at startup, the imported
+    /// dashboard is visible immediately. A dashboard with the same UID is overwritten.
+    /// Uses the host URL when Grafana is published, the internal URL otherwise.
+    pub async fn import_dashboard(
+        &self,
+        dashboard: Value,
+    ) -> Result<ImportedDashboard, anyhow::Error> {
+        let url = self
+            .host_url
+            .as_ref()
+            .unwrap_or(&self.url)
+            .join("api/dashboards/db")
+            .context("Failed to build Grafana dashboards URL")?;
+
+        let response = crate::rpc::create_client()?
+            .post(url)
+            .basic_auth(&self.admin_user, Some(&self.admin_password))
+            .json(&import_request(dashboard))
+            .send()
+            .await
+            .context("Failed to send dashboard to Grafana")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Grafana rejected the dashboard ({}): {}", status, body);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse Grafana dashboard import response")
+    }
+}
+
+/// Handler for Loki.
+pub struct LokiHandler {
+    pub container_id: String,
+    pub container_name: String,
+
+    /// The URL for the Loki HTTP API (internal Docker network).
+    pub url: Url,
+
+    /// The URL accessible from host (if published). None if not published.
+    pub host_url: Option<Url>,
+}
+
+/// Handler for Promtail.
+pub struct PromtailHandler {
+    pub container_id: String,
+    pub container_name: String,
+}
+
+/// Handlers for log aggregation.
+pub struct LogsHandler {
+    pub loki: LokiHandler,
+    pub promtail: PromtailHandler,
 }
 
 /// Handler for the complete monitoring stack.
 pub struct MonitoringHandler {
     pub prometheus: PrometheusHandler,
     pub grafana: GrafanaHandler,
+    /// Loki and Promtail. None when log aggregation is disabled.
+    pub logs: Option<LogsHandler>,
+}
+
+impl MonitoringHandler {
+    /// Host URL of the built-in Reth dashboard over the last 15 minutes, refreshed live.
+    ///
+    /// Returns None if Grafana is not published to the host.
+    pub fn default_dashboard_url(&self) -> Option<Url> {
+        let mut url = self
+            .grafana
+            .dashboard_url(DEFAULT_DASHBOARD_UID, "now-15m", "now")?;
+        url.query_pairs_mut().append_pair("refresh", "5s");
+        Some(url)
+    }
+
+    /// Query Prometheus `/api/v1/targets` and return the scrape state of every active target.
+    ///
+    /// Uses the host URL when Prometheus is published, the internal URL otherwise.
+    /// Useful to assert that services are actually scraped, e.g. to catch a wrong metrics port.
+    pub async fn up_targets(&self) -> Result<Vec<TargetStatus>, anyhow::Error> {
+        let base_url = self
+            .prometheus
+            .host_url
+            .as_ref()
+            .unwrap_or(&self.prometheus.url);
+        let url = base_url
+            .join("api/v1/targets?state=active")
+            .context("Failed to build Prometheus targets URL")?;
+
+        let response: Value = crate::rpc::create_client()?
+            .get(url)
+            .send()
+            .await
+            .context("Failed to query Prometheus targets")?
+            .error_for_status()
+            .context("Prometheus targets request failed")?
+            .json()
+            .await
+            .context("Failed to parse Prometheus targets response")?;
+
+        Ok(TargetStatus::from_targets_response(&response))
+    }
+}
+
+/// Scrape state of a Prometheus target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetStatus {
+    pub job_name: String,
+    /// Scraped address (`<container>:<port>`).
+    pub instance: String,
+    /// Whether the last scrape succeeded.
+    pub up: bool,
+    /// Error of the last scrape, if it failed.
+    pub last_error: Option<String>,
+}
+
+impl TargetStatus {
+    /// Parse the active targets of a Prometheus `/api/v1/targets` response.
+    pub(crate) fn from_targets_response(response: &Value) -> Vec<Self> {
+        let active = response
+            .pointer("/data/activeTargets")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        active
+            .iter()
+            .filter_map(|target| {
+                let label = |key: &str| {
+                    target
+                        .pointer(&format!("/labels/{key}"))
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                };
+
+                Some(Self {
+                    job_name: label("job")?,
+                    instance: label("instance").unwrap_or_default(),
+                    up: target.get("health").and_then(|v| v.as_str()) == Some("up"),
+                    last_error: target
+                        .get("lastError")
+                        .and_then(|v| v.as_str())
+                        .filter(|e| !e.is_empty())
+                        .map(str::to_string),
+                })
+            })
+            .collect()
+    }
 }
 
 /// Metrics target for Prometheus scraping.
@@ -246,12 +563,13 @@ scrape_configs:{}"#,
         Ok(config_path)
     }
 
-    /// Generate the Grafana datasource configuration.
+    /// Generate the Grafana datasource configuration: Prometheus, plus Loki when log
+    /// aggregation is enabled.
     async fn generate_grafana_datasource(
         &self,
         host_config_path: &Path,
     ) -> Result<PathBuf, anyhow::Error> {
-        let datasource_content = format!(
+        let mut datasource_content = format!(
             r#"apiVersion: 1
 
 datasources:
@@ -266,6 +584,20 @@ datasources:
       httpMethod: 'POST'"#,
             self.prometheus.container_name, self.prometheus.port, self.prometheus.scrape_interval
         );
+        if self.logs.enabled {
+            datasource_content.push_str(&format!(
+                r#"
+
+  - name: Loki
+    type: loki
+    access: proxy
+    url: http://{}:{}
+    editable: true
+    jsonData:
+      maxLines: 1000"#,
+                self.logs.loki.container_name, self.logs.loki.port
+            ));
+        }
 
         let datasources_dir = host_config_path.join("grafana/provisioning/datasources");
         tokio::fs::create_dir_all(&datasources_dir)
@@ -421,35 +753,11 @@ providers:
         docker: &mut KupDocker,
         host_config_path: &Path,
     ) -> Result<GrafanaHandler, anyhow::Error> {
-        // Grafana listens on port 3000 inside the container by default
-        const GRAFANA_INTERNAL_PORT: u16 = 3000;
-
-        let grafana_provisioning_path = host_config_path.join("grafana/provisioning");
-
-        let env = vec![
-            format!("GF_SECURITY_ADMIN_USER={}", self.grafana.admin_user),
-            format!("GF_SECURITY_ADMIN_PASSWORD={}", self.grafana.admin_password),
-            "GF_USERS_ALLOW_SIGN_UP=false".to_string(),
-            "GF_AUTH_ANONYMOUS_ENABLED=true".to_string(),
-            "GF_AUTH_ANONYMOUS_ORG_ROLE=Viewer".to_string(),
-        ];
-
         self.grafana.docker_image.pull(docker).await?;
 
-        // Build port mappings only for ports that should be published to host
-        let port_mappings: Vec<PortMapping> =
-            PortMapping::tcp_optional(GRAFANA_INTERNAL_PORT, self.grafana.host_port)
-                .into_iter()
-                .collect();
-
-        let service_config = ServiceConfig::new(self.grafana.docker_image.clone())
-            .ports(port_mappings)
-            .expose(ExposedPort::tcp(GRAFANA_INTERNAL_PORT))
-            .bind_str(format!(
-                "{}:/etc/grafana/provisioning:ro",
-                grafana_provisioning_path.display()
-            ))
-            .env(env);
+        let service_config = self
+            .grafana
+            .service_config(&host_config_path.join("grafana/provisioning"));
 
         let handler = docker
             .start_service(
@@ -461,13 +769,11 @@ providers:
             .context("Failed to start Grafana container")?;
 
         // Build internal Docker network URL
-        let url = KupDocker::build_http_url(&handler.container_name, GRAFANA_INTERNAL_PORT)?;
+        let url = KupDocker::build_http_url(&handler.container_name, self.grafana.port)?;
 
         // Build host-accessible URL from bound port
         let host_url = handler
-            .get_tcp_host_port(GRAFANA_INTERNAL_PORT)
-            .map(|port| Url::parse(&format!("http://localhost:{}/", port)))
-            .transpose()
+            .build_host_url(self.grafana.port, "http")
             .context("Failed to build Grafana host URL")?;
 
         tracing::info!(
@@ -482,6 +788,231 @@ providers:
             container_name: handler.container_name,
             url,
             host_url,
+            admin_user: self.grafana.admin_user.clone(),
+            admin_password: self.grafana.admin_password.clone(),
+        })
+    }
+
+    /// Handler of the Grafana container of an already deployed network.
+    ///
+    /// Fails if the container is not running.
+    pub async fn running_grafana(&self) -> Result<GrafanaHandler, anyhow::Error> {
+        let docker = bollard::Docker::connect_with_local_defaults()
+            .context("Failed to connect to Docker daemon")?;
+        let name = &self.grafana.container_name;
+        let inspect = docker
+            .inspect_container(name, None)
+            .await
+            .with_context(|| format!("Grafana container '{}' not found", name))?;
+        if !inspect
+            .state
+            .and_then(|state| state.running)
+            .unwrap_or(false)
+        {
+            anyhow::bail!("Grafana container '{}' is not running", name);
+        }
+
+        let host_url = crate::health::build_host_rpc_url(&docker, name, self.grafana.port)
+            .await
+            .map(|url| Url::parse(&url))
+            .transpose()
+            .context("Failed to parse Grafana host URL")?;
+
+        Ok(GrafanaHandler {
+            container_id: inspect.id.unwrap_or_default(),
+            container_name: name.clone(),
+            url: KupDocker::build_http_url(name, self.grafana.port)?,
+            host_url,
+            admin_user: self.grafana.admin_user.clone(),
+            admin_password: self.grafana.admin_password.clone(),
+        })
+    }
+
+    /// Generate the Loki configuration file: a single-process Loki storing chunks and
+    /// index on the local filesystem.
+    async fn generate_loki_config(
+        &self,
+        host_config_path: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        let config_content = format!(
+            r#"# Loki configuration for kupcake OP Stack
+
+auth_enabled: false
+
+server:
+  http_listen_port: {}
+
+common:
+  path_prefix: /loki
+  replication_factor: 1
+  storage:
+    filesystem:
+      chunks_directory: /loki/chunks
+      rules_directory: /loki/rules
+  ring:
+    kvstore:
+      store: inmemory
+
+schema_config:
+  configs:
+    - from: 2024-01-01
+      store: tsdb
+      object_store: filesystem
+      schema: v13
+      index:
+        prefix: index_
+        period: 24h
+"#,
+            self.logs.loki.port
+        );
+
+        let config_path = host_config_path.join("loki.yml");
+        tokio::fs::write(&config_path, config_content)
+            .await
+            .context("Failed to write Loki config file")?;
+
+        tracing::debug!(path = ?config_path, "Loki config written");
+        Ok(config_path)
+    }
+
+    /// Generate the Promtail configuration file.
+    ///
+    /// Promtail discovers the containers attached to `network_name` through the Docker
+    /// socket and pushes their logs to Loki, labelled with the container name.
+    async fn generate_promtail_config(
+        &self,
+        host_config_path: &Path,
+        network_name: &str,
+    ) -> Result<PathBuf, anyhow::Error> {
+        let config_content = format!(
+            r#"# Promtail configuration for kupcake OP Stack
+
+server:
+  http_listen_port: 9080
+  grpc_listen_port: 0
+
+positions:
+  filename: /tmp/positions.yaml
+
+clients:
+  - url: http://{}:{}/loki/api/v1/push
+
+scrape_configs:
+  - job_name: 'kupcake'
+    docker_sd_configs:
+      - host: unix:///var/run/docker.sock
+        refresh_interval: 5s
+        filters:
+          - name: network
+            values: ['{}']
+    relabel_configs:
+      - source_labels: ['__meta_docker_container_name']
+        regex: '/(.*)'
+        target_label: 'container'
+      - source_labels: ['__meta_docker_container_log_stream']
+        target_label: 'stream'
+"#,
+            self.logs.loki.container_name, self.logs.loki.port, network_name
+        );
+
+        let config_path = host_config_path.join("promtail.yml");
+        tokio::fs::write(&config_path, config_content)
+            .await
+            .context("Failed to write Promtail config file")?;
+
+        tracing::debug!(path = ?config_path, "Promtail config written");
+        Ok(config_path)
+    }
+
+    /// Start the Loki and Promtail containers.
+    async fn start_logs(
+        &self,
+        docker: &mut KupDocker,
+        host_config_path: &Path,
+    ) -> Result<LogsHandler, anyhow::Error> {
+        let loki = &self.logs.loki;
+
+        // Loki runs as uid 10001 inside the container, so the data directory must be
+        // world-writable for the bind mount to work on Linux.
+        let loki_data_path = host_config_path.join("loki-data");
+        FsHandler::create_host_config_directory(&loki_data_path)?;
+
+        loki.docker_image.pull(docker).await?;
+
+        let service_config = ServiceConfig::new(loki.docker_image.clone())
+            .cmd(vec!["-config.file=/etc/loki/loki.yml".to_string()])
+            .ports(PortMapping::tcp_optional(loki.port, loki.host_port))
+            .expose(ExposedPort::tcp(loki.port))
+            .bind_str(format!(
+                "{}:/etc/loki/loki.yml:ro",
+                host_config_path.join("loki.yml").display()
+            ))
+            .bind(&loki_data_path, Path::new("/loki"), "rw");
+
+        let handler = docker
+            .start_service(
+                &loki.container_name,
+                service_config,
+                CreateAndStartContainerOptions::default(),
+            )
+            .await
+            .context("Failed to start Loki container")?;
+
+        let url = KupDocker::build_http_url(&handler.container_name, loki.port)?;
+        let host_url = handler
+            .build_host_url(loki.port, "http")
+            .context("Failed to build Loki host URL")?;
+
+        tracing::info!(
+            container_id = %handler.container_id,
+            container_name = %handler.container_name,
+            ?host_url,
+            "Loki container started"
+        );
+
+        let loki_handler = LokiHandler {
+            container_id: handler.container_id,
+            container_name: handler.container_name,
+            url,
+            host_url,
+        };
+
+        let promtail = &self.logs.promtail;
+        promtail.docker_image.pull(docker).await?;
+
+        let service_config = ServiceConfig::new(promtail.docker_image.clone())
+            .cmd(vec!["-config.file=/etc/promtail/promtail.yml".to_string()])
+            .bind_str(format!(
+                "{}:/etc/promtail/promtail.yml:ro",
+                host_config_path.join("promtail.yml").display()
+            ))
+            .bind(
+                &promtail.docker_socket,
+                Path::new("/var/run/docker.sock"),
+                "ro",
+            );
+
+        let handler = docker
+            .start_service(
+                &promtail.container_name,
+                service_config,
+                CreateAndStartContainerOptions::default(),
+            )
+            .await
+            .context("Failed to start Promtail container")?;
+
+        tracing::info!(
+            container_id = %handler.container_id,
+            container_name = %handler.container_name,
+            "Promtail container started"
+        );
+
+        Ok(LogsHandler {
+            loki: loki_handler,
+            promtail: PromtailHandler {
+                container_id: handler.container_id,
+                container_name: handler.container_name,
+            },
         })
     }
 
@@ -511,11 +1042,15 @@ providers:
         Ok(handler)
     }
 
-    /// Start the complete monitoring stack (Prometheus + Grafana).
+    /// Start the complete monitoring stack (Prometheus + Grafana, and Loki + Promtail when
+    /// log aggregation is enabled).
+    ///
+    /// Promtail collects the logs of the containers attached to `network_name`.
     pub async fn start(
         &self,
         docker: &mut KupDocker,
         host_config_path: PathBuf,
+        network_name: &str,
         metrics_targets: Vec<MetricsTarget>,
         dashboards_source: Option<PathBuf>,
     ) -> Result<MonitoringHandler, anyhow::Error> {
@@ -544,6 +1079,18 @@ providers:
                 .await?;
         }
 
+        // Start Loki before Grafana so that its datasource is reachable right away
+        let logs_handler = if self.logs.enabled {
+            self.generate_loki_config(&host_config_path).await?;
+            self.generate_promtail_config(&host_config_path, network_name)
+                .await?;
+
+            tracing::info!("Starting Loki and Promtail...");
+            Some(self.start_logs(docker, &host_config_path).await?)
+        } else {
+            None
+        };
+
         tracing::info!("Starting Prometheus...");
         let prometheus_handler = self.start_prometheus(docker, &host_config_path).await?;
 
@@ -559,6 +1106,179 @@ providers:
         Ok(MonitoringHandler {
             prometheus: prometheus_handler,
             grafana: grafana_handler,
+            logs: logs_handler,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_url() {
+        let grafana = GrafanaHandler {
+            container_id: "id".to_string(),
+            container_name: "kup-test-grafana".to_string(),
+            url: Url::parse("http://kup-test-grafana:3000/").unwrap(),
+            host_url: Some(Url::parse("http://localhost:49152/").unwrap()),
+            admin_user: "admin".to_string(),
+            admin_password: "admin".to_string(),
+        };
+
+        let url = grafana
+            .dashboard_url("2k8BXz24x", "1700000000000", "now")
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://localhost:49152/d/2k8BXz24x?from=1700000000000&to=now"
+        );
+
+        let unpublished = GrafanaHandler {
+            host_url: None,
+            ..grafana
+        };
+        assert!(
+            unpublished
+                .dashboard_url("2k8BXz24x", "now-1h", "now")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_import_request() {
+        let model = serde_json::json!({ "id": 7, "uid": "abc", "title": "Test" });
+        let expected = serde_json::json!({
+            "dashboard": { "id": null, "uid": "abc", "title": "Test" },
+            "overwrite": true,
+        });
+
+        assert_eq!(import_request(model.clone()), expected);
+        assert_eq!(
+            import_request(serde_json::json!({ "dashboard": model, "meta": {} })),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_grafana_host_url_maps_to_container_port() {
+        let config = GrafanaConfig::default();
+        let service_config = config.service_config(Path::new("/tmp/provisioning"));
+
+        let bindings: Vec<_> = service_config
+            .port_bindings
+            .iter()
+            .map(|pm| (pm.container_port, pm.host_port))
+            .collect();
+        assert_eq!(bindings, [(3000, 0)]);
+        assert!(
+            service_config
+                .env
+                .unwrap()
+                .contains(&"GF_SERVER_HTTP_PORT=3000".to_string())
+        );
+
+        // Docker bound the container port 3000 to a random host port.
+        let handler = crate::docker::ServiceHandler {
+            container_id: "id".to_string(),
+            container_name: config.container_name.clone(),
+            bound_ports: [("3000/tcp".to_string(), 49152)].into(),
+        };
+        let host_url = handler.build_host_url(config.port, "http").unwrap();
+        assert_eq!(
+            host_url.map(String::from).as_deref(),
+            Some("http://localhost:49152/")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_config_files() {
+        let dir = tempdir::TempDir::new("monitoring-test").unwrap();
+        let mut config = MonitoringConfig::default();
+
+        config
+            .generate_grafana_datasource(dir.path())
+            .await
+            .unwrap();
+        let datasources = std::fs::read_to_string(
+            dir.path()
+                .join("grafana/provisioning/datasources/prometheus.yml"),
+        )
+        .unwrap();
+        assert!(!datasources.contains("type: loki"));
+
+        config.logs.enabled = true;
+        let path = config
+            .generate_grafana_datasource(dir.path())
+            .await
+            .unwrap();
+        let datasources = std::fs::read_to_string(path).unwrap();
+        assert!(datasources.contains("type: loki"));
+        assert!(datasources.contains("url: http://kupcake-loki:3100"));
+
+        let path = config
+            .generate_promtail_config(dir.path(), "kup-test-network")
+            .await
+            .unwrap();
+        let promtail = std::fs::read_to_string(path).unwrap();
+        assert!(promtail.contains("http://kupcake-loki:3100/loki/api/v1/push"));
+        assert!(promtail.contains("values: ['kup-test-network']"));
+
+        let docker = crate::KupDockerConfig {
+            external_network: Some("shared".to_string()),
+            ..crate::deployer::tests::test_deployer(dir.path().to_path_buf()).docker
+        };
+        let path = config
+            .generate_promtail_config(dir.path(), docker.attached_network())
+            .await
+            .unwrap();
+        let promtail = std::fs::read_to_string(path).unwrap();
+        assert!(promtail.contains("values: ['shared']"));
+
+        let path = config.generate_loki_config(dir.path()).await.unwrap();
+        assert!(
+            std::fs::read_to_string(path)
+                .unwrap()
+                .contains("http_listen_port: 3100")
+        );
+    }
+
+    #[test]
+    fn test_logs_config_defaults_when_missing() {
+        let mut value = toml::Value::try_from(MonitoringConfig::default()).unwrap();
+        value.as_table_mut().unwrap().remove("logs");
+        let config: MonitoringConfig = value.try_into().unwrap();
+        assert_eq!(config.logs, LogsConfig::default());
+        assert!(!config.logs.enabled);
+    }
+
+    #[test]
+    fn test_target_status_from_targets_response() {
+        let response = serde_json::json!({
+            "status": "success",
+            "data": {
+                "activeTargets": [
+                    {
+                        "labels": { "job": "op-reth", "instance": "kup-op-reth:9001" },
+                        "health": "up",
+                        "lastError": ""
+                    },
+                    {
+                        "labels": { "job": "kona-node", "instance": "kup-kona-node:9999" },
+                        "health": "down",
+                        "lastError": "connection refused"
+                    },
+                    { "labels": {}, "health": "up" }
+                ]
+            }
+        });
+
+        let targets = TargetStatus::from_targets_response(&response);
+        assert_eq!(targets.len(), 2);
+        assert!(targets[0].up);
+        assert_eq!(targets[0].last_error, None);
+        assert_eq!(targets[1].instance, "kup-kona-node:9999");
+        assert!(!targets[1].up);
+        assert_eq!(targets[1].last_error.as_deref(), Some("connection refused"));
+    }
+}
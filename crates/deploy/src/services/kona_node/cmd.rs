@@ -2,6 +2,8 @@
 
 use std::path::Path;
 
+use crate::services::extra_args::merge_extra_args;
+
 pub const DEFAULT_P2P_PORT: u16 = 9222;
 
 /// Builder for kona-node consensus client commands.
@@ -9,6 +11,8 @@ pub const DEFAULT_P2P_PORT: u16 = 9222;
 pub struct KonaNodeCmdBuilder {
     mode: String,
     l1_rpc: String,
+    /// L1 WebSocket URL, used for `--l1` instead of `l1_rpc` when set.
+    l1_ws: Option<String>,
     l1_beacon: String,
     l1_slot_duration: u64,
     l2_rpc: String,
@@ -54,6 +58,7 @@ impl KonaNodeCmdBuilder {
         Self {
             mode: "validator".to_string(),
             l1_rpc: l1_rpc.into(),
+            l1_ws: None,
             l1_beacon: String::new(),
             l1_slot_duration: 12,
             l2_rpc: l2_rpc.into(),
@@ -104,6 +109,12 @@ impl KonaNodeCmdBuilder {
         self
     }
 
+    /// Follow the L1 over a WebSocket URL. The beacon fallback keeps using the HTTP URL.
+    pub fn l1_ws(mut self, url: impl Into<String>) -> Self {
+        self.l1_ws = Some(url.into());
+        self
+    }
+
     /// Set the L1 slot duration in seconds.
     pub fn l1_slot_duration(mut self, duration: u64) -> Self {
         self.l1_slot_duration = duration;
@@ -184,7 +195,7 @@ impl KonaNodeCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -214,7 +225,7 @@ impl KonaNodeCmdBuilder {
 
         // L1 configuration
         cmd.push("--l1".to_string());
-        cmd.push(self.l1_rpc.clone());
+        cmd.push(self.l1_ws.unwrap_or_else(|| self.l1_rpc.clone()));
 
         cmd.push("--l1-beacon".to_string());
         if self.l1_beacon.is_empty() {
@@ -310,9 +321,7 @@ impl KonaNodeCmdBuilder {
         }
 
         cmd.push(self.verbosity);
-        cmd.extend(self.extra_args);
-
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
@@ -338,6 +347,24 @@ mod tests {
         assert!(cmd.contains(&"validator".to_string()));
     }
 
+    #[test]
+    fn test_l1_ws_keeps_http_beacon() {
+        let cmd = KonaNodeCmdBuilder::new(
+            "http://l1:8545",
+            "http://localhost:9551",
+            "0.0.0.0",
+            "/data/rollup.json",
+            "/data/jwt.hex",
+        )
+        .l1_ws("ws://l1:8546")
+        .build();
+
+        let l1_pos = cmd.iter().position(|s| s == "--l1").unwrap();
+        assert_eq!(cmd[l1_pos + 1], "ws://l1:8546");
+        let beacon_pos = cmd.iter().position(|s| s == "--l1-beacon").unwrap();
+        assert_eq!(cmd[beacon_pos + 1], "http://l1:8545");
+    }
+
     #[test]
     fn test_flashblocks_flags() {
         let cmd = KonaNodeCmdBuilder::new(
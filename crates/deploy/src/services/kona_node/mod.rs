@@ -17,7 +17,8 @@ use crate::{
     ExposedPort,
     docker::{DockerImage, KupDocker, PortMapping, ServiceConfig},
     metrics::ContainerDeployTimings,
-    service::{self, KupcakeService},
+    service::{self, KupcakeService, ScrapeTarget},
+    services::MetricsTarget,
 };
 
 use super::l2_node::L2NodeRole;
@@ -31,6 +32,8 @@ pub struct KonaNodeInput {
     /// L1 RPC URL accessible from the host (used for L1 config generation).
     /// Falls back to `l1_rpc_url` if None.
     pub l1_host_url: Option<String>,
+    /// L1 WebSocket URL to follow the L1 over. Falls back to `l1_rpc_url` if None.
+    pub l1_ws_url: Option<String>,
     /// The authenticated Engine API URL of the paired op-reth instance.
     pub authrpc_url: String,
     /// Unsafe block signer private key (hex-encoded, without 0x prefix).
@@ -299,6 +302,13 @@ pub struct KonaNodeBuilder {
     /// Verbosity flag (e.g., "-vvv" for info, "-vvvv" for debug).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub verbosity: Option<String>,
+    /// Linux capabilities added to the container (e.g. `NET_ADMIN` to run `tc` inside it).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cap_add: Vec<String>,
+    /// Run the container in privileged mode. Must be opted into explicitly; a warning is
+    /// logged when the container starts.
+    #[serde(default)]
+    pub privileged: bool,
     /// Extra arguments to pass to kona-node.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
@@ -324,6 +334,8 @@ impl Default for KonaNodeBuilder {
             flashblocks_enabled: false,
             flashblocks_relay_port: None,
             verbosity: None,
+            cap_add: Vec::new(),
+            privileged: false,
             extra_args: Vec::new(),
         }
     }
@@ -337,6 +349,8 @@ pub struct KonaNodeHandler {
     pub container_name: String,
     /// P2P port for peer discovery.
     pub p2p_port: u16,
+    /// Port for metrics (container port).
+    pub metrics_port: u16,
     /// P2P keypair for this node.
     pub p2p_keypair: P2pKeypair,
     /// The RPC URL for the kona-node (internal Docker network).
@@ -368,6 +382,18 @@ impl KonaNodeHandler {
     }
 }
 
+impl ScrapeTarget for KonaNodeHandler {
+    fn metrics_target(&self) -> Option<MetricsTarget> {
+        Some(MetricsTarget {
+            job_name: self.container_name.clone(),
+            container_name: self.container_name.clone(),
+            port: self.metrics_port,
+            service_label: "kona-node".to_string(),
+            layer_label: "consensus".to_string(),
+        })
+    }
+}
+
 impl KonaNodeBuilder {
     /// Build the kona-node command, including P2P keypair generation.
     pub fn build_cmd(
@@ -407,6 +433,10 @@ impl KonaNodeBuilder {
         .verbosity(self.verbosity.as_deref().unwrap_or("-vvvv"))
         .extra_args(self.extra_args.clone());
 
+        if let Some(ref l1_ws_url) = input.l1_ws_url {
+            cmd_builder = cmd_builder.l1_ws(l1_ws_url);
+        }
+
         // For local/custom chains, add the L1 config file path.
         // The actual file generation is async and happens in deploy().
         if !is_known_l1_chain(input.l1_chain_id) {
@@ -526,7 +556,10 @@ impl KupcakeService for KonaNodeBuilder {
             .expose(ExposedPort::tcp(self.metrics_port))
             .expose(ExposedPort::tcp(DEFAULT_P2P_PORT))
             .expose(ExposedPort::udp(DEFAULT_P2P_PORT))
-            .bind(host_config_path, &container_config_path, "rw");
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?)
+            .cap_add(self.cap_add.iter().cloned())
+            .privileged(self.privileged);
 
         if let Some(relay_port) = flashblocks_relay_port {
             service_config = service_config.expose(ExposedPort::tcp(relay_port));
@@ -566,6 +599,7 @@ impl KupcakeService for KonaNodeBuilder {
             container_id: handler.container_id,
             container_name: handler.container_name,
             p2p_port: DEFAULT_P2P_PORT,
+            metrics_port: self.metrics_port,
             p2p_keypair,
             rpc_url,
             rpc_host_url,
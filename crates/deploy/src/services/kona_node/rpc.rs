@@ -2,6 +2,7 @@
 
 use anyhow::Context;
 use serde::Deserialize;
+use url::Url;
 
 use crate::rpc;
 
@@ -19,6 +20,14 @@ pub struct SyncStatus {
     pub finalized_l2: BlockRef,
 }
 
+/// Subset of the peer info returned by the `opp2p_self` RPC.
+#[derive(Debug, Clone, Deserialize)]
+struct PeerInfo {
+    /// Ethereum Node Record of the local node.
+    #[serde(rename = "ENR")]
+    enr: String,
+}
+
 /// Block reference with number and hash.
 #[derive(Debug, Clone, Deserialize)]
 pub struct BlockRef {
@@ -29,6 +38,13 @@ pub struct BlockRef {
 }
 
 impl KonaNodeHandler {
+    /// Returns the RPC URL to use from the host.
+    ///
+    /// Prefers the host-published URL and falls back to the Docker-internal one.
+    pub fn rpc_url(&self) -> &Url {
+        self.rpc_host_url.as_ref().unwrap_or(&self.rpc_url)
+    }
+
     /// Query the sync status from this kona-node using the optimism_syncStatus RPC method.
     ///
     /// Returns the sync status if the node is accessible and responding, or an error if
@@ -50,6 +66,25 @@ impl KonaNodeHandler {
         rpc::json_rpc_call(&client, rpc_url.as_str(), "optimism_syncStatus", vec![]).await
     }
 
+    /// Fetch this node's ENR using the `opp2p_self` RPC method.
+    ///
+    /// Supported by both kona-node and op-node.
+    ///
+    /// # Errors
+    /// Returns an error if the RPC URL is not published to the host or the request fails.
+    pub async fn fetch_enr(&self) -> Result<String, anyhow::Error> {
+        let rpc_url = self
+            .rpc_host_url
+            .as_ref()
+            .context("RPC URL not published to host")?;
+
+        let client = rpc::create_client()?;
+
+        let peer_info: PeerInfo =
+            rpc::json_rpc_call(&client, rpc_url.as_str(), "opp2p_self", vec![]).await?;
+        Ok(peer_info.enr)
+    }
+
     /// Wait for this kona-node to be ready by polling the RPC endpoint.
     ///
     /// Polls the node's RPC endpoint until it responds successfully or the timeout is reached.
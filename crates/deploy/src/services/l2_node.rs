@@ -12,11 +12,13 @@ use url::Url;
 use crate::{
     OpConductorBuilder, OpConductorHandler,
     docker::{DockerImage, KupDocker},
-    service::KupcakeService,
+    service::{KupcakeService, ScrapeTarget},
     services::{
-        OpConductorInput, OpRethInput,
+        MetricsTarget, OpConductorInput, OpRethInput,
         kona_node::{KonaNodeBuilder, KonaNodeHandler, KonaNodeInput, P2pKeypair},
+        op_node::OpNodeBuilder,
         op_reth::{OpRethBuilder, OpRethHandler},
+        sidecar::{L2NodeContext, L2NodeSidecar, Sidecars},
     },
 };
 
@@ -163,6 +165,40 @@ impl L2NodeRole {
     }
 }
 
+/// Consensus client implementation backing the L2 nodes.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ConsensusClient {
+    /// kona-node (Rust), the default.
+    #[default]
+    Kona,
+    /// op-node (Go), for comparison against the reference implementation.
+    OpNode,
+}
+
+impl ConsensusClient {
+    /// Returns the service name of this consensus client (e.g., for labels).
+    pub fn service_name(&self) -> &'static str {
+        match self {
+            ConsensusClient::Kona => "kona-node",
+            ConsensusClient::OpNode => "op-node",
+        }
+    }
+}
+
 /// Input parameters for deploying an L2 node (op-reth + kona-node + optional op-conductor).
 ///
 /// Decoupled from handler references — uses raw URLs and keys.
@@ -171,6 +207,8 @@ pub struct L2NodeInput {
     pub l1_rpc_url: String,
     /// L1 RPC URL accessible from the host (used for L1 config generation).
     pub l1_host_url: Option<String>,
+    /// L1 WebSocket URL the consensus client follows the L1 over, instead of `l1_rpc_url`.
+    pub l1_ws_url: Option<String>,
     /// Unsafe block signer private key (hex-encoded, without 0x prefix).
     pub unsafe_block_signer_key: String,
     /// Optional URL of the sequencer's op-reth HTTP RPC (required for validators).
@@ -188,6 +226,9 @@ pub struct L2NodeInput {
     /// Optional pre-generated P2P keypair for op-reth.
     /// If None, a random keypair will be generated.
     pub op_reth_p2p_secret_key: Option<String>,
+    /// Whether to record op-reth's genesis hash in `rollup.json` before the consensus
+    /// client starts, because `genesis.json` was patched after op-deployer generated it.
+    pub patch_genesis_hash: bool,
 }
 
 /// Configuration for an L2 node (op-reth + kona-node pair).
@@ -199,9 +240,15 @@ pub struct L2NodeBuilder<EL = OpRethBuilder, CL = KonaNodeBuilder, Cond = OpCond
     pub op_reth: EL,
     /// Configuration for kona-node consensus client.
     pub kona_node: CL,
+    /// Configuration for op-node. When set, op-node replaces kona-node as the consensus client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub op_node: Option<OpNodeBuilder>,
     /// Configuration for op-conductor (only for sequencer nodes in multi-sequencer setups).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub op_conductor: Option<Cond>,
+    /// Custom services started after this node. Not persisted to the configuration file.
+    #[serde(skip)]
+    pub sidecars: Sidecars,
 }
 
 impl Default for L2NodeBuilder {
@@ -210,7 +257,9 @@ impl Default for L2NodeBuilder {
             role: L2NodeRole::Sequencer,
             op_reth: OpRethBuilder::default(),
             kona_node: KonaNodeBuilder::default(),
+            op_node: None,
             op_conductor: None,
+            sidecars: Sidecars::default(),
         }
     }
 }
@@ -241,6 +290,15 @@ impl<EL, CL, Cond> L2NodeBuilder<EL, CL, Cond> {
         self.op_conductor = Some(conductor);
         self
     }
+
+    /// Attach a custom service started after this node.
+    ///
+    /// The sidecar receives an [`L2NodeContext`] with the node's container names and
+    /// RPC URLs. Sidecars start in the order they were attached.
+    pub fn with_sidecar(mut self, sidecar: impl L2NodeSidecar) -> Self {
+        self.sidecars.push(sidecar);
+        self
+    }
 }
 
 impl L2NodeBuilder {
@@ -248,11 +306,40 @@ impl L2NodeBuilder {
     pub fn with_name_suffix(mut self, suffix: &str) -> Self {
         self.op_reth.container_name = format!("{}-{}", self.op_reth.container_name, suffix);
         self.kona_node.container_name = format!("{}-{}", self.kona_node.container_name, suffix);
+        if let Some(ref mut op_node) = self.op_node {
+            op_node.container_name = format!("{}-{}", op_node.container_name, suffix);
+        }
         if let Some(ref mut conductor) = self.op_conductor {
             conductor.container_name = format!("{}-{}", conductor.container_name, suffix);
         }
         self
     }
+
+    /// Returns which consensus client this node runs.
+    pub fn consensus_client(&self) -> ConsensusClient {
+        if self.op_node.is_some() {
+            ConsensusClient::OpNode
+        } else {
+            ConsensusClient::Kona
+        }
+    }
+
+    /// Container name of the consensus client (kona-node or op-node).
+    pub fn consensus_container_name(&self) -> &str {
+        self.op_node
+            .as_ref()
+            .map_or(&self.kona_node.container_name, |op_node| {
+                &op_node.container_name
+            })
+    }
+
+    /// Persisted P2P secret key of the consensus client (kona-node or op-node).
+    pub fn consensus_p2p_secret_key(&self) -> Option<&String> {
+        match self.op_node {
+            Some(ref op_node) => op_node.p2p_secret_key.as_ref(),
+            None => self.kona_node.p2p_secret_key.as_ref(),
+        }
+    }
 }
 
 impl<EL, CL, Cond> KupcakeService for L2NodeBuilder<EL, CL, Cond>
@@ -327,6 +414,20 @@ where
             "op-reth enode computed"
         );
 
+        if input.patch_genesis_hash {
+            let op_reth_url = op_reth_handler
+                .http_host_url
+                .as_ref()
+                .unwrap_or(&op_reth_handler.http_rpc_url);
+            crate::l2_genesis::patch_rollup_l2_genesis_hash(
+                &docker.rpc_client,
+                &host_config_path.join("rollup.json"),
+                op_reth_url.as_str(),
+            )
+            .await
+            .context("Failed to update the L2 genesis hash of rollup.json")?;
+        }
+
         // Pre-compute conductor RPC URL if conductor is configured
         let conductor_rpc_url = self.op_conductor.as_ref().map(|c| {
             format!(
@@ -369,33 +470,39 @@ where
                 .context("op-rbuilder RPC not ready before kona-node startup")?;
         }
 
-        // Start kona-node with decoupled input
-        let kona_node_handler = self
-            .kona_node
-            .deploy(
-                docker,
-                host_config_path,
-                KonaNodeInput {
-                    l1_rpc_url: input.l1_rpc_url,
-                    l1_host_url: input.l1_host_url,
-                    authrpc_url: op_reth_handler.authrpc_url.to_string(),
-                    unsafe_block_signer_key: input.unsafe_block_signer_key,
-                    role: self.role,
-                    jwt_filename,
-                    bootnodes: input.kona_node_enodes,
-                    l1_chain_id: input.l1_chain_id,
-                    conductor_rpc: conductor_rpc_url,
-                    is_conductor_leader,
-                    flashblocks_builder_url,
-                },
-            )
-            .await?;
+        // Start the consensus client (kona-node, or op-node if configured) with decoupled input
+        let consensus_input = KonaNodeInput {
+            l1_rpc_url: input.l1_rpc_url,
+            l1_host_url: input.l1_host_url,
+            l1_ws_url: input.l1_ws_url,
+            authrpc_url: op_reth_handler.authrpc_url.to_string(),
+            unsafe_block_signer_key: input.unsafe_block_signer_key,
+            role: self.role,
+            jwt_filename,
+            bootnodes: input.kona_node_enodes,
+            l1_chain_id: input.l1_chain_id,
+            conductor_rpc: conductor_rpc_url,
+            is_conductor_leader,
+            flashblocks_builder_url,
+        };
+        let kona_node_handler = match self.op_node {
+            Some(ref op_node) => {
+                op_node
+                    .deploy(docker, host_config_path, consensus_input)
+                    .await?
+            }
+            None => {
+                self.kona_node
+                    .deploy(docker, host_config_path, consensus_input)
+                    .await?
+            }
+        };
 
         let kona_node_enode = kona_node_handler.enode();
         tracing::info!(
             container_name = %kona_node_handler.container_name,
             enode = %kona_node_enode,
-            "kona-node enode computed"
+            "Consensus client enode computed"
         );
 
         // Wait for both RPCs before starting conductor
@@ -420,11 +527,11 @@ where
                 .unwrap_or(kona_node_handler.rpc_url.as_str());
             tracing::info!(
                 rpc_url = %kona_wait_url,
-                "Waiting for kona-node RPC to be ready before starting conductor..."
+                "Waiting for consensus client RPC to be ready before starting conductor..."
             );
             wait_for_consensus_rpc_ready(kona_wait_url, 30)
                 .await
-                .context("kona-node RPC not ready in time for conductor startup")?;
+                .context("Consensus client RPC not ready in time for conductor startup")?;
         }
 
         let op_conductor = match (&self.op_conductor, input.conductor_context) {
@@ -477,12 +584,32 @@ where
             _ => None,
         };
 
-        Ok(L2NodeHandler {
+        let mut handler = L2NodeHandler {
             role: self.role,
             op_reth: op_reth_handler,
             kona_node: kona_node_handler,
             op_conductor,
-        })
+            sidecars: Vec::with_capacity(self.sidecars.len()),
+        };
+
+        for sidecar in self.sidecars.iter() {
+            tracing::info!(
+                container_name = %sidecar.container_name(),
+                node = %handler.op_reth.container_name,
+                "Starting sidecar..."
+            );
+            sidecar
+                .deploy_sidecar(
+                    docker,
+                    host_config_path,
+                    L2NodeContext::from_handler(&handler),
+                )
+                .await
+                .with_context(|| format!("Failed to start sidecar {}", sidecar.container_name()))?;
+            handler.sidecars.push(sidecar.container_name().to_string());
+        }
+
+        Ok(handler)
     }
 }
 
@@ -492,10 +619,12 @@ pub struct L2NodeHandler {
     pub role: L2NodeRole,
     /// Handler for the op-reth execution client.
     pub op_reth: OpRethHandler,
-    /// Handler for the kona-node consensus client.
+    /// Handler for the consensus client (kona-node, or op-node when configured).
     pub kona_node: KonaNodeHandler,
     /// Handler for the op-conductor instance (only present if this is a sequencer).
     pub op_conductor: Option<OpConductorHandler>,
+    /// Container names of the sidecars started after this node.
+    pub sidecars: Vec<String>,
 }
 
 impl L2NodeHandler {
@@ -508,4 +637,20 @@ impl L2NodeHandler {
     pub fn is_validator(&self) -> bool {
         self.role == L2NodeRole::Validator
     }
+
+    /// Scrape targets of the execution and consensus clients, with the node role
+    /// appended to their service label (e.g. `op-reth-sequencer`).
+    pub fn metrics_targets(&self) -> Vec<MetricsTarget> {
+        [
+            self.op_reth.metrics_target(),
+            self.kona_node.metrics_target(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|mut target| {
+            target.service_label = format!("{}-{}", target.service_label, self.role);
+            target
+        })
+        .collect()
+    }
 }
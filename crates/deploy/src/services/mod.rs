@@ -9,6 +9,8 @@ use std::path::Path;
 use anyhow::Context;
 
 pub mod anvil;
+pub mod blockscout;
+mod extra_args;
 pub mod grafana;
 pub mod kona_node;
 pub mod l2_node;
@@ -16,35 +18,50 @@ pub mod op_batcher;
 pub mod op_challenger;
 pub mod op_conductor;
 pub mod op_deployer;
+pub mod op_node;
 pub mod op_proposer;
 pub mod op_reth;
+pub mod op_supervisor;
+pub mod sidecar;
 
 // Re-export commonly used types
 pub use anvil::{
     AnvilAccounts, AnvilConfig, AnvilHandler, AnvilInitMode, AnvilInput,
     DEFAULT_DOCKER_IMAGE as ANVIL_DEFAULT_IMAGE, DEFAULT_DOCKER_TAG as ANVIL_DEFAULT_TAG,
+    KNOWN_HARDFORKS as ANVIL_KNOWN_HARDFORKS,
+};
+pub use blockscout::{
+    BlockscoutBuilder, BlockscoutHandler, BlockscoutInput,
+    DEFAULT_DOCKER_IMAGE as BLOCKSCOUT_DEFAULT_IMAGE, DEFAULT_DOCKER_TAG as BLOCKSCOUT_DEFAULT_TAG,
 };
 pub use grafana::{
     DEFAULT_GRAFANA_DOCKER_IMAGE as GRAFANA_DEFAULT_IMAGE,
     DEFAULT_GRAFANA_DOCKER_TAG as GRAFANA_DEFAULT_TAG,
+    DEFAULT_LOKI_DOCKER_IMAGE as LOKI_DEFAULT_IMAGE, DEFAULT_LOKI_DOCKER_TAG as LOKI_DEFAULT_TAG,
     DEFAULT_PROMETHEUS_DOCKER_IMAGE as PROMETHEUS_DEFAULT_IMAGE,
-    DEFAULT_PROMETHEUS_DOCKER_TAG as PROMETHEUS_DEFAULT_TAG, GrafanaConfig, MetricsTarget,
-    MonitoringConfig, MonitoringHandler, PrometheusConfig,
+    DEFAULT_PROMETHEUS_DOCKER_TAG as PROMETHEUS_DEFAULT_TAG,
+    DEFAULT_PROMTAIL_DOCKER_IMAGE as PROMTAIL_DEFAULT_IMAGE,
+    DEFAULT_PROMTAIL_DOCKER_TAG as PROMTAIL_DEFAULT_TAG, GrafanaConfig, GrafanaHandler,
+    ImportedDashboard, LogsConfig, LogsHandler, LokiConfig, LokiHandler, MetricsTarget,
+    MonitoringConfig, MonitoringHandler, PrometheusConfig, PromtailConfig, PromtailHandler,
+    TargetStatus,
 };
 pub use kona_node::{
     DEFAULT_DOCKER_IMAGE as KONA_NODE_DEFAULT_IMAGE, DEFAULT_DOCKER_TAG as KONA_NODE_DEFAULT_TAG,
     KonaNodeBuilder, KonaNodeHandler, KonaNodeInput,
     rpc::{BlockRef, SyncStatus},
 };
-pub use l2_node::{ConductorContext, L2NodeBuilder, L2NodeHandler, L2NodeInput, L2NodeRole};
+pub use l2_node::{
+    ConductorContext, ConsensusClient, L2NodeBuilder, L2NodeHandler, L2NodeInput, L2NodeRole,
+};
 pub use op_batcher::{
     DEFAULT_DOCKER_IMAGE as OP_BATCHER_DEFAULT_IMAGE, DEFAULT_DOCKER_TAG as OP_BATCHER_DEFAULT_TAG,
-    OpBatcherBuilder, OpBatcherHandler, OpBatcherInput,
+    DataAvailabilityType, OpBatcherBuilder, OpBatcherHandler, OpBatcherInput,
 };
 pub use op_challenger::{
     DEFAULT_DOCKER_IMAGE as OP_CHALLENGER_DEFAULT_IMAGE,
-    DEFAULT_DOCKER_TAG as OP_CHALLENGER_DEFAULT_TAG, OpChallengerBuilder, OpChallengerHandler,
-    OpChallengerInput,
+    DEFAULT_DOCKER_TAG as OP_CHALLENGER_DEFAULT_TAG, FaultProofVm, OpChallengerBuilder,
+    OpChallengerHandler, OpChallengerInput,
 };
 pub use op_conductor::{
     DEFAULT_DOCKER_IMAGE as OP_CONDUCTOR_DEFAULT_IMAGE,
@@ -55,6 +72,10 @@ pub use op_deployer::{
     DEFAULT_DOCKER_IMAGE as OP_DEPLOYER_DEFAULT_IMAGE,
     DEFAULT_DOCKER_TAG as OP_DEPLOYER_DEFAULT_TAG, OpDeployerConfig, anvil_accounts_from_infos,
 };
+pub use op_node::{
+    DEFAULT_DOCKER_IMAGE as OP_NODE_DEFAULT_IMAGE, DEFAULT_DOCKER_TAG as OP_NODE_DEFAULT_TAG,
+    OpNodeBuilder,
+};
 pub use op_proposer::{
     DEFAULT_DOCKER_IMAGE as OP_PROPOSER_DEFAULT_IMAGE,
     DEFAULT_DOCKER_TAG as OP_PROPOSER_DEFAULT_TAG, OpProposerBuilder, OpProposerHandler,
@@ -63,24 +84,22 @@ pub use op_proposer::{
 pub use op_reth::{
     DEFAULT_DOCKER_IMAGE as OP_RETH_DEFAULT_IMAGE, DEFAULT_DOCKER_TAG as OP_RETH_DEFAULT_TAG,
     DEFAULT_RBUILDER_DOCKER_IMAGE as OP_RBUILDER_DEFAULT_IMAGE,
-    DEFAULT_RBUILDER_DOCKER_TAG as OP_RBUILDER_DEFAULT_TAG, OpRethBuilder, OpRethHandler,
-    OpRethInput,
+    DEFAULT_RBUILDER_DOCKER_TAG as OP_RBUILDER_DEFAULT_TAG, MINIMUM_PRUNING_DISTANCE,
+    OpRethBuilder, OpRethHandler, OpRethInput, PruningMode,
     rpc::{EthSyncProgress, OpRethStatus},
 };
+pub use op_supervisor::{
+    DEFAULT_DOCKER_IMAGE as OP_SUPERVISOR_DEFAULT_IMAGE,
+    DEFAULT_DOCKER_TAG as OP_SUPERVISOR_DEFAULT_TAG, OpSupervisorBuilder, OpSupervisorHandler,
+    OpSupervisorInput, SupervisedChain,
+};
+pub use sidecar::{L2NodeContext, L2NodeSidecar};
 
 /// Read the DisputeGameFactory proxy address from state.json.
 ///
 /// Used by op-proposer and op-challenger to look up the DGF address at deploy time.
 pub fn read_dgf_address(host_config_path: &Path) -> Result<String, anyhow::Error> {
-    let state_file_path = host_config_path.join("state.json");
-    let state_content = std::fs::read_to_string(&state_file_path)
+    let state = crate::json_file::read_state_json(host_config_path)
         .context("Failed to read state.json for DisputeGameFactory address")?;
-
-    let state: serde_json::Value =
-        serde_json::from_str(&state_content).context("Failed to parse state.json")?;
-
-    state["opChainDeployments"][0]["DisputeGameFactoryProxy"]
-        .as_str()
-        .map(String::from)
-        .context("DisputeGameFactory address not found in state.json")
+    crate::json_file::state_chain_address(&state, "DisputeGameFactoryProxy")
 }
@@ -1,5 +1,7 @@
 //! Command builder for op-batcher.
 
+use crate::services::extra_args::merge_extra_args;
+
 /// Builder for op-batcher commands.
 #[derive(Debug, Clone)]
 pub struct OpBatcherCmdBuilder {
@@ -19,6 +21,8 @@ pub struct OpBatcherCmdBuilder {
     sub_safety_margin: Option<u64>,
     poll_interval: Option<String>,
     log_level: Option<String>,
+    stopped: bool,
+    alt_da_server: Option<String>,
     extra_args: Vec<String>,
 }
 
@@ -47,6 +51,8 @@ impl OpBatcherCmdBuilder {
             sub_safety_margin: None,
             poll_interval: None,
             log_level: None,
+            stopped: false,
+            alt_da_server: None,
             extra_args: Vec::new(),
         }
     }
@@ -77,7 +83,7 @@ impl OpBatcherCmdBuilder {
         self
     }
 
-    /// Set the data availability type (blobs, calldata).
+    /// Set the data availability type (blobs, calldata, auto).
     pub fn data_availability_type(mut self, da_type: impl Into<String>) -> Self {
         self.data_availability_type = da_type.into();
         self
@@ -113,7 +119,21 @@ impl OpBatcherCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Start the batcher without submitting batches.
+    pub fn stopped(mut self, stopped: bool) -> Self {
+        self.stopped = stopped;
+        self
+    }
+
+    /// Store the batch data on the DA server at `url` and post only its commitment to L1.
+    ///
+    /// Commitments are sent as calldata, whatever the data availability type.
+    pub fn maybe_alt_da_server(mut self, url: Option<String>) -> Self {
+        self.alt_da_server = url;
+        self
+    }
+
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -159,7 +179,16 @@ impl OpBatcherCmdBuilder {
 
         // Batcher configuration
         cmd.push("--data-availability-type".to_string());
-        cmd.push(self.data_availability_type);
+        match self.alt_da_server {
+            Some(da_server) => {
+                // op-batcher refuses alt-DA commitments in blobs.
+                cmd.push("calldata".to_string());
+                cmd.push("--altda.enabled".to_string());
+                cmd.push("--altda.da-server".to_string());
+                cmd.push(da_server);
+            }
+            None => cmd.push(self.data_availability_type),
+        }
 
         // For local devnet, disable DA throttling
         cmd.push("--throttle.unsafe-da-bytes-lower-threshold".to_string());
@@ -190,9 +219,11 @@ impl OpBatcherCmdBuilder {
             cmd.push(level);
         }
 
-        cmd.extend(self.extra_args);
+        if self.stopped {
+            cmd.push("--stopped".to_string());
+        }
 
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
@@ -213,5 +244,66 @@ mod tests {
 
         assert!(cmd.contains(&"op-batcher".to_string()));
         assert!(cmd.contains(&"--l1-eth-rpc".to_string()));
+        assert!(!cmd.contains(&"--stopped".to_string()));
+    }
+
+    #[test]
+    fn test_op_batcher_cmd_builder_stopped() {
+        let cmd = OpBatcherCmdBuilder::new(
+            "http://localhost:8545",
+            "http://localhost:9545",
+            "http://localhost:7545",
+            "0xdeadbeef",
+        )
+        .stopped(true)
+        .build();
+
+        assert!(cmd.contains(&"--stopped".to_string()));
+        assert!(cmd.contains(&"--rpc.enable-admin".to_string()));
+    }
+
+    #[test]
+    fn test_op_batcher_cmd_builder_da_type() {
+        for (da_type, expected) in [
+            (crate::DataAvailabilityType::Blobs, "blobs"),
+            (crate::DataAvailabilityType::Calldata, "calldata"),
+            (crate::DataAvailabilityType::Auto, "auto"),
+        ] {
+            let cmd = OpBatcherCmdBuilder::new(
+                "http://localhost:8545",
+                "http://localhost:9545",
+                "http://localhost:7545",
+                "0xdeadbeef",
+            )
+            .data_availability_type(da_type.to_string())
+            .build();
+
+            let flag = cmd
+                .iter()
+                .position(|s| s == "--data-availability-type")
+                .unwrap();
+            assert_eq!(cmd[flag + 1], expected);
+        }
+    }
+
+    #[test]
+    fn test_op_batcher_cmd_builder_alt_da() {
+        let cmd = OpBatcherCmdBuilder::new(
+            "http://localhost:8545",
+            "http://localhost:9545",
+            "http://localhost:7545",
+            "0xdeadbeef",
+        )
+        .maybe_alt_da_server(Some("http://da-server:3100".to_string()))
+        .build();
+
+        let da_type = cmd
+            .iter()
+            .position(|s| s == "--data-availability-type")
+            .unwrap();
+        assert_eq!(cmd[da_type + 1], "calldata");
+        assert!(cmd.contains(&"--altda.enabled".to_string()));
+        let server = cmd.iter().position(|s| s == "--altda.da-server").unwrap();
+        assert_eq!(cmd[server + 1], "http://da-server:3100");
     }
 }
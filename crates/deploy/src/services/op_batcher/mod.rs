@@ -12,12 +12,15 @@ pub use cmd::OpBatcherCmdBuilder;
 
 use crate::docker::{DockerImage, ExposedPort, KupDocker, PortMapping, ServiceConfig};
 use crate::metrics::ContainerDeployTimings;
-use crate::service::{self, KupcakeService};
+use crate::service::{self, KupcakeService, ScrapeTarget};
+use crate::services::MetricsTarget;
 
 /// Input parameters for deploying the op-batcher.
 pub struct OpBatcherInput {
     /// L1 RPC URL (e.g., Anvil).
     pub l1_rpc_url: String,
+    /// L1 WebSocket URL, used instead of `l1_rpc_url` when set.
+    pub l1_ws_url: Option<String>,
     /// L2 execution client RPC URL (e.g., op-reth HTTP).
     pub l2_rpc_url: String,
     /// Rollup (consensus) RPC URL (e.g., kona-node).
@@ -35,6 +38,39 @@ pub const DEFAULT_DOCKER_IMAGE: &str = "us-docker.pkg.dev/oplabs-tools-artifacts
 /// Default Docker tag for op-batcher.
 pub const DEFAULT_DOCKER_TAG: &str = "v1.15.0";
 
+/// Where op-batcher posts the batch data on L1 (`--data-availability-type`).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum DataAvailabilityType {
+    /// EIP-4844 blob transactions, the default.
+    #[default]
+    Blobs,
+    /// Calldata transactions, for an L1 without a working blob pool.
+    Calldata,
+    /// Blobs or calldata, whichever is cheaper on L1.
+    Auto,
+}
+
+impl DataAvailabilityType {
+    /// Whether this is the default type (blobs).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Configuration for the op-batcher component.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OpBatcherBuilder {
@@ -68,6 +104,17 @@ pub struct OpBatcherBuilder {
     /// Extra arguments to pass to op-batcher.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
+    /// Start without submitting batches (`--stopped`), e.g. for a hot spare. Submission
+    /// is started with the `admin_startBatcher` RPC.
+    #[serde(default)]
+    pub stopped: bool,
+    /// DA server to store the batch data on (alt-DA). None posts the data to L1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_da_server: Option<String>,
+    /// How the batch data is posted to L1. Ignored with an alt-DA server, which always
+    /// posts its commitments in calldata.
+    #[serde(default, skip_serializing_if = "DataAvailabilityType::is_default")]
+    pub da_type: DataAvailabilityType,
 }
 
 impl Default for OpBatcherBuilder {
@@ -86,6 +133,9 @@ impl Default for OpBatcherBuilder {
             poll_interval: "1s".to_string(),
             log_level: None,
             extra_args: Vec::new(),
+            stopped: false,
+            alt_da_server: None,
+            da_type: DataAvailabilityType::default(),
         }
     }
 }
@@ -98,6 +148,8 @@ pub struct OpBatcherHandler {
     pub container_name: String,
     /// The RPC URL for the op-batcher (internal Docker network).
     pub rpc_url: Url,
+    /// Port for metrics (container port).
+    pub metrics_port: u16,
     /// The RPC URL accessible from host (if published). None if not published.
     pub rpc_host_url: Option<Url>,
     /// The metrics URL accessible from host (if published). None if not published.
@@ -106,6 +158,18 @@ pub struct OpBatcherHandler {
     pub deploy_timings: ContainerDeployTimings,
 }
 
+impl ScrapeTarget for OpBatcherHandler {
+    fn metrics_target(&self) -> Option<MetricsTarget> {
+        Some(MetricsTarget {
+            job_name: self.container_name.clone(),
+            container_name: self.container_name.clone(),
+            port: self.metrics_port,
+            service_label: "op-batcher".to_string(),
+            layer_label: "batcher".to_string(),
+        })
+    }
+}
+
 impl OpBatcherBuilder {
     /// Build the Docker command arguments for op-batcher.
     pub fn build_cmd(
@@ -114,14 +178,20 @@ impl OpBatcherBuilder {
         input: &OpBatcherInput,
     ) -> Result<Vec<String>, anyhow::Error> {
         let mut cmd_builder = OpBatcherCmdBuilder::new(
-            input.l1_rpc_url.to_string(),
+            input
+                .l1_ws_url
+                .as_ref()
+                .unwrap_or(&input.l1_rpc_url)
+                .to_string(),
             input.l2_rpc_url.to_string(),
             input.rollup_rpc_url.to_string(),
             input.batcher_private_key.to_string(),
         )
         .rpc_port(self.rpc_port)
         .metrics(true, "0.0.0.0", self.metrics_port)
-        .data_availability_type("blobs")
+        .data_availability_type(self.da_type.to_string())
+        .stopped(self.stopped)
+        .maybe_alt_da_server(self.alt_da_server.clone())
         .extra_args(self.extra_args.clone());
 
         if let Some(ref level) = self.log_level {
@@ -198,6 +268,7 @@ impl KupcakeService for OpBatcherBuilder {
             container_id: handler.container_id,
             container_name: handler.container_name,
             rpc_url,
+            metrics_port: self.metrics_port,
             rpc_host_url,
             metrics_host_url,
             deploy_timings: timings,
@@ -1,5 +1,31 @@
 //! Command builder for op-challenger.
 
+use crate::services::extra_args::merge_extra_args;
+
+/// Paths and URLs op-challenger needs to run cannon (fault proof VM) traces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CannonArgs {
+    /// Path to the cannon binary.
+    pub bin: String,
+    /// Path to the op-program server binary run inside cannon.
+    pub server: String,
+    /// Path to the absolute prestate matching the one in the dispute game.
+    pub prestate: String,
+    /// L2 RPC used by op-program. Defaults to `--l2-eth-rpc` when None.
+    pub l2_rpc: Option<String>,
+}
+
+/// Paths op-challenger needs to run asterisc (RISC-V fault proof VM) traces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsteriscArgs {
+    /// Path to the asterisc binary.
+    pub bin: String,
+    /// Path to the op-program server binary run inside asterisc.
+    pub server: String,
+    /// Path to the absolute prestate matching the one in the dispute game.
+    pub prestate: String,
+}
+
 /// Builder for op-challenger commands.
 #[derive(Debug, Clone)]
 pub struct OpChallengerCmdBuilder {
@@ -12,8 +38,10 @@ pub struct OpChallengerCmdBuilder {
     datadir: String,
     rollup_config: String,
     l2_genesis: String,
-    trace_type: String,
+    trace_types: Vec<String>,
     game_allowlist: Vec<u8>,
+    cannon: Option<CannonArgs>,
+    asterisc: Option<AsteriscArgs>,
     metrics_enabled: bool,
     metrics_addr: String,
     metrics_port: u16,
@@ -42,8 +70,10 @@ impl OpChallengerCmdBuilder {
             datadir: datadir.into(),
             rollup_config: String::new(),
             l2_genesis: String::new(),
-            trace_type: "permissioned".to_string(),
+            trace_types: vec!["permissioned".to_string()],
             game_allowlist: vec![254], // Permissioned game type
+            cannon: None,
+            asterisc: None,
             metrics_enabled: true,
             metrics_addr: "0.0.0.0".to_string(),
             metrics_port: 7303,
@@ -63,9 +93,33 @@ impl OpChallengerCmdBuilder {
         self
     }
 
-    /// Set the trace type.
+    /// Set a single trace type.
     pub fn trace_type(mut self, trace_type: impl Into<String>) -> Self {
-        self.trace_type = trace_type.into();
+        self.trace_types = vec![trace_type.into()];
+        self
+    }
+
+    /// Set the trace types, one `--trace-type` flag each.
+    pub fn trace_types(mut self, trace_types: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.trace_types = trace_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the L1 beacon API URL. Defaults to the L1 RPC URL.
+    pub fn l1_beacon(mut self, url: impl Into<String>) -> Self {
+        self.l1_beacon = url.into();
+        self
+    }
+
+    /// Configure cannon traces.
+    pub fn cannon(mut self, cannon: CannonArgs) -> Self {
+        self.cannon = Some(cannon);
+        self
+    }
+
+    /// Configure asterisc traces.
+    pub fn asterisc(mut self, asterisc: AsteriscArgs) -> Self {
+        self.asterisc = Some(asterisc);
         self
     }
 
@@ -89,7 +143,7 @@ impl OpChallengerCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -113,10 +167,35 @@ impl OpChallengerCmdBuilder {
             self.game_factory_address,
             "--datadir".to_string(),
             self.datadir,
-            "--trace-type".to_string(),
-            self.trace_type,
         ];
 
+        for trace_type in self.trace_types {
+            cmd.push("--trace-type".to_string());
+            cmd.push(trace_type);
+        }
+
+        if let Some(cannon) = self.cannon {
+            cmd.push("--cannon-bin".to_string());
+            cmd.push(cannon.bin);
+            cmd.push("--cannon-server".to_string());
+            cmd.push(cannon.server);
+            cmd.push("--cannon-prestate".to_string());
+            cmd.push(cannon.prestate);
+            if let Some(l2_rpc) = cannon.l2_rpc {
+                cmd.push("--cannon-l2".to_string());
+                cmd.push(l2_rpc);
+            }
+        }
+
+        if let Some(asterisc) = self.asterisc {
+            cmd.push("--asterisc-bin".to_string());
+            cmd.push(asterisc.bin);
+            cmd.push("--asterisc-server".to_string());
+            cmd.push(asterisc.server);
+            cmd.push("--asterisc-prestate".to_string());
+            cmd.push(asterisc.prestate);
+        }
+
         // Rollup config and L2 genesis
         if !self.rollup_config.is_empty() {
             cmd.push("--rollup-config".to_string());
@@ -147,9 +226,7 @@ impl OpChallengerCmdBuilder {
             cmd.push(level);
         }
 
-        cmd.extend(self.extra_args);
-
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
@@ -174,5 +251,74 @@ mod tests {
         assert!(cmd.contains(&"--game-factory-address".to_string()));
         assert!(cmd.contains(&"--datadir".to_string()));
         assert!(cmd.contains(&"/data".to_string()));
+        assert!(!cmd.contains(&"--cannon-bin".to_string()));
+    }
+
+    #[test]
+    fn test_op_challenger_cmd_builder_cannon() {
+        let cmd = OpChallengerCmdBuilder::new(
+            "http://l1:8545",
+            "http://l2:9545",
+            "http://rollup:7545",
+            "0xdeadbeef",
+            "0x1234567890abcdef",
+            "/data",
+        )
+        .trace_types(["cannon", "permissioned"])
+        .l1_beacon("http://beacon:5052")
+        .cannon(CannonArgs {
+            bin: "/usr/local/bin/cannon".to_string(),
+            server: "/usr/local/bin/op-program".to_string(),
+            prestate: "/data/prestate.bin.gz".to_string(),
+            l2_rpc: None,
+        })
+        .build();
+
+        let value_of = |flag: &str| {
+            cmd.iter()
+                .position(|arg| arg == flag)
+                .map(|i| cmd[i + 1].as_str())
+        };
+        assert_eq!(cmd.iter().filter(|arg| *arg == "--trace-type").count(), 2);
+        assert_eq!(value_of("--l1-beacon"), Some("http://beacon:5052"));
+        assert_eq!(
+            value_of("--cannon-server"),
+            Some("/usr/local/bin/op-program")
+        );
+        assert_eq!(value_of("--cannon-prestate"), Some("/data/prestate.bin.gz"));
+        assert_eq!(value_of("--cannon-l2"), None);
+    }
+
+    #[test]
+    fn test_op_challenger_cmd_builder_asterisc() {
+        let cmd = OpChallengerCmdBuilder::new(
+            "http://l1:8545",
+            "http://l2:9545",
+            "http://rollup:7545",
+            "0xdeadbeef",
+            "0x1234567890abcdef",
+            "/data",
+        )
+        .trace_types(["asterisc", "permissioned"])
+        .game_allowlist([2, 254])
+        .asterisc(AsteriscArgs {
+            bin: "/usr/local/bin/asterisc".to_string(),
+            server: "/usr/local/bin/op-program".to_string(),
+            prestate: "/prestate/prestate-rv64.bin.gz".to_string(),
+        })
+        .build();
+
+        let value_of = |flag: &str| {
+            cmd.iter()
+                .position(|arg| arg == flag)
+                .map(|i| cmd[i + 1].as_str())
+        };
+        assert_eq!(value_of("--trace-type"), Some("asterisc"));
+        assert_eq!(value_of("--game-allowlist"), Some("2"));
+        assert_eq!(
+            value_of("--asterisc-prestate"),
+            Some("/prestate/prestate-rv64.bin.gz")
+        );
+        assert!(!cmd.contains(&"--cannon-bin".to_string()));
     }
 }
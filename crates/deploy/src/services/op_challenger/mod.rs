@@ -8,16 +8,20 @@ use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-pub use cmd::OpChallengerCmdBuilder;
+pub use cmd::{AsteriscArgs, CannonArgs, OpChallengerCmdBuilder};
 
 use crate::docker::{DockerImage, ExposedPort, KupDocker, PortMapping, ServiceConfig};
 use crate::metrics::ContainerDeployTimings;
-use crate::service::{self, KupcakeService};
+use crate::service::{self, KupcakeService, ScrapeTarget};
+use crate::services::MetricsTarget;
 
 /// Input parameters for deploying the op-challenger.
 pub struct OpChallengerInput {
     /// L1 RPC URL (e.g., Anvil).
     pub l1_rpc_url: String,
+    /// L1 RPC URL accessible from host, used to check the DisputeGameFactory before
+    /// starting with fault proofs.
+    pub l1_host_url: Option<String>,
     /// L2 execution client RPC URL (e.g., op-reth HTTP).
     pub l2_rpc_url: String,
     /// Rollup (consensus) RPC URL (e.g., kona-node).
@@ -31,6 +35,74 @@ pub const DEFAULT_RPC_PORT: u16 = 8561;
 /// Default metrics port for op-challenger.
 pub const DEFAULT_METRICS_PORT: u16 = 7303;
 
+/// Game type of permissionless cannon fault dispute games.
+pub const CANNON_GAME_TYPE: u8 = 0;
+/// Game type of permissionless asterisc fault dispute games.
+pub const ASTERISC_GAME_TYPE: u8 = 2;
+/// Game type of the permissioned dispute games created by op-proposer.
+pub const PERMISSIONED_GAME_TYPE: u8 = 254;
+
+/// Path of the cannon binary in the op-challenger image.
+pub const DEFAULT_CANNON_BIN: &str = "/usr/local/bin/cannon";
+/// Path of the op-program binary in the op-challenger image.
+pub const DEFAULT_CANNON_SERVER: &str = "/usr/local/bin/op-program";
+/// Path of the asterisc binary in the op-challenger image.
+pub const DEFAULT_ASTERISC_BIN: &str = "/usr/local/bin/asterisc";
+/// Path of the op-program binary run by asterisc in the op-challenger image.
+pub const DEFAULT_ASTERISC_SERVER: &str = "/usr/local/bin/op-program";
+
+/// Directory where `prestate_file` is mounted in the container.
+const PRESTATE_MOUNT_DIR: &str = "/prestate";
+/// Directory of the data directory where `prestate_url` is downloaded.
+const PRESTATE_DOWNLOAD_DIR: &str = "prestates";
+
+/// Fault proof VM running the traces of permissionless dispute games.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum FaultProofVm {
+    /// MIPS VM, the default (`--trace-type cannon`, game type 0).
+    #[default]
+    Cannon,
+    /// RISC-V VM (`--trace-type asterisc`, game type 2).
+    Asterisc,
+}
+
+impl FaultProofVm {
+    /// Whether this is the default VM (cannon).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The op-challenger `--trace-type` of this VM.
+    pub fn trace_type(&self) -> &'static str {
+        match self {
+            Self::Cannon => "cannon",
+            Self::Asterisc => "asterisc",
+        }
+    }
+
+    /// The dispute game type this VM is deployed with by default.
+    pub fn game_type(&self) -> u8 {
+        match self {
+            Self::Cannon => CANNON_GAME_TYPE,
+            Self::Asterisc => ASTERISC_GAME_TYPE,
+        }
+    }
+}
+
 /// Configuration for the op-challenger component.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OpChallengerBuilder {
@@ -51,6 +123,58 @@ pub struct OpChallengerBuilder {
     /// Extra arguments to pass to op-challenger.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
+    /// Play permissionless games in addition to permissioned ones, with `vm` traces.
+    ///
+    /// Requires an absolute prestate for the VM and a DisputeGameFactory with an
+    /// implementation of the game type.
+    #[serde(default)]
+    pub fault_proofs_enabled: bool,
+    /// Fault proof VM of the permissionless games (cannon by default).
+    #[serde(default, skip_serializing_if = "FaultProofVm::is_default")]
+    pub vm: FaultProofVm,
+    /// Permissionless game type to play. Defaults to the VM's game type (0 for cannon,
+    /// 2 for asterisc).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_type: Option<u8>,
+    /// Path (inside the container) to the cannon binary. Defaults to the one shipped
+    /// in the op-challenger image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cannon_bin: Option<String>,
+    /// Path (inside the container) to the op-program server. Defaults to the one
+    /// shipped in the op-challenger image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cannon_server: Option<String>,
+    /// Path (inside the container, the data directory is mounted at `/data`) to the
+    /// cannon absolute prestate. Required when fault proofs are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cannon_prestate: Option<String>,
+    /// L2 RPC for op-program. Defaults to the sequencer op-reth HTTP RPC that
+    /// op-challenger already uses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cannon_l2_rpc: Option<String>,
+    /// Path (inside the container) to the asterisc binary. Defaults to the one shipped
+    /// in the op-challenger image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asterisc_bin: Option<String>,
+    /// Path (inside the container) to the op-program server run by asterisc. Defaults to
+    /// the one shipped in the op-challenger image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asterisc_server: Option<String>,
+    /// Path (inside the container) to the asterisc absolute prestate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asterisc_prestate: Option<String>,
+    /// Absolute prestate on the host, mounted read-only in the container. Used when the
+    /// prestate path of the VM is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prestate_file: Option<PathBuf>,
+    /// URL of the absolute prestate, downloaded once into the data directory. Used when
+    /// neither the prestate path of the VM nor `prestate_file` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prestate_url: Option<String>,
+    /// L1 beacon API URL. Anvil has no beacon API, so this defaults to the L1 RPC URL,
+    /// which is enough for permissioned games but not for cannon traces that need blobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_beacon: Option<String>,
 }
 
 /// Default Docker image for op-challenger.
@@ -69,6 +193,19 @@ impl Default for OpChallengerBuilder {
             metrics_host_port: Some(0),
             log_level: None,
             extra_args: Vec::new(),
+            fault_proofs_enabled: false,
+            vm: FaultProofVm::default(),
+            game_type: None,
+            cannon_bin: None,
+            cannon_server: None,
+            cannon_prestate: None,
+            cannon_l2_rpc: None,
+            asterisc_bin: None,
+            asterisc_server: None,
+            asterisc_prestate: None,
+            prestate_file: None,
+            prestate_url: None,
+            l1_beacon: None,
         }
     }
 }
@@ -85,7 +222,117 @@ pub struct OpChallengerHandler {
     pub deploy_timings: ContainerDeployTimings,
 }
 
+impl ScrapeTarget for OpChallengerHandler {
+    fn metrics_target(&self) -> Option<MetricsTarget> {
+        Some(MetricsTarget {
+            job_name: self.container_name.clone(),
+            container_name: self.container_name.clone(),
+            port: self.metrics_url.port()?,
+            service_label: "op-challenger".to_string(),
+            layer_label: "challenger".to_string(),
+        })
+    }
+}
+
 impl OpChallengerBuilder {
+    /// Enable or disable permissionless fault proofs.
+    pub fn fault_proofs(mut self, enabled: bool) -> Self {
+        self.fault_proofs_enabled = enabled;
+        self
+    }
+
+    /// Set the fault proof VM of the permissionless games.
+    pub fn vm(mut self, vm: FaultProofVm) -> Self {
+        self.vm = vm;
+        self
+    }
+
+    /// Set the permissionless game type, instead of the VM's default one.
+    pub fn game_type(mut self, game_type: u8) -> Self {
+        self.game_type = Some(game_type);
+        self
+    }
+
+    /// Set the cannon absolute prestate path (inside the container).
+    pub fn cannon_prestate(mut self, prestate: impl Into<String>) -> Self {
+        self.cannon_prestate = Some(prestate.into());
+        self
+    }
+
+    /// Set the asterisc absolute prestate path (inside the container).
+    pub fn asterisc_prestate(mut self, prestate: impl Into<String>) -> Self {
+        self.asterisc_prestate = Some(prestate.into());
+        self
+    }
+
+    /// Mount an absolute prestate from the host.
+    pub fn prestate_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.prestate_file = Some(path.into());
+        self
+    }
+
+    /// Download the absolute prestate from `url` before op-challenger starts.
+    pub fn prestate_url(mut self, url: impl Into<String>) -> Self {
+        self.prestate_url = Some(url.into());
+        self
+    }
+
+    /// Game type of the permissionless games.
+    pub fn permissionless_game_type(&self) -> u8 {
+        self.game_type.unwrap_or_else(|| self.vm.game_type())
+    }
+
+    /// Game types op-challenger plays.
+    pub fn game_types(&self) -> Vec<u8> {
+        if self.fault_proofs_enabled {
+            vec![self.permissionless_game_type(), PERMISSIONED_GAME_TYPE]
+        } else {
+            vec![PERMISSIONED_GAME_TYPE]
+        }
+    }
+
+    /// Where the absolute prestate comes from, in order of precedence: the prestate path
+    /// of the VM, `prestate_file`, then `prestate_url`.
+    fn prestate_source(&self) -> Option<PrestateSource<'_>> {
+        let vm_prestate = match self.vm {
+            FaultProofVm::Cannon => &self.cannon_prestate,
+            FaultProofVm::Asterisc => &self.asterisc_prestate,
+        };
+        if let Some(ref path) = *vm_prestate {
+            Some(PrestateSource::Container(path))
+        } else if let Some(ref path) = self.prestate_file {
+            Some(PrestateSource::Host(path))
+        } else {
+            self.prestate_url.as_deref().map(PrestateSource::Url)
+        }
+    }
+
+    /// Path of the absolute prestate inside the container.
+    fn prestate_path(&self) -> Result<String, anyhow::Error> {
+        match self.prestate_source() {
+            Some(PrestateSource::Container(path)) => Ok(path.clone()),
+            Some(PrestateSource::Host(path)) => {
+                let name = path.file_name().with_context(|| {
+                    format!(
+                        "op-challenger prestate_file {} has no file name",
+                        path.display()
+                    )
+                })?;
+                Ok(format!("{}/{}", PRESTATE_MOUNT_DIR, name.to_string_lossy()))
+            }
+            Some(PrestateSource::Url(url)) => Ok(format!(
+                "/data/{}/{}",
+                PRESTATE_DOWNLOAD_DIR,
+                prestate_url_file_name(url)?
+            )),
+            None => anyhow::bail!(
+                "op-challenger fault proofs require an absolute prestate: set {}_prestate \
+                 (path inside the container, e.g. /data/prestate.bin.gz), prestate_file or prestate_url",
+                self.vm
+            ),
+        }
+    }
+
     /// Build the Docker command arguments for op-challenger.
     pub fn build_cmd(
         &self,
@@ -108,11 +355,46 @@ impl OpChallengerBuilder {
             container_config_path.join("rollup.json").to_string_lossy(),
             container_config_path.join("genesis.json").to_string_lossy(),
         )
-        .trace_type("permissioned")
-        .game_allowlist([254]) // Permissioned game type
+        .game_allowlist(self.game_types())
         .metrics(true, "0.0.0.0", self.metrics_port)
         .extra_args(self.extra_args.clone());
 
+        if self.fault_proofs_enabled {
+            let prestate = self.prestate_path()?;
+            cmd_builder = cmd_builder.trace_types([self.vm.trace_type(), "permissioned"]);
+            cmd_builder = match self.vm {
+                FaultProofVm::Cannon => cmd_builder.cannon(CannonArgs {
+                    bin: self
+                        .cannon_bin
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_CANNON_BIN.to_string()),
+                    server: self
+                        .cannon_server
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_CANNON_SERVER.to_string()),
+                    prestate,
+                    l2_rpc: self.cannon_l2_rpc.clone(),
+                }),
+                FaultProofVm::Asterisc => cmd_builder.asterisc(AsteriscArgs {
+                    bin: self
+                        .asterisc_bin
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_ASTERISC_BIN.to_string()),
+                    server: self
+                        .asterisc_server
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_ASTERISC_SERVER.to_string()),
+                    prestate,
+                }),
+            };
+        } else {
+            cmd_builder = cmd_builder.trace_type("permissioned");
+        }
+
+        if let Some(ref beacon) = self.l1_beacon {
+            cmd_builder = cmd_builder.l1_beacon(beacon);
+        }
+
         if let Some(ref level) = self.log_level {
             cmd_builder = cmd_builder.log_level(level);
         }
@@ -141,6 +423,50 @@ impl KupcakeService for OpChallengerBuilder {
     ) -> Result<OpChallengerHandler, anyhow::Error> {
         let container_config_path = PathBuf::from("/data");
 
+        let mut prestate_bind = None;
+        if self.fault_proofs_enabled {
+            if self.l1_beacon.is_none() {
+                tracing::warn!(
+                    vm = %self.vm,
+                    "op-challenger fault proofs enabled without l1_beacon; \
+                     traces that need blob data will fail"
+                );
+            }
+            let game_type = self.permissionless_game_type();
+            match input.l1_host_url {
+                Some(ref l1_host_url) => {
+                    let dgf_address = super::read_dgf_address(host_config_path)?;
+                    check_game_type_supported(
+                        &docker.rpc_client,
+                        l1_host_url,
+                        &dgf_address,
+                        game_type,
+                    )
+                    .await?;
+                }
+                None => tracing::warn!(
+                    game_type,
+                    "L1 RPC is not published to the host; skipping DisputeGameFactory game type check"
+                ),
+            }
+
+            match self.prestate_source() {
+                Some(PrestateSource::Host(path)) => {
+                    let host_path = std::fs::canonicalize(path).with_context(|| {
+                        format!("op-challenger prestate_file {} not found", path.display())
+                    })?;
+                    prestate_bind = Some((host_path, PathBuf::from(self.prestate_path()?)));
+                }
+                Some(PrestateSource::Url(url)) => {
+                    let dest = host_config_path
+                        .join(PRESTATE_DOWNLOAD_DIR)
+                        .join(prestate_url_file_name(url)?);
+                    download_prestate(url, &dest).await?;
+                }
+                _ => {}
+            }
+        }
+
         let cmd = self.build_cmd(host_config_path, &input)?;
 
         // op-challenger doesn't have an RPC server, only metrics
@@ -156,7 +482,14 @@ impl KupcakeService for OpChallengerBuilder {
             .cmd(cmd)
             .ports(port_mappings)
             .expose(ExposedPort::tcp(self.metrics_port))
-            .bind(host_config_path, &container_config_path, "rw");
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?);
+        let service_config = match prestate_bind {
+            Some((host_path, container_path)) => {
+                service_config.bind(&host_path, &container_path, "ro")
+            }
+            None => service_config,
+        };
 
         let (handler, timings) = service::deploy_container(
             docker,
@@ -184,3 +517,165 @@ impl KupcakeService for OpChallengerBuilder {
         })
     }
 }
+
+/// Source of the absolute prestate of the fault proof VM.
+enum PrestateSource<'a> {
+    /// Path inside the container.
+    Container(&'a String),
+    /// File on the host, mounted in the container.
+    Host(&'a Path),
+    /// URL downloaded into the data directory.
+    Url(&'a str),
+}
+
+/// File name of a prestate URL, e.g. `prestate-mt64.bin.gz`.
+fn prestate_url_file_name(url: &str) -> Result<String, anyhow::Error> {
+    let parsed = Url::parse(url).with_context(|| format!("Invalid prestate_url '{}'", url))?;
+    parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .with_context(|| format!("prestate_url '{}' does not end with a file name", url))
+}
+
+/// Download the absolute prestate at `url` to `dest`, unless it is already there.
+async fn download_prestate(url: &str, dest: &Path) -> Result<(), anyhow::Error> {
+    if dest.exists() {
+        tracing::debug!(path = %dest.display(), "Reusing downloaded op-challenger prestate");
+        return Ok(());
+    }
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    tracing::info!(url, "Downloading op-challenger absolute prestate");
+    let bytes = reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to download prestate from {}", url))?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to download prestate from {}", url))?;
+
+    // Write to a temporary file first so that an interrupted download is not reused.
+    let partial = dest.with_extension("partial");
+    std::fs::write(&partial, &bytes)
+        .with_context(|| format!("Failed to write {}", partial.display()))?;
+    std::fs::rename(&partial, dest)
+        .with_context(|| format!("Failed to write {}", dest.display()))?;
+    Ok(())
+}
+
+/// Check that the DisputeGameFactory has an implementation registered for a game type.
+async fn check_game_type_supported(
+    client: &reqwest::Client,
+    l1_rpc_url: &str,
+    dgf_address: &str,
+    game_type: u8,
+) -> Result<(), anyhow::Error> {
+    let result: String = crate::rpc::json_rpc_call(
+        client,
+        l1_rpc_url,
+        "eth_call",
+        vec![
+            serde_json::json!({ "to": dgf_address, "data": game_impls_calldata(game_type) }),
+            serde_json::json!("latest"),
+        ],
+    )
+    .await
+    .context("Failed to query DisputeGameFactory game implementation")?;
+
+    if is_zero_word(&result) {
+        anyhow::bail!(
+            "DisputeGameFactory {} has no implementation for game type {}; \
+             deploy it with permissionless fault proofs, set game_type or disable fault_proofs_enabled",
+            dgf_address,
+            game_type
+        );
+    }
+    Ok(())
+}
+
+/// ABI-encode a `gameImpls(uint32)` call.
+fn game_impls_calldata(game_type: u8) -> String {
+    let selector = &alloy_core::primitives::keccak256("gameImpls(uint32)")[..4];
+    format!("0x{}{:064x}", hex::encode(selector), game_type)
+}
+
+/// Whether an `eth_call` result is empty or all zeros.
+fn is_zero_word(result: &str) -> bool {
+    result.trim_start_matches("0x").chars().all(|c| c == '0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_types() {
+        let challenger = OpChallengerBuilder::default();
+        assert_eq!(challenger.game_types(), vec![PERMISSIONED_GAME_TYPE]);
+        assert_eq!(
+            challenger.fault_proofs(true).game_types(),
+            vec![CANNON_GAME_TYPE, PERMISSIONED_GAME_TYPE]
+        );
+
+        let asterisc = OpChallengerBuilder::default()
+            .fault_proofs(true)
+            .vm(FaultProofVm::Asterisc);
+        assert_eq!(
+            asterisc.game_types(),
+            vec![ASTERISC_GAME_TYPE, PERMISSIONED_GAME_TYPE]
+        );
+        assert_eq!(
+            asterisc.game_type(3).game_types(),
+            vec![3, PERMISSIONED_GAME_TYPE]
+        );
+    }
+
+    #[test]
+    fn test_prestate_path() {
+        let challenger = OpChallengerBuilder::default().fault_proofs(true);
+        assert!(challenger.prestate_path().is_err());
+
+        let from_url = challenger
+            .clone()
+            .prestate_url("https://example.com/prestates/prestate-rv64.bin.gz");
+        assert_eq!(
+            from_url.prestate_path().unwrap(),
+            "/data/prestates/prestate-rv64.bin.gz"
+        );
+
+        let from_file = from_url.prestate_file("/tmp/prestate-mt64.bin.gz");
+        assert_eq!(
+            from_file.prestate_path().unwrap(),
+            "/prestate/prestate-mt64.bin.gz"
+        );
+
+        // The prestate of the selected VM wins over the shared sources.
+        let explicit = from_file
+            .asterisc_prestate("/data/asterisc.bin.gz")
+            .vm(FaultProofVm::Asterisc);
+        assert_eq!(explicit.prestate_path().unwrap(), "/data/asterisc.bin.gz");
+
+        assert!(
+            OpChallengerBuilder::default()
+                .prestate_url("https://example.com/")
+                .prestate_path()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_game_impls_calldata() {
+        assert_eq!(
+            game_impls_calldata(CANNON_GAME_TYPE),
+            format!("0x1b685b9e{}", "0".repeat(64))
+        );
+        assert!(is_zero_word(&format!("0x{}", "0".repeat(64))));
+        assert!(is_zero_word("0x"));
+        assert!(!is_zero_word(&format!("0x{}1", "0".repeat(63))));
+    }
+}
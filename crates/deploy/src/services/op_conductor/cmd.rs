@@ -1,5 +1,7 @@
 //! Command builder for op-conductor.
 
+use crate::services::extra_args::merge_extra_args;
+
 /// Builder for op-conductor commands.
 #[derive(Debug, Clone)]
 pub struct OpConductorCmdBuilder {
@@ -29,6 +31,12 @@ pub struct OpConductorCmdBuilder {
     healthcheck_unsafe_interval: String,
     /// Minimum number of peers required to be considered healthy.
     healthcheck_min_peer_count: String,
+    /// Interval between Raft snapshots (Go duration). op-conductor default when None.
+    raft_snapshot_interval: Option<String>,
+    /// Raft heartbeat timeout (Go duration). op-conductor default when None.
+    raft_heartbeat_timeout: Option<String>,
+    /// Raft leader lease timeout (Go duration). op-conductor default when None.
+    raft_lease_timeout: Option<String>,
     /// Paused mode - start with sequencer paused.
     paused: bool,
     /// Log level.
@@ -60,6 +68,9 @@ impl OpConductorCmdBuilder {
             healthcheck_interval: "5".to_string(),
             healthcheck_unsafe_interval: "600".to_string(),
             healthcheck_min_peer_count: "1".to_string(),
+            raft_snapshot_interval: None,
+            raft_heartbeat_timeout: None,
+            raft_lease_timeout: None,
             paused: false,
             log_level: "DEBUG".to_string(),
             extra_args: Vec::new(),
@@ -116,6 +127,24 @@ impl OpConductorCmdBuilder {
         self
     }
 
+    /// Set the Raft snapshot interval (e.g. "120s").
+    pub fn raft_snapshot_interval(mut self, interval: Option<impl Into<String>>) -> Self {
+        self.raft_snapshot_interval = interval.map(Into::into);
+        self
+    }
+
+    /// Set the Raft heartbeat timeout (e.g. "1s").
+    pub fn raft_heartbeat_timeout(mut self, timeout: Option<impl Into<String>>) -> Self {
+        self.raft_heartbeat_timeout = timeout.map(Into::into);
+        self
+    }
+
+    /// Set the Raft leader lease timeout (e.g. "500ms").
+    pub fn raft_lease_timeout(mut self, timeout: Option<impl Into<String>>) -> Self {
+        self.raft_lease_timeout = timeout.map(Into::into);
+        self
+    }
+
     /// Set whether to start in paused mode.
     pub fn paused(mut self, paused: bool) -> Self {
         self.paused = paused;
@@ -128,7 +157,7 @@ impl OpConductorCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -178,13 +207,22 @@ impl OpConductorCmdBuilder {
             cmd.push("--raft.bootstrap".to_string());
         }
 
+        for (flag, value) in [
+            ("--raft.snapshot-interval", self.raft_snapshot_interval),
+            ("--raft.heartbeat-timeout", self.raft_heartbeat_timeout),
+            ("--raft.lease-timeout", self.raft_lease_timeout),
+        ] {
+            if let Some(value) = value {
+                cmd.push(flag.to_string());
+                cmd.push(value);
+            }
+        }
+
         if self.paused {
             cmd.push("--paused".to_string());
         }
 
-        cmd.extend(self.extra_args);
-
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
@@ -223,5 +261,26 @@ mod tests {
         .build();
 
         assert!(!cmd.contains(&"--raft.bootstrap".to_string()));
+        assert!(!cmd.contains(&"--raft.heartbeat-timeout".to_string()));
+    }
+
+    #[test]
+    fn test_op_conductor_cmd_builder_raft_tuning() {
+        let cmd = OpConductorCmdBuilder::new(
+            "http://localhost:7545",
+            "http://localhost:8545",
+            "sequencer-0",
+            "/data/raft",
+            "/data/rollup.json",
+        )
+        .raft_snapshot_interval(Some("300s"))
+        .raft_heartbeat_timeout(Some("3s"))
+        .raft_lease_timeout(None::<String>)
+        .build();
+
+        let joined = cmd.join(" ");
+        assert!(joined.contains("--raft.snapshot-interval 300s"));
+        assert!(joined.contains("--raft.heartbeat-timeout 3s"));
+        assert!(!joined.contains("--raft.lease-timeout"));
     }
 }
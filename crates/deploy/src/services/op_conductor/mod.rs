@@ -39,6 +39,17 @@ pub const DEFAULT_DOCKER_IMAGE: &str =
 /// Default Docker tag for op-conductor.
 pub const DEFAULT_DOCKER_TAG: &str = "v0.9.0";
 
+/// L2 block time (seconds) at or below which
+/// [`OpConductorBuilder::raft_tuning_for_l2_block_time`] relaxes the Raft timings.
+pub const FAST_BLOCK_TIME_SECS: u64 = 2;
+/// Raft heartbeat timeout used with fast block times (op-conductor default: 1s).
+pub const FAST_BLOCK_RAFT_HEARTBEAT_TIMEOUT: &str = "3s";
+/// Raft leader lease timeout used with fast block times (op-conductor default: 500ms).
+/// Must not exceed the heartbeat timeout.
+pub const FAST_BLOCK_RAFT_LEASE_TIMEOUT: &str = "1s";
+/// Raft snapshot interval used with fast block times (op-conductor default: 120s).
+pub const FAST_BLOCK_RAFT_SNAPSHOT_INTERVAL: &str = "300s";
+
 /// Information about a sequencer node that the conductor manages.
 #[derive(Debug, Clone)]
 pub struct SequencerInfo {
@@ -75,6 +86,18 @@ pub struct OpConductorBuilder {
     pub healthcheck_unsafe_interval: String,
     /// Minimum number of peers required to be considered healthy.
     pub healthcheck_min_peer_count: String,
+    /// Interval between Raft snapshots (Go duration, e.g. "120s").
+    /// None uses the op-conductor default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raft_snapshot_interval: Option<String>,
+    /// Raft heartbeat timeout (Go duration, e.g. "1s").
+    /// None uses the op-conductor default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raft_heartbeat_timeout: Option<String>,
+    /// Raft leader lease timeout (Go duration, e.g. "500ms"). Must not exceed the
+    /// heartbeat timeout. None uses the op-conductor default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raft_lease_timeout: Option<String>,
     /// Log level for op-conductor (e.g., "INFO", "DEBUG").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub log_level: Option<String>,
@@ -96,6 +119,9 @@ impl Default for OpConductorBuilder {
             healthcheck_interval: "5".to_string(),
             healthcheck_unsafe_interval: "600".to_string(),
             healthcheck_min_peer_count: "1".to_string(),
+            raft_snapshot_interval: None,
+            raft_heartbeat_timeout: None,
+            raft_lease_timeout: None,
             log_level: None,
             extra_args: Vec::new(),
         }
@@ -117,6 +143,24 @@ pub struct OpConductorHandler {
 }
 
 impl OpConductorBuilder {
+    /// Fill in relaxed Raft timings when the L2 block time is at most
+    /// [`FAST_BLOCK_TIME_SECS`].
+    ///
+    /// With fast blocks on a loaded machine (e.g. CI runners) the default 1s heartbeat
+    /// is regularly missed and leadership keeps moving between sequencers. Timings that
+    /// are already set are kept.
+    pub fn raft_tuning_for_l2_block_time(mut self, l2_block_time: u64) -> Self {
+        if l2_block_time <= FAST_BLOCK_TIME_SECS {
+            self.raft_heartbeat_timeout
+                .get_or_insert_with(|| FAST_BLOCK_RAFT_HEARTBEAT_TIMEOUT.to_string());
+            self.raft_lease_timeout
+                .get_or_insert_with(|| FAST_BLOCK_RAFT_LEASE_TIMEOUT.to_string());
+            self.raft_snapshot_interval
+                .get_or_insert_with(|| FAST_BLOCK_RAFT_SNAPSHOT_INTERVAL.to_string());
+        }
+        self
+    }
+
     /// Build the Docker command arguments for op-conductor.
     pub fn build_cmd(
         &self,
@@ -142,6 +186,9 @@ impl OpConductorBuilder {
         .healthcheck_interval(&self.healthcheck_interval)
         .healthcheck_unsafe_interval(&self.healthcheck_unsafe_interval)
         .healthcheck_min_peer_count(&self.healthcheck_min_peer_count)
+        .raft_snapshot_interval(self.raft_snapshot_interval.as_deref())
+        .raft_heartbeat_timeout(self.raft_heartbeat_timeout.as_deref())
+        .raft_lease_timeout(self.raft_lease_timeout.as_deref())
         .log_level(self.log_level.as_deref().unwrap_or("DEBUG"))
         .extra_args(self.extra_args.clone())
         .build())
@@ -184,7 +231,8 @@ impl KupcakeService for OpConductorBuilder {
             .ports(port_mappings)
             .expose(ExposedPort::tcp(self.rpc_port))
             .expose(ExposedPort::tcp(self.consensus_port))
-            .bind(host_config_path, &container_config_path, "rw");
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?);
 
         let (handler, timings) = service::deploy_container(
             docker,
@@ -219,3 +267,26 @@ impl KupcakeService for OpConductorBuilder {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raft_tuning_for_l2_block_time() {
+        let fast = OpConductorBuilder::default().raft_tuning_for_l2_block_time(1);
+        assert_eq!(fast.raft_heartbeat_timeout.as_deref(), Some("3s"));
+        assert_eq!(fast.raft_lease_timeout.as_deref(), Some("1s"));
+        assert_eq!(fast.raft_snapshot_interval.as_deref(), Some("300s"));
+
+        let slow = OpConductorBuilder::default().raft_tuning_for_l2_block_time(12);
+        assert_eq!(slow, OpConductorBuilder::default());
+
+        let custom = OpConductorBuilder {
+            raft_heartbeat_timeout: Some("10s".to_string()),
+            ..Default::default()
+        }
+        .raft_tuning_for_l2_block_time(1);
+        assert_eq!(custom.raft_heartbeat_timeout.as_deref(), Some("10s"));
+    }
+}
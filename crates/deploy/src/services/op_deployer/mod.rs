@@ -6,11 +6,14 @@ use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    docker::{CreateAndStartContainerOptions, DockerImage, KupDocker, ServiceConfig},
+    docker::{
+        CreateAndStartContainerOptions, DockerImage, KupDocker, ServiceConfig, host_path_owner,
+    },
     fs::FsHandler,
+    verification::DeployedAddresses,
 };
 
-use crate::AccountInfo;
+use crate::{AccountInfo, AltDaConfig};
 
 use super::{
     anvil::{AnvilAccounts, AnvilHandler},
@@ -19,45 +22,11 @@ use super::{
 
 /// Create named accounts from a vector of account infos.
 ///
-/// Maps account infos to OP Stack participant roles by index.
-/// Returns an error if fewer than 10 accounts are provided.
+/// Forwards to [`AnvilAccounts::from_accounts`], which owns the index-to-role mapping.
 pub fn anvil_accounts_from_infos(
     accounts: Vec<AccountInfo>,
 ) -> Result<AnvilAccounts, anyhow::Error> {
-    if accounts.len() < AnvilAccounts::MIN_REQUIRED_ACCOUNTS {
-        anyhow::bail!(
-            "Not enough accounts provided. Need at least {}, got {}",
-            AnvilAccounts::MIN_REQUIRED_ACCOUNTS,
-            accounts.len()
-        );
-    }
-
-    let mut iter = accounts.into_iter();
-
-    // Length was validated above, so these nexts are guaranteed to succeed.
-    let result = AnvilAccounts {
-        deployer: iter.next().context("missing deployer account")?,
-        l1_fee_vault_recipient: iter
-            .next()
-            .context("missing l1_fee_vault_recipient account")?,
-        sequencer_fee_vault_recipient: iter
-            .next()
-            .context("missing sequencer_fee_vault_recipient account")?,
-        l1_proxy_admin_owner: iter
-            .next()
-            .context("missing l1_proxy_admin_owner account")?,
-        l2_proxy_admin_owner: iter
-            .next()
-            .context("missing l2_proxy_admin_owner account")?,
-        system_config_owner: iter.next().context("missing system_config_owner account")?,
-        unsafe_block_signer: iter.next().context("missing unsafe_block_signer account")?,
-        batcher: iter.next().context("missing batcher account")?,
-        proposer: iter.next().context("missing proposer account")?,
-        challenger: iter.next().context("missing challenger account")?,
-        extra_accounts: iter.collect(),
-    };
-
-    Ok(result)
+    AnvilAccounts::from_accounts(accounts)
 }
 
 /// Default Docker image for op-deployer.
@@ -66,6 +35,9 @@ pub const DEFAULT_DOCKER_IMAGE: &str =
 /// Default Docker tag for op-deployer.
 pub const DEFAULT_DOCKER_TAG: &str = "v0.5.0-rc.2";
 
+/// L2 block time op-deployer uses when the intent does not override it, in seconds.
+pub const DEFAULT_L2_BLOCK_TIME_SECS: u64 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct IntentFile {
@@ -83,6 +55,9 @@ struct IntentFile {
     #[serde(skip_serializing_if = "Option::is_none")]
     superchain_roles: Option<SuperchainRoles>,
     chains: Vec<ChainConfig>,
+    /// Intent fields kupcake does not model, kept as-is.
+    #[serde(flatten)]
+    extra: toml::Table,
 }
 
 /// Superchain-level roles for custom intent type.
@@ -113,6 +88,9 @@ struct ChainConfig {
     min_base_fee: u64,
     da_footprint_gas_scalar: u64,
     roles: ChainRoles,
+    /// Chain fields kupcake does not model (e.g. `deployOverrides`), kept as-is.
+    #[serde(flatten)]
+    extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +105,288 @@ struct ChainRoles {
     challenger: String,
 }
 
+/// Check that an intent document targets the given L1 chain.
+fn check_intent_l1_chain_id(intent: &toml::Value, l1_chain_id: u64) -> Result<(), anyhow::Error> {
+    let intent_chain_id = intent
+        .get("l1ChainID")
+        .and_then(|id| id.as_integer())
+        .context("Intent file has no l1ChainID")?;
+    if u64::try_from(intent_chain_id).ok() != Some(l1_chain_id) {
+        anyhow::bail!(
+            "Intent file targets L1 chain {}, but the deployment uses L1 chain {}",
+            intent_chain_id,
+            l1_chain_id
+        );
+    }
+    Ok(())
+}
+
+/// Check that the chains of an intent document are the deployment's L2 chains, in order.
+fn check_intent_l2_chain_ids(
+    intent: &toml::Value,
+    l2_chain_ids: &[u64],
+) -> Result<(), anyhow::Error> {
+    let chains = intent
+        .get("chains")
+        .and_then(|chains| chains.as_array())
+        .context("Intent file has no chains")?;
+    let intent_chain_ids = chains
+        .iter()
+        .map(|chain| {
+            let id = chain
+                .get("id")
+                .and_then(|id| id.as_str())
+                .context("Intent chain has no id")?;
+            let digits = id.strip_prefix("0x").unwrap_or(id).trim_start_matches('0');
+            if digits.is_empty() {
+                return Ok(0);
+            }
+            u64::from_str_radix(digits, 16)
+                .with_context(|| format!("Invalid chain id in intent file: {id}"))
+        })
+        .collect::<Result<Vec<u64>, anyhow::Error>>()?;
+    if intent_chain_ids != l2_chain_ids {
+        anyhow::bail!(
+            "Intent file targets L2 chains {:?}, but the deployment uses L2 chains {:?}",
+            intent_chain_ids,
+            l2_chain_ids
+        );
+    }
+    Ok(())
+}
+
+/// Set `deployOverrides.l2BlockTime` on every chain of an intent document.
+fn set_l2_block_time(intent: &mut toml::Value, l2_block_time: u64) -> Result<(), anyhow::Error> {
+    let chains = intent
+        .get_mut("chains")
+        .and_then(|chains| chains.as_array_mut())
+        .context("Intent file has no chains")?;
+
+    for chain in chains {
+        let chain = chain
+            .as_table_mut()
+            .context("Intent chain is not a table")?;
+        let overrides = chain
+            .entry("deployOverrides")
+            .or_insert_with(|| toml::Value::Table(Default::default()))
+            .as_table_mut()
+            .context("Intent deployOverrides is not a table")?;
+        overrides.insert(
+            "l2BlockTime".to_string(),
+            toml::Value::Integer(
+                i64::try_from(l2_block_time).context("l2_block_time overflows i64")?,
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// Set `sequencerFeeVaultRecipient` on every chain of an intent document.
+fn set_sequencer_fee_recipient(
+    intent: &mut toml::Value,
+    recipient: &str,
+) -> Result<(), anyhow::Error> {
+    let chains = intent
+        .get_mut("chains")
+        .and_then(|chains| chains.as_array_mut())
+        .context("Intent file has no chains")?;
+
+    for chain in chains {
+        chain
+            .as_table_mut()
+            .context("Intent chain is not a table")?
+            .insert(
+                "sequencerFeeVaultRecipient".to_string(),
+                toml::Value::String(recipient.to_lowercase()),
+            );
+    }
+    Ok(())
+}
+
+/// Set the gas limit and EIP-1559 parameters on every chain of an intent document.
+///
+/// The gas limit is written both to `gasLimit` (the `SystemConfig` value) and to
+/// `deployOverrides.l2GenesisBlockGasLimit`, so that the genesis block already uses it.
+/// The denominator is written to `eip1559Denominator` and `eip1559DenominatorCanyon`,
+/// since Canyon is active from genesis.
+fn set_chain_fee_params(
+    intent: &mut toml::Value,
+    gas_limit: Option<u64>,
+    eip1559_denominator: Option<u64>,
+    eip1559_elasticity: Option<u64>,
+) -> Result<(), anyhow::Error> {
+    let to_integer = |value: u64, field: &str| {
+        i64::try_from(value).with_context(|| format!("{field} overflows i64"))
+    };
+    let chains = intent
+        .get_mut("chains")
+        .and_then(|chains| chains.as_array_mut())
+        .context("Intent file has no chains")?;
+
+    for chain in chains {
+        let chain = chain
+            .as_table_mut()
+            .context("Intent chain is not a table")?;
+        if let Some(denominator) = eip1559_denominator {
+            let denominator = to_integer(denominator, "eip1559_denominator")?;
+            chain.insert(
+                "eip1559Denominator".to_string(),
+                toml::Value::Integer(denominator),
+            );
+            chain.insert(
+                "eip1559DenominatorCanyon".to_string(),
+                toml::Value::Integer(denominator),
+            );
+        }
+        if let Some(elasticity) = eip1559_elasticity {
+            chain.insert(
+                "eip1559Elasticity".to_string(),
+                toml::Value::Integer(to_integer(elasticity, "eip1559_elasticity")?),
+            );
+        }
+        if let Some(gas_limit) = gas_limit {
+            let gas_limit = to_integer(gas_limit, "gas_limit")?;
+            chain.insert("gasLimit".to_string(), toml::Value::Integer(gas_limit));
+            chain
+                .entry("deployOverrides")
+                .or_insert_with(|| toml::Value::Table(Default::default()))
+                .as_table_mut()
+                .context("Intent deployOverrides is not a table")?
+                .insert(
+                    "l2GenesisBlockGasLimit".to_string(),
+                    toml::Value::Integer(gas_limit),
+                );
+        }
+    }
+    Ok(())
+}
+
+/// Set `dangerousAltDAConfig` on every chain of an intent document.
+fn set_alt_da(intent: &mut toml::Value, alt_da: &AltDaConfig) -> Result<(), anyhow::Error> {
+    let table = alt_da.intent_table()?;
+    let chains = intent
+        .get_mut("chains")
+        .and_then(|chains| chains.as_array_mut())
+        .context("Intent file has no chains")?;
+
+    for chain in chains {
+        chain
+            .as_table_mut()
+            .context("Intent chain is not a table")?
+            .insert(
+                "dangerousAltDAConfig".to_string(),
+                toml::Value::Table(table.clone()),
+            );
+    }
+    Ok(())
+}
+
+/// Schemes accepted by op-deployer for `l1ContractsLocator` / `l2ContractsLocator`,
+/// besides the `embedded` artifacts bundled with the op-deployer image.
+const LOCATOR_SCHEMES: &[&str] = &["tag://", "file://", "http://", "https://"];
+
+/// Container directory under which `file://` contract artifacts are mounted.
+const CONTAINER_ARTIFACTS_DIR: &str = "/artifacts";
+
+/// Whether `locator` is a contract artifacts locator op-deployer understands.
+pub fn is_valid_contracts_locator(locator: &str) -> bool {
+    locator == "embedded"
+        || LOCATOR_SCHEMES
+            .iter()
+            .any(|scheme| locator.len() > scheme.len() && locator.starts_with(scheme))
+}
+
+/// Host directory of a `file://` locator, made absolute. None for other locators.
+fn locator_host_path(locator: &str) -> Result<Option<PathBuf>, anyhow::Error> {
+    locator
+        .strip_prefix("file://")
+        .map(|path| {
+            std::path::absolute(path)
+                .with_context(|| format!("Failed to make contracts locator {} absolute", locator))
+        })
+        .transpose()
+}
+
+/// Locator as seen from the op-deployer container: host `file://` directories are
+/// mounted under [`CONTAINER_ARTIFACTS_DIR`]/`<layer>`, other locators are unchanged.
+fn container_locator(locator: &str, layer: &str) -> String {
+    if locator.starts_with("file://") {
+        format!("file://{}/{}", CONTAINER_ARTIFACTS_DIR, layer)
+    } else {
+        locator.to_string()
+    }
+}
+
+/// Set the top-level contract artifacts locators of an intent document.
+fn set_contracts_locators(
+    intent: &mut toml::Value,
+    l1_locator: Option<&str>,
+    l2_locator: Option<&str>,
+) -> Result<(), anyhow::Error> {
+    let root = intent
+        .as_table_mut()
+        .context("Intent file is not a table")?;
+    for (key, locator, layer) in [
+        ("l1ContractsLocator", l1_locator, "l1"),
+        ("l2ContractsLocator", l2_locator, "l2"),
+    ] {
+        if let Some(locator) = locator {
+            root.insert(
+                key.to_string(),
+                toml::Value::String(container_locator(locator, layer)),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Parse a chain ID as written by op-deployer, a 0x-prefixed 32-byte hex string.
+fn parse_chain_id(id: &str) -> Option<u64> {
+    let id = id.strip_prefix("0x")?.trim_start_matches('0');
+    if id.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(id, 16).ok()
+}
+
+/// Restrict a `state.json` to the deployment of `l2_chain_id`.
+///
+/// The result has the shape of a single-chain deployment, so the services of an additional
+/// chain find their contracts at `opChainDeployments[0]` like those of the primary chain.
+fn single_chain_state(
+    state: &serde_json::Value,
+    l2_chain_id: u64,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let deployment = state["opChainDeployments"]
+        .as_array()
+        .and_then(|deployments| {
+            deployments.iter().find(|deployment| {
+                deployment["id"].as_str().and_then(parse_chain_id) == Some(l2_chain_id)
+            })
+        })
+        .with_context(|| format!("state.json has no deployment of L2 chain {}", l2_chain_id))?;
+
+    let mut state = state.clone();
+    state["opChainDeployments"] = serde_json::Value::Array(vec![deployment.clone()]);
+    Ok(state)
+}
+
+/// Read the contract addresses recorded in op-deployer's `state.json`.
+fn read_deployed_addresses(host_config_path: &Path) -> Result<DeployedAddresses, anyhow::Error> {
+    let state = crate::json_file::read_state_json(host_config_path)?;
+    DeployedAddresses::from_state(&state)
+}
+
+/// Names of the implementation contracts whose address differs between two deployments.
+fn changed_implementations(before: &DeployedAddresses, after: &DeployedAddresses) -> Vec<String> {
+    after
+        .implementations
+        .iter()
+        .filter(|(name, address)| before.implementations.get(*name) != Some(*address))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
 /// Configuration for the OP Deployer service.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OpDeployerConfig {
@@ -134,6 +394,57 @@ pub struct OpDeployerConfig {
     pub docker_image: DockerImage,
     /// Container name for op-deployer.
     pub container_name: String,
+    /// L2 block time in seconds, written to the intent's `deployOverrides.l2BlockTime`.
+    /// None keeps the op-deployer default ([`DEFAULT_L2_BLOCK_TIME_SECS`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_block_time: Option<u64>,
+    /// User-supplied intent.toml. When set, it is copied into the work directory
+    /// instead of running `op-deployer init`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intent_file: Option<PathBuf>,
+    /// Whether to replace the role and fee recipient addresses of the intent with
+    /// the Anvil accounts. Disable to keep the roles of a custom `intent_file`.
+    #[serde(default = "default_patch_roles")]
+    pub patch_roles: bool,
+    /// Recipient of the L2 sequencer fee vault, written to the intent's
+    /// `sequencerFeeVaultRecipient`. None keeps the Anvil account (or the intent's value).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequencer_fee_recipient: Option<String>,
+    /// Contract artifacts used for the L1 contracts, written to the intent's
+    /// `l1ContractsLocator` (e.g. `tag://op-contracts/v3.0.0` or `file:///path/to/forge-artifacts`).
+    /// Host `file://` directories are mounted read-only into the op-deployer container.
+    /// None keeps the op-deployer default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_contracts_locator: Option<String>,
+    /// Contract artifacts used for the L2 predeploys, written to the intent's
+    /// `l2ContractsLocator`. Same format as [`Self::l1_contracts_locator`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_contracts_locator: Option<String>,
+    /// Alt-DA settings, written to the intent's `dangerousAltDAConfig`. None deploys a
+    /// regular rollup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_da: Option<AltDaConfig>,
+    /// Chain IDs of the additional L2 chains, deployed in the same intent as the primary one.
+    /// Chain `i` of this list submits with the accounts of
+    /// [`AnvilAccounts::for_l2_chain`]`(i + 1)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_l2_chain_ids: Vec<u64>,
+    /// L2 block gas limit, written to the intent's `gasLimit` and
+    /// `deployOverrides.l2GenesisBlockGasLimit`. None keeps the intent's value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// EIP-1559 base fee change denominator, written to the intent's `eip1559Denominator`
+    /// and `eip1559DenominatorCanyon`. None keeps the intent's value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eip1559_denominator: Option<u64>,
+    /// EIP-1559 elasticity multiplier, written to the intent's `eip1559Elasticity`.
+    /// None keeps the intent's value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eip1559_elasticity: Option<u64>,
+}
+
+fn default_patch_roles() -> bool {
+    true
 }
 
 impl Default for OpDeployerConfig {
@@ -141,6 +452,17 @@ impl Default for OpDeployerConfig {
         Self {
             docker_image: DockerImage::new(DEFAULT_DOCKER_IMAGE, DEFAULT_DOCKER_TAG),
             container_name: "kupcake-op-deployer".to_string(),
+            l2_block_time: None,
+            intent_file: None,
+            patch_roles: true,
+            sequencer_fee_recipient: None,
+            l1_contracts_locator: None,
+            l2_contracts_locator: None,
+            alt_da: None,
+            additional_l2_chain_ids: Vec::new(),
+            gas_limit: None,
+            eip1559_denominator: None,
+            eip1559_elasticity: None,
         }
     }
 }
@@ -161,15 +483,27 @@ impl OpDeployerConfig {
             "rw",
         );
 
-        // Get current user UID and GID to run container as non-root
-        // This ensures files created by the container have the correct permissions
-        // and can be rewritten by this process.
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::MetadataExt;
-            let metadata = std::fs::metadata(host_config_path)
-                .context("Failed to get metadata for host config path")?;
-            service_config = service_config.user(format!("{}:{}", metadata.uid(), metadata.gid()));
+        // Always run as the owner of the config directory, whatever
+        // `run_as_host_user` says: the files op-deployer creates are rewritten by this
+        // process.
+        service_config = service_config.maybe_user(host_path_owner(host_config_path)?);
+
+        for (locator, layer) in [
+            (&self.l1_contracts_locator, "l1"),
+            (&self.l2_contracts_locator, "l2"),
+        ] {
+            if let Some(host_path) = locator
+                .as_deref()
+                .map(locator_host_path)
+                .transpose()?
+                .flatten()
+            {
+                service_config = service_config.bind(
+                    &host_path,
+                    &Path::new(CONTAINER_ARTIFACTS_DIR).join(layer),
+                    "ro",
+                );
+            }
         }
 
         let handler = docker
@@ -227,7 +561,7 @@ impl OpDeployerConfig {
 
     /// Apply contract deployments to a live L1.
     ///
-    /// SAFETY: Private key in command is from the well-known Anvil test mnemonic.
+    /// SAFETY: Private key in command is an Anvil dev account (the well-known test mnemonic by default).
     async fn apply_contract_deployments(
         &self,
         docker: &mut KupDocker,
@@ -350,6 +684,17 @@ impl OpDeployerConfig {
         genesis_result.context("Op Deployer genesis config file was not created in time")?;
         rollup_result.context("Op Deployer rollup config file was not created in time")?;
 
+        if let Some(ref alt_da) = self.alt_da {
+            let rollup: serde_json::Value = serde_json::from_str(
+                &std::fs::read_to_string(&rollup_file_path)
+                    .context("Failed to read rollup.json")?,
+            )
+            .context("Failed to parse rollup.json")?;
+            alt_da
+                .check_rollup(&rollup)
+                .context("Alt-DA windows of the deployed contracts do not match the config")?;
+        }
+
         tracing::debug!(
             ?genesis_file_path,
             ?rollup_file_path,
@@ -437,7 +782,7 @@ impl OpDeployerConfig {
             .context("Failed to add l1DevGenesisParams to intent")?;
 
         // Apply with --deployment-target genesis (no L1 RPC needed).
-        // SAFETY: Private key in command is from the well-known Anvil test mnemonic.
+        // SAFETY: Private key in command is an Anvil dev account (the well-known test mnemonic by default).
         let container_config_path = PathBuf::from("/data");
         let container_config_path_str = container_config_path.display().to_string();
 
@@ -464,6 +809,88 @@ impl OpDeployerConfig {
         Ok(())
     }
 
+    /// Upgrade the L1 contracts of an existing deployment to `target_release`.
+    ///
+    /// Points the intent's `l1ContractsLocator` at `target_release` (same format as
+    /// [`Self::l1_contracts_locator`]), runs `op-deployer apply` against the already
+    /// deployed L1, then regenerates `genesis.json` and `rollup.json`. The L2 predeploys
+    /// are left untouched. The upgrade is confirmed by comparing the implementation
+    /// addresses of `state.json` before and after; it fails if none changed.
+    ///
+    /// Returns the contract addresses after the upgrade. Restarting the services that
+    /// read the regenerated files is up to the caller.
+    pub async fn upgrade_contracts(
+        &self,
+        docker: &mut KupDocker,
+        host_config_path: &Path,
+        anvil_handler: &AnvilHandler,
+        target_release: &str,
+    ) -> Result<DeployedAddresses, anyhow::Error> {
+        if !is_valid_contracts_locator(target_release) {
+            anyhow::bail!(
+                "L1 contracts locator {:?} is invalid: use \"embedded\", tag://, file://, http:// or https://",
+                target_release
+            );
+        }
+
+        let before = read_deployed_addresses(host_config_path)
+            .context("Cannot upgrade contracts without an existing deployment")?;
+        let l2_chain_id =
+            parse_chain_id(&before.chains[0].id).context("Invalid L2 chain ID in state.json")?;
+
+        // Mounts a file:// target release into the op-deployer containers.
+        let upgraded = Self {
+            l1_contracts_locator: Some(target_release.to_string()),
+            ..self.clone()
+        };
+
+        let intent_path = host_config_path.join("intent.toml");
+        let content = tokio::fs::read_to_string(&intent_path)
+            .await
+            .context("Failed to read intent file")?;
+        let mut doc: toml::Value =
+            toml::from_str(&content).context("Failed to parse intent file")?;
+        set_contracts_locators(&mut doc, Some(target_release), None)?;
+        tokio::fs::write(&intent_path, toml::to_string_pretty(&doc)?)
+            .await
+            .context("Failed to write updated intent file")?;
+
+        tracing::info!(%target_release, "Upgrading L1 contracts...");
+        let container_config_path = PathBuf::from("/data");
+        upgraded
+            .apply_contract_deployments(
+                docker,
+                &self.docker_image,
+                host_config_path,
+                &container_config_path,
+                anvil_handler,
+            )
+            .await
+            .context("Failed to apply contract upgrade")?;
+
+        upgraded
+            .generate_l2_config_files(docker, host_config_path, l2_chain_id)
+            .await
+            .context("Failed to regenerate L2 config files")?;
+
+        let after = read_deployed_addresses(host_config_path)?;
+        let changed = changed_implementations(&before, &after);
+        if changed.is_empty() {
+            anyhow::bail!(
+                "op-deployer apply did not change any implementation contract; \
+                 is {} a different release than the deployed one?",
+                target_release
+            );
+        }
+
+        tracing::info!(
+            %target_release,
+            implementations = %changed.join(", "),
+            "L1 contracts upgraded"
+        );
+        Ok(after)
+    }
+
     /// Generate L2 config files (genesis.json + rollup.json) using op-deployer inspect.
     ///
     /// This can be called after either live or genesis deployment, as long as
@@ -486,6 +913,76 @@ impl OpDeployerConfig {
         .await
     }
 
+    /// Generate the config files of an additional L2 chain into its own directory.
+    ///
+    /// `host_config_path` is the op-deployer work directory, shared by all the chains of the
+    /// intent. `chain_config_path` receives the chain's `genesis.json` and `rollup.json`, and a
+    /// `state.json` restricted to the chain (see [`Self::additional_l2_chain_ids`]), so that it
+    /// has the same layout as the work directory of a single-chain deployment.
+    pub async fn generate_additional_chain_files(
+        &self,
+        docker: &mut KupDocker,
+        host_config_path: &Path,
+        l2_chain_id: u64,
+        chain_config_path: &Path,
+    ) -> Result<(), anyhow::Error> {
+        FsHandler::create_host_config_directory(&chain_config_path.to_path_buf())?;
+
+        let container_config_path = PathBuf::from("/data");
+        let container_config_path_str = container_config_path.display().to_string();
+        for config_type in ["genesis", "rollup"] {
+            let file_name = format!("{}-{}.json", l2_chain_id, config_type);
+            let cmd = vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                format!(
+                    "op-deployer --cache-dir {container_config_path_str}/.cache inspect {config_type} --workdir {container_config_path_str} {l2_chain_id} > {container_config_path_str}/{file_name}",
+                ),
+            ];
+
+            self.run_docker_container(
+                docker,
+                &self.docker_image,
+                &format!(
+                    "{}-inspect-{}-{}",
+                    self.container_name, config_type, l2_chain_id
+                ),
+                host_config_path,
+                &container_config_path,
+                cmd,
+            )
+            .await?;
+
+            let file_path = host_config_path.join(&file_name);
+            FsHandler::wait_for_file(&file_path, std::time::Duration::from_secs(120))
+                .await
+                .with_context(|| format!("{} was not created in time", file_name))?;
+            let target = chain_config_path.join(format!("{}.json", config_type));
+            std::fs::rename(&file_path, &target).with_context(|| {
+                format!(
+                    "Failed to move {} to {}",
+                    file_path.display(),
+                    target.display()
+                )
+            })?;
+        }
+
+        let state = crate::json_file::read_state_json(host_config_path)?;
+        let state = single_chain_state(&state, l2_chain_id)?;
+        std::fs::write(
+            chain_config_path.join("state.json"),
+            serde_json::to_string_pretty(&state)?,
+        )
+        .context("Failed to write the state.json of the L2 chain")?;
+
+        tracing::debug!(
+            l2_chain_id,
+            path = %chain_config_path.display(),
+            "L2 chain config files created"
+        );
+        Ok(())
+    }
+
     /// Generate an intent.toml using op-deployer init with a specific intent type.
     ///
     /// Used in snapshot mode to generate a standard-overrides intent file.
@@ -507,7 +1004,11 @@ impl OpDeployerConfig {
             "--l1-chain-id".to_string(),
             l1_chain_id.to_string(),
             "--l2-chain-ids".to_string(),
-            l2_chain_id.to_string(),
+            std::iter::once(l2_chain_id)
+                .chain(self.additional_l2_chain_ids.iter().copied())
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
             "--workdir".to_string(),
             container_config_path.display().to_string(),
             "--intent-type".to_string(),
@@ -544,23 +1045,110 @@ impl OpDeployerConfig {
         l1_chain_id: u64,
         l2_chain_id: u64,
     ) -> Result<PathBuf, anyhow::Error> {
-        let config_file_path = self
-            .generate_intent_file(
-                docker,
-                &self.docker_image,
-                host_config_path,
-                &PathBuf::from("/data"),
-                l1_chain_id,
-                l2_chain_id,
+        let config_file_path = match &self.intent_file {
+            Some(intent_file) => {
+                let l2_chain_ids: Vec<u64> = std::iter::once(l2_chain_id)
+                    .chain(self.additional_l2_chain_ids.iter().copied())
+                    .collect();
+                Self::copy_intent_file(intent_file, host_config_path, l1_chain_id, &l2_chain_ids)
+                    .await
+                    .context("Failed to use custom intent file")?
+            }
+            None => self
+                .generate_intent_file(
+                    docker,
+                    &self.docker_image,
+                    host_config_path,
+                    &PathBuf::from("/data"),
+                    l1_chain_id,
+                    l2_chain_id,
+                )
+                .await
+                .context("Failed to generate intent file")?,
+        };
+
+        if self.patch_roles {
+            Self::update_intent_with_accounts(
+                &config_file_path,
+                accounts,
+                &self.additional_l2_chain_ids,
             )
             .await
-            .context("Failed to generate intent file")?;
+            .context("Failed to update intent file with account addresses")?;
+
+            tracing::debug!("Intent file updated with account addresses");
+        }
+
+        if self.l2_block_time.is_some()
+            || self.sequencer_fee_recipient.is_some()
+            || self.l1_contracts_locator.is_some()
+            || self.l2_contracts_locator.is_some()
+            || self.alt_da.is_some()
+            || self.gas_limit.is_some()
+            || self.eip1559_denominator.is_some()
+            || self.eip1559_elasticity.is_some()
+        {
+            let content = tokio::fs::read_to_string(&config_file_path)
+                .await
+                .context("Failed to read intent file")?;
+            let mut doc: toml::Value =
+                toml::from_str(&content).context("Failed to parse intent file")?;
+            if let Some(l2_block_time) = self.l2_block_time {
+                set_l2_block_time(&mut doc, l2_block_time)?;
+                tracing::debug!(l2_block_time, "Set L2 block time in intent.toml");
+            }
+            if let Some(ref recipient) = self.sequencer_fee_recipient {
+                set_sequencer_fee_recipient(&mut doc, recipient)?;
+                tracing::debug!(%recipient, "Set sequencer fee recipient in intent.toml");
+            }
+            set_contracts_locators(
+                &mut doc,
+                self.l1_contracts_locator.as_deref(),
+                self.l2_contracts_locator.as_deref(),
+            )?;
+            if let Some(ref alt_da) = self.alt_da {
+                set_alt_da(&mut doc, alt_da)?;
+                tracing::debug!(?alt_da, "Enabled alt-DA in intent.toml");
+            }
+            set_chain_fee_params(
+                &mut doc,
+                self.gas_limit,
+                self.eip1559_denominator,
+                self.eip1559_elasticity,
+            )?;
+            tokio::fs::write(&config_file_path, toml::to_string_pretty(&doc)?)
+                .await
+                .context("Failed to write updated intent file")?;
+        }
+
+        Ok(config_file_path)
+    }
+
+    /// Copy a user-supplied intent file into the op-deployer work directory.
+    ///
+    /// Fails if the intent targets a different L1 chain or different L2 chains than the
+    /// deployment.
+    async fn copy_intent_file(
+        intent_file: &Path,
+        host_config_path: &Path,
+        l1_chain_id: u64,
+        l2_chain_ids: &[u64],
+    ) -> Result<PathBuf, anyhow::Error> {
+        let content = tokio::fs::read_to_string(intent_file)
+            .await
+            .with_context(|| format!("Failed to read intent file {}", intent_file.display()))?;
+        let intent: toml::Value =
+            toml::from_str(&content).context("Failed to parse intent file")?;
+        check_intent_l1_chain_id(&intent, l1_chain_id)?;
+        check_intent_l2_chain_ids(&intent, l2_chain_ids)?;
 
-        Self::update_intent_with_accounts(&config_file_path, accounts)
+        let config_file_path = host_config_path.join("intent.toml");
+        tokio::fs::write(&config_file_path, content)
             .await
-            .context("Failed to update intent file with account addresses")?;
+            .context("Failed to write intent.toml")?;
+
+        tracing::info!(intent_file = %intent_file.display(), "Using custom intent file");
 
-        tracing::debug!("Intent file updated with account addresses");
         Ok(config_file_path)
     }
 
@@ -610,10 +1198,12 @@ impl OpDeployerConfig {
     /// Updates the intent.toml file with account addresses from Anvil.
     ///
     /// This function replaces the placeholder addresses in the intent file with
-    /// actual addresses from the accounts generated by Anvil at startup.
+    /// actual addresses from the accounts generated by Anvil at startup. The chains
+    /// listed in `additional_l2_chain_ids` get their own submitter accounts.
     async fn update_intent_with_accounts(
         intent_path: &PathBuf,
         accounts: &AnvilAccounts,
+        additional_l2_chain_ids: &[u64],
     ) -> Result<(), anyhow::Error> {
         // Read the intent file
         let content = tokio::fs::read_to_string(intent_path)
@@ -630,6 +1220,15 @@ impl OpDeployerConfig {
 
         // Update the roles with account addresses using named fields
         for chain in &mut intent.chains {
+            let chain_index = parse_chain_id(&chain.id)
+                .and_then(|id| {
+                    additional_l2_chain_ids
+                        .iter()
+                        .position(|other| *other == id)
+                })
+                .map_or(0, |i| i + 1);
+            let accounts = &accounts.for_l2_chain(chain_index)?;
+
             // The deployer account is also used as base_fee_vault_recipient
             chain.base_fee_vault_recipient = format_address(&accounts.deployer.address);
             chain.l1_fee_vault_recipient = format_address(&accounts.l1_fee_vault_recipient.address);
@@ -703,3 +1302,271 @@ impl OpDeployerConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_l2_block_time() {
+        let mut intent: toml::Value = toml::from_str(
+            r#"
+            configType = "custom"
+            [[chains]]
+            id = "0x01"
+            [[chains]]
+            id = "0x02"
+            [chains.deployOverrides]
+            l2GenesisBlockGasLimit = 60000000
+            "#,
+        )
+        .unwrap();
+
+        set_l2_block_time(&mut intent, 1).unwrap();
+
+        let chains = intent["chains"].as_array().unwrap();
+        assert_eq!(
+            chains[0]["deployOverrides"]["l2BlockTime"].as_integer(),
+            Some(1)
+        );
+        assert_eq!(
+            chains[1]["deployOverrides"]["l2BlockTime"].as_integer(),
+            Some(1)
+        );
+        assert_eq!(
+            chains[1]["deployOverrides"]["l2GenesisBlockGasLimit"].as_integer(),
+            Some(60000000)
+        );
+    }
+
+    #[test]
+    fn test_set_sequencer_fee_recipient() {
+        let mut intent: toml::Value = toml::from_str(
+            r#"
+            [[chains]]
+            id = "0x01"
+            sequencerFeeVaultRecipient = "0x00"
+            "#,
+        )
+        .unwrap();
+
+        set_sequencer_fee_recipient(&mut intent, "0xABCDEF0000000000000000000000000000000001")
+            .unwrap();
+
+        assert_eq!(
+            intent["chains"][0]["sequencerFeeVaultRecipient"].as_str(),
+            Some("0xabcdef0000000000000000000000000000000001")
+        );
+    }
+
+    #[test]
+    fn test_set_chain_fee_params() {
+        let mut intent: toml::Value = toml::from_str(
+            r#"
+            [[chains]]
+            id = "0x01"
+            eip1559DenominatorCanyon = 250
+            eip1559Denominator = 50
+            eip1559Elasticity = 6
+            gasLimit = 60000000
+            "#,
+        )
+        .unwrap();
+
+        set_chain_fee_params(&mut intent, None, None, Some(10)).unwrap();
+        let chain = &intent["chains"][0];
+        assert_eq!(chain["eip1559Elasticity"].as_integer(), Some(10));
+        assert_eq!(chain["eip1559Denominator"].as_integer(), Some(50));
+        assert_eq!(chain["gasLimit"].as_integer(), Some(60000000));
+        assert!(chain.get("deployOverrides").is_none());
+
+        set_chain_fee_params(&mut intent, Some(100_000_000), Some(8), None).unwrap();
+        let chain = &intent["chains"][0];
+        assert_eq!(chain["eip1559Denominator"].as_integer(), Some(8));
+        assert_eq!(chain["eip1559DenominatorCanyon"].as_integer(), Some(8));
+        assert_eq!(chain["eip1559Elasticity"].as_integer(), Some(10));
+        assert_eq!(chain["gasLimit"].as_integer(), Some(100_000_000));
+        assert_eq!(
+            chain["deployOverrides"]["l2GenesisBlockGasLimit"].as_integer(),
+            Some(100_000_000)
+        );
+    }
+
+    #[test]
+    fn test_set_contracts_locators() {
+        let mut intent: toml::Value = toml::from_str(
+            r#"
+            l1ContractsLocator = "embedded"
+            l2ContractsLocator = "embedded"
+            "#,
+        )
+        .unwrap();
+
+        set_contracts_locators(
+            &mut intent,
+            Some("tag://op-contracts/v3.0.0"),
+            Some("file:///home/me/optimism/packages/contracts-bedrock/forge-artifacts"),
+        )
+        .unwrap();
+        assert_eq!(
+            intent["l1ContractsLocator"].as_str(),
+            Some("tag://op-contracts/v3.0.0")
+        );
+        assert_eq!(
+            intent["l2ContractsLocator"].as_str(),
+            Some("file:///artifacts/l2")
+        );
+
+        set_contracts_locators(&mut intent, None, Some("embedded")).unwrap();
+        assert_eq!(
+            intent["l1ContractsLocator"].as_str(),
+            Some("tag://op-contracts/v3.0.0")
+        );
+        assert_eq!(intent["l2ContractsLocator"].as_str(), Some("embedded"));
+    }
+
+    #[test]
+    fn test_is_valid_contracts_locator() {
+        assert!(is_valid_contracts_locator("embedded"));
+        assert!(is_valid_contracts_locator("tag://op-contracts/v3.0.0"));
+        assert!(is_valid_contracts_locator("file:///tmp/forge-artifacts"));
+        assert!(is_valid_contracts_locator(
+            "https://example.com/artifacts.tar.gz"
+        ));
+        assert!(!is_valid_contracts_locator("op-contracts/v3.0.0"));
+        assert!(!is_valid_contracts_locator("file://"));
+    }
+
+    #[test]
+    fn test_parse_chain_id() {
+        assert_eq!(
+            parse_chain_id("0x0000000000000000000000000000000000000000000000000000000000003039"),
+            Some(12345)
+        );
+        assert_eq!(parse_chain_id("0x00"), Some(0));
+        assert_eq!(parse_chain_id("3039"), None);
+    }
+
+    #[test]
+    fn test_single_chain_state() {
+        let state = serde_json::json!({
+            "opChainDeployments": [
+                {"id": "0x3039", "DisputeGameFactoryProxy": "0x01"},
+                {"id": "0x303a", "DisputeGameFactoryProxy": "0x02"},
+            ],
+            "implementationsDeployment": {},
+        });
+
+        let single = single_chain_state(&state, 12346).unwrap();
+        let deployments = single["opChainDeployments"].as_array().unwrap();
+        assert_eq!(deployments.len(), 1);
+        assert_eq!(deployments[0]["DisputeGameFactoryProxy"], "0x02");
+        assert!(single.get("implementationsDeployment").is_some());
+
+        assert!(single_chain_state(&state, 1).is_err());
+    }
+
+    #[test]
+    fn test_changed_implementations() {
+        let before = DeployedAddresses {
+            implementations: [
+                ("OpcmImpl".to_string(), format!("0x{}", "11".repeat(20))),
+                ("MipsImpl".to_string(), format!("0x{}", "22".repeat(20))),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        assert!(changed_implementations(&before, &before).is_empty());
+
+        let mut after = before.clone();
+        after
+            .implementations
+            .insert("OpcmImpl".to_string(), format!("0x{}", "33".repeat(20)));
+        assert_eq!(changed_implementations(&before, &after), ["OpcmImpl"]);
+    }
+
+    #[test]
+    fn test_check_intent_l1_chain_id() {
+        let intent: toml::Value = toml::from_str("l1ChainID = 11155111").unwrap();
+        assert!(check_intent_l1_chain_id(&intent, 11155111).is_ok());
+
+        let err = check_intent_l1_chain_id(&intent, 1).unwrap_err();
+        assert!(err.to_string().contains("11155111"), "{err}");
+
+        let intent: toml::Value = toml::from_str("configType = \"custom\"").unwrap();
+        assert!(check_intent_l1_chain_id(&intent, 1).is_err());
+    }
+
+    #[test]
+    fn test_check_intent_l2_chain_ids() {
+        let intent: toml::Value = toml::from_str(
+            r#"
+            [[chains]]
+            id = "0x000000000000000000000000000000000000000000000000000000000000a455"
+
+            [[chains]]
+            id = "0x000000000000000000000000000000000000000000000000000000000000a456"
+            "#,
+        )
+        .unwrap();
+        assert!(check_intent_l2_chain_ids(&intent, &[42069, 42070]).is_ok());
+
+        let err = check_intent_l2_chain_ids(&intent, &[42069]).unwrap_err();
+        assert!(err.to_string().contains("[42069, 42070]"), "{err}");
+        assert!(check_intent_l2_chain_ids(&intent, &[1, 42070]).is_err());
+
+        let intent: toml::Value = toml::from_str("configType = \"custom\"").unwrap();
+        assert!(check_intent_l2_chain_ids(&intent, &[42069]).is_err());
+    }
+
+    #[test]
+    fn test_intent_file_keeps_unmodeled_fields() {
+        let content = r#"
+            configType = "custom"
+            l1ChainID = 900
+            fundDevAccounts = false
+            l1ContractsLocator = "embedded"
+            l2ContractsLocator = "embedded"
+            useInterop = true
+
+            [[chains]]
+            id = "0x01"
+            baseFeeVaultRecipient = "0x00"
+            l1FeeVaultRecipient = "0x00"
+            sequencerFeeVaultRecipient = "0x00"
+            eip1559DenominatorCanyon = 250
+            eip1559Denominator = 50
+            eip1559Elasticity = 6
+            gasLimit = 60000000
+            operatorFeeScalar = 0
+            operatorFeeConstant = 0
+            minBaseFee = 0
+            daFootprintGasScalar = 0
+
+            [chains.roles]
+            l1ProxyAdminOwner = "0x00"
+            l2ProxyAdminOwner = "0x00"
+            systemConfigOwner = "0x00"
+            unsafeBlockSigner = "0x00"
+            batcher = "0x00"
+            proposer = "0x00"
+            challenger = "0x00"
+
+            [chains.deployOverrides]
+            l2GenesisBlockGasLimit = 60000000
+        "#;
+
+        let intent: IntentFile = toml::from_str(content).unwrap();
+        let restored: toml::Value = toml::from_str(&toml::to_string(&intent).unwrap()).unwrap();
+
+        assert_eq!(restored["useInterop"].as_bool(), Some(true));
+        assert_eq!(
+            restored["chains"][0]["deployOverrides"]["l2GenesisBlockGasLimit"].as_integer(),
+            Some(60000000)
+        );
+        assert_eq!(
+            restored["chains"][0]["roles"]["batcher"].as_str(),
+            Some("0x00")
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Command builder for the op-node consensus client.
+
+use std::path::Path;
+
+use crate::services::extra_args::merge_extra_args;
+
+/// Default P2P port for op-node (kept equal to kona-node's for interchangeability).
+pub const DEFAULT_P2P_PORT: u16 = 9222;
+
+/// Builder for op-node consensus client commands.
+#[derive(Debug, Clone)]
+pub struct OpNodeCmdBuilder {
+    l1_rpc: String,
+    /// L1 WebSocket URL, used for `--l1` instead of `l1_rpc` when set.
+    l1_ws: Option<String>,
+    l1_beacon: String,
+    l2_rpc: String,
+    rollup_cfg: String,
+    jwt_secret: String,
+    l1_chain_config: Option<String>,
+    rpc_addr: String,
+    rpc_port: u16,
+    p2p_port: u16,
+    metrics_enabled: bool,
+    metrics_port: u16,
+    no_discovery: bool,
+    bootnodes: Vec<String>,
+    /// P2P private key (32 bytes hex-encoded)
+    p2p_priv_key: Option<String>,
+    /// Whether this node runs the sequencer.
+    sequencer_enabled: bool,
+    unsafe_block_signer_key: Option<String>,
+    /// Conductor RPC URL (enables conductor control when set)
+    conductor_rpc: Option<String>,
+    /// Start sequencer in stopped state (for conductor-managed sequencers)
+    sequencer_stopped: bool,
+    log_level: Option<String>,
+    /// DA server to fetch the batch data from (alt-DA).
+    alt_da_server: Option<String>,
+    extra_args: Vec<String>,
+}
+
+impl OpNodeCmdBuilder {
+    /// Create a new op-node command builder with required configuration.
+    pub fn new(
+        l1_rpc: impl Into<String>,
+        l2_rpc: impl Into<String>,
+        rollup_cfg: impl AsRef<Path>,
+        jwt_secret: impl AsRef<Path>,
+    ) -> Self {
+        Self {
+            l1_rpc: l1_rpc.into(),
+            l1_ws: None,
+            l1_beacon: String::new(),
+            l2_rpc: l2_rpc.into(),
+            rollup_cfg: rollup_cfg.as_ref().display().to_string(),
+            jwt_secret: jwt_secret.as_ref().display().to_string(),
+            l1_chain_config: None,
+            rpc_addr: "0.0.0.0".to_string(),
+            rpc_port: 7545,
+            p2p_port: DEFAULT_P2P_PORT,
+            metrics_enabled: true,
+            metrics_port: 7300,
+            no_discovery: false,
+            bootnodes: Vec::new(),
+            p2p_priv_key: None,
+            sequencer_enabled: false,
+            unsafe_block_signer_key: None,
+            conductor_rpc: None,
+            sequencer_stopped: false,
+            log_level: None,
+            alt_da_server: None,
+            extra_args: Vec::new(),
+        }
+    }
+
+    /// Set the L1 beacon API URL. Defaults to the L1 RPC URL.
+    pub fn l1_beacon(mut self, url: impl Into<String>) -> Self {
+        self.l1_beacon = url.into();
+        self
+    }
+
+    /// Follow the L1 over a WebSocket URL. The beacon fallback keeps using the HTTP URL.
+    pub fn l1_ws(mut self, url: impl Into<String>) -> Self {
+        self.l1_ws = Some(url.into());
+        self
+    }
+
+    /// Set the L1 chain config file path (for custom/local L1 chains like Anvil).
+    pub fn l1_chain_config(mut self, path: impl Into<String>) -> Self {
+        self.l1_chain_config = Some(path.into());
+        self
+    }
+
+    /// Set the RPC server port.
+    pub fn rpc_port(mut self, port: u16) -> Self {
+        self.rpc_port = port;
+        self
+    }
+
+    /// Enable or disable metrics.
+    pub fn metrics(mut self, enabled: bool, port: u16) -> Self {
+        self.metrics_enabled = enabled;
+        self.metrics_port = port;
+        self
+    }
+
+    /// Enable or disable P2P discovery.
+    pub fn discovery(mut self, enabled: bool) -> Self {
+        self.no_discovery = !enabled;
+        self
+    }
+
+    /// Set the P2P bootnodes (enode URLs).
+    pub fn bootnodes(mut self, bootnodes: Vec<String>) -> Self {
+        self.bootnodes = bootnodes;
+        self
+    }
+
+    /// Set the P2P private key (32 bytes hex-encoded).
+    pub fn p2p_priv_key(mut self, key: impl Into<String>) -> Self {
+        self.p2p_priv_key = Some(key.into());
+        self
+    }
+
+    /// Enable the sequencer.
+    pub fn sequencer(mut self, enabled: bool) -> Self {
+        self.sequencer_enabled = enabled;
+        self
+    }
+
+    /// Set the unsafe block signer key (hex-encoded, without 0x prefix).
+    pub fn unsafe_block_signer_key(mut self, key: impl Into<String>) -> Self {
+        self.unsafe_block_signer_key = Some(key.into());
+        self
+    }
+
+    /// Set the conductor RPC URL for conductor-managed sequencers.
+    pub fn conductor_rpc(mut self, url: impl Into<String>) -> Self {
+        self.conductor_rpc = Some(url.into());
+        self
+    }
+
+    /// Start the sequencer in stopped state (for conductor-managed sequencers).
+    pub fn sequencer_stopped(mut self, stopped: bool) -> Self {
+        self.sequencer_stopped = stopped;
+        self
+    }
+
+    /// Set the log level (e.g., "info", "debug").
+    pub fn log_level(mut self, level: impl Into<String>) -> Self {
+        self.log_level = Some(level.into());
+        self
+    }
+
+    /// Fetch the batch data from the DA server at `url` (alt-DA).
+    pub fn maybe_alt_da_server(mut self, url: Option<String>) -> Self {
+        self.alt_da_server = url;
+        self
+    }
+
+    /// Add extra arguments. Long flags replace the ones the builder sets.
+    pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.extra_args.extend(args.into_iter().map(|s| s.into()));
+        self
+    }
+
+    /// Build the command as a vector of strings.
+    pub fn build(self) -> Vec<String> {
+        let l1_beacon = if self.l1_beacon.is_empty() {
+            self.l1_rpc.clone()
+        } else {
+            self.l1_beacon
+        };
+
+        let mut cmd = vec![
+            "op-node".to_string(),
+            // L1 configuration
+            "--l1".to_string(),
+            self.l1_ws.unwrap_or(self.l1_rpc),
+            "--l1.beacon".to_string(),
+            l1_beacon,
+            "--l1.rpckind".to_string(),
+            "standard".to_string(),
+            "--l1.trustrpc".to_string(),
+            // L2 configuration
+            "--l2".to_string(),
+            self.l2_rpc,
+            "--l2.jwt-secret".to_string(),
+            self.jwt_secret,
+            "--rollup.config".to_string(),
+            self.rollup_cfg,
+            // RPC
+            "--rpc.addr".to_string(),
+            self.rpc_addr,
+            "--rpc.port".to_string(),
+            self.rpc_port.to_string(),
+            "--rpc.enable-admin".to_string(),
+            // Derive from unconfirmed L1 blocks, like kona-node on a local devnet
+            "--verifier.l1-confs".to_string(),
+            "0".to_string(),
+            // P2P
+            "--p2p.listen.ip".to_string(),
+            "0.0.0.0".to_string(),
+            "--p2p.listen.tcp".to_string(),
+            self.p2p_port.to_string(),
+            "--p2p.listen.udp".to_string(),
+            self.p2p_port.to_string(),
+        ];
+
+        if let Some(l1_chain_config) = self.l1_chain_config {
+            cmd.push("--rollup.l1-chain-config".to_string());
+            cmd.push(l1_chain_config);
+        }
+
+        if self.no_discovery {
+            cmd.push("--p2p.no-discovery".to_string());
+        }
+
+        if !self.bootnodes.is_empty() {
+            cmd.push("--p2p.bootnodes".to_string());
+            cmd.push(self.bootnodes.join(","));
+        }
+
+        if let Some(p2p_priv_key) = self.p2p_priv_key {
+            cmd.push("--p2p.priv.raw".to_string());
+            cmd.push(p2p_priv_key);
+        }
+
+        if let Some(unsafe_block_signer_key) = self.unsafe_block_signer_key {
+            cmd.push("--p2p.sequencer.key".to_string());
+            cmd.push(unsafe_block_signer_key);
+        }
+
+        // Sequencer configuration
+        if self.sequencer_enabled {
+            cmd.push("--sequencer.enabled".to_string());
+            cmd.push("--sequencer.l1-confs".to_string());
+            cmd.push("0".to_string());
+        }
+
+        if self.sequencer_stopped {
+            cmd.push("--sequencer.stopped".to_string());
+        }
+
+        // Conductor configuration (for sequencer high-availability)
+        if let Some(conductor_rpc) = self.conductor_rpc {
+            cmd.push("--conductor.enabled".to_string());
+            cmd.push("--conductor.rpc".to_string());
+            cmd.push(conductor_rpc);
+        }
+
+        // Metrics
+        if self.metrics_enabled {
+            cmd.push("--metrics.enabled".to_string());
+            cmd.push("--metrics.addr".to_string());
+            cmd.push("0.0.0.0".to_string());
+            cmd.push("--metrics.port".to_string());
+            cmd.push(self.metrics_port.to_string());
+        }
+
+        if let Some(level) = self.log_level {
+            cmd.push("--log.level".to_string());
+            cmd.push(level);
+        }
+
+        // Alt-DA (the windows are read from rollup.json)
+        if let Some(da_server) = self.alt_da_server {
+            cmd.push("--altda.enabled".to_string());
+            cmd.push("--altda.da-server".to_string());
+            cmd.push(da_server);
+        }
+
+        merge_extra_args(cmd, self.extra_args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_builder() -> OpNodeCmdBuilder {
+        OpNodeCmdBuilder::new(
+            "http://anvil:8545",
+            "http://op-reth:9551",
+            "/data/rollup.json",
+            "/data/jwt.hex",
+        )
+    }
+
+    #[test]
+    fn test_op_node_cmd_builder_validator() {
+        let cmd = base_builder().rpc_port(7545).build();
+
+        assert_eq!(cmd[0], "op-node");
+        let rollup_pos = cmd.iter().position(|s| s == "--rollup.config").unwrap();
+        assert_eq!(cmd[rollup_pos + 1], "/data/rollup.json");
+        // Beacon defaults to the L1 RPC
+        let beacon_pos = cmd.iter().position(|s| s == "--l1.beacon").unwrap();
+        assert_eq!(cmd[beacon_pos + 1], "http://anvil:8545");
+        assert!(!cmd.contains(&"--sequencer.enabled".to_string()));
+    }
+
+    #[test]
+    fn test_op_node_cmd_builder_sequencer_with_conductor() {
+        let cmd = base_builder()
+            .sequencer(true)
+            .unsafe_block_signer_key("abcd")
+            .conductor_rpc("http://op-conductor:8547/")
+            .sequencer_stopped(true)
+            .build();
+
+        assert!(cmd.contains(&"--sequencer.enabled".to_string()));
+        assert!(cmd.contains(&"--sequencer.stopped".to_string()));
+        assert!(cmd.contains(&"--conductor.enabled".to_string()));
+        let key_pos = cmd.iter().position(|s| s == "--p2p.sequencer.key").unwrap();
+        assert_eq!(cmd[key_pos + 1], "abcd");
+    }
+}
@@ -0,0 +1,438 @@
+//! op-node consensus client service.
+//!
+//! The Go op-node is an alternative to kona-node. It consumes the same inputs and
+//! exposes the same `optimism_*` / `opp2p_*` RPC surface, so it reuses
+//! [`KonaNodeInput`] and [`KonaNodeHandler`] and can back any L2 node transparently.
+
+mod cmd;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+pub use cmd::{DEFAULT_P2P_PORT, OpNodeCmdBuilder};
+
+use crate::{
+    ExposedPort,
+    docker::{DockerImage, KupDocker, PortMapping, ServiceConfig},
+    service::{self, KupcakeService},
+    services::{
+        kona_node::{
+            DEFAULT_METRICS_PORT, DEFAULT_RPC_PORT, KonaNodeBuilder, KonaNodeHandler,
+            KonaNodeInput, P2pKeypair, is_known_l1_chain,
+        },
+        l2_node::L2NodeRole,
+    },
+};
+
+/// Default Docker image for op-node.
+pub const DEFAULT_DOCKER_IMAGE: &str = "us-docker.pkg.dev/oplabs-tools-artifacts/images/op-node";
+/// Default Docker tag for op-node.
+pub const DEFAULT_DOCKER_TAG: &str = "develop";
+
+/// Name of the L1 chain config file generated for custom L1 chains.
+const L1_CHAIN_CONFIG_FILENAME: &str = "op-node-l1-chain-config.json";
+
+/// Configuration for the op-node consensus client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OpNodeBuilder {
+    /// Docker image configuration for op-node.
+    pub docker_image: DockerImage,
+    /// Container name for op-node.
+    pub container_name: String,
+    /// Port for the op-node RPC server (container port).
+    pub rpc_port: u16,
+    /// Port for metrics (container port).
+    pub metrics_port: u16,
+    /// Host port for RPC. If None, not published to host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_host_port: Option<u16>,
+    /// Host port for metrics. If None, not published to host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_host_port: Option<u16>,
+    /// P2P secret key (32 bytes hex-encoded) for deterministic node identity.
+    /// If None, a random key will be generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p2p_secret_key: Option<String>,
+    /// Log level (e.g., "info", "debug").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    /// Extra arguments to pass to op-node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+    /// DA server to fetch the batch data from (alt-DA). None reads it from L1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_da_server: Option<String>,
+}
+
+impl Default for OpNodeBuilder {
+    fn default() -> Self {
+        Self {
+            docker_image: DockerImage::new(DEFAULT_DOCKER_IMAGE, DEFAULT_DOCKER_TAG),
+            container_name: "kupcake-op-node".to_string(),
+            rpc_port: DEFAULT_RPC_PORT,
+            metrics_port: DEFAULT_METRICS_PORT,
+            rpc_host_port: Some(0),
+            metrics_host_port: Some(0),
+            p2p_secret_key: None,
+            log_level: None,
+            extra_args: Vec::new(),
+            alt_da_server: None,
+        }
+    }
+}
+
+impl OpNodeBuilder {
+    /// Derive an op-node configuration from a kona-node configuration.
+    ///
+    /// Keeps ports, host port publishing and P2P identity, and swaps `kona-node`
+    /// for `op-node` in the container name.
+    pub fn from_kona_node(kona_node: &KonaNodeBuilder, docker_image: DockerImage) -> Self {
+        Self {
+            docker_image,
+            container_name: kona_node.container_name.replace("kona-node", "op-node"),
+            rpc_port: kona_node.rpc_port,
+            metrics_port: kona_node.metrics_port,
+            rpc_host_port: kona_node.rpc_host_port,
+            metrics_host_port: kona_node.metrics_host_port,
+            p2p_secret_key: kona_node.p2p_secret_key.clone(),
+            log_level: None,
+            extra_args: Vec::new(),
+            alt_da_server: None,
+        }
+    }
+
+    /// Build the op-node command arguments.
+    pub fn build_cmd(
+        &self,
+        input: &KonaNodeInput,
+        p2p_keypair: &P2pKeypair,
+    ) -> Result<Vec<String>, anyhow::Error> {
+        let container_config_path = PathBuf::from("/data");
+
+        let mut cmd_builder = OpNodeCmdBuilder::new(
+            input.l1_rpc_url.clone(),
+            input.authrpc_url.clone(),
+            container_config_path.join("rollup.json"),
+            container_config_path.join(&input.jwt_filename),
+        )
+        .rpc_port(self.rpc_port)
+        .metrics(true, self.metrics_port)
+        .discovery(true)
+        .bootnodes(input.bootnodes.clone())
+        .p2p_priv_key(&p2p_keypair.private_key)
+        .sequencer(input.role == L2NodeRole::Sequencer)
+        .unsafe_block_signer_key(&input.unsafe_block_signer_key)
+        .maybe_alt_da_server(self.alt_da_server.clone())
+        .extra_args(self.extra_args.clone());
+
+        if let Some(ref l1_ws_url) = input.l1_ws_url {
+            cmd_builder = cmd_builder.l1_ws(l1_ws_url);
+        }
+
+        if !is_known_l1_chain(input.l1_chain_id) {
+            cmd_builder = cmd_builder.l1_chain_config(
+                container_config_path
+                    .join(L1_CHAIN_CONFIG_FILENAME)
+                    .display()
+                    .to_string(),
+            );
+        }
+
+        if let Some(ref conductor_url) = input.conductor_rpc {
+            cmd_builder = cmd_builder.conductor_rpc(conductor_url);
+            if !input.is_conductor_leader {
+                cmd_builder = cmd_builder.sequencer_stopped(true);
+            }
+        }
+
+        if let Some(ref level) = self.log_level {
+            cmd_builder = cmd_builder.log_level(level);
+        }
+
+        Ok(cmd_builder.build())
+    }
+}
+
+/// Geth-style L1 chain config for a local/custom L1 chain.
+///
+/// op-node only knows the chain configs of public L1s. The hardforks of a custom L1 are
+/// told from the fields of its `latest` block header: `baseFeePerGas` (London),
+/// `withdrawalsRoot` (Shanghai), `excessBlobGas` (Cancun) and `requestsHash` (Prague).
+/// The genesis block is not used, as a forked or external L1 may have activated forks
+/// since. The forks active at the latest block are assumed active from genesis, which
+/// only matters for L1 blocks older than the L2 chain.
+fn l1_chain_config(l1_chain_id: u64, latest: &Value) -> Value {
+    let has = |field: &str| latest.get(field).is_some_and(|value| !value.is_null());
+    let shanghai = has("withdrawalsRoot");
+
+    let mut config = json!({
+        "chainId": l1_chain_id,
+        "homesteadBlock": 0,
+        "eip150Block": 0,
+        "eip155Block": 0,
+        "eip158Block": 0,
+        "byzantiumBlock": 0,
+        "constantinopleBlock": 0,
+        "petersburgBlock": 0,
+        "istanbulBlock": 0,
+        "muirGlacierBlock": 0,
+        "berlinBlock": 0,
+    });
+    if has("baseFeePerGas") {
+        config["londonBlock"] = json!(0);
+        config["arrowGlacierBlock"] = json!(0);
+        config["grayGlacierBlock"] = json!(0);
+    }
+    if shanghai || latest["difficulty"].as_str() == Some("0x0") {
+        config["mergeNetsplitBlock"] = json!(0);
+        config["terminalTotalDifficulty"] = json!(0);
+    }
+    if shanghai {
+        config["shanghaiTime"] = json!(0);
+    }
+
+    let mut blob_schedule = serde_json::Map::new();
+    if has("excessBlobGas") {
+        config["cancunTime"] = json!(0);
+        blob_schedule.insert(
+            "cancun".to_string(),
+            json!({ "target": 3, "max": 6, "baseFeeUpdateFraction": 3338477 }),
+        );
+    }
+    if has("requestsHash") {
+        config["pragueTime"] = json!(0);
+        blob_schedule.insert(
+            "prague".to_string(),
+            json!({ "target": 6, "max": 9, "baseFeeUpdateFraction": 5007716 }),
+        );
+    }
+    if !blob_schedule.is_empty() {
+        config["blobSchedule"] = Value::Object(blob_schedule);
+    }
+    config
+}
+
+/// Write the [`l1_chain_config`] of the L1 served at `l1_rpc_url`.
+///
+/// The file is regenerated on every deployment, so that it follows a change of L1
+/// hardfork. A fork activated on the L1 while the deployment runs is not picked up
+/// until the next deployment.
+async fn write_l1_chain_config(
+    client: &reqwest::Client,
+    host_config_path: &Path,
+    l1_rpc_url: &str,
+    l1_chain_id: u64,
+) -> anyhow::Result<()> {
+    let latest: Value = crate::rpc::json_rpc_call(
+        client,
+        l1_rpc_url,
+        "eth_getBlockByNumber",
+        vec![json!("latest"), json!(false)],
+    )
+    .await
+    .context("Failed to fetch the latest L1 block")?;
+    if !latest.is_object() {
+        anyhow::bail!(
+            "L1 at {} returned no latest block; cannot tell its hardforks for the op-node \
+             L1 chain config",
+            l1_rpc_url
+        );
+    }
+    let config = l1_chain_config(l1_chain_id, &latest);
+
+    let path = host_config_path.join(L1_CHAIN_CONFIG_FILENAME);
+    let content =
+        serde_json::to_string_pretty(&config).context("Failed to serialize L1 chain config")?;
+    tokio::fs::write(&path, content)
+        .await
+        .with_context(|| format!("Failed to write L1 chain config to {}", path.display()))?;
+
+    tracing::debug!(path = %path.display(), l1_chain_id, "Generated L1 chain config for op-node");
+    Ok(())
+}
+
+impl KupcakeService for OpNodeBuilder {
+    type Input = KonaNodeInput;
+    type Output = KonaNodeHandler;
+
+    fn container_name(&self) -> &str {
+        &self.container_name
+    }
+
+    fn docker_image(&self) -> &DockerImage {
+        &self.docker_image
+    }
+
+    async fn deploy<'a>(
+        &'a self,
+        docker: &'a mut KupDocker,
+        host_config_path: &'a Path,
+        input: KonaNodeInput,
+    ) -> Result<KonaNodeHandler, anyhow::Error> {
+        let container_config_path = PathBuf::from("/data");
+
+        if !is_known_l1_chain(input.l1_chain_id) {
+            let l1_rpc_for_host = input.l1_host_url.as_deref().unwrap_or(&input.l1_rpc_url);
+            write_l1_chain_config(
+                &docker.rpc_client,
+                host_config_path,
+                l1_rpc_for_host,
+                input.l1_chain_id,
+            )
+            .await?;
+        }
+
+        if input.flashblocks_builder_url.is_some() {
+            tracing::warn!(
+                container_name = %self.container_name,
+                "op-node does not support flashblocks; ignoring flashblocks builder URL"
+            );
+        }
+
+        let p2p_keypair = match &self.p2p_secret_key {
+            Some(key) => P2pKeypair::from_private_key(key)
+                .context("Failed to create P2P keypair from provided secret key")?,
+            None => P2pKeypair::generate(),
+        };
+
+        let cmd = self.build_cmd(&input, &p2p_keypair)?;
+
+        let port_mappings: Vec<PortMapping> = [
+            PortMapping::tcp_optional(self.rpc_port, self.rpc_host_port),
+            PortMapping::tcp_optional(self.metrics_port, self.metrics_host_port),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let service_config = ServiceConfig::new(self.docker_image.clone())
+            .cmd(cmd)
+            .ports(port_mappings)
+            .expose(ExposedPort::tcp(self.rpc_port))
+            .expose(ExposedPort::tcp(self.metrics_port))
+            .expose(ExposedPort::tcp(DEFAULT_P2P_PORT))
+            .expose(ExposedPort::udp(DEFAULT_P2P_PORT))
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?);
+
+        let (handler, timings) = service::deploy_container(
+            docker,
+            &self.docker_image,
+            &self.container_name,
+            service_config,
+        )
+        .await
+        .context("Failed to start op-node container")?;
+
+        let rpc_url = KupDocker::build_http_url(&handler.container_name, self.rpc_port)?;
+        let rpc_host_url = handler.build_host_url(self.rpc_port, "http")?;
+        let metrics_host_url = handler.build_host_url(self.metrics_port, "http")?;
+
+        tracing::info!(
+            container_id = %handler.container_id,
+            container_name = %handler.container_name,
+            ?rpc_host_url,
+            ?metrics_host_url,
+            "op-node container started"
+        );
+
+        Ok(KonaNodeHandler {
+            container_id: handler.container_id,
+            container_name: handler.container_name,
+            p2p_port: DEFAULT_P2P_PORT,
+            metrics_port: self.metrics_port,
+            p2p_keypair,
+            rpc_url,
+            rpc_host_url,
+            metrics_host_url,
+            flashblocks_relay_url: None,
+            deploy_timings: timings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_kona_node_renames_container() {
+        let kona = KonaNodeBuilder {
+            container_name: "kup-net-kona-node-validator-1".to_string(),
+            p2p_secret_key: Some("ab".repeat(32)),
+            ..Default::default()
+        };
+        let op_node = OpNodeBuilder::from_kona_node(
+            &kona,
+            DockerImage::new(DEFAULT_DOCKER_IMAGE, DEFAULT_DOCKER_TAG),
+        );
+
+        assert_eq!(op_node.container_name, "kup-net-op-node-validator-1");
+        assert_eq!(op_node.rpc_port, kona.rpc_port);
+        assert_eq!(op_node.p2p_secret_key, kona.p2p_secret_key);
+    }
+
+    #[test]
+    fn test_l1_chain_config_follows_latest_header() {
+        let cancun = json!({
+            "difficulty": "0x0",
+            "baseFeePerGas": "0x3b9aca00",
+            "withdrawalsRoot": "0x56e8",
+            "excessBlobGas": "0x0",
+        });
+        let config = l1_chain_config(900, &cancun);
+        assert_eq!(config["chainId"], 900);
+        assert_eq!(config["terminalTotalDifficulty"], 0);
+        assert_eq!(config["cancunTime"], 0);
+        assert!(config.get("pragueTime").is_none());
+        assert!(config["blobSchedule"].get("prague").is_none());
+
+        let mut prague = cancun;
+        prague["requestsHash"] = json!("0xe3b0");
+        let config = l1_chain_config(900, &prague);
+        assert_eq!(config["pragueTime"], 0);
+        assert_eq!(config["blobSchedule"]["prague"]["max"], 9);
+
+        let config = l1_chain_config(900, &json!({ "difficulty": "0x1" }));
+        assert!(config.get("londonBlock").is_none());
+        assert!(config.get("terminalTotalDifficulty").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_l1_chain_config_uses_latest_block() {
+        // A forked L1 whose genesis predates the merge, now running Prague.
+        let l1_url = crate::rpc::tests::serve_json_rpc(|method, params| {
+            assert_eq!(method, "eth_getBlockByNumber");
+            match params[0].as_str() {
+                Some("latest") => json!({
+                    "difficulty": "0x0",
+                    "baseFeePerGas": "0x7",
+                    "withdrawalsRoot": "0x56e8",
+                    "excessBlobGas": "0x0",
+                    "requestsHash": "0xe3b0",
+                }),
+                _ => json!({ "difficulty": "0x400000000" }),
+            }
+        })
+        .await;
+        let dir = tempdir::TempDir::new("kupcake-test").unwrap();
+
+        write_l1_chain_config(
+            &crate::rpc::create_client().unwrap(),
+            dir.path(),
+            &l1_url,
+            900,
+        )
+        .await
+        .unwrap();
+
+        let config = std::fs::read_to_string(dir.path().join(L1_CHAIN_CONFIG_FILENAME)).unwrap();
+        let config: Value = serde_json::from_str(&config).unwrap();
+        assert_eq!(config["terminalTotalDifficulty"], 0);
+        assert_eq!(config["shanghaiTime"], 0);
+        assert_eq!(config["pragueTime"], 0);
+    }
+}
@@ -1,5 +1,7 @@
 //! Command builder for op-proposer.
 
+use crate::services::extra_args::merge_extra_args;
+
 /// Builder for op-proposer commands.
 #[derive(Debug, Clone)]
 pub struct OpProposerCmdBuilder {
@@ -11,6 +13,7 @@ pub struct OpProposerCmdBuilder {
     proposal_interval: String,
     rpc_addr: String,
     rpc_port: u16,
+    rpc_enable_admin: bool,
     metrics_enabled: bool,
     metrics_addr: String,
     metrics_port: u16,
@@ -35,6 +38,7 @@ impl OpProposerCmdBuilder {
             proposal_interval: "12s".to_string(),
             rpc_addr: "0.0.0.0".to_string(),
             rpc_port: 8560,
+            rpc_enable_admin: false,
             metrics_enabled: true,
             metrics_addr: "0.0.0.0".to_string(),
             metrics_port: 7302,
@@ -67,6 +71,12 @@ impl OpProposerCmdBuilder {
         self
     }
 
+    /// Enable or disable the admin RPC (`admin_startProposer` / `admin_stopProposer`).
+    pub fn rpc_enable_admin(mut self, enabled: bool) -> Self {
+        self.rpc_enable_admin = enabled;
+        self
+    }
+
     /// Configure metrics.
     pub fn metrics(mut self, enabled: bool, addr: impl Into<String>, port: u16) -> Self {
         self.metrics_enabled = enabled;
@@ -81,7 +91,7 @@ impl OpProposerCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -110,6 +120,10 @@ impl OpProposerCmdBuilder {
             self.rpc_port.to_string(),
         ];
 
+        if self.rpc_enable_admin {
+            cmd.push("--rpc.enable-admin".to_string());
+        }
+
         // Metrics
         if self.metrics_enabled {
             cmd.push("--metrics.enabled".to_string());
@@ -124,9 +138,7 @@ impl OpProposerCmdBuilder {
             cmd.push(level);
         }
 
-        cmd.extend(self.extra_args);
-
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
@@ -12,7 +12,9 @@ pub use cmd::OpProposerCmdBuilder;
 
 use crate::docker::{DockerImage, ExposedPort, KupDocker, PortMapping, ServiceConfig};
 use crate::metrics::ContainerDeployTimings;
-use crate::service::{self, KupcakeService};
+use crate::rpc;
+use crate::service::{self, KupcakeService, ScrapeTarget};
+use crate::services::MetricsTarget;
 
 /// Input parameters for deploying the op-proposer.
 pub struct OpProposerInput {
@@ -55,8 +57,16 @@ pub struct OpProposerBuilder {
     /// Extra arguments to pass to op-proposer.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
+    /// Stop proposing right after startup, e.g. for a hot spare. op-proposer has no flag
+    /// for this, so its admin RPC is enabled and published to call `admin_stopProposer`;
+    /// proposing is resumed with `admin_startProposer`.
+    #[serde(default)]
+    pub stopped: bool,
 }
 
+/// Time to wait for a stopped op-proposer's admin RPC, in seconds.
+const ADMIN_RPC_TIMEOUT_SECS: u64 = 60;
+
 /// Default Docker image for op-proposer.
 pub const DEFAULT_DOCKER_IMAGE: &str =
     "us-docker.pkg.dev/oplabs-tools-artifacts/images/op-proposer";
@@ -76,6 +86,7 @@ impl Default for OpProposerBuilder {
             proposal_interval: "12s".to_string(),
             log_level: None,
             extra_args: Vec::new(),
+            stopped: false,
         }
     }
 }
@@ -88,10 +99,26 @@ pub struct OpProposerHandler {
     pub container_name: String,
     /// The RPC URL for the op-proposer.
     pub rpc_url: Url,
+    /// Port for metrics (container port).
+    pub metrics_port: u16,
+    /// The RPC URL accessible from host (if published). None if not published.
+    pub rpc_host_url: Option<Url>,
     /// Deploy timings for metrics.
     pub deploy_timings: ContainerDeployTimings,
 }
 
+impl ScrapeTarget for OpProposerHandler {
+    fn metrics_target(&self) -> Option<MetricsTarget> {
+        Some(MetricsTarget {
+            job_name: self.container_name.clone(),
+            container_name: self.container_name.clone(),
+            port: self.metrics_port,
+            service_label: "op-proposer".to_string(),
+            layer_label: "proposer".to_string(),
+        })
+    }
+}
+
 impl OpProposerBuilder {
     /// Build the Docker command arguments for op-proposer.
     pub fn build_cmd(
@@ -110,6 +137,7 @@ impl OpProposerBuilder {
         .game_type(254) // Permissioned game type
         .proposal_interval(&self.proposal_interval)
         .rpc_port(self.rpc_port)
+        .rpc_enable_admin(self.stopped)
         .metrics(true, "0.0.0.0", self.metrics_port)
         .extra_args(self.extra_args.clone());
 
@@ -144,8 +172,10 @@ impl KupcakeService for OpProposerBuilder {
         let cmd = self.build_cmd(host_config_path, &input)?;
 
         // Build port mappings only for ports that should be published to host
+        // A stopped proposer is stopped through its admin RPC from the host.
+        let rpc_host_port = self.rpc_host_port.or(self.stopped.then_some(0));
         let port_mappings: Vec<PortMapping> = [
-            PortMapping::tcp_optional(self.rpc_port, self.rpc_host_port),
+            PortMapping::tcp_optional(self.rpc_port, rpc_host_port),
             PortMapping::tcp_optional(self.metrics_port, self.metrics_host_port),
         ]
         .into_iter()
@@ -175,11 +205,34 @@ impl KupcakeService for OpProposerBuilder {
         );
 
         let rpc_url = KupDocker::build_http_url(&handler.container_name, self.rpc_port)?;
+        let rpc_host_url = handler.build_host_url(self.rpc_port, "http")?;
+
+        if self.stopped {
+            let url = rpc_host_url
+                .as_ref()
+                .context("Stopped op-proposer has no host-published RPC")?;
+            let client = &docker.rpc_client;
+            rpc::wait_until_ready("op-proposer admin RPC", ADMIN_RPC_TIMEOUT_SECS, || async {
+                rpc::json_rpc_call::<serde_json::Value>(
+                    client,
+                    url.as_str(),
+                    "admin_stopProposer",
+                    vec![],
+                )
+                .await
+                .map(|_| ())
+            })
+            .await
+            .context("Failed to stop op-proposer")?;
+            tracing::info!(container_name = %handler.container_name, "op-proposer stopped");
+        }
 
         Ok(OpProposerHandler {
             container_id: handler.container_id,
             container_name: handler.container_name,
             rpc_url,
+            metrics_port: self.metrics_port,
+            rpc_host_url,
             deploy_timings: timings,
         })
     }
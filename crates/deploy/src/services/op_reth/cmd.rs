@@ -2,6 +2,8 @@
 
 use std::path::Path;
 
+use crate::services::{extra_args::merge_extra_args, op_reth::PruningMode};
+
 /// Builder for op-reth execution client commands.
 #[derive(Debug, Clone)]
 pub struct OpRethCmdBuilder {
@@ -10,9 +12,12 @@ pub struct OpRethCmdBuilder {
     http_addr: String,
     http_port: u16,
     http_api: String,
+    http_corsdomain: Option<String>,
+    http_vhosts: Option<String>,
     ws_addr: String,
     ws_port: u16,
     ws_api: String,
+    ws_origins: Option<String>,
     authrpc_addr: String,
     authrpc_port: u16,
     authrpc_jwtsecret: String,
@@ -38,6 +43,10 @@ pub struct OpRethCmdBuilder {
     proofs_history: bool,
     /// Storage path for historical proofs sidecar DB.
     proofs_history_storage_path: Option<String>,
+    /// Whether stateless validation is enabled.
+    stateless: bool,
+    /// Which historical state and chain data the node keeps.
+    pruning: PruningMode,
     extra_args: Vec<String>,
 }
 
@@ -50,9 +59,12 @@ impl OpRethCmdBuilder {
             http_addr: "0.0.0.0".to_string(),
             http_port: 8545,
             http_api: "eth,net,web3,debug,trace,txpool,admin".to_string(),
+            http_corsdomain: None,
+            http_vhosts: None,
             ws_addr: "0.0.0.0".to_string(),
             ws_port: 8546,
             ws_api: "eth,net,web3,debug,trace,txpool,admin".to_string(),
+            ws_origins: None,
             authrpc_addr: "0.0.0.0".to_string(),
             authrpc_port: 8551,
             authrpc_jwtsecret: String::new(),
@@ -72,6 +84,8 @@ impl OpRethCmdBuilder {
             flashblocks_port: 1111,
             proofs_history: false,
             proofs_history_storage_path: None,
+            stateless: false,
+            pruning: PruningMode::default(),
             extra_args: Vec::new(),
         }
     }
@@ -108,6 +122,18 @@ impl OpRethCmdBuilder {
         self
     }
 
+    /// Set the allowed CORS origins for the HTTP RPC (`*` or comma-separated origins).
+    pub fn http_corsdomain(mut self, domains: impl Into<String>) -> Self {
+        self.http_corsdomain = Some(domains.into());
+        self
+    }
+
+    /// Set the virtual hostnames accepted by the HTTP RPC (`*` or comma-separated hosts).
+    pub fn http_vhosts(mut self, hosts: impl Into<String>) -> Self {
+        self.http_vhosts = Some(hosts.into());
+        self
+    }
+
     /// Set the WebSocket RPC address.
     pub fn ws_addr(mut self, addr: impl Into<String>) -> Self {
         self.ws_addr = addr.into();
@@ -126,6 +152,12 @@ impl OpRethCmdBuilder {
         self
     }
 
+    /// Set the allowed origins for WebSocket RPC connections.
+    pub fn ws_origins(mut self, origins: impl Into<String>) -> Self {
+        self.ws_origins = Some(origins.into());
+        self
+    }
+
     /// Set the listen port.
     pub fn listen_port(mut self, port: u16) -> Self {
         self.listen_port = port;
@@ -217,7 +249,19 @@ impl OpRethCmdBuilder {
         self
     }
 
-    /// Add extra arguments.
+    /// Enable stateless validation (`--engine.stateless`).
+    pub fn stateless(mut self) -> Self {
+        self.stateless = true;
+        self
+    }
+
+    /// Set which historical state and chain data the node keeps.
+    pub fn pruning(mut self, pruning: PruningMode) -> Self {
+        self.pruning = pruning;
+        self
+    }
+
+    /// Add extra arguments. Long flags replace the ones the builder sets.
     pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.extra_args.extend(args.into_iter().map(|s| s.into()));
         self
@@ -258,6 +302,21 @@ impl OpRethCmdBuilder {
             self.listen_port.to_string(),
         ];
 
+        if let Some(domains) = self.http_corsdomain {
+            cmd.push("--http.corsdomain".to_string());
+            cmd.push(domains);
+        }
+
+        if let Some(hosts) = self.http_vhosts {
+            cmd.push("--http.vhosts".to_string());
+            cmd.push(hosts);
+        }
+
+        if let Some(origins) = self.ws_origins {
+            cmd.push("--ws.origins".to_string());
+            cmd.push(origins);
+        }
+
         if let Some(nat_dns) = self.nat_dns {
             cmd.push(format!("--nat=extaddr:{}", nat_dns));
         }
@@ -319,6 +378,12 @@ impl OpRethCmdBuilder {
             }
         }
 
+        if self.stateless {
+            cmd.push("--engine.stateless".to_string());
+        }
+
+        cmd.extend(self.pruning.flags());
+
         if let Some(filter) = self.log_filter {
             cmd.push("--log.stdout.filter".to_string());
             cmd.push(filter);
@@ -334,15 +399,14 @@ impl OpRethCmdBuilder {
         cmd.push("--log.stdout.format".to_string());
         cmd.push(self.log_format);
 
-        cmd.extend(self.extra_args);
-
-        cmd
+        merge_extra_args(cmd, self.extra_args)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::op_reth::MINIMUM_PRUNING_DISTANCE;
 
     #[test]
     fn test_op_reth_cmd_builder() {
@@ -397,6 +461,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cors_flags() {
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data")
+            .http_corsdomain("http://localhost:3000,http://localhost:5173")
+            .http_vhosts("localhost,kupcake-op-reth")
+            .ws_origins("*")
+            .build();
+
+        let pos = cmd.iter().position(|s| s == "--http.corsdomain").unwrap();
+        assert_eq!(cmd[pos + 1], "http://localhost:3000,http://localhost:5173");
+        let pos = cmd.iter().position(|s| s == "--http.vhosts").unwrap();
+        assert_eq!(cmd[pos + 1], "localhost,kupcake-op-reth");
+        let pos = cmd.iter().position(|s| s == "--ws.origins").unwrap();
+        assert_eq!(cmd[pos + 1], "*");
+    }
+
+    #[test]
+    fn test_cors_flags_absent_by_default() {
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data").build();
+        assert!(!cmd.contains(&"--http.corsdomain".to_string()));
+        assert!(!cmd.contains(&"--http.vhosts".to_string()));
+        assert!(!cmd.contains(&"--ws.origins".to_string()));
+    }
+
     #[test]
     fn test_rpc_max_connections_absent_by_default() {
         let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data").build();
@@ -452,4 +540,50 @@ mod tests {
             "Should not contain proofs-history flags when not enabled"
         );
     }
+
+    #[test]
+    fn test_stateless_flag() {
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data").build();
+        assert!(!cmd.contains(&"--engine.stateless".to_string()));
+
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data")
+            .stateless()
+            .build();
+        assert!(cmd.contains(&"--engine.stateless".to_string()));
+    }
+
+    #[test]
+    fn test_pruning_flags() {
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data").build();
+        assert!(
+            !cmd.iter()
+                .any(|s| s == "--full" || s.starts_with("--prune."))
+        );
+
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data")
+            .pruning(PruningMode::Full)
+            .build();
+        assert!(cmd.contains(&"--full".to_string()));
+
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data")
+            .pruning(PruningMode::MinimalPruned)
+            .build();
+        assert!(!cmd.contains(&"--full".to_string()));
+        assert!(cmd.contains(&"--prune.transactionlookup.full".to_string()));
+        let pos = cmd
+            .iter()
+            .position(|s| s == "--prune.accounthistory.distance")
+            .unwrap();
+        assert_eq!(cmd[pos + 1], MINIMUM_PRUNING_DISTANCE.to_string());
+    }
+
+    #[test]
+    fn test_extra_args_override_managed_flags() {
+        let cmd = OpRethCmdBuilder::new("/data/genesis.json", "/data/reth-data")
+            .extra_args(["--http.port", "9000"])
+            .build();
+        assert_eq!(cmd.iter().filter(|s| *s == "--http.port").count(), 1);
+        assert!(!cmd.contains(&"8545".to_string()));
+        assert_eq!(cmd[cmd.len() - 2..], ["--http.port", "9000"]);
+    }
 }
@@ -15,8 +15,8 @@ use crate::{
     ExposedPort,
     docker::{DockerImage, KupDocker, PortMapping, ServiceConfig},
     metrics::ContainerDeployTimings,
-    service::{self, KupcakeService},
-    services::kona_node::P2pKeypair,
+    service::{self, KupcakeService, ScrapeTarget},
+    services::{MetricsTarget, kona_node::P2pKeypair},
 };
 
 /// Input parameters for deploying op-reth.
@@ -41,6 +41,65 @@ pub const DEFAULT_DISCOVERY_PORT: u16 = 30303;
 pub const DEFAULT_LISTEN_PORT: u16 = 30303;
 pub const DEFAULT_METRICS_PORT: u16 = 9001;
 
+/// Smallest number of recent blocks reth keeps the receipts and state history of when
+/// pruning them (`MINIMUM_PRUNING_DISTANCE` in reth).
+pub const MINIMUM_PRUNING_DISTANCE: u64 = 10_064;
+
+/// Which historical state and chain data op-reth keeps.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum PruningMode {
+    /// Keep everything, so that state can be queried at any block. The default.
+    #[default]
+    Archive,
+    /// reth's full node (`--full`): the state history of the last
+    /// [`MINIMUM_PRUNING_DISTANCE`] blocks only.
+    Full,
+    /// Like [`Self::Full`], but also drop the transaction hash index, so transactions
+    /// and receipts can no longer be looked up by hash.
+    MinimalPruned,
+}
+
+impl PruningMode {
+    /// Whether this is the default mode (archive).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// op-reth flags of this mode.
+    pub fn flags(&self) -> Vec<String> {
+        let distance = MINIMUM_PRUNING_DISTANCE.to_string();
+        let flags: &[&str] = match self {
+            Self::Archive => &[],
+            Self::Full => &["--full"],
+            Self::MinimalPruned => &[
+                "--prune.senderrecovery.full",
+                "--prune.transactionlookup.full",
+                "--prune.receipts.distance",
+                &distance,
+                "--prune.accounthistory.distance",
+                &distance,
+                "--prune.storagehistory.distance",
+                &distance,
+            ],
+        };
+        flags.iter().map(|flag| flag.to_string()).collect()
+    }
+}
+
 /// Configuration for the op-reth execution client.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OpRethBuilder {
@@ -99,9 +158,40 @@ pub struct OpRethBuilder {
     /// Whether historical proofs ExEx is enabled.
     #[serde(default)]
     pub proofs_history: bool,
+    /// Whether stateless validation is enabled (`--engine.stateless`), for witness
+    /// experiments. Only known to be supported by the `develop` / `nightly` op-reth images.
+    #[serde(default)]
+    pub stateless: bool,
+    /// Which historical state and chain data the node keeps. Only applies to a new
+    /// datadir: data an earlier mode pruned is not restored.
+    #[serde(default, skip_serializing_if = "PruningMode::is_default")]
+    pub pruning: PruningMode,
     /// Log filter for stdout (e.g., "info", "debug").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub log_filter: Option<String>,
+    /// Allowed CORS origins for the HTTP RPC (`--http.corsdomain`): `*` or a
+    /// comma-separated list of origins. If None, no CORS headers are sent and
+    /// browser apps cannot call the RPC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_corsdomain: Option<String>,
+    /// Virtual hostnames accepted by the HTTP RPC (`--http.vhosts`): `*` or a
+    /// comma-separated list of hosts. If None, op-reth's default applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_vhosts: Option<String>,
+    /// Allowed origins for WebSocket RPC connections (`--ws.origins`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_origins: Option<String>,
+    /// HTTP RPC API namespaces (`--http.api`, e.g. "eth,net,web3").
+    /// If None, all namespaces useful for a devnet are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_api: Option<String>,
+    /// Linux capabilities added to the container (e.g. `NET_ADMIN` to run `tc` inside it).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cap_add: Vec<String>,
+    /// Run the container in privileged mode. Must be opted into explicitly; a warning is
+    /// logged when the container starts.
+    #[serde(default)]
+    pub privileged: bool,
     /// Extra arguments to pass to op-reth.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
@@ -118,6 +208,12 @@ pub const DEFAULT_RBUILDER_DOCKER_IMAGE: &str = "ghcr.io/theochap/op-rbuilder";
 pub const DEFAULT_RBUILDER_DOCKER_TAG: &str = "v0.3.2-rc4";
 /// Default port for the flashblocks WebSocket server.
 pub const DEFAULT_FLASHBLOCKS_PORT: u16 = 1111;
+/// Default allowed CORS / WebSocket origins: browser apps served from localhost, on any
+/// port, can reach the devnet RPC, other web pages cannot.
+pub const DEFAULT_RPC_ORIGINS: &str = "http://localhost:*,http://127.0.0.1:*";
+/// op-reth tags known to support stateless validation. They track the main branch; release
+/// tags are not checked and only trigger a warning.
+pub const STATELESS_SUPPORTED_TAGS: &[&str] = &["develop", "nightly"];
 
 impl Default for OpRethBuilder {
     fn default() -> Self {
@@ -144,7 +240,15 @@ impl Default for OpRethBuilder {
             flashblocks_enabled: false,
             flashblocks_port: None,
             proofs_history: false,
+            stateless: false,
+            pruning: PruningMode::default(),
             log_filter: None,
+            http_corsdomain: Some(DEFAULT_RPC_ORIGINS.to_string()),
+            http_vhosts: None,
+            ws_origins: Some(DEFAULT_RPC_ORIGINS.to_string()),
+            http_api: None,
+            cap_add: Vec::new(),
+            privileged: false,
             extra_args: Vec::new(),
         }
     }
@@ -154,12 +258,16 @@ impl Default for OpRethBuilder {
 pub struct OpRethHandler {
     /// Port for P2P discovery (container port).
     pub discovery_port: u16,
+    /// The UDP host port bound to the discovery port (if published). None if not published.
+    pub discovery_host_port: Option<u16>,
     /// Docker container ID.
     pub container_id: String,
     /// Docker container name.
     pub container_name: String,
     /// The P2P listen port (used for enode URL construction).
     pub listen_port: u16,
+    /// Port for metrics (container port).
+    pub metrics_port: u16,
     /// P2P keypair for this node (used for enode computation).
     pub p2p_keypair: P2pKeypair,
     /// The HTTP RPC URL for the L2 execution client (internal Docker network).
@@ -187,6 +295,24 @@ impl OpRethHandler {
         self.p2p_keypair
             .to_enode(&self.container_name, self.discovery_port)
     }
+
+    /// Host address (`localhost:<port>`) of the UDP discovery port, if published.
+    pub fn discovery_host_addr(&self) -> Option<String> {
+        self.discovery_host_port
+            .map(|port| format!("localhost:{}", port))
+    }
+}
+
+impl ScrapeTarget for OpRethHandler {
+    fn metrics_target(&self) -> Option<MetricsTarget> {
+        Some(MetricsTarget {
+            job_name: self.container_name.clone(),
+            container_name: self.container_name.clone(),
+            port: self.metrics_port,
+            service_label: "op-reth".to_string(),
+            layer_label: "execution".to_string(),
+        })
+    }
 }
 
 impl OpRethBuilder {
@@ -194,6 +320,26 @@ impl OpRethBuilder {
     pub fn docker_rpc_url(&self) -> String {
         format!("http://{}:{}/", self.container_name, self.http_port)
     }
+
+    /// Name of this node's datadir under the shared L2 bind mount.
+    pub fn datadir_name(&self) -> String {
+        format!("reth-data-{}", self.container_name)
+    }
+
+    /// Whether the configured image is known to support stateless validation.
+    ///
+    /// Only the upstream op-reth image with one of [`STATELESS_SUPPORTED_TAGS`] is known to
+    /// accept the flag; other images, release tags and local binaries are unknown.
+    pub fn stateless_supported(&self) -> bool {
+        !self.flashblocks_enabled
+            && self.docker_image.binary.is_none()
+            && self.docker_image.image.as_deref() == Some(DEFAULT_DOCKER_IMAGE)
+            && self
+                .docker_image
+                .tag
+                .as_deref()
+                .is_some_and(|tag| STATELESS_SUPPORTED_TAGS.contains(&tag))
+    }
 }
 
 impl OpRethBuilder {
@@ -207,9 +353,11 @@ impl OpRethBuilder {
 
         let mut cmd_builder = OpRethCmdBuilder::new(
             container_config_path.join("genesis.json"),
-            container_config_path.join(format!("reth-data-{}", self.container_name)),
+            container_config_path.join(self.datadir_name()),
         )
+        .http_addr(&self.host)
         .http_port(self.http_port)
+        .ws_addr(&self.host)
         .ws_port(self.ws_port)
         .authrpc_port(self.authrpc_port)
         .authrpc_jwtsecret(container_config_path.join(&input.jwt_filename))
@@ -241,10 +389,32 @@ impl OpRethBuilder {
             cmd_builder = cmd_builder.proofs_history(proofs_path);
         }
 
+        if self.stateless {
+            cmd_builder = cmd_builder.stateless();
+        }
+
+        cmd_builder = cmd_builder.pruning(self.pruning);
+
         if let Some(ref filter) = self.log_filter {
             cmd_builder = cmd_builder.log_filter(filter);
         }
 
+        if let Some(ref domains) = self.http_corsdomain {
+            cmd_builder = cmd_builder.http_corsdomain(domains);
+        }
+
+        if let Some(ref hosts) = self.http_vhosts {
+            cmd_builder = cmd_builder.http_vhosts(hosts);
+        }
+
+        if let Some(ref origins) = self.ws_origins {
+            cmd_builder = cmd_builder.ws_origins(origins);
+        }
+
+        if let Some(ref api) = self.http_api {
+            cmd_builder = cmd_builder.http_api(api);
+        }
+
         Ok(cmd_builder.build())
     }
 }
@@ -277,14 +447,20 @@ impl KupcakeService for OpRethBuilder {
             "Using P2P keypair for op-reth"
         );
 
+        if self.stateless && !self.stateless_supported() {
+            tracing::warn!(
+                container_name = %self.container_name,
+                image = %self.docker_image,
+                "Stateless validation is enabled but the op-reth image is not known to support \
+                 --engine.stateless; the node may fail to start"
+            );
+        }
+
         // Run proofs history initialization if enabled
         if self.proofs_history {
             let container_config_path_str = container_config_path.display().to_string();
             let chain_path = format!("{}/genesis.json", container_config_path_str);
-            let datadir = format!(
-                "{}/reth-data-{}",
-                container_config_path_str, self.container_name
-            );
+            let datadir = format!("{}/{}", container_config_path_str, self.datadir_name());
             let proofs_path = format!(
                 "{}/proofs-{}",
                 container_config_path_str, self.container_name
@@ -303,7 +479,8 @@ impl KupcakeService for OpRethBuilder {
                     "--datadir".to_string(),
                     datadir.clone(),
                 ])
-                .bind(host_config_path, &container_config_path, "rw");
+                .bind(host_config_path, &container_config_path, "rw")
+                .maybe_user(docker.service_user(host_config_path)?);
 
             docker
                 .run_command(init_config)
@@ -326,7 +503,8 @@ impl KupcakeService for OpRethBuilder {
                     "--proofs-history.storage-path".to_string(),
                     proofs_path,
                 ])
-                .bind(host_config_path, &container_config_path, "rw");
+                .bind(host_config_path, &container_config_path, "rw")
+                .maybe_user(docker.service_user(host_config_path)?);
 
             docker
                 .run_command(proofs_init_config)
@@ -367,7 +545,10 @@ impl KupcakeService for OpRethBuilder {
             .cmd(cmd)
             .ports(port_mappings)
             .expose_ports(exposed_ports)
-            .bind(host_config_path, &container_config_path, "rw");
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?)
+            .cap_add(self.cap_add.iter().cloned())
+            .privileged(self.privileged);
 
         let (handler, timings) = service::deploy_container(
             docker,
@@ -386,6 +567,7 @@ impl KupcakeService for OpRethBuilder {
         // Build host-accessible URLs from bound ports
         let http_host_url = handler.build_host_url(self.http_port, "http")?;
         let ws_host_url = handler.build_host_url(self.ws_port, "ws")?;
+        let discovery_host_port = handler.get_udp_host_port(self.discovery_port);
 
         // Build flashblocks WebSocket URL if enabled
         let flashblocks_ws_url = self
@@ -398,6 +580,7 @@ impl KupcakeService for OpRethBuilder {
             container_name = %handler.container_name,
             ?http_host_url,
             ?ws_host_url,
+            ?discovery_host_port,
             ?flashblocks_ws_url,
             "op-reth container started"
         );
@@ -406,7 +589,9 @@ impl KupcakeService for OpRethBuilder {
             container_id: handler.container_id,
             container_name: handler.container_name,
             listen_port: self.listen_port,
+            metrics_port: self.metrics_port,
             discovery_port: self.discovery_port,
+            discovery_host_port,
             p2p_keypair,
             http_rpc_url,
             ws_rpc_url,
@@ -418,3 +603,25 @@ impl KupcakeService for OpRethBuilder {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stateless_supported() {
+        assert!(OpRethBuilder::default().stateless_supported());
+
+        let release = OpRethBuilder {
+            docker_image: DockerImage::new(DEFAULT_DOCKER_IMAGE, "v1.0.0"),
+            ..Default::default()
+        };
+        assert!(!release.stateless_supported());
+
+        let rbuilder = OpRethBuilder {
+            flashblocks_enabled: true,
+            ..Default::default()
+        };
+        assert!(!rbuilder.stateless_supported());
+    }
+}
@@ -0,0 +1,165 @@
+//! Command builder for op-supervisor.
+
+use crate::services::extra_args::merge_extra_args;
+
+/// Builder for op-supervisor commands.
+#[derive(Debug, Clone)]
+pub struct OpSupervisorCmdBuilder {
+    /// L1 RPC endpoint.
+    l1_rpc: String,
+    /// RPC endpoints of the consensus nodes of every chain in the dependency set.
+    l2_consensus_nodes: Vec<String>,
+    /// Path to the JWT secret shared with the consensus nodes.
+    l2_consensus_jwt_secret: String,
+    /// Directory for the supervisor databases.
+    datadir: String,
+    /// Path to the dependency set JSON file.
+    dependency_set: String,
+    /// Glob matching the rollup configs of the chains in the dependency set.
+    rollup_config_paths: String,
+    /// RPC listen address.
+    rpc_addr: String,
+    /// RPC port.
+    rpc_port: u16,
+    /// Log level.
+    log_level: String,
+    /// Extra arguments to pass to op-supervisor.
+    extra_args: Vec<String>,
+}
+
+impl OpSupervisorCmdBuilder {
+    /// Create a new op-supervisor command builder.
+    pub fn new(
+        l1_rpc: impl Into<String>,
+        l2_consensus_jwt_secret: impl Into<String>,
+        datadir: impl Into<String>,
+        dependency_set: impl Into<String>,
+        rollup_config_paths: impl Into<String>,
+    ) -> Self {
+        Self {
+            l1_rpc: l1_rpc.into(),
+            l2_consensus_nodes: Vec::new(),
+            l2_consensus_jwt_secret: l2_consensus_jwt_secret.into(),
+            datadir: datadir.into(),
+            dependency_set: dependency_set.into(),
+            rollup_config_paths: rollup_config_paths.into(),
+            rpc_addr: "0.0.0.0".to_string(),
+            rpc_port: 8545,
+            log_level: "DEBUG".to_string(),
+            extra_args: Vec::new(),
+        }
+    }
+
+    /// Add the RPC endpoints of consensus nodes to sync from.
+    pub fn l2_consensus_nodes(
+        mut self,
+        nodes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.l2_consensus_nodes
+            .extend(nodes.into_iter().map(|s| s.into()));
+        self
+    }
+
+    /// Set the RPC listen address.
+    pub fn rpc_addr(mut self, addr: impl Into<String>) -> Self {
+        self.rpc_addr = addr.into();
+        self
+    }
+
+    /// Set the RPC port.
+    pub fn rpc_port(mut self, port: u16) -> Self {
+        self.rpc_port = port;
+        self
+    }
+
+    /// Set the log level.
+    pub fn log_level(mut self, level: impl Into<String>) -> Self {
+        self.log_level = level.into();
+        self
+    }
+
+    /// Add extra arguments. Long flags replace the ones the builder sets.
+    pub fn extra_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.extra_args.extend(args.into_iter().map(|s| s.into()));
+        self
+    }
+
+    /// Build the command as a vector of strings.
+    pub fn build(self) -> Vec<String> {
+        let mut cmd = vec![
+            "op-supervisor".to_string(),
+            "--l1-rpc".to_string(),
+            self.l1_rpc,
+            "--l2-consensus.jwt-secret".to_string(),
+            self.l2_consensus_jwt_secret,
+            "--datadir".to_string(),
+            self.datadir,
+            "--dependency-set".to_string(),
+            self.dependency_set,
+            "--rollup-config-paths".to_string(),
+            self.rollup_config_paths,
+            // RPC
+            "--rpc.addr".to_string(),
+            self.rpc_addr,
+            "--rpc.port".to_string(),
+            self.rpc_port.to_string(),
+            "--rpc.enable-admin".to_string(),
+            // Log level
+            "--log.level".to_string(),
+            self.log_level,
+        ];
+
+        if !self.l2_consensus_nodes.is_empty() {
+            cmd.push("--l2-consensus.nodes".to_string());
+            cmd.push(self.l2_consensus_nodes.join(","));
+        }
+
+        merge_extra_args(cmd, self.extra_args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_supervisor_cmd_builder() {
+        let cmd = OpSupervisorCmdBuilder::new(
+            "http://anvil:8545",
+            "/data/jwt.hex",
+            "/data/db",
+            "/data/dependency-set.json",
+            "/data/rollup-*.json",
+        )
+        .l2_consensus_nodes(["http://kona-a:7545", "http://kona-b:7545"])
+        .rpc_port(8555)
+        .build();
+
+        let joined = cmd.join(" ");
+        assert_eq!(cmd[0], "op-supervisor");
+        assert!(joined.contains("--l2-consensus.nodes http://kona-a:7545,http://kona-b:7545"));
+        assert!(joined.contains("--dependency-set /data/dependency-set.json"));
+        assert!(joined.contains("--rpc.port 8555"));
+    }
+
+    #[test]
+    fn test_op_supervisor_cmd_builder_without_nodes() {
+        let cmd = OpSupervisorCmdBuilder::new(
+            "http://anvil:8545",
+            "/data/jwt.hex",
+            "/data/db",
+            "/data/dependency-set.json",
+            "/data/rollup-*.json",
+        )
+        .extra_args(["--log.level", "INFO"])
+        .build();
+
+        assert!(!cmd.contains(&"--l2-consensus.nodes".to_string()));
+        assert_eq!(
+            cmd.iter().filter(|arg| *arg == "--log.level").count(),
+            1,
+            "{cmd:?}"
+        );
+        assert!(cmd.ends_with(&["--log.level".to_string(), "INFO".to_string()]));
+    }
+}
@@ -0,0 +1,349 @@
+//! op-supervisor service for interop (cross-chain messaging).
+//!
+//! The op-supervisor follows the consensus nodes of every L2 in a dependency set and
+//! validates the cross-chain messages between them. It reads the dependency set and the
+//! rollup config of each chain from files generated on the host before it starts.
+//!
+//! Deploying several L2s against the same L1 is not supported yet, so nothing in the
+//! default deployment starts this service.
+
+mod cmd;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use url::Url;
+
+pub use cmd::OpSupervisorCmdBuilder;
+
+use crate::docker::{DockerImage, ExposedPort, KupDocker, PortMapping, ServiceConfig};
+use crate::metrics::ContainerDeployTimings;
+use crate::service::{self, KupcakeService};
+
+/// Default port for the op-supervisor RPC.
+pub const DEFAULT_RPC_PORT: u16 = 8545;
+
+/// Default Docker image for op-supervisor.
+pub const DEFAULT_DOCKER_IMAGE: &str =
+    "us-docker.pkg.dev/oplabs-tools-artifacts/images/op-supervisor";
+/// Default Docker tag for op-supervisor. Interop is only released from `develop`.
+pub const DEFAULT_DOCKER_TAG: &str = "develop";
+
+/// Name of the dependency set file in the op-supervisor config directory.
+pub const DEPENDENCY_SET_FILENAME: &str = "dependency-set.json";
+/// Name of the copied JWT secret in the op-supervisor config directory.
+const JWT_FILENAME: &str = "jwt.hex";
+
+/// A chain of the dependency set.
+#[derive(Debug, Clone)]
+pub struct SupervisedChain {
+    /// L2 chain ID.
+    pub chain_id: u64,
+    /// Host path of the chain's `rollup.json`.
+    pub rollup_config_path: PathBuf,
+    /// RPC URL of a consensus node (kona-node or op-node) of the chain.
+    pub node_rpc_url: String,
+}
+
+/// Input parameters for deploying op-supervisor.
+pub struct OpSupervisorInput {
+    /// L1 RPC URL (e.g., Anvil's Docker-internal URL).
+    pub l1_rpc_url: String,
+    /// Chains of the dependency set.
+    pub chains: Vec<SupervisedChain>,
+    /// Host path of the JWT secret shared with the consensus nodes.
+    pub jwt_secret_path: PathBuf,
+}
+
+/// Configuration for the op-supervisor component.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OpSupervisorBuilder {
+    /// Docker image configuration for op-supervisor.
+    pub docker_image: DockerImage,
+    /// Container name for op-supervisor.
+    pub container_name: String,
+    /// Host for the RPC endpoint.
+    pub host: String,
+    /// Port for the op-supervisor RPC server (container port).
+    pub rpc_port: u16,
+    /// Host port for RPC. If None, not published to host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_host_port: Option<u16>,
+    /// Log level for op-supervisor (e.g., "INFO", "DEBUG").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    /// Extra arguments to pass to op-supervisor.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+}
+
+impl Default for OpSupervisorBuilder {
+    fn default() -> Self {
+        Self {
+            docker_image: DockerImage::new(DEFAULT_DOCKER_IMAGE, DEFAULT_DOCKER_TAG),
+            container_name: "kupcake-op-supervisor".to_string(),
+            host: "0.0.0.0".to_string(),
+            rpc_port: DEFAULT_RPC_PORT,
+            rpc_host_port: Some(0),
+            log_level: None,
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+/// Handler for a running op-supervisor instance.
+pub struct OpSupervisorHandler {
+    /// Docker container ID.
+    pub container_id: String,
+    /// Docker container name.
+    pub container_name: String,
+    /// The RPC URL for the op-supervisor (internal Docker network).
+    pub rpc_url: Url,
+    /// The RPC URL accessible from host (if published). None if not published.
+    pub rpc_host_url: Option<Url>,
+    /// Deploy timings for metrics.
+    pub deploy_timings: ContainerDeployTimings,
+}
+
+/// The dependency set of `chains`: every chain can exchange messages with every other,
+/// from genesis.
+fn dependency_set(chains: &[SupervisedChain]) -> serde_json::Value {
+    let dependencies: serde_json::Map<String, serde_json::Value> = chains
+        .iter()
+        .map(|chain| {
+            (
+                chain.chain_id.to_string(),
+                json!({
+                    "chainIndex": chain.chain_id,
+                    "activationTime": 0,
+                    "historyMinTime": 0,
+                }),
+            )
+        })
+        .collect();
+    json!({ "dependencies": dependencies })
+}
+
+/// Name of the copied rollup config of a chain in the op-supervisor config directory.
+fn rollup_config_filename(chain_id: u64) -> String {
+    format!("rollup-{}.json", chain_id)
+}
+
+impl OpSupervisorBuilder {
+    /// Write the files op-supervisor reads at startup to `host_config_path`: the
+    /// dependency set, the rollup config of each chain and the JWT secret.
+    ///
+    /// Returns the path of the dependency set.
+    pub async fn generate_config_files(
+        &self,
+        host_config_path: &Path,
+        input: &OpSupervisorInput,
+    ) -> Result<PathBuf, anyhow::Error> {
+        if input.chains.is_empty() {
+            anyhow::bail!("op-supervisor requires at least one chain in the dependency set");
+        }
+
+        tokio::fs::create_dir_all(host_config_path)
+            .await
+            .context("Failed to create op-supervisor config directory")?;
+
+        for chain in &input.chains {
+            let dest = host_config_path.join(rollup_config_filename(chain.chain_id));
+            tokio::fs::copy(&chain.rollup_config_path, &dest)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to copy the rollup config of chain {} from {}",
+                        chain.chain_id,
+                        chain.rollup_config_path.display()
+                    )
+                })?;
+        }
+
+        tokio::fs::copy(&input.jwt_secret_path, host_config_path.join(JWT_FILENAME))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to copy the JWT secret from {}",
+                    input.jwt_secret_path.display()
+                )
+            })?;
+
+        let config_path = host_config_path.join(DEPENDENCY_SET_FILENAME);
+        let content = serde_json::to_string_pretty(&dependency_set(&input.chains))
+            .context("Failed to serialize the dependency set")?;
+        tokio::fs::write(&config_path, content)
+            .await
+            .context("Failed to write the dependency set file")?;
+
+        tracing::debug!(path = ?config_path, chains = input.chains.len(), "Dependency set written");
+        Ok(config_path)
+    }
+
+    /// Build the Docker command arguments for op-supervisor.
+    pub fn build_cmd(
+        &self,
+        _host_config_path: &Path,
+        input: &OpSupervisorInput,
+    ) -> Result<Vec<String>, anyhow::Error> {
+        let container_config_path = PathBuf::from("/data");
+
+        Ok(OpSupervisorCmdBuilder::new(
+            input.l1_rpc_url.clone(),
+            container_config_path
+                .join(JWT_FILENAME)
+                .display()
+                .to_string(),
+            container_config_path.join("db").display().to_string(),
+            container_config_path
+                .join(DEPENDENCY_SET_FILENAME)
+                .display()
+                .to_string(),
+            container_config_path
+                .join("rollup-*.json")
+                .display()
+                .to_string(),
+        )
+        .l2_consensus_nodes(input.chains.iter().map(|chain| chain.node_rpc_url.clone()))
+        .rpc_addr(&self.host)
+        .rpc_port(self.rpc_port)
+        .log_level(self.log_level.as_deref().unwrap_or("DEBUG"))
+        .extra_args(self.extra_args.clone())
+        .build())
+    }
+}
+
+impl KupcakeService for OpSupervisorBuilder {
+    type Input = OpSupervisorInput;
+    type Output = OpSupervisorHandler;
+
+    fn container_name(&self) -> &str {
+        &self.container_name
+    }
+
+    fn docker_image(&self) -> &DockerImage {
+        &self.docker_image
+    }
+
+    async fn deploy<'a>(
+        &'a self,
+        docker: &'a mut KupDocker,
+        host_config_path: &'a Path,
+        input: OpSupervisorInput,
+    ) -> Result<OpSupervisorHandler, anyhow::Error> {
+        let container_config_path = PathBuf::from("/data");
+
+        self.generate_config_files(host_config_path, &input).await?;
+        let cmd = self.build_cmd(host_config_path, &input)?;
+
+        let port_mappings: Vec<PortMapping> =
+            PortMapping::tcp_optional(self.rpc_port, self.rpc_host_port)
+                .into_iter()
+                .collect();
+
+        let service_config = ServiceConfig::new(self.docker_image.clone())
+            .cmd(cmd)
+            .ports(port_mappings)
+            .expose(ExposedPort::tcp(self.rpc_port))
+            .bind(host_config_path, &container_config_path, "rw")
+            .maybe_user(docker.service_user(host_config_path)?);
+
+        let (handler, timings) = service::deploy_container(
+            docker,
+            &self.docker_image,
+            &self.container_name,
+            service_config,
+        )
+        .await
+        .context("Failed to start op-supervisor container")?;
+
+        // Build internal Docker network URL
+        let rpc_url = KupDocker::build_http_url(&handler.container_name, self.rpc_port)?;
+
+        // Build host-accessible URLs from bound ports
+        let rpc_host_url = handler.build_host_url(self.rpc_port, "http")?;
+
+        tracing::info!(
+            container_id = %handler.container_id,
+            container_name = %handler.container_name,
+            chains = input.chains.len(),
+            ?rpc_host_url,
+            "op-supervisor container started"
+        );
+
+        Ok(OpSupervisorHandler {
+            container_id: handler.container_id,
+            container_name: handler.container_name,
+            rpc_url,
+            rpc_host_url,
+            deploy_timings: timings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(dir: &Path, chain_id: u64) -> SupervisedChain {
+        let rollup_config_path = dir.join(format!("rollup-src-{}.json", chain_id));
+        std::fs::write(
+            &rollup_config_path,
+            format!(r#"{{"l2_chain_id": {}}}"#, chain_id),
+        )
+        .unwrap();
+        SupervisedChain {
+            chain_id,
+            rollup_config_path,
+            node_rpc_url: format!("http://kona-{}:7545", chain_id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_config_files() {
+        let dir = tempdir::TempDir::new("op-supervisor-test").unwrap();
+        let jwt_secret_path = dir.path().join("jwt-src.hex");
+        std::fs::write(&jwt_secret_path, "0xdeadbeef").unwrap();
+        let input = OpSupervisorInput {
+            l1_rpc_url: "http://anvil:8545".to_string(),
+            chains: vec![chain(dir.path(), 901), chain(dir.path(), 902)],
+            jwt_secret_path,
+        };
+
+        let config_dir = dir.path().join("op-supervisor");
+        let builder = OpSupervisorBuilder::default();
+        let path = builder
+            .generate_config_files(&config_dir, &input)
+            .await
+            .unwrap();
+
+        let depset: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(depset["dependencies"]["901"]["chainIndex"], 901);
+        assert_eq!(depset["dependencies"]["902"]["activationTime"], 0);
+        assert!(config_dir.join("rollup-902.json").exists());
+        assert!(config_dir.join(JWT_FILENAME).exists());
+
+        let cmd = builder.build_cmd(&config_dir, &input).unwrap().join(" ");
+        assert!(cmd.contains("--l2-consensus.nodes http://kona-901:7545,http://kona-902:7545"));
+        assert!(cmd.contains("--rollup-config-paths /data/rollup-*.json"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_config_files_requires_chains() {
+        let dir = tempdir::TempDir::new("op-supervisor-test").unwrap();
+        let input = OpSupervisorInput {
+            l1_rpc_url: "http://anvil:8545".to_string(),
+            chains: Vec::new(),
+            jwt_secret_path: dir.path().join("jwt.hex"),
+        };
+        assert!(
+            OpSupervisorBuilder::default()
+                .generate_config_files(dir.path(), &input)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Custom sidecar containers attached to an L2 node.
+//!
+//! A sidecar is any [`KupcakeService`] whose input is an [`L2NodeContext`]. It is
+//! started right after its L2 node (and the node's op-conductor, if any) and receives
+//! the node's container names and RPC URLs, e.g. to run an indexer or a load tester
+//! against that specific node.
+//!
+//! Sidecars are attached programmatically with [`L2NodeBuilder::with_sidecar`] and
+//! are not persisted to `Kupcake.toml`.
+//!
+//! [`L2NodeBuilder::with_sidecar`]: crate::L2NodeBuilder::with_sidecar
+
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use anyhow::Result;
+use url::Url;
+
+use crate::{
+    docker::KupDocker,
+    service::KupcakeService,
+    services::l2_node::{L2NodeHandler, L2NodeRole},
+};
+
+/// Information about a started L2 node, passed to each of its sidecars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L2NodeContext {
+    /// Role of the node.
+    pub role: L2NodeRole,
+    /// Container name of the execution client (op-reth).
+    pub execution_container_name: String,
+    /// HTTP RPC URL of the execution client (internal Docker network).
+    pub execution_rpc_url: Url,
+    /// WebSocket RPC URL of the execution client (internal Docker network).
+    pub execution_ws_url: Url,
+    /// HTTP RPC URL of the execution client accessible from host, if published.
+    pub execution_host_url: Option<Url>,
+    /// Container name of the consensus client (kona-node or op-node).
+    pub consensus_container_name: String,
+    /// RPC URL of the consensus client (internal Docker network).
+    pub consensus_rpc_url: Url,
+    /// RPC URL of the consensus client accessible from host, if published.
+    pub consensus_host_url: Option<Url>,
+}
+
+impl L2NodeContext {
+    /// Build the context of a running L2 node.
+    pub fn from_handler(handler: &L2NodeHandler) -> Self {
+        Self {
+            role: handler.role,
+            execution_container_name: handler.op_reth.container_name.clone(),
+            execution_rpc_url: handler.op_reth.http_rpc_url.clone(),
+            execution_ws_url: handler.op_reth.ws_rpc_url.clone(),
+            execution_host_url: handler.op_reth.http_host_url.clone(),
+            consensus_container_name: handler.kona_node.container_name.clone(),
+            consensus_rpc_url: handler.kona_node.rpc_url.clone(),
+            consensus_host_url: handler.kona_node.rpc_host_url.clone(),
+        }
+    }
+}
+
+/// Object-safe view of a sidecar service.
+///
+/// Implemented for every [`KupcakeService`] taking an [`L2NodeContext`] as input, so
+/// sidecars of different types can be stored on the same node. The sidecar's output
+/// handler is dropped; only its container name is kept on the [`L2NodeHandler`].
+pub trait L2NodeSidecar: Send + Sync + 'static {
+    /// The container name of the sidecar.
+    fn container_name(&self) -> &str;
+
+    /// Deploy the sidecar against a running L2 node.
+    fn deploy_sidecar<'a>(
+        &'a self,
+        docker: &'a mut KupDocker,
+        host_config_path: &'a Path,
+        context: L2NodeContext,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+}
+
+impl<S> L2NodeSidecar for S
+where
+    S: KupcakeService<Input = L2NodeContext>,
+{
+    fn container_name(&self) -> &str {
+        KupcakeService::container_name(self)
+    }
+
+    fn deploy_sidecar<'a>(
+        &'a self,
+        docker: &'a mut KupDocker,
+        host_config_path: &'a Path,
+        context: L2NodeContext,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            self.deploy(docker, host_config_path, context).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Sidecars attached to an L2 node, in start order.
+///
+/// Two sets of sidecars compare equal when their container names match.
+#[derive(Clone, Default)]
+pub struct Sidecars(Vec<Arc<dyn L2NodeSidecar>>);
+
+impl Sidecars {
+    /// Add a sidecar, started after the ones already registered.
+    pub fn push(&mut self, sidecar: impl L2NodeSidecar) {
+        self.0.push(Arc::new(sidecar));
+    }
+
+    /// Iterate over the sidecars in start order.
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn L2NodeSidecar>> {
+        self.0.iter()
+    }
+
+    /// Container names of the sidecars, in start order.
+    pub fn container_names(&self) -> Vec<&str> {
+        self.0.iter().map(|s| s.container_name()).collect()
+    }
+
+    /// Returns true if no sidecar is attached.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of attached sidecars.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl fmt::Debug for Sidecars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.container_names()).finish()
+    }
+}
+
+impl PartialEq for Sidecars {
+    fn eq(&self, other: &Self) -> bool {
+        self.container_names() == other.container_names()
+    }
+}
+
+impl Eq for Sidecars {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{L2NodeBuilder, docker::DockerImage};
+
+    struct Indexer {
+        container_name: String,
+        image: DockerImage,
+    }
+
+    impl Indexer {
+        fn new(container_name: &str) -> Self {
+            Self {
+                container_name: container_name.to_string(),
+                image: DockerImage::new("indexer", "latest"),
+            }
+        }
+    }
+
+    impl KupcakeService for Indexer {
+        type Input = L2NodeContext;
+        type Output = ();
+
+        fn container_name(&self) -> &str {
+            &self.container_name
+        }
+
+        fn docker_image(&self) -> &DockerImage {
+            &self.image
+        }
+
+        async fn deploy<'a>(
+            &'a self,
+            _docker: &'a mut KupDocker,
+            _host_config_path: &'a Path,
+            _input: L2NodeContext,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_with_sidecar_keeps_start_order() {
+        let node = L2NodeBuilder::validator()
+            .with_sidecar(Indexer::new("indexer-a"))
+            .with_sidecar(Indexer::new("indexer-b"));
+
+        assert_eq!(node.sidecars.len(), 2);
+        assert_eq!(
+            node.sidecars.container_names(),
+            vec!["indexer-a", "indexer-b"]
+        );
+        assert_eq!(
+            format!("{:?}", node.sidecars),
+            r#"["indexer-a", "indexer-b"]"#
+        );
+        assert_ne!(node, L2NodeBuilder::validator());
+    }
+
+    #[test]
+    fn test_sidecars_are_not_serialized() {
+        let node = L2NodeBuilder::validator().with_sidecar(Indexer::new("indexer"));
+
+        let toml = toml::to_string(&node).unwrap();
+        assert!(!toml.contains("indexer"));
+
+        let restored: L2NodeBuilder = toml::from_str(&toml).unwrap();
+        assert!(restored.sidecars.is_empty());
+    }
+}
@@ -2,20 +2,30 @@
 //!
 //! Runs a Contender Docker container against a deployed kupcake L2 network,
 //! automatically funding the spammer account via the L1→L2 faucet deposit.
+//! A [`SpamReport`] of the L2 blocks produced during the run can be written as JSON.
 
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{
-    Deployer, DockerImage, KupDocker, ServiceConfig, docker::CreateAndStartContainerOptions, faucet,
+    Deployer, DockerImage, KupDocker, ServiceConfig, docker::CreateAndStartContainerOptions,
+    faucet, rpc,
 };
 
 /// Default Docker image for Contender.
 pub const CONTENDER_DEFAULT_IMAGE: &str = "flashbots/contender";
 /// Default Docker tag for Contender.
 pub const CONTENDER_DEFAULT_TAG: &str = "latest";
+/// Path of [`SpamConfig::scenario_file`] inside the Contender container.
+pub const SCENARIO_FILE_CONTAINER_PATH: &str = "/data/scenario.toml";
+/// Default path of the spam report, relative to the outdata directory.
+pub const SPAM_REPORT_PATH: &str = "contender/report.json";
 
 /// Named spam presets for quick workload selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
@@ -58,6 +68,7 @@ impl SpamPreset {
 
         SpamConfig {
             scenario: scenario.to_string(),
+            scenario_file: None,
             tps,
             duration: 0,
             forever: true,
@@ -78,7 +89,12 @@ impl SpamPreset {
 /// Configuration for a spam run.
 pub struct SpamConfig {
     /// Scenario name (built-in) or path to custom TOML file.
+    ///
+    /// Must be empty when `scenario_file` is set.
     pub scenario: String,
+    /// Custom scenario TOML file, mounted into the container at
+    /// [`SCENARIO_FILE_CONTAINER_PATH`] and run instead of a built-in scenario.
+    pub scenario_file: Option<PathBuf>,
     /// Transactions per second.
     pub tps: u64,
     /// Duration in seconds (ignored if `forever` is true).
@@ -107,22 +123,91 @@ pub struct SpamConfig {
     pub quiet: bool,
 }
 
+/// Summary of a spam run, written as JSON by [`run_spam`].
+///
+/// The counts cover the L2 transactions, deposits excluded, included in the blocks
+/// produced while Contender was running.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpamReport {
+    /// Scenario name, or path of the scenario file.
+    pub scenario: String,
+    /// Requested transactions per second.
+    pub target_tps: u64,
+    /// Transactions per second achieved over the run.
+    pub tps: f64,
+    /// Number of transactions included during the run.
+    pub total_transactions: u64,
+    /// Number of included transactions that reverted.
+    pub failed_transactions: u64,
+    /// Gas used by the included transactions.
+    pub gas_used: u64,
+    /// First L2 block of the run.
+    pub first_block: u64,
+    /// Last L2 block scanned.
+    pub last_block: u64,
+    /// Wall-clock duration of the run, in seconds.
+    pub duration_secs: f64,
+    /// Exit code of Contender, or `None` if the run was interrupted with Ctrl+C.
+    pub exit_code: Option<i64>,
+    /// Whether every block of the run was scanned. Partial data otherwise.
+    pub complete: bool,
+}
+
+impl SpamReport {
+    /// Add the receipts of a block to the report, skipping deposit transactions.
+    fn add_receipts(&mut self, receipts: &[Value]) {
+        for receipt in receipts {
+            if receipt["type"].as_str() == Some(DEPOSIT_TX_TYPE) {
+                continue;
+            }
+            self.total_transactions += 1;
+            self.gas_used += faucet::hex_field(receipt, "gasUsed").unwrap_or(0);
+            if receipt["status"].as_str() == Some("0x0") {
+                self.failed_transactions += 1;
+            }
+        }
+    }
+
+    /// Write the report as pretty-printed JSON, creating the parent directory.
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize spam report")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write spam report to {}", path.display()))
+    }
+}
+
+/// Transaction type of the L1-to-L2 deposits.
+const DEPOSIT_TX_TYPE: &str = "0x7e";
+
 /// Run the Contender spammer against a deployed L2 network.
 ///
 /// The caller is responsible for resolving the target node's RPC URL and
 /// setting it on `config.rpc_url` before calling this function.
+///
+/// With a `report_path` (e.g. [`SPAM_REPORT_PATH`] under the outdata directory), a
+/// [`SpamReport`] is written there once Contender stops, even if it failed or was
+/// interrupted.
 pub async fn run_spam(
     docker: &mut KupDocker,
     deployer: &Deployer,
     config: &SpamConfig,
+    report_path: Option<PathBuf>,
 ) -> Result<()> {
     if config.rpc_url.is_empty() {
         anyhow::bail!("rpc_url must be set on SpamConfig before calling run_spam");
     }
 
+    // Resolve scenario (built-in name, custom file path or scenario file) before funding
+    // and pulling the image, so that a bad scenario file fails fast
+    let (scenario_arg, scenario_mount) = resolve_scenario_config(config)?;
+
     // Load funder account from anvil.json
     let (funder_address, funder_private_key) =
-        load_funder_account(&deployer.outdata, config.funder_account_index)?;
+        load_funder_account(&deployer.outdata, config.funder_account_index).await?;
 
     tracing::info!(
         funder_address = %funder_address,
@@ -132,15 +217,14 @@ pub async fn run_spam(
     );
 
     // Fund the funder on L2 via faucet deposit (wait for confirmation)
-    faucet::faucet_deposit(docker, deployer, &funder_address, config.fund_amount, true)
+    let fund_amount =
+        faucet::EthAmount::from_eth_f64(config.fund_amount).context("Invalid spam fund amount")?;
+    faucet::faucet_deposit(docker, deployer, &funder_address, fund_amount, true)
         .await
         .context("Failed to fund spammer account on L2")?;
 
     tracing::info!("Funder account funded on L2");
 
-    // Resolve scenario (built-in name vs custom file path)
-    let (scenario_arg, scenario_file) = resolve_scenario(&config.scenario)?;
-
     tracing::info!(rpc_url = %config.rpc_url, "Targeting sequencer RPC");
 
     // Create contender data directory for DB persistence
@@ -169,17 +253,24 @@ pub async fn run_spam(
     ));
 
     // If custom scenario file, mount it read-only
-    if let Some(ref file_path) = scenario_file {
+    if let Some((ref file_path, ref container_path)) = scenario_mount {
         let abs_path = file_path
             .canonicalize()
             .with_context(|| format!("Scenario file not found: {}", file_path.display()))?;
-        service_config = service_config.bind_str(format!(
-            "{}:/scenarios/{}:ro",
-            abs_path.display(),
-            file_path.file_name().unwrap_or_default().to_string_lossy()
-        ));
+        service_config =
+            service_config.bind_str(format!("{}:{}:ro", abs_path.display(), container_path));
     }
 
+    // Blocks after this one are attributed to the spam run
+    let report_start = match report_path {
+        Some(_) => {
+            let l2_rpc_url = faucet::l2_rpc_url(docker, deployer).await?;
+            let start_block = latest_block_number(&docker.rpc_client, &l2_rpc_url).await?;
+            Some((l2_rpc_url, start_block))
+        }
+        None => None,
+    };
+
     tracing::info!(container = %container_name, "Starting Contender container...");
 
     docker
@@ -190,21 +281,97 @@ pub async fn run_spam(
         )
         .await
         .context("Failed to start Contender container")?;
+    let started = Instant::now();
 
     tracing::info!("Contender is running, streaming logs...");
 
     // Stream logs until container exits
-    docker.stream_logs(&container_name).await?;
+    let exit_code = docker.follow_logs(&container_name).await?;
+
+    if let (Some(path), Some((l2_rpc_url, start_block))) = (report_path, report_start) {
+        let mut report = SpamReport {
+            scenario: match config.scenario_file {
+                Some(ref path) => path.display().to_string(),
+                None => config.scenario.clone(),
+            },
+            target_tps: config.tps,
+            duration_secs: started.elapsed().as_secs_f64(),
+            exit_code,
+            ..Default::default()
+        };
+        if let Err(e) = scan_blocks(&docker.rpc_client, &l2_rpc_url, start_block, &mut report).await
+        {
+            tracing::warn!(error = %e, "Spam report is incomplete");
+        }
+        if report.duration_secs > 0.0 {
+            report.tps = report.total_transactions as f64 / report.duration_secs;
+        }
+        report.write_to_file(&path)?;
+        tracing::info!(
+            path = %path.display(),
+            total_transactions = report.total_transactions,
+            tps = report.tps,
+            "Spam report written"
+        );
+    }
+
+    match exit_code {
+        Some(code) if code != 0 => {
+            anyhow::bail!("Container {} exited with code {}", container_name, code)
+        }
+        Some(_) => tracing::info!(container_name, "Container completed successfully"),
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// Number of the latest L2 block.
+async fn latest_block_number(client: &reqwest::Client, rpc_url: &str) -> Result<u64> {
+    let block: Value = rpc::json_rpc_call(
+        client,
+        rpc_url,
+        "eth_getBlockByNumber",
+        vec![serde_json::json!("latest"), serde_json::json!(false)],
+    )
+    .await?;
+    faucet::hex_field(&block, "number").context("Latest L2 block has no number")
+}
+
+/// Add the transactions of the L2 blocks after `start_block`, up to the latest one, to
+/// `report`.
+///
+/// On error, `report` keeps the blocks scanned so far and stays incomplete.
+async fn scan_blocks(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    start_block: u64,
+    report: &mut SpamReport,
+) -> Result<()> {
+    let head = latest_block_number(client, rpc_url).await?;
+
+    report.first_block = start_block + 1;
+    report.last_block = start_block;
+    for block in start_block + 1..=head {
+        let receipts: Vec<Value> = rpc::json_rpc_call(
+            client,
+            rpc_url,
+            "eth_getBlockReceipts",
+            vec![serde_json::json!(format!("{block:#x}"))],
+        )
+        .await
+        .with_context(|| format!("Failed to get the receipts of L2 block {block}"))?;
+        report.add_receipts(&receipts);
+        report.last_block = block;
+    }
+    report.complete = true;
 
     Ok(())
 }
 
 /// Load a funder account (address + private key) from `anvil.json` at the given index.
-fn load_funder_account(outdata: &Path, index: usize) -> Result<(String, String)> {
-    let anvil_path = outdata.join("anvil/anvil.json");
-    let content = std::fs::read_to_string(&anvil_path)
-        .with_context(|| format!("Failed to read {}", anvil_path.display()))?;
-    let data: Value = serde_json::from_str(&content).context("Failed to parse anvil.json")?;
+async fn load_funder_account(outdata: &Path, index: usize) -> Result<(String, String)> {
+    let data = crate::json_file::read_anvil_json(outdata).await?;
 
     let address = data["available_accounts"]
         .get(index)
@@ -231,6 +398,32 @@ fn load_funder_account(outdata: &Path, index: usize) -> Result<(String, String)>
     Ok((address, private_key))
 }
 
+/// Resolve the scenario of `config` to a contender argument and an optional file to
+/// mount, as a (host path, container path) pair.
+///
+/// A `scenario_file` must be a readable file and excludes a `scenario` name.
+fn resolve_scenario_config(config: &SpamConfig) -> Result<(String, Option<(PathBuf, String)>)> {
+    let Some(ref path) = config.scenario_file else {
+        let (arg, file) = resolve_scenario(&config.scenario)?;
+        return Ok((arg.clone(), file.map(|file| (file, arg))));
+    };
+    if !config.scenario.is_empty() {
+        anyhow::bail!(
+            "Give either a scenario name ({}) or a scenario file ({}), not both",
+            config.scenario,
+            path.display()
+        );
+    }
+    if !path.is_file() {
+        anyhow::bail!("Scenario file {} is not a file", path.display());
+    }
+    std::fs::File::open(path)
+        .with_context(|| format!("Cannot read scenario file {}", path.display()))?;
+
+    let container_path = SCENARIO_FILE_CONTAINER_PATH.to_string();
+    Ok((container_path.clone(), Some((path.clone(), container_path))))
+}
+
 /// Resolve a scenario argument to a contender argument and optional file path.
 ///
 /// Built-in scenarios (e.g., "transfers", "erc20") are passed directly.
@@ -341,10 +534,40 @@ mod tests {
         assert_eq!(file.unwrap(), PathBuf::from("path/to/scenario.toml"));
     }
 
+    #[test]
+    fn test_resolve_scenario_config_file() {
+        let dir = tempdir::TempDir::new("spam-test").unwrap();
+        let path = dir.path().join("custom.toml");
+        std::fs::write(&path, "[[spam]]\n").unwrap();
+
+        let mut config = SpamPreset::Light.to_config("http://test:8545/");
+        let (arg, mount) = resolve_scenario_config(&config).unwrap();
+        assert_eq!(arg, "transfers");
+        assert!(mount.is_none());
+
+        config.scenario_file = Some(path.clone());
+        let err = resolve_scenario_config(&config).unwrap_err().to_string();
+        assert!(err.contains("not both"), "{err}");
+
+        config.scenario.clear();
+        let (arg, mount) = resolve_scenario_config(&config).unwrap();
+        assert_eq!(arg, SCENARIO_FILE_CONTAINER_PATH);
+        assert_eq!(
+            mount,
+            Some((path, SCENARIO_FILE_CONTAINER_PATH.to_string()))
+        );
+
+        config.scenario_file = Some(dir.path().join("missing.toml"));
+        assert!(resolve_scenario_config(&config).is_err());
+        config.scenario_file = Some(dir.path().to_path_buf());
+        assert!(resolve_scenario_config(&config).is_err());
+    }
+
     #[test]
     fn test_build_contender_cmd_basic() {
         let config = SpamConfig {
             scenario: "transfers".to_string(),
+            scenario_file: None,
             tps: 10,
             duration: 30,
             forever: false,
@@ -393,6 +616,7 @@ mod tests {
     fn test_build_contender_cmd_forever_mode() {
         let config = SpamConfig {
             scenario: "transfers".to_string(),
+            scenario_file: None,
             tps: 100,
             duration: 30,
             forever: true,
@@ -421,6 +645,7 @@ mod tests {
     fn test_build_contender_cmd_with_report_and_extra_args() {
         let config = SpamConfig {
             scenario: "transfers".to_string(),
+            scenario_file: None,
             tps: 50,
             duration: 60,
             forever: false,
@@ -451,7 +676,39 @@ mod tests {
     }
 
     #[test]
-    fn test_load_funder_account() {
+    fn test_spam_report_add_receipts() {
+        let mut report = SpamReport::default();
+        report.add_receipts(&[
+            serde_json::json!({ "type": "0x7e", "status": "0x1", "gasUsed": "0xc350" }),
+            serde_json::json!({ "type": "0x2", "status": "0x1", "gasUsed": "0x5208" }),
+            serde_json::json!({ "type": "0x2", "status": "0x0", "gasUsed": "0x7530" }),
+        ]);
+        report.add_receipts(&[]);
+
+        assert_eq!(report.total_transactions, 2);
+        assert_eq!(report.failed_transactions, 1);
+        assert_eq!(report.gas_used, 21_000 + 30_000);
+    }
+
+    #[test]
+    fn test_spam_report_write_to_file() {
+        let dir = tempdir::TempDir::new("spam-test").unwrap();
+        let path = dir.path().join(SPAM_REPORT_PATH);
+        let report = SpamReport {
+            scenario: "transfers".to_string(),
+            total_transactions: 10,
+            exit_code: Some(1),
+            ..Default::default()
+        };
+        report.write_to_file(&path).unwrap();
+
+        let loaded: SpamReport =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded, report);
+    }
+
+    #[tokio::test]
+    async fn test_load_funder_account() {
         let dir = tempdir::TempDir::new("spam-test").unwrap();
         let anvil_dir = dir.path().join("anvil");
         std::fs::create_dir_all(&anvil_dir).unwrap();
@@ -473,14 +730,14 @@ mod tests {
         )
         .unwrap();
 
-        let (addr, key) = load_funder_account(dir.path(), 0).unwrap();
+        let (addr, key) = load_funder_account(dir.path(), 0).await.unwrap();
         assert_eq!(addr, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
         assert_eq!(
             key,
             "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
         );
 
-        let (addr, key) = load_funder_account(dir.path(), 1).unwrap();
+        let (addr, key) = load_funder_account(dir.path(), 1).await.unwrap();
         assert_eq!(addr, "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
         assert_eq!(
             key,
@@ -488,8 +745,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_load_funder_account_out_of_range() {
+    #[tokio::test]
+    async fn test_load_funder_account_out_of_range() {
         let dir = tempdir::TempDir::new("spam-test").unwrap();
         let anvil_dir = dir.path().join("anvil");
         std::fs::create_dir_all(&anvil_dir).unwrap();
@@ -509,7 +766,7 @@ mod tests {
         )
         .unwrap();
 
-        let result = load_funder_account(dir.path(), 5);
+        let result = load_funder_account(dir.path(), 5).await;
         assert!(result.is_err());
     }
 
@@ -686,9 +943,20 @@ mod tests {
                 log_max_size: None,
                 log_max_file: None,
                 stream_logs: false,
+                restart_policy: Default::default(),
+                extra_hosts: Vec::new(),
+                ca_bundle: None,
+                proxy: None,
+                rpc_client: Default::default(),
+                run_as_host_user: true,
+                recorded_host_ports: Default::default(),
+                subnet: None,
+                gateway: None,
+                external_network: None,
             },
             l2_stack: Default::default(),
             monitoring: Default::default(),
+            blockscout: Default::default(),
             dashboards_path: None,
             detach: false,
             snapshot: None,
@@ -696,6 +964,14 @@ mod tests {
             deployment_target: Default::default(),
             dump_state: true,
             override_state: None,
+            external_l1: None,
+            post_deploy: None,
+            min_disk_space_gib: crate::DEFAULT_MIN_DISK_SPACE_GIB,
+            keep_going: false,
+            verify_chain_ids: false,
+            reuse_if_healthy: false,
+            restart_unhealthy: false,
+            additional_l2_chains: Vec::new(),
         };
 
         assert_eq!(container_name(&deployer), "kup-test-contender");
@@ -1,12 +1,18 @@
 //! Network status reporting for deployed Kupcake networks.
 
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use bollard::Docker;
+use bollard::{Docker, container::ListContainersOptions};
 use comfy_table::{Attribute, Cell, Color, Table};
+use serde::Serialize;
 
-use crate::{Deployer, docker::ContainerState};
+use crate::{Deployer, deployer::KUPCONF_FILENAME, docker::ContainerState};
+
+/// Default container name prefix of the networks listed by [`running_networks`].
+pub const DEFAULT_NETWORK_PREFIX: &str = "kup-";
 
 /// Status of a single service (container).
 pub struct ServiceStatus {
@@ -45,7 +51,7 @@ pub struct NetworkStatus {
 }
 
 /// Get the state of a container via Docker inspect.
-async fn container_state(docker: &Docker, name: &str) -> ContainerState {
+pub(crate) async fn container_state(docker: &Docker, name: &str) -> ContainerState {
     match docker.inspect_container(name, None).await {
         Ok(info) => {
             let status = info
@@ -113,9 +119,9 @@ pub async fn network_status(deployer: &Deployer) -> Result<NetworkStatus> {
                 state: container_state(&docker, &seq.op_reth.container_name).await,
             },
             consensus: ServiceStatus {
-                label: "kona-node".to_string(),
-                container_name: seq.kona_node.container_name.clone(),
-                state: container_state(&docker, &seq.kona_node.container_name).await,
+                label: seq.consensus_client().service_name().to_string(),
+                container_name: seq.consensus_container_name().to_string(),
+                state: container_state(&docker, seq.consensus_container_name()).await,
             },
             conductor,
         });
@@ -132,9 +138,9 @@ pub async fn network_status(deployer: &Deployer) -> Result<NetworkStatus> {
                 state: container_state(&docker, &val.op_reth.container_name).await,
             },
             consensus: ServiceStatus {
-                label: "kona-node".to_string(),
-                container_name: val.kona_node.container_name.clone(),
-                state: container_state(&docker, &val.kona_node.container_name).await,
+                label: val.consensus_client().service_name().to_string(),
+                container_name: val.consensus_container_name().to_string(),
+                state: container_state(&docker, val.consensus_container_name()).await,
             },
             conductor: None,
         });
@@ -171,6 +177,171 @@ pub async fn network_status(deployer: &Deployer) -> Result<NetworkStatus> {
     })
 }
 
+/// A container of a network found by [`running_networks`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunningContainer {
+    /// Service name: the container name without the network prefix (e.g. `op-reth`).
+    pub service: String,
+    /// Docker container name.
+    pub container_name: String,
+    /// Docker state, e.g. `running` or `exited`.
+    pub state: String,
+    /// Published ports, as `host_port->container_port/protocol`.
+    pub ports: Vec<String>,
+}
+
+/// A network found by [`running_networks`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunningNetwork {
+    /// Network name, without the `-network` suffix.
+    pub name: String,
+    /// L1 chain ID from the network's `Kupcake.toml`, if it was found.
+    pub l1_chain_id: Option<u64>,
+    /// L2 chain ID from the network's `Kupcake.toml`, if it was found.
+    pub l2_chain_id: Option<u64>,
+    /// Containers of the network, sorted by name.
+    pub containers: Vec<RunningContainer>,
+}
+
+/// A listed container, before grouping by network.
+struct ListedContainer {
+    name: String,
+    networks: Vec<String>,
+    state: String,
+    ports: Vec<String>,
+}
+
+/// Split `container` into its network name and service name, if it is prefixed by `name`.
+fn split_container_name<'a>(container: &'a str, name: &'a str) -> Option<(&'a str, &'a str)> {
+    let service = container.strip_prefix(name)?.strip_prefix('-')?;
+    Some((name, service))
+}
+
+/// Group containers by the kupcake network they belong to: the Docker network
+/// `<name>-network` they are attached to, or else the longest of the `registered`
+/// network names (whose containers may run on an external Docker network), where
+/// `<name>` prefixes the container name. Other containers are left out.
+fn group_by_network(
+    containers: Vec<ListedContainer>,
+    registered: &[&str],
+) -> BTreeMap<String, Vec<RunningContainer>> {
+    let mut networks: BTreeMap<String, Vec<RunningContainer>> = BTreeMap::new();
+    for container in containers {
+        let attached = container.networks.iter().find_map(|network| {
+            split_container_name(&container.name, network.strip_suffix("-network")?)
+        });
+        let Some((network, service)) = attached.or_else(|| {
+            registered
+                .iter()
+                .filter_map(|name| split_container_name(&container.name, name))
+                .max_by_key(|(name, _)| name.len())
+        }) else {
+            continue;
+        };
+        let (network, service) = (network.to_string(), service.to_string());
+        networks.entry(network).or_default().push(RunningContainer {
+            service,
+            container_name: container.name,
+            state: container.state,
+            ports: container.ports,
+        });
+    }
+    for containers in networks.values_mut() {
+        containers.sort_by(|a, b| a.container_name.cmp(&b.container_name));
+    }
+    networks
+}
+
+/// Where the `Kupcake.toml` of network `name` is: its data directory in the devnet
+/// registry, or `./data-<name>` when it is not registered.
+fn network_config_path(name: &str, registry: &[crate::DevnetEntry]) -> PathBuf {
+    registry
+        .iter()
+        .find(|entry| entry.name == name)
+        .map(|entry| entry.datadir.clone())
+        .unwrap_or_else(|| PathBuf::from(format!("data-{}", name)))
+        .join(KUPCONF_FILENAME)
+}
+
+/// Discover the kupcake networks that have containers, running or not, whose name
+/// starts with `prefix` (see [`DEFAULT_NETWORK_PREFIX`]).
+///
+/// Containers are grouped by network, and the chain IDs are read from the `Kupcake.toml`
+/// of each network when it can be found.
+pub async fn running_networks(prefix: &str) -> Result<Vec<RunningNetwork>> {
+    let docker =
+        Docker::connect_with_local_defaults().context("Failed to connect to Docker daemon")?;
+
+    let options = ListContainersOptions {
+        all: true,
+        filters: HashMap::from([("name".to_string(), vec![prefix.to_string()])]),
+        ..Default::default()
+    };
+    let containers = docker
+        .list_containers(Some(options))
+        .await
+        .context("Failed to list containers")?;
+
+    let listed = containers
+        .into_iter()
+        .filter_map(|c| {
+            // Container names from Docker API start with "/"
+            let name = c
+                .names?
+                .into_iter()
+                .map(|name| name.strip_prefix('/').map(String::from).unwrap_or(name))
+                .find(|name| name.starts_with(prefix))?;
+            let networks = c
+                .network_settings
+                .and_then(|settings| settings.networks)
+                .map(|networks| networks.into_keys().collect())
+                .unwrap_or_default();
+            let mut ports: Vec<String> = c
+                .ports
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|port| {
+                    let protocol = port
+                        .typ
+                        .map(|t| t.to_string())
+                        .unwrap_or_else(|| "tcp".to_string());
+                    Some(format!(
+                        "{}->{}/{}",
+                        port.public_port?, port.private_port, protocol
+                    ))
+                })
+                .collect();
+            // IPv4 and IPv6 bindings of the same port are listed separately.
+            ports.sort();
+            ports.dedup();
+            Some(ListedContainer {
+                name,
+                networks,
+                state: c.state.unwrap_or_default(),
+                ports,
+            })
+        })
+        .collect();
+
+    let registry = crate::DevnetRegistry::new()
+        .and_then(|registry| registry.list())
+        .unwrap_or_default();
+    let registered: Vec<&str> = registry.iter().map(|entry| entry.name.as_str()).collect();
+
+    Ok(group_by_network(listed, &registered)
+        .into_iter()
+        .map(|(name, containers)| {
+            let deployer = Deployer::load_from_file(&network_config_path(&name, &registry)).ok();
+            RunningNetwork {
+                l1_chain_id: deployer.as_ref().map(|d| d.l1_chain_id),
+                l2_chain_id: deployer.as_ref().map(|d| d.l2_chain_id),
+                name,
+                containers,
+            }
+        })
+        .collect())
+}
+
 // -- Display helpers --
 
 fn state_cell(state: ContainerState) -> Cell {
@@ -269,3 +440,123 @@ impl fmt::Display for NetworkStatus {
         Ok(())
     }
 }
+
+/// Display a list of networks as a single table.
+pub struct RunningNetworks<'a>(pub &'a [RunningNetwork]);
+
+impl fmt::Display for RunningNetworks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chain_id = |id: Option<u64>| {
+            id.map(|id| id.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        let mut table = Table::new();
+        table.set_header(vec![
+            header("Network"),
+            header("L1"),
+            header("L2"),
+            header("Service"),
+            header("State"),
+            header("Ports"),
+        ]);
+        for network in self.0 {
+            for (i, container) in network.containers.iter().enumerate() {
+                let (name, l1, l2) = if i == 0 {
+                    (
+                        Cell::new(&network.name).add_attribute(Attribute::Bold),
+                        Cell::new(chain_id(network.l1_chain_id)),
+                        Cell::new(chain_id(network.l2_chain_id)),
+                    )
+                } else {
+                    (Cell::new(""), Cell::new(""), Cell::new(""))
+                };
+                let state = match container.state.as_str() {
+                    "running" => Cell::new(&container.state).fg(Color::Green),
+                    "paused" | "restarting" => Cell::new(&container.state).fg(Color::Yellow),
+                    _ => Cell::new(&container.state).fg(Color::Red),
+                };
+                table.add_row(vec![
+                    name,
+                    l1,
+                    l2,
+                    Cell::new(&container.service),
+                    state,
+                    Cell::new(container.ports.join(", ")),
+                ]);
+            }
+        }
+        writeln!(f, "{table}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listed(name: &str, networks: &[&str]) -> ListedContainer {
+        ListedContainer {
+            name: name.to_string(),
+            networks: networks.iter().map(|n| n.to_string()).collect(),
+            state: "running".to_string(),
+            ports: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_group_by_network() {
+        let networks = group_by_network(
+            vec![
+                listed("kup-a-op-reth", &["kup-a-network"]),
+                listed("kup-a-anvil", &["kup-a-network"]),
+                listed("kup-ab-op-reth", &["kup-ab-network"]),
+                listed("kup-a-stray", &["bridge"]),
+                listed("kup-b-anvil", &["kup-a-network"]),
+            ],
+            &[],
+        );
+
+        assert_eq!(networks.keys().collect::<Vec<_>>(), vec!["kup-a", "kup-ab"]);
+        let services: Vec<_> = networks["kup-a"]
+            .iter()
+            .map(|c| c.service.as_str())
+            .collect();
+        assert_eq!(services, vec!["anvil", "op-reth"]);
+        assert_eq!(networks["kup-ab"][0].service, "op-reth");
+    }
+
+    #[test]
+    fn test_group_by_network_external() {
+        let networks = group_by_network(
+            vec![
+                listed("kup-c-op-reth", &["shared"]),
+                listed("kup-cd-anvil", &["shared"]),
+                listed("other-anvil", &["shared"]),
+            ],
+            &["kup-c", "kup-cd"],
+        );
+
+        assert_eq!(networks.keys().collect::<Vec<_>>(), vec!["kup-c", "kup-cd"]);
+        assert_eq!(networks["kup-c"][0].service, "op-reth");
+        assert_eq!(networks["kup-cd"][0].service, "anvil");
+    }
+
+    #[test]
+    fn test_network_config_path() {
+        let registry = vec![crate::DevnetEntry {
+            name: "kup-a".to_string(),
+            state: crate::DevnetState::Running,
+            datadir: PathBuf::from("/tmp/data-a"),
+            created_at: String::new(),
+            stopped_at: None,
+        }];
+        assert_eq!(
+            network_config_path("kup-a", &registry),
+            PathBuf::from("/tmp/data-a/Kupcake.toml")
+        );
+        assert_eq!(
+            network_config_path("kup-b", &registry),
+            PathBuf::from("data-kup-b/Kupcake.toml")
+        );
+    }
+}
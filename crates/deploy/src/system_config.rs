@@ -0,0 +1,123 @@
+//! Fee parameter updates on a running network through the L1 `SystemConfig` contract.
+//!
+//! Transactions are sent by the system config owner, one of Anvil's unlocked accounts,
+//! so these helpers only work with the Anvil L1 (not with an external L1).
+
+use anyhow::{Context, Result};
+
+use crate::{Deployer, KupDocker, health::build_host_rpc_url, rpc};
+
+/// ABI-encode a call to `signature` with `uint32` arguments.
+fn encode_uint32_call(signature: &str, args: &[u32]) -> String {
+    let selector = &alloy_core::primitives::keccak256(signature)[..4];
+    let args: String = args.iter().map(|arg| format!("{:064x}", arg)).collect();
+    format!("0x{}{}", hex::encode(selector), args)
+}
+
+impl Deployer {
+    /// Address of the deployed `SystemConfigProxy`, read from `l2-stack/state.json`.
+    pub fn system_config_address(&self) -> Result<String> {
+        let state = crate::json_file::read_state_json(&self.outdata.join("l2-stack"))?;
+        crate::json_file::state_chain_address(&state, "SystemConfigProxy")
+    }
+
+    /// Send `calldata` to the `SystemConfig` contract from the system config owner and
+    /// wait for it to be mined. Returns the L1 transaction hash.
+    ///
+    /// Fails if the transaction reverts.
+    pub async fn send_system_config_tx(
+        &self,
+        docker: &KupDocker,
+        calldata: &str,
+    ) -> Result<String> {
+        if self.external_l1.is_some() {
+            anyhow::bail!(
+                "SystemConfig updates are sent from an Anvil account and are not supported with an external L1"
+            );
+        }
+
+        let client = docker.rpc_client.clone();
+        let system_config = self.system_config_address()?;
+        let owner = self.derive_accounts()?.system_config_owner;
+        let owner = format!("0x{}", hex::encode(&owner.address));
+        let l1_url = build_host_rpc_url(docker, &self.anvil.container_name, self.anvil.port)
+            .await
+            .context("Failed to build L1 RPC URL - is Anvil running?")?;
+
+        rpc::send_unlocked_transaction_and_wait(
+            &client,
+            &l1_url,
+            serde_json::json!({
+                "from": owner,
+                "to": system_config,
+                "data": calldata,
+            }),
+            "SystemConfig",
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "SystemConfig transaction from {} failed (is it still the system config owner?)",
+                owner
+            )
+        })
+    }
+
+    /// Set the Ecotone L1 fee scalars of the L2 via `SystemConfig.setGasConfigEcotone`.
+    ///
+    /// The L2 picks the new values up from the next L1 block it derives from.
+    pub async fn set_base_fee_scalars(
+        &self,
+        docker: &KupDocker,
+        base_fee_scalar: u32,
+        blob_base_fee_scalar: u32,
+    ) -> Result<String> {
+        let calldata = encode_uint32_call(
+            "setGasConfigEcotone(uint32,uint32)",
+            &[base_fee_scalar, blob_base_fee_scalar],
+        );
+        let tx_hash = self.send_system_config_tx(docker, &calldata).await?;
+
+        tracing::info!(
+            base_fee_scalar,
+            blob_base_fee_scalar,
+            %tx_hash,
+            "Updated L1 fee scalars"
+        );
+        Ok(tx_hash)
+    }
+
+    /// Set the L2 EIP-1559 parameters via `SystemConfig.setEIP1559Params` (Holocene+).
+    pub async fn set_eip1559_params(
+        &self,
+        docker: &KupDocker,
+        denominator: u32,
+        elasticity: u32,
+    ) -> Result<String> {
+        let calldata = encode_uint32_call(
+            "setEIP1559Params(uint32,uint32)",
+            &[denominator, elasticity],
+        );
+        let tx_hash = self.send_system_config_tx(docker, &calldata).await?;
+
+        tracing::info!(denominator, elasticity, %tx_hash, "Updated EIP-1559 parameters");
+        Ok(tx_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_uint32_call() {
+        assert_eq!(
+            encode_uint32_call("setGasConfigEcotone(uint32,uint32)", &[1368, 810949]),
+            format!("0x21d7fde5{:064x}{:064x}", 1368, 810949)
+        );
+        assert_eq!(
+            encode_uint32_call("setEIP1559Params(uint32,uint32)", &[250, 6]),
+            format!("0xc0fd4b41{:064x}{:064x}", 250, 6)
+        );
+    }
+}
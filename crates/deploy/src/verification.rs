@@ -0,0 +1,233 @@
+//! Export of the deployed L1 contracts for explorer verification.
+//!
+//! op-deployer records every address it deployed in `l2-stack/state.json`, and the
+//! contract release it deployed from in the `l1ContractsLocator` / `l2ContractsLocator`
+//! entries of `l2-stack/intent.toml`. [`Deployer::export_verification_bundle`] gathers
+//! both into a folder that can be fed to `forge verify-contract` or an explorer import:
+//!
+//! - `addresses.json`: the [`DeployedAddresses`], grouped by superchain, implementations
+//!   and OP chain.
+//! - `manifest.json`: chain IDs, contract locators and the op-deployer image.
+//! - `state.json` and `intent.toml`: the raw op-deployer outputs.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Deployer;
+
+/// Contract addresses deployed by op-deployer, keyed by contract name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeployedAddresses {
+    /// Superchain contracts (SuperchainConfig, ProtocolVersions, ...).
+    pub superchain: BTreeMap<String, String>,
+    /// Shared implementation contracts (OPCM, implementations of the proxies).
+    pub implementations: BTreeMap<String, String>,
+    /// Contracts of each OP chain, in deployment order.
+    pub chains: Vec<ChainAddresses>,
+}
+
+/// Contract addresses of a single OP chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainAddresses {
+    /// Chain ID as recorded by op-deployer (32-byte hex).
+    pub id: String,
+    /// Contract addresses, keyed by contract name.
+    pub contracts: BTreeMap<String, String>,
+}
+
+impl DeployedAddresses {
+    /// Parse the addresses out of op-deployer's `state.json`.
+    ///
+    /// Only string fields holding a 20-byte hex address are kept; nested objects such
+    /// as the start block are skipped.
+    pub fn from_state(state: &Value) -> Result<Self> {
+        let superchain = state
+            .get("superchainDeployment")
+            .or_else(|| state.get("superchainContracts"))
+            .map(addresses_of)
+            .unwrap_or_default();
+        let implementations = state
+            .get("implementationsDeployment")
+            .map(addresses_of)
+            .unwrap_or_default();
+        let chains: Vec<ChainAddresses> = state["opChainDeployments"]
+            .as_array()
+            .with_context(|| {
+                format!(
+                    "opChainDeployments not found in state.json; expected {}",
+                    crate::json_file::STATE_JSON_SHAPE
+                )
+            })?
+            .iter()
+            .map(|chain| ChainAddresses {
+                id: chain["id"].as_str().unwrap_or_default().to_string(),
+                contracts: addresses_of(chain),
+            })
+            .collect();
+
+        if chains.is_empty() {
+            anyhow::bail!("state.json does not contain any OP chain deployment");
+        }
+
+        Ok(Self {
+            superchain,
+            implementations,
+            chains,
+        })
+    }
+}
+
+/// Metadata written next to the addresses in a verification bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerificationManifest {
+    pub l1_chain_id: u64,
+    pub l2_chain_id: u64,
+    /// Contract release of the L1 contracts (e.g. `tag://op-contracts/v3.0.0`).
+    pub l1_contracts_locator: Option<String>,
+    /// Contract release of the L2 predeploys.
+    pub l2_contracts_locator: Option<String>,
+    /// op-deployer image that performed the deployment.
+    pub op_deployer_image: String,
+}
+
+/// Keep the fields of a JSON object that hold an address.
+fn addresses_of(value: &Value) -> BTreeMap<String, String> {
+    value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| {
+            let address = value.as_str()?;
+            is_address(address).then(|| (name.clone(), address.to_string()))
+        })
+        .collect()
+}
+
+pub(crate) fn is_address(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Read the contract locators from op-deployer's `intent.toml`.
+fn contract_locators(intent: &toml::Value) -> (Option<String>, Option<String>) {
+    let locator = |key: &str| intent.get(key).and_then(|v| v.as_str()).map(String::from);
+    (locator("l1ContractsLocator"), locator("l2ContractsLocator"))
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    let content = serde_json::to_string_pretty(value)?;
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+impl Deployer {
+    /// Write the deployed contract addresses and versions to `out` for explorer
+    /// verification.
+    ///
+    /// Requires the op-deployer outputs of a live deployment (`l2-stack/state.json`
+    /// and `l2-stack/intent.toml`); they are not available when restored from a
+    /// snapshot. Returns the exported addresses.
+    pub fn export_verification_bundle(&self, out: &Path) -> Result<DeployedAddresses> {
+        let l2_stack_path = self.outdata.join("l2-stack");
+        let state_path = l2_stack_path.join("state.json");
+        let intent_path = l2_stack_path.join("intent.toml");
+
+        let state = crate::json_file::read_state_json(&l2_stack_path)?;
+        let addresses = DeployedAddresses::from_state(&state)?;
+
+        let intent_content = std::fs::read_to_string(&intent_path)
+            .with_context(|| format!("Failed to read {}", intent_path.display()))?;
+        let intent: toml::Value =
+            toml::from_str(&intent_content).context("Failed to parse intent.toml")?;
+        let (l1_contracts_locator, l2_contracts_locator) = contract_locators(&intent);
+
+        let manifest = VerificationManifest {
+            l1_chain_id: self.l1_chain_id,
+            l2_chain_id: self.l2_chain_id,
+            l1_contracts_locator,
+            l2_contracts_locator,
+            op_deployer_image: self.op_deployer.docker_image.to_string(),
+        };
+
+        std::fs::create_dir_all(out)
+            .with_context(|| format!("Failed to create {}", out.display()))?;
+        write_json(&out.join("addresses.json"), &addresses)?;
+        write_json(&out.join("manifest.json"), &manifest)?;
+        std::fs::copy(&state_path, out.join("state.json")).context("Failed to copy state.json")?;
+        std::fs::write(out.join("intent.toml"), intent_content)
+            .context("Failed to copy intent.toml")?;
+
+        tracing::info!(
+            out = %out.display(),
+            chains = addresses.chains.len(),
+            "Exported contract verification bundle"
+        );
+
+        Ok(addresses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORTAL: &str = "0x1111111111111111111111111111111111111111";
+    const OPCM: &str = "0x2222222222222222222222222222222222222222";
+    const SUPERCHAIN_CONFIG: &str = "0x3333333333333333333333333333333333333333";
+
+    #[test]
+    fn test_deployed_addresses_from_state() {
+        let state = serde_json::json!({
+            "superchainDeployment": { "SuperchainConfigProxy": SUPERCHAIN_CONFIG },
+            "implementationsDeployment": { "OpcmImpl": OPCM },
+            "opChainDeployments": [{
+                "id": "0x000000000000000000000000000000000000000000000000000000000000a455",
+                "OptimismPortalProxy": PORTAL,
+                "StartBlock": { "number": "0x1" },
+                "ProxyAdminAddress": "0x"
+            }]
+        });
+
+        let addresses = DeployedAddresses::from_state(&state).unwrap();
+        assert_eq!(
+            addresses.superchain["SuperchainConfigProxy"],
+            SUPERCHAIN_CONFIG
+        );
+        assert_eq!(addresses.implementations["OpcmImpl"], OPCM);
+        assert_eq!(addresses.chains.len(), 1);
+        assert_eq!(
+            addresses.chains[0].contracts,
+            BTreeMap::from([("OptimismPortalProxy".to_string(), PORTAL.to_string())])
+        );
+    }
+
+    #[test]
+    fn test_deployed_addresses_requires_a_chain() {
+        let err = DeployedAddresses::from_state(&serde_json::json!({ "opChainDeployments": [] }))
+            .unwrap_err();
+        assert!(err.to_string().contains("OP chain"), "{err}");
+        assert!(DeployedAddresses::from_state(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_contract_locators() {
+        let intent: toml::Value = toml::from_str(
+            r#"
+            l1ContractsLocator = "tag://op-contracts/v3.0.0"
+            l2ContractsLocator = "embedded"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            contract_locators(&intent),
+            (
+                Some("tag://op-contracts/v3.0.0".to_string()),
+                Some("embedded".to_string())
+            )
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Non-fatal issues collected during a deployment.
+//!
+//! They are logged as they happen and returned in
+//! [`DeploymentResult::warnings`](crate::DeploymentResult), so that callers and CI can
+//! assert on them.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// Time after which waiting for a service to be ready is reported as slow.
+pub(crate) const SLOW_READINESS_THRESHOLD: Duration = Duration::from_secs(20);
+
+/// A non-fatal issue encountered during a deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, derive_more::Display)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum DeployWarning {
+    /// A non-critical service failed to start and was skipped (`keep_going`).
+    #[display("{service} failed to start and was skipped: {error}")]
+    ServiceSkipped { service: String, error: String },
+    /// A service took longer than [`SLOW_READINESS_THRESHOLD`] to be ready.
+    #[display("{service} took {elapsed_secs}s to be ready")]
+    SlowReadiness { service: String, elapsed_secs: u64 },
+    /// Sequencing was not started on the op-conductor leader.
+    #[display("sequencing not started on the op-conductor leader: {reason}")]
+    SequencingNotStarted { reason: String },
+    /// A recorded host port was in use, so Docker published the service on another port.
+    #[display(
+        "recorded host port {port} of {service} is in use, published on a Docker-assigned port instead"
+    )]
+    HostPortMoved { service: String, port: u16 },
+}
+
+/// Handle the result of starting a non-critical service.
+///
+/// With `keep_going`, a failure is logged and recorded as
+/// [`DeployWarning::ServiceSkipped`] and `Ok(None)` is returned; otherwise it is
+/// returned as is.
+pub(crate) fn best_effort<T>(
+    service: &str,
+    result: Result<T>,
+    keep_going: bool,
+    warnings: &mut Vec<DeployWarning>,
+) -> Result<Option<T>> {
+    match result {
+        Ok(handler) => Ok(Some(handler)),
+        Err(e) if keep_going => {
+            let warning = DeployWarning::ServiceSkipped {
+                service: service.to_string(),
+                error: format!("{:#}", e),
+            };
+            tracing::warn!("{}", warning);
+            warnings.push(warning);
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Record a [`DeployWarning::SlowReadiness`] if `service` took more than
+/// [`SLOW_READINESS_THRESHOLD`] to be ready.
+pub(crate) fn check_readiness(service: &str, elapsed: Duration, warnings: &mut Vec<DeployWarning>) {
+    if elapsed > SLOW_READINESS_THRESHOLD {
+        let warning = DeployWarning::SlowReadiness {
+            service: service.to_string(),
+            elapsed_secs: elapsed.as_secs(),
+        };
+        tracing::warn!("{}", warning);
+        warnings.push(warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_effort() {
+        let mut warnings = Vec::new();
+
+        let ok = best_effort("op-proposer", Ok(1), false, &mut warnings).unwrap();
+        assert_eq!(ok, Some(1));
+
+        let failed = || -> Result<u32> { Err(anyhow::anyhow!("missing prestate")) };
+        assert!(best_effort("op-challenger", failed(), false, &mut warnings).is_err());
+        assert!(warnings.is_empty());
+
+        let skipped = best_effort("op-challenger", failed(), true, &mut warnings).unwrap();
+        assert_eq!(skipped, None);
+        assert_eq!(
+            warnings,
+            [DeployWarning::ServiceSkipped {
+                service: "op-challenger".to_string(),
+                error: "missing prestate".to_string(),
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "op-challenger failed to start and was skipped: missing prestate"
+        );
+    }
+
+    #[test]
+    fn test_check_readiness() {
+        let mut warnings = Vec::new();
+        check_readiness("L1 contracts", Duration::from_secs(3), &mut warnings);
+        assert!(warnings.is_empty());
+
+        check_readiness("L1 contracts", Duration::from_secs(45), &mut warnings);
+        assert_eq!(
+            warnings,
+            [DeployWarning::SlowReadiness {
+                service: "L1 contracts".to_string(),
+                elapsed_secs: 45,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_warning_serializes_with_kind() {
+        let warning = DeployWarning::SequencingNotStarted {
+            reason: "RPC not published".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&warning).unwrap(),
+            serde_json::json!({
+                "kind": "sequencing-not-started",
+                "reason": "RPC not published",
+            })
+        );
+    }
+}
@@ -0,0 +1,1046 @@
+//! L2-to-L1 ETH withdrawals through the `L2ToL1MessagePasser` and the L1 `OptimismPortal`.
+//!
+//! A withdrawal goes through three steps:
+//! 1. [`initiate`] sends `initiateWithdrawal` to the message passer on L2, signed with the
+//!    withdrawing account's key.
+//! 2. [`prove`] proves it on L1 against a dispute game created by op-proposer for an L2
+//!    block at or after the withdrawal.
+//! 3. [`finalize`] releases the ETH on L1 once the proof maturity delay and the dispute
+//!    game finality delay have passed, optionally fast-forwarding Anvil's clock.
+//!
+//! L1 transactions are sent from the Anvil deployer account (index 0), so proving and
+//! finalizing only work with the Anvil L1 (not with an external L1).
+
+use std::str::FromStr;
+
+use alloy_consensus::{TxLegacy, transaction::RlpEcdsaEncodableTx};
+use alloy_network::TxSignerSync;
+use alloy_primitives::{Address, B256, Bytes, TxKind, U256, keccak256};
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{SolCall, SolEvent, SolValue};
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::faucet::{
+    EthAmount, hex_field, l2_rpc_url, load_deployer_address, load_optimism_portal_address,
+};
+use crate::{Deployer, KupDocker, health::build_host_rpc_url, rpc};
+
+/// Address of the `L2ToL1MessagePasser` predeploy.
+pub const L2_TO_L1_MESSAGE_PASSER: &str = "0x4200000000000000000000000000000000000016";
+
+/// Gas limit of the withdrawal when it is executed on L1 by `finalizeWithdrawalTransaction`.
+const WITHDRAWAL_GAS_LIMIT: u64 = 100_000;
+
+/// Default time [`initiate`] waits for op-proposer to create a dispute game covering
+/// the withdrawal, in seconds.
+pub const DEFAULT_PROVABLE_TIMEOUT_SECS: u64 = 600;
+
+/// Number of L2 blocks per `eth_getLogs` request when looking up a withdrawal.
+const LOG_SCAN_BLOCKS: u64 = 10_000;
+
+/// `GameStatus.IN_PROGRESS` of a dispute game.
+const GAME_STATUS_IN_PROGRESS: u8 = 0;
+
+/// ABI of the message passer, the `OptimismPortal`, the `DisputeGameFactory` and the
+/// `FaultDisputeGame` functions used by withdrawals.
+mod abi {
+    alloy_sol_types::sol! {
+        struct WithdrawalTransaction {
+            uint256 nonce;
+            address sender;
+            address target;
+            uint256 value;
+            uint256 gasLimit;
+            bytes data;
+        }
+
+        struct OutputRootProof {
+            bytes32 version;
+            bytes32 stateRoot;
+            bytes32 messagePasserStorageRoot;
+            bytes32 latestBlockhash;
+        }
+
+        event MessagePassed(
+            uint256 indexed nonce,
+            address indexed sender,
+            address indexed target,
+            uint256 value,
+            uint256 gasLimit,
+            bytes data,
+            bytes32 withdrawalHash
+        );
+
+        function initiateWithdrawal(address target, uint256 gasLimit, bytes data);
+
+        function proveWithdrawalTransaction(
+            WithdrawalTransaction withdrawal,
+            uint256 disputeGameIndex,
+            OutputRootProof outputRootProof,
+            bytes[] withdrawalProof
+        );
+        function finalizeWithdrawalTransaction(WithdrawalTransaction withdrawal);
+        function provenWithdrawals(bytes32 withdrawalHash, address prover)
+            returns (address disputeGameProxy, uint64 timestamp);
+        function checkWithdrawal(bytes32 withdrawalHash, address prover);
+        function respectedGameType() returns (uint32);
+        function proofMaturityDelaySeconds() returns (uint256);
+        function disputeGameFinalityDelaySeconds() returns (uint256);
+        function anchorStateRegistry() returns (address);
+
+        function gameCount() returns (uint256);
+        function gameAtIndex(uint256 index)
+            returns (uint32 gameType, uint64 timestamp, address proxy);
+
+        function status() returns (uint8);
+        function maxClockDuration() returns (uint64);
+        function l2BlockNumber() returns (uint256);
+        function rootClaim() returns (bytes32);
+        function resolveClaim(uint256 claimIndex, uint256 numToResolve);
+        function resolve() returns (uint8);
+    }
+}
+
+/// An L2-to-L1 withdrawal, as emitted by the `MessagePassed` event (`Types.WithdrawalTransaction`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawalTransaction {
+    /// Nonce of the message passer (with the message version in the upper two bytes).
+    pub nonce: U256,
+    /// L2 account that initiated the withdrawal.
+    pub sender: Address,
+    /// L1 recipient of the withdrawal.
+    pub target: Address,
+    /// Amount of ETH withdrawn, in wei.
+    pub value: U256,
+    /// Gas limit of the L1 execution.
+    pub gas_limit: U256,
+    /// Calldata of the L1 execution.
+    pub data: Bytes,
+}
+
+impl WithdrawalTransaction {
+    /// The withdrawal hash, which identifies the withdrawal on both layers.
+    pub fn hash(&self) -> B256 {
+        keccak256(
+            (
+                self.nonce,
+                self.sender,
+                self.target,
+                self.value,
+                self.gas_limit,
+                self.data.clone(),
+            )
+                .abi_encode_params(),
+        )
+    }
+
+    /// The withdrawal as a `Types.WithdrawalTransaction` struct argument.
+    fn to_abi(&self) -> abi::WithdrawalTransaction {
+        abi::WithdrawalTransaction {
+            nonce: self.nonce,
+            sender: self.sender,
+            target: self.target,
+            value: self.value,
+            gasLimit: self.gas_limit,
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Result of [`initiate`].
+#[derive(Debug)]
+pub struct WithdrawalInitiated {
+    /// Hash of the L2 `initiateWithdrawal` transaction.
+    pub l2_tx_hash: String,
+    /// L2 block that includes the withdrawal.
+    pub l2_block: u64,
+    /// Withdrawal hash, to pass to [`prove`] and [`finalize`].
+    pub withdrawal_hash: B256,
+    /// The withdrawal itself.
+    pub withdrawal: WithdrawalTransaction,
+    /// Whether a dispute game covering the withdrawal exists, so it can be proven.
+    /// Only checked when [`initiate`] waits.
+    pub provable: bool,
+}
+
+/// Result of [`prove`].
+#[derive(Debug)]
+pub struct WithdrawalProven {
+    /// Hash of the L1 `proveWithdrawalTransaction` transaction.
+    pub l1_tx_hash: String,
+    /// Dispute game the withdrawal was proven against.
+    pub dispute_game: Address,
+    /// Index of the dispute game in the `DisputeGameFactory`.
+    pub dispute_game_index: u64,
+    /// L2 block of the dispute game's output root.
+    pub l2_block: u64,
+}
+
+/// Result of [`finalize`].
+#[derive(Debug)]
+pub struct WithdrawalFinalized {
+    /// Hash of the L1 `finalizeWithdrawalTransaction` transaction.
+    pub l1_tx_hash: String,
+    /// L1 recipient of the withdrawal.
+    pub target: Address,
+    /// Amount of ETH released on L1, in wei.
+    pub value: U256,
+}
+
+/// Initiate a withdrawal of `amount` ETH from L2 to L1.
+///
+/// Signs an `initiateWithdrawal` call to the `L2ToL1MessagePasser` with `from_key`
+/// (hex-encoded, with or without `0x`) and waits for it to be mined. The ETH is
+/// withdrawn to the same address on L1. With `wait`, also waits until op-proposer has
+/// created a dispute game covering the withdrawal, so that it can be proven right away.
+pub async fn initiate(
+    docker: &KupDocker,
+    deployer: &Deployer,
+    from_key: &str,
+    amount: EthAmount,
+    wait: bool,
+) -> Result<WithdrawalInitiated> {
+    let signer = parse_private_key(from_key)?;
+    let from = signer.address();
+
+    let client = docker.rpc_client.clone();
+    let l2_url = l2_rpc_url(docker, deployer).await?;
+
+    let value = U256::from(amount.wei());
+    let data = abi::initiateWithdrawalCall {
+        target: from,
+        gasLimit: U256::from(WITHDRAWAL_GAS_LIMIT),
+        data: Bytes::new(),
+    }
+    .abi_encode();
+    let to = Address::from_str(L2_TO_L1_MESSAGE_PASSER)?;
+
+    let chain_id = rpc_quantity(&client, &l2_url, "eth_chainId", vec![]).await?;
+    let nonce = rpc_quantity(
+        &client,
+        &l2_url,
+        "eth_getTransactionCount",
+        vec![serde_json::json!(from), serde_json::json!("pending")],
+    )
+    .await?;
+    let gas_price = rpc_quantity(&client, &l2_url, "eth_gasPrice", vec![]).await?;
+    let gas = rpc_quantity(
+        &client,
+        &l2_url,
+        "eth_estimateGas",
+        vec![serde_json::json!({
+            "from": from,
+            "to": to,
+            "value": format!("0x{:x}", value),
+            "data": format!("0x{}", hex::encode(&data)),
+        })],
+    )
+    .await
+    .context("Failed to estimate withdrawal gas - does the account hold enough ETH on L2?")?;
+
+    let raw_tx = sign_legacy_tx(
+        &signer,
+        TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price: u128::from(gas_price),
+            gas_limit: gas,
+            to: TxKind::Call(to),
+            value,
+            input: data.into(),
+        },
+    )?;
+
+    let l2_tx_hash: String = rpc::json_rpc_call(
+        &client,
+        &l2_url,
+        "eth_sendRawTransaction",
+        vec![serde_json::json!(format!("0x{}", hex::encode(raw_tx)))],
+    )
+    .await
+    .context("Failed to send withdrawal transaction")?;
+
+    tracing::info!(tx_hash = %l2_tx_hash, from = %from, "Withdrawal transaction sent on L2");
+
+    let receipt = rpc::wait_for_receipt(
+        &client,
+        &l2_url,
+        &l2_tx_hash,
+        "L2 withdrawal",
+        rpc::DEFAULT_RECEIPT_TIMEOUT_SECS,
+    )
+    .await?;
+    let (withdrawal, withdrawal_hash, l2_block) = receipt["logs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find_map(|log| parse_message_passed(log).ok())
+        .context("Withdrawal receipt has no MessagePassed event")?;
+
+    tracing::info!(%withdrawal_hash, l2_block, "Withdrawal initiated");
+
+    let provable = if wait {
+        let l1_url = l1_rpc_url(docker, deployer).await?;
+        let portal = load_optimism_portal_address(&deployer.outdata)?;
+        let dgf = crate::services::read_dgf_address(&deployer.outdata.join("l2-stack"))?;
+
+        tracing::info!("Waiting for a dispute game covering the withdrawal...");
+        rpc::wait_until_ready(
+            "Dispute game covering the withdrawal",
+            DEFAULT_PROVABLE_TIMEOUT_SECS,
+            || async {
+                find_dispute_game(&client, &l1_url, &portal, &dgf, l2_block)
+                    .await?
+                    .map(|_| ())
+                    .context("No dispute game covers the withdrawal block yet")
+            },
+        )
+        .await?;
+        true
+    } else {
+        false
+    };
+
+    Ok(WithdrawalInitiated {
+        l2_tx_hash,
+        l2_block,
+        withdrawal_hash,
+        withdrawal,
+        provable,
+    })
+}
+
+/// Prove a withdrawal on L1.
+///
+/// Looks up the withdrawal on L2, picks the newest dispute game of the respected game
+/// type whose L2 block is at or after the withdrawal, and sends
+/// `proveWithdrawalTransaction` to the `OptimismPortal` from the Anvil deployer account.
+///
+/// Requires `eth_getProof` at the dispute game's L2 block, which op-reth only serves for
+/// recent blocks unless proofs history is enabled.
+pub async fn prove(
+    docker: &KupDocker,
+    deployer: &Deployer,
+    withdrawal_hash: &str,
+) -> Result<WithdrawalProven> {
+    ensure_anvil_l1(deployer)?;
+    let withdrawal_hash = parse_withdrawal_hash(withdrawal_hash)?;
+
+    let client = docker.rpc_client.clone();
+    let l2_url = l2_rpc_url(docker, deployer).await?;
+    let l1_url = l1_rpc_url(docker, deployer).await?;
+
+    let (withdrawal, l2_block) = find_withdrawal(&client, &l2_url, withdrawal_hash).await?;
+
+    let from = load_deployer_address(&deployer.outdata).await?;
+    let portal = load_optimism_portal_address(&deployer.outdata)?;
+    let dgf = crate::services::read_dgf_address(&deployer.outdata.join("l2-stack"))?;
+
+    let game = find_dispute_game(&client, &l1_url, &portal, &dgf, l2_block)
+        .await?
+        .with_context(|| {
+            format!(
+                "No dispute game covers L2 block {} yet; wait for op-proposer to propose a later output",
+                l2_block
+            )
+        })?;
+
+    let proof = output_root_proof(&client, &l2_url, withdrawal_hash, game.l2_block).await?;
+    if proof.output_root() != game.root_claim {
+        anyhow::bail!(
+            "Output root of L2 block {} ({}) does not match the root claim of dispute game {} ({})",
+            game.l2_block,
+            proof.output_root(),
+            game.proxy,
+            game.root_claim
+        );
+    }
+
+    let data = abi::proveWithdrawalTransactionCall {
+        withdrawal: withdrawal.to_abi(),
+        disputeGameIndex: U256::from(game.index),
+        outputRootProof: proof.to_abi(),
+        withdrawalProof: proof.withdrawal_proof,
+    }
+    .abi_encode();
+
+    let l1_tx_hash =
+        send_l1_transaction(&client, &l1_url, &from, &portal, data, "Prove withdrawal").await?;
+
+    tracing::info!(
+        tx_hash = %l1_tx_hash,
+        dispute_game = %game.proxy,
+        l2_block = game.l2_block,
+        "Withdrawal proven on L1"
+    );
+
+    Ok(WithdrawalProven {
+        l1_tx_hash,
+        dispute_game: game.proxy,
+        dispute_game_index: game.index,
+        l2_block: game.l2_block,
+    })
+}
+
+/// Finalize a proven withdrawal on L1, releasing the ETH to its target.
+///
+/// Finalization requires the dispute game to be resolved in favor of the proposer and
+/// the proof maturity and dispute game finality delays to have passed. With
+/// `skip_challenge_period`, Anvil's clock is fast-forwarded past the game clock and
+/// these delays with `evm_increaseTime`, and the game is resolved if needed. The L1
+/// then runs ahead of the L2, which keeps its current L1 origin until it catches up.
+pub async fn finalize(
+    docker: &KupDocker,
+    deployer: &Deployer,
+    withdrawal_hash: &str,
+    skip_challenge_period: bool,
+) -> Result<WithdrawalFinalized> {
+    ensure_anvil_l1(deployer)?;
+    let withdrawal_hash = parse_withdrawal_hash(withdrawal_hash)?;
+
+    let client = docker.rpc_client.clone();
+    let l2_url = l2_rpc_url(docker, deployer).await?;
+    let l1_url = l1_rpc_url(docker, deployer).await?;
+
+    let (withdrawal, _) = find_withdrawal(&client, &l2_url, withdrawal_hash).await?;
+
+    let from = load_deployer_address(&deployer.outdata).await?;
+    let prover = Address::from_str(&from)?;
+    let portal = load_optimism_portal_address(&deployer.outdata)?;
+
+    let game = eth_call(
+        &client,
+        &l1_url,
+        &portal,
+        abi::provenWithdrawalsCall {
+            withdrawalHash: withdrawal_hash,
+            prover,
+        },
+    )
+    .await
+    .context("Failed to read proven withdrawal")?
+    .disputeGameProxy;
+    if game.is_zero() {
+        anyhow::bail!(
+            "Withdrawal {} has not been proven by {} yet; run prove first",
+            withdrawal_hash,
+            from
+        );
+    }
+
+    if skip_challenge_period {
+        skip_challenge_period_for(&client, &l1_url, &from, &portal, &game).await?;
+    }
+
+    eth_call(
+        &client,
+        &l1_url,
+        &portal,
+        abi::checkWithdrawalCall {
+            withdrawalHash: withdrawal_hash,
+            prover,
+        },
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Withdrawal {} cannot be finalized yet (dispute game {} unresolved or delays not elapsed); \
+             retry later or use --skip-challenge-period",
+            withdrawal_hash, game
+        )
+    })?;
+
+    let data = abi::finalizeWithdrawalTransactionCall {
+        withdrawal: withdrawal.to_abi(),
+    }
+    .abi_encode();
+    let l1_tx_hash = send_l1_transaction(
+        &client,
+        &l1_url,
+        &from,
+        &portal,
+        data,
+        "Finalize withdrawal",
+    )
+    .await?;
+
+    tracing::info!(tx_hash = %l1_tx_hash, target = %withdrawal.target, "Withdrawal finalized on L1");
+
+    Ok(WithdrawalFinalized {
+        l1_tx_hash,
+        target: withdrawal.target,
+        value: withdrawal.value,
+    })
+}
+
+/// Fast-forward Anvil past the clock of `game` and the portal's finalization delays,
+/// resolving the game on the way if it is still in progress.
+async fn skip_challenge_period_for(
+    client: &reqwest::Client,
+    l1_url: &str,
+    from: &str,
+    portal: &str,
+    game: &Address,
+) -> Result<()> {
+    let game = game.to_string();
+
+    let status = eth_call(client, l1_url, &game, abi::statusCall {}).await?;
+    if status == GAME_STATUS_IN_PROGRESS {
+        let max_clock = eth_call(client, l1_url, &game, abi::maxClockDurationCall {}).await?;
+        tracing::info!(
+            seconds = max_clock + 1,
+            "Fast-forwarding L1 past the dispute game clock"
+        );
+        rpc::evm_increase_time(l1_url, max_clock + 1).await?;
+
+        let resolve_claim = abi::resolveClaimCall {
+            claimIndex: U256::ZERO,
+            numToResolve: U256::ZERO,
+        }
+        .abi_encode();
+        send_l1_transaction(
+            client,
+            l1_url,
+            from,
+            &game,
+            resolve_claim,
+            "Resolve root claim",
+        )
+        .await?;
+        send_l1_transaction(
+            client,
+            l1_url,
+            from,
+            &game,
+            abi::resolveCall {}.abi_encode(),
+            "Resolve dispute game",
+        )
+        .await?;
+    }
+
+    let maturity = call_u64(
+        client,
+        l1_url,
+        portal,
+        abi::proofMaturityDelaySecondsCall {},
+    )
+    .await?;
+    // Moved from the portal to the AnchorStateRegistry in later contract versions.
+    let finality = match call_u64(
+        client,
+        l1_url,
+        portal,
+        abi::disputeGameFinalityDelaySecondsCall {},
+    )
+    .await
+    {
+        Ok(delay) => delay,
+        Err(_) => {
+            let registry = eth_call(client, l1_url, portal, abi::anchorStateRegistryCall {})
+                .await
+                .context("Failed to read the AnchorStateRegistry address")?
+                .to_string();
+            call_u64(
+                client,
+                l1_url,
+                &registry,
+                abi::disputeGameFinalityDelaySecondsCall {},
+            )
+            .await?
+        }
+    };
+
+    let seconds = maturity.max(finality) + 1;
+    tracing::info!(seconds, "Fast-forwarding L1 past the withdrawal delays");
+    rpc::evm_increase_time(l1_url, seconds).await
+}
+
+/// A dispute game found by [`find_dispute_game`].
+struct DisputeGame {
+    index: u64,
+    proxy: Address,
+    l2_block: u64,
+    root_claim: B256,
+}
+
+/// Find the newest dispute game of the portal's respected game type whose L2 block is
+/// at or after `l2_block`.
+async fn find_dispute_game(
+    client: &reqwest::Client,
+    l1_url: &str,
+    portal: &str,
+    dgf: &str,
+    l2_block: u64,
+) -> Result<Option<DisputeGame>> {
+    let respected = eth_call(client, l1_url, portal, abi::respectedGameTypeCall {}).await?;
+    let count = call_u64(client, l1_url, dgf, abi::gameCountCall {}).await?;
+
+    for index in (0..count).rev() {
+        let game = eth_call(
+            client,
+            l1_url,
+            dgf,
+            abi::gameAtIndexCall {
+                index: U256::from(index),
+            },
+        )
+        .await
+        .with_context(|| format!("Failed to read dispute game {index}"))?;
+        if game.gameType != respected {
+            continue;
+        }
+
+        let proxy = game.proxy;
+        let game_block = call_u64(
+            client,
+            l1_url,
+            &proxy.to_string(),
+            abi::l2BlockNumberCall {},
+        )
+        .await?;
+        // Games are proposed for increasing L2 blocks, older ones cannot cover it either.
+        if game_block < l2_block {
+            return Ok(None);
+        }
+
+        let root_claim =
+            eth_call(client, l1_url, &proxy.to_string(), abi::rootClaimCall {}).await?;
+        return Ok(Some(DisputeGame {
+            index,
+            proxy,
+            l2_block: game_block,
+            root_claim,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// The output root preimage of an L2 block and the storage proof of a withdrawal in it.
+struct OutputRootProof {
+    state_root: B256,
+    message_passer_storage_root: B256,
+    block_hash: B256,
+    /// Storage proof of `sentMessages[withdrawalHash]` in the message passer.
+    withdrawal_proof: Vec<Bytes>,
+}
+
+impl OutputRootProof {
+    /// The version 0 output root.
+    fn output_root(&self) -> B256 {
+        keccak256(self.to_abi().abi_encode())
+    }
+
+    /// The version 0 preimage as a `Types.OutputRootProof` struct argument.
+    fn to_abi(&self) -> abi::OutputRootProof {
+        abi::OutputRootProof {
+            version: B256::ZERO,
+            stateRoot: self.state_root,
+            messagePasserStorageRoot: self.message_passer_storage_root,
+            latestBlockhash: self.block_hash,
+        }
+    }
+}
+
+/// Build the output root proof of a withdrawal at `l2_block`.
+async fn output_root_proof(
+    client: &reqwest::Client,
+    l2_url: &str,
+    withdrawal_hash: B256,
+    l2_block: u64,
+) -> Result<OutputRootProof> {
+    let block_tag = format!("0x{l2_block:x}");
+    let block: Value = rpc::json_rpc_call(
+        client,
+        l2_url,
+        "eth_getBlockByNumber",
+        vec![serde_json::json!(block_tag), serde_json::json!(false)],
+    )
+    .await
+    .with_context(|| format!("Failed to get L2 block {l2_block}"))?;
+
+    let slot = sent_messages_slot(withdrawal_hash);
+    let proof: Value = rpc::json_rpc_call(
+        client,
+        l2_url,
+        "eth_getProof",
+        vec![
+            serde_json::json!(L2_TO_L1_MESSAGE_PASSER),
+            serde_json::json!([slot]),
+            serde_json::json!(block_tag),
+        ],
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to get the message passer storage proof at L2 block {l2_block} \
+             (op-reth only serves proofs of recent blocks unless proofs history is enabled)"
+        )
+    })?;
+
+    let storage_proof = &proof["storageProof"][0];
+    if storage_proof["value"]
+        .as_str()
+        .and_then(|v| U256::from_str(v).ok())
+        .is_none_or(|v| v.is_zero())
+    {
+        anyhow::bail!(
+            "Withdrawal {} is not in the message passer at L2 block {}",
+            withdrawal_hash,
+            l2_block
+        );
+    }
+
+    let b256_field = |value: &Value, key: &str| {
+        value[key]
+            .as_str()
+            .and_then(|v| B256::from_str(v).ok())
+            .with_context(|| format!("Missing {key} at L2 block {l2_block}"))
+    };
+
+    Ok(OutputRootProof {
+        state_root: b256_field(&block, "stateRoot")?,
+        message_passer_storage_root: b256_field(&proof, "storageHash")?,
+        block_hash: b256_field(&block, "hash")?,
+        withdrawal_proof: storage_proof["proof"]
+            .as_array()
+            .context("Storage proof has no proof nodes")?
+            .iter()
+            .map(|node| {
+                node.as_str()
+                    .and_then(|n| Bytes::from_str(n).ok())
+                    .context("Invalid storage proof node")
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+/// Storage slot of `sentMessages[withdrawal_hash]` in the message passer.
+fn sent_messages_slot(withdrawal_hash: B256) -> B256 {
+    keccak256((withdrawal_hash, U256::ZERO).abi_encode())
+}
+
+/// Look up a withdrawal by hash in the `MessagePassed` events of the message passer,
+/// newest blocks first. Returns the withdrawal and its L2 block.
+async fn find_withdrawal(
+    client: &reqwest::Client,
+    l2_url: &str,
+    withdrawal_hash: B256,
+) -> Result<(WithdrawalTransaction, u64)> {
+    let latest = rpc_quantity(client, l2_url, "eth_blockNumber", vec![]).await?;
+    let topic = abi::MessagePassed::SIGNATURE_HASH;
+
+    let mut to_block = latest;
+    loop {
+        let from_block = to_block.saturating_sub(LOG_SCAN_BLOCKS - 1);
+        let logs: Vec<Value> = rpc::json_rpc_call(
+            client,
+            l2_url,
+            "eth_getLogs",
+            vec![serde_json::json!({
+                "address": L2_TO_L1_MESSAGE_PASSER,
+                "topics": [topic],
+                "fromBlock": format!("0x{from_block:x}"),
+                "toBlock": format!("0x{to_block:x}"),
+            })],
+        )
+        .await
+        .context("Failed to get MessagePassed events")?;
+
+        for log in &logs {
+            let (withdrawal, hash, block) = parse_message_passed(log)?;
+            if hash == withdrawal_hash {
+                return Ok((withdrawal, block));
+            }
+        }
+
+        if from_block == 0 {
+            anyhow::bail!("Withdrawal {} not found on L2", withdrawal_hash);
+        }
+        to_block = from_block - 1;
+    }
+}
+
+/// Parse a `MessagePassed` log into the withdrawal, its hash and its L2 block.
+///
+/// Fails if the log is not a `MessagePassed` event of the message passer, or if the
+/// emitted hash does not match the withdrawal.
+fn parse_message_passed(log: &Value) -> Result<(WithdrawalTransaction, B256, u64)> {
+    let passer = Address::from_str(L2_TO_L1_MESSAGE_PASSER)?;
+    if log["address"]
+        .as_str()
+        .and_then(|a| Address::from_str(a).ok())
+        != Some(passer)
+    {
+        anyhow::bail!("Log is not from the L2ToL1MessagePasser");
+    }
+
+    let topics: Vec<B256> = log["topics"]
+        .as_array()
+        .context("Log has no topics")?
+        .iter()
+        .map(|t| t.as_str().and_then(|t| B256::from_str(t).ok()))
+        .collect::<Option<_>>()
+        .context("Invalid log topic")?;
+    let data = log["data"]
+        .as_str()
+        .and_then(|d| Bytes::from_str(d).ok())
+        .context("Log has no hex data")?;
+    let event = abi::MessagePassed::decode_raw_log(topics, &data)
+        .context("Log is not a MessagePassed event")?;
+
+    let withdrawal = WithdrawalTransaction {
+        nonce: event.nonce,
+        sender: event.sender,
+        target: event.target,
+        value: event.value,
+        gas_limit: event.gasLimit,
+        data: event.data,
+    };
+    let hash = event.withdrawalHash;
+    if withdrawal.hash() != hash {
+        anyhow::bail!(
+            "MessagePassed withdrawal hash {} does not match the withdrawal ({})",
+            hash,
+            withdrawal.hash()
+        );
+    }
+
+    let block = hex_field(log, "blockNumber").context("Log has no block number")?;
+    Ok((withdrawal, hash, block))
+}
+
+/// Proving and finalizing send transactions from an unlocked Anvil account.
+fn ensure_anvil_l1(deployer: &Deployer) -> Result<()> {
+    if deployer.external_l1.is_some() {
+        anyhow::bail!(
+            "Withdrawals are proven and finalized from an Anvil account and are not supported with an external L1"
+        );
+    }
+    Ok(())
+}
+
+/// Host RPC URL of Anvil.
+async fn l1_rpc_url(docker: &KupDocker, deployer: &Deployer) -> Result<String> {
+    build_host_rpc_url(docker, &deployer.anvil.container_name, deployer.anvil.port)
+        .await
+        .context("Failed to build L1 RPC URL - is Anvil running?")
+}
+
+/// Parse a 0x-prefixed withdrawal hash.
+fn parse_withdrawal_hash(hash: &str) -> Result<B256> {
+    B256::from_str(hash).with_context(|| {
+        format!("Invalid withdrawal hash: expected 0x-prefixed 64 hex chars, got '{hash}'")
+    })
+}
+
+/// Send `data` to `to` from an unlocked Anvil account and wait for it to succeed.
+async fn send_l1_transaction(
+    client: &reqwest::Client,
+    l1_url: &str,
+    from: &str,
+    to: &str,
+    data: Vec<u8>,
+    what: &str,
+) -> Result<String> {
+    rpc::send_unlocked_transaction_and_wait(
+        client,
+        l1_url,
+        serde_json::json!({ "from": from, "to": to, "data": Bytes::from(data) }),
+        what,
+    )
+    .await
+}
+
+/// Call a JSON-RPC method returning a hex quantity.
+async fn rpc_quantity(
+    client: &reqwest::Client,
+    url: &str,
+    method: &str,
+    params: Vec<Value>,
+) -> Result<u64> {
+    let value: String = rpc::json_rpc_call(client, url, method, params).await?;
+    u64::from_str_radix(value.trim_start_matches("0x"), 16)
+        .with_context(|| format!("Invalid {method} result: '{value}'"))
+}
+
+/// `eth_call` a contract at the latest block and decode the result.
+async fn eth_call<C: SolCall>(
+    client: &reqwest::Client,
+    url: &str,
+    to: &str,
+    call: C,
+) -> Result<C::Return> {
+    let result: String = rpc::json_rpc_call(
+        client,
+        url,
+        "eth_call",
+        vec![
+            serde_json::json!({ "to": to, "data": Bytes::from(call.abi_encode()) }),
+            serde_json::json!("latest"),
+        ],
+    )
+    .await
+    .with_context(|| format!("Failed to call {} on {to}", C::SIGNATURE))?;
+    let result = Bytes::from_str(&result)
+        .with_context(|| format!("Invalid {} result: '{result}'", C::SIGNATURE))?;
+    C::abi_decode_returns(&result)
+        .with_context(|| format!("Failed to decode {} result from {to}", C::SIGNATURE))
+}
+
+/// [`eth_call`] for view functions returning a `uint256` that fits in a `u64`
+/// (durations, counts, block numbers).
+async fn call_u64<C: SolCall<Return = U256>>(
+    client: &reqwest::Client,
+    url: &str,
+    to: &str,
+    call: C,
+) -> Result<u64> {
+    let value = eth_call(client, url, to, call).await?;
+    u64::try_from(value).with_context(|| format!("{} on {to} returned {value}", C::SIGNATURE))
+}
+
+/// Parse a hex-encoded secp256k1 private key (with or without `0x` prefix).
+fn parse_private_key(key: &str) -> Result<PrivateKeySigner> {
+    PrivateKeySigner::from_str(key).context("Private key is not a valid hex secp256k1 key")
+}
+
+/// Sign a legacy transaction with EIP-155 replay protection. Returns the raw transaction.
+fn sign_legacy_tx(signer: &PrivateKeySigner, mut tx: TxLegacy) -> Result<Vec<u8>> {
+    let signature = signer
+        .sign_transaction_sync(&mut tx)
+        .context("Failed to sign withdrawal transaction")?;
+    let mut raw = Vec::new();
+    tx.rlp_encode_signed(&signature, &mut raw);
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_consensus::transaction::RlpEcdsaDecodableTx;
+
+    use super::*;
+
+    // First account of Anvil's default mnemonic.
+    const KEY_0: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_signer_address() {
+        let signer = parse_private_key(KEY_0).unwrap();
+        assert_eq!(
+            signer.address(),
+            Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap()
+        );
+        assert!(parse_private_key("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_sign_legacy_tx_recovers_signer() {
+        let signer = parse_private_key(KEY_0).unwrap();
+        let tx = TxLegacy {
+            chain_id: Some(42069),
+            nonce: 3,
+            gas_price: 1_000_000_000,
+            gas_limit: 100_000,
+            to: TxKind::Call(Address::from_str(L2_TO_L1_MESSAGE_PASSER).unwrap()),
+            value: U256::from(10u64.pow(18)),
+            input: Bytes::from(vec![0xab; 70]),
+        };
+        let raw = sign_legacy_tx(&signer, tx.clone()).unwrap();
+
+        let signed = TxLegacy::rlp_decode_signed(&mut raw.as_slice()).unwrap();
+        assert_eq!(signed.tx(), &tx);
+        let recovered = signed
+            .signature()
+            .recover_address_from_prehash(&signed.signature_hash())
+            .unwrap();
+        assert_eq!(recovered, signer.address());
+    }
+
+    fn withdrawal() -> WithdrawalTransaction {
+        WithdrawalTransaction {
+            nonce: (U256::from(1) << 240) + U256::from(7),
+            sender: Address::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap(),
+            target: Address::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap(),
+            value: U256::from(10u64.pow(18)),
+            gas_limit: U256::from(WITHDRAWAL_GAS_LIMIT),
+            data: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn test_withdrawal_hash() {
+        // keccak256(abi.encode(nonce, sender, target, value, gasLimit, data)), as in
+        // `Hashing.hashWithdrawal`.
+        let withdrawal = WithdrawalTransaction {
+            data: Bytes::from(vec![0xab, 0xcd]),
+            ..withdrawal()
+        };
+        assert_eq!(
+            withdrawal.hash(),
+            B256::from_str("0x5db6819ebecae304a28a050d6a915967ac15652b9dd2b658106cef12be3e9894")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_message_passed() {
+        let withdrawal = withdrawal();
+        let hash = withdrawal.hash();
+        let data = [
+            format!("{:064x}", withdrawal.value),
+            format!("{:064x}", withdrawal.gas_limit),
+            format!("{:064x}", 0x80),
+            hex::encode(hash),
+            format!("{:064x}", 0),
+        ]
+        .concat();
+        let log = serde_json::json!({
+            "address": L2_TO_L1_MESSAGE_PASSER,
+            "blockNumber": "0x2a",
+            "topics": [
+                abi::MessagePassed::SIGNATURE_HASH,
+                B256::from(withdrawal.nonce),
+                withdrawal.sender.into_word(),
+                withdrawal.target.into_word(),
+            ],
+            "data": format!("0x{data}"),
+        });
+
+        let (parsed, parsed_hash, block) = parse_message_passed(&log).unwrap();
+        assert_eq!(parsed, withdrawal);
+        assert_eq!(parsed_hash, hash);
+        assert_eq!(block, 42);
+
+        // A different emitted hash is rejected.
+        let mut tampered = log.clone();
+        tampered["data"] = serde_json::json!(format!(
+            "0x{}",
+            data.replace(&hex::encode(hash), &"0".repeat(64))
+        ));
+        assert!(parse_message_passed(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_output_root() {
+        let proof = OutputRootProof {
+            state_root: B256::repeat_byte(1),
+            message_passer_storage_root: B256::repeat_byte(2),
+            block_hash: B256::repeat_byte(3),
+            withdrawal_proof: Vec::new(),
+        };
+        let preimage = [[0u8; 32], [1; 32], [2; 32], [3; 32]].concat();
+        assert_eq!(proof.output_root(), keccak256(preimage));
+        assert_ne!(
+            sent_messages_slot(B256::repeat_byte(1)),
+            sent_messages_slot(B256::repeat_byte(2))
+        );
+    }
+
+    #[test]
+    fn test_parse_withdrawal_hash() {
+        assert!(parse_withdrawal_hash(&format!("0x{}", "ab".repeat(32))).is_ok());
+        assert!(parse_withdrawal_hash("0x1234").is_err());
+    }
+}
@@ -88,7 +88,7 @@ impl TestContext {
         let mut docker = KupDocker::new(deployer.docker.clone()).await?;
         let deploy_result = timeout(
             Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-            deployer.deploy(&mut docker, false, false),
+            deployer.deploy_detached(&mut docker, false),
         )
         .await;
 
@@ -189,7 +189,7 @@ pub async fn wait_for_all_nodes(deployment: &DeploymentResult) {
     }
 }
 
-/// Wait for all L2 nodes to have block_number > 0 by polling the health check.
+/// Wait for the unsafe head of all L2 nodes to advance by polling the health check.
 /// Validators may take a while to sync from the sequencer, so this is more
 /// reliable than a fixed sleep.
 pub async fn wait_for_all_nodes_advancing(
@@ -197,12 +197,12 @@ pub async fn wait_for_all_nodes_advancing(
     timeout_secs: u64,
 ) -> Result<()> {
     let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+    let mut tracker = kupcake_deploy::head_tracker::HeadTracker::default();
     loop {
-        let report = health::health_check(deployer).await?;
-        let all_advancing = report
-            .nodes
-            .iter()
-            .all(|node| node.execution.block_number.unwrap_or(0) > 0);
+        let report = health::health_check_with_tracker(deployer, &mut tracker).await?;
+        let all_advancing = report.nodes.iter().all(|node| {
+            node.execution.block_number.unwrap_or(0) > 0 && node.advancing == Some(true)
+        });
         if all_advancing {
             return Ok(());
         }
@@ -190,3 +190,40 @@ async fn test_load_nonexistent_network_fails() -> Result<()> {
     tracing::info!("Non-existent network directory correctly rejected");
     Ok(())
 }
+
+/// Test that `Deployer::canonicalize` matches what a save/load cycle produces,
+/// is idempotent, and that the full config also survives a JSON round-trip.
+#[tokio::test]
+async fn test_canonicalize_matches_save_and_load() -> Result<()> {
+    init_test_tracing();
+    let ctx = TestContext::new("canonical");
+
+    let mut deployer = ctx.build_deployer().await?;
+    deployer.snapshot = Some(PathBuf::from("/tmp/snapshot"));
+    deployer.monitoring.prometheus.host_port = None;
+
+    let canonical = deployer.canonicalize()?;
+    assert_eq!(canonical.snapshot, None, "Runtime-only fields are dropped");
+    assert_eq!(canonical.monitoring.prometheus.host_port, Some(0));
+    assert_eq!(
+        canonical.canonicalize()?,
+        canonical,
+        "canonicalize is idempotent"
+    );
+
+    let config_path = deployer.save_config()?;
+    let loaded = Deployer::load_from_file(&config_path)?;
+    assert_eq!(
+        loaded, canonical,
+        "canonicalize must match a save/load cycle"
+    );
+
+    let json = serde_json::to_string(&canonical)?;
+    let from_json: Deployer = serde_json::from_str(&json)?;
+    assert_eq!(
+        from_json, canonical,
+        "Deployer config must round-trip through JSON"
+    );
+
+    Ok(())
+}
@@ -17,8 +17,8 @@ use anyhow::{Context, Result};
 use kupcake_deploy::{
     CreateAndStartContainerOptions, DeployerBuilder, DeploymentTarget, DockerImage,
     KONA_NODE_DEFAULT_IMAGE, KONA_NODE_DEFAULT_TAG, KupDocker, OP_RETH_DEFAULT_IMAGE,
-    OP_RETH_DEFAULT_TAG, OutDataPath, ServiceConfig, cleanup_by_prefix, faucet, health, rpc,
-    services::SyncStatus,
+    OP_RETH_DEFAULT_TAG, OutDataPath, ServiceConfig, cleanup_by_prefix, faucet, faucet::EthAmount,
+    health, rpc, services::SyncStatus,
 };
 use serde_json::Value;
 use tokio::time::{sleep, timeout};
@@ -64,63 +64,12 @@ fn get_container_host_port(container_name: &str, container_port: u16) -> Result<
     })
 }
 
-/// Status of op-batcher.
-#[derive(Debug, Clone)]
-struct OpBatcherStatus {
-    /// Whether the batcher is healthy (RPC responding).
-    is_healthy: bool,
-    /// Error message if not healthy.
-    error: Option<String>,
-}
-
-/// Check op-batcher health by calling its RPC endpoint.
-/// op-batcher exposes admin_nodeInfo which can be used as a health check.
-async fn get_op_batcher_status(rpc_url: &str) -> Result<OpBatcherStatus> {
-    let client = rpc::create_client()?;
-
-    // Try opp_version as a health check (standard op-service method)
-    let response = client
-        .post(rpc_url)
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "opp_version",
-            "params": [],
-            "id": 1
-        }))
-        .send()
-        .await;
-
-    match response {
-        Ok(resp) => {
-            let body: Value = resp
-                .json()
-                .await
-                .context("Failed to parse opp_version response")?;
-            if body.get("error").is_some() {
-                // Even if there's an error, the service is responding
-                Ok(OpBatcherStatus {
-                    is_healthy: true,
-                    error: body["error"]["message"].as_str().map(|s| s.to_string()),
-                })
-            } else {
-                Ok(OpBatcherStatus {
-                    is_healthy: true,
-                    error: None,
-                })
-            }
-        }
-        Err(e) => Ok(OpBatcherStatus {
-            is_healthy: false,
-            error: Some(e.to_string()),
-        }),
-    }
-}
-
 /// Wait for op-batcher to be ready by polling its RPC endpoint.
 async fn wait_for_op_batcher_ready(rpc_url: &str, timeout_secs: u64) -> Result<()> {
+    let client = rpc::create_client()?;
     rpc::wait_until_ready("op-batcher", timeout_secs, || async {
-        let status = get_op_batcher_status(rpc_url).await?;
-        if status.is_healthy {
+        let status = rpc::probe_op_service(&client, rpc_url).await;
+        if status.responding {
             Ok(())
         } else {
             anyhow::bail!("op-batcher not healthy yet")
@@ -171,7 +120,7 @@ async fn test_network_deployment_and_sync_status() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deploy_result = timeout(
         Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -303,7 +252,7 @@ async fn test_op_reth_sync_and_block_advancement() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deploy_result = timeout(
         Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -510,7 +459,7 @@ async fn test_multi_sequencer_with_conductor() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deploy_result = timeout(
         Duration::from_secs(CONDUCTOR_DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.clone().deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -628,13 +577,13 @@ async fn test_multi_sequencer_with_conductor() -> Result<()> {
         anyhow::bail!("No sequencer nodes available for testing");
     }
 
-    // Note: With conductor-controlled sequencers, block production is managed by the conductor
-    // and requires the conductor to elect a leader and enable sequencing. This takes longer
-    // to stabilize. For this test, we focus on verifying the infrastructure is deployed correctly:
-    // - Conductor containers running
-    // - Conductor RPCs responding
-    // - Sequencer nodes ready (RPCs responding with sync status)
-    // Block production testing with conductor coordination is a more advanced scenario.
+    // Deploy started sequencing on the elected conductor leader, so every node
+    // (both sequencers and the validator) should see the chain advance.
+    tracing::info!("=== Waiting for blocks to be produced... ===");
+    if let Err(e) = wait_for_all_nodes_advancing(&deployer, NODE_READY_TIMEOUT_SECS).await {
+        let _ = cleanup_by_prefix(&network_name).await;
+        return Err(e).context("Blocks are not produced with conductor-managed sequencers");
+    }
 
     // Cleanup
     tracing::info!("=== Cleaning up network... ===");
@@ -725,7 +674,7 @@ async fn test_op_batcher_health() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deploy_result = timeout(
         Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -759,9 +708,9 @@ async fn test_op_batcher_health() -> Result<()> {
 
     // Check op-batcher health
     tracing::info!("=== Checking op-batcher health... ===");
-    let status = get_op_batcher_status(&batcher_url).await?;
+    let status = rpc::probe_op_service(&rpc::create_client()?, &batcher_url).await;
 
-    if !status.is_healthy {
+    if !status.responding {
         let _ = cleanup_by_prefix(&network_name).await;
         anyhow::bail!(
             "op-batcher is not healthy: {}",
@@ -874,7 +823,7 @@ async fn test_publish_all_ports() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deploy_result = timeout(
         Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -1126,7 +1075,7 @@ async fn test_local_kona_binary() -> Result<()> {
     tracing::info!("=== Deploying network with local kona-node binary... ===");
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deployment = deployer
-        .deploy(&mut docker, false, false)
+        .deploy_detached(&mut docker, false)
         .await
         .context("Failed to deploy network")?;
 
@@ -1171,10 +1120,8 @@ async fn test_local_kona_binary() -> Result<()> {
     tracing::info!("=== Verifying kona nodes are advancing... ===");
 
     // Get RPC URLs directly from the deployment result
-    let sequencer_rpc_url = deployment.l2_stack.sequencers[0]
-        .kona_node
-        .rpc_host_url
-        .as_ref()
+    let sequencer_rpc_url = deployment
+        .rollup_rpc_url()
         .context("Sequencer kona-node RPC URL not available")?;
 
     let validator_rpc_url = deployment.l2_stack.validators[0]
@@ -1865,7 +1812,14 @@ async fn test_faucet_deposit_with_wait() -> Result<()> {
 
     // Send 1 ETH via faucet with wait=true
     tracing::info!("=== Sending 1 ETH via faucet (wait=true)... ===");
-    let result = faucet::faucet_deposit(&docker, &loaded_deployer, test_address, 1.0, true).await?;
+    let result = faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        test_address,
+        EthAmount::from_ether(1),
+        true,
+    )
+    .await?;
 
     // Verify tx hash is returned
     assert!(
@@ -1883,6 +1837,13 @@ async fn test_faucet_deposit_with_wait() -> Result<()> {
     let l2_balance_hex = result.l2_balance.as_ref().unwrap();
     tracing::info!("L2 balance after deposit: {}", l2_balance_hex);
 
+    // Verify the L2 deposit tx hash was found
+    let l2_tx_hash = result
+        .l2_tx_hash
+        .as_ref()
+        .expect("L2 deposit tx hash should be returned when wait=true");
+    tracing::info!("L2 deposit tx hash: {}", l2_tx_hash);
+
     // Verify balance is now ~1 ETH (1e18 wei)
     let final_balance = get_l2_balance(&l2_rpc_url, test_address).await?;
     tracing::info!("Final L2 balance: {} wei", final_balance);
@@ -1942,8 +1903,14 @@ async fn test_faucet_deposit_no_wait() -> Result<()> {
 
     // Send 2 ETH via faucet with wait=false
     tracing::info!("=== Sending 2 ETH via faucet (wait=false)... ===");
-    let result =
-        faucet::faucet_deposit(&docker, &loaded_deployer, test_address, 2.0, false).await?;
+    let result = faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        test_address,
+        EthAmount::from_ether(2),
+        false,
+    )
+    .await?;
 
     // Verify tx hash is returned
     assert!(
@@ -2047,8 +2014,14 @@ async fn test_faucet_multiple_deposits() -> Result<()> {
 
     // First deposit: 1 ETH
     tracing::info!("=== Sending first deposit: 1 ETH... ===");
-    let result1 =
-        faucet::faucet_deposit(&docker, &loaded_deployer, test_address, 1.0, true).await?;
+    let result1 = faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        test_address,
+        EthAmount::from_ether(1),
+        true,
+    )
+    .await?;
     tracing::info!("First deposit L1 tx: {}", result1.l1_tx_hash);
 
     let balance_after_first = get_l2_balance(&l2_rpc_url, test_address).await?;
@@ -2061,8 +2034,14 @@ async fn test_faucet_multiple_deposits() -> Result<()> {
 
     // Second deposit: 0.5 ETH
     tracing::info!("=== Sending second deposit: 0.5 ETH... ===");
-    let result2 =
-        faucet::faucet_deposit(&docker, &loaded_deployer, test_address, 0.5, true).await?;
+    let result2 = faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        test_address,
+        "0.5".parse()?,
+        true,
+    )
+    .await?;
     tracing::info!("Second deposit L1 tx: {}", result2.l1_tx_hash);
 
     // Verify tx hashes are different
@@ -2107,7 +2086,14 @@ async fn test_faucet_rejects_invalid_address() -> Result<()> {
     let docker = KupDocker::new(loaded_deployer.docker.clone()).await?;
 
     // Test with too-short address
-    let result = faucet::faucet_deposit(&docker, &loaded_deployer, "0x1234", 1.0, false).await;
+    let result = faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        "0x1234",
+        EthAmount::from_ether(1),
+        false,
+    )
+    .await;
     assert!(result.is_err(), "Should reject short address");
     let err_msg = result.unwrap_err().to_string();
     assert!(
@@ -2121,7 +2107,7 @@ async fn test_faucet_rejects_invalid_address() -> Result<()> {
         &docker,
         &loaded_deployer,
         "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef00",
-        1.0,
+        EthAmount::from_ether(1),
         false,
     )
     .await;
@@ -2132,7 +2118,7 @@ async fn test_faucet_rejects_invalid_address() -> Result<()> {
         &docker,
         &loaded_deployer,
         "0xZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ",
-        1.0,
+        EthAmount::from_ether(1),
         false,
     )
     .await;
@@ -2160,6 +2146,7 @@ async fn test_spam_rejects_empty_rpc_url() -> Result<()> {
 
     let spam_config = kupcake_deploy::spam::SpamConfig {
         scenario: "transfers".to_string(),
+        scenario_file: None,
         tps: 10,
         duration: 5,
         forever: false,
@@ -2175,7 +2162,8 @@ async fn test_spam_rejects_empty_rpc_url() -> Result<()> {
         quiet: false,
     };
 
-    let result = kupcake_deploy::spam::run_spam(&mut docker, &loaded_deployer, &spam_config).await;
+    let result =
+        kupcake_deploy::spam::run_spam(&mut docker, &loaded_deployer, &spam_config, None).await;
     assert!(result.is_err(), "Should reject empty rpc_url");
     let err_msg = result.expect_err("checked above").to_string();
     assert!(
@@ -2210,6 +2198,7 @@ async fn test_spam_transfers() -> Result<()> {
 
     let spam_config = kupcake_deploy::spam::SpamConfig {
         scenario: "transfers".to_string(),
+        scenario_file: None,
         tps: 10,
         duration: 5,
         forever: false,
@@ -2228,7 +2217,7 @@ async fn test_spam_transfers() -> Result<()> {
     // Run spam with a timeout
     let spam_result = timeout(
         Duration::from_secs(300),
-        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config),
+        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config, None),
     )
     .await;
 
@@ -2333,30 +2322,44 @@ async fn test_faucet_deposit_various_amounts() -> Result<()> {
     tracing::info!("=== Test 1: Depositing 0.001 ETH... ===");
     let initial1 = get_l2_balance(&l2_rpc_url, addr1).await?;
     assert_eq!(initial1, 0, "Test address 1 should start with zero balance");
-    faucet::faucet_deposit(&docker, &loaded_deployer, addr1, 0.001, true).await?;
+    faucet::faucet_deposit(&docker, &loaded_deployer, addr1, "0.001".parse()?, true).await?;
     let balance1 = get_l2_balance(&l2_rpc_url, addr1).await?;
     let expected1: u128 = 1_000_000_000_000_000; // 0.001 ETH
     tracing::info!("Balance: {} wei (expected: {} wei)", balance1, expected1);
     assert_eq!(balance1, expected1, "0.001 ETH deposit should be exact");
 
-    // Test 2: Fractional amount (0.123456789 ETH) — test gwei precision boundary
+    // Test 2: Fractional amount with 18 decimals — exact to the wei
     let addr2 = "0x000000000000000000000000000000000000Aa02";
-    tracing::info!("=== Test 2: Depositing 0.123456789 ETH... ===");
+    tracing::info!("=== Test 2: Depositing 0.123456789012345678 ETH... ===");
     let initial2 = get_l2_balance(&l2_rpc_url, addr2).await?;
     assert_eq!(initial2, 0, "Test address 2 should start with zero balance");
-    faucet::faucet_deposit(&docker, &loaded_deployer, addr2, 0.123456789, true).await?;
+    faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        addr2,
+        "0.123456789012345678".parse()?,
+        true,
+    )
+    .await?;
     let balance2 = get_l2_balance(&l2_rpc_url, addr2).await?;
-    let expected2: u128 = 123_456_789_000_000_000; // 0.123456789 ETH (gwei precision)
+    let expected2: u128 = 123_456_789_012_345_678; // 0.123456789012345678 ETH
     tracing::info!("Balance: {} wei (expected: {} wei)", balance2, expected2);
     assert_eq!(
         balance2, expected2,
-        "0.123456789 ETH deposit should match gwei precision"
+        "0.123456789012345678 ETH deposit should be exact to the wei"
     );
 
     // Test 3: Large amount (10 ETH)
     let addr3 = "0x000000000000000000000000000000000000Aa03";
     tracing::info!("=== Test 3: Depositing 10 ETH... ===");
-    faucet::faucet_deposit(&docker, &loaded_deployer, addr3, 10.0, true).await?;
+    faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        addr3,
+        EthAmount::from_ether(10),
+        true,
+    )
+    .await?;
     let balance3 = get_l2_balance(&l2_rpc_url, addr3).await?;
     let expected3: u128 = 10_000_000_000_000_000_000; // 10 ETH
     tracing::info!("Balance: {} wei (expected: {} wei)", balance3, expected3);
@@ -2438,7 +2441,14 @@ async fn test_faucet_deposit_visible_on_validator() -> Result<()> {
 
     // Deposit 1 ETH
     tracing::info!("=== Depositing 1 ETH via faucet... ===");
-    faucet::faucet_deposit(&docker, &loaded_deployer, test_address, 1.0, true).await?;
+    faucet::faucet_deposit(
+        &docker,
+        &loaded_deployer,
+        test_address,
+        EthAmount::from_ether(1),
+        true,
+    )
+    .await?;
 
     // Verify on sequencer
     let seq_balance = get_l2_balance(&seq_rpc_url, test_address).await?;
@@ -2559,6 +2569,7 @@ async fn test_spam_generates_l2_traffic() -> Result<()> {
     // Run spam with low TPS and short duration to avoid rate limiting
     let spam_config = kupcake_deploy::spam::SpamConfig {
         scenario: "transfers".to_string(),
+        scenario_file: None,
         tps: 2,
         duration: 10,
         forever: false,
@@ -2577,7 +2588,7 @@ async fn test_spam_generates_l2_traffic() -> Result<()> {
     tracing::info!("=== Running spam (tps=2, duration=10s, accounts=2)... ===");
     let spam_result = timeout(
         Duration::from_secs(300),
-        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config),
+        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config, None),
     )
     .await;
 
@@ -2718,6 +2729,7 @@ async fn test_spam_container_on_correct_network() -> Result<()> {
     // Run spam with very short duration — we just need the container to start
     let spam_config = kupcake_deploy::spam::SpamConfig {
         scenario: "transfers".to_string(),
+        scenario_file: None,
         tps: 1,
         duration: 5,
         forever: false,
@@ -2734,7 +2746,8 @@ async fn test_spam_container_on_correct_network() -> Result<()> {
     };
     let spam_handle = tokio::spawn(async move {
         let mut spam_docker = KupDocker::new(loaded_deployer_clone.docker.clone()).await?;
-        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer_clone, &spam_config).await
+        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer_clone, &spam_config, None)
+            .await
     });
 
     // Wait for the contender container to appear (faucet deposit + image pull + start)
@@ -2887,7 +2900,7 @@ async fn run_preset_and_verify_traffic(
 
     let spam_result = timeout(
         Duration::from_secs(300),
-        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config),
+        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config, None),
     )
     .await;
 
@@ -3070,7 +3083,7 @@ async fn test_spam_deploy_no_wait_then_reload_and_spam() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deployment_result = timeout(
         Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -3116,7 +3129,7 @@ async fn test_spam_deploy_no_wait_then_reload_and_spam() -> Result<()> {
     tracing::info!("=== Running spam from reloaded deployer... ===");
     let spam_result = timeout(
         Duration::from_secs(300),
-        kupcake_deploy::spam::run_spam(&mut spam_docker, &reloaded, &spam_config),
+        kupcake_deploy::spam::run_spam(&mut spam_docker, &reloaded, &spam_config, None),
     )
     .await;
 
@@ -3538,6 +3551,7 @@ async fn test_flashblocks_with_spam() -> Result<()> {
     // Run spam with moderate TPS and short duration
     let spam_config = kupcake_deploy::spam::SpamConfig {
         scenario: "transfers".to_string(),
+        scenario_file: None,
         tps: 20,
         duration: 10,
         forever: false,
@@ -3556,7 +3570,7 @@ async fn test_flashblocks_with_spam() -> Result<()> {
     tracing::info!("=== Running spam against flashblocks sequencer (tps=20, duration=10s)... ===");
     let spam_result = timeout(
         Duration::from_secs(300),
-        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config),
+        kupcake_deploy::spam::run_spam(&mut spam_docker, &loaded_deployer, &spam_config, None),
     )
     .await;
 
@@ -3973,7 +3987,7 @@ async fn test_genesis_deployment_mode() -> Result<()> {
     let mut docker = KupDocker::new(deployer.docker.clone()).await?;
     let deploy_result = timeout(
         Duration::from_secs(DEPLOYMENT_TIMEOUT_SECS),
-        deployer.deploy(&mut docker, false, false),
+        deployer.deploy_detached(&mut docker, false),
     )
     .await;
 
@@ -4839,10 +4853,8 @@ async fn test_secondary_docker_drop_preserves_devnet() -> Result<()> {
     wait_for_all_nodes(&deployment).await;
 
     // Get L2 RPC URL and verify blocks are advancing
-    let l2_rpc_url = deployment.l2_stack.sequencers[0]
-        .op_reth
-        .http_host_url
-        .as_ref()
+    let l2_rpc_url = deployment
+        .l2_rpc_url()
         .context("Sequencer HTTP host URL not available")?
         .to_string();
     let block_before = get_block_number(&l2_rpc_url).await?;
@@ -4912,3 +4924,147 @@ async fn test_secondary_docker_drop_preserves_devnet() -> Result<()> {
     tracing::info!("=== Test passed! Secondary docker drop preserved devnet. ===");
     Ok(())
 }
+
+/// Test that the op-reth datadir is owned by the invoking user after a deployment, so
+/// that it can be removed without root.
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_datadir_owned_by_host_user() -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let _permit = TEST_SEMAPHORE.acquire().await.context("test semaphore")?;
+    init_test_tracing();
+
+    let ctx = TestContext::new("host-user");
+    let deployer = ctx.build_deployer().await?;
+    let datadir = ctx
+        .outdata_path
+        .join("l2-stack")
+        .join(deployer.l2_stack.sequencers[0].op_reth.datadir_name());
+
+    let (_docker, deployment) = ctx.deploy(deployer).await?;
+    wait_for_all_nodes(&deployment).await;
+
+    let owner = std::fs::metadata(&ctx.outdata_path)?.uid();
+    let mut pending = vec![datadir];
+    while let Some(path) = pending.pop() {
+        let metadata = std::fs::symlink_metadata(&path)?;
+        if metadata.uid() != owner {
+            anyhow::bail!(
+                "{} is owned by uid {} instead of {}",
+                path.display(),
+                metadata.uid(),
+                owner
+            );
+        }
+        if metadata.is_dir() {
+            for entry in std::fs::read_dir(&path)? {
+                pending.push(entry?.path());
+            }
+        }
+    }
+    tracing::info!("✓ op-reth datadir is owned by uid {}", owner);
+
+    Ok(())
+}
+
+/// Test a deployment against an Anvil started outside of kupcake (external L1): no
+/// Anvil container is started and the L2 derives from the external node.
+///
+/// The external Anvil publishes its RPC on the host and is reached through the Docker
+/// bridge gateway, from the host and from the containers alike.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_external_l1_deployment() -> Result<()> {
+    let _permit = TEST_SEMAPHORE.acquire().await.context("test semaphore")?;
+    init_test_tracing();
+
+    let ctx = TestContext::new("external-l1");
+    // Prefixed with the network name, so that cleanup_by_prefix removes it as well.
+    let anvil_name = format!("{}-external-anvil", ctx.network_name);
+
+    let output = Command::new("docker")
+        .args([
+            "network",
+            "inspect",
+            "bridge",
+            "--format",
+            "{{(index .IPAM.Config 0).Gateway}}",
+        ])
+        .output()
+        .context("Failed to inspect the bridge network")?;
+    let gateway = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let status = Command::new("docker")
+        .args([
+            "run",
+            "-d",
+            "--name",
+            &anvil_name,
+            "-p",
+            "8545",
+            "--entrypoint",
+            "anvil",
+            &format!(
+                "{}:{}",
+                kupcake_deploy::ANVIL_DEFAULT_IMAGE,
+                kupcake_deploy::ANVIL_DEFAULT_TAG
+            ),
+            "--host",
+            "0.0.0.0",
+            "--chain-id",
+            &ctx.l1_chain_id.to_string(),
+            "--block-time",
+            "2",
+        ])
+        .status()
+        .context("Failed to start the external Anvil")?;
+    assert!(status.success(), "docker run of the external Anvil failed");
+
+    let rpc_url = format!(
+        "http://{}:{}",
+        gateway,
+        get_container_host_port(&anvil_name, 8545)?
+    );
+    tracing::info!(rpc_url, "External Anvil started");
+
+    // Anvil default accounts 0 and 1.
+    let external_l1 = kupcake_deploy::ExternalL1Config::new(
+        &rpc_url,
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    )
+    .batcher_private_key("0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d");
+
+    let deployer = DeployerBuilder::new(ctx.l1_chain_id)
+        .network_name(&ctx.network_name)
+        .outdata(OutDataPath::Path(ctx.outdata_path.clone()))
+        .l2_node_count(2)
+        .sequencer_count(1)
+        .block_time(2)
+        .detach(true)
+        .dump_state(false)
+        .no_proposer(true)
+        .no_challenger(true)
+        .monitoring_enabled(false)
+        .external_l1_config(external_l1)
+        .build()
+        .await?;
+    let anvil_container = deployer.anvil.container_name.clone();
+
+    let result = async {
+        let (_docker, deployment) = ctx.deploy(deployer).await?;
+        wait_for_all_nodes(&deployment).await;
+
+        let anvil_check = Command::new("docker")
+            .args(["inspect", &anvil_container])
+            .output()?;
+        assert!(
+            !anvil_check.status.success(),
+            "kupcake should not start Anvil with an external L1"
+        );
+        anyhow::Ok(())
+    }
+    .await;
+
+    cleanup_by_prefix(&ctx.network_name).await?;
+    result
+}
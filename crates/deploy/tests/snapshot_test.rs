@@ -255,7 +255,7 @@ async fn test_snapshot_genesis_mode() -> Result<()> {
     restored_deployer.save_config()?;
     let mut docker2 = KupDocker::new(restored_deployer.docker.clone()).await?;
     let deployment2 = restored_deployer
-        .deploy(&mut docker2, false, false)
+        .deploy_detached(&mut docker2, false)
         .await
         .context("Failed to deploy from snapshot")?;
 
@@ -394,7 +394,7 @@ async fn test_snapshot_live_mode() -> Result<()> {
     restored_deployer.save_config()?;
     let mut docker2 = KupDocker::new(restored_deployer.docker.clone()).await?;
     let deployment2 = restored_deployer
-        .deploy(&mut docker2, false, false)
+        .deploy_detached(&mut docker2, false)
         .await
         .context("Failed to deploy from snapshot")?;
 